@@ -6,6 +6,9 @@ use std::sync::Arc;
 
 use mux::llm::{AnthropicClient, GeminiClient, LlmClient, OpenAIClient};
 
+use crate::llm_debug::wrap_if_enabled;
+use crate::llm_retry::{RetryPolicy, RetryingClient};
+
 /// Read an env var and return `Some(value)` only if it is non-empty after trimming.
 /// Prevents empty or whitespace-only values from producing invalid URLs or model names.
 fn non_empty_env(key: &str) -> Option<String> {
@@ -19,15 +22,84 @@ fn non_empty_env(key: &str) -> Option<String> {
     })
 }
 
+/// Where a local Ollama server listens when `OLLAMA_BASE_URL` is unset.
+pub const DEFAULT_OLLAMA_BASE_URL: &str = "http://localhost:11434/v1";
+
+/// The Ollama server to talk to: `OLLAMA_BASE_URL`, or the local default.
+pub fn ollama_base_url() -> String {
+    non_empty_env("OLLAMA_BASE_URL").unwrap_or_else(|| DEFAULT_OLLAMA_BASE_URL.to_string())
+}
+
+/// The OpenAI-compatible endpoint under `base_url`. Ollama serves it at
+/// /v1, so a bare server address gets that suffix added.
+pub fn ollama_api_url(base_url: &str) -> String {
+    let trimmed = base_url.trim_end_matches('/');
+    if trimmed.ends_with("/v1") {
+        trimmed.to_string()
+    } else {
+        format!("{}/v1", trimmed)
+    }
+}
+
+/// Model-name prefixes each provider is known to serve. Only used to warn
+/// about likely mix-ups; model names change too often to reject unknown ones.
+const KNOWN_MODEL_PREFIXES: &[(&str, &[&str])] = &[
+    ("anthropic", &["claude-"]),
+    ("openai", &["gpt-", "chatgpt-", "o1", "o3", "o4"]),
+    ("gemini", &["gemini-", "gemma-"]),
+];
+
+/// A warning when `model` looks like it belongs to a provider other than
+/// `provider` (e.g. `gpt-4o` with `anthropic`). Models no provider claims
+/// pass silently, since proxies behind a custom base URL may use any name.
+pub fn model_mismatch_warning(provider: &str, model: &str) -> Option<String> {
+    let model_lower = model.trim().to_ascii_lowercase();
+    let owner = KNOWN_MODEL_PREFIXES.iter().find_map(|(name, prefixes)| {
+        prefixes
+            .iter()
+            .any(|p| model_lower.starts_with(p))
+            .then_some(*name)
+    })?;
+    let provider_known = KNOWN_MODEL_PREFIXES
+        .iter()
+        .any(|(name, _)| *name == provider);
+    (provider_known && owner != provider).then(|| {
+        format!(
+            "model '{}' looks like a {} model, but the provider is {}",
+            model, owner, provider
+        )
+    })
+}
+
 /// Create an LLM client for the given provider name.
 ///
 /// Returns a tuple of (client, resolved_model). The model is resolved from:
 /// 1. The explicit `model` parameter (if Some)
 /// 2. A provider-specific environment variable (e.g. ANTHROPIC_MODEL)
 /// 3. A sensible default for that provider
+///
+/// A model that looks like it belongs to another provider is logged as a
+/// warning (see `model_mismatch_warning`) but still used.
+///
+/// Anthropic calls that hit a rate limit or server error are retried with
+/// backoff (see `llm_retry`).
+///
+/// With `BARNSTORMER_DEBUG_LLM` on, the client is wrapped to log requests and
+/// responses (redacted); see `llm_debug`.
 pub fn create_llm_client(
     provider: &str,
     model: Option<&str>,
+) -> Result<(Arc<dyn LlmClient>, String), anyhow::Error> {
+    let (client, resolved_model) = create_provider_client(provider, model)?;
+    if let Some(warning) = model_mismatch_warning(provider, &resolved_model) {
+        tracing::warn!("{}", warning);
+    }
+    Ok((client, resolved_model))
+}
+
+fn create_provider_client(
+    provider: &str,
+    model: Option<&str>,
 ) -> Result<(Arc<dyn LlmClient>, String), anyhow::Error> {
     match provider {
         "anthropic" => {
@@ -37,11 +109,14 @@ pub fn create_llm_client(
                 .map(String::from)
                 .or_else(|| non_empty_env("ANTHROPIC_MODEL"))
                 .unwrap_or_else(|| "claude-sonnet-4-5-20250929".to_string());
-            let mut client = AnthropicClient::new(api_key);
+            let mut client = AnthropicClient::new(api_key.clone());
             if let Some(base_url) = non_empty_env("ANTHROPIC_BASE_URL") {
                 client = client.with_base_url(base_url);
             }
-            Ok((Arc::new(client), resolved_model))
+            // Anthropic throttles bursts and briefly overloads; a short wait
+            // usually succeeds where surfacing the error would stall the agent.
+            let client = RetryingClient::new(Arc::new(client), RetryPolicy::from_env());
+            Ok((wrap_if_enabled(Arc::new(client), &api_key), resolved_model))
         }
         "openai" => {
             let api_key = env::var("OPENAI_API_KEY")
@@ -50,11 +125,11 @@ pub fn create_llm_client(
                 .map(String::from)
                 .or_else(|| non_empty_env("OPENAI_MODEL"))
                 .unwrap_or_else(|| "gpt-4o".to_string());
-            let mut client = OpenAIClient::new(api_key);
+            let mut client = OpenAIClient::new(api_key.clone());
             if let Some(base_url) = non_empty_env("OPENAI_BASE_URL") {
                 client = client.with_base_url(base_url);
             }
-            Ok((Arc::new(client), resolved_model))
+            Ok((wrap_if_enabled(Arc::new(client), &api_key), resolved_model))
         }
         "gemini" => {
             let api_key = env::var("GEMINI_API_KEY")
@@ -63,11 +138,24 @@ pub fn create_llm_client(
                 .map(String::from)
                 .or_else(|| non_empty_env("GEMINI_MODEL"))
                 .unwrap_or_else(|| "gemini-2.0-flash".to_string());
-            let mut client = GeminiClient::new(api_key);
+            let mut client = GeminiClient::new(api_key.clone());
             if let Some(base_url) = non_empty_env("GEMINI_BASE_URL") {
                 client = client.with_base_url(base_url);
             }
-            Ok((Arc::new(client), resolved_model))
+            Ok((wrap_if_enabled(Arc::new(client), &api_key), resolved_model))
+        }
+        "ollama" => {
+            // Ollama (and other local OpenAI-compatible servers) speak the
+            // OpenAI API, tool calls included. No key is needed, but the
+            // client sends one.
+            let api_key = "ollama".to_string();
+            let resolved_model = model
+                .map(String::from)
+                .or_else(|| non_empty_env("OLLAMA_MODEL"))
+                .unwrap_or_else(|| "llama3.1".to_string());
+            let base_url = ollama_api_url(&ollama_base_url());
+            let client = OpenAIClient::new(api_key.clone()).with_base_url(base_url);
+            Ok((wrap_if_enabled(Arc::new(client), &api_key), resolved_model))
         }
         unknown => Err(anyhow::anyhow!("unsupported LLM provider: {}", unknown)),
     }
@@ -76,10 +164,7 @@ pub fn create_llm_client(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Mutex;
-
-    /// Serialize all tests that read/write env vars to prevent race conditions.
-    static ENV_MUTEX: Mutex<()> = Mutex::new(());
+    use crate::testing::ENV_MUTEX;
 
     /// All env var names that tests may read or mutate.
     const ENV_VARS: &[&str] = &[
@@ -92,6 +177,8 @@ mod tests {
         "GEMINI_API_KEY",
         "GEMINI_MODEL",
         "GEMINI_BASE_URL",
+        "OLLAMA_MODEL",
+        "OLLAMA_BASE_URL",
     ];
 
     /// Save the current values of all env vars we touch, returning a snapshot.
@@ -170,6 +257,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ollama_needs_no_api_key() {
+        let _guard = ENV_MUTEX.lock().unwrap();
+        let saved = save_env();
+        unsafe {
+            env::remove_var("OLLAMA_MODEL");
+            env::remove_var("OLLAMA_BASE_URL");
+        }
+        let base_url = ollama_base_url();
+        let default = create_llm_client("ollama", None).map(|(_, model)| model);
+        unsafe { env::set_var("OLLAMA_MODEL", "qwen2.5:14b") };
+        let from_env = create_llm_client("ollama", None).map(|(_, model)| model);
+        restore_env(&saved);
+
+        assert_eq!(default.unwrap(), "llama3.1");
+        assert_eq!(from_env.unwrap(), "qwen2.5:14b");
+        assert_eq!(base_url, DEFAULT_OLLAMA_BASE_URL);
+    }
+
+    #[test]
+    fn ollama_api_url_adds_v1_only_once() {
+        assert_eq!(
+            ollama_api_url(DEFAULT_OLLAMA_BASE_URL),
+            "http://localhost:11434/v1"
+        );
+        assert_eq!(
+            ollama_api_url("http://gpu-box:11434/"),
+            "http://gpu-box:11434/v1"
+        );
+        assert_eq!(
+            ollama_api_url("http://127.0.0.1:8080/v1/"),
+            "http://127.0.0.1:8080/v1"
+        );
+    }
+
     #[test]
     fn explicit_model_param_overrides_default() {
         let _guard = ENV_MUTEX.lock().unwrap();
@@ -208,4 +330,20 @@ mod tests {
             resolved_model
         );
     }
+
+    #[test]
+    fn mismatched_model_and_provider_warns() {
+        let warning = model_mismatch_warning("anthropic", "gpt-4o").unwrap();
+        assert!(warning.contains("openai"), "{}", warning);
+        assert!(model_mismatch_warning("openai", "claude-sonnet-4-5-20250929").is_some());
+        assert!(model_mismatch_warning("gemini", "o3-mini").is_some());
+    }
+
+    #[test]
+    fn matching_or_unknown_models_do_not_warn() {
+        assert!(model_mismatch_warning("anthropic", "claude-sonnet-4-5-20250929").is_none());
+        assert!(model_mismatch_warning("openai", "gpt-4o").is_none());
+        assert!(model_mismatch_warning("gemini", "gemini-2.0-flash").is_none());
+        assert!(model_mismatch_warning("openai", "llama-3.1-70b").is_none());
+    }
 }
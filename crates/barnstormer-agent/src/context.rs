@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
+use barnstormer_core::card::CardReviewResolution;
 use barnstormer_core::event::{Event, EventPayload};
 use barnstormer_core::state::ContextAttachment;
 use barnstormer_core::transcript::TranscriptMessage;
@@ -27,6 +28,15 @@ pub enum AgentRole {
 }
 
 impl AgentRole {
+    /// Every role, in the order the agent controls list them.
+    pub const ALL: [AgentRole; 5] = [
+        AgentRole::Manager,
+        AgentRole::Brainstormer,
+        AgentRole::Planner,
+        AgentRole::DotGenerator,
+        AgentRole::Critic,
+    ];
+
     /// Return a human-readable label for this role.
     pub fn label(&self) -> &'static str {
         match self {
@@ -37,6 +47,19 @@ impl AgentRole {
             AgentRole::Critic => "critic",
         }
     }
+
+    /// Parse a role label as returned by [`label`](Self::label), ignoring
+    /// case and surrounding whitespace.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "manager" => Some(AgentRole::Manager),
+            "brainstormer" => Some(AgentRole::Brainstormer),
+            "planner" => Some(AgentRole::Planner),
+            "dot_generator" => Some(AgentRole::DotGenerator),
+            "critic" => Some(AgentRole::Critic),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for AgentRole {
@@ -169,6 +192,24 @@ fn describe_event_payload(payload: &EventPayload) -> String {
         EventPayload::SpecCreated { title, .. } => {
             format!("spec created: '{}'", title)
         }
+        EventPayload::SpecForked {
+            parent_spec_id,
+            cards,
+            ..
+        } => {
+            format!(
+                "spec branched from {} with {} cards",
+                parent_spec_id,
+                cards.len()
+            )
+        }
+        EventPayload::SpecMerged {
+            source_spec_id,
+            cards,
+            ..
+        } => {
+            format!("merged {} cards from spec {}", cards.len(), source_spec_id)
+        }
         EventPayload::SpecCoreUpdated { title, .. } => {
             if let Some(t) = title {
                 format!("spec updated (title -> '{}')", t)
@@ -189,9 +230,82 @@ fn describe_event_payload(payload: &EventPayload) -> String {
         EventPayload::CardMoved { card_id, lane, .. } => {
             format!("card {} moved to '{}'", card_id, lane)
         }
+        EventPayload::CardsReordered { positions } => match positions.as_slice() {
+            [p] => format!("card {} moved to '{}'", p.card_id, p.lane),
+            _ => format!("{} cards reordered", positions.len()),
+        },
         EventPayload::CardDeleted { card_id } => {
             format!("card {} deleted", card_id)
         }
+        EventPayload::CardDoneSet { card_id, done } => {
+            if *done {
+                format!("card {} marked done", card_id)
+            } else {
+                format!("card {} marked not done", card_id)
+            }
+        }
+        EventPayload::CardArchivedSet { card_id, archived } => {
+            if *archived {
+                format!("card {} archived", card_id)
+            } else {
+                format!("card {} unarchived", card_id)
+            }
+        }
+        EventPayload::CardDueDateSet { card_id, due_date } => match due_date {
+            Some(due) => format!("card {} due {}", card_id, due.format("%Y-%m-%d")),
+            None => format!("card {} due date cleared", card_id),
+        },
+        EventPayload::CardReviewSet {
+            card_id,
+            review,
+            resolution,
+        } => match (review, resolution) {
+            (Some(review), _) => format!(
+                "card {} flagged for human review by {}: {}",
+                card_id, review.requested_by, review.reason
+            ),
+            (None, Some(CardReviewResolution::Approved)) => {
+                format!("card {} review approved by human", card_id)
+            }
+            (None, Some(CardReviewResolution::Dismissed)) => {
+                format!("card {} review dismissed by human", card_id)
+            }
+            (None, None) => format!("card {} review cleared", card_id),
+        },
+        EventPayload::CardAttachmentAdded {
+            card_id,
+            attachment,
+            ..
+        } => format!(
+            "card {} linked to '{}' ({})",
+            card_id, attachment.label, attachment.target
+        ),
+        EventPayload::CardAttachmentRemoved { card_id, .. } => {
+            format!("card {} attachment removed", card_id)
+        }
+        EventPayload::LaneRenamed {
+            from, to, card_ids, ..
+        } => format!(
+            "lane '{}' renamed to '{}' ({} cards moved)",
+            from,
+            to,
+            card_ids.len()
+        ),
+        EventPayload::LaneDeleted {
+            name,
+            moved_to,
+            card_ids,
+            ..
+        } => match moved_to {
+            Some(moved_to) => format!(
+                "lane '{}' deleted ({} cards moved to '{}')",
+                name,
+                card_ids.len(),
+                moved_to
+            ),
+            None => format!("lane '{}' deleted", name),
+        },
+        EventPayload::LanesChanged { lanes } => format!("lanes now: {}", lanes.join(", ")),
         EventPayload::TranscriptAppended { message } => {
             let preview = truncate_chars(&message.content, 50);
             format!("{} said: {}", message.sender, preview)
@@ -218,6 +332,10 @@ fn describe_event_payload(payload: &EventPayload) -> String {
         } => {
             format!("undo applied to event #{}", target_event_id)
         }
+        EventPayload::RedoApplied { payloads } => {
+            let redone: Vec<String> = payloads.iter().map(describe_event_payload).collect();
+            format!("redo applied: {}", redone.join("; "))
+        }
         EventPayload::SnapshotWritten { snapshot_id } => {
             format!("snapshot #{} written", snapshot_id)
         }
@@ -258,6 +376,77 @@ fn describe_event_payload(payload: &EventPayload) -> String {
         EventPayload::StreamingToolActivity { agent_id, activity } => {
             format!("{}: {}", agent_id, activity)
         }
+        EventPayload::ProposalQueued { proposal } => {
+            format!(
+                "proposal {} queued for review by {}: {}",
+                proposal.proposal_id, proposal.proposed_by, proposal.reason
+            )
+        }
+        EventPayload::ProposalApproved { proposal_id } => {
+            format!("proposal {} approved by human", proposal_id)
+        }
+        EventPayload::ProposalRejected { proposal_id } => {
+            format!("proposal {} rejected by human", proposal_id)
+        }
+        EventPayload::AgentModelPinned { provider, model } => {
+            format!("agents now running on {}/{}", provider, model)
+        }
+        EventPayload::UsageRecorded {
+            role,
+            input_tokens,
+            output_tokens,
+            ..
+        } => format!(
+            "{} used {} input and {} output tokens",
+            role, input_tokens, output_tokens
+        ),
+        EventPayload::SpecArchivedSet { archived } => {
+            if *archived {
+                "spec archived".to_string()
+            } else {
+                "spec unarchived".to_string()
+            }
+        }
+        EventPayload::AgentRosterSet { roles } => {
+            format!("agent roster set to {}", roles.join(", "))
+        }
+        EventPayload::FetchHostsSet { hosts } if hosts.is_empty() => {
+            "fetch_url turned off".to_string()
+        }
+        EventPayload::FetchHostsSet { hosts } => {
+            format!("fetch_url allowed for {}", hosts.join(", "))
+        }
+        EventPayload::ModelOverrideSet { model: Some(m) } => {
+            format!("agents set to start on {}/{}", m.provider, m.model)
+        }
+        EventPayload::ModelOverrideSet { model: None } => {
+            "agents set to start on the server defaults".to_string()
+        }
+        EventPayload::SnapshotOnShutdownSet { enabled } => {
+            format!(
+                "snapshot on shutdown turned {}",
+                if *enabled { "on" } else { "off" }
+            )
+        }
+        EventPayload::ChecklistItemAdded { item } => {
+            format!("done checklist item added: '{}'", item.text)
+        }
+        EventPayload::ChecklistItemToggled { item_id, checked } => {
+            if *checked {
+                format!("done checklist item {} checked", item_id)
+            } else {
+                format!("done checklist item {} unchecked", item_id)
+            }
+        }
+        EventPayload::ChecklistItemRemoved { item_id } => {
+            format!("done checklist item {} removed", item_id)
+        }
+        EventPayload::ExternalLinkAdded { link, .. } => {
+            format!("external link added: '{}' ({})", link.label, link.url)
+        }
+        EventPayload::ExternalLinkRemoved { link_id } => {
+            format!("external link {} removed", link_id)
+        }
     }
 }
 
@@ -461,6 +650,17 @@ mod tests {
         assert_eq!(AgentRole::Brainstormer.label(), "brainstormer");
         assert_eq!(AgentRole::Planner.label(), "planner");
         assert_eq!(AgentRole::DotGenerator.label(), "dot_generator");
+        for role in [
+            AgentRole::Manager,
+            AgentRole::Brainstormer,
+            AgentRole::Planner,
+            AgentRole::DotGenerator,
+            AgentRole::Critic,
+        ] {
+            assert_eq!(AgentRole::from_label(role.label()), Some(role));
+        }
+        assert_eq!(AgentRole::from_label(" Planner "), Some(AgentRole::Planner));
+        assert_eq!(AgentRole::from_label("wizard"), None);
         assert_eq!(AgentRole::Critic.label(), "critic");
     }
 
@@ -0,0 +1,318 @@
+// ABOUTME: Rough token and dollar estimates for running the agent swarm, from prompt size and a per-model price table.
+// ABOUTME: Meant as a warning before agents start on a large spec, not as billing.
+
+use std::fmt;
+
+/// Characters per token used to turn prompt length into tokens.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Think-act iterations assumed per agent step. Each iteration resends the
+/// prompt, so input tokens scale with it.
+pub const ITERATIONS_PER_STEP: u64 = 3;
+
+/// Output tokens assumed per agent step (tool calls plus narration).
+pub const OUTPUT_TOKENS_PER_STEP: u64 = 1_000;
+
+/// Agent cycles assumed per hour. Busy cycles follow each other within
+/// seconds, but a cycle waits on every agent's LLM calls in turn, and idle
+/// cycles sleep; one cycle every two minutes is a middle ground.
+pub const CYCLES_PER_HOUR: f64 = 30.0;
+
+/// USD per million tokens for one model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+impl ModelPrice {
+    /// USD for the given token counts.
+    pub fn cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input_per_mtok + output_tokens as f64 * self.output_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// Prices by model-name prefix. The longest prefix that starts a model name
+/// wins, so `claude-sonnet-4-5` covers dated releases of that model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTable {
+    entries: Vec<(String, ModelPrice)>,
+}
+
+impl Default for PriceTable {
+    /// List prices for common models at the time of writing.
+    fn default() -> Self {
+        let entries = [
+            ("claude-opus", 15.0, 75.0),
+            ("claude-sonnet", 3.0, 15.0),
+            ("claude-haiku", 1.0, 5.0),
+            ("gpt-4o", 2.5, 10.0),
+            ("gpt-4o-mini", 0.15, 0.6),
+            ("gpt-4.1", 2.0, 8.0),
+            ("gemini-2.0-flash", 0.1, 0.4),
+            ("gemini-2.5-flash", 0.3, 2.5),
+            ("gemini-2.5-pro", 1.25, 10.0),
+        ];
+        Self {
+            entries: entries
+                .iter()
+                .map(|(prefix, input, output)| {
+                    (
+                        prefix.to_string(),
+                        ModelPrice {
+                            input_per_mtok: *input,
+                            output_per_mtok: *output,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl PriceTable {
+    /// The built-in table with `overrides` applied. `overrides` is a
+    /// comma-separated list of `model=input/output` entries in USD per
+    /// million tokens, e.g. `claude-sonnet-4-5=3/15,my-proxy-model=0.5/1.5`.
+    /// An entry replaces a built-in one with the same prefix.
+    pub fn with_overrides(overrides: &str) -> Result<Self, String> {
+        let mut table = Self::default();
+        for entry in overrides
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let (model, prices) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected model=input/output, got '{}'", entry))?;
+            let (input, output) = prices
+                .split_once('/')
+                .ok_or_else(|| format!("expected input/output prices, got '{}'", prices))?;
+            let parse = |v: &str| {
+                v.trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|p| p.is_finite() && *p >= 0.0)
+                    .ok_or_else(|| format!("invalid price '{}' for {}", v.trim(), model.trim()))
+            };
+            let price = ModelPrice {
+                input_per_mtok: parse(input)?,
+                output_per_mtok: parse(output)?,
+            };
+            let model = model.trim().to_ascii_lowercase();
+            table.entries.retain(|(prefix, _)| *prefix != model);
+            table.entries.push((model, price));
+        }
+        Ok(table)
+    }
+
+    /// Price for `model` on `provider`. Local Ollama models are free; other
+    /// models without a matching entry return None.
+    pub fn price_for(&self, provider: &str, model: &str) -> Option<ModelPrice> {
+        if provider == "ollama" {
+            return Some(ModelPrice {
+                input_per_mtok: 0.0,
+                output_per_mtok: 0.0,
+            });
+        }
+        let model = model.trim().to_ascii_lowercase();
+        self.entries
+            .iter()
+            .filter(|(prefix, _)| model.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| *price)
+    }
+}
+
+/// Estimated usage of one agent step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepCost {
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// None when the model has no price.
+    pub usd: Option<f64>,
+}
+
+/// Estimate one agent step on `model` with a prompt of `context_size`
+/// characters (system prompt plus task prompt).
+pub fn estimate_step_cost(
+    provider: &str,
+    model: &str,
+    context_size: usize,
+    prices: &PriceTable,
+) -> StepCost {
+    let input_tokens = context_size.div_ceil(CHARS_PER_TOKEN) as u64 * ITERATIONS_PER_STEP;
+    let output_tokens = OUTPUT_TOKENS_PER_STEP;
+    let usd = prices
+        .price_for(provider, model)
+        .map(|price| price.cost(input_tokens, output_tokens));
+    StepCost {
+        model: model.to_string(),
+        input_tokens,
+        output_tokens,
+        usd,
+    }
+}
+
+/// Estimated usage of a full cycle, one step per enabled agent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleCost {
+    pub agent_count: usize,
+    pub tokens_per_cycle: u64,
+    /// None when any agent's model has no price.
+    pub usd_per_cycle: Option<f64>,
+    /// Models that have no price, so the estimate is missing.
+    pub unpriced_models: Vec<String>,
+}
+
+impl CycleCost {
+    /// Sum the steps of one cycle.
+    pub fn from_steps(steps: &[StepCost]) -> Self {
+        let mut unpriced_models: Vec<String> = Vec::new();
+        for step in steps.iter().filter(|s| s.usd.is_none()) {
+            if !unpriced_models.contains(&step.model) {
+                unpriced_models.push(step.model.clone());
+            }
+        }
+        Self {
+            agent_count: steps.len(),
+            tokens_per_cycle: steps.iter().map(|s| s.input_tokens + s.output_tokens).sum(),
+            usd_per_cycle: steps.iter().map(|s| s.usd).sum(),
+            unpriced_models,
+        }
+    }
+
+    /// Projected spend per hour at [`CYCLES_PER_HOUR`].
+    pub fn usd_per_hour(&self) -> Option<f64> {
+        self.usd_per_cycle.map(|usd| usd * CYCLES_PER_HOUR)
+    }
+}
+
+impl fmt::Display for CycleCost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.usd_per_hour() {
+            Some(usd) => write!(f, "≈ ${:.2}/hour at current settings", usd),
+            None => write!(
+                f,
+                "Cost unknown: no price for {}",
+                self.unpriced_models.join(", ")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sonnet_cycle(agents: usize, context_size: usize, prices: &PriceTable) -> CycleCost {
+        let steps: Vec<StepCost> = (0..agents)
+            .map(|_| {
+                estimate_step_cost(
+                    "anthropic",
+                    "claude-sonnet-4-5-20250929",
+                    context_size,
+                    prices,
+                )
+            })
+            .collect();
+        CycleCost::from_steps(&steps)
+    }
+
+    #[test]
+    fn step_cost_uses_the_price_table() {
+        let step = estimate_step_cost(
+            "anthropic",
+            "claude-sonnet-4-5-20250929",
+            4_000,
+            &PriceTable::default(),
+        );
+        assert_eq!(step.input_tokens, 1_000 * ITERATIONS_PER_STEP);
+        assert_eq!(step.output_tokens, OUTPUT_TOKENS_PER_STEP);
+        let expected = (3_000.0 * 3.0 + 1_000.0 * 15.0) / 1_000_000.0;
+        assert!((step.usd.unwrap() - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn estimate_scales_with_agent_count_and_context_size() {
+        let prices = PriceTable::default();
+        let base = sonnet_cycle(2, 8_000, &prices);
+        let more_agents = sonnet_cycle(4, 8_000, &prices);
+        let bigger_context = sonnet_cycle(2, 80_000, &prices);
+
+        assert_eq!(more_agents.agent_count, 4);
+        assert_eq!(more_agents.tokens_per_cycle, base.tokens_per_cycle * 2);
+        assert!(
+            (more_agents.usd_per_hour().unwrap() - base.usd_per_hour().unwrap() * 2.0).abs() < 1e-9
+        );
+        assert!(bigger_context.tokens_per_cycle > base.tokens_per_cycle);
+        assert!(bigger_context.usd_per_hour().unwrap() > base.usd_per_hour().unwrap());
+    }
+
+    #[test]
+    fn configured_prices_override_the_built_in_table() {
+        let prices = PriceTable::with_overrides("claude-sonnet=30/150, house-model=1/2").unwrap();
+        let default = sonnet_cycle(1, 4_000, &PriceTable::default());
+        let configured = sonnet_cycle(1, 4_000, &prices);
+        assert!(
+            (configured.usd_per_cycle.unwrap() - default.usd_per_cycle.unwrap() * 10.0).abs()
+                < 1e-12
+        );
+        assert_eq!(
+            prices.price_for("openai", "house-model-v2"),
+            Some(ModelPrice {
+                input_per_mtok: 1.0,
+                output_per_mtok: 2.0
+            })
+        );
+    }
+
+    #[test]
+    fn longest_prefix_wins() {
+        let prices = PriceTable::default();
+        assert_eq!(
+            prices
+                .price_for("openai", "gpt-4o-mini")
+                .unwrap()
+                .input_per_mtok,
+            0.15
+        );
+        assert_eq!(
+            prices.price_for("openai", "gpt-4o").unwrap().input_per_mtok,
+            2.5
+        );
+    }
+
+    #[test]
+    fn unknown_models_are_unpriced_and_ollama_is_free() {
+        let prices = PriceTable::default();
+        let steps = [
+            estimate_step_cost("openai", "mystery-model", 4_000, &prices),
+            estimate_step_cost("ollama", "llama3.1", 4_000, &prices),
+        ];
+        assert_eq!(steps[1].usd, Some(0.0));
+        let cycle = CycleCost::from_steps(&steps);
+        assert!(cycle.usd_per_hour().is_none());
+        assert_eq!(
+            cycle.to_string(),
+            "Cost unknown: no price for mystery-model"
+        );
+
+        let free = CycleCost::from_steps(&steps[1..]);
+        assert_eq!(free.to_string(), "≈ $0.00/hour at current settings");
+    }
+
+    #[test]
+    fn malformed_overrides_are_rejected() {
+        assert!(PriceTable::with_overrides("claude-sonnet").is_err());
+        assert!(PriceTable::with_overrides("claude-sonnet=3").is_err());
+        assert!(PriceTable::with_overrides("claude-sonnet=x/15").is_err());
+        assert!(PriceTable::with_overrides("claude-sonnet=-1/15").is_err());
+        assert_eq!(
+            PriceTable::with_overrides("").unwrap(),
+            PriceTable::default()
+        );
+    }
+}
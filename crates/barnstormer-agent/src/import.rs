@@ -1,12 +1,12 @@
-// ABOUTME: LLM-powered spec import — parses arbitrary text into structured spec commands.
-// ABOUTME: Sends content to an LLM, extracts JSON with spec metadata and cards, converts to Commands.
+// ABOUTME: Spec import — parses arbitrary text (via an LLM) or native JSON into structured spec commands.
+// ABOUTME: Extracts spec metadata and cards into an ImportResult, then converts it to Commands.
 
 use std::sync::Arc;
 
 use mux::llm::{LlmClient, Message, Request};
 use serde::{Deserialize, Serialize};
 
-use barnstormer_core::Command;
+use barnstormer_core::{Command, SpecState};
 
 /// Result of parsing input content via the LLM. Contains the core spec
 /// metadata and any cards extracted from the source material.
@@ -161,6 +161,60 @@ pub fn extract_json(text: &str) -> Result<ImportResult, anyhow::Error> {
     ))
 }
 
+/// Parse a native JSON payload without an LLM: either a barnstormer JSON
+/// export (see `barnstormer_core::export::export_json`) or a bare
+/// `ImportResult`. Deterministic, so repeated imports of the same file
+/// produce the same commands. Card IDs, refs and the transcript are not
+/// carried over; cards are recreated in export order.
+pub fn parse_native_json(content: &str) -> Result<ImportResult, anyhow::Error> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| anyhow::anyhow!("input is not valid JSON: {}", e))?;
+
+    if value.get("format").is_some() {
+        let state = barnstormer_core::export::import_json(content)
+            .map_err(|e| anyhow::anyhow!("invalid barnstormer JSON export: {}", e))?;
+        return import_result_from_state(&state);
+    }
+
+    serde_json::from_value::<ImportResult>(value)
+        .map_err(|e| anyhow::anyhow!("JSON does not match the import schema: {}", e))
+}
+
+/// Flatten an exported spec into an ImportResult, with cards in the same
+/// order as the export.
+fn import_result_from_state(state: &SpecState) -> Result<ImportResult, anyhow::Error> {
+    let core = state
+        .core
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("exported spec has no core"))?;
+
+    let cards = barnstormer_core::export::cards_in_export_order(state);
+
+    Ok(ImportResult {
+        spec: ImportSpec {
+            title: core.title.clone(),
+            one_liner: core.one_liner.clone(),
+            goal: core.goal.clone(),
+        },
+        update: Some(ImportUpdate {
+            description: core.description.clone(),
+            constraints: core.constraints.clone(),
+            success_criteria: core.success_criteria.clone(),
+            risks: core.risks.clone(),
+            notes: core.notes.clone(),
+        }),
+        cards: cards
+            .into_iter()
+            .map(|card| ImportCard {
+                card_type: card.card_type.clone(),
+                title: card.title.clone(),
+                body: card.body.clone(),
+                lane: Some(card.lane.clone()),
+            })
+            .collect(),
+    })
+}
+
 /// Strip markdown code fences from text.
 fn strip_code_fences(text: &str) -> String {
     let mut lines: Vec<&str> = Vec::new();
@@ -216,19 +270,30 @@ pub fn to_commands(result: &ImportResult) -> Vec<Command> {
         }
     }
 
-    // CreateCard commands
-    for card in &result.cards {
-        commands.push(Command::CreateCard {
-            card_type: card.card_type.clone(),
+    commands.extend(to_card_commands(result));
+
+    commands
+}
+
+/// Convert only the cards of an ImportResult into CreateCard commands, for
+/// merging an import into a spec that already exists. Spec identity and
+/// narrative fields are left out so the existing spec's core is untouched.
+/// Card types are normalized; one the board does not know becomes an idea
+/// rather than failing the import.
+pub fn to_card_commands(result: &ImportResult) -> Vec<Command> {
+    result
+        .cards
+        .iter()
+        .map(|card| Command::CreateCard {
+            card_type: barnstormer_core::normalize_card_type(&card.card_type)
+                .unwrap_or_else(|| "idea".to_string()),
             title: card.title.clone(),
             body: card.body.clone(),
             lane: card.lane.clone(),
             created_by: "import".to_string(),
             source_attachment_id: None,
-        });
-    }
-
-    commands
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -363,6 +428,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn to_card_commands_omits_spec_identity_and_core() {
+        let result = sample_import_result();
+        let commands = to_card_commands(&result);
+
+        assert_eq!(commands.len(), result.cards.len());
+        assert!(commands.iter().all(
+            |c| matches!(c, Command::CreateCard { created_by, .. } if created_by == "import")
+        ));
+    }
+
     #[test]
     fn to_commands_handles_empty_cards() {
         let result = ImportResult {
@@ -438,6 +514,60 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("failed to parse"));
     }
 
+    // -- parse_native_json tests --
+
+    #[test]
+    fn parse_native_json_reads_a_bare_import_result() {
+        let json = serde_json::to_string(&sample_import_result()).unwrap();
+        let result = parse_native_json(&json).unwrap();
+        assert_eq!(result.spec.title, "Todo App");
+        assert_eq!(result.cards.len(), 2);
+    }
+
+    #[test]
+    fn parse_native_json_reads_a_json_export() {
+        let mut state = SpecState::new();
+        let mut core = barnstormer_core::SpecCore::new(
+            "Exported".to_string(),
+            "From JSON".to_string(),
+            "Import without an LLM".to_string(),
+        );
+        core.risks = Some("Drift".to_string());
+        state.core = Some(core);
+        for (title, order) in [("Later", 2.0), ("Sooner", 1.0)] {
+            let mut card = barnstormer_core::Card::new(
+                "task".to_string(),
+                title.to_string(),
+                "human".to_string(),
+            );
+            card.lane = "Plan".to_string();
+            card.order = order;
+            state.cards.insert(card.card_id, card);
+        }
+        let json = barnstormer_core::export::export_json(&state).unwrap();
+
+        let result = parse_native_json(&json).unwrap();
+        assert_eq!(result.spec.title, "Exported");
+        assert_eq!(
+            result.update.as_ref().unwrap().risks.as_deref(),
+            Some("Drift")
+        );
+        let titles: Vec<&str> = result.cards.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Sooner", "Later"]);
+        assert_eq!(result.cards[0].lane.as_deref(), Some("Plan"));
+
+        // Same input, same commands.
+        let again = parse_native_json(&json).unwrap();
+        assert_eq!(to_commands(&result), to_commands(&again));
+    }
+
+    #[test]
+    fn parse_native_json_rejects_other_json_and_text() {
+        assert!(parse_native_json(r#"{"name": "not a spec"}"#).is_err());
+        assert!(parse_native_json(r#"{"format": "other", "version": 1}"#).is_err());
+        assert!(parse_native_json("# A markdown spec").is_err());
+    }
+
     // -- ImportResult serde round-trip --
 
     #[test]
@@ -456,6 +586,21 @@ mod tests {
 
     // -- build_import_system_prompt tests --
 
+    #[test]
+    fn card_commands_normalize_unknown_card_types_to_ideas() {
+        let mut result = sample_import_result();
+        result.cards[0].card_type = "Open Question".to_string();
+        result.cards[1].card_type = "requirement".to_string();
+        let types: Vec<String> = to_card_commands(&result)
+            .into_iter()
+            .map(|command| match command {
+                Command::CreateCard { card_type, .. } => card_type,
+                other => panic!("expected CreateCard, got {:?}", other),
+            })
+            .collect();
+        assert_eq!(types, vec!["open_question", "idea"]);
+    }
+
     #[test]
     fn system_prompt_includes_card_types() {
         let prompt = build_import_system_prompt(None);
@@ -4,14 +4,22 @@
 pub mod attachment_summarizer;
 pub mod client;
 pub mod context;
+pub mod cost;
 pub mod import;
+pub mod llm_debug;
+pub mod llm_retry;
+pub mod metrics;
 pub mod mux_tools;
+pub mod step_error;
 pub mod streaming_hook;
 pub mod swarm;
 pub mod testing;
 
 pub use attachment_summarizer::AttachmentSummarizer;
 pub use context::{AgentContext, AgentRole, contexts_from_snapshot_map, contexts_to_snapshot_map};
+pub use step_error::AgentError;
 pub use swarm::{
-    AgentRunner, SwarmOrchestrator, render_context_files_section, run_loop, system_prompt_for_role,
+    AgentRunner, ContextWindows, DEFAULT_ROLES, StepTrace, StepTraceEntry, SwarmOrchestrator,
+    env_model_overrides, estimated_prompt_chars, render_context_files_section, roles_or_default,
+    roster_roles, run_loop, system_prompt_for_role,
 };
@@ -0,0 +1,279 @@
+// ABOUTME: Opt-in LLM request/response logging (BARNSTORMER_DEBUG_LLM) with regex-based redaction.
+// ABOUTME: Wraps any LlmClient; only the logged copy is redacted, the provider sees the request as-is.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::Stream;
+use mux::error::LlmError;
+use mux::llm::{LlmClient, Request, Response, StreamEvent};
+use regex::Regex;
+
+/// Replacement text for anything the redactor removes.
+pub const REDACTED: &str = "<redacted>";
+
+/// Patterns redacted from logged prompts and responses unless
+/// `BARNSTORMER_DEBUG_LLM_REDACT` replaces them: email addresses, bearer
+/// tokens, and common API key shapes.
+pub const DEFAULT_REDACT_PATTERNS: &[&str] = &[
+    r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+    r"(?i)bearer\s+[A-Za-z0-9._~+/=-]+",
+    r"\bsk-[A-Za-z0-9_-]{16,}",
+    r"\bAKIA[0-9A-Z]{16}\b",
+    r"\bgh[pousr]_[A-Za-z0-9]{20,}",
+];
+
+/// Scrubs text before it is logged. The provider API key is always removed,
+/// whatever patterns are configured.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    /// Build a redactor from regex `patterns` plus literal `secrets`.
+    /// Patterns that fail to compile are logged and skipped.
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>, secrets: Vec<String>) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .filter_map(|p| match Regex::new(p) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    tracing::warn!("ignoring invalid redaction pattern {:?}: {}", p, e);
+                    None
+                }
+            })
+            .collect();
+        let secrets = secrets.into_iter().filter(|s| !s.is_empty()).collect();
+        Self { patterns, secrets }
+    }
+
+    /// Patterns from `BARNSTORMER_DEBUG_LLM_REDACT` (separated by `;;`), or
+    /// the defaults when unset, always redacting `api_key`.
+    pub fn from_env(api_key: &str) -> Self {
+        let secrets = vec![api_key.to_string()];
+        match std::env::var("BARNSTORMER_DEBUG_LLM_REDACT") {
+            Ok(raw) if !raw.trim().is_empty() => Self::new(
+                raw.split(";;").map(str::trim).filter(|p| !p.is_empty()),
+                secrets,
+            ),
+            _ => Self::new(DEFAULT_REDACT_PATTERNS.iter().copied(), secrets),
+        }
+    }
+
+    /// Return `text` with every secret and pattern match replaced.
+    pub fn redact(&self, text: &str) -> String {
+        let mut out = text.to_string();
+        for secret in &self.secrets {
+            out = out.replace(secret.as_str(), REDACTED);
+        }
+        for re in &self.patterns {
+            out = re.replace_all(&out, REDACTED).into_owned();
+        }
+        out
+    }
+}
+
+/// Client wrapper that logs each request and response through `tracing`
+/// (redacted) and, when `raw_log` is set, appends them unredacted to that
+/// file. Requests are forwarded to the inner client untouched.
+pub struct DebugLoggingClient {
+    inner: Arc<dyn LlmClient>,
+    redactor: Redactor,
+    raw_log: Option<PathBuf>,
+}
+
+impl DebugLoggingClient {
+    pub fn new(inner: Arc<dyn LlmClient>, redactor: Redactor, raw_log: Option<PathBuf>) -> Self {
+        Self {
+            inner,
+            redactor,
+            raw_log,
+        }
+    }
+
+    /// The form of `req` that goes to the shared log.
+    pub fn logged_request(&self, req: &Request) -> String {
+        self.redactor.redact(&format!("{:?}", req))
+    }
+
+    fn log_request(&self, req: &Request) {
+        tracing::info!(target: "barnstormer::llm", "request: {}", self.logged_request(req));
+        self.append_raw("request", &format!("{:?}", req));
+    }
+
+    fn append_raw(&self, kind: &str, text: &str) {
+        let Some(path) = &self.raw_log else {
+            return;
+        };
+        let result = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| {
+                writeln!(
+                    file,
+                    "{} {}: {}",
+                    chrono::Utc::now().to_rfc3339(),
+                    kind,
+                    text
+                )
+            });
+        if let Err(e) = result {
+            tracing::warn!("failed to write raw LLM log {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for DebugLoggingClient {
+    async fn create_message(&self, req: &Request) -> Result<Response, LlmError> {
+        self.log_request(req);
+        let result = self.inner.create_message(req).await;
+        match &result {
+            Ok(resp) => {
+                let raw = format!("{:?}", resp);
+                tracing::info!(
+                    target: "barnstormer::llm",
+                    "response: {}",
+                    self.redactor.redact(&raw)
+                );
+                self.append_raw("response", &raw);
+            }
+            Err(e) => {
+                tracing::info!(
+                    target: "barnstormer::llm",
+                    "error: {}",
+                    self.redactor.redact(&e.to_string())
+                );
+            }
+        }
+        result
+    }
+
+    fn create_message_stream(
+        &self,
+        req: &Request,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        self.log_request(req);
+        self.inner.create_message_stream(req)
+    }
+}
+
+/// Wrap `client` in a `DebugLoggingClient` when `BARNSTORMER_DEBUG_LLM` is
+/// on; otherwise return it unchanged. The unredacted file log additionally
+/// needs `BARNSTORMER_DEBUG_LLM_RAW_LOG` set to a path.
+pub fn wrap_if_enabled(client: Arc<dyn LlmClient>, api_key: &str) -> Arc<dyn LlmClient> {
+    let enabled = std::env::var("BARNSTORMER_DEBUG_LLM")
+        .map(|v| matches!(v.trim(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false);
+    if !enabled {
+        return client;
+    }
+    let raw_log = std::env::var("BARNSTORMER_DEBUG_LLM_RAW_LOG")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from);
+    Arc::new(DebugLoggingClient::new(
+        client,
+        Redactor::from_env(api_key),
+        raw_log,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mux::llm::Message;
+    use std::sync::Mutex;
+
+    /// Records the Debug form of every request it is sent.
+    struct RecordingClient {
+        seen: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl LlmClient for RecordingClient {
+        async fn create_message(&self, req: &Request) -> Result<Response, LlmError> {
+            self.seen.lock().unwrap().push(format!("{:?}", req));
+            crate::testing::StubLlmClient::done()
+                .create_message(req)
+                .await
+        }
+
+        fn create_message_stream(
+            &self,
+            _req: &Request,
+        ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+            Box::pin(futures::stream::empty())
+        }
+    }
+
+    #[test]
+    fn redacts_defaults_and_the_api_key() {
+        let redactor = Redactor::new(
+            DEFAULT_REDACT_PATTERNS.iter().copied(),
+            vec!["secret-key-123".to_string()],
+        );
+        let out = redactor.redact(
+            "mail ada@example.com, auth Bearer abc.def-ghi, key secret-key-123, sk-abcdefghijklmnopqrst",
+        );
+        assert!(!out.contains("ada@example.com"), "{}", out);
+        assert!(!out.contains("abc.def-ghi"));
+        assert!(!out.contains("secret-key-123"));
+        assert!(!out.contains("sk-abcdefghijklmnopqrst"));
+        assert_eq!(out.matches(REDACTED).count(), 4, "{}", out);
+    }
+
+    #[test]
+    fn custom_patterns_replace_defaults_and_bad_ones_are_skipped() {
+        let redactor = Redactor::new(["ACME-\\d+", "(unclosed"], Vec::new());
+        let out = redactor.redact("ticket ACME-42 from bob@example.com");
+        assert_eq!(out, "ticket <redacted> from bob@example.com");
+    }
+
+    #[tokio::test]
+    async fn logged_prompt_is_redacted_but_provider_request_is_not() {
+        let inner = Arc::new(RecordingClient {
+            seen: Mutex::new(Vec::new()),
+        });
+        let client = DebugLoggingClient::new(
+            inner.clone(),
+            Redactor::new(DEFAULT_REDACT_PATTERNS.iter().copied(), Vec::new()),
+            None,
+        );
+        let req = Request::new("test-model").message(Message::user(
+            "Follow up with ada@example.com about pricing",
+        ));
+
+        let logged = client.logged_request(&req);
+        assert!(!logged.contains("ada@example.com"), "{}", logged);
+        assert!(logged.contains("about pricing"));
+
+        client.create_message(&req).await.unwrap();
+        let seen = inner.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0].contains("ada@example.com"));
+    }
+
+    #[tokio::test]
+    async fn raw_log_keeps_the_unredacted_prompt() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("llm.log");
+        let client = DebugLoggingClient::new(
+            Arc::new(crate::testing::StubLlmClient::done()),
+            Redactor::new(DEFAULT_REDACT_PATTERNS.iter().copied(), Vec::new()),
+            Some(path.clone()),
+        );
+        let req = Request::new("test-model").message(Message::user("ping ada@example.com"));
+        client.create_message(&req).await.unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(raw.contains("ada@example.com"));
+        assert!(raw.contains("response:"));
+    }
+}
@@ -0,0 +1,259 @@
+// ABOUTME: Retries throttled (429/overloaded) and server-error (5xx) LLM calls with exponential backoff.
+// ABOUTME: Wraps an LlmClient; honors a retry-after hint in the error text, configured by ANTHROPIC_MAX_RETRIES.
+
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::Stream;
+use mux::error::LlmError;
+use mux::llm::{LlmClient, Request, Response, StreamEvent};
+
+use crate::step_error::AgentError;
+
+/// Longest wait honored from a provider's retry-after hint, so a bogus
+/// value cannot park an agent for an hour.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// How many times to retry a retryable failure and how long to wait first.
+/// The wait doubles on every retry unless the provider says otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The policy from `ANTHROPIC_MAX_RETRIES` and
+    /// `ANTHROPIC_RETRY_BASE_DELAY_MS`, falling back to the defaults for
+    /// unset or unparseable values.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let parse = |key: &str| std::env::var(key).ok()?.trim().parse::<u64>().ok();
+        Self {
+            max_retries: parse("ANTHROPIC_MAX_RETRIES")
+                .map(|n| n.min(u64::from(u32::MAX)) as u32)
+                .unwrap_or(default.max_retries),
+            base_delay: parse("ANTHROPIC_RETRY_BASE_DELAY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.base_delay),
+        }
+    }
+
+    /// How long to wait before retry number `retry` (0-based) after `error`,
+    /// or `None` when the error is not worth retrying.
+    pub fn delay_for(&self, retry: u32, error: &dyn Display) -> Option<Duration> {
+        let text = error.to_string();
+        if !is_retryable(&text) {
+            return None;
+        }
+        Some(retry_after(&text).unwrap_or_else(|| {
+            self.base_delay
+                .saturating_mul(2u32.saturating_pow(retry.min(16)))
+        }))
+    }
+
+    /// Run `call`, retrying it per this policy. The last error is returned
+    /// once retries run out or the error is not retryable.
+    pub async fn run<T, E, F, Fut>(&self, mut call: F) -> Result<T, E>
+    where
+        E: Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut retry = 0;
+        loop {
+            let err = match call().await {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            if retry >= self.max_retries {
+                return Err(err);
+            }
+            let Some(delay) = self.delay_for(retry, &err) else {
+                return Err(err);
+            };
+            tracing::warn!(
+                "LLM call failed ({}); retry {}/{} in {:?}",
+                err,
+                retry + 1,
+                self.max_retries,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            retry += 1;
+        }
+    }
+}
+
+/// Rate limits and server errors are transient; everything else (bad key,
+/// unknown model, malformed request) fails the same way on every attempt.
+fn is_retryable(text: &str) -> bool {
+    if AgentError::classify(&text) == AgentError::RateLimited {
+        return true;
+    }
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter_map(|token| token.parse::<u16>().ok())
+        .any(|code| (500..600).contains(&code))
+}
+
+/// A `retry-after` hint in seconds, when the provider error carries one
+/// (e.g. "retry-after: 7" or "retry after 7s"). Capped at `MAX_RETRY_AFTER`.
+fn retry_after(text: &str) -> Option<Duration> {
+    let lower = text.to_ascii_lowercase().replace(['-', '_'], " ");
+    let rest = &lower[lower.find("retry after")? + "retry after".len()..];
+    let digits: String = rest
+        .trim_start_matches(|c: char| c == ':' || c == '=' || c.is_whitespace())
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let seconds: f64 = digits.parse().ok()?;
+    (seconds.is_finite() && seconds >= 0.0)
+        .then(|| Duration::from_secs_f64(seconds).min(MAX_RETRY_AFTER))
+}
+
+/// Client wrapper that retries `create_message` per a `RetryPolicy`.
+/// Streams are passed through untouched: a stream that fails partway has
+/// already delivered output, so replaying it would duplicate text.
+pub struct RetryingClient {
+    inner: Arc<dyn LlmClient>,
+    policy: RetryPolicy,
+}
+
+impl RetryingClient {
+    pub fn new(inner: Arc<dyn LlmClient>, policy: RetryPolicy) -> Self {
+        Self { inner, policy }
+    }
+}
+
+#[async_trait]
+impl LlmClient for RetryingClient {
+    async fn create_message(&self, req: &Request) -> Result<Response, LlmError> {
+        self.policy.run(|| self.inner.create_message(req)).await
+    }
+
+    fn create_message_stream(
+        &self,
+        req: &Request,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        self.inner.create_message_stream(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+        }
+    }
+
+    /// Fail with each of `errors` in turn, then succeed with the call count.
+    async fn flaky(calls: &AtomicU32, errors: &[&str]) -> Result<u32, String> {
+        let n = calls.fetch_add(1, Ordering::SeqCst);
+        match errors.get(n as usize) {
+            Some(err) => Err(err.to_string()),
+            None => Ok(n + 1),
+        }
+    }
+
+    #[tokio::test]
+    async fn rate_limits_and_server_errors_are_retried() {
+        let calls = AtomicU32::new(0);
+        let errors = [
+            "API error 429: rate_limit_error",
+            "API error 529: overloaded_error",
+            "API error 503: service unavailable",
+        ];
+        let result = fast_policy(3).run(|| flaky(&calls, &errors)).await;
+        assert_eq!(result, Ok(4));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let calls = AtomicU32::new(0);
+        let errors = ["API error 429: rate_limit_error"; 5];
+        let result = fast_policy(2).run(|| flaky(&calls, &errors)).await;
+        assert_eq!(result, Err(errors[0].to_string()));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn non_retryable_errors_fail_immediately() {
+        let calls = AtomicU32::new(0);
+        let errors = ["API error 401: authentication_error: invalid x-api-key"];
+        let result = fast_policy(3).run(|| flaky(&calls, &errors)).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn backoff_doubles_unless_the_provider_says_when() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+        };
+        let throttled = "API error 429: rate_limit_error";
+        assert_eq!(
+            policy.delay_for(0, &throttled),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            policy.delay_for(2, &throttled),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay_for(0, &"API error 429 (retry-after: 7)"),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            policy.delay_for(0, &"429 Too Many Requests, retry after 3600s"),
+            Some(MAX_RETRY_AFTER)
+        );
+        assert_eq!(policy.delay_for(0, &"API error 400: invalid_request"), None);
+    }
+
+    #[test]
+    fn policy_reads_env_and_falls_back_on_garbage() {
+        let _guard = crate::testing::ENV_MUTEX
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let prior: Vec<_> = ["ANTHROPIC_MAX_RETRIES", "ANTHROPIC_RETRY_BASE_DELAY_MS"]
+            .iter()
+            .map(|k| (*k, std::env::var(k).ok()))
+            .collect();
+
+        // SAFETY: ENV_MUTEX serializes env-mutating tests in this crate; the
+        // prior values are restored below.
+        unsafe {
+            std::env::set_var("ANTHROPIC_MAX_RETRIES", "5");
+            std::env::set_var("ANTHROPIC_RETRY_BASE_DELAY_MS", "soon");
+        }
+        let policy = RetryPolicy::from_env();
+        for (key, value) in prior {
+            match value {
+                Some(v) => unsafe { std::env::set_var(key, v) },
+                None => unsafe { std::env::remove_var(key) },
+            }
+        }
+
+        assert_eq!(policy.max_retries, 5);
+        assert_eq!(policy.base_delay, RetryPolicy::default().base_delay);
+    }
+}
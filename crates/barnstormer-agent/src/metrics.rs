@@ -0,0 +1,32 @@
+// ABOUTME: Process-wide counters for agent steps run and failed, read by the server's /metrics endpoint.
+// ABOUTME: Plain atomics so run_agent_step can count without threading a handle through every swarm.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static AGENT_STEPS: AtomicU64 = AtomicU64::new(0);
+static AGENT_ERRORS: AtomicU64 = AtomicU64::new(0);
+
+/// Agent step totals since the process started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AgentStepCounts {
+    pub steps: u64,
+    pub errors: u64,
+}
+
+/// Count one agent step as started.
+pub fn record_step() {
+    AGENT_STEPS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Count one agent step as failed.
+pub fn record_error() {
+    AGENT_ERRORS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Current agent step totals.
+pub fn agent_step_counts() -> AgentStepCounts {
+    AgentStepCounts {
+        steps: AGENT_STEPS.load(Ordering::Relaxed),
+        errors: AGENT_ERRORS.load(Ordering::Relaxed),
+    }
+}
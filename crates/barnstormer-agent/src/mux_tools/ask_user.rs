@@ -1,5 +1,5 @@
 // ABOUTME: Implements three ask_user tool variants (boolean, multiple_choice, freeform) via mux Tool trait.
-// ABOUTME: Each tool gates on an AtomicBool to prevent multiple concurrent questions.
+// ABOUTME: Questions queue up in the spec; the shared AtomicBool tracks whether any are waiting.
 
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -13,6 +13,38 @@ use barnstormer_core::actor::SpecActorHandle;
 use barnstormer_core::command::Command;
 use barnstormer_core::transcript::UserQuestion;
 
+/// Queue `question` for the user and raise the pending flag. Questions from
+/// several agents wait in line instead of being dropped. If the command
+/// fails, the flag is re-synced from the actor so it stays set only while
+/// the queue is non-empty.
+async fn enqueue_question(
+    actor: &SpecActorHandle,
+    question_pending: &AtomicBool,
+    question: UserQuestion,
+) -> Result<ToolResult, anyhow::Error> {
+    question_pending.store(true, Ordering::SeqCst);
+    if let Err(e) = actor.send_command(Command::AskQuestion { question }).await {
+        let waiting = !actor.read_state().await.pending_questions.is_empty();
+        question_pending.store(waiting, Ordering::SeqCst);
+        return Err(anyhow::anyhow!("failed to ask question: {}", e));
+    }
+
+    let ahead = actor
+        .read_state()
+        .await
+        .pending_questions
+        .len()
+        .saturating_sub(1);
+    if ahead == 0 {
+        Ok(ToolResult::text("Question asked"))
+    } else {
+        Ok(ToolResult::text(format!(
+            "Question queued behind {} earlier question(s)",
+            ahead
+        )))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ask_user_boolean
 // ---------------------------------------------------------------------------
@@ -64,32 +96,13 @@ impl Tool for AskUserBooleanTool {
 
         let default = params.get("default").and_then(|v| v.as_bool());
 
-        // Atomically check-and-set to avoid TOCTOU race between agents.
-        if self
-            .question_pending
-            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-            .is_err()
-        {
-            return Ok(ToolResult::text("Question already pending, skipping"));
-        }
-
         let question = UserQuestion::Boolean {
             question_id: Ulid::new(),
             question: question_text,
             default,
         };
 
-        if let Err(e) = self
-            .actor
-            .send_command(Command::AskQuestion { question })
-            .await
-        {
-            // Reset flag on failure so another agent can retry.
-            self.question_pending.store(false, Ordering::SeqCst);
-            return Err(anyhow::anyhow!("failed to ask question: {}", e));
-        }
-
-        Ok(ToolResult::text("Question asked"))
+        enqueue_question(&self.actor, &self.question_pending, question).await
     }
 }
 
@@ -161,15 +174,6 @@ impl Tool for AskUserMultipleChoiceTool {
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
-        // Atomically check-and-set to avoid TOCTOU race between agents.
-        if self
-            .question_pending
-            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-            .is_err()
-        {
-            return Ok(ToolResult::text("Question already pending, skipping"));
-        }
-
         let question = UserQuestion::MultipleChoice {
             question_id: Ulid::new(),
             question: question_text,
@@ -177,17 +181,7 @@ impl Tool for AskUserMultipleChoiceTool {
             allow_multi,
         };
 
-        if let Err(e) = self
-            .actor
-            .send_command(Command::AskQuestion { question })
-            .await
-        {
-            // Reset flag on failure so another agent can retry.
-            self.question_pending.store(false, Ordering::SeqCst);
-            return Err(anyhow::anyhow!("failed to ask question: {}", e));
-        }
-
-        Ok(ToolResult::text("Question asked"))
+        enqueue_question(&self.actor, &self.question_pending, question).await
     }
 }
 
@@ -254,15 +248,6 @@ impl Tool for AskUserFreeformTool {
             .and_then(|v| v.as_str())
             .map(String::from);
 
-        // Atomically check-and-set to avoid TOCTOU race between agents.
-        if self
-            .question_pending
-            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
-            .is_err()
-        {
-            return Ok(ToolResult::text("Question already pending, skipping"));
-        }
-
         let question = UserQuestion::Freeform {
             question_id: Ulid::new(),
             question: question_text,
@@ -270,17 +255,7 @@ impl Tool for AskUserFreeformTool {
             validation_hint,
         };
 
-        if let Err(e) = self
-            .actor
-            .send_command(Command::AskQuestion { question })
-            .await
-        {
-            // Reset flag on failure so another agent can retry.
-            self.question_pending.store(false, Ordering::SeqCst);
-            return Err(anyhow::anyhow!("failed to ask question: {}", e));
-        }
-
-        Ok(ToolResult::text("Question asked"))
+        enqueue_question(&self.actor, &self.question_pending, question).await
     }
 }
 
@@ -347,20 +322,35 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn boolean_returns_already_pending_when_flag_set() {
+    async fn second_question_queues_behind_the_first() {
         let (_id, handle) = make_test_actor();
-        let pending = Arc::new(AtomicBool::new(true));
-        let tool = AskUserBooleanTool {
-            actor: Arc::new(handle),
-            question_pending: pending,
-            agent_id: "test".to_string(),
+        let handle = Arc::new(handle);
+        let pending = make_pending_flag();
+        let boolean = AskUserBooleanTool {
+            actor: Arc::clone(&handle),
+            question_pending: pending.clone(),
+            agent_id: "manager".to_string(),
+        };
+        let freeform = AskUserFreeformTool {
+            actor: Arc::clone(&handle),
+            question_pending: pending.clone(),
+            agent_id: "planner".to_string(),
         };
 
-        let result = tool
-            .execute(json!({ "question": "Another?" }))
+        boolean
+            .execute(json!({ "question": "First?" }))
+            .await
+            .unwrap();
+        let result = freeform
+            .execute(json!({ "question": "Second?" }))
             .await
             .unwrap();
-        assert_eq!(result.content, "Question already pending, skipping");
+        assert_eq!(
+            result.content,
+            "Question queued behind 1 earlier question(s)"
+        );
+        assert!(pending.load(Ordering::SeqCst));
+        assert_eq!(handle.read_state().await.pending_questions.len(), 2);
     }
 
     #[tokio::test]
@@ -378,8 +368,8 @@ mod tests {
             .unwrap();
 
         let state = handle.read_state().await;
-        assert!(state.pending_question.is_some());
-        match &state.pending_question {
+        assert!(state.pending_question().is_some());
+        match state.pending_question() {
             Some(UserQuestion::Boolean {
                 question, default, ..
             }) => {
@@ -437,7 +427,7 @@ mod tests {
         assert_eq!(result.content, "Question asked");
 
         let state = handle.read_state().await;
-        match &state.pending_question {
+        match state.pending_question() {
             Some(UserQuestion::MultipleChoice {
                 question,
                 choices,
@@ -453,20 +443,24 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn multiple_choice_returns_already_pending() {
+    async fn multiple_choice_queues_when_question_pending() {
         let (_id, handle) = make_test_actor();
-        let pending = Arc::new(AtomicBool::new(true));
+        let pending = make_pending_flag();
         let tool = AskUserMultipleChoiceTool {
-            actor: Arc::new(handle),
-            question_pending: pending,
+            actor: Arc::new(handle.clone()),
+            question_pending: pending.clone(),
             agent_id: "test".to_string(),
         };
 
-        let result = tool
-            .execute(json!({ "question": "Pick", "choices": ["a", "b"] }))
-            .await
-            .unwrap();
-        assert_eq!(result.content, "Question already pending, skipping");
+        let params = json!({ "question": "Pick", "choices": ["a", "b"] });
+        tool.execute(params.clone()).await.unwrap();
+        let result = tool.execute(params).await.unwrap();
+        assert_eq!(
+            result.content,
+            "Question queued behind 1 earlier question(s)"
+        );
+        assert_eq!(handle.read_state().await.pending_questions.len(), 2);
+        assert!(pending.load(Ordering::SeqCst));
     }
 
     // --- ask_user_freeform tests ---
@@ -516,7 +510,7 @@ mod tests {
         assert_eq!(result.content, "Question asked");
 
         let state = handle.read_state().await;
-        match &state.pending_question {
+        match state.pending_question() {
             Some(UserQuestion::Freeform {
                 question,
                 placeholder,
@@ -532,16 +526,18 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn freeform_returns_already_pending() {
+    async fn freeform_asks_when_flag_is_stale() {
+        // A flag left set with nothing in the queue must not block the question.
         let (_id, handle) = make_test_actor();
         let pending = Arc::new(AtomicBool::new(true));
         let tool = AskUserFreeformTool {
-            actor: Arc::new(handle),
+            actor: Arc::new(handle.clone()),
             question_pending: pending,
             agent_id: "test".to_string(),
         };
 
         let result = tool.execute(json!({ "question": "What?" })).await.unwrap();
-        assert_eq!(result.content, "Question already pending, skipping");
+        assert_eq!(result.content, "Question asked");
+        assert!(handle.read_state().await.pending_question().is_some());
     }
 }
@@ -15,6 +15,8 @@ use barnstormer_core::command::Command;
 pub struct EmitNarrationTool {
     pub(crate) actor: Arc<SpecActorHandle>,
     pub(crate) agent_id: String,
+    /// Display name recorded on the message in place of the role label.
+    pub(crate) persona_name: Option<String>,
 }
 
 #[async_trait]
@@ -51,6 +53,7 @@ impl Tool for EmitNarrationTool {
             .send_command(Command::AppendTranscript {
                 sender: self.agent_id.clone(),
                 content: message,
+                display_name: self.persona_name.clone(),
             })
             .await
             .map_err(|e| anyhow::anyhow!("failed to append transcript: {}", e))?;
@@ -78,6 +81,7 @@ mod tests {
         let tool = EmitNarrationTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
         assert_eq!(tool.name(), "emit_narration");
     }
@@ -88,6 +92,7 @@ mod tests {
         let tool = EmitNarrationTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
         assert!(tool.description().contains("narration message"));
     }
@@ -98,6 +103,7 @@ mod tests {
         let tool = EmitNarrationTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
         let schema = tool.schema();
         assert!(schema.is_object());
@@ -110,6 +116,7 @@ mod tests {
         let tool = EmitNarrationTool {
             actor: Arc::new(handle.clone()),
             agent_id: "narrator".to_string(),
+            persona_name: None,
         };
 
         let params = json!({ "message": "This is a narration." });
@@ -122,6 +129,25 @@ mod tests {
         assert_eq!(state.transcript.len(), 1);
         assert_eq!(state.transcript[0].sender, "narrator");
         assert_eq!(state.transcript[0].content, "This is a narration.");
+        assert!(state.transcript[0].display_name.is_none());
+    }
+
+    #[tokio::test]
+    async fn execute_records_persona_name() {
+        let (_id, handle) = make_test_actor();
+        let tool = EmitNarrationTool {
+            actor: Arc::new(handle.clone()),
+            agent_id: "planner-01HTEST".to_string(),
+            persona_name: Some("Ada".to_string()),
+        };
+
+        tool.execute(json!({ "message": "Sketching the plan." }))
+            .await
+            .unwrap();
+
+        let state = handle.read_state().await;
+        assert_eq!(state.transcript[0].sender, "planner-01HTEST");
+        assert_eq!(state.transcript[0].display_name.as_deref(), Some("Ada"));
     }
 
     #[tokio::test]
@@ -130,6 +156,7 @@ mod tests {
         let tool = EmitNarrationTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
 
         let result = tool.execute(json!({})).await;
@@ -0,0 +1,420 @@
+// ABOUTME: fetch_url mux tool — lets agents GET a page from an allowlisted host so
+// ABOUTME: external state (a repo, an issue tracker) can inform their reasoning.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mux::tool::{Tool, ToolResult};
+use reqwest::Url;
+use reqwest::redirect;
+use serde_json::json;
+
+/// Default cap on how much of a response body is handed back to the agent.
+const DEFAULT_MAX_BYTES: usize = 256 * 1024;
+
+/// Default time budget for the whole request (connect + headers + body).
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Which hosts the fetch_url tool may reach, and how much it may read.
+///
+/// Hosts are matched exactly (case-insensitive); there is no wildcard or
+/// subdomain matching. Even an allowlisted host is refused if it resolves
+/// to a loopback, private, link-local, or otherwise non-public address.
+#[derive(Debug, Clone)]
+pub struct FetchPolicy {
+    pub allowed_hosts: Vec<String>,
+    pub max_bytes: usize,
+    pub timeout: Duration,
+    /// Lets tests talk to a listener on 127.0.0.1. Never set outside tests.
+    pub(crate) allow_loopback: bool,
+}
+
+impl FetchPolicy {
+    /// Build a policy for the given hosts with the default size and timeout caps.
+    pub fn new<I, S>(allowed_hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed_hosts: allowed_hosts
+                .into_iter()
+                .map(|h| h.into().trim().to_ascii_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect(),
+            max_bytes: DEFAULT_MAX_BYTES,
+            timeout: DEFAULT_TIMEOUT,
+            allow_loopback: false,
+        }
+    }
+
+    /// The policy for a spec's `fetch_hosts` allowlist. Returns None when it
+    /// lists no hosts, which keeps the tool out of the registry entirely.
+    pub fn for_hosts(hosts: &[String]) -> Option<Self> {
+        let policy = Self::new(hosts.iter().map(String::as_str));
+        if policy.allowed_hosts.is_empty() {
+            None
+        } else {
+            Some(policy)
+        }
+    }
+
+    fn host_allowed(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.allowed_hosts.contains(&host)
+    }
+
+    fn address_allowed(&self, ip: IpAddr) -> bool {
+        is_public_ip(ip) || (self.allow_loopback && ip.is_loopback())
+    }
+}
+
+/// Returns true only for globally routable unicast addresses. Loopback,
+/// RFC 1918, CGNAT, link-local, multicast, documentation, benchmarking,
+/// reserved, and IPv6 unique-local ranges are all rejected, as are IPv6
+/// addresses that embed an IPv4 address from any of those (IPv4-mapped,
+/// NAT64, and 6to4).
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_public_ipv4(v4),
+        IpAddr::V6(v6) => match embedded_ipv4(v6) {
+            Some(v4) => is_public_ipv4(v4),
+            None => is_public_ipv6(v6),
+        },
+    }
+}
+
+/// The IPv4 address an IPv6 address carries, for the forms a gateway will
+/// translate back to IPv4: IPv4-mapped (::ffff:0:0/96), NAT64 well-known
+/// prefix (64:ff9b::/96), and 6to4 (2002::/16).
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return Some(v4);
+    }
+    let s = ip.segments();
+    let from = |hi: u16, lo: u16| Ipv4Addr::from((u32::from(hi) << 16) | u32::from(lo));
+    if s[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        Some(from(s[6], s[7]))
+    } else if s[0] == 0x2002 {
+        Some(from(s[1], s[2]))
+    } else {
+        None
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (b == 18 || b == 19))
+        || a >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || (first & 0xfe00) == 0xfc00
+        || (first & 0xffc0) == 0xfe80
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+}
+
+#[derive(Clone)]
+pub struct FetchUrlTool {
+    pub(crate) policy: Arc<FetchPolicy>,
+}
+
+impl FetchUrlTool {
+    /// Validate the URL against the policy and resolve it to addresses that
+    /// are safe to connect to. The resolved addresses are pinned on the
+    /// client so a second DNS lookup cannot swap in a private address.
+    async fn check(&self, raw: &str) -> Result<(Url, String, Vec<SocketAddr>), String> {
+        let url = Url::parse(raw).map_err(|e| format!("invalid url: {e}"))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(format!("scheme '{}' is not allowed", url.scheme()));
+        }
+        if !url.username().is_empty() || url.password().is_some() {
+            return Err("urls with credentials are not allowed".to_string());
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| "url has no host".to_string())?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        if !self.policy.host_allowed(&host) {
+            return Err(format!("host '{host}' is not on the fetch allowlist"));
+        }
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| "url has no port".to_string())?;
+
+        let addrs: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => tokio::net::lookup_host((host.as_str(), port))
+                .await
+                .map_err(|e| format!("failed to resolve '{host}': {e}"))?
+                .collect(),
+        };
+        if addrs.is_empty() {
+            return Err(format!("'{host}' did not resolve to any address"));
+        }
+        if let Some(bad) = addrs.iter().find(|a| !self.policy.address_allowed(a.ip())) {
+            return Err(format!(
+                "host '{host}' resolves to non-public address {}",
+                bad.ip()
+            ));
+        }
+        Ok((url, host, addrs))
+    }
+}
+
+#[async_trait]
+impl Tool for FetchUrlTool {
+    fn name(&self) -> &str {
+        "fetch_url"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch a web page or API response with an HTTP GET and return its text. Only hosts \
+         on this spec's allowlist can be reached; redirects are not followed and long \
+         responses are truncated. Use it to check external state the spec depends on, such \
+         as a repository README or an issue tracker entry."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": format!(
+                        "Absolute http(s) URL. Allowed hosts: {}",
+                        self.policy.allowed_hosts.join(", ")
+                    )
+                }
+            },
+            "required": ["url"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> Result<ToolResult, anyhow::Error> {
+        let raw = params
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("missing 'url' parameter"))?;
+
+        let (url, host, addrs) = match self.check(raw).await {
+            Ok(checked) => checked,
+            Err(reason) => {
+                tracing::warn!(url = raw, reason = %reason, "fetch_url refused");
+                return Ok(ToolResult::error(reason));
+            }
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(self.policy.timeout)
+            .redirect(redirect::Policy::none())
+            .no_proxy()
+            .resolve_to_addrs(&host, &addrs)
+            .build()?;
+
+        let mut response = match client.get(url).send().await {
+            Ok(r) => r,
+            Err(e) => return Ok(ToolResult::error(format!("request failed: {e}"))),
+        };
+        let status = response.status();
+        if status.is_redirection() {
+            return Ok(ToolResult::error(format!(
+                "HTTP {status}: redirects are not followed"
+            )));
+        }
+        if !status.is_success() {
+            return Ok(ToolResult::error(format!("HTTP {status}")));
+        }
+
+        let max = self.policy.max_bytes;
+        let mut body: Vec<u8> = Vec::new();
+        let mut truncated = false;
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    let room = max - body.len();
+                    if chunk.len() > room {
+                        body.extend_from_slice(&chunk[..room]);
+                        truncated = true;
+                        break;
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(e) => return Ok(ToolResult::error(format!("failed reading body: {e}"))),
+            }
+        }
+
+        let mut text = String::from_utf8_lossy(&body).into_owned();
+        if truncated {
+            text.push_str(&format!("\n[truncated at {max} bytes]"));
+        }
+        Ok(ToolResult::text(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve a single canned 200 response on 127.0.0.1 and return its address.
+    async fn serve_once(body: &'static str) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut sock, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = sock.read(&mut buf).await;
+            let resp = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            sock.write_all(resp.as_bytes()).await.unwrap();
+        });
+        addr
+    }
+
+    fn tool(policy: FetchPolicy) -> FetchUrlTool {
+        FetchUrlTool {
+            policy: Arc::new(policy),
+        }
+    }
+
+    #[test]
+    fn public_ip_classification() {
+        for ip in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip} should be public");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+            "64:ff9b::a9fe:a9fe",
+            "64:ff9b::7f00:1",
+            "2002:c0a8:0101::1",
+            "2002:0a00:0001::",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip} should be private");
+        }
+        // The same prefixes wrapping a public address stay reachable.
+        for ip in ["64:ff9b::5db8:d822", "2002:5db8:d822::1"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip} should be public");
+        }
+    }
+
+    #[test]
+    fn empty_spec_allowlist_disables_the_tool() {
+        assert!(FetchPolicy::for_hosts(&[]).is_none());
+        assert!(FetchPolicy::for_hosts(&[" ".to_string()]).is_none());
+        let policy = FetchPolicy::for_hosts(&["docs.rs".to_string()]).unwrap();
+        assert_eq!(policy.allowed_hosts, vec!["docs.rs"]);
+    }
+
+    #[test]
+    fn policy_normalizes_and_drops_blank_hosts() {
+        let policy = FetchPolicy::new(" GitHub.com ,, docs.rs".split(','));
+        assert_eq!(policy.allowed_hosts, vec!["github.com", "docs.rs"]);
+        assert!(policy.host_allowed("GITHUB.COM"));
+        assert!(!policy.host_allowed("api.github.com"));
+    }
+
+    #[tokio::test]
+    async fn allowlisted_host_is_fetched() {
+        let addr = serve_once("issue #42 is closed").await;
+        let mut policy = FetchPolicy::new(["127.0.0.1"]);
+        policy.allow_loopback = true;
+
+        let result = tool(policy)
+            .execute(json!({ "url": format!("http://{addr}/issues/42") }))
+            .await
+            .unwrap();
+        assert!(!result.is_error, "unexpected error: {}", result.content);
+        assert_eq!(result.content, "issue #42 is closed");
+    }
+
+    #[tokio::test]
+    async fn response_is_truncated_at_max_bytes() {
+        let addr = serve_once("0123456789abcdef").await;
+        let mut policy = FetchPolicy::new(["127.0.0.1"]);
+        policy.allow_loopback = true;
+        policy.max_bytes = 10;
+
+        let result = tool(policy)
+            .execute(json!({ "url": format!("http://{addr}/") }))
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+        assert!(result.content.starts_with("0123456789\n"));
+        assert!(result.content.contains("[truncated at 10 bytes]"));
+    }
+
+    #[tokio::test]
+    async fn host_not_on_allowlist_is_refused() {
+        let result = tool(FetchPolicy::new(["docs.rs"]))
+            .execute(json!({ "url": "https://example.com/" }))
+            .await
+            .unwrap();
+        assert!(result.is_error);
+        assert!(result.content.contains("not on the fetch allowlist"));
+    }
+
+    #[tokio::test]
+    async fn private_ip_is_refused_even_when_allowlisted() {
+        let addr = serve_once("secret").await;
+        let t = tool(FetchPolicy::new(["127.0.0.1", "169.254.169.254"]));
+
+        for url in [
+            format!("http://{addr}/"),
+            "http://169.254.169.254/latest/meta-data".to_string(),
+        ] {
+            let result = t.execute(json!({ "url": url })).await.unwrap();
+            assert!(result.is_error, "{url} should be refused");
+            assert!(result.content.contains("non-public address"));
+        }
+    }
+
+    #[tokio::test]
+    async fn non_http_scheme_is_refused() {
+        let result = tool(FetchPolicy::new(["docs.rs"]))
+            .execute(json!({ "url": "file:///etc/passwd" }))
+            .await
+            .unwrap();
+        assert!(result.is_error);
+        assert!(result.content.contains("scheme 'file'"));
+    }
+
+    #[tokio::test]
+    async fn missing_url_param_is_an_error() {
+        let result = tool(FetchPolicy::new(["docs.rs"])).execute(json!({})).await;
+        assert!(result.is_err());
+    }
+}
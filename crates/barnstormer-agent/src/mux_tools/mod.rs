@@ -4,7 +4,9 @@
 mod ask_user;
 mod emit_diff_summary;
 mod emit_narration;
+mod fetch_url;
 mod propose_transition;
+mod read_card_history;
 mod read_state;
 mod retrieve_context;
 mod write_commands;
@@ -12,7 +14,9 @@ mod write_commands;
 pub use ask_user::{AskUserBooleanTool, AskUserFreeformTool, AskUserMultipleChoiceTool};
 pub use emit_diff_summary::EmitDiffSummaryTool;
 pub use emit_narration::EmitNarrationTool;
+pub use fetch_url::{FetchPolicy, FetchUrlTool, is_public_ip};
 pub use propose_transition::ProposeTransitionTool;
+pub use read_card_history::ReadCardHistoryTool;
 pub use read_state::ReadStateTool;
 pub use retrieve_context::RetrieveContextTool;
 pub use write_commands::WriteCommandsTool;
@@ -32,14 +36,18 @@ use crate::AttachmentSummarizer;
 ///
 /// The returned registry contains: read_state, write_commands, emit_narration,
 /// emit_diff_summary, ask_user_boolean, ask_user_multiple_choice, ask_user_freeform,
-/// propose_transition, retrieve_context.
+/// propose_transition, retrieve_context, read_card_history. When `fetch_policy` is set (the spec
+/// has a `fetch_hosts` allowlist), fetch_url is registered as well, limited to those hosts.
+#[allow(clippy::too_many_arguments)]
 pub async fn build_registry(
     actor: Arc<SpecActorHandle>,
     question_pending: Arc<AtomicBool>,
     pending_transition_question: Arc<Mutex<Option<Ulid>>>,
     agent_id: String,
+    persona_name: Option<String>,
     home: PathBuf,
     summarizer: Arc<dyn AttachmentSummarizer>,
+    fetch_policy: Option<Arc<FetchPolicy>>,
 ) -> Registry {
     let registry = Registry::new();
 
@@ -53,6 +61,7 @@ pub async fn build_registry(
         .register(WriteCommandsTool {
             actor: Arc::clone(&actor),
             agent_id: agent_id.clone(),
+            persona_name: persona_name.clone(),
         })
         .await;
 
@@ -60,6 +69,7 @@ pub async fn build_registry(
         .register(EmitNarrationTool {
             actor: Arc::clone(&actor),
             agent_id: agent_id.clone(),
+            persona_name,
         })
         .await;
 
@@ -105,11 +115,22 @@ pub async fn build_registry(
     registry
         .register(retrieve_context::RetrieveContextTool {
             actor: Arc::clone(&actor),
-            home,
+            home: home.clone(),
             summarizer,
         })
         .await;
 
+    registry
+        .register(ReadCardHistoryTool {
+            actor: Arc::clone(&actor),
+            home,
+        })
+        .await;
+
+    if let Some(policy) = fetch_policy {
+        registry.register(FetchUrlTool { policy }).await;
+    }
+
     registry
 }
 
@@ -146,19 +167,21 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn build_registry_registers_all_9_tools() {
+    async fn build_registry_registers_all_10_tools() {
         let (_id, handle) = make_test_actor();
         let registry = build_registry(
             Arc::new(handle),
             Arc::new(AtomicBool::new(false)),
             Arc::new(Mutex::new(None)),
             "test-agent".to_string(),
+            None,
             PathBuf::from("/tmp/barnstormer-test"),
             stub_summarizer(),
+            None,
         )
         .await;
 
-        assert_eq!(registry.count().await, 9);
+        assert_eq!(registry.count().await, 10);
 
         let names = registry.list().await;
         assert!(names.contains(&"read_state".to_string()));
@@ -170,6 +193,27 @@ mod tests {
         assert!(names.contains(&"ask_user_freeform".to_string()));
         assert!(names.contains(&"propose_transition".to_string()));
         assert!(names.contains(&"retrieve_context".to_string()));
+        assert!(names.contains(&"read_card_history".to_string()));
+        assert!(!names.contains(&"fetch_url".to_string()));
+    }
+
+    #[tokio::test]
+    async fn build_registry_adds_fetch_url_when_policy_is_set() {
+        let (_id, handle) = make_test_actor();
+        let registry = build_registry(
+            Arc::new(handle),
+            Arc::new(AtomicBool::new(false)),
+            Arc::new(Mutex::new(None)),
+            "test-agent".to_string(),
+            None,
+            PathBuf::from("/tmp/barnstormer-test"),
+            stub_summarizer(),
+            Some(Arc::new(FetchPolicy::new(["docs.rs"]))),
+        )
+        .await;
+
+        assert_eq!(registry.count().await, 11);
+        assert!(registry.get("fetch_url").await.is_some());
     }
 
     #[tokio::test]
@@ -180,8 +224,10 @@ mod tests {
             Arc::new(AtomicBool::new(false)),
             Arc::new(Mutex::new(None)),
             "test-agent".to_string(),
+            None,
             PathBuf::from("/tmp/barnstormer-test"),
             stub_summarizer(),
+            None,
         )
         .await;
 
@@ -195,6 +241,7 @@ mod tests {
             "ask_user_freeform",
             "propose_transition",
             "retrieve_context",
+            "read_card_history",
         ] {
             let tool = registry.get(name).await;
             assert!(tool.is_some(), "tool '{}' should be in registry", name);
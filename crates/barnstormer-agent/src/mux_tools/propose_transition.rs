@@ -198,7 +198,7 @@ mod tests {
         assert!(stored.is_some());
 
         let state = handle.read_state().await;
-        assert!(state.pending_question.is_some());
+        assert!(state.pending_question().is_some());
     }
 
     #[tokio::test]
@@ -343,7 +343,7 @@ mod tests {
             .unwrap();
 
         let state = handle.read_state().await;
-        if let Some(UserQuestion::Boolean { question, .. }) = &state.pending_question {
+        if let Some(UserQuestion::Boolean { question, .. }) = state.pending_question() {
             assert!(
                 question.contains("organizing the spec"),
                 "brainstorming question should mention organizing: {}",
@@ -385,7 +385,7 @@ mod tests {
             .unwrap();
 
         let state = handle.read_state().await;
-        if let Some(UserQuestion::Boolean { question, .. }) = &state.pending_question {
+        if let Some(UserQuestion::Boolean { question, .. }) = state.pending_question() {
             assert!(
                 question.contains("Ready to finalize"),
                 "refining question should mention finalizing: {}",
@@ -0,0 +1,322 @@
+// ABOUTME: read_card_history mux tool — lists every recorded change to one card,
+// ABOUTME: oldest first, by replaying the spec's event log from disk.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use mux::tool::{Tool, ToolResult};
+use serde_json::json;
+use ulid::Ulid;
+
+use barnstormer_core::actor::SpecActorHandle;
+use barnstormer_core::card::CardReviewResolution;
+use barnstormer_core::event::{Event, EventPayload};
+
+/// Tool that reconstructs the change history of a single card from the
+/// spec's `events.jsonl`, so agents can see what a human changed on purpose.
+#[derive(Clone)]
+pub struct ReadCardHistoryTool {
+    pub(crate) actor: Arc<SpecActorHandle>,
+    pub(crate) home: PathBuf,
+}
+
+/// Describe how `payload` changed `card_id`, or None if it did not touch it.
+fn describe_card_change(payload: &EventPayload, card_id: Ulid) -> Option<String> {
+    match payload {
+        EventPayload::CardCreated { card } if card.card_id == card_id => Some(format!(
+            "created by {} as {} \"{}\" in lane {}",
+            card.created_by, card.card_type, card.title, card.lane
+        )),
+        EventPayload::CardUpdated {
+            card_id: id,
+            title,
+            body,
+            card_type,
+            refs,
+            tags,
+        } if *id == card_id => {
+            let mut changes = Vec::new();
+            if let Some(title) = title {
+                changes.push(format!("title -> \"{}\"", title));
+            }
+            match body {
+                Some(Some(body)) => changes.push(format!("body -> \"{}\"", body)),
+                Some(None) => changes.push("body cleared".to_string()),
+                None => {}
+            }
+            if let Some(card_type) = card_type {
+                changes.push(format!("type -> {}", card_type));
+            }
+            if let Some(refs) = refs {
+                changes.push(format!("refs -> [{}]", refs.join(", ")));
+            }
+            if let Some(tags) = tags {
+                changes.push(format!("tags -> [{}]", tags.join(", ")));
+            }
+            Some(format!("updated: {}", changes.join("; ")))
+        }
+        EventPayload::CardMoved {
+            card_id: id,
+            lane,
+            order,
+        } if *id == card_id => Some(format!("moved to lane {} (order {})", lane, order)),
+        EventPayload::CardsReordered { positions } => positions
+            .iter()
+            .find(|p| p.card_id == card_id)
+            .map(|p| format!("moved to lane {} (order {})", p.lane, p.order)),
+        EventPayload::CardDoneSet { card_id: id, done } if *id == card_id => Some(if *done {
+            "marked done".to_string()
+        } else {
+            "marked not done".to_string()
+        }),
+        EventPayload::CardArchivedSet {
+            card_id: id,
+            archived,
+        } if *id == card_id => Some(if *archived {
+            "archived".to_string()
+        } else {
+            "unarchived".to_string()
+        }),
+        EventPayload::CardReviewSet {
+            card_id: id,
+            review,
+            resolution,
+        } if *id == card_id => Some(match (review, resolution) {
+            (Some(review), _) => format!(
+                "flagged for human review by {}: {}",
+                review.requested_by, review.reason
+            ),
+            (None, Some(CardReviewResolution::Approved)) => "review approved".to_string(),
+            (None, Some(CardReviewResolution::Dismissed)) => "review dismissed".to_string(),
+            (None, None) => "review cleared".to_string(),
+        }),
+        EventPayload::CardAttachmentAdded {
+            card_id: id,
+            attachment,
+            ..
+        } if *id == card_id => Some(format!(
+            "attached \"{}\" ({})",
+            attachment.label, attachment.target
+        )),
+        EventPayload::CardAttachmentRemoved {
+            card_id: id,
+            attachment_id,
+        } if *id == card_id => Some(format!("removed attachment {}", attachment_id)),
+        EventPayload::CardDeleted { card_id: id } if *id == card_id => Some("deleted".to_string()),
+        EventPayload::UndoApplied {
+            target_event_id,
+            inverse_events,
+        } => {
+            let undone: Vec<String> = inverse_events
+                .iter()
+                .filter_map(|inverse| describe_card_change(inverse, card_id))
+                .collect();
+            if undone.is_empty() {
+                None
+            } else {
+                Some(format!(
+                    "undo of event #{} -> {}",
+                    target_event_id,
+                    undone.join(", ")
+                ))
+            }
+        }
+        EventPayload::RedoApplied { payloads } => {
+            let redone: Vec<String> = payloads
+                .iter()
+                .filter_map(|payload| describe_card_change(payload, card_id))
+                .collect();
+            if redone.is_empty() {
+                None
+            } else {
+                Some(format!("redo -> {}", redone.join(", ")))
+            }
+        }
+        _ => None,
+    }
+}
+
+/// One line per event that touched `card_id`, in log order.
+fn card_history(events: &[Event], card_id: Ulid) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| {
+            describe_card_change(&event.payload, card_id).map(|change| {
+                format!(
+                    "#{} {} {}",
+                    event.event_id,
+                    event.timestamp.to_rfc3339(),
+                    change
+                )
+            })
+        })
+        .collect()
+}
+
+#[async_trait]
+impl Tool for ReadCardHistoryTool {
+    fn name(&self) -> &str {
+        "read_card_history"
+    }
+
+    fn description(&self) -> &str {
+        "Read the chronological list of changes to one card (creation, edits, moves, done \
+         toggles, deletion, undo). Check this before editing a card someone else has changed \
+         so you do not revert a deliberate edit."
+    }
+
+    fn schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "card_id": {
+                    "type": "string",
+                    "description": "The ULID of the card whose history to read"
+                }
+            },
+            "required": ["card_id"]
+        })
+    }
+
+    async fn execute(&self, params: serde_json::Value) -> Result<ToolResult, anyhow::Error> {
+        let id_str = params
+            .get("card_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("missing 'card_id' parameter"))?;
+        let card_id: Ulid = id_str
+            .parse()
+            .map_err(|e| anyhow::anyhow!("bad card id: {e}"))?;
+
+        let log_path = self
+            .home
+            .join("specs")
+            .join(self.actor.spec_id.to_string())
+            .join("events.jsonl");
+        let raw = match tokio::fs::read_to_string(&log_path).await {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(anyhow::anyhow!("failed to read event log: {e}")),
+        };
+        // A torn trailing line from an in-flight append is skipped rather
+        // than failing the whole lookup.
+        let events: Vec<Event> = raw
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        let history = card_history(&events, card_id);
+        if history.is_empty() {
+            return Ok(ToolResult::text(format!(
+                "No recorded history for card {}",
+                card_id
+            )));
+        }
+        Ok(ToolResult::text(format!(
+            "History for card {} ({} changes, oldest first):\n{}",
+            card_id,
+            history.len(),
+            history.join("\n")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barnstormer_core::actor;
+    use barnstormer_core::command::Command;
+    use barnstormer_core::state::SpecState;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn returns_changes_for_created_then_updated_card() {
+        let tmp = TempDir::new().unwrap();
+        let spec_id = Ulid::new();
+        let handle = actor::spawn(spec_id, SpecState::new());
+
+        let mut events = handle
+            .send_command(Command::CreateSpec {
+                title: "t".to_string(),
+                one_liner: "o".to_string(),
+                goal: "g".to_string(),
+            })
+            .await
+            .unwrap();
+        let created = handle
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "Original".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        let card_id = match &created[0].payload {
+            EventPayload::CardCreated { card } => card.card_id,
+            other => panic!("expected CardCreated, got {:?}", other),
+        };
+        events.extend(created);
+        events.extend(
+            handle
+                .send_command(Command::UpdateCard {
+                    card_id,
+                    title: Some("Renamed by human".to_string()),
+                    body: None,
+                    card_type: None,
+                    refs: None,
+                    tags: None,
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap(),
+        );
+
+        let spec_dir = tmp.path().join("specs").join(spec_id.to_string());
+        std::fs::create_dir_all(&spec_dir).unwrap();
+        let mut log = std::fs::File::create(spec_dir.join("events.jsonl")).unwrap();
+        for event in &events {
+            writeln!(log, "{}", serde_json::to_string(event).unwrap()).unwrap();
+        }
+
+        let tool = ReadCardHistoryTool {
+            actor: Arc::new(handle),
+            home: tmp.path().to_path_buf(),
+        };
+        let result = tool
+            .execute(json!({ "card_id": card_id.to_string() }))
+            .await
+            .unwrap();
+        assert!(!result.is_error);
+
+        let lines: Vec<&str> = result.content.lines().collect();
+        assert_eq!(
+            lines.len(),
+            3,
+            "header plus two changes: {}",
+            result.content
+        );
+        assert!(lines[0].contains("2 changes"));
+        assert!(lines[1].contains("created by human as idea \"Original\""));
+        assert!(lines[2].contains("updated: title -> \"Renamed by human\""));
+    }
+
+    #[tokio::test]
+    async fn unknown_card_has_no_history() {
+        let tmp = TempDir::new().unwrap();
+        let handle = actor::spawn(Ulid::new(), SpecState::new());
+        let tool = ReadCardHistoryTool {
+            actor: Arc::new(handle),
+            home: tmp.path().to_path_buf(),
+        };
+        let result = tool
+            .execute(json!({ "card_id": Ulid::new().to_string() }))
+            .await
+            .unwrap();
+        assert!(result.content.starts_with("No recorded history"));
+    }
+}
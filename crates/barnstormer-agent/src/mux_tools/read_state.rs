@@ -48,12 +48,12 @@ impl Tool for ReadStateTool {
     async fn execute(&self, _params: serde_json::Value) -> Result<ToolResult, anyhow::Error> {
         // Clone the data we need so we release the RwLockReadGuard quickly
         // instead of holding it across the entire formatting operation.
-        let (core, cards, pending_question, transcript_len, recent_transcript, lanes) = {
+        let (core, cards, pending_questions, transcript_len, recent_transcript, lanes) = {
             let state = self.actor.read_state().await;
             (
                 state.core.clone(),
                 state.cards.values().cloned().collect::<Vec<_>>(),
-                state.pending_question.clone(),
+                state.pending_questions.clone(),
                 state.transcript.len(),
                 state
                     .transcript
@@ -114,14 +114,17 @@ impl Tool for ReadStateTool {
             ));
         }
 
-        // Pending question
+        // Pending questions, in the order the user will see them
         lines.push(String::new());
-        match &pending_question {
-            Some(q) => {
-                lines.push(format!("## Pending Question: {:?}", q));
-            }
-            None => {
-                lines.push("## No pending question".to_string());
+        if pending_questions.is_empty() {
+            lines.push("## No pending question".to_string());
+        } else {
+            lines.push(format!(
+                "## Pending Questions ({} queued)",
+                pending_questions.len()
+            ));
+            for q in &pending_questions {
+                lines.push(format!("- {:?}", q));
             }
         }
 
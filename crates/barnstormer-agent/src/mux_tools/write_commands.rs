@@ -11,11 +11,39 @@ use tracing::warn;
 use barnstormer_core::actor::SpecActorHandle;
 use barnstormer_core::command::Command;
 
+/// Self-reported confidence below which every spec-mutating command in the
+/// batch is queued for human review instead of applied.
+const REVIEW_CONFIDENCE_THRESHOLD: f64 = 0.5;
+
+/// Decide whether a command should be parked as a proposal. Deleting a card
+/// always needs a human; other mutations only when the agent is unsure.
+/// Transcript messages are never gated.
+fn review_reason(cmd: &Command, confidence: Option<f64>) -> Option<String> {
+    match cmd {
+        // Asking for a human's eyes is itself the escalation path.
+        Command::AppendTranscript { .. } | Command::RequestCardReview { .. } => None,
+        Command::DeleteCard { .. } => Some("card deletion needs human approval".to_string()),
+        Command::ResolveCardReview { .. } => {
+            Some("only a human can close a card review".to_string())
+        }
+        _ => match confidence {
+            Some(c) if c < REVIEW_CONFIDENCE_THRESHOLD => Some(format!(
+                "agent confidence {:.2} is below {:.2}",
+                c, REVIEW_CONFIDENCE_THRESHOLD
+            )),
+            _ => None,
+        },
+    }
+}
+
 /// Tool that accepts an array of Command objects and sends each to the spec actor.
+/// Risky or low-confidence commands are queued as pending proposals instead.
 #[derive(Clone)]
 pub struct WriteCommandsTool {
     pub(crate) actor: Arc<SpecActorHandle>,
     pub(crate) agent_id: String,
+    /// Display name stamped on transcript messages this agent appends.
+    pub(crate) persona_name: Option<String>,
 }
 
 #[async_trait]
@@ -25,7 +53,7 @@ impl Tool for WriteCommandsTool {
     }
 
     fn description(&self) -> &str {
-        "Submit one or more commands to modify the spec. Commands can create/update/move/delete cards, update spec metadata, or append to the transcript."
+        "Submit one or more commands to modify the spec. Commands can create/update/move/delete cards, update spec metadata, or append to the transcript. Card deletions, and every change when you report low confidence, are queued for human approval rather than applied immediately. Marking the spec complete or archiving it always waits at a review gate until the human approves."
     }
 
     fn schema(&self) -> serde_json::Value {
@@ -37,16 +65,20 @@ impl Tool for WriteCommandsTool {
                     "description": "List of commands to execute against the spec. Each command is an object with a 'type' field.",
                     "items": {
                         "type": "object",
-                        "description": "A tagged command object. The 'type' field selects the variant. Valid types and their fields:\n\n- CreateCard: { type: \"CreateCard\", card_type: string (\"idea\"|\"task\"|\"constraint\"|\"risk\"|\"note\"), title: string, body: string|null, lane: string|null (default \"Ideas\"), created_by: string (your agent_id) }\n- UpdateCard: { type: \"UpdateCard\", card_id: string (ULID), title: string|null, body: string|null|null, card_type: string|null, refs: [string]|null, updated_by: string }\n- MoveCard: { type: \"MoveCard\", card_id: string (ULID), lane: string (\"Ideas\"|\"Plan\"|\"Spec\"), order: number, updated_by: string }\n- DeleteCard: { type: \"DeleteCard\", card_id: string (ULID), updated_by: string }\n- UpdateSpecCore: { type: \"UpdateSpecCore\", title: string|null, one_liner: string|null, goal: string|null, description: string|null, constraints: string|null, success_criteria: string|null, risks: string|null, notes: string|null }\n- AppendTranscript: { type: \"AppendTranscript\", sender: string (your agent_id), content: string }",
+                        "description": "A tagged command object. The 'type' field selects the variant. Valid types and their fields:\n\n- CreateCard: { type: \"CreateCard\", card_type: string (\"idea\"|\"task\"|\"plan\"|\"decision\"|\"constraint\"|\"risk\"|\"assumption\"|\"open_question\"|\"note\"|\"inspiration\"|\"vibes\"; anything else is rejected), title: string, body: string|null, lane: string|null (default \"Ideas\"), created_by: string (your agent_id) }\n- UpdateCard: { type: \"UpdateCard\", card_id: string (ULID), title: string|null, body: string|null|null, card_type: string|null, refs: [string]|null, tags: [string]|null (replaces the card's tags), updated_by: string }\n- MoveCard: { type: \"MoveCard\", card_id: string (ULID), lane: string (\"Ideas\"|\"Plan\"|\"Spec\"), order: number, updated_by: string }\n- DeleteCard: { type: \"DeleteCard\", card_id: string (ULID), updated_by: string }\n- SetCardDone: { type: \"SetCardDone\", card_id: string (ULID), done: boolean, updated_by: string }\n- RequestCardReview: { type: \"RequestCardReview\", card_id: string (ULID), reason: string (what the human should look at), requested_by: string (your agent_id) } (flags the card for human review; never queued)\n- AddCardAttachment: { type: \"AddCardAttachment\", card_id: string (ULID), target: string (http(s) URL or file path), label: string, updated_by: string }\n- UpdateSpecCore: { type: \"UpdateSpecCore\", title: string|null, one_liner: string|null, goal: string|null, description: string|null, constraints: string|null, success_criteria: string|null, risks: string|null, notes: string|null }\n- AppendTranscript: { type: \"AppendTranscript\", sender: string (your agent_id), content: string }\n- TransitionPhase: { type: \"TransitionPhase\", target: \"Complete\" } (review gate)\n- SetSpecArchived: { type: \"SetSpecArchived\", archived: true } (review gate)",
                         "properties": {
                             "type": {
                                 "type": "string",
-                                "enum": ["CreateCard", "UpdateCard", "MoveCard", "DeleteCard", "UpdateSpecCore", "AppendTranscript"],
+                                "enum": ["CreateCard", "UpdateCard", "MoveCard", "DeleteCard", "SetCardDone", "RequestCardReview", "AddCardAttachment", "UpdateSpecCore", "AppendTranscript", "TransitionPhase", "SetSpecArchived"],
                                 "description": "The command type to execute."
                             }
                         },
                         "required": ["type"]
                     }
+                },
+                "confidence": {
+                    "type": "number",
+                    "description": "Optional. How sure you are that these changes reflect what the human wants, from 0.0 to 1.0. Below 0.5 the changes are queued for human approval."
                 }
             },
             "required": ["commands"]
@@ -65,14 +97,54 @@ impl Tool for WriteCommandsTool {
             return Ok(ToolResult::text("No commands to execute."));
         }
 
+        let confidence = params.get("confidence").and_then(|v| v.as_f64());
+
         let total = commands.len();
         let mut successes = 0;
+        let mut queued = 0;
         let mut failures = Vec::new();
 
-        for (i, cmd) in commands.into_iter().enumerate() {
+        for (i, mut cmd) in commands.into_iter().enumerate() {
+            if let Command::AppendTranscript {
+                sender,
+                display_name,
+                ..
+            } = &mut cmd
+                && display_name.is_none()
+                && *sender == self.agent_id
+            {
+                display_name.clone_from(&self.persona_name);
+            }
+            // Release-type actions always stop at a review gate, whatever
+            // the agent's confidence.
+            let (is_proposal, cmd) = if let Some(action) = cmd.review_gate_action() {
+                let reason = format!("{} needs human approval", action);
+                let cmd = Command::RequestReviewGate {
+                    command: Box::new(cmd),
+                    requested_by: self.agent_id.clone(),
+                    reason,
+                };
+                (true, cmd)
+            } else {
+                match review_reason(&cmd, confidence) {
+                    Some(reason) => (
+                        true,
+                        Command::ProposeAction {
+                            command: Box::new(cmd),
+                            proposed_by: self.agent_id.clone(),
+                            reason,
+                        },
+                    ),
+                    None => (false, cmd),
+                }
+            };
             match self.actor.send_command(cmd).await {
                 Ok(events) => {
-                    successes += 1;
+                    if is_proposal {
+                        queued += 1;
+                    } else {
+                        successes += 1;
+                    }
                     tracing::debug!(
                         agent_id = %self.agent_id,
                         command_index = i,
@@ -92,8 +164,10 @@ impl Tool for WriteCommandsTool {
             }
         }
 
-        let summary = if failures.is_empty() {
+        let mut summary = if failures.is_empty() && queued == 0 {
             format!("All {} commands executed successfully.", total)
+        } else if failures.is_empty() {
+            format!("{}/{} commands executed successfully.", successes, total)
         } else {
             format!(
                 "{}/{} commands succeeded. Failures:\n{}",
@@ -102,6 +176,12 @@ impl Tool for WriteCommandsTool {
                 failures.join("\n")
             )
         };
+        if queued > 0 {
+            summary.push_str(&format!(
+                "\n{} queued for human approval; do not retry them.",
+                queued
+            ));
+        }
 
         Ok(ToolResult::text(summary))
     }
@@ -126,6 +206,7 @@ mod tests {
         let tool = WriteCommandsTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
         assert_eq!(tool.name(), "write_commands");
     }
@@ -136,6 +217,7 @@ mod tests {
         let tool = WriteCommandsTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
         assert!(tool.description().contains("Submit one or more commands"));
     }
@@ -146,6 +228,7 @@ mod tests {
         let tool = WriteCommandsTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
         let schema = tool.schema();
         assert!(schema.is_object());
@@ -169,6 +252,7 @@ mod tests {
         let tool = WriteCommandsTool {
             actor: Arc::new(handle.clone()),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
 
         let params = json!({
@@ -197,6 +281,7 @@ mod tests {
         let tool = WriteCommandsTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
 
         // Try to update a card that doesn't exist (spec not created)
@@ -222,10 +307,194 @@ mod tests {
         let tool = WriteCommandsTool {
             actor: Arc::new(handle),
             agent_id: "test-agent".to_string(),
+            persona_name: None,
         };
 
         let params = json!({ "commands": [] });
         let result = tool.execute(params).await.unwrap();
         assert!(result.content.contains("No commands to execute"));
     }
+
+    #[tokio::test]
+    async fn delete_card_is_queued_as_proposal() {
+        let (_id, handle) = make_test_actor();
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Test".to_string(),
+                one_liner: "Test".to_string(),
+                goal: "Test".to_string(),
+            })
+            .await
+            .unwrap();
+        let events = handle
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "Doomed".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        let card_id = match &events[0].payload {
+            barnstormer_core::EventPayload::CardCreated { card } => card.card_id,
+            _ => panic!("wrong event"),
+        };
+
+        let tool = WriteCommandsTool {
+            actor: Arc::new(handle.clone()),
+            agent_id: "test-agent".to_string(),
+            persona_name: None,
+        };
+        let params = json!({
+            "commands": [{
+                "type": "DeleteCard",
+                "card_id": card_id.to_string(),
+                "updated_by": "test-agent"
+            }]
+        });
+
+        let result = tool.execute(params).await.unwrap();
+        assert!(result.content.contains("1 queued for human approval"));
+
+        let state = handle.read_state().await;
+        assert!(state.cards.contains_key(&card_id));
+        assert_eq!(state.pending_proposals.len(), 1);
+        assert_eq!(state.pending_proposals[0].proposed_by, "test-agent");
+    }
+
+    #[tokio::test]
+    async fn archiving_raises_a_review_gate_that_applies_on_approval() {
+        let (_id, handle) = make_test_actor();
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Test".to_string(),
+                one_liner: "Test".to_string(),
+                goal: "Test".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let tool = WriteCommandsTool {
+            actor: Arc::new(handle.clone()),
+            agent_id: "manager-01HTEST".to_string(),
+            persona_name: None,
+        };
+        let params = json!({
+            "commands": [{ "type": "SetSpecArchived", "archived": true }],
+            "confidence": 0.99
+        });
+        let result = tool.execute(params).await.unwrap();
+        assert!(result.content.contains("1 queued for human approval"));
+
+        let question_id = {
+            let state = handle.read_state().await;
+            assert!(!state.archived, "archive must wait for approval");
+            assert_eq!(state.pending_proposals.len(), 1);
+            match state.pending_question() {
+                Some(barnstormer_core::UserQuestion::ReviewGate {
+                    question_id,
+                    question,
+                    ..
+                }) => {
+                    assert!(question.contains("manager-01HTEST"));
+                    *question_id
+                }
+                other => panic!("expected ReviewGate question, got {:?}", other),
+            }
+        };
+
+        handle
+            .send_command(Command::AnswerQuestion {
+                question_id,
+                answer: "Approve".to_string(),
+            })
+            .await
+            .unwrap();
+        let state = handle.read_state().await;
+        assert!(state.archived);
+        assert!(state.pending_proposals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn low_confidence_batch_is_queued_but_transcript_is_not() {
+        let (_id, handle) = make_test_actor();
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Test".to_string(),
+                one_liner: "Test".to_string(),
+                goal: "Test".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let tool = WriteCommandsTool {
+            actor: Arc::new(handle.clone()),
+            agent_id: "test-agent".to_string(),
+            persona_name: None,
+        };
+        let params = json!({
+            "confidence": 0.2,
+            "commands": [
+                {
+                    "type": "CreateCard",
+                    "card_type": "idea",
+                    "title": "Maybe",
+                    "body": null,
+                    "lane": null,
+                    "created_by": "test-agent"
+                },
+                {
+                    "type": "AppendTranscript",
+                    "sender": "test-agent",
+                    "content": "Not sure about this one."
+                }
+            ]
+        });
+
+        tool.execute(params).await.unwrap();
+
+        let state = handle.read_state().await;
+        assert!(state.cards.is_empty());
+        assert_eq!(state.pending_proposals.len(), 1);
+        assert!(
+            state
+                .transcript
+                .iter()
+                .any(|m| m.content == "Not sure about this one.")
+        );
+    }
+
+    #[test]
+    fn review_reason_gates_only_risky_or_unsure_commands() {
+        let create = Command::CreateCard {
+            card_type: "idea".to_string(),
+            title: "x".to_string(),
+            body: None,
+            lane: None,
+            created_by: "a".to_string(),
+            source_attachment_id: None,
+        };
+        assert!(review_reason(&create, None).is_none());
+        assert!(review_reason(&create, Some(0.9)).is_none());
+        assert!(review_reason(&create, Some(0.1)).is_some());
+        let delete = Command::DeleteCard {
+            card_id: Ulid::new(),
+            updated_by: "a".to_string(),
+        };
+        assert!(review_reason(&delete, Some(1.0)).is_some());
+        let flag = Command::RequestCardReview {
+            card_id: Ulid::new(),
+            reason: "Unclear scope".to_string(),
+            requested_by: "a".to_string(),
+        };
+        assert!(review_reason(&flag, Some(0.1)).is_none());
+        let resolve = Command::ResolveCardReview {
+            card_id: Ulid::new(),
+            approved: true,
+            resolved_by: "a".to_string(),
+        };
+        assert!(review_reason(&resolve, Some(1.0)).is_some());
+    }
 }
@@ -0,0 +1,156 @@
+// ABOUTME: Classifies a failed agent step (rate limit, bad key, unknown model, network) from its error.
+// ABOUTME: Each class maps to a short, actionable transcript line instead of raw provider output.
+
+use std::fmt::Display;
+
+/// Why an agent step failed, in terms the user can act on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentError {
+    /// The provider is throttling requests (HTTP 429 or overloaded).
+    RateLimited,
+    /// The API key is missing, invalid, or lacks access (HTTP 401/403).
+    Auth,
+    /// The provider does not recognise the configured model.
+    UnknownModel,
+    /// The provider could not be reached at all.
+    Unreachable,
+    /// Anything else, with a one-line summary of the error.
+    Other(String),
+}
+
+impl AgentError {
+    /// Classify a step error by its message. Provider clients report HTTP
+    /// failures as text, so status codes and well-known phrases are matched.
+    pub fn classify(error: &dyn Display) -> Self {
+        let text = error.to_string();
+        let lower = text.to_ascii_lowercase();
+        let has_code = |code: &str| {
+            lower
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|token| token == code)
+        };
+        let has_any = |phrases: &[&str]| phrases.iter().any(|p| lower.contains(p));
+
+        if has_code("429")
+            || has_code("529")
+            || has_any(&[
+                "rate limit",
+                "rate_limit",
+                "too many requests",
+                "overloaded",
+            ])
+        {
+            Self::RateLimited
+        } else if has_code("401")
+            || has_code("403")
+            || has_any(&[
+                "unauthorized",
+                "authentication",
+                "invalid api key",
+                "invalid x-api-key",
+                "permission denied",
+                "api_key",
+            ])
+        {
+            Self::Auth
+        } else if lower.contains("model")
+            && has_any(&[
+                "not found",
+                "not_found",
+                "does not exist",
+                "unknown",
+                "invalid",
+            ])
+        {
+            Self::UnknownModel
+        } else if has_any(&[
+            "error sending request",
+            "connection refused",
+            "connection reset",
+            "failed to connect",
+            "dns error",
+            "timed out",
+        ]) {
+            Self::Unreachable
+        } else {
+            let summary: String = text
+                .chars()
+                .filter(|c| *c != '\n' && *c != '\r')
+                .take(100)
+                .collect();
+            Self::Other(summary.trim().to_string())
+        }
+    }
+
+    /// The transcript line posted for this failure by the agent with
+    /// `role_label` (e.g. "Manager").
+    pub fn transcript_message(&self, role_label: &str) -> String {
+        match self {
+            Self::RateLimited => format!(
+                "[{}] is rate-limited by the provider; slowing down before retrying.",
+                role_label
+            ),
+            Self::Auth => format!(
+                "[{}] can't authenticate with the provider — check your API key.",
+                role_label
+            ),
+            Self::UnknownModel => format!(
+                "[{}] the provider doesn't recognise the configured model — check the model name.",
+                role_label
+            ),
+            Self::Unreachable => format!(
+                "[{}] can't reach the provider — check your network or base URL.",
+                role_label
+            ),
+            Self::Other(summary) => format!(
+                "[{}] encountered an issue ({}). Will retry next cycle.",
+                role_label, summary
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_for(error: &str) -> String {
+        AgentError::classify(&anyhow::anyhow!(error.to_string())).transcript_message("Manager")
+    }
+
+    #[test]
+    fn rate_limit_gets_its_own_message() {
+        let err = anyhow::anyhow!("API error 429: rate_limit_error: Number of requests exceeded");
+        assert_eq!(AgentError::classify(&err), AgentError::RateLimited);
+        assert_eq!(
+            message_for("HTTP 429 Too Many Requests"),
+            "[Manager] is rate-limited by the provider; slowing down before retrying."
+        );
+    }
+
+    #[test]
+    fn auth_failure_points_at_the_api_key() {
+        let err = anyhow::anyhow!("API error 401: authentication_error: invalid x-api-key");
+        assert_eq!(AgentError::classify(&err), AgentError::Auth);
+        let msg = message_for("401 Unauthorized");
+        assert!(msg.contains("check your API key"), "{}", msg);
+        assert!(!msg.contains("401"));
+    }
+
+    #[test]
+    fn model_and_network_failures_are_recognised() {
+        let model = anyhow::anyhow!("API error 404: not_found_error: model: claude-nope");
+        assert_eq!(AgentError::classify(&model), AgentError::UnknownModel);
+        let network = anyhow::anyhow!("error sending request for url (https://api.example.com)");
+        assert_eq!(AgentError::classify(&network), AgentError::Unreachable);
+    }
+
+    #[test]
+    fn unknown_errors_keep_the_generic_message() {
+        let msg = message_for("tool registry exploded\nwith details");
+        assert_eq!(
+            msg,
+            "[Manager] encountered an issue (tool registry explodedwith details). Will retry next cycle."
+        );
+    }
+}
@@ -10,12 +10,13 @@ use mux::hook::{Hook, HookAction, HookEvent};
 /// A mux Hook that forwards streaming events from the LLM agent loop into
 /// the barnstormer event system via the SpecActorHandle.
 ///
-/// Manager agents stream text deltas to the UI. All agents (manager and worker)
+/// Agents with `stream_text` set (the manager, or every agent under
+/// `BARNSTORMER_STREAM_TOKENS`) stream text deltas to the UI. All agents
 /// stream tool activity notifications so users can see what the agent is doing.
 pub struct StreamingHook {
     actor: Arc<SpecActorHandle>,
     agent_id: String,
-    is_manager: bool,
+    stream_text: bool,
 }
 
 impl StreamingHook {
@@ -23,12 +24,13 @@ impl StreamingHook {
     ///
     /// - `actor`: handle to the spec actor for sending commands
     /// - `agent_id`: identifier for the agent producing events
-    /// - `is_manager`: if true, text deltas are forwarded; workers skip text streaming
-    pub fn new(actor: Arc<SpecActorHandle>, agent_id: String, is_manager: bool) -> Self {
+    /// - `stream_text`: if true, text deltas are forwarded; otherwise only
+    ///   tool activity is
+    pub fn new(actor: Arc<SpecActorHandle>, agent_id: String, stream_text: bool) -> Self {
         Self {
             actor,
             agent_id,
-            is_manager,
+            stream_text,
         }
     }
 }
@@ -46,7 +48,7 @@ impl Hook for StreamingHook {
 
     async fn on_event(&self, event: &HookEvent) -> Result<HookAction, anyhow::Error> {
         match event {
-            HookEvent::StreamDelta { text, .. } if self.is_manager => {
+            HookEvent::StreamDelta { text, .. } if self.stream_text => {
                 let _ = self
                     .actor
                     .send_command(Command::StreamDelta {
@@ -57,7 +59,7 @@ impl Hook for StreamingHook {
             }
 
             HookEvent::StreamDelta { .. } => {
-                // Workers don't stream text deltas
+                // Text streaming is off for this agent
             }
 
             HookEvent::PostToolUse {
@@ -163,7 +165,7 @@ mod tests {
     #[tokio::test]
     async fn hook_sends_tool_activity_for_any_agent() {
         let (actor, mut rx) = setup_actor();
-        // Use is_manager=false to show tool activity works for workers too
+        // Use stream_text=false to show tool activity works for workers too
         let hook = StreamingHook::new(actor, "worker-1".to_string(), false);
 
         let event = HookEvent::PostToolUse {
@@ -1,11 +1,14 @@
 // ABOUTME: SwarmOrchestrator manages multiple agents per spec, using mux SubAgent for LLM execution.
 // ABOUTME: Each agent runs as a mux SubAgent with domain tools, coordinated by pause/resume flags and event subscriptions.
 
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use tokio::sync::{Notify, broadcast};
 use tracing;
 use ulid::Ulid;
@@ -20,11 +23,11 @@ use std::collections::HashMap;
 
 use crate::client;
 use crate::context::{AgentContext, AgentRole};
-use crate::mux_tools;
+use crate::mux_tools::{self, FetchPolicy};
 use barnstormer_core::actor::SpecActorHandle;
 use barnstormer_core::command::Command;
 use barnstormer_core::event::{Event, EventPayload};
-use barnstormer_core::state::SpecPhase;
+use barnstormer_core::state::{SpecPhase, SpecState};
 
 /// System prompt for the Manager agent role.
 const MANAGER_SYSTEM_PROMPT: &str = "You are the manager agent for a product specification. \
@@ -83,7 +86,9 @@ const DOT_GENERATOR_SYSTEM_PROMPT: &str = "You are the diagram analyst. Your job
 /// System prompt for the Critic agent role.
 const CRITIC_SYSTEM_PROMPT: &str = "You are the critic agent. Your job is to review the spec for \
     gaps, inconsistencies, and potential issues. Read the current state, then create cards with \
-    card_type 'risk' or 'constraint' for issues you find. Narrate your analysis and provide \
+    card_type 'risk' or 'constraint' for issues you find. When a specific card needs a human decision \
+    (it contradicts another card, or you cannot tell whether it is right), flag it with a \
+    RequestCardReview command and a one-sentence reason. Narrate your analysis and provide \
     constructive feedback. Ask the user questions when you identify ambiguities that need human input.";
 
 /// System prompt for the Manager agent during the brainstorming phase.
@@ -111,6 +116,123 @@ const MANAGER_BRAINSTORMING_PROMPT: &str = r#"You are the Manager agent in brain
 
 IMPORTANT: You are the primary point of contact for the human user. When you see messages from 'human' in the recent transcript, treat them as top priority — acknowledge them with narration, take action based on their input, and route their requests to the appropriate workflow. The human is actively engaged, so always respond to their messages before doing other work."#;
 
+/// In condensed-cycle mode, a non-manager agent whose last this-many steps
+/// produced nothing but narration sits out the next cycle.
+const CONDENSE_AFTER_NARRATION_STEPS: u32 = 2;
+
+/// Default ceiling on think-act iterations per agent step. Overridable via
+/// `BARNSTORMER_AGENT_MAX_ITERATIONS`.
+pub const DEFAULT_AGENT_MAX_ITERATIONS: usize = 10;
+
+/// Default limit on how long one agent step may run before it is abandoned,
+/// so a stalled provider cannot hold up the loop. Overridable (in seconds)
+/// via `BARNSTORMER_AGENT_STEP_TIMEOUT`.
+pub const DEFAULT_AGENT_STEP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Roles a swarm built by [`SwarmOrchestrator::with_defaults`] runs, in
+/// slot order. The server's configured `agent_roles` replace them.
+pub const DEFAULT_ROLES: [AgentRole; 4] = [
+    AgentRole::Manager,
+    AgentRole::Brainstormer,
+    AgentRole::Planner,
+    AgentRole::DotGenerator,
+];
+
+/// Default number of recent transcript messages and events copied into an
+/// agent's context on each refresh.
+pub const DEFAULT_CONTEXT_WINDOW: usize = 10;
+
+/// How much recent history `refresh_context_with_flag` keeps in an agent's
+/// context. Overridable via `BARNSTORMER_CONTEXT_TRANSCRIPT_WINDOW` and
+/// `BARNSTORMER_CONTEXT_EVENT_WINDOW`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextWindows {
+    pub transcript: usize,
+    pub events: usize,
+}
+
+impl Default for ContextWindows {
+    fn default() -> Self {
+        Self {
+            transcript: DEFAULT_CONTEXT_WINDOW,
+            events: DEFAULT_CONTEXT_WINDOW,
+        }
+    }
+}
+
+impl ContextWindows {
+    /// Read both window sizes from the environment, falling back to the
+    /// default for unset or unparseable values.
+    pub fn from_env() -> Self {
+        let read = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .unwrap_or(DEFAULT_CONTEXT_WINDOW)
+        };
+        Self {
+            transcript: read("BARNSTORMER_CONTEXT_TRANSCRIPT_WINDOW"),
+            events: read("BARNSTORMER_CONTEXT_EVENT_WINDOW"),
+        }
+    }
+}
+
+/// Per-step figures recorded on the runner after each agent step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentStepMetrics {
+    pub iterations: usize,
+    pub tool_calls: usize,
+    /// True when the step used every allowed iteration, which usually means
+    /// the agent was cut off before it could finish.
+    pub hit_iteration_limit: bool,
+}
+
+/// Steps kept per agent in the swarm's step trace.
+pub const STEP_TRACE_CAPACITY: usize = 20;
+
+/// One agent step as recorded in the step trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StepTraceEntry {
+    pub agent_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// Size of the task prompt sent to the model, in characters. Zero for
+    /// steps that never reached the model.
+    pub prompt_chars: usize,
+    pub tool_calls: usize,
+    pub did_work: bool,
+    /// Why the step ended the way it did: "tools used", "no tool calls",
+    /// "iteration limit", "error", or "condensed skip".
+    pub reason: String,
+}
+
+/// Bounded in-memory record of the most recent steps of each agent, kept
+/// for debugging misbehaving agents. Not persisted. Idle cycles where an
+/// agent had no new events are not recorded, so they cannot crowd out the
+/// steps that actually ran.
+#[derive(Debug, Default)]
+pub struct StepTrace {
+    per_agent: HashMap<String, VecDeque<StepTraceEntry>>,
+}
+
+impl StepTrace {
+    /// Record a step, dropping that agent's oldest entry once it holds
+    /// `STEP_TRACE_CAPACITY` entries.
+    pub fn record(&mut self, entry: StepTraceEntry) {
+        let steps = self.per_agent.entry(entry.agent_id.clone()).or_default();
+        if steps.len() == STEP_TRACE_CAPACITY {
+            steps.pop_front();
+        }
+        steps.push_back(entry);
+    }
+
+    /// Every recorded step across all agents, oldest first.
+    pub fn entries(&self) -> Vec<StepTraceEntry> {
+        let mut entries: Vec<StepTraceEntry> = self.per_agent.values().flatten().cloned().collect();
+        entries.sort_by_key(|e| e.timestamp);
+        entries
+    }
+}
+
 /// Tool usage and workflow guidance appended to all agent system prompts at runtime.
 /// Includes the agent's own ID so it can use it in commands.
 fn tool_usage_guide(agent_id: &str) -> String {
@@ -125,6 +247,7 @@ fn tool_usage_guide(agent_id: &str) -> String {
             - source_attachment_id is optional: set it to an attachment ULID (from the Context Files section) when the card is synthesized from that attachment; leave null otherwise.\n\
           * {{\"type\": \"UpdateSpecCore\", \"description\": \"A detailed description\", \"constraints\": null, \"success_criteria\": null, \"risks\": null, \"notes\": null, \"title\": null, \"one_liner\": null, \"goal\": null}}\n\
           * {{\"type\": \"MoveCard\", \"card_id\": \"<ULID from read_state>\", \"lane\": \"Plan\", \"order\": 1.0, \"updated_by\": \"{agent_id}\"}}\n\
+        - read_card_history: List every change to one card, oldest first. Check it before changing a card someone else edited so you do not revert a deliberate edit.\n\
         - emit_narration: Post a message to the activity feed. Use this OFTEN to explain your reasoning.\n\
         - emit_diff_summary: Mark your step as finished with a change summary. Call this LAST.\n\
         - ask_user_boolean / ask_user_freeform / ask_user_multiple_choice: Ask the user questions.\n\n\
@@ -195,6 +318,22 @@ pub struct AgentRunner {
     pub role: AgentRole,
     pub context: AgentContext,
     pub agent_id: String,
+    /// Consecutive steps that produced no spec mutation (narration only).
+    /// Drives the condensed-cycle skip; reset whenever the agent changes
+    /// something or sits out a cycle.
+    pub narration_streak: u32,
+    /// Metrics from the most recent step that returned a result.
+    pub last_step: Option<AgentStepMetrics>,
+    /// Number of steps that ran out of iterations over this runner's life.
+    pub iteration_limit_hits: u32,
+    /// Character length of the task prompt built for the most recent step.
+    pub last_prompt_chars: usize,
+    /// Friendly name shown for this agent's transcript messages (e.g.
+    /// "Ada"). Display only; the role still decides what the agent does.
+    pub persona_name: Option<String>,
+    /// Model this agent runs on instead of the swarm's default, from
+    /// `BARNSTORMER_MODEL_<ROLE>`. None uses the swarm's model.
+    pub model: Option<String>,
 }
 
 impl AgentRunner {
@@ -206,8 +345,109 @@ impl AgentRunner {
             role,
             context,
             agent_id,
+            narration_streak: 0,
+            last_step: None,
+            iteration_limit_hits: 0,
+            last_prompt_chars: 0,
+            persona_name: None,
+            model: None,
         }
     }
+
+    /// The model this agent's steps use: its own override, or `default`.
+    pub fn resolved_model<'a>(&'a self, default: &'a str) -> &'a str {
+        self.model.as_deref().unwrap_or(default)
+    }
+}
+
+/// Per-role model overrides from `BARNSTORMER_MODEL_<ROLE>` variables (e.g.
+/// `BARNSTORMER_MODEL_PLANNER`), keyed by role label. `lookup` reads a
+/// variable; blank values are ignored.
+fn model_overrides(
+    roles: &[AgentRole],
+    lookup: impl Fn(&str) -> Option<String>,
+) -> HashMap<String, String> {
+    roles
+        .iter()
+        .filter_map(|role| {
+            let var = format!("BARNSTORMER_MODEL_{}", role.label().to_ascii_uppercase());
+            let model = lookup(&var)?.trim().to_string();
+            (!model.is_empty()).then(|| (role.label().to_string(), model))
+        })
+        .collect()
+}
+
+/// Parse a `BARNSTORMER_AGENT_ROLES`-style comma-separated list of role
+/// labels (e.g. `manager,planner`) in slot order. Unknown names are logged
+/// and skipped; a role listed twice gets two slots.
+fn parse_roles(raw: &str) -> Vec<AgentRole> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .filter_map(|label| {
+            let role = AgentRole::from_label(label);
+            if role.is_none() {
+                tracing::warn!(
+                    role = label,
+                    "ignoring unknown role in the configured agent roles"
+                );
+            }
+            role
+        })
+        .collect()
+}
+
+/// The swarm roster named by `labels` in slot order, or [`DEFAULT_ROLES`]
+/// when none of them is a known role. This is how the `agent_roles` setting
+/// from `barnstormer.toml` becomes a roster.
+pub fn roles_or_default(labels: &[String]) -> Vec<AgentRole> {
+    let roles = parse_roles(&labels.join(","));
+    if roles.is_empty() {
+        DEFAULT_ROLES.to_vec()
+    } else {
+        roles
+    }
+}
+
+/// The roster a spec's swarm runs: the spec's own `agent_roster` when set,
+/// else `fallback` (the server's configured roster). Unknown labels are
+/// logged and skipped; a roster with no known role falls back too.
+pub fn roster_roles(state: &SpecState, fallback: &[AgentRole]) -> Vec<AgentRole> {
+    let roles: Vec<AgentRole> = state
+        .agent_roster
+        .iter()
+        .flatten()
+        .filter_map(|label| {
+            let role = AgentRole::from_label(label);
+            if role.is_none() {
+                tracing::warn!(role = %label, "ignoring unknown role in spec agent roster");
+            }
+            role
+        })
+        .collect();
+    if roles.is_empty() {
+        fallback.to_vec()
+    } else {
+        roles
+    }
+}
+
+/// [`model_overrides`] read from the process environment.
+pub fn env_model_overrides(roles: &[AgentRole]) -> HashMap<String, String> {
+    model_overrides(roles, |var| std::env::var(var).ok())
+}
+
+/// Parse `BARNSTORMER_AGENT_PERSONAS`-style `role=Name` pairs separated by
+/// commas (e.g. `planner=Ada,critic=Grace`) into a role-label → name map.
+/// Malformed or empty entries are ignored.
+fn parse_personas(raw: &str) -> HashMap<String, String> {
+    raw.split(',')
+        .filter_map(|pair| {
+            let (role, name) = pair.split_once('=')?;
+            let (role, name) = (role.trim().to_ascii_lowercase(), name.trim());
+            (!role.is_empty() && !name.is_empty()).then(|| (role, name.to_string()))
+        })
+        .collect()
 }
 
 /// Orchestrates a swarm of agents working on a single spec.
@@ -218,12 +458,21 @@ pub struct SwarmOrchestrator {
     /// Each slot holds an Option so the run_loop can temporarily take ownership
     /// of a runner without needing a placeholder value (fixes Ulid::nil() hack).
     pub agents: Vec<Option<AgentRunner>>,
+    /// Role of each slot in `agents`, recorded at construction so a slot
+    /// emptied by a cancelled task is restored with the role it had.
+    slot_roles: Vec<AgentRole>,
+    /// Whether each slot in `agents` may run. A disabled agent keeps its
+    /// runner and context but is skipped by `run_loop`.
+    enabled: Vec<bool>,
     /// Per-agent broadcast receivers so each agent sees all events independently.
     /// One receiver per agent, created at swarm construction time.
     event_receivers: Vec<broadcast::Receiver<Event>>,
     pub paused: Arc<AtomicBool>,
     pub question_pending: Arc<AtomicBool>,
     pub client: Arc<dyn LlmClient>,
+    /// Provider the client was built for ("anthropic", "openai", "gemini", "ollama"),
+    /// or "custom" when the client was injected via `with_agents`.
+    pub provider: String,
     pub model: String,
     /// Signal that a human message has arrived; wakes the run_loop from its
     /// idle sleep so the manager agent can respond promptly.
@@ -237,11 +486,42 @@ pub struct SwarmOrchestrator {
     /// Question-mode dispatcher for the retrieve_context tool. Implemented by
     /// the server crate so the agent crate stays free of summarizer internals.
     pub summarizer: Arc<dyn crate::AttachmentSummarizer>,
+    /// Token-saving mode: non-manager agents that have only been narrating
+    /// skip a cycle when no new work has arrived for them. The manager always
+    /// runs. Off by default; enabled via `BARNSTORMER_CONDENSED_CYCLES`.
+    pub condensed_cycles: bool,
+    /// Stream every agent's tokens to the UI as they arrive, not just the
+    /// manager's. Off by default; enabled via `BARNSTORMER_STREAM_TOKENS`.
+    pub stream_tokens: bool,
+    /// Think-act iteration ceiling for each agent step.
+    pub max_iterations: usize,
+    /// Wall-clock limit for each agent step.
+    pub step_timeout: std::time::Duration,
+    /// Recent transcript/event window sizes used when refreshing context.
+    pub context_windows: ContextWindows,
+    /// Persona names by role label, from `BARNSTORMER_AGENT_PERSONAS`.
+    /// Applied to runners created by the orchestrator itself.
+    pub personas: HashMap<String, String>,
+    /// Model overrides by role label, from `BARNSTORMER_MODEL_<ROLE>`.
+    /// Applied to runners created by the orchestrator itself.
+    pub agent_models: HashMap<String, String>,
+    /// Recent steps per agent, for `GET /api/specs/{id}/agents/trace`.
+    pub step_trace: StepTrace,
+    /// End of a bounded "run for N minutes" session; `run_loop` pauses the
+    /// swarm once it passes. Uses tokio's clock so tests can advance it.
+    pub run_deadline: Option<tokio::time::Instant>,
+    /// Agent steps run since the swarm was last resumed.
+    pub steps_taken: AtomicU32,
+    /// Most steps per session before the swarm pauses itself. None means no
+    /// limit; the server sets it from `BARNSTORMER_MAX_STEPS_PER_SESSION`.
+    pub max_steps: Option<u32>,
 }
 
 impl SwarmOrchestrator {
-    /// Create a new orchestrator with default agents for the given spec.
-    /// Uses the default provider (from env or "anthropic") and model.
+    /// Create a new orchestrator for the given spec running [`DEFAULT_ROLES`],
+    /// talking to `provider` (see `client::create_llm_client`). `model`
+    /// overrides the provider's default model when set. Callers resolve these
+    /// per spec, so two specs can run on different providers at once.
     ///
     /// `home` is the barnstormer data directory; it is passed to tool
     /// registries so tools like `retrieve_context` can resolve attachment files.
@@ -253,26 +533,68 @@ impl SwarmOrchestrator {
         actor: SpecActorHandle,
         home: PathBuf,
         summarizer: Arc<dyn crate::AttachmentSummarizer>,
+        provider: &str,
+        model: Option<&str>,
     ) -> Result<Self, anyhow::Error> {
-        let provider = std::env::var("BARNSTORMER_DEFAULT_PROVIDER")
-            .unwrap_or_else(|_| "anthropic".to_string());
-        let model_override = std::env::var("BARNSTORMER_DEFAULT_MODEL").ok();
+        Self::with_roles(
+            spec_id,
+            actor,
+            &DEFAULT_ROLES,
+            home,
+            summarizer,
+            provider,
+            model,
+        )
+    }
 
-        let (llm_client, resolved_model) =
-            client::create_llm_client(&provider, model_override.as_deref())?;
+    /// Like [`with_defaults`](Self::with_defaults), but running one agent
+    /// per entry of `roles`, in that order, instead of the default roster.
+    pub fn with_roles(
+        spec_id: Ulid,
+        actor: SpecActorHandle,
+        roles: &[AgentRole],
+        home: PathBuf,
+        summarizer: Arc<dyn crate::AttachmentSummarizer>,
+        provider: &str,
+        model: Option<&str>,
+    ) -> Result<Self, anyhow::Error> {
+        anyhow::ensure!(!roles.is_empty(), "a swarm needs at least one agent role");
+        let condensed_cycles = std::env::var("BARNSTORMER_CONDENSED_CYCLES")
+            .map(|v| matches!(v.trim(), "1" | "true" | "yes" | "on"))
+            .unwrap_or(false);
+        let stream_tokens = std::env::var("BARNSTORMER_STREAM_TOKENS")
+            .map(|v| matches!(v.trim(), "1" | "true" | "yes" | "on"))
+            .unwrap_or(false);
+        let max_iterations = std::env::var("BARNSTORMER_AGENT_MAX_ITERATIONS")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_AGENT_MAX_ITERATIONS);
+        let step_timeout = std::env::var("BARNSTORMER_AGENT_STEP_TIMEOUT")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(DEFAULT_AGENT_STEP_TIMEOUT);
+        let context_windows = ContextWindows::from_env();
+        let personas = std::env::var("BARNSTORMER_AGENT_PERSONAS")
+            .map(|v| parse_personas(&v))
+            .unwrap_or_default();
+
+        let (llm_client, resolved_model) = client::create_llm_client(provider, model)?;
 
         let actor = Arc::new(actor);
 
-        let roles = [
-            AgentRole::Manager,
-            AgentRole::Brainstormer,
-            AgentRole::Planner,
-            AgentRole::DotGenerator,
-        ];
+        let agent_models = env_model_overrides(roles);
 
         let agents: Vec<Option<AgentRunner>> = roles
             .iter()
-            .map(|role| Some(AgentRunner::new(spec_id, *role)))
+            .map(|role| {
+                let mut runner = AgentRunner::new(spec_id, *role);
+                runner.persona_name = personas.get(role.label()).cloned();
+                runner.model = agent_models.get(role.label()).cloned();
+                Some(runner)
+            })
             .collect();
 
         // Each agent gets its own broadcast receiver so events are not
@@ -283,15 +605,29 @@ impl SwarmOrchestrator {
             spec_id,
             actor,
             agents,
+            slot_roles: roles.to_vec(),
+            enabled: vec![true; roles.len()],
             event_receivers,
             paused: Arc::new(AtomicBool::new(false)),
             question_pending: Arc::new(AtomicBool::new(false)),
             client: llm_client,
+            provider: provider.to_string(),
             model: resolved_model,
             human_message_notify: Arc::new(Notify::new()),
             pending_transition_question: Arc::new(Mutex::new(None)),
             home,
             summarizer,
+            condensed_cycles,
+            stream_tokens,
+            max_iterations,
+            step_timeout,
+            context_windows,
+            personas,
+            agent_models,
+            step_trace: StepTrace::default(),
+            run_deadline: None,
+            steps_taken: AtomicU32::new(0),
+            max_steps: None,
         })
     }
 
@@ -307,20 +643,36 @@ impl SwarmOrchestrator {
     ) -> Self {
         let actor = Arc::new(actor);
         let event_receivers = agents.iter().map(|_| actor.subscribe()).collect();
+        let slot_roles = agents.iter().map(|runner| runner.role).collect();
+        let enabled = vec![true; agents.len()];
         let agents = agents.into_iter().map(Some).collect();
         Self {
             spec_id,
             actor,
             agents,
+            slot_roles,
+            enabled,
             event_receivers,
             paused: Arc::new(AtomicBool::new(false)),
             question_pending: Arc::new(AtomicBool::new(false)),
             client,
+            provider: "custom".to_string(),
             model,
             human_message_notify: Arc::new(Notify::new()),
             pending_transition_question: Arc::new(Mutex::new(None)),
             home,
             summarizer,
+            condensed_cycles: false,
+            stream_tokens: false,
+            max_iterations: DEFAULT_AGENT_MAX_ITERATIONS,
+            step_timeout: DEFAULT_AGENT_STEP_TIMEOUT,
+            context_windows: ContextWindows::default(),
+            personas: HashMap::new(),
+            agent_models: HashMap::new(),
+            step_trace: StepTrace::default(),
+            run_deadline: None,
+            steps_taken: AtomicU32::new(0),
+            max_steps: None,
         }
     }
 
@@ -336,8 +688,9 @@ impl SwarmOrchestrator {
         tracing::info!(spec_id = %self.spec_id, "swarm paused");
     }
 
-    /// Resume agent loops.
+    /// Resume agent loops, starting a fresh step budget.
     pub fn resume(&self) {
+        self.steps_taken.store(0, Ordering::SeqCst);
         self.paused.store(false, Ordering::SeqCst);
         tracing::info!(spec_id = %self.spec_id, "swarm resumed");
     }
@@ -347,6 +700,147 @@ impl SwarmOrchestrator {
         self.paused.load(Ordering::SeqCst)
     }
 
+    /// How many agents are out of their slot running a step right now.
+    pub fn steps_in_flight(&self) -> usize {
+        self.agents.iter().filter(|slot| slot.is_none()).count()
+    }
+
+    /// Enable or disable every agent whose role label (e.g. `dot_generator`)
+    /// matches `role`. Returns false when no agent has that role.
+    pub fn set_agent_enabled(&mut self, role: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for (slot_role, flag) in self.slot_roles.iter().zip(self.enabled.iter_mut()) {
+            if slot_role.label() == role {
+                *flag = enabled;
+                found = true;
+            }
+        }
+        if found {
+            tracing::info!(spec_id = %self.spec_id, role, enabled, "agent toggled");
+        }
+        found
+    }
+
+    /// Pause the agents with role label `role` while the rest of the swarm
+    /// keeps working. The swarm-wide [`pause`](Self::pause) still stops
+    /// everyone, and resuming the swarm does not resume this role. Returns
+    /// false when no agent has that role.
+    pub fn pause_agent(&mut self, role: &str) -> bool {
+        self.set_agent_enabled(role, false)
+    }
+
+    /// Undo [`pause_agent`](Self::pause_agent) for `role`. The agents run
+    /// again once the swarm itself is not paused.
+    pub fn resume_agent(&mut self, role: &str) -> bool {
+        self.set_agent_enabled(role, true)
+    }
+
+    /// Flip the agents with role label `role` on or off, returning the new
+    /// state, or None when no agent has that role.
+    pub fn toggle_agent(&mut self, role: &str) -> Option<bool> {
+        let enabled = !self.is_role_enabled(role)?;
+        self.set_agent_enabled(role, enabled);
+        Some(enabled)
+    }
+
+    /// Whether the agents with role label `role` are enabled. None when no
+    /// agent has that role.
+    pub fn is_role_enabled(&self, role: &str) -> Option<bool> {
+        self.slot_roles
+            .iter()
+            .position(|r| r.label() == role)
+            .map(|i| self.enabled[i])
+    }
+
+    /// Each distinct role in the swarm, in slot order, with whether it is
+    /// enabled.
+    pub fn agent_roles_enabled(&self) -> Vec<(AgentRole, bool)> {
+        let mut roles: Vec<(AgentRole, bool)> = Vec::new();
+        for (role, enabled) in self.slot_roles.iter().zip(&self.enabled) {
+            if !roles.iter().any(|(r, _)| r == role) {
+                roles.push((*role, *enabled));
+            }
+        }
+        roles
+    }
+
+    /// Role of every enabled slot, in slot order. Unlike
+    /// [`agent_roles_enabled`](Self::agent_roles_enabled), a role that
+    /// fills several slots is listed once per slot.
+    pub fn enabled_slot_roles(&self) -> Vec<AgentRole> {
+        self.slot_roles
+            .iter()
+            .zip(&self.enabled)
+            .filter(|(_, enabled)| **enabled)
+            .map(|(role, _)| *role)
+            .collect()
+    }
+
+    /// Resume the swarm for `duration`, after which `run_loop` pauses it
+    /// again. Announces the run in the transcript.
+    pub async fn run_for(&mut self, duration: std::time::Duration) {
+        self.run_deadline = Some(tokio::time::Instant::now() + duration);
+        self.resume();
+        self.narrate(format!(
+            "Focus run started: agents will pause in {}.",
+            format_run_length(duration)
+        ))
+        .await;
+    }
+
+    /// Pause the swarm if its focus-run deadline has passed, announcing the
+    /// stop. A deadline that passes while already paused is just cleared.
+    /// Returns true when this call paused the swarm.
+    pub async fn enforce_run_deadline(&mut self) -> bool {
+        match self.run_deadline {
+            Some(deadline) if tokio::time::Instant::now() >= deadline => {
+                self.run_deadline = None;
+                if self.is_paused() {
+                    return false;
+                }
+                self.pause();
+                self.narrate("Focus run finished: agents paused.".to_string())
+                    .await;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// True once this session has used up its step budget; `resume` starts
+    /// a new one.
+    pub fn step_budget_reached(&self) -> bool {
+        self.max_steps
+            .is_some_and(|max| self.steps_taken.load(Ordering::SeqCst) >= max)
+    }
+
+    /// Pause the swarm if its step budget is used up, announcing the stop.
+    /// Returns true when this call paused the swarm.
+    pub async fn enforce_step_budget(&self) -> bool {
+        if !self.step_budget_reached() || self.is_paused() {
+            return false;
+        }
+        self.pause();
+        self.narrate(format!(
+            "Step budget reached ({} steps): agents paused.",
+            self.steps_taken.load(Ordering::SeqCst)
+        ))
+        .await;
+        true
+    }
+
+    /// Post a system message to the spec transcript.
+    async fn narrate(&self, content: String) {
+        let command = Command::AppendTranscript {
+            sender: "system".to_string(),
+            content,
+            display_name: None,
+        };
+        if let Err(e) = self.actor.send_command(command).await {
+            tracing::warn!(spec_id = %self.spec_id, error = %e, "failed to post swarm narration");
+        }
+    }
+
     /// Returns true if a question is currently pending for the user.
     pub fn has_pending_question(&self) -> bool {
         self.question_pending.load(Ordering::SeqCst)
@@ -359,30 +853,37 @@ impl SwarmOrchestrator {
     }
 
     /// Re-create any agent runner slots that are `None` (e.g. from a cancelled task).
-    /// Each restored slot gets a fresh AgentRunner and event receiver.
-    /// Only works for slots whose index maps to a known default role.
+    /// Each restored slot gets a fresh AgentRunner, with the role the slot
+    /// was constructed with, and a fresh event receiver.
     pub fn recover_empty_slots(&mut self) {
-        let default_roles = [
-            AgentRole::Manager,
-            AgentRole::Brainstormer,
-            AgentRole::Planner,
-            AgentRole::DotGenerator,
-        ];
         for i in 0..self.agents.len() {
-            if self.agents[i].is_none()
-                && let Some(&role) = default_roles.get(i)
-            {
+            if self.agents[i].is_none() {
+                let role = self.slot_roles[i];
                 tracing::warn!(
                     agent_index = i,
                     role = %role,
                     "recovering empty agent slot after cancellation"
                 );
-                self.agents[i] = Some(AgentRunner::new(self.spec_id, role));
+                let mut runner = AgentRunner::new(self.spec_id, role);
+                runner.persona_name = self.personas.get(role.label()).cloned();
+                runner.model = self.agent_models.get(role.label()).cloned();
+                self.agents[i] = Some(runner);
                 self.event_receivers[i] = self.actor.subscribe();
             }
         }
     }
 
+    /// The live context of the first agent whose role label (e.g. `planner`)
+    /// matches `role`. None when no such agent exists or its runner is
+    /// currently out of its slot for a step.
+    pub fn agent_context(&self, role: &str) -> Option<&AgentContext> {
+        self.agents
+            .iter()
+            .flatten()
+            .find(|runner| runner.role.label() == role)
+            .map(|runner| &runner.context)
+    }
+
     /// Collect all agent contexts for inclusion in a snapshot.
     pub fn collect_agent_contexts(&self) -> HashMap<String, serde_json::Value> {
         let contexts: Vec<AgentContext> = self
@@ -416,6 +917,11 @@ impl SwarmOrchestrator {
     ///
     /// Creates a fresh SubAgent with the domain tool registry, sends it the
     /// agent's context as a task prompt, and lets mux handle the think-act loop.
+    /// Records the step's metrics on the runner; a step that uses all
+    /// `max_iterations` is logged and noted in the transcript, and a step
+    /// still running after `step_timeout` is abandoned the same way. The
+    /// manager always streams its tokens to the UI; other agents do when
+    /// `stream_tokens` is set.
     /// Returns true if the agent produced useful work, false if idle/error.
     #[allow(clippy::too_many_arguments)]
     pub async fn run_agent_step(
@@ -424,12 +930,18 @@ impl SwarmOrchestrator {
         question_pending: &Arc<AtomicBool>,
         pending_transition_question: &Arc<Mutex<Option<Ulid>>>,
         client: &Arc<dyn LlmClient>,
+        // Swarm default; the runner's own `model` takes precedence.
         model: &str,
         phase: &SpecPhase,
         home: &Path,
         summarizer: &Arc<dyn crate::AttachmentSummarizer>,
+        fetch_policy: Option<&Arc<FetchPolicy>>,
+        max_iterations: usize,
+        step_timeout: std::time::Duration,
+        stream_tokens: bool,
     ) -> bool {
         // Start agent step
+        crate::metrics::record_step();
         let start_cmd = Command::StartAgentStep {
             agent_id: runner.agent_id.clone(),
             description: format!("{} reasoning step", runner.role.label()),
@@ -448,13 +960,13 @@ impl SwarmOrchestrator {
             Arc::clone(question_pending),
             Arc::clone(pending_transition_question),
             runner.agent_id.clone(),
+            runner.persona_name.clone(),
             home.to_path_buf(),
             Arc::clone(summarizer),
+            fetch_policy.cloned(),
         )
         .await;
 
-        let is_manager = runner.role == AgentRole::Manager;
-
         // Create agent definition with role-specific system prompt + tool guide.
         //
         // The system prompt (base role prompt + phase block + tool_usage_guide)
@@ -469,10 +981,13 @@ impl SwarmOrchestrator {
         let mut definition = AgentDefinition::new(runner.role.label(), system_prompt.clone())
             .system_block(SystemBlock::cached(system_prompt))
             .cache_tools(true)
-            .model(model)
-            .max_iterations(10);
+            .model(runner.resolved_model(model))
+            .max_iterations(max_iterations);
 
-        if is_manager {
+        // Streaming yields the same final response as a buffered call; it
+        // just lets text reach the UI as it is generated.
+        let stream_text = streams_text(runner.role, stream_tokens);
+        if stream_text {
             definition = definition.streaming(true);
         }
 
@@ -481,15 +996,45 @@ impl SwarmOrchestrator {
 
         // Attach streaming hook for real-time event forwarding
         let hook_registry = Arc::new(HookRegistry::new());
-        let hook = StreamingHook::new(Arc::clone(actor), runner.agent_id.clone(), is_manager);
+        let hook = StreamingHook::new(Arc::clone(actor), runner.agent_id.clone(), stream_text);
         hook_registry.register(hook).await;
         sub_agent = sub_agent.with_hooks(hook_registry);
 
-        // Build task prompt from context
-        let task_prompt = build_task_prompt(&runner.context);
+        // Build task prompt from context. The critic also gets the
+        // deterministic lint findings so it can start from known problems.
+        let mut task_prompt = build_task_prompt(&runner.context);
+        if runner.role == AgentRole::Critic {
+            let findings =
+                barnstormer_core::lint::lint(&*actor.read_state().await, chrono::Utc::now());
+            task_prompt.push_str(&render_lint_section(&findings));
+        }
+        runner.last_prompt_chars = task_prompt.chars().count();
 
-        // Run the agent
-        match sub_agent.run(&task_prompt).await {
+        // Run the agent, giving up on a step that outlives its timeout.
+        let outcome = match tokio::time::timeout(step_timeout, sub_agent.run(&task_prompt)).await {
+            Ok(outcome) => outcome,
+            Err(_) => {
+                crate::metrics::record_error();
+                tracing::warn!(
+                    agent = %runner.agent_id,
+                    timeout = ?step_timeout,
+                    "agent step timed out"
+                );
+                let _ = actor
+                    .send_command(Command::AppendTranscript {
+                        sender: runner.agent_id.clone(),
+                        content: format!(
+                            "[{}] timed out after {}s waiting on the model; trying again next cycle.",
+                            runner.role.label(),
+                            step_timeout.as_secs_f64(),
+                        ),
+                        display_name: runner.persona_name.clone(),
+                    })
+                    .await;
+                return false;
+            }
+        };
+        match outcome {
             Ok(result) => {
                 tracing::info!(
                     agent = %runner.agent_id,
@@ -498,6 +1043,47 @@ impl SwarmOrchestrator {
                     "agent step completed"
                 );
 
+                let hit_iteration_limit = result.iterations >= max_iterations;
+                runner.last_step = Some(AgentStepMetrics {
+                    iterations: result.iterations,
+                    tool_calls: result.tool_use_count,
+                    hit_iteration_limit,
+                });
+                if hit_iteration_limit {
+                    runner.iteration_limit_hits += 1;
+                    tracing::warn!(
+                        agent = %runner.agent_id,
+                        max_iterations,
+                        hits = runner.iteration_limit_hits,
+                        "agent step hit iteration limit; work may be truncated"
+                    );
+                    let _ = actor
+                        .send_command(Command::AppendTranscript {
+                            sender: runner.agent_id.clone(),
+                            content: format!(
+                                "[{}] ran out of steps ({}) before finishing; picking up next cycle.",
+                                runner.role.label(),
+                                max_iterations,
+                            ),
+                            display_name: runner.persona_name.clone(),
+                        })
+                        .await;
+                }
+
+                let usage = Command::RecordUsage {
+                    role: runner.role.label().to_string(),
+                    model: runner.resolved_model(model).to_string(),
+                    input_tokens: u64::from(result.usage.input_tokens),
+                    output_tokens: u64::from(result.usage.output_tokens),
+                };
+                if let Err(e) = actor.send_command(usage).await {
+                    tracing::warn!(
+                        agent = %runner.agent_id,
+                        error = %e,
+                        "failed to record token usage"
+                    );
+                }
+
                 // FinishAgentStep is emitted by the emit_diff_summary tool,
                 // so we do not send it here to avoid duplicate events.
 
@@ -505,31 +1091,23 @@ impl SwarmOrchestrator {
                 result.tool_use_count > 0
             }
             Err(e) => {
+                crate::metrics::record_error();
                 // Log the full error details for debugging
                 tracing::error!(
                     agent = %runner.agent_id,
                     error = %e,
                     "agent step failed"
                 );
-                // Show a sanitized, user-friendly message in the transcript
-                // with a short error summary for debugging context.
-                let error_text = e.to_string();
-                let error_summary: String = error_text
-                    .chars()
-                    .filter(|c| *c != '\n' && *c != '\r')
-                    .take(100)
-                    .collect::<String>()
-                    .trim()
-                    .to_string();
-                let user_msg = format!(
-                    "[{}] encountered an issue ({}). Will retry next cycle.",
-                    runner.role.label(),
-                    error_summary,
-                );
+                // Show an actionable message in the transcript: rate limits,
+                // key and model problems get their own wording; anything else
+                // falls back to a short error summary.
+                let user_msg = crate::step_error::AgentError::classify(&e)
+                    .transcript_message(runner.role.label());
                 let _ = actor
                     .send_command(Command::AppendTranscript {
                         sender: runner.agent_id.clone(),
                         content: user_msg,
+                        display_name: runner.persona_name.clone(),
                     })
                     .await;
                 false
@@ -537,22 +1115,26 @@ impl SwarmOrchestrator {
         }
     }
 
-    /// Update an agent's context from the current actor state.
-    /// If `question_pending` is provided, syncs the atomic flag from actor state.
+    /// Update an agent's context from the current actor state, using the
+    /// default context windows.
     pub async fn refresh_context(
         runner: &mut AgentRunner,
         actor: &SpecActorHandle,
         event_rx: &mut broadcast::Receiver<Event>,
     ) {
-        Self::refresh_context_with_flag(runner, actor, event_rx, None).await;
+        Self::refresh_context_with_flag(runner, actor, event_rx, None, ContextWindows::default())
+            .await;
     }
 
     /// Update an agent's context and optionally sync the question_pending flag.
+    /// Only the last `windows.events` drained events and `windows.transcript`
+    /// transcript messages are kept; the rolling summary still sees every event.
     pub async fn refresh_context_with_flag(
         runner: &mut AgentRunner,
         actor: &SpecActorHandle,
         event_rx: &mut broadcast::Receiver<Event>,
         question_pending: Option<&AtomicBool>,
+        windows: ContextWindows,
     ) {
         // Drain any buffered events
         let mut events = Vec::new();
@@ -560,28 +1142,24 @@ impl SwarmOrchestrator {
             events.push(event);
         }
         runner.context.update_from_events(&events);
+        let skip = events.len().saturating_sub(windows.events);
+        events.drain(..skip);
         runner.context.recent_events = events;
 
         // Read current state for the summary
         let state = actor.read_state().await;
-        if let Some(ref core) = state.core {
-            runner.context.state_summary = format!(
-                "Title: {}. Goal: {}. Cards: {}. Pending question: {}",
-                core.title,
-                core.goal,
-                state.cards.len(),
-                state.pending_question.is_some()
-            );
+        if let Some(summary) = state_summary(&state) {
+            runner.context.state_summary = summary;
         }
 
         // Sync question_pending flag from actor state
         if let Some(flag) = question_pending {
-            flag.store(state.pending_question.is_some(), Ordering::SeqCst);
+            flag.store(!state.pending_questions.is_empty(), Ordering::SeqCst);
         }
 
         // Copy recent transcript
         let transcript_len = state.transcript.len();
-        let start = transcript_len.saturating_sub(10);
+        let start = transcript_len.saturating_sub(windows.transcript);
         runner.context.recent_transcript = state.transcript[start..].to_vec();
 
         // Copy non-removed context attachments so build_task_prompt can
@@ -611,6 +1189,19 @@ async fn run_agent_by_index(
         let model = s.model.clone();
         let home = s.home.clone();
         let summarizer = Arc::clone(&s.summarizer);
+        let condensed = s.condensed_cycles;
+        let stream_tokens = s.stream_tokens;
+        let max_iterations = s.max_iterations;
+        let step_timeout = s.step_timeout;
+        let context_windows = s.context_windows;
+        if !s.enabled[index] {
+            tracing::debug!(agent_index = index, "agent is disabled, skipping");
+            return false;
+        }
+        if s.step_budget_reached() {
+            s.enforce_step_budget().await;
+            return false;
+        }
         match s.agents[index].take() {
             Some(runner) => {
                 // Swap out the receiver with a fresh one; the old one keeps its
@@ -627,6 +1218,11 @@ async fn run_agent_by_index(
                     model,
                     home,
                     summarizer,
+                    condensed,
+                    stream_tokens,
+                    max_iterations,
+                    step_timeout,
+                    context_windows,
                 ))
             }
             None => {
@@ -645,6 +1241,11 @@ async fn run_agent_by_index(
         model,
         home,
         summarizer,
+        condensed,
+        stream_tokens,
+        max_iterations,
+        step_timeout,
+        context_windows,
     )) = extracted
     else {
         return false;
@@ -655,6 +1256,7 @@ async fn run_agent_by_index(
         &actor_ref,
         &mut event_rx,
         Some(&question_pending),
+        context_windows,
     )
     .await;
 
@@ -673,8 +1275,58 @@ async fn run_agent_by_index(
         return false;
     }
 
-    let phase = actor_ref.read_state().await.phase.clone();
+    // Condensed cycles: an agent that has only been narrating sits this cycle
+    // out, unless something it should react to has happened since it last ran.
+    if condensed
+        && runner.role != AgentRole::Manager
+        && runner.narration_streak >= CONDENSE_AFTER_NARRATION_STEPS
+        && runner
+            .context
+            .recent_events
+            .iter()
+            .all(|e| is_chatter(&e.payload))
+    {
+        tracing::debug!(
+            agent = %runner.agent_id,
+            streak = runner.narration_streak,
+            "narration-only agent sitting out condensed cycle"
+        );
+        runner.narration_streak = 0;
+        let mut s = swarm.lock().await;
+        s.step_trace.record(StepTraceEntry {
+            agent_id: runner.agent_id.clone(),
+            timestamp: Utc::now(),
+            prompt_chars: 0,
+            tool_calls: 0,
+            did_work: false,
+            reason: "condensed skip".to_string(),
+        });
+        s.agents[index] = Some(runner);
+        s.event_receivers[index] = event_rx;
+        return false;
+    }
+
+    // The fetch_url allowlist is per spec, so it is read on every step and
+    // a change applies from the next one.
+    let (phase, fetch_policy) = {
+        let state = actor_ref.read_state().await;
+        let policy = FetchPolicy::for_hosts(&state.fetch_hosts).map(Arc::new);
+        (state.phase.clone(), policy)
+    };
+
+    // Watch what the step emits so we can tell narration-only steps apart
+    // from ones that changed the spec.
+    let mut step_rx = actor_ref.subscribe();
+
+    swarm
+        .lock()
+        .await
+        .steps_taken
+        .fetch_add(1, Ordering::SeqCst);
 
+    // Park the previous metrics so a failed step (which records none) can be
+    // told apart from one that returned, without losing them.
+    let previous_step = runner.last_step.take();
     let did_work = SwarmOrchestrator::run_agent_step(
         &mut runner,
         &actor_ref,
@@ -685,12 +1337,49 @@ async fn run_agent_by_index(
         &phase,
         &home,
         &summarizer,
+        fetch_policy.as_ref(),
+        max_iterations,
+        step_timeout,
+        stream_tokens,
     )
     .await;
 
+    let mut narration_only = true;
+    loop {
+        match step_rx.try_recv() {
+            Ok(event) => narration_only &= is_chatter(&event.payload),
+            Err(broadcast::error::TryRecvError::Lagged(_)) => narration_only = false,
+            Err(_) => break,
+        }
+    }
+    runner.narration_streak = if narration_only {
+        runner.narration_streak + 1
+    } else {
+        0
+    };
+
+    let (tool_calls, reason) = match runner.last_step {
+        Some(metrics) if metrics.hit_iteration_limit => (metrics.tool_calls, "iteration limit"),
+        Some(metrics) if metrics.tool_calls > 0 => (metrics.tool_calls, "tools used"),
+        Some(_) => (0, "no tool calls"),
+        None => {
+            runner.last_step = previous_step;
+            (0, "error")
+        }
+    };
+    let entry = StepTraceEntry {
+        agent_id: runner.agent_id.clone(),
+        timestamp: Utc::now(),
+        prompt_chars: runner.last_prompt_chars,
+        tool_calls,
+        did_work,
+        reason: reason.to_string(),
+    };
+
     // Put the runner and its (now-drained) receiver back
     {
         let mut s = swarm.lock().await;
+        s.step_trace.record(entry);
         s.agents[index] = Some(runner);
         s.event_receivers[index] = event_rx;
     }
@@ -698,6 +1387,29 @@ async fn run_agent_by_index(
     did_work
 }
 
+/// Whether an agent's tokens are streamed to the UI as they arrive: always
+/// for the manager (the user's point of contact), and for every other agent
+/// when `stream_tokens` is on.
+fn streams_text(role: AgentRole, stream_tokens: bool) -> bool {
+    role == AgentRole::Manager || stream_tokens
+}
+
+/// True for events that carry no work for other agents: step bookkeeping,
+/// streaming, and narration posted by agents. Anything else (card or core
+/// changes, questions, answers, human messages, phase changes) counts as work.
+fn is_chatter(payload: &EventPayload) -> bool {
+    match payload {
+        EventPayload::AgentStepStarted { .. }
+        | EventPayload::AgentStepFinished { .. }
+        | EventPayload::StreamingDelta { .. }
+        | EventPayload::StreamingToolActivity { .. }
+        | EventPayload::UsageRecorded { .. }
+        | EventPayload::SnapshotWritten { .. } => true,
+        EventPayload::TranscriptAppended { message } => message.sender != "human",
+        _ => false,
+    }
+}
+
 /// Find the index of the manager agent (first agent with AgentRole::Manager).
 fn find_manager_index(swarm: &SwarmOrchestrator) -> Option<usize> {
     swarm.agents.iter().position(|opt| {
@@ -770,6 +1482,52 @@ async fn drain_transition_answers(
     }
 }
 
+/// Run each agent in the swarm once, applying pause, phase, question, and
+/// condensed-cycle gating. Returns true if any agent did work.
+async fn run_cycle(swarm: &Arc<tokio::sync::Mutex<SwarmOrchestrator>>, agent_count: usize) -> bool {
+    let mut any_work = false;
+    for i in 0..agent_count {
+        // Check pause before each agent
+        {
+            let s = swarm.lock().await;
+            if s.is_paused() {
+                break;
+            }
+        }
+
+        // Phase gating: skip non-Manager agents during brainstorming
+        {
+            let s = swarm.lock().await;
+            let phase = s.actor.read_state().await.phase.clone();
+            if phase == SpecPhase::Brainstorming
+                && let Some(Some(agent)) = s.agents.get(i)
+                && agent.role != AgentRole::Manager
+            {
+                continue;
+            }
+        }
+
+        // Question gating: skip all agents while a question is pending.
+        // The user needs to answer before agents can make progress.
+        // The loop will wake immediately via human_message_notify when
+        // the answer arrives.
+        {
+            let s = swarm.lock().await;
+            if s.has_pending_question() {
+                continue;
+            }
+        }
+
+        let did_work = run_agent_by_index(swarm, i).await;
+
+        if did_work {
+            any_work = true;
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+    any_work
+}
+
 /// Run the agent loop. This drives all agents in the swarm through their
 /// think-act cycles. Runs until the task is cancelled (via JoinHandle::abort).
 ///
@@ -794,15 +1552,31 @@ pub async fn run_loop(swarm: Arc<tokio::sync::Mutex<SwarmOrchestrator>>) {
         (s.actor.subscribe(), s.actor.subscribe())
     };
 
+    // Record which provider/model this swarm runs on so agent-produced
+    // events in the log can be attributed to it.
+    {
+        let s = swarm.lock().await;
+        let pin = Command::PinAgentModel {
+            provider: s.provider.clone(),
+            model: s.model.clone(),
+        };
+        if let Err(e) = s.actor.send_command(pin).await {
+            tracing::warn!(spec_id = %s.spec_id, error = %e, "failed to pin agent model");
+        }
+    }
+
     loop {
-        // Recover any empty slots from prior cancellations, then check pause.
-        let (is_paused, agent_count, notify) = {
+        // Recover any empty slots from prior cancellations, stop a focus run
+        // whose time is up, then check pause.
+        let (is_paused, agent_count, notify, run_deadline) = {
             let mut s = swarm.lock().await;
             s.recover_empty_slots();
+            s.enforce_run_deadline().await;
             (
                 s.is_paused(),
                 s.agents.len(),
                 Arc::clone(&s.human_message_notify),
+                s.run_deadline,
             )
         };
 
@@ -811,46 +1585,7 @@ pub async fn run_loop(swarm: Arc<tokio::sync::Mutex<SwarmOrchestrator>>) {
             continue;
         }
 
-        let mut any_work = false;
-        for i in 0..agent_count {
-            // Check pause before each agent
-            {
-                let s = swarm.lock().await;
-                if s.is_paused() {
-                    break;
-                }
-            }
-
-            // Phase gating: skip non-Manager agents during brainstorming
-            {
-                let s = swarm.lock().await;
-                let phase = s.actor.read_state().await.phase.clone();
-                if phase == SpecPhase::Brainstorming
-                    && let Some(Some(agent)) = s.agents.get(i)
-                    && agent.role != AgentRole::Manager
-                {
-                    continue;
-                }
-            }
-
-            // Question gating: skip all agents while a question is pending.
-            // The user needs to answer before agents can make progress.
-            // The loop will wake immediately via human_message_notify when
-            // the answer arrives.
-            {
-                let s = swarm.lock().await;
-                if s.has_pending_question() {
-                    continue;
-                }
-            }
-
-            let did_work = run_agent_by_index(&swarm, i).await;
-
-            if did_work {
-                any_work = true;
-                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-            }
-        }
+        let any_work = run_cycle(&swarm, agent_count).await;
 
         // Check for transition question answers buffered during the for-loop.
         drain_transition_answers(&swarm, &mut phase_rx).await;
@@ -860,11 +1595,16 @@ pub async fn run_loop(swarm: Arc<tokio::sync::Mutex<SwarmOrchestrator>>) {
         // `wake_rx` is a separate subscriber from `phase_rx` so consuming
         // wake-up events here doesn't drop the `QuestionAnswered` events
         // that the transition watcher above relies on.
-        let sleep_duration = if any_work {
+        let mut sleep_duration = if any_work {
             std::time::Duration::from_secs(1)
         } else {
             std::time::Duration::from_secs(5)
         };
+        // Wake in time to stop a focus run on schedule.
+        if let Some(deadline) = run_deadline {
+            sleep_duration =
+                sleep_duration.min(deadline.saturating_duration_since(tokio::time::Instant::now()));
+        }
 
         tokio::select! {
             _ = tokio::time::sleep(sleep_duration) => {}
@@ -900,6 +1640,55 @@ pub async fn run_loop(swarm: Arc<tokio::sync::Mutex<SwarmOrchestrator>>) {
     }
 }
 
+/// "10 minutes", "1 minute", or "30 seconds" for run-length narration.
+fn format_run_length(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        60.. if secs.is_multiple_of(60) => {
+            let minutes = secs / 60;
+            format!("{} minute{}", minutes, if minutes == 1 { "" } else { "s" })
+        }
+        _ => format!("{} second{}", secs, if secs == 1 { "" } else { "s" }),
+    }
+}
+
+/// One-line state summary at the top of every task prompt. None until the
+/// spec has a core.
+fn state_summary(state: &SpecState) -> Option<String> {
+    let core = state.core.as_ref()?;
+    Some(format!(
+        "Title: {}. Goal: {}. Cards: {}. Pending questions: {}",
+        core.title,
+        core.goal,
+        state.cards.len(),
+        state.pending_questions.len()
+    ))
+}
+
+/// Approximate size in characters of the prompt an agent in `role` would
+/// be sent for `state`: its system prompt plus a task prompt built the way
+/// `refresh_context_with_flag` builds one. Recent events and the rolling
+/// summary live only in a running agent's context and are left out, so
+/// this undercounts an agent that has been busy for a while.
+pub fn estimated_prompt_chars(
+    state: &SpecState,
+    role: AgentRole,
+    windows: ContextWindows,
+) -> usize {
+    let agent_id = format!("{}-estimate", role.label());
+    let mut ctx = AgentContext::new(Ulid::nil(), agent_id.clone(), role);
+    ctx.state_summary = state_summary(state).unwrap_or_default();
+    let start = state.transcript.len().saturating_sub(windows.transcript);
+    ctx.recent_transcript = state.transcript[start..].to_vec();
+    ctx.context_attachments = state
+        .context_attachments
+        .iter()
+        .filter(|a| !a.removed)
+        .cloned()
+        .collect();
+    full_system_prompt(&role, &agent_id, &state.phase).len() + build_task_prompt(&ctx).len()
+}
+
 /// Build a task prompt string from the agent's current context.
 ///
 /// Combines the state summary, recent events, and rolling summary into
@@ -961,6 +1750,25 @@ fn build_task_prompt(ctx: &AgentContext) -> String {
     }
 }
 
+/// Render lint findings as a prompt section for the critic. Empty when the
+/// spec is clean.
+fn render_lint_section(findings: &[barnstormer_core::lint::LintFinding]) -> String {
+    if findings.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = findings
+        .iter()
+        .map(|f| match f.card_id {
+            Some(card_id) => format!("  - [{}] {} (card {})", f.rule.label(), f.message, card_id),
+            None => format!("  - [{}] {}", f.rule.label(), f.message),
+        })
+        .collect();
+    format!(
+        "\n\nSpec health check findings (deterministic lint):\n{}",
+        lines.join("\n")
+    )
+}
+
 /// Render the `## Context Files` section that `build_task_prompt` injects
 /// into the agent's task prompt. Returns an empty string when there are no
 /// attachments so callers can skip the section cleanly. Exposed publicly so
@@ -1088,12 +1896,50 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn swarm_pause_resume() {
+    async fn custom_roster_recovers_emptied_slots_to_their_roles() {
         let (spec_id, actor) = make_test_actor();
-        let swarm = SwarmOrchestrator::with_agents(
+        // Five agents in a non-default order; the last two slots have no
+        // counterpart among the four default roles.
+        let roles = [
+            AgentRole::Critic,
+            AgentRole::Manager,
+            AgentRole::Planner,
+            AgentRole::Brainstormer,
+            AgentRole::Critic,
+        ];
+        let agents: Vec<AgentRunner> = roles
+            .iter()
+            .map(|role| AgentRunner::new(spec_id, *role))
+            .collect();
+        let mut swarm = SwarmOrchestrator::with_agents(
             spec_id,
             actor,
-            Vec::new(),
+            agents,
+            make_test_client(),
+            "stub-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+
+        swarm.agents[0] = None;
+        swarm.agents[4] = None;
+        swarm.recover_empty_slots();
+
+        let recovered: Vec<AgentRole> = swarm
+            .agents
+            .iter()
+            .map(|slot| slot.as_ref().expect("slot should be restored").role)
+            .collect();
+        assert_eq!(recovered, roles);
+    }
+
+    #[tokio::test]
+    async fn swarm_pause_resume() {
+        let (spec_id, actor) = make_test_actor();
+        let swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            actor,
+            Vec::new(),
             make_test_client(),
             "stub-model".to_string(),
             PathBuf::from("/tmp/barnstormer-test"),
@@ -1121,6 +1967,7 @@ mod tests {
 
         let home = PathBuf::from("/tmp/barnstormer-test");
         let summarizer = make_test_summarizer();
+        let steps_before = crate::metrics::agent_step_counts().steps;
         let did_work = SwarmOrchestrator::run_agent_step(
             &mut runner,
             &actor_arc,
@@ -1131,11 +1978,161 @@ mod tests {
             &SpecPhase::Refining,
             &home,
             &summarizer,
+            None,
+            DEFAULT_AGENT_MAX_ITERATIONS,
+            DEFAULT_AGENT_STEP_TIMEOUT,
+            false,
         )
         .await;
 
         // StubLlmClient returns text-only (no tool use), so agent does no tool work
         assert!(!did_work);
+        assert!(crate::metrics::agent_step_counts().steps > steps_before);
+    }
+
+    /// Shared buffer that a tracing fmt subscriber writes into.
+    #[derive(Clone, Default)]
+    struct LogBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn run_agent_step_flags_iteration_limit() {
+        let logs = LogBuffer::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let (spec_id, actor) = make_test_actor();
+        let actor_arc = Arc::new(actor);
+        let client: Arc<dyn LlmClient> =
+            Arc::new(crate::testing::ToolLoopLlmClient::new("read_state"));
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Brainstormer);
+
+        SwarmOrchestrator::run_agent_step(
+            &mut runner,
+            &actor_arc,
+            &Arc::new(AtomicBool::new(false)),
+            &Arc::new(Mutex::new(None)),
+            &client,
+            "stub-model",
+            &SpecPhase::Refining,
+            &PathBuf::from("/tmp/barnstormer-test"),
+            &make_test_summarizer(),
+            None,
+            3,
+            DEFAULT_AGENT_STEP_TIMEOUT,
+            false,
+        )
+        .await;
+
+        let metrics = runner.last_step.expect("step metrics recorded");
+        assert_eq!(metrics.iterations, 3);
+        assert!(metrics.hit_iteration_limit);
+        assert_eq!(runner.iteration_limit_hits, 1);
+
+        let state = actor_arc.read_state().await;
+        assert!(
+            state
+                .transcript
+                .iter()
+                .any(|m| m.sender == runner.agent_id && m.content.contains("ran out of steps")),
+            "expected a transcript note about the iteration limit"
+        );
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("WARN"), "missing warning: {output}");
+        assert!(output.contains("agent step hit iteration limit"));
+    }
+
+    #[tokio::test]
+    async fn run_agent_step_gives_up_after_its_timeout() {
+        let (spec_id, actor) = make_test_actor();
+        let actor_arc = Arc::new(actor);
+        let client: Arc<dyn LlmClient> = Arc::new(crate::testing::StubLlmClient::delayed(
+            "Done.",
+            std::time::Duration::from_secs(30),
+        ));
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Brainstormer);
+        let errors_before = crate::metrics::agent_step_counts().errors;
+
+        let started = std::time::Instant::now();
+        let did_work = SwarmOrchestrator::run_agent_step(
+            &mut runner,
+            &actor_arc,
+            &Arc::new(AtomicBool::new(false)),
+            &Arc::new(Mutex::new(None)),
+            &client,
+            "stub-model",
+            &SpecPhase::Refining,
+            &PathBuf::from("/tmp/barnstormer-test"),
+            &make_test_summarizer(),
+            None,
+            DEFAULT_AGENT_MAX_ITERATIONS,
+            std::time::Duration::from_millis(50),
+            false,
+        )
+        .await;
+
+        assert!(!did_work);
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        assert!(runner.last_step.is_none());
+        assert!(crate::metrics::agent_step_counts().errors > errors_before);
+        let state = actor_arc.read_state().await;
+        assert!(
+            state
+                .transcript
+                .iter()
+                .any(|m| m.sender == runner.agent_id && m.content.contains("timed out")),
+            "expected a transcript note about the timeout"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_agent_step_under_limit_is_not_flagged() {
+        let (spec_id, actor) = make_test_actor();
+        let actor_arc = Arc::new(actor);
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Brainstormer);
+
+        SwarmOrchestrator::run_agent_step(
+            &mut runner,
+            &actor_arc,
+            &Arc::new(AtomicBool::new(false)),
+            &Arc::new(Mutex::new(None)),
+            &make_test_client(),
+            "stub-model",
+            &SpecPhase::Refining,
+            &PathBuf::from("/tmp/barnstormer-test"),
+            &make_test_summarizer(),
+            None,
+            DEFAULT_AGENT_MAX_ITERATIONS,
+            DEFAULT_AGENT_STEP_TIMEOUT,
+            false,
+        )
+        .await;
+
+        let metrics = runner.last_step.expect("step metrics recorded");
+        assert!(!metrics.hit_iteration_limit);
+        assert_eq!(runner.iteration_limit_hits, 0);
+        let state = actor_arc.read_state().await;
+        assert!(
+            !state
+                .transcript
+                .iter()
+                .any(|m| m.content.contains("ran out of steps"))
+        );
     }
 
     #[tokio::test]
@@ -1161,6 +2158,58 @@ mod tests {
         assert!(runner.context.last_event_seen > 0);
     }
 
+    #[tokio::test]
+    async fn refresh_context_honours_configured_windows() {
+        let (spec_id, actor) = make_test_actor();
+        let mut event_rx = actor.subscribe();
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Manager);
+
+        actor
+            .send_command(Command::CreateSpec {
+                title: "Windowed".to_string(),
+                one_liner: "w".to_string(),
+                goal: "g".to_string(),
+            })
+            .await
+            .unwrap();
+        for i in 1..=6 {
+            actor
+                .send_command(Command::AppendTranscript {
+                    sender: "human".to_string(),
+                    content: format!("message {i}"),
+                    display_name: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        SwarmOrchestrator::refresh_context_with_flag(
+            &mut runner,
+            &actor,
+            &mut event_rx,
+            None,
+            ContextWindows {
+                transcript: 3,
+                events: 2,
+            },
+        )
+        .await;
+
+        let contents: Vec<&str> = runner
+            .context
+            .recent_transcript
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["message 4", "message 5", "message 6"]);
+        assert_eq!(runner.context.recent_events.len(), 2);
+        // The rolling summary still advanced past every drained event.
+        assert_eq!(
+            runner.context.last_event_seen,
+            runner.context.recent_events[1].event_id
+        );
+    }
+
     #[test]
     fn system_prompt_for_role_returns_non_empty() {
         let roles = [
@@ -1298,6 +2347,30 @@ mod tests {
         assert!(prompt.contains("take the next appropriate action"));
     }
 
+    #[test]
+    fn estimated_prompt_chars_follows_the_transcript_window() {
+        use barnstormer_core::transcript::TranscriptMessage;
+
+        let windows = ContextWindows {
+            transcript: 2,
+            events: 2,
+        };
+        let mut state = SpecState::new();
+        let empty = estimated_prompt_chars(&state, AgentRole::Planner, windows);
+        assert!(empty > system_prompt_for_role(&AgentRole::Planner).len());
+
+        let message = |n: usize| TranscriptMessage::new("human".to_string(), "x".repeat(n));
+        state.transcript.push(message(500));
+        let one = estimated_prompt_chars(&state, AgentRole::Planner, windows);
+        assert!(one >= empty + 500);
+
+        // Only the last `windows.transcript` messages count.
+        state.transcript.insert(0, message(5_000));
+        state.transcript.push(message(10));
+        let windowed = estimated_prompt_chars(&state, AgentRole::Planner, windows);
+        assert!(windowed > one && windowed < one + 5_000);
+    }
+
     #[test]
     fn task_prompt_includes_context_files_section_when_present() {
         use barnstormer_core::state::ContextAttachment;
@@ -1324,6 +2397,23 @@ mod tests {
         assert!(prompt.contains("retrieve_context"));
     }
 
+    #[test]
+    fn lint_section_lists_findings_with_card_ids() {
+        let card_id = Ulid::new();
+        let findings = vec![barnstormer_core::lint::LintFinding {
+            rule: barnstormer_core::lint::LintRule::EmptyCardBody,
+            card_id: Some(card_id),
+            message: "task \"X\" has no body.".to_string(),
+        }];
+        let section = render_lint_section(&findings);
+        assert!(section.contains("Spec health check findings"));
+        assert!(section.contains(&format!(
+            "[Empty card body] task \"X\" has no body. (card {})",
+            card_id
+        )));
+        assert!(render_lint_section(&[]).is_empty());
+    }
+
     #[test]
     fn task_prompt_omits_context_section_when_empty() {
         let ctx = AgentContext::new(Ulid::new(), "test-agent".to_string(), AgentRole::Manager);
@@ -1584,6 +2674,7 @@ mod tests {
             &actor,
             &mut event_rx,
             Some(&question_pending),
+            ContextWindows::default(),
         )
         .await;
 
@@ -1594,6 +2685,51 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn question_pending_stays_set_until_queue_empties() {
+        let (spec_id, actor) = make_test_actor();
+        let question_pending = AtomicBool::new(true);
+        let ids = [Ulid::new(), Ulid::new()];
+        for question_id in ids {
+            actor
+                .send_command(Command::AskQuestion {
+                    question: barnstormer_core::transcript::UserQuestion::Freeform {
+                        question_id,
+                        question: "What color?".to_string(),
+                        placeholder: None,
+                        validation_hint: None,
+                    },
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut event_rx = actor.subscribe();
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Manager);
+        for (i, question_id) in ids.into_iter().enumerate() {
+            actor
+                .send_command(Command::AnswerQuestion {
+                    question_id,
+                    answer: "Blue".to_string(),
+                })
+                .await
+                .unwrap();
+            SwarmOrchestrator::refresh_context_with_flag(
+                &mut runner,
+                &actor,
+                &mut event_rx,
+                Some(&question_pending),
+                ContextWindows::default(),
+            )
+            .await;
+            assert_eq!(
+                question_pending.load(Ordering::SeqCst),
+                i == 0,
+                "flag should clear only after the last queued answer"
+            );
+        }
+    }
+
     #[tokio::test]
     async fn drain_transition_answers_fires_transition_on_matching_yes() {
         // Direct unit test on the helper that both run_loop drain points share.
@@ -1827,6 +2963,77 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_loop_pauses_when_focus_run_ends() {
+        let (spec_id, actor) = make_test_actor();
+        actor
+            .send_command(Command::CreateSpec {
+                title: "Test".to_string(),
+                one_liner: "t".to_string(),
+                goal: "g".to_string(),
+            })
+            .await
+            .unwrap();
+        let swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            actor,
+            Vec::new(),
+            make_test_client(),
+            "stub-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        let actor_handle = Arc::clone(&swarm.actor);
+        swarm.pause();
+
+        let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+        let handle = tokio::spawn(run_loop(Arc::clone(&swarm)));
+        swarm
+            .lock()
+            .await
+            .run_for(std::time::Duration::from_millis(150))
+            .await;
+        assert!(!swarm.lock().await.is_paused());
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(3);
+        while !swarm.lock().await.is_paused() {
+            if std::time::Instant::now() > deadline {
+                handle.abort();
+                panic!("swarm was not paused after the focus run ended");
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        handle.abort();
+        let _ = handle.await;
+
+        assert!(swarm.lock().await.run_deadline.is_none());
+        let transcript: Vec<String> = actor_handle
+            .read_state()
+            .await
+            .transcript
+            .iter()
+            .map(|m| m.content.clone())
+            .collect();
+        assert!(
+            transcript
+                .iter()
+                .any(|c| c.starts_with("Focus run started"))
+        );
+        assert!(
+            transcript
+                .iter()
+                .any(|c| c.starts_with("Focus run finished"))
+        );
+    }
+
+    #[test]
+    fn run_length_reads_naturally() {
+        use std::time::Duration;
+        assert_eq!(format_run_length(Duration::from_secs(600)), "10 minutes");
+        assert_eq!(format_run_length(Duration::from_secs(60)), "1 minute");
+        assert_eq!(format_run_length(Duration::from_secs(30)), "30 seconds");
+    }
+
     #[tokio::test]
     async fn run_loop_can_be_cancelled() {
         let (spec_id, actor) = make_test_actor();
@@ -2234,6 +3441,538 @@ mod tests {
         assert_eq!(swarm.agents.iter().flatten().count(), 3);
     }
 
+    #[test]
+    fn only_the_manager_streams_tokens_unless_enabled_for_all() {
+        assert!(streams_text(AgentRole::Manager, false));
+        assert!(!streams_text(AgentRole::Brainstormer, false));
+        assert!(!streams_text(AgentRole::Critic, false));
+        assert!(streams_text(AgentRole::Brainstormer, true));
+        assert!(streams_text(AgentRole::Planner, true));
+    }
+
+    /// Stub client that counts provider round trips, buffered or streaming.
+    struct CountingLlmClient {
+        inner: StubLlmClient,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl LlmClient for CountingLlmClient {
+        async fn create_message(
+            &self,
+            req: &mux::llm::Request,
+        ) -> Result<mux::llm::Response, mux::error::LlmError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.create_message(req).await
+        }
+
+        fn create_message_stream(
+            &self,
+            req: &mux::llm::Request,
+        ) -> std::pin::Pin<
+            Box<
+                dyn futures::Stream<Item = Result<mux::llm::StreamEvent, mux::error::LlmError>>
+                    + Send
+                    + 'static,
+            >,
+        > {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.create_message_stream(req)
+        }
+    }
+
+    /// Run `cycles` swarm cycles over a manager plus three agents that only
+    /// ever narrate, returning (provider calls, manager narration streak).
+    async fn narration_only_cycles(condensed: bool, cycles: usize) -> (usize, u32) {
+        let (spec_id, handle) = make_test_actor();
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Test".to_string(),
+                one_liner: "t".to_string(),
+                goal: "g".to_string(),
+            })
+            .await
+            .unwrap();
+        handle
+            .send_command(Command::TransitionPhase {
+                target: SpecPhase::Refining,
+            })
+            .await
+            .unwrap();
+
+        let client = Arc::new(CountingLlmClient {
+            inner: StubLlmClient::done(),
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let agents = vec![
+            AgentRunner::new(spec_id, AgentRole::Manager),
+            AgentRunner::new(spec_id, AgentRole::Brainstormer),
+            AgentRunner::new(spec_id, AgentRole::Planner),
+            AgentRunner::new(spec_id, AgentRole::DotGenerator),
+        ];
+        let mut swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle,
+            agents,
+            Arc::clone(&client) as Arc<dyn LlmClient>,
+            "test-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        swarm.condensed_cycles = condensed;
+        let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+
+        for _ in 0..cycles {
+            run_cycle(&swarm, 4).await;
+        }
+
+        let manager_streak = swarm.lock().await.agents[0]
+            .as_ref()
+            .unwrap()
+            .narration_streak;
+        (client.calls.load(Ordering::SeqCst), manager_streak)
+    }
+
+    #[tokio::test]
+    async fn condensed_cycles_skip_narration_only_agents_but_not_manager() {
+        let (default_calls, default_manager_steps) = narration_only_cycles(false, 3).await;
+        let (condensed_calls, condensed_manager_steps) = narration_only_cycles(true, 3).await;
+
+        assert!(
+            condensed_calls < default_calls,
+            "condensed mode should make fewer provider calls ({} vs {})",
+            condensed_calls,
+            default_calls
+        );
+        // The manager ran (and only narrated) in every cycle in both modes.
+        assert_eq!(default_manager_steps, 3);
+        assert_eq!(condensed_manager_steps, 3);
+    }
+
+    #[tokio::test]
+    async fn step_trace_records_each_agent_step() {
+        let (spec_id, handle) = make_test_actor();
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Trace".to_string(),
+                one_liner: "t".to_string(),
+                goal: "g".to_string(),
+            })
+            .await
+            .unwrap();
+        let manager = AgentRunner::new(spec_id, AgentRole::Manager);
+        let planner = AgentRunner::new(spec_id, AgentRole::Planner);
+        let (manager_id, planner_id) = (manager.agent_id.clone(), planner.agent_id.clone());
+
+        let mut swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle,
+            vec![manager, planner],
+            Arc::new(crate::testing::ToolLoopLlmClient::new("read_state")),
+            "test-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        swarm.max_iterations = 2;
+        let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+
+        // The manager calls tools; the planner runs against a client that
+        // only answers with text.
+        assert!(run_agent_by_index(&swarm, 0).await);
+        swarm.lock().await.client = make_test_client();
+        assert!(!run_agent_by_index(&swarm, 1).await);
+
+        let trace = swarm.lock().await.step_trace.entries();
+        assert_eq!(trace.len(), 2);
+        assert_eq!(trace[0].agent_id, manager_id);
+        assert!(trace[0].did_work);
+        assert_eq!(trace[0].tool_calls, 2);
+        assert_eq!(trace[0].reason, "iteration limit");
+        assert!(trace[0].prompt_chars > 0);
+        assert_eq!(trace[1].agent_id, planner_id);
+        assert!(!trace[1].did_work);
+        assert_eq!(trace[1].reason, "no tool calls");
+    }
+
+    #[tokio::test]
+    async fn disabled_agent_is_skipped_while_others_run() {
+        let (spec_id, handle) = make_test_actor();
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Toggle".to_string(),
+                one_liner: "t".to_string(),
+                goal: "g".to_string(),
+            })
+            .await
+            .unwrap();
+        let manager = AgentRunner::new(spec_id, AgentRole::Manager);
+        let dot = AgentRunner::new(spec_id, AgentRole::DotGenerator);
+        let dot_id = dot.agent_id.clone();
+
+        let mut swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle,
+            vec![manager, dot],
+            Arc::new(crate::testing::ToolLoopLlmClient::new("read_state")),
+            "test-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        swarm.max_iterations = 2;
+        assert_eq!(swarm.toggle_agent("dot_generator"), Some(false));
+        assert_eq!(swarm.toggle_agent("nobody"), None);
+        assert_eq!(
+            swarm.agent_roles_enabled(),
+            vec![(AgentRole::Manager, true), (AgentRole::DotGenerator, false)]
+        );
+        let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+
+        assert!(run_agent_by_index(&swarm, 0).await, "manager still runs");
+        assert!(!run_agent_by_index(&swarm, 1).await);
+        {
+            let s = swarm.lock().await;
+            assert!(s.agents[1].is_some(), "disabled runner stays in its slot");
+            let trace = s.step_trace.entries();
+            assert!(trace.iter().all(|e| e.agent_id != dot_id));
+        }
+
+        // Re-enabled, it runs again.
+        assert!(swarm.lock().await.set_agent_enabled("dot_generator", true));
+        assert!(run_agent_by_index(&swarm, 1).await);
+    }
+
+    #[tokio::test]
+    async fn step_budget_pauses_the_swarm_until_resumed() {
+        let (spec_id, handle) = make_test_actor();
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Budget".to_string(),
+                one_liner: "t".to_string(),
+                goal: "g".to_string(),
+            })
+            .await
+            .unwrap();
+        let mut swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle,
+            vec![
+                AgentRunner::new(spec_id, AgentRole::Manager),
+                AgentRunner::new(spec_id, AgentRole::Brainstormer),
+                AgentRunner::new(spec_id, AgentRole::Planner),
+            ],
+            make_test_client(),
+            "stub-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        swarm.max_steps = Some(2);
+        let actor_handle = Arc::clone(&swarm.actor);
+        let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+
+        run_agent_by_index(&swarm, 0).await;
+        run_agent_by_index(&swarm, 1).await;
+        assert!(!swarm.lock().await.is_paused());
+        assert!(!run_agent_by_index(&swarm, 2).await);
+        {
+            let s = swarm.lock().await;
+            assert!(s.is_paused());
+            assert!(s.step_budget_reached());
+            assert_eq!(s.steps_taken.load(Ordering::SeqCst), 2);
+            assert_eq!(s.step_trace.entries().len(), 2, "third step never ran");
+        }
+        let transcript = actor_handle.read_state().await.transcript.clone();
+        assert!(
+            transcript
+                .iter()
+                .any(|m| m.content.starts_with("Step budget reached"))
+        );
+
+        let s = swarm.lock().await;
+        s.resume();
+        assert!(!s.is_paused());
+        assert!(!s.step_budget_reached());
+        assert_eq!(s.steps_taken.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn swarm_resume_leaves_individually_paused_agents_paused() {
+        let (spec_id, handle) = make_test_actor();
+        let mut swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle,
+            vec![
+                AgentRunner::new(spec_id, AgentRole::Brainstormer),
+                AgentRunner::new(spec_id, AgentRole::Planner),
+            ],
+            Arc::new(crate::testing::ToolLoopLlmClient::new("read_state")),
+            "test-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        assert!(swarm.pause_agent("brainstormer"));
+        assert!(!swarm.pause_agent("critic"));
+
+        swarm.pause();
+        swarm.resume();
+        assert!(!swarm.is_paused());
+        assert_eq!(swarm.is_role_enabled("brainstormer"), Some(false));
+        assert_eq!(swarm.enabled_slot_roles(), vec![AgentRole::Planner]);
+
+        assert!(swarm.resume_agent("brainstormer"));
+        assert_eq!(swarm.is_role_enabled("brainstormer"), Some(true));
+    }
+
+    #[test]
+    fn step_trace_keeps_only_the_most_recent_steps_per_agent() {
+        let mut trace = StepTrace::default();
+        for i in 0..STEP_TRACE_CAPACITY + 5 {
+            trace.record(StepTraceEntry {
+                agent_id: "planner-1".to_string(),
+                timestamp: Utc::now(),
+                prompt_chars: i,
+                tool_calls: 0,
+                did_work: false,
+                reason: "no tool calls".to_string(),
+            });
+        }
+        let entries = trace.entries();
+        assert_eq!(entries.len(), STEP_TRACE_CAPACITY);
+        assert_eq!(entries[0].prompt_chars, 5);
+    }
+
+    #[tokio::test]
+    async fn condensed_cycles_do_not_skip_agent_with_pending_work() {
+        let (spec_id, handle) = make_test_actor();
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Planner);
+        runner.narration_streak = CONDENSE_AFTER_NARRATION_STEPS;
+        runner.context.last_event_seen = 1;
+
+        let mut swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle,
+            vec![runner],
+            make_test_client(),
+            "test-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        swarm.condensed_cycles = true;
+
+        // A human message is pending work the planner must see.
+        swarm
+            .actor
+            .send_command(Command::AppendTranscript {
+                sender: "human".to_string(),
+                content: "Please break this down".to_string(),
+                display_name: None,
+            })
+            .await
+            .unwrap();
+
+        let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+        run_agent_by_index(&swarm, 0).await;
+
+        // The step ran (and only narrated), so the streak grew rather than reset.
+        let streak = swarm.lock().await.agents[0]
+            .as_ref()
+            .unwrap()
+            .narration_streak;
+        assert_eq!(streak, CONDENSE_AFTER_NARRATION_STEPS + 1);
+    }
+
+    #[test]
+    fn parse_roles_skips_unknown_names() {
+        assert_eq!(
+            parse_roles(" Manager, planner,wizard,,planner "),
+            vec![AgentRole::Manager, AgentRole::Planner, AgentRole::Planner]
+        );
+        assert!(parse_roles("wizard").is_empty());
+    }
+
+    #[test]
+    fn roles_or_default_falls_back_when_no_label_is_known() {
+        assert_eq!(
+            roles_or_default(&["critic".to_string(), "manager".to_string()]),
+            vec![AgentRole::Critic, AgentRole::Manager]
+        );
+        assert_eq!(roles_or_default(&[]), DEFAULT_ROLES.to_vec());
+        assert_eq!(
+            roles_or_default(&["wizard".to_string()]),
+            DEFAULT_ROLES.to_vec()
+        );
+    }
+
+    #[test]
+    fn roster_roles_prefers_the_spec_roster() {
+        let mut state = SpecState::new();
+        assert_eq!(roster_roles(&state, &DEFAULT_ROLES), DEFAULT_ROLES.to_vec());
+
+        state.agent_roster = Some(vec![
+            "critic".to_string(),
+            "wizard".to_string(),
+            "manager".to_string(),
+        ]);
+        assert_eq!(
+            roster_roles(&state, &DEFAULT_ROLES),
+            vec![AgentRole::Critic, AgentRole::Manager]
+        );
+
+        state.agent_roster = Some(vec!["wizard".to_string()]);
+        assert_eq!(
+            roster_roles(&state, &[AgentRole::Planner]),
+            vec![AgentRole::Planner]
+        );
+    }
+
+    #[tokio::test]
+    async fn with_roles_runs_exactly_the_given_roster() {
+        let (spec_id, handle) = make_test_actor();
+        let mut swarm = SwarmOrchestrator::with_roles(
+            spec_id,
+            handle,
+            &[AgentRole::Manager, AgentRole::Critic],
+            PathBuf::from("/tmp"),
+            make_test_summarizer(),
+            "ollama",
+            None,
+        )
+        .expect("ollama needs no API key");
+        assert_eq!(
+            swarm.enabled_slot_roles(),
+            vec![AgentRole::Manager, AgentRole::Critic]
+        );
+
+        // A recovered slot gets its configured role back.
+        swarm.agents[1] = None;
+        swarm.recover_empty_slots();
+        assert_eq!(swarm.agents[1].as_ref().unwrap().role, AgentRole::Critic);
+
+        // Snapshot contexts are restored onto whichever roles are present.
+        let mut planner = AgentContext::new(spec_id, "planner-old".to_string(), AgentRole::Planner);
+        planner.rolling_summary = "planner memory".to_string();
+        let mut critic = AgentContext::new(spec_id, "critic-old".to_string(), AgentRole::Critic);
+        critic.rolling_summary = "critic memory".to_string();
+        swarm.restore_agent_contexts(&crate::context::contexts_to_snapshot_map(&[
+            planner, critic,
+        ]));
+        let summaries: Vec<&str> = swarm
+            .agents
+            .iter()
+            .flatten()
+            .map(|r| r.context.rolling_summary.as_str())
+            .collect();
+        assert_eq!(summaries, vec!["", "critic memory"]);
+
+        let (spec_id, handle) = make_test_actor();
+        assert!(
+            SwarmOrchestrator::with_roles(
+                spec_id,
+                handle,
+                &[],
+                PathBuf::from("/tmp"),
+                make_test_summarizer(),
+                "ollama",
+                None,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn model_overrides_read_per_role_variables() {
+        let roles = [
+            AgentRole::Manager,
+            AgentRole::Planner,
+            AgentRole::DotGenerator,
+        ];
+        let overrides = model_overrides(&roles, |var| match var {
+            "BARNSTORMER_MODEL_PLANNER" => Some(" cheap-model ".to_string()),
+            "BARNSTORMER_MODEL_DOT_GENERATOR" => Some("  ".to_string()),
+            _ => None,
+        });
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides["planner"], "cheap-model");
+    }
+
+    #[tokio::test]
+    async fn agents_resolve_their_own_model_or_the_swarm_default() {
+        let (spec_id, handle) = make_test_actor();
+        let manager = AgentRunner::new(spec_id, AgentRole::Manager);
+        let mut planner = AgentRunner::new(spec_id, AgentRole::Planner);
+        planner.model = Some("cheap-model".to_string());
+
+        let mut swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle,
+            vec![manager, planner],
+            make_test_client(),
+            "strong-model".to_string(),
+            PathBuf::from("/tmp/barnstormer-test"),
+            make_test_summarizer(),
+        );
+        let resolved: Vec<&str> = swarm
+            .agents
+            .iter()
+            .flatten()
+            .map(|r| r.resolved_model(&swarm.model))
+            .collect();
+        assert_eq!(resolved, vec!["strong-model", "cheap-model"]);
+
+        // Overrides survive a slot being recovered after cancellation.
+        swarm
+            .agent_models
+            .insert("planner".to_string(), "cheap-model".to_string());
+        swarm.agents[1] = None;
+        swarm.recover_empty_slots();
+        assert_eq!(
+            swarm.agents[1].as_ref().unwrap().model.as_deref(),
+            Some("cheap-model")
+        );
+    }
+
+    #[test]
+    fn parse_personas_maps_role_labels_to_names() {
+        let personas = parse_personas(" planner = Ada, Critic=Grace,broken,manager=");
+        assert_eq!(personas.len(), 2);
+        assert_eq!(personas["planner"], "Ada");
+        assert_eq!(personas["critic"], "Grace");
+    }
+
+    #[tokio::test]
+    async fn agent_with_persona_records_name_in_transcript() {
+        let (spec_id, actor) = make_test_actor();
+        let actor_arc = Arc::new(actor);
+        let client: Arc<dyn LlmClient> =
+            Arc::new(crate::testing::ToolLoopLlmClient::new("read_state"));
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Planner);
+        runner.persona_name = Some("Ada".to_string());
+
+        SwarmOrchestrator::run_agent_step(
+            &mut runner,
+            &actor_arc,
+            &Arc::new(AtomicBool::new(false)),
+            &Arc::new(Mutex::new(None)),
+            &client,
+            "stub-model",
+            &SpecPhase::Refining,
+            &PathBuf::from("/tmp/barnstormer-test"),
+            &make_test_summarizer(),
+            None,
+            1,
+            DEFAULT_AGENT_STEP_TIMEOUT,
+            false,
+        )
+        .await;
+
+        let state = actor_arc.read_state().await;
+        let note = state
+            .transcript
+            .iter()
+            .find(|m| m.sender == runner.agent_id && m.content.contains("ran out of steps"))
+            .expect("iteration limit note");
+        assert_eq!(note.display_name.as_deref(), Some("Ada"));
+    }
+
     #[test]
     fn should_transition_on_yes_answer() {
         let id = Ulid::new();
@@ -2319,4 +4058,53 @@ mod tests {
             "removing the Refining phase block from the Refining prompt should match the Brainstorming prompt"
         );
     }
+
+    #[tokio::test]
+    async fn with_defaults_builds_clients_for_per_spec_provider_overrides() {
+        let saved: Vec<(&str, Option<String>)> = ["ANTHROPIC_API_KEY", "OPENAI_API_KEY"]
+            .into_iter()
+            .map(|k| (k, std::env::var(k).ok()))
+            .collect();
+        let (anthropic_swarm, openai_swarm) = {
+            let _guard = crate::testing::ENV_MUTEX.lock().unwrap();
+            unsafe {
+                std::env::set_var("ANTHROPIC_API_KEY", "test-anthropic-key");
+                std::env::set_var("OPENAI_API_KEY", "test-openai-key");
+            }
+
+            let (spec_a, actor_a) = make_test_actor();
+            let (spec_b, actor_b) = make_test_actor();
+            let a = SwarmOrchestrator::with_defaults(
+                spec_a,
+                actor_a,
+                PathBuf::from("/tmp"),
+                make_test_summarizer(),
+                "anthropic",
+                None,
+            );
+            let b = SwarmOrchestrator::with_defaults(
+                spec_b,
+                actor_b,
+                PathBuf::from("/tmp"),
+                make_test_summarizer(),
+                "openai",
+                Some("llama3.1:8b"),
+            );
+
+            for (key, val) in &saved {
+                match val {
+                    Some(v) => unsafe { std::env::set_var(key, v) },
+                    None => unsafe { std::env::remove_var(key) },
+                }
+            }
+            (a, b)
+        };
+
+        let anthropic_swarm = anthropic_swarm.expect("anthropic swarm should build");
+        let openai_swarm = openai_swarm.expect("openai swarm should build");
+        assert_eq!(anthropic_swarm.provider, "anthropic");
+        assert_eq!(openai_swarm.provider, "openai");
+        assert_eq!(openai_swarm.model, "llama3.1:8b");
+        assert_ne!(anthropic_swarm.model, openai_swarm.model);
+    }
 }
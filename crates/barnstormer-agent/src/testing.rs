@@ -2,12 +2,18 @@
 // ABOUTME: Used in tests to simulate LLM responses without real API calls.
 
 use std::pin::Pin;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use futures::Stream;
 use mux::error::LlmError;
 use mux::llm::{ContentBlock, LlmClient, Request, Response, StopReason, StreamEvent, Usage};
 
+/// Serializes tests that read or write process env vars (API keys, model
+/// overrides) so they don't race each other across modules.
+#[cfg(test)]
+pub(crate) static ENV_MUTEX: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
 /// A stub LLM client that returns a pre-configured text response.
 ///
 /// Useful in tests to drive a SubAgent to immediate completion without
@@ -16,6 +22,7 @@ use mux::llm::{ContentBlock, LlmClient, Request, Response, StopReason, StreamEve
 #[derive(Debug, Clone)]
 pub struct StubLlmClient {
     response_text: String,
+    delay: Duration,
 }
 
 impl StubLlmClient {
@@ -23,6 +30,16 @@ impl StubLlmClient {
     pub fn new(response_text: &str) -> Self {
         Self {
             response_text: response_text.to_owned(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Create a stub client that waits `delay` before answering, to
+    /// simulate a stalled provider.
+    pub fn delayed(response_text: &str, delay: Duration) -> Self {
+        Self {
+            delay,
+            ..Self::new(response_text)
         }
     }
 
@@ -38,6 +55,9 @@ impl StubLlmClient {
 #[async_trait]
 impl LlmClient for StubLlmClient {
     async fn create_message(&self, _req: &Request) -> Result<Response, LlmError> {
+        if !self.delay.is_zero() {
+            tokio::time::sleep(self.delay).await;
+        }
         Ok(Response {
             id: "stub-msg-001".to_owned(),
             content: vec![ContentBlock::text(&self.response_text)],
@@ -62,6 +82,53 @@ impl LlmClient for StubLlmClient {
     }
 }
 
+/// A stub LLM client that requests the same tool call on every turn.
+///
+/// Never ends its turn on its own, so a SubAgent driven by it runs until it
+/// hits its iteration ceiling.
+#[derive(Debug, Clone)]
+pub struct ToolLoopLlmClient {
+    tool_name: String,
+}
+
+impl ToolLoopLlmClient {
+    /// Create a stub client that always calls `tool_name` with empty input.
+    pub fn new(tool_name: &str) -> Self {
+        Self {
+            tool_name: tool_name.to_owned(),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for ToolLoopLlmClient {
+    async fn create_message(&self, _req: &Request) -> Result<Response, LlmError> {
+        Ok(Response {
+            id: "stub-msg-loop".to_owned(),
+            content: vec![ContentBlock::ToolUse {
+                id: format!("toolu_{}", ulid::Ulid::new()),
+                name: self.tool_name.clone(),
+                input: serde_json::json!({}),
+            }],
+            stop_reason: StopReason::ToolUse,
+            model: "stub-model".to_owned(),
+            usage: Usage {
+                input_tokens: 0,
+                output_tokens: 0,
+                cache_read_tokens: 0,
+                cache_write_tokens: 0,
+            },
+        })
+    }
+
+    fn create_message_stream(
+        &self,
+        _req: &Request,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        Box::pin(futures::stream::empty())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,4 +168,14 @@ mod tests {
         // Verify there are no tool-use blocks (agent should not loop).
         assert!(!resp.has_tool_use());
     }
+
+    #[tokio::test]
+    async fn tool_loop_client_always_requests_tool() {
+        let client = ToolLoopLlmClient::new("read_state");
+        let req = Request::new("test-model");
+        let resp = client.create_message(&req).await.unwrap();
+
+        assert_eq!(resp.stop_reason, StopReason::ToolUse);
+        assert!(resp.has_tool_use());
+    }
 }
@@ -1,6 +1,7 @@
 // ABOUTME: Async actor for processing spec commands and publishing events via tokio channels.
 // ABOUTME: Provides SpecActorHandle for sending commands, subscribing to events, and reading state.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use chrono::Utc;
@@ -8,11 +9,12 @@ use thiserror::Error;
 use tokio::sync::{RwLock, broadcast, mpsc, oneshot};
 use ulid::Ulid;
 
-use crate::card::Card;
+use crate::card::{Card, CardAttachment, CardPosition, CardReview, CardReviewResolution};
 use crate::command::Command;
 use crate::event::{Event, EventPayload};
-use crate::state::{ContextAttachment, SpecPhase, SpecState};
-use crate::transcript::TranscriptMessage;
+use crate::model::ExternalLink;
+use crate::state::{ChecklistItem, ContextAttachment, ProposedAction, SpecPhase, SpecState};
+use crate::transcript::{TranscriptMessage, UserQuestion};
 
 /// Errors that can occur when processing commands in the actor.
 #[derive(Debug, Error)]
@@ -20,9 +22,36 @@ pub enum ActorError {
     #[error("spec not yet created")]
     SpecNotCreated,
 
+    #[error("spec already created")]
+    SpecAlreadyCreated,
+
     #[error("card not found: {0}")]
     CardNotFound(Ulid),
 
+    #[error("lane not found: {0}")]
+    LaneNotFound(String),
+
+    #[error("invalid lane name: {0:?}")]
+    InvalidLaneName(String),
+
+    #[error("lane already exists: {0}")]
+    LaneExists(String),
+
+    #[error("lane {0} still holds cards; choose a lane to move them to")]
+    LaneNotEmpty(String),
+
+    #[error("invalid lane order: {0}")]
+    InvalidLaneOrder(String),
+
+    #[error("unknown card type: {0:?}")]
+    UnknownCardType(String),
+
+    #[error("invalid card attachment: {0}")]
+    InvalidCardAttachment(String),
+
+    #[error("card attachment not found: {0}")]
+    CardAttachmentNotFound(Ulid),
+
     #[error("attachment not found: {0}")]
     AttachmentNotFound(Ulid),
 
@@ -32,9 +61,6 @@ pub enum ActorError {
     #[error("attachment already exists: {0}")]
     AttachmentAlreadyExists(Ulid),
 
-    #[error("a question is already pending")]
-    QuestionAlreadyPending,
-
     #[error("no pending question to answer")]
     NoPendingQuestion,
 
@@ -47,6 +73,36 @@ pub enum ActorError {
     #[error("already in target phase")]
     AlreadyInPhase,
 
+    #[error("proposal not found: {0}")]
+    ProposalNotFound(Ulid),
+
+    #[error("proposal commands cannot themselves be proposed")]
+    InvalidProposal,
+
+    #[error("command does not need a review gate")]
+    NotReviewGated,
+
+    #[error("checklist item not found: {0}")]
+    ChecklistItemNotFound(Ulid),
+
+    #[error("checklist item text must not be blank")]
+    BlankChecklistItem,
+
+    #[error("agent roster must name at least one role")]
+    EmptyAgentRoster,
+
+    #[error("invalid external link: {0}")]
+    InvalidExternalLink(String),
+
+    #[error("external link not found: {0}")]
+    ExternalLinkNotFound(Ulid),
+
+    #[error("card review reason must not be blank")]
+    BlankReviewReason,
+
+    #[error("card has no open review: {0}")]
+    NoCardReview(Ulid),
+
     #[error("actor channel closed")]
     ChannelClosed,
 }
@@ -159,25 +215,168 @@ impl SpecActor {
     async fn command_to_events(&mut self, cmd: Command) -> Result<Vec<Event>, ActorError> {
         let state = self.state.read().await;
 
-        let payloads = match cmd {
-            Command::CreateSpec {
-                title,
-                one_liner,
-                goal,
-            } => {
-                vec![
-                    EventPayload::SpecCreated {
-                        title,
-                        one_liner,
-                        goal,
-                    },
-                    EventPayload::PhaseTransitioned {
-                        phase: SpecPhase::Brainstorming,
-                    },
-                ]
+        let payloads = payloads_for_command(&state, cmd)?;
+
+        // Drop the read lock before creating events
+        drop(state);
+
+        let now = Utc::now();
+        let events = payloads
+            .into_iter()
+            .map(|payload| {
+                // Ephemeral events (streaming deltas, tool activity) get event_id 0
+                // and do not consume a monotonic ID. This avoids gaps in the
+                // persisted JSONL log since ephemeral events are never written.
+                let event_id = if payload.is_ephemeral() {
+                    0
+                } else {
+                    let id = self.next_event_id;
+                    self.next_event_id += 1;
+                    id
+                };
+                Event {
+                    event_id,
+                    spec_id: self.spec_id,
+                    timestamp: now,
+                    payload,
+                }
+            })
+            .collect();
+
+        Ok(events)
+    }
+}
+
+/// Validate a command against the current state and translate it into the
+/// event payloads it produces. Pure and synchronous so proposals can re-use
+/// it to dry-run and later apply a buffered command.
+fn payloads_for_command(state: &SpecState, cmd: Command) -> Result<Vec<EventPayload>, ActorError> {
+    let payloads = match cmd {
+        Command::CreateSpec {
+            title,
+            one_liner,
+            goal,
+        } => {
+            vec![
+                EventPayload::SpecCreated {
+                    title,
+                    one_liner,
+                    goal,
+                },
+                EventPayload::PhaseTransitioned {
+                    phase: SpecPhase::Brainstorming,
+                },
+            ]
+        }
+
+        Command::ForkSpec {
+            parent_spec_id,
+            core,
+            cards,
+            lanes,
+            phase,
+        } => {
+            // Forking only seeds a fresh actor — never overwrite a live spec.
+            if state.core.is_some() {
+                return Err(ActorError::SpecAlreadyCreated);
+            }
+            vec![
+                EventPayload::SpecForked {
+                    parent_spec_id,
+                    core,
+                    cards,
+                    lanes,
+                },
+                EventPayload::PhaseTransitioned { phase },
+            ]
+        }
+
+        Command::MergeFromSpec {
+            source_spec_id,
+            source_title,
+            cards,
+            lanes,
+            transcript,
+        } => {
+            if state.core.is_none() {
+                return Err(ActorError::SpecNotCreated);
+            }
+            let lanes_added: Vec<String> = lanes
+                .into_iter()
+                .chain(cards.iter().map(|c| c.lane.clone()))
+                .fold(Vec::new(), |mut added, lane| {
+                    if !state.lanes.contains(&lane) && !added.contains(&lane) {
+                        added.push(lane);
+                    }
+                    added
+                });
+
+            // Fresh IDs for every source card; refs between merged cards
+            // follow them, refs to cards outside the source are dropped.
+            let id_map: HashMap<Ulid, Ulid> =
+                cards.iter().map(|c| (c.card_id, Ulid::new())).collect();
+            let remap = |id: &str| -> Option<String> {
+                let old = Ulid::from_string(id).ok()?;
+                id_map.get(&old).map(|new| new.to_string())
+            };
+
+            // Merged cards land after whatever the destination lane already
+            // holds, keeping their relative source order.
+            let mut next_order: HashMap<String, f64> = HashMap::new();
+            for card in state.cards.values() {
+                let slot = next_order.entry(card.lane.clone()).or_insert(0.0);
+                *slot = slot.max(card.order + 1.0);
             }
+            let mut sorted = cards;
+            sorted.sort_by(|a, b| {
+                a.order
+                    .partial_cmp(&b.order)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            let merged: Vec<Card> = sorted
+                .into_iter()
+                .map(|mut card| {
+                    card.card_id = id_map[&card.card_id];
+                    card.refs = card.refs.iter().filter_map(|r| remap(r)).collect();
+                    card.source_attachment_id = None;
+                    let slot = next_order.entry(card.lane.clone()).or_insert(0.0);
+                    card.order = *slot;
+                    *slot += 1.0;
+                    card
+                })
+                .collect();
+
+            let mut merged_transcript = vec![TranscriptMessage::new(
+                "system".to_string(),
+                format!(
+                    "--- Merged from \"{}\" ({}) ---",
+                    source_title, source_spec_id
+                ),
+            )];
+            merged_transcript.extend(transcript);
+
+            vec![EventPayload::SpecMerged {
+                source_spec_id,
+                cards: merged,
+                lanes_added,
+                transcript: merged_transcript,
+            }]
+        }
 
-            Command::UpdateSpecCore {
+        Command::UpdateSpecCore {
+            title,
+            one_liner,
+            goal,
+            description,
+            constraints,
+            success_criteria,
+            risks,
+            notes,
+        } => {
+            if state.core.is_none() {
+                return Err(ActorError::SpecNotCreated);
+            }
+            vec![EventPayload::SpecCoreUpdated {
                 title,
                 one_liner,
                 goal,
@@ -186,344 +385,927 @@ impl SpecActor {
                 success_criteria,
                 risks,
                 notes,
-            } => {
-                if state.core.is_none() {
-                    return Err(ActorError::SpecNotCreated);
+            }]
+        }
+
+        Command::CreateCard {
+            card_type,
+            title,
+            body,
+            lane,
+            created_by,
+            source_attachment_id,
+        } => {
+            let card_type = crate::card::normalize_card_type(&card_type)
+                .ok_or(ActorError::UnknownCardType(card_type))?;
+            // If the card claims to come from an attachment, that
+            // attachment must exist and not be tombstoned. Rejecting
+            // here prevents dangling provenance links if the Manager
+            // invents or misremembers an ID.
+            if let Some(att_id) = source_attachment_id {
+                let att = state
+                    .context_attachments
+                    .iter()
+                    .find(|a| a.attachment_id == att_id);
+                match att {
+                    None => return Err(ActorError::AttachmentNotFound(att_id)),
+                    Some(a) if a.removed => {
+                        return Err(ActorError::AttachmentNotFound(att_id));
+                    }
+                    _ => {}
                 }
-                vec![EventPayload::SpecCoreUpdated {
-                    title,
-                    one_liner,
-                    goal,
-                    description,
-                    constraints,
-                    success_criteria,
-                    risks,
-                    notes,
-                }]
             }
-
-            Command::CreateCard {
+            let now = Utc::now();
+            let card = Card {
+                card_id: Ulid::new(),
                 card_type,
                 title,
                 body,
-                lane,
-                created_by,
+                lane: lane.unwrap_or_else(|| "Ideas".to_string()),
+                order: 0.0,
+                refs: Vec::new(),
+                created_at: now,
+                updated_at: now,
+                created_by: created_by.clone(),
+                updated_by: created_by,
                 source_attachment_id,
-            } => {
-                // If the card claims to come from an attachment, that
-                // attachment must exist and not be tombstoned. Rejecting
-                // here prevents dangling provenance links if the Manager
-                // invents or misremembers an ID.
-                if let Some(att_id) = source_attachment_id {
-                    let att = state
-                        .context_attachments
-                        .iter()
-                        .find(|a| a.attachment_id == att_id);
-                    match att {
-                        None => return Err(ActorError::AttachmentNotFound(att_id)),
-                        Some(a) if a.removed => {
-                            return Err(ActorError::AttachmentNotFound(att_id));
-                        }
-                        _ => {}
-                    }
-                }
-                let now = Utc::now();
-                let card = Card {
-                    card_id: Ulid::new(),
-                    card_type,
-                    title,
-                    body,
-                    lane: lane.unwrap_or_else(|| "Ideas".to_string()),
-                    order: 0.0,
-                    refs: Vec::new(),
-                    created_at: now,
-                    updated_at: now,
-                    created_by: created_by.clone(),
-                    updated_by: created_by,
-                    source_attachment_id,
-                };
-                vec![EventPayload::CardCreated { card }]
-            }
+                done: false,
+                attachments: Vec::new(),
+                due_date: None,
+                tags: Vec::new(),
+                review: None,
+                archived: false,
+            };
+            vec![EventPayload::CardCreated { card }]
+        }
 
-            Command::UpdateCard {
+        Command::UpdateCard {
+            card_id,
+            title,
+            body,
+            card_type,
+            refs,
+            tags,
+            updated_by: _,
+        } => {
+            let Some(card) = state.cards.get(&card_id) else {
+                return Err(ActorError::CardNotFound(card_id));
+            };
+            // A card keeps a type from before validation until it is changed.
+            let card_type = match card_type {
+                Some(t) if t == card.card_type => Some(t),
+                Some(t) => Some(
+                    crate::card::normalize_card_type(&t).ok_or(ActorError::UnknownCardType(t))?,
+                ),
+                None => None,
+            };
+            vec![EventPayload::CardUpdated {
                 card_id,
                 title,
                 body,
                 card_type,
                 refs,
-                updated_by: _,
-            } => {
-                if !state.cards.contains_key(&card_id) {
-                    return Err(ActorError::CardNotFound(card_id));
-                }
-                vec![EventPayload::CardUpdated {
-                    card_id,
-                    title,
-                    body,
-                    card_type,
-                    refs,
-                }]
-            }
+                tags: tags.map(|tags| crate::card::normalize_tags(&tags)),
+            }]
+        }
 
-            Command::MoveCard {
+        Command::MoveCard {
+            card_id,
+            lane,
+            order,
+            updated_by: _,
+        } => {
+            if !state.cards.contains_key(&card_id) {
+                return Err(ActorError::CardNotFound(card_id));
+            }
+            if !state.lanes.contains(&lane) {
+                return Err(ActorError::LaneNotFound(lane));
+            }
+            vec![EventPayload::CardMoved {
                 card_id,
                 lane,
                 order,
-                updated_by: _,
-            } => {
-                if !state.cards.contains_key(&card_id) {
-                    return Err(ActorError::CardNotFound(card_id));
-                }
-                vec![EventPayload::CardMoved {
-                    card_id,
-                    lane,
-                    order,
-                }]
+            }]
+        }
+
+        Command::ReorderCard {
+            card_id,
+            lane,
+            index,
+            updated_by: _,
+        } => {
+            if !state.cards.contains_key(&card_id) {
+                return Err(ActorError::CardNotFound(card_id));
+            }
+            let lane = lane.trim().to_string();
+            if lane.is_empty() {
+                return Err(ActorError::InvalidLaneName(lane));
+            }
+            if !state.lanes.contains(&lane) {
+                return Err(ActorError::LaneNotFound(lane));
+            }
+            let positions = reorder_positions(state, card_id, &lane, index);
+            if positions.is_empty() {
+                Vec::new()
+            } else {
+                vec![EventPayload::CardsReordered { positions }]
+            }
+        }
+
+        Command::DeleteCard {
+            card_id,
+            updated_by: _,
+        } => {
+            if !state.cards.contains_key(&card_id) {
+                return Err(ActorError::CardNotFound(card_id));
+            }
+            vec![EventPayload::CardDeleted { card_id }]
+        }
+
+        Command::SetCardDone {
+            card_id,
+            done,
+            updated_by: _,
+        } => {
+            if !state.cards.contains_key(&card_id) {
+                return Err(ActorError::CardNotFound(card_id));
+            }
+            vec![EventPayload::CardDoneSet { card_id, done }]
+        }
+
+        Command::ArchiveCard {
+            card_id,
+            updated_by: _,
+        } => set_card_archived(state, card_id, true)?,
+
+        Command::UnarchiveCard {
+            card_id,
+            updated_by: _,
+        } => set_card_archived(state, card_id, false)?,
+
+        Command::SetCardDueDate {
+            card_id,
+            due_date,
+            updated_by: _,
+        } => {
+            if !state.cards.contains_key(&card_id) {
+                return Err(ActorError::CardNotFound(card_id));
+            }
+            vec![EventPayload::CardDueDateSet { card_id, due_date }]
+        }
+
+        Command::RequestCardReview {
+            card_id,
+            reason,
+            requested_by,
+        } => {
+            if !state.cards.contains_key(&card_id) {
+                return Err(ActorError::CardNotFound(card_id));
             }
+            let reason = reason.trim();
+            if reason.is_empty() {
+                return Err(ActorError::BlankReviewReason);
+            }
+            vec![EventPayload::CardReviewSet {
+                card_id,
+                review: Some(CardReview {
+                    reason: reason.to_string(),
+                    requested_by,
+                    requested_at: Utc::now(),
+                }),
+                resolution: None,
+            }]
+        }
 
-            Command::DeleteCard {
+        Command::ResolveCardReview {
+            card_id,
+            approved,
+            resolved_by: _,
+        } => {
+            let card = state
+                .cards
+                .get(&card_id)
+                .ok_or(ActorError::CardNotFound(card_id))?;
+            if card.review.is_none() {
+                return Err(ActorError::NoCardReview(card_id));
+            }
+            vec![EventPayload::CardReviewSet {
                 card_id,
-                updated_by: _,
-            } => {
-                if !state.cards.contains_key(&card_id) {
-                    return Err(ActorError::CardNotFound(card_id));
-                }
-                vec![EventPayload::CardDeleted { card_id }]
+                review: None,
+                resolution: Some(if approved {
+                    CardReviewResolution::Approved
+                } else {
+                    CardReviewResolution::Dismissed
+                }),
+            }]
+        }
+
+        Command::AddCardAttachment {
+            card_id,
+            target,
+            label,
+            updated_by: _,
+        } => {
+            let card = state
+                .cards
+                .get(&card_id)
+                .ok_or(ActorError::CardNotFound(card_id))?;
+            let attachment =
+                CardAttachment::new(&target, &label).map_err(ActorError::InvalidCardAttachment)?;
+            vec![EventPayload::CardAttachmentAdded {
+                card_id,
+                index: card.attachments.len(),
+                attachment,
+            }]
+        }
+
+        Command::RemoveCardAttachment {
+            card_id,
+            attachment_id,
+            updated_by: _,
+        } => {
+            let card = state
+                .cards
+                .get(&card_id)
+                .ok_or(ActorError::CardNotFound(card_id))?;
+            if !card
+                .attachments
+                .iter()
+                .any(|a| a.attachment_id == attachment_id)
+            {
+                return Err(ActorError::CardAttachmentNotFound(attachment_id));
+            }
+            vec![EventPayload::CardAttachmentRemoved {
+                card_id,
+                attachment_id,
+            }]
+        }
+
+        Command::RenameLane {
+            from,
+            to,
+            updated_by: _,
+        } => {
+            let to = to.trim().to_string();
+            if to.is_empty() {
+                return Err(ActorError::InvalidLaneName(to));
+            }
+            let card_ids: Vec<Ulid> = state
+                .cards
+                .values()
+                .filter(|c| c.lane == from)
+                .map(|c| c.card_id)
+                .collect();
+            let Some(idx) = state.lanes.iter().position(|l| *l == from) else {
+                return Err(ActorError::LaneNotFound(from));
+            };
+            if from == to {
+                return Ok(vec![]);
+            }
+            // The renamed lane keeps its position; when the target already
+            // exists the source lane is dropped and its cards join the target.
+            let mut lanes = state.lanes.clone();
+            if lanes.contains(&to) {
+                lanes.remove(idx);
+            } else {
+                lanes[idx] = to.clone();
+            }
+            vec![EventPayload::LaneRenamed {
+                from,
+                to,
+                lanes,
+                card_ids,
+            }]
+        }
+
+        Command::CreateLane {
+            name,
+            updated_by: _,
+        } => {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err(ActorError::InvalidLaneName(name));
             }
+            if state.lanes.contains(&name) {
+                return Err(ActorError::LaneExists(name));
+            }
+            let mut lanes = state.lanes.clone();
+            lanes.push(name);
+            vec![EventPayload::LanesChanged { lanes }]
+        }
 
-            Command::AppendTranscript { sender, content } => {
-                let message = TranscriptMessage::new(sender, content);
-                vec![EventPayload::TranscriptAppended { message }]
+        Command::DeleteLane {
+            name,
+            move_cards_to,
+            updated_by: _,
+        } => {
+            let card_ids: Vec<Ulid> = state
+                .cards
+                .values()
+                .filter(|c| c.lane == name)
+                .map(|c| c.card_id)
+                .collect();
+            if !state.lanes.contains(&name) {
+                return Err(ActorError::LaneNotFound(name));
             }
+            let moved_to = match move_cards_to.map(|t| t.trim().to_string()) {
+                Some(target) if target == name => {
+                    return Err(ActorError::InvalidLaneName(target));
+                }
+                Some(target) => {
+                    if !state.lanes.contains(&target) {
+                        return Err(ActorError::LaneNotFound(target));
+                    }
+                    Some(target)
+                }
+                None if !card_ids.is_empty() => return Err(ActorError::LaneNotEmpty(name)),
+                None => None,
+            };
+            let lanes = state
+                .lanes
+                .iter()
+                .filter(|l| **l != name)
+                .cloned()
+                .collect();
+            vec![EventPayload::LaneDeleted {
+                name,
+                lanes,
+                moved_to,
+                card_ids,
+            }]
+        }
 
-            Command::AskQuestion { question } => {
-                if state.pending_question.is_some() {
-                    return Err(ActorError::QuestionAlreadyPending);
+        Command::ReorderLanes {
+            order,
+            updated_by: _,
+        } => {
+            let mut seen = std::collections::HashSet::new();
+            for lane in &order {
+                if !seen.insert(lane.as_str()) {
+                    return Err(ActorError::InvalidLaneOrder(format!(
+                        "lane {:?} listed twice",
+                        lane
+                    )));
                 }
-                vec![EventPayload::QuestionAsked { question }]
+                if !state.lanes.contains(lane) {
+                    return Err(ActorError::LaneNotFound(lane.clone()));
+                }
+            }
+            if let Some(missing) = state.lanes.iter().find(|l| !seen.contains(l.as_str())) {
+                return Err(ActorError::InvalidLaneOrder(format!(
+                    "lane {:?} is missing",
+                    missing
+                )));
+            }
+            if order == state.lanes {
+                return Ok(vec![]);
+            }
+            vec![EventPayload::LanesChanged { lanes: order }]
+        }
+
+        Command::AppendTranscript {
+            sender,
+            content,
+            display_name,
+        } => {
+            let mut message = TranscriptMessage::new(sender, content);
+            if TranscriptMessage::is_agent_sender(&message.sender) {
+                message.agent_model = state.agent_model.clone();
+                message.display_name = display_name;
             }
+            vec![EventPayload::TranscriptAppended { message }]
+        }
 
-            Command::AnswerQuestion {
-                question_id,
-                answer,
-            } => {
-                match &state.pending_question {
-                    None => return Err(ActorError::NoPendingQuestion),
-                    Some(q) => {
-                        let pending_id = question_id_of(q);
-                        if pending_id != question_id {
-                            return Err(ActorError::QuestionIdMismatch {
-                                expected: pending_id,
-                                got: question_id,
-                            });
-                        }
+        // Questions queue up; the user answers them in the order asked.
+        Command::AskQuestion { question } => vec![EventPayload::QuestionAsked { question }],
+
+        Command::AnswerQuestion {
+            question_id,
+            answer,
+        } => {
+            match state.pending_question() {
+                None => return Err(ActorError::NoPendingQuestion),
+                Some(q) => {
+                    let pending_id = q.question_id();
+                    if pending_id != question_id {
+                        return Err(ActorError::QuestionIdMismatch {
+                            expected: pending_id,
+                            got: question_id,
+                        });
                     }
                 }
-                vec![EventPayload::QuestionAnswered {
-                    question_id,
-                    answer,
-                }]
             }
+            let gated_proposal = match state.pending_question() {
+                Some(UserQuestion::ReviewGate { proposal_id, .. }) => Some(*proposal_id),
+                _ => None,
+            };
+            let approved = is_approval(&answer);
+            let mut payloads = vec![EventPayload::QuestionAnswered {
+                question_id,
+                answer,
+            }];
+            // A review gate resolves its proposal in the same batch, so the
+            // gated command lands (or is dropped) together with the answer.
+            if let Some(proposal_id) = gated_proposal {
+                let resolve = if approved {
+                    Command::ApproveProposal { proposal_id }
+                } else {
+                    Command::RejectProposal { proposal_id }
+                };
+                payloads.extend(payloads_for_command(state, resolve)?);
+            }
+            payloads
+        }
 
-            Command::StartAgentStep {
+        Command::StartAgentStep {
+            agent_id,
+            description,
+        } => {
+            vec![EventPayload::AgentStepStarted {
                 agent_id,
                 description,
-            } => {
-                vec![EventPayload::AgentStepStarted {
-                    agent_id,
-                    description,
-                }]
-            }
+            }]
+        }
 
-            Command::FinishAgentStep {
+        Command::FinishAgentStep {
+            agent_id,
+            diff_summary,
+        } => {
+            vec![EventPayload::AgentStepFinished {
                 agent_id,
                 diff_summary,
-            } => {
-                vec![EventPayload::AgentStepFinished {
-                    agent_id,
-                    diff_summary,
-                }]
-            }
+            }]
+        }
 
-            Command::TransitionPhase { target } => {
-                if state.phase == target {
-                    return Err(ActorError::AlreadyInPhase);
-                }
-                vec![EventPayload::PhaseTransitioned { phase: target }]
+        Command::TransitionPhase { target } => {
+            if state.phase == target {
+                return Err(ActorError::AlreadyInPhase);
             }
+            vec![EventPayload::PhaseTransitioned { phase: target }]
+        }
 
-            Command::UpdateCanvas { content } => {
-                vec![EventPayload::CanvasUpdated { content }]
-            }
+        Command::UpdateCanvas { content } => {
+            vec![EventPayload::CanvasUpdated { content }]
+        }
 
-            Command::AttachContext {
+        Command::AttachContext {
+            attachment_id,
+            filename,
+            mime_type,
+            size_bytes,
+        } => {
+            if state.core.is_none() {
+                return Err(ActorError::SpecNotCreated);
+            }
+            // Reject duplicate IDs up front. The follow-up commands
+            // (Summarize/UpdateNotes/Remove/CreateCard with source) all use
+            // `find(...)` over context_attachments, which would only ever
+            // hit the first entry — leaving any duplicate orphaned in
+            // state and on disk.
+            if state
+                .context_attachments
+                .iter()
+                .any(|a| a.attachment_id == attachment_id)
+            {
+                return Err(ActorError::AttachmentAlreadyExists(attachment_id));
+            }
+            let attachment = ContextAttachment {
                 attachment_id,
                 filename,
                 mime_type,
                 size_bytes,
-            } => {
-                if state.core.is_none() {
-                    return Err(ActorError::SpecNotCreated);
-                }
-                // Reject duplicate IDs up front. The follow-up commands
-                // (Summarize/UpdateNotes/Remove/CreateCard with source) all use
-                // `find(...)` over context_attachments, which would only ever
-                // hit the first entry — leaving any duplicate orphaned in
-                // state and on disk.
-                if state
-                    .context_attachments
-                    .iter()
-                    .any(|a| a.attachment_id == attachment_id)
-                {
-                    return Err(ActorError::AttachmentAlreadyExists(attachment_id));
-                }
-                let attachment = ContextAttachment {
-                    attachment_id,
-                    filename,
-                    mime_type,
-                    size_bytes,
-                    summary: None,
-                    user_notes: None,
-                    added_at: Utc::now(),
-                    removed: false,
-                    summary_error: None,
-                };
-                vec![EventPayload::ContextAttached { attachment }]
-            }
+                summary: None,
+                user_notes: None,
+                added_at: Utc::now(),
+                removed: false,
+                summary_error: None,
+            };
+            vec![EventPayload::ContextAttached { attachment }]
+        }
 
-            Command::SummarizeContext {
+        Command::SummarizeContext {
+            attachment_id,
+            summary,
+        } => {
+            let Some(att) = state
+                .context_attachments
+                .iter()
+                .find(|a| a.attachment_id == attachment_id)
+            else {
+                return Err(ActorError::AttachmentNotFound(attachment_id));
+            };
+            if att.removed {
+                return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
+            }
+            vec![EventPayload::ContextSummarized {
                 attachment_id,
                 summary,
-            } => {
-                let Some(att) = state
-                    .context_attachments
-                    .iter()
-                    .find(|a| a.attachment_id == attachment_id)
-                else {
-                    return Err(ActorError::AttachmentNotFound(attachment_id));
-                };
-                if att.removed {
-                    return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
-                }
-                vec![EventPayload::ContextSummarized {
-                    attachment_id,
-                    summary,
-                }]
-            }
+            }]
+        }
 
-            Command::MarkContextSummarizeFailed {
+        Command::MarkContextSummarizeFailed {
+            attachment_id,
+            reason,
+        } => {
+            let Some(att) = state
+                .context_attachments
+                .iter()
+                .find(|a| a.attachment_id == attachment_id)
+            else {
+                return Err(ActorError::AttachmentNotFound(attachment_id));
+            };
+            if att.removed {
+                return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
+            }
+            vec![EventPayload::ContextSummarizeFailed {
                 attachment_id,
                 reason,
-            } => {
-                let Some(att) = state
-                    .context_attachments
-                    .iter()
-                    .find(|a| a.attachment_id == attachment_id)
-                else {
-                    return Err(ActorError::AttachmentNotFound(attachment_id));
-                };
-                if att.removed {
-                    return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
-                }
-                vec![EventPayload::ContextSummarizeFailed {
-                    attachment_id,
-                    reason,
-                }]
-            }
+            }]
+        }
 
-            Command::UpdateContextNotes {
+        Command::UpdateContextNotes {
+            attachment_id,
+            notes,
+        } => {
+            let Some(att) = state
+                .context_attachments
+                .iter()
+                .find(|a| a.attachment_id == attachment_id)
+            else {
+                return Err(ActorError::AttachmentNotFound(attachment_id));
+            };
+            if att.removed {
+                return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
+            }
+            vec![EventPayload::ContextNotesUpdated {
                 attachment_id,
                 notes,
-            } => {
-                let Some(att) = state
-                    .context_attachments
-                    .iter()
-                    .find(|a| a.attachment_id == attachment_id)
-                else {
-                    return Err(ActorError::AttachmentNotFound(attachment_id));
-                };
-                if att.removed {
-                    return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
-                }
-                vec![EventPayload::ContextNotesUpdated {
-                    attachment_id,
-                    notes,
-                }]
+            }]
+        }
+
+        Command::RemoveContext { attachment_id } => {
+            let Some(att) = state
+                .context_attachments
+                .iter()
+                .find(|a| a.attachment_id == attachment_id)
+            else {
+                return Err(ActorError::AttachmentNotFound(attachment_id));
+            };
+            if att.removed {
+                return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
             }
+            vec![EventPayload::ContextRemoved { attachment_id }]
+        }
 
-            Command::RemoveContext { attachment_id } => {
-                let Some(att) = state
-                    .context_attachments
-                    .iter()
-                    .find(|a| a.attachment_id == attachment_id)
-                else {
-                    return Err(ActorError::AttachmentNotFound(attachment_id));
-                };
-                if att.removed {
-                    return Err(ActorError::AttachmentAlreadyRemoved(attachment_id));
-                }
-                vec![EventPayload::ContextRemoved { attachment_id }]
+        Command::StreamDelta { agent_id, text } => {
+            vec![EventPayload::StreamingDelta { agent_id, text }]
+        }
+
+        Command::StreamToolActivity { agent_id, activity } => {
+            vec![EventPayload::StreamingToolActivity { agent_id, activity }]
+        }
+
+        Command::Undo => {
+            if state.undo_stack.is_empty() {
+                return Err(ActorError::NothingToUndo);
             }
+            let entry = state.undo_stack.last().unwrap();
+            let target_event_id = entry.event_id;
+            let inverse_events = entry.inverse.clone();
+            vec![EventPayload::UndoApplied {
+                target_event_id,
+                inverse_events,
+            }]
+        }
 
-            Command::StreamDelta { agent_id, text } => {
-                vec![EventPayload::StreamingDelta { agent_id, text }]
+        Command::Redo => match state.redo_stack.last() {
+            Some(payloads) => vec![EventPayload::RedoApplied {
+                payloads: payloads.clone(),
+            }],
+            None => vec![],
+        },
+
+        Command::ProposeAction {
+            command,
+            proposed_by,
+            reason,
+        } => {
+            if matches!(
+                *command,
+                Command::ProposeAction { .. }
+                    | Command::ApproveProposal { .. }
+                    | Command::RejectProposal { .. }
+                    | Command::PinAgentModel { .. }
+                    | Command::RecordUsage { .. }
+                    | Command::SetAgentRoster { .. }
+                    | Command::SetFetchHosts { .. }
+                    | Command::SetSnapshotOnShutdown { .. }
+                    | Command::SetModelOverride { .. }
+                    | Command::SetSpecArchived { .. }
+                    | Command::RequestReviewGate { .. }
+            ) {
+                return Err(ActorError::InvalidProposal);
             }
+            // Dry-run validation: a proposal that could never be applied
+            // is rejected now rather than when a human approves it.
+            payloads_for_command(state, (*command).clone())?;
+            vec![EventPayload::ProposalQueued {
+                proposal: ProposedAction {
+                    proposal_id: Ulid::new(),
+                    command: *command,
+                    proposed_by,
+                    reason,
+                    proposed_at: Utc::now(),
+                },
+            }]
+        }
+
+        Command::ApproveProposal { proposal_id } => {
+            let Some(proposal) = state
+                .pending_proposals
+                .iter()
+                .find(|p| p.proposal_id == proposal_id)
+            else {
+                return Err(ActorError::ProposalNotFound(proposal_id));
+            };
+            // If the buffered command no longer validates (e.g. its card
+            // was deleted meanwhile) the error surfaces and the proposal
+            // stays queued so the human can reject it.
+            let mut payloads = vec![EventPayload::ProposalApproved { proposal_id }];
+            payloads.extend(payloads_for_command(state, proposal.command.clone())?);
+            payloads
+        }
 
-            Command::StreamToolActivity { agent_id, activity } => {
-                vec![EventPayload::StreamingToolActivity { agent_id, activity }]
+        Command::RejectProposal { proposal_id } => {
+            if !state
+                .pending_proposals
+                .iter()
+                .any(|p| p.proposal_id == proposal_id)
+            {
+                return Err(ActorError::ProposalNotFound(proposal_id));
             }
+            vec![EventPayload::ProposalRejected { proposal_id }]
+        }
 
-            Command::Undo => {
-                if state.undo_stack.is_empty() {
-                    return Err(ActorError::NothingToUndo);
-                }
-                let entry = state.undo_stack.last().unwrap();
-                let target_event_id = entry.event_id;
-                let inverse_events = entry.inverse.clone();
-                vec![EventPayload::UndoApplied {
-                    target_event_id,
-                    inverse_events,
+        Command::PinAgentModel { provider, model } => {
+            let unchanged = state
+                .agent_model
+                .as_ref()
+                .is_some_and(|m| m.provider == provider && m.model == model);
+            if unchanged {
+                vec![]
+            } else {
+                vec![EventPayload::AgentModelPinned { provider, model }]
+            }
+        }
+
+        Command::RecordUsage {
+            role,
+            model,
+            input_tokens,
+            output_tokens,
+        } => {
+            if input_tokens == 0 && output_tokens == 0 {
+                vec![]
+            } else {
+                vec![EventPayload::UsageRecorded {
+                    role,
+                    model,
+                    input_tokens,
+                    output_tokens,
                 }]
             }
-        };
+        }
 
-        // Drop the read lock before creating events
-        drop(state);
+        Command::SetAgentRoster { roles } => {
+            let roles: Vec<String> = roles
+                .iter()
+                .map(|r| r.trim().to_ascii_lowercase())
+                .filter(|r| !r.is_empty())
+                .collect();
+            if roles.is_empty() {
+                return Err(ActorError::EmptyAgentRoster);
+            }
+            if state.agent_roster.as_ref() == Some(&roles) {
+                vec![]
+            } else {
+                vec![EventPayload::AgentRosterSet { roles }]
+            }
+        }
 
-        let now = Utc::now();
-        let events = payloads
-            .into_iter()
-            .map(|payload| {
-                // Ephemeral events (streaming deltas, tool activity) get event_id 0
-                // and do not consume a monotonic ID. This avoids gaps in the
-                // persisted JSONL log since ephemeral events are never written.
-                let event_id = if payload.is_ephemeral() {
-                    0
-                } else {
-                    let id = self.next_event_id;
-                    self.next_event_id += 1;
-                    id
-                };
-                Event {
-                    event_id,
-                    spec_id: self.spec_id,
-                    timestamp: now,
-                    payload,
-                }
-            })
-            .collect();
+        Command::SetFetchHosts { hosts } => {
+            let mut hosts: Vec<String> = hosts
+                .iter()
+                .map(|h| h.trim().to_ascii_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect();
+            hosts.sort();
+            hosts.dedup();
+            if state.fetch_hosts == hosts {
+                vec![]
+            } else {
+                vec![EventPayload::FetchHostsSet { hosts }]
+            }
+        }
 
-        Ok(events)
-    }
+        Command::SetModelOverride { model } => {
+            if state.model_override == model {
+                vec![]
+            } else {
+                vec![EventPayload::ModelOverrideSet { model }]
+            }
+        }
+
+        Command::SetSnapshotOnShutdown { enabled } => {
+            if state.snapshot_on_shutdown == enabled {
+                vec![]
+            } else {
+                vec![EventPayload::SnapshotOnShutdownSet { enabled }]
+            }
+        }
+
+        Command::SetSpecArchived { archived } => {
+            if state.core.is_none() {
+                return Err(ActorError::SpecNotCreated);
+            }
+            if state.archived == archived {
+                vec![]
+            } else {
+                vec![EventPayload::SpecArchivedSet { archived }]
+            }
+        }
+
+        Command::AddChecklistItem { text } => {
+            if state.core.is_none() {
+                return Err(ActorError::SpecNotCreated);
+            }
+            let text = text.trim();
+            if text.is_empty() {
+                return Err(ActorError::BlankChecklistItem);
+            }
+            vec![EventPayload::ChecklistItemAdded {
+                item: ChecklistItem {
+                    item_id: Ulid::new(),
+                    text: text.to_string(),
+                    checked: false,
+                },
+            }]
+        }
+
+        Command::ToggleChecklistItem { item_id } => {
+            let Some(item) = state.done_checklist.iter().find(|i| i.item_id == item_id) else {
+                return Err(ActorError::ChecklistItemNotFound(item_id));
+            };
+            vec![EventPayload::ChecklistItemToggled {
+                item_id,
+                checked: !item.checked,
+            }]
+        }
+
+        Command::RemoveChecklistItem { item_id } => {
+            if !state.done_checklist.iter().any(|i| i.item_id == item_id) {
+                return Err(ActorError::ChecklistItemNotFound(item_id));
+            }
+            vec![EventPayload::ChecklistItemRemoved { item_id }]
+        }
+
+        Command::AddExternalLink { url, label } => {
+            let Some(core) = &state.core else {
+                return Err(ActorError::SpecNotCreated);
+            };
+            let link = ExternalLink::new(&url, &label).map_err(ActorError::InvalidExternalLink)?;
+            vec![EventPayload::ExternalLinkAdded {
+                index: core.external_links.len(),
+                link,
+            }]
+        }
+
+        Command::RemoveExternalLink { link_id } => {
+            let Some(core) = &state.core else {
+                return Err(ActorError::SpecNotCreated);
+            };
+            if !core.external_links.iter().any(|l| l.link_id == link_id) {
+                return Err(ActorError::ExternalLinkNotFound(link_id));
+            }
+            vec![EventPayload::ExternalLinkRemoved { link_id }]
+        }
+
+        Command::RequestReviewGate {
+            command,
+            requested_by,
+            reason,
+        } => {
+            let Some(action) = command.review_gate_action() else {
+                return Err(ActorError::NotReviewGated);
+            };
+            payloads_for_command(state, (*command).clone())?;
+            let proposal_id = Ulid::new();
+            vec![
+                EventPayload::ProposalQueued {
+                    proposal: ProposedAction {
+                        proposal_id,
+                        command: *command,
+                        proposed_by: requested_by.clone(),
+                        reason,
+                        proposed_at: Utc::now(),
+                    },
+                },
+                EventPayload::QuestionAsked {
+                    question: UserQuestion::ReviewGate {
+                        question_id: Ulid::new(),
+                        question: format!("{} wants to {}. Approve?", requested_by, action),
+                        proposal_id,
+                    },
+                },
+            ]
+        }
+    };
+    Ok(payloads)
 }
 
-/// Extract the question_id from any UserQuestion variant.
-fn question_id_of(q: &crate::transcript::UserQuestion) -> Ulid {
-    match q {
-        crate::transcript::UserQuestion::Boolean { question_id, .. } => *question_id,
-        crate::transcript::UserQuestion::MultipleChoice { question_id, .. } => *question_id,
-        crate::transcript::UserQuestion::Freeform { question_id, .. } => *question_id,
-    }
+/// The event that archives or unarchives `card_id`, or nothing when the
+/// card is already in that state.
+fn set_card_archived(
+    state: &SpecState,
+    card_id: Ulid,
+    archived: bool,
+) -> Result<Vec<EventPayload>, ActorError> {
+    let card = state
+        .cards
+        .get(&card_id)
+        .ok_or(ActorError::CardNotFound(card_id))?;
+    Ok(if card.archived == archived {
+        Vec::new()
+    } else {
+        vec![EventPayload::CardArchivedSet { card_id, archived }]
+    })
+}
+
+/// Positions that put `card_id` at `index` in `lane`. The card takes the
+/// midpoint between its new neighbours, or one past the end of the lane. When
+/// the neighbours' orders are too close for a midpoint to fall strictly
+/// between them, the whole lane is renumbered 1.0, 2.0, ... instead. Only
+/// cards whose lane or order actually changes are returned.
+fn reorder_positions(
+    state: &SpecState,
+    card_id: Ulid,
+    lane: &str,
+    index: usize,
+) -> Vec<CardPosition> {
+    let mut siblings: Vec<&Card> = state
+        .cards
+        .values()
+        .filter(|c| c.lane == lane && c.card_id != card_id && !c.archived)
+        .collect();
+    siblings.sort_by(|a, b| a.order.total_cmp(&b.order));
+    let index = index.min(siblings.len());
+
+    let before = index.checked_sub(1).map(|i| siblings[i].order);
+    let after = siblings.get(index).map(|c| c.order);
+    let order = match (before, after) {
+        (None, None) => Some(1.0),
+        (Some(before), None) => Some(before + 1.0),
+        (None, Some(after)) => Some(after - 1.0),
+        (Some(before), Some(after)) => {
+            let mid = before + (after - before) / 2.0;
+            (before < mid && mid < after).then_some(mid)
+        }
+    };
+
+    let placed: Vec<(Ulid, f64)> = match order {
+        Some(order) => vec![(card_id, order)],
+        None => {
+            let mut ids: Vec<Ulid> = siblings.iter().map(|c| c.card_id).collect();
+            ids.insert(index, card_id);
+            ids.into_iter()
+                .enumerate()
+                .map(|(i, id)| (id, (i + 1) as f64))
+                .collect()
+        }
+    };
+
+    placed
+        .into_iter()
+        .filter(|(id, order)| {
+            state
+                .cards
+                .get(id)
+                .is_some_and(|c| c.lane != lane || c.order != *order)
+        })
+        .map(|(card_id, order)| CardPosition {
+            card_id,
+            lane: lane.to_string(),
+            order,
+        })
+        .collect()
+}
+
+/// Whether a review-gate answer approves the gated action.
+fn is_approval(answer: &str) -> bool {
+    matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "approve" | "approved" | "yes" | "y"
+    )
 }
 
 #[cfg(test)]
@@ -607,6 +1389,400 @@ mod tests {
         assert_eq!(state.cards.len(), 1);
     }
 
+    /// Spawn an actor with a created spec and one card per `(title, lane)`.
+    async fn spawn_with_cards(cards: &[(&str, &str)]) -> (SpecActorHandle, Vec<Ulid>) {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        handle
+            .send_command(Command::CreateSpec {
+                title: "s".into(),
+                one_liner: "o".into(),
+                goal: "g".into(),
+            })
+            .await
+            .unwrap();
+        let mut ids = Vec::new();
+        for (title, lane) in cards {
+            let events = handle
+                .send_command(Command::CreateCard {
+                    card_type: "task".into(),
+                    title: title.to_string(),
+                    body: None,
+                    lane: Some(lane.to_string()),
+                    created_by: "human".into(),
+                    source_attachment_id: None,
+                })
+                .await
+                .unwrap();
+            match &events[0].payload {
+                EventPayload::CardCreated { card } => ids.push(card.card_id),
+                other => panic!("expected CardCreated, got {:?}", other),
+            }
+        }
+        (handle, ids)
+    }
+
+    fn rename(from: &str, to: &str) -> Command {
+        Command::RenameLane {
+            from: from.into(),
+            to: to.into(),
+            updated_by: "human".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn rename_lane_moves_lane_entry_and_cards() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan"), ("b", "Plan"), ("c", "Ideas")]).await;
+
+        let events = handle.send_command(rename("Plan", "Doing")).await.unwrap();
+        assert_eq!(events.len(), 1, "lane and cards change in one event");
+
+        let state = handle.read_state().await;
+        assert_eq!(state.lanes, vec!["Ideas", "Doing", "Spec"]);
+        assert_eq!(state.cards[&ids[0]].lane, "Doing");
+        assert_eq!(state.cards[&ids[1]].lane, "Doing");
+        assert_eq!(state.cards[&ids[2]].lane, "Ideas");
+        assert!(state.cards.values().all(|c| c.lane != "Plan"));
+    }
+
+    #[tokio::test]
+    async fn rename_lane_onto_existing_lane_merges_and_undoes() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan"), ("b", "Spec")]).await;
+
+        handle.send_command(rename("Plan", "Spec")).await.unwrap();
+        {
+            let state = handle.read_state().await;
+            assert_eq!(state.lanes, vec!["Ideas", "Spec"]);
+            assert_eq!(state.cards[&ids[0]].lane, "Spec");
+            assert_eq!(state.cards[&ids[1]].lane, "Spec");
+        }
+
+        // Undo splits the merged lane back apart.
+        handle.send_command(Command::Undo).await.unwrap();
+        let state = handle.read_state().await;
+        assert_eq!(state.lanes, vec!["Ideas", "Plan", "Spec"]);
+        assert_eq!(state.cards[&ids[0]].lane, "Plan");
+        assert_eq!(state.cards[&ids[1]].lane, "Spec");
+    }
+
+    #[tokio::test]
+    async fn update_card_normalizes_tags_and_undoes() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan")]).await;
+        let set_tags = |tags: &[&str]| Command::UpdateCard {
+            card_id: ids[0],
+            title: None,
+            body: None,
+            card_type: None,
+            refs: None,
+            tags: Some(tags.iter().map(|t| t.to_string()).collect()),
+            updated_by: "human".to_string(),
+        };
+
+        handle
+            .send_command(set_tags(&[" UX ", "backend", "ux", ""]))
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.read_state().await.cards[&ids[0]].tags,
+            vec!["ux", "backend"]
+        );
+
+        handle.send_command(Command::Undo).await.unwrap();
+        assert!(handle.read_state().await.cards[&ids[0]].tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rename_lane_rejects_unknown_lane_and_blank_name() {
+        let (handle, _) = spawn_with_cards(&[]).await;
+        assert!(matches!(
+            handle.send_command(rename("Nope", "Doing")).await,
+            Err(ActorError::LaneNotFound(_))
+        ));
+        assert!(matches!(
+            handle.send_command(rename("Plan", "  ")).await,
+            Err(ActorError::InvalidLaneName(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn create_lane_appends_and_undoes() {
+        let (handle, _) = spawn_with_cards(&[]).await;
+        handle
+            .send_command(Command::CreateLane {
+                name: " Review ".into(),
+                updated_by: "human".into(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.read_state().await.lanes,
+            vec!["Ideas", "Plan", "Spec", "Review"]
+        );
+        assert!(matches!(
+            handle
+                .send_command(Command::CreateLane {
+                    name: "Plan".into(),
+                    updated_by: "human".into(),
+                })
+                .await,
+            Err(ActorError::LaneExists(_))
+        ));
+
+        handle.send_command(Command::Undo).await.unwrap();
+        assert_eq!(
+            handle.read_state().await.lanes,
+            vec!["Ideas", "Plan", "Spec"]
+        );
+    }
+
+    fn delete(name: &str, move_cards_to: Option<&str>) -> Command {
+        Command::DeleteLane {
+            name: name.into(),
+            move_cards_to: move_cards_to.map(Into::into),
+            updated_by: "human".into(),
+        }
+    }
+
+    fn reorder(card_id: Ulid, lane: &str, index: usize) -> Command {
+        Command::ReorderCard {
+            card_id,
+            lane: lane.into(),
+            index,
+            updated_by: "human".into(),
+        }
+    }
+
+    fn lane_order(state: &SpecState, lane: &str) -> Vec<(Ulid, f64)> {
+        let mut cards: Vec<(Ulid, f64)> = state
+            .cards
+            .values()
+            .filter(|c| c.lane == lane)
+            .map(|c| (c.card_id, c.order))
+            .collect();
+        cards.sort_by(|a, b| a.1.total_cmp(&b.1));
+        cards
+    }
+
+    #[tokio::test]
+    async fn reorder_card_renumbers_a_lane_with_no_gap_then_uses_midpoints() {
+        // New cards all share order 0.0, so there is no room between them.
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan"), ("b", "Plan"), ("c", "Plan")]).await;
+
+        let events = handle
+            .send_command(reorder(ids[2], "Plan", 1))
+            .await
+            .unwrap();
+        match &events[0].payload {
+            EventPayload::CardsReordered { positions } => assert_eq!(positions.len(), 3),
+            other => panic!("expected CardsReordered, got {:?}", other),
+        }
+        let lane = lane_order(&*handle.read_state().await, "Plan");
+        assert_eq!(
+            lane.iter().map(|c| c.1).collect::<Vec<_>>(),
+            vec![1.0, 2.0, 3.0]
+        );
+        assert_eq!(lane[1].0, ids[2]);
+
+        // Now there is a gap, so only the moved card changes.
+        let (first, last) = (lane[0].0, lane[2].0);
+        let events = handle.send_command(reorder(last, "Plan", 1)).await.unwrap();
+        match &events[0].payload {
+            EventPayload::CardsReordered { positions } => {
+                assert_eq!(positions.len(), 1);
+                assert_eq!(positions[0].order, 1.5);
+            }
+            other => panic!("expected CardsReordered, got {:?}", other),
+        }
+        assert_eq!(
+            lane_order(&*handle.read_state().await, "Plan"),
+            vec![(first, 1.0), (last, 1.5), (ids[2], 2.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn reorder_card_renumbers_when_the_midpoint_underflows() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan"), ("b", "Plan"), ("c", "Plan")]).await;
+        // Adjacent floats: no f64 lies strictly between them.
+        let low = 1.0_f64;
+        let high = f64::from_bits(low.to_bits() + 1);
+        for (id, order) in [(ids[0], low), (ids[1], high)] {
+            handle
+                .send_command(Command::MoveCard {
+                    card_id: id,
+                    lane: "Plan".into(),
+                    order,
+                    updated_by: "human".into(),
+                })
+                .await
+                .unwrap();
+        }
+        handle
+            .send_command(Command::MoveCard {
+                card_id: ids[2],
+                lane: "Spec".into(),
+                order: 0.0,
+                updated_by: "human".into(),
+            })
+            .await
+            .unwrap();
+
+        handle
+            .send_command(reorder(ids[2], "Plan", 1))
+            .await
+            .unwrap();
+        assert_eq!(
+            lane_order(&*handle.read_state().await, "Plan"),
+            vec![(ids[0], 1.0), (ids[2], 2.0), (ids[1], 3.0)]
+        );
+    }
+
+    #[tokio::test]
+    async fn reorder_card_into_another_lane_and_undo() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan"), ("b", "Spec")]).await;
+
+        // Past the end of the lane is clamped to the last slot.
+        handle
+            .send_command(reorder(ids[0], " Spec ", 9))
+            .await
+            .unwrap();
+        {
+            let state = handle.read_state().await;
+            assert_eq!(state.cards[&ids[0]].lane, "Spec");
+            assert_eq!(state.cards[&ids[0]].order, 1.0);
+        }
+
+        handle.send_command(Command::Undo).await.unwrap();
+        let state = handle.read_state().await;
+        assert_eq!(state.cards[&ids[0]].lane, "Plan");
+        assert_eq!(state.cards[&ids[0]].order, 0.0);
+    }
+
+    #[tokio::test]
+    async fn reorder_card_rejects_unknown_card_and_blank_lane() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan")]).await;
+        assert!(matches!(
+            handle.send_command(reorder(Ulid::new(), "Plan", 0)).await,
+            Err(ActorError::CardNotFound(_))
+        ));
+        assert!(matches!(
+            handle.send_command(reorder(ids[0], "  ", 0)).await,
+            Err(ActorError::InvalidLaneName(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn moving_a_card_into_a_missing_lane_is_rejected() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan")]).await;
+        assert!(matches!(
+            handle.send_command(reorder(ids[0], "Backlog", 0)).await,
+            Err(ActorError::LaneNotFound(lane)) if lane == "Backlog"
+        ));
+        let result = handle
+            .send_command(Command::MoveCard {
+                card_id: ids[0],
+                lane: "Backlog".into(),
+                order: 1.0,
+                updated_by: "human".into(),
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::LaneNotFound(_))));
+        let state = handle.read_state().await;
+        assert_eq!(state.cards[&ids[0]].lane, "Plan");
+        assert_eq!(state.lanes, vec!["Ideas", "Plan", "Spec"]);
+    }
+
+    #[tokio::test]
+    async fn delete_lane_removes_an_empty_lane() {
+        let (handle, _) = spawn_with_cards(&[("a", "Plan")]).await;
+        handle.send_command(delete("Spec", None)).await.unwrap();
+        assert_eq!(handle.read_state().await.lanes, vec!["Ideas", "Plan"]);
+
+        handle.send_command(Command::Undo).await.unwrap();
+        assert_eq!(
+            handle.read_state().await.lanes,
+            vec!["Ideas", "Plan", "Spec"]
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_lane_with_cards_requires_a_target() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan"), ("b", "Plan"), ("c", "Spec")]).await;
+
+        assert!(matches!(
+            handle.send_command(delete("Plan", None)).await,
+            Err(ActorError::LaneNotEmpty(_))
+        ));
+        assert!(matches!(
+            handle.send_command(delete("Plan", Some("Nope"))).await,
+            Err(ActorError::LaneNotFound(_))
+        ));
+        assert!(matches!(
+            handle.send_command(delete("Plan", Some("Plan"))).await,
+            Err(ActorError::InvalidLaneName(_))
+        ));
+        assert!(matches!(
+            handle.send_command(delete("Nope", None)).await,
+            Err(ActorError::LaneNotFound(_))
+        ));
+        assert_eq!(
+            handle.read_state().await.lanes,
+            vec!["Ideas", "Plan", "Spec"]
+        );
+
+        handle
+            .send_command(delete("Plan", Some("Spec")))
+            .await
+            .unwrap();
+        {
+            let state = handle.read_state().await;
+            assert_eq!(state.lanes, vec!["Ideas", "Spec"]);
+            assert!(ids.iter().all(|id| state.cards[id].lane == "Spec"));
+        }
+
+        // Undo restores the lane in place and sends only its cards home.
+        handle.send_command(Command::Undo).await.unwrap();
+        let state = handle.read_state().await;
+        assert_eq!(state.lanes, vec!["Ideas", "Plan", "Spec"]);
+        assert_eq!(state.cards[&ids[0]].lane, "Plan");
+        assert_eq!(state.cards[&ids[1]].lane, "Plan");
+        assert_eq!(state.cards[&ids[2]].lane, "Spec");
+    }
+
+    #[tokio::test]
+    async fn reorder_lanes_requires_every_lane_once() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan"), ("b", "Parking")]).await;
+        let reorder = |order: &[&str]| Command::ReorderLanes {
+            order: order.iter().map(|s| s.to_string()).collect(),
+            updated_by: "human".into(),
+        };
+
+        assert!(matches!(
+            handle.send_command(reorder(&["Spec", "Ideas"])).await,
+            Err(ActorError::InvalidLaneOrder(_))
+        ));
+        assert!(matches!(
+            handle
+                .send_command(reorder(&["Spec", "Plan", "Ideas", "Plan"]))
+                .await,
+            Err(ActorError::InvalidLaneOrder(_))
+        ));
+        assert!(matches!(
+            handle
+                .send_command(reorder(&["Spec", "Plan", "Ideas", "Nope"]))
+                .await,
+            Err(ActorError::LaneNotFound(_))
+        ));
+
+        // A card created in a new lane put that lane on the board.
+        handle
+            .send_command(reorder(&["Spec", "Parking", "Plan", "Ideas"]))
+            .await
+            .unwrap();
+        let state = handle.read_state().await;
+        assert_eq!(state.lanes, vec!["Spec", "Parking", "Plan", "Ideas"]);
+        assert_eq!(state.cards[&ids[0]].lane, "Plan");
+        assert_eq!(state.cards[&ids[1]].lane, "Parking");
+    }
+
     #[tokio::test]
     async fn actor_accepts_create_card_with_valid_source_attachment_id() {
         let spec_id = Ulid::new();
@@ -757,38 +1933,59 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn actor_rejects_second_pending_question() {
+    async fn actor_queues_questions_and_answers_them_in_order() {
         let spec_id = Ulid::new();
         let handle = spawn(spec_id, SpecState::new());
 
-        let q1 = UserQuestion::Boolean {
-            question_id: Ulid::new(),
-            question: "First?".to_string(),
-            default: None,
-        };
-
+        let first_id = Ulid::new();
+        let second_id = Ulid::new();
         handle
-            .send_command(Command::AskQuestion { question: q1 })
+            .send_command(Command::AskQuestion {
+                question: UserQuestion::Boolean {
+                    question_id: first_id,
+                    question: "First?".to_string(),
+                    default: None,
+                },
+            })
+            .await
+            .unwrap();
+        handle
+            .send_command(Command::AskQuestion {
+                question: UserQuestion::Freeform {
+                    question_id: second_id,
+                    question: "Second?".to_string(),
+                    placeholder: None,
+                    validation_hint: None,
+                },
+            })
             .await
             .unwrap();
 
-        let q2 = UserQuestion::Boolean {
-            question_id: Ulid::new(),
-            question: "Second?".to_string(),
-            default: None,
-        };
-
-        let result = handle
-            .send_command(Command::AskQuestion { question: q2 })
-            .await;
+        let state = handle.read_state().await;
+        assert_eq!(state.pending_questions.len(), 2);
+        assert_eq!(state.pending_question().unwrap().question_id(), first_id);
+        drop(state);
 
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(
-            matches!(err, ActorError::QuestionAlreadyPending),
-            "expected QuestionAlreadyPending, got: {}",
-            err
-        );
+        // Only the front question can be answered.
+        let err = handle
+            .send_command(Command::AnswerQuestion {
+                question_id: second_id,
+                answer: "Early".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ActorError::QuestionIdMismatch { .. }));
+
+        handle
+            .send_command(Command::AnswerQuestion {
+                question_id: first_id,
+                answer: "Yes".to_string(),
+            })
+            .await
+            .unwrap();
+        let state = handle.read_state().await;
+        assert_eq!(state.pending_questions.len(), 1);
+        assert_eq!(state.pending_question().unwrap().question_id(), second_id);
     }
 
     #[tokio::test]
@@ -843,6 +2040,7 @@ mod tests {
                 body: None,
                 card_type: None,
                 refs: None,
+                tags: None,
                 updated_by: "human".to_string(),
             })
             .await;
@@ -884,44 +2082,596 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn actor_undo_reverses_card_creation() {
-        let spec_id = Ulid::new();
-        let handle = spawn(spec_id, SpecState::new());
-
-        // Create spec first
-        handle
-            .send_command(Command::CreateSpec {
-                title: "Spec".to_string(),
-                one_liner: "One".to_string(),
-                goal: "Goal".to_string(),
-            })
-            .await
-            .unwrap();
+    async fn actor_undo_reverses_card_creation() {
+        let spec_id = Ulid::new();
+        let handle = spawn(spec_id, SpecState::new());
+
+        // Create spec first
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Spec".to_string(),
+                one_liner: "One".to_string(),
+                goal: "Goal".to_string(),
+            })
+            .await
+            .unwrap();
+
+        // Create a card
+        handle
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "Undo Me".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+
+        // Verify card exists
+        {
+            let state = handle.read_state().await;
+            assert_eq!(state.cards.len(), 1);
+        }
+
+        // Undo should remove the card
+        handle.send_command(Command::Undo).await.unwrap();
+
+        let state = handle.read_state().await;
+        assert_eq!(state.cards.len(), 0, "card should be removed after undo");
+    }
+
+    #[tokio::test]
+    async fn set_card_done_survives_replay_and_undo_reverts_it() {
+        let spec_id = Ulid::new();
+        let handle = spawn(spec_id, SpecState::new());
+        let mut log = handle
+            .send_command(Command::CreateSpec {
+                title: "Spec".to_string(),
+                one_liner: "One".to_string(),
+                goal: "Goal".to_string(),
+            })
+            .await
+            .unwrap();
+        let created = handle
+            .send_command(Command::CreateCard {
+                card_type: "task".to_string(),
+                title: "Check me".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        let card_id = match &created[0].payload {
+            EventPayload::CardCreated { card } => card.card_id,
+            other => panic!("expected CardCreated, got {:?}", other),
+        };
+        log.extend(created);
+        log.extend(
+            handle
+                .send_command(Command::SetCardDone {
+                    card_id,
+                    done: true,
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap(),
+        );
+        assert!(handle.read_state().await.cards[&card_id].done);
+
+        // Replaying the serialized log into a fresh state restores the flag.
+        let mut replayed = SpecState::new();
+        for event in &log {
+            let json = serde_json::to_string(event).unwrap();
+            replayed.apply(&serde_json::from_str(&json).unwrap());
+        }
+        assert!(replayed.cards[&card_id].done);
+
+        handle.send_command(Command::Undo).await.unwrap();
+        assert!(!handle.read_state().await.cards[&card_id].done);
+    }
+
+    #[tokio::test]
+    async fn archive_card_is_idempotent_and_undoable() {
+        let spec_id = Ulid::new();
+        let handle = spawn(spec_id, SpecState::new());
+        let created = handle
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "Maybe later".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        let card_id = match &created[0].payload {
+            EventPayload::CardCreated { card } => card.card_id,
+            other => panic!("expected CardCreated, got {:?}", other),
+        };
+        let archive = |card_id| Command::ArchiveCard {
+            card_id,
+            updated_by: "critic-1".to_string(),
+        };
+
+        let events = handle.send_command(archive(card_id)).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(handle.read_state().await.cards[&card_id].archived);
+        let events = handle.send_command(archive(card_id)).await.unwrap();
+        assert!(events.is_empty(), "archiving twice is a no-op");
+
+        handle.send_command(Command::Undo).await.unwrap();
+        assert!(!handle.read_state().await.cards[&card_id].archived);
+
+        handle.send_command(archive(card_id)).await.unwrap();
+        handle
+            .send_command(Command::UnarchiveCard {
+                card_id,
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(!handle.read_state().await.cards[&card_id].archived);
+
+        let missing = Ulid::new();
+        let err = handle.send_command(archive(missing)).await.unwrap_err();
+        assert!(matches!(err, ActorError::CardNotFound(id) if id == missing));
+    }
+
+    #[tokio::test]
+    async fn card_types_are_normalized_or_rejected() {
+        let mut state = SpecState::new();
+        let legacy = Card::new(
+            "brainwave".to_string(),
+            "Old card".to_string(),
+            "human".to_string(),
+        );
+        let legacy_id = legacy.card_id;
+        state.cards.insert(legacy_id, legacy);
+        let handle = spawn(Ulid::new(), state);
+        let create = |card_type: &str| Command::CreateCard {
+            card_type: card_type.to_string(),
+            title: "Typed".to_string(),
+            body: None,
+            lane: None,
+            created_by: "human".to_string(),
+            source_attachment_id: None,
+        };
+        let update = |card_id, card_type: &str| Command::UpdateCard {
+            card_id,
+            title: None,
+            body: None,
+            card_type: Some(card_type.to_string()),
+            refs: None,
+            tags: None,
+            updated_by: "human".to_string(),
+        };
+
+        let created = handle.send_command(create("Open Question")).await.unwrap();
+        let card_id = match &created[0].payload {
+            EventPayload::CardCreated { card } => {
+                assert_eq!(card.card_type, "open_question");
+                card.card_id
+            }
+            other => panic!("expected CardCreated, got {:?}", other),
+        };
+        let err = handle.send_command(create("tsak")).await.unwrap_err();
+        assert!(matches!(err, ActorError::UnknownCardType(t) if t == "tsak"));
+
+        handle.send_command(update(card_id, "TODO")).await.unwrap();
+        assert_eq!(handle.read_state().await.cards[&card_id].card_type, "task");
+        let err = handle
+            .send_command(update(card_id, "brainwave"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ActorError::UnknownCardType(_)));
+
+        // A card with a type from before validation can keep it.
+        handle
+            .send_command(update(legacy_id, "brainwave"))
+            .await
+            .unwrap();
+        handle
+            .send_command(update(legacy_id, "risk"))
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.read_state().await.cards[&legacy_id].card_type,
+            "risk"
+        );
+    }
+
+    #[tokio::test]
+    async fn record_usage_sums_tokens_per_role_without_undo() {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        let record = |role: &str, model: &str, input_tokens, output_tokens| Command::RecordUsage {
+            role: role.to_string(),
+            model: model.to_string(),
+            input_tokens,
+            output_tokens,
+        };
+        for cmd in [
+            record("planner", "model-a", 1_000, 100),
+            record("planner", "model-b", 500, 50),
+            record("manager", "model-a", 2_000, 200),
+        ] {
+            assert_eq!(handle.send_command(cmd).await.unwrap().len(), 1);
+        }
+        // A step that used no tokens records nothing.
+        let empty = handle
+            .send_command(record("planner", "model-b", 0, 0))
+            .await
+            .unwrap();
+        assert!(empty.is_empty());
+
+        let state = handle.read_state().await;
+        let planner = &state.token_usage["planner"];
+        assert_eq!(planner.model, "model-b");
+        assert_eq!(
+            (planner.input_tokens, planner.output_tokens, planner.steps),
+            (1_500, 150, 2)
+        );
+        assert_eq!(state.token_usage["manager"].input_tokens, 2_000);
+        assert!(state.undo_stack.is_empty());
+    }
+
+    #[tokio::test]
+    async fn agent_roster_is_normalized_and_not_undoable() {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        let blank = handle
+            .send_command(Command::SetAgentRoster {
+                roles: vec![" ".to_string()],
+            })
+            .await;
+        assert!(matches!(blank, Err(ActorError::EmptyAgentRoster)));
+
+        let roster = |roles: &[&str]| Command::SetAgentRoster {
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+        };
+        let set = handle
+            .send_command(roster(&[" Manager", "critic", ""]))
+            .await
+            .unwrap();
+        assert_eq!(set.len(), 1);
+        let repeat = handle
+            .send_command(roster(&["manager", "critic"]))
+            .await
+            .unwrap();
+        assert!(repeat.is_empty());
+
+        let state = handle.read_state().await;
+        assert_eq!(
+            state.agent_roster.as_deref(),
+            Some(&["manager".to_string(), "critic".to_string()][..])
+        );
+        assert!(state.undo_stack.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetch_hosts_are_normalized_and_not_undoable() {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        let hosts = |hosts: &[&str]| Command::SetFetchHosts {
+            hosts: hosts.iter().map(|h| h.to_string()).collect(),
+        };
+        let set = handle
+            .send_command(hosts(&[" Docs.rs", "github.com", "", "docs.rs"]))
+            .await
+            .unwrap();
+        assert_eq!(set.len(), 1);
+        let repeat = handle
+            .send_command(hosts(&["github.com", "docs.rs"]))
+            .await
+            .unwrap();
+        assert!(repeat.is_empty());
+
+        {
+            let state = handle.read_state().await;
+            assert_eq!(state.fetch_hosts, vec!["docs.rs", "github.com"]);
+            assert!(state.undo_stack.is_empty());
+        }
+
+        handle.send_command(hosts(&[])).await.unwrap();
+        assert!(handle.read_state().await.fetch_hosts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn agent_messages_record_pinned_model() {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        let mut log = handle
+            .send_command(Command::PinAgentModel {
+                provider: "openai".to_string(),
+                model: "llama3.1:8b".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(log.len(), 1);
+
+        // Re-pinning the same pair is a no-op.
+        let repeat = handle
+            .send_command(Command::PinAgentModel {
+                provider: "openai".to_string(),
+                model: "llama3.1:8b".to_string(),
+            })
+            .await
+            .unwrap();
+        assert!(repeat.is_empty());
+
+        for sender in ["brainstormer-01HTEST", "human"] {
+            log.extend(
+                handle
+                    .send_command(Command::AppendTranscript {
+                        sender: sender.to_string(),
+                        content: "hello".to_string(),
+                        display_name: None,
+                    })
+                    .await
+                    .unwrap(),
+            );
+        }
+        let expected = crate::state::AgentModel {
+            provider: "openai".to_string(),
+            model: "llama3.1:8b".to_string(),
+        };
+        match &log[1].payload {
+            EventPayload::TranscriptAppended { message } => {
+                assert_eq!(message.agent_model.as_ref(), Some(&expected));
+            }
+            other => panic!("expected TranscriptAppended, got {:?}", other),
+        }
+        match &log[2].payload {
+            EventPayload::TranscriptAppended { message } => assert!(message.agent_model.is_none()),
+            other => panic!("expected TranscriptAppended, got {:?}", other),
+        }
+
+        // Replaying the serialized log keeps both the pin and the stamp.
+        let mut replayed = SpecState::new();
+        for event in &log {
+            let json = serde_json::to_string(event).unwrap();
+            replayed.apply(&serde_json::from_str(&json).unwrap());
+        }
+        assert_eq!(replayed.agent_model.as_ref(), Some(&expected));
+        assert_eq!(replayed.transcript[0].agent_model.as_ref(), Some(&expected));
+        assert!(replayed.transcript[1].agent_model.is_none());
+    }
+
+    #[tokio::test]
+    async fn card_attachments_keep_order_and_undo_restores_position() {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Spec".to_string(),
+                one_liner: "One".to_string(),
+                goal: "Goal".to_string(),
+            })
+            .await
+            .unwrap();
+        let created = handle
+            .send_command(Command::CreateCard {
+                card_type: "task".to_string(),
+                title: "Linked".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        let card_id = match &created[0].payload {
+            EventPayload::CardCreated { card } => card.card_id,
+            other => panic!("expected CardCreated, got {:?}", other),
+        };
+        for (target, label) in [
+            ("https://example.com/design", "Design doc"),
+            ("docs/adr/0007.md", ""),
+        ] {
+            handle
+                .send_command(Command::AddCardAttachment {
+                    card_id,
+                    target: target.to_string(),
+                    label: label.to_string(),
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let attachments = handle.read_state().await.cards[&card_id]
+            .attachments
+            .clone();
+        let labels: Vec<&str> = attachments.iter().map(|a| a.label.as_str()).collect();
+        assert_eq!(labels, vec!["Design doc", "docs/adr/0007.md"]);
+
+        // Removing the first and undoing puts it back in front.
+        handle
+            .send_command(Command::RemoveCardAttachment {
+                card_id,
+                attachment_id: attachments[0].attachment_id,
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            handle.read_state().await.cards[&card_id].attachments.len(),
+            1
+        );
+        handle.send_command(Command::Undo).await.unwrap();
+        assert_eq!(
+            handle.read_state().await.cards[&card_id].attachments,
+            attachments
+        );
+
+        let err = handle
+            .send_command(Command::AddCardAttachment {
+                card_id,
+                target: "ftp://example.com/file".to_string(),
+                label: String::new(),
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ActorError::InvalidCardAttachment(_)));
+        let err = handle
+            .send_command(Command::RemoveCardAttachment {
+                card_id,
+                attachment_id: Ulid::new(),
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ActorError::CardAttachmentNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn request_card_review_flags_card_with_reason() {
+        let (handle, ids) = spawn_with_cards(&[("Cache everything", "Plan")]).await;
+        let card_id = ids[0];
+
+        let events = handle
+            .send_command(Command::RequestCardReview {
+                card_id,
+                reason: "  Conflicts with the freshness constraint  ".to_string(),
+                requested_by: "critic-1".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+
+        let state = handle.read_state().await;
+        let card = &state.cards[&card_id];
+        assert!(card.needs_review());
+        let review = card.review.as_ref().unwrap();
+        assert_eq!(review.reason, "Conflicts with the freshness constraint");
+        assert_eq!(review.requested_by, "critic-1");
+        drop(state);
+
+        let err = handle
+            .send_command(Command::RequestCardReview {
+                card_id,
+                reason: "   ".to_string(),
+                requested_by: "critic-1".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ActorError::BlankReviewReason));
+
+        let missing = Ulid::new();
+        let err = handle
+            .send_command(Command::RequestCardReview {
+                card_id: missing,
+                reason: "Why?".to_string(),
+                requested_by: "critic-1".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ActorError::CardNotFound(id) if id == missing));
+    }
+
+    #[tokio::test]
+    async fn resolving_card_review_clears_flag_and_undo_restores_it() {
+        let (handle, ids) = spawn_with_cards(&[("Cache everything", "Plan")]).await;
+        let card_id = ids[0];
+        let resolve = |approved| Command::ResolveCardReview {
+            card_id,
+            approved,
+            resolved_by: "human".to_string(),
+        };
+
+        // Nothing to resolve yet.
+        let err = handle.send_command(resolve(true)).await.unwrap_err();
+        assert!(matches!(err, ActorError::NoCardReview(id) if id == card_id));
+
+        handle
+            .send_command(Command::RequestCardReview {
+                card_id,
+                reason: "Is this in scope?".to_string(),
+                requested_by: "critic-1".to_string(),
+            })
+            .await
+            .unwrap();
+        let events = handle.send_command(resolve(false)).await.unwrap();
+        assert!(matches!(
+            &events[0].payload,
+            EventPayload::CardReviewSet {
+                review: None,
+                resolution: Some(CardReviewResolution::Dismissed),
+                ..
+            }
+        ));
+        assert!(!handle.read_state().await.cards[&card_id].needs_review());
+
+        handle.send_command(Command::Undo).await.unwrap();
+        let state = handle.read_state().await;
+        assert_eq!(
+            state.cards[&card_id].review.as_ref().unwrap().reason,
+            "Is this in scope?"
+        );
+    }
+
+    #[tokio::test]
+    async fn set_card_done_rejects_unknown_card() {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        let missing = Ulid::new();
+        let err = handle
+            .send_command(Command::SetCardDone {
+                card_id: missing,
+                done: true,
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ActorError::CardNotFound(id) if id == missing));
+    }
+
+    #[tokio::test]
+    async fn actor_redo_restores_undone_change_and_is_a_noop_when_empty() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan")]).await;
+
+        // Nothing undone yet: redo succeeds without emitting anything.
+        let events = handle.send_command(Command::Redo).await.unwrap();
+        assert!(events.is_empty());
+
+        handle.send_command(rename("Plan", "Doing")).await.unwrap();
+        handle.send_command(Command::Undo).await.unwrap();
+        assert_eq!(handle.read_state().await.cards[&ids[0]].lane, "Plan");
+
+        let events = handle.send_command(Command::Redo).await.unwrap();
+        assert_eq!(events.len(), 1);
+        let state = handle.read_state().await;
+        assert_eq!(state.cards[&ids[0]].lane, "Doing");
+        assert_eq!(state.lanes, vec!["Ideas", "Doing", "Spec"]);
+        drop(state);
+
+        // Undo/redo can be repeated.
+        handle.send_command(Command::Undo).await.unwrap();
+        handle.send_command(Command::Redo).await.unwrap();
+        assert_eq!(handle.read_state().await.cards[&ids[0]].lane, "Doing");
+    }
+
+    #[tokio::test]
+    async fn actor_redo_is_invalidated_by_a_new_command() {
+        let (handle, ids) = spawn_with_cards(&[("a", "Plan")]).await;
+        handle.send_command(rename("Plan", "Doing")).await.unwrap();
+        handle.send_command(Command::Undo).await.unwrap();
 
-        // Create a card
         handle
-            .send_command(Command::CreateCard {
-                card_type: "idea".to_string(),
-                title: "Undo Me".to_string(),
-                body: None,
-                lane: None,
-                created_by: "human".to_string(),
-                source_attachment_id: None,
+            .send_command(Command::MoveCard {
+                card_id: ids[0],
+                lane: "Spec".into(),
+                order: 0.0,
+                updated_by: "human".into(),
             })
             .await
             .unwrap();
 
-        // Verify card exists
-        {
-            let state = handle.read_state().await;
-            assert_eq!(state.cards.len(), 1);
-        }
-
-        // Undo should remove the card
-        handle.send_command(Command::Undo).await.unwrap();
-
+        let events = handle.send_command(Command::Redo).await.unwrap();
+        assert!(events.is_empty(), "stale redo must not be applied");
         let state = handle.read_state().await;
-        assert_eq!(state.cards.len(), 0, "card should be removed after undo");
+        assert_eq!(state.cards[&ids[0]].lane, "Spec");
+        assert!(state.lanes.contains(&"Plan".to_string()));
     }
 
     #[tokio::test]
@@ -966,6 +2716,76 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn actor_fork_spec_seeds_core_cards_and_lineage() {
+        let parent = crate::model::SpecCore::new(
+            "Parent".to_string(),
+            "Original".to_string(),
+            "Explore".to_string(),
+        );
+        let mut card = Card::new(
+            "task".to_string(),
+            "Carried".to_string(),
+            "human".to_string(),
+        );
+        card.lane = "Plan".to_string();
+        let card_id = card.card_id;
+
+        let child_id = Ulid::new();
+        let handle = spawn(child_id, SpecState::new());
+        let events = handle
+            .send_command(Command::ForkSpec {
+                parent_spec_id: parent.spec_id,
+                core: parent.clone(),
+                cards: vec![card],
+                lanes: vec!["Spec".to_string(), "Plan".to_string()],
+                phase: SpecPhase::Refining,
+            })
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        let state = handle.read_state().await;
+        let core = state.core.as_ref().expect("core should be seeded");
+        assert_eq!(core.spec_id, child_id);
+        assert_eq!(core.parent_spec_id, Some(parent.spec_id));
+        assert_eq!(core.title, "Parent");
+        assert_eq!(state.cards[&card_id].lane, "Plan");
+        assert_eq!(state.lanes, vec!["Spec", "Plan"]);
+        assert_eq!(state.phase, SpecPhase::Refining);
+        assert!(state.undo_stack.is_empty());
+    }
+
+    #[tokio::test]
+    async fn actor_rejects_fork_into_existing_spec() {
+        let spec_id = Ulid::new();
+        let handle = spawn(spec_id, SpecState::new());
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Live".to_string(),
+                one_liner: "Already here".to_string(),
+                goal: "Stay put".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let parent = crate::model::SpecCore::new(
+            "Other".to_string(),
+            "Other".to_string(),
+            "Other".to_string(),
+        );
+        let result = handle
+            .send_command(Command::ForkSpec {
+                parent_spec_id: parent.spec_id,
+                core: parent,
+                cards: Vec::new(),
+                lanes: Vec::new(),
+                phase: SpecPhase::Refining,
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::SpecAlreadyCreated)));
+    }
+
     #[tokio::test]
     async fn transition_phase_produces_event() {
         let spec_id = Ulid::new();
@@ -1680,4 +3500,398 @@ mod tests {
             _ => panic!("wrong event"),
         }
     }
+
+    /// Spawn an actor with a created spec and one card; returns the handle
+    /// and the card's ID for proposal tests.
+    async fn spec_with_card() -> (SpecActorHandle, Ulid) {
+        let handle = spawn(Ulid::new(), SpecState::new());
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Spec".to_string(),
+                one_liner: "One".to_string(),
+                goal: "Goal".to_string(),
+            })
+            .await
+            .unwrap();
+        let events = handle
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "Keep or drop".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        let card_id = match &events[0].payload {
+            EventPayload::CardCreated { card } => card.card_id,
+            _ => panic!("wrong event"),
+        };
+        (handle, card_id)
+    }
+
+    async fn propose_delete(handle: &SpecActorHandle, card_id: Ulid) -> Ulid {
+        let events = handle
+            .send_command(Command::ProposeAction {
+                command: Box::new(Command::DeleteCard {
+                    card_id,
+                    updated_by: "planner".to_string(),
+                }),
+                proposed_by: "planner".to_string(),
+                reason: "card deletion needs review".to_string(),
+            })
+            .await
+            .unwrap();
+        match &events[0].payload {
+            EventPayload::ProposalQueued { proposal } => proposal.proposal_id,
+            _ => panic!("wrong event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn proposal_is_queued_without_applying_command() {
+        let (handle, card_id) = spec_with_card().await;
+        propose_delete(&handle, card_id).await;
+
+        let state = handle.read_state().await;
+        assert_eq!(state.pending_proposals.len(), 1);
+        assert!(state.cards.contains_key(&card_id), "card must survive");
+    }
+
+    #[tokio::test]
+    async fn approving_proposal_applies_buffered_command() {
+        let (handle, card_id) = spec_with_card().await;
+        let proposal_id = propose_delete(&handle, card_id).await;
+
+        let events = handle
+            .send_command(Command::ApproveProposal { proposal_id })
+            .await
+            .unwrap();
+        assert!(matches!(
+            events[0].payload,
+            EventPayload::ProposalApproved { .. }
+        ));
+        assert!(matches!(
+            events[1].payload,
+            EventPayload::CardDeleted { .. }
+        ));
+
+        let state = handle.read_state().await;
+        assert!(state.pending_proposals.is_empty());
+        assert!(!state.cards.contains_key(&card_id));
+    }
+
+    #[tokio::test]
+    async fn rejecting_proposal_discards_it_without_state_change() {
+        let (handle, card_id) = spec_with_card().await;
+        let proposal_id = propose_delete(&handle, card_id).await;
+        let cards_before = handle.read_state().await.cards.len();
+
+        handle
+            .send_command(Command::RejectProposal { proposal_id })
+            .await
+            .unwrap();
+
+        let state = handle.read_state().await;
+        assert!(state.pending_proposals.is_empty());
+        assert_eq!(state.cards.len(), cards_before);
+        assert!(state.cards.contains_key(&card_id));
+    }
+
+    #[tokio::test]
+    async fn resolving_unknown_proposal_is_an_error() {
+        let (handle, _) = spec_with_card().await;
+        let missing = Ulid::new();
+        for cmd in [
+            Command::ApproveProposal {
+                proposal_id: missing,
+            },
+            Command::RejectProposal {
+                proposal_id: missing,
+            },
+        ] {
+            let result = handle.send_command(cmd).await;
+            assert!(matches!(result, Err(ActorError::ProposalNotFound(id)) if id == missing));
+        }
+    }
+
+    async fn request_archive_gate(handle: &SpecActorHandle) -> (Ulid, Ulid) {
+        handle
+            .send_command(Command::RequestReviewGate {
+                command: Box::new(Command::SetSpecArchived { archived: true }),
+                requested_by: "manager".to_string(),
+                reason: "spec is done".to_string(),
+            })
+            .await
+            .unwrap();
+        let state = handle.read_state().await;
+        match state.pending_question() {
+            Some(UserQuestion::ReviewGate {
+                question_id,
+                proposal_id,
+                question,
+            }) => {
+                assert!(question.contains("archive the spec"), "{}", question);
+                (*question_id, *proposal_id)
+            }
+            other => panic!("expected ReviewGate question, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn approving_review_gate_applies_the_gated_command() {
+        let (handle, _) = spec_with_card().await;
+        let (question_id, proposal_id) = request_archive_gate(&handle).await;
+        {
+            let state = handle.read_state().await;
+            assert!(!state.archived, "gated command must not apply yet");
+            assert_eq!(state.pending_proposals[0].proposal_id, proposal_id);
+        }
+
+        handle
+            .send_command(Command::AnswerQuestion {
+                question_id,
+                answer: "Approve".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let state = handle.read_state().await;
+        assert!(state.archived);
+        assert!(state.pending_proposals.is_empty());
+        assert!(state.pending_questions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn declining_review_gate_drops_the_proposal() {
+        let (handle, _) = spec_with_card().await;
+        let (question_id, _) = request_archive_gate(&handle).await;
+
+        handle
+            .send_command(Command::AnswerQuestion {
+                question_id,
+                answer: "Decline".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let state = handle.read_state().await;
+        assert!(!state.archived);
+        assert!(state.pending_proposals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn approving_gated_proposal_directly_clears_its_question() {
+        let (handle, _) = spec_with_card().await;
+        let (_, proposal_id) = request_archive_gate(&handle).await;
+
+        handle
+            .send_command(Command::ApproveProposal { proposal_id })
+            .await
+            .unwrap();
+
+        let state = handle.read_state().await;
+        assert!(state.archived);
+        assert!(state.pending_questions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn review_gate_refuses_ordinary_commands() {
+        let (handle, card_id) = spec_with_card().await;
+        let result = handle
+            .send_command(Command::RequestReviewGate {
+                command: Box::new(Command::SetCardDone {
+                    card_id,
+                    done: true,
+                    updated_by: "manager".to_string(),
+                }),
+                requested_by: "manager".to_string(),
+                reason: "not gated".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::NotReviewGated)));
+    }
+
+    #[tokio::test]
+    async fn invalid_or_nested_proposals_are_refused() {
+        let (handle, _) = spec_with_card().await;
+
+        let result = handle
+            .send_command(Command::ProposeAction {
+                command: Box::new(Command::DeleteCard {
+                    card_id: Ulid::new(),
+                    updated_by: "planner".to_string(),
+                }),
+                proposed_by: "planner".to_string(),
+                reason: "stale card".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::CardNotFound(_))));
+
+        let result = handle
+            .send_command(Command::ProposeAction {
+                command: Box::new(Command::RejectProposal {
+                    proposal_id: Ulid::new(),
+                }),
+                proposed_by: "planner".to_string(),
+                reason: "nested".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::InvalidProposal)));
+        assert!(handle.read_state().await.pending_proposals.is_empty());
+    }
+
+    #[tokio::test]
+    async fn undoing_a_merge_removes_the_merged_cards_and_cannot_be_redone() {
+        let (handle, kept) = spec_with_card().await;
+        let (source, source_card) = spec_with_card().await;
+        let source_state = source.read_state().await.clone();
+
+        handle
+            .send_command(Command::MergeFromSpec {
+                source_spec_id: Ulid::new(),
+                source_title: "Other".to_string(),
+                cards: source_state.cards.values().cloned().collect(),
+                lanes: source_state.lanes.clone(),
+                transcript: Vec::new(),
+            })
+            .await
+            .unwrap();
+        let state = handle.read_state().await.clone();
+        assert_eq!(state.cards.len(), 2);
+        assert!(!state.cards.contains_key(&source_card));
+        assert_eq!(state.transcript.len(), 1);
+
+        handle.send_command(Command::Undo).await.unwrap();
+        let state = handle.read_state().await;
+        assert_eq!(state.cards.keys().collect::<Vec<_>>(), vec![&kept]);
+        drop(state);
+
+        // The undo left the transcript and lineage, so replaying the merge
+        // would duplicate them; there is nothing to redo.
+        let events = handle.send_command(Command::Redo).await.unwrap();
+        assert!(events.is_empty());
+        let state = handle.read_state().await;
+        assert_eq!(state.cards.len(), 1);
+        assert_eq!(state.transcript.len(), 1);
+        assert_eq!(state.core.as_ref().unwrap().merged_from.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn checklist_items_can_be_added_toggled_and_removed() {
+        let (handle, _) = spec_with_card().await;
+        let result = handle
+            .send_command(Command::AddChecklistItem {
+                text: "   ".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::BlankChecklistItem)));
+
+        handle
+            .send_command(Command::AddChecklistItem {
+                text: " Docs written ".to_string(),
+            })
+            .await
+            .unwrap();
+        let item_id = {
+            let state = handle.read_state().await;
+            assert_eq!(state.done_checklist[0].text, "Docs written");
+            assert!(!state.done_checklist[0].checked);
+            state.done_checklist[0].item_id
+        };
+
+        handle
+            .send_command(Command::ToggleChecklistItem { item_id })
+            .await
+            .unwrap();
+        assert!(handle.read_state().await.done_checklist[0].checked);
+        handle
+            .send_command(Command::ToggleChecklistItem { item_id })
+            .await
+            .unwrap();
+        assert!(!handle.read_state().await.done_checklist[0].checked);
+
+        handle.send_command(Command::Undo).await.unwrap();
+        assert!(handle.read_state().await.done_checklist[0].checked);
+
+        handle
+            .send_command(Command::RemoveChecklistItem { item_id })
+            .await
+            .unwrap();
+        assert!(handle.read_state().await.done_checklist.is_empty());
+        let result = handle
+            .send_command(Command::ToggleChecklistItem { item_id })
+            .await;
+        assert!(matches!(result, Err(ActorError::ChecklistItemNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn external_links_keep_their_order_and_can_be_removed() {
+        let (handle, _) = spec_with_card().await;
+        let result = handle
+            .send_command(Command::AddExternalLink {
+                url: "javascript:alert(1)".to_string(),
+                label: "Bad".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::InvalidExternalLink(_))));
+
+        for (url, label) in [
+            ("https://github.com/acme/app/issues/1", "Epic"),
+            (" https://jira.example.com/browse/APP-2 ", "  "),
+        ] {
+            handle
+                .send_command(Command::AddExternalLink {
+                    url: url.to_string(),
+                    label: label.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+        let links = handle
+            .read_state()
+            .await
+            .core
+            .clone()
+            .unwrap()
+            .external_links;
+        let labels: Vec<&str> = links.iter().map(|l| l.label.as_str()).collect();
+        assert_eq!(labels, ["Epic", "https://jira.example.com/browse/APP-2"]);
+
+        handle
+            .send_command(Command::RemoveExternalLink {
+                link_id: links[0].link_id,
+            })
+            .await
+            .unwrap();
+        let remaining = handle
+            .read_state()
+            .await
+            .core
+            .clone()
+            .unwrap()
+            .external_links;
+        assert_eq!(remaining, vec![links[1].clone()]);
+
+        // Undo puts the removed link back in its original position.
+        handle.send_command(Command::Undo).await.unwrap();
+        assert_eq!(
+            handle
+                .read_state()
+                .await
+                .core
+                .clone()
+                .unwrap()
+                .external_links,
+            links
+        );
+
+        let result = handle
+            .send_command(Command::RemoveExternalLink {
+                link_id: Ulid::new(),
+            })
+            .await;
+        assert!(matches!(result, Err(ActorError::ExternalLinkNotFound(_))));
+    }
 }
@@ -13,7 +13,24 @@ use ulid::Ulid;
 /// brief). Cards authored organically during brainstorming leave this field
 /// as None. The field deserializes as None when absent, so pre-existing
 /// events in the log continue to materialize without migration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `done` marks a card as completed checklist-style without moving it to
+/// another lane. It defaults to false for the same reason.
+///
+/// `attachments` links the card to external supporting material (URLs or
+/// file paths), in the order they were added. Empty when absent.
+///
+/// `due_date` is an optional target date for planning; see `is_overdue`.
+///
+/// `tags` are freeform labels, normalized by `normalize_tags`, that the
+/// board can filter on. Empty when absent.
+///
+/// `review` is set while an agent has flagged the card for human review,
+/// and cleared when a human approves or dismisses it. None when absent.
+///
+/// `archived` hides the card from the board and exports without deleting
+/// it, so it can be brought back later. It defaults to false when absent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Card {
     pub card_id: Ulid,
     pub card_type: String,
@@ -28,9 +45,157 @@ pub struct Card {
     pub updated_by: String,
     #[serde(default)]
     pub source_attachment_id: Option<Ulid>,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub attachments: Vec<CardAttachment>,
+    #[serde(default)]
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<CardReview>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+}
+
+/// A request for a human to look at a card, raised by an agent (typically
+/// the critic) that found something it cannot settle on its own.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardReview {
+    pub reason: String,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// How a human closed a card review: the card is fine as it stands
+/// (`Approved`), or the concern does not apply (`Dismissed`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardReviewResolution {
+    Approved,
+    Dismissed,
+}
+
+/// Where a card sits on the board: its lane and its sort order within it.
+/// Used by `CardsReordered` to place several cards in one event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardPosition {
+    pub card_id: Ulid,
+    pub lane: String,
+    pub order: f64,
+}
+
+/// An external reference attached to a card: a URL or file path plus a
+/// human-readable label.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardAttachment {
+    pub attachment_id: Ulid,
+    pub target: String,
+    pub label: String,
+}
+
+impl CardAttachment {
+    /// Build an attachment after trimming and validating `target`. A blank
+    /// label falls back to the target itself.
+    pub fn new(target: &str, label: &str) -> Result<Self, String> {
+        let target = target.trim();
+        validate_attachment_target(target)?;
+        let label = label.trim();
+        Ok(Self {
+            attachment_id: Ulid::new(),
+            target: target.to_string(),
+            label: if label.is_empty() { target } else { label }.to_string(),
+        })
+    }
+
+    /// Whether the target is a web link rather than a file path.
+    pub fn is_url(&self) -> bool {
+        self.target.starts_with("http://") || self.target.starts_with("https://")
+    }
+}
+
+/// Card types the exporters, lint and agents understand, in the order
+/// forms list them.
+const KNOWN_CARD_TYPES: &[&str] = &[
+    "idea",
+    "task",
+    "plan",
+    "decision",
+    "constraint",
+    "risk",
+    "assumption",
+    "open_question",
+    "note",
+    "inspiration",
+    "vibes",
+];
+
+/// Common alternate spellings and their canonical card type.
+const CARD_TYPE_ALIASES: &[(&str, &str)] = &[
+    ("question", "open_question"),
+    ("todo", "task"),
+    ("tsk", "task"),
+    ("ideas", "idea"),
+    ("tasks", "task"),
+    ("risks", "risk"),
+];
+
+/// The canonical form of a card type: trimmed, lowercased, with spaces and
+/// dashes as underscores and common aliases (e.g. "question", "todo")
+/// mapped. None when the result is not one of [`Card::known_types`].
+pub fn normalize_card_type(card_type: &str) -> Option<String> {
+    let normalized = card_type.trim().to_lowercase().replace([' ', '-'], "_");
+    let canonical = CARD_TYPE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == normalized)
+        .map_or(normalized.as_str(), |(_, canonical)| canonical);
+    KNOWN_CARD_TYPES
+        .contains(&canonical)
+        .then(|| canonical.to_string())
+}
+
+/// Clean up user-entered tags: trim and lowercase each one, drop blanks and
+/// duplicates, and keep the first-seen order.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if !tag.is_empty() && !out.contains(&tag) {
+            out.push(tag);
+        }
+    }
+    out
+}
+
+/// Minimal sanity check for an attachment target. Anything with a `scheme://`
+/// prefix must be http(s); everything else is treated as a file path and only
+/// has to be non-empty and free of control characters.
+fn validate_attachment_target(target: &str) -> Result<(), String> {
+    if target.is_empty() {
+        return Err("attachment target must not be empty".to_string());
+    }
+    if target.chars().any(char::is_control) {
+        return Err("attachment target must not contain control characters".to_string());
+    }
+    if let Some((scheme, rest)) = target.split_once("://") {
+        let scheme = scheme.to_ascii_lowercase();
+        if scheme != "http" && scheme != "https" {
+            return Err(format!("unsupported URL scheme: {}", scheme));
+        }
+        if rest.is_empty() || rest.starts_with('/') {
+            return Err("URL is missing a host".to_string());
+        }
+    }
+    Ok(())
 }
 
 impl Card {
+    /// The card types commands accept; see [`normalize_card_type`].
+    pub fn known_types() -> &'static [&'static str] {
+        KNOWN_CARD_TYPES
+    }
+
     /// Create a new Card with the given type, title, and creator. Defaults
     /// to the "Ideas" lane with order 0.0, no body, and empty refs.
     pub fn new(card_type: String, title: String, created_by: String) -> Self {
@@ -48,6 +213,32 @@ impl Card {
             created_by: created_by.clone(),
             updated_by: created_by,
             source_attachment_id: None,
+            done: false,
+            attachments: Vec::new(),
+            due_date: None,
+            tags: Vec::new(),
+            review: None,
+            archived: false,
+        }
+    }
+
+    /// Whether an agent has flagged the card for human review.
+    pub fn needs_review(&self) -> bool {
+        self.review.is_some()
+    }
+
+    /// Whether the card carries `tag`, compared case-insensitively.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim();
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Whether the card's due date has passed as of `now`. Finished cards,
+    /// either marked done or sitting in a "Done" lane, are never overdue.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        match self.due_date {
+            Some(due) => due < now && !self.done && !self.lane.eq_ignore_ascii_case("done"),
+            None => false,
         }
     }
 }
@@ -74,6 +265,25 @@ mod tests {
         assert_eq!(card.order, 0.0);
     }
 
+    #[test]
+    fn normalize_card_type_maps_aliases_and_rejects_typos() {
+        assert_eq!(normalize_card_type("Task").as_deref(), Some("task"));
+        assert_eq!(
+            normalize_card_type(" open-question ").as_deref(),
+            Some("open_question")
+        );
+        assert_eq!(
+            normalize_card_type("Question").as_deref(),
+            Some("open_question")
+        );
+        assert_eq!(normalize_card_type("tsk").as_deref(), Some("task"));
+        assert_eq!(normalize_card_type("tsak"), None);
+        assert_eq!(normalize_card_type(""), None);
+        for card_type in Card::known_types() {
+            assert_eq!(normalize_card_type(card_type).as_deref(), Some(*card_type));
+        }
+    }
+
     #[test]
     fn card_serde_round_trip() {
         let card = Card::new(
@@ -95,6 +305,25 @@ mod tests {
         assert_eq!(card.source_attachment_id, deserialized.source_attachment_id);
     }
 
+    #[test]
+    fn past_due_card_is_overdue_until_finished() {
+        let now = Utc::now();
+        let mut card = Card::new("task".to_string(), "Ship".to_string(), "human".to_string());
+        assert!(!card.is_overdue(now));
+
+        card.due_date = Some(now + chrono::Duration::days(1));
+        assert!(!card.is_overdue(now));
+
+        card.due_date = Some(now - chrono::Duration::days(1));
+        assert!(card.is_overdue(now));
+
+        card.lane = "Done".to_string();
+        assert!(!card.is_overdue(now));
+        card.lane = "Plan".to_string();
+        card.done = true;
+        assert!(!card.is_overdue(now));
+    }
+
     #[test]
     fn card_new_defaults_source_attachment_id_to_none() {
         let card = Card::new(
@@ -139,5 +368,44 @@ mod tests {
         });
         let card: Card = serde_json::from_value(legacy).expect("deserialize legacy");
         assert!(card.source_attachment_id.is_none());
+        assert!(card.attachments.is_empty());
+        assert!(card.tags.is_empty());
+    }
+
+    #[test]
+    fn tags_are_normalized_and_matched_case_insensitively() {
+        let tags = normalize_tags(&[
+            " Backend ".to_string(),
+            "".to_string(),
+            "backend".to_string(),
+            "UX".to_string(),
+        ]);
+        assert_eq!(tags, vec!["backend".to_string(), "ux".to_string()]);
+
+        let mut card = Card::new(
+            "idea".to_string(),
+            "Tagged".to_string(),
+            "human".to_string(),
+        );
+        card.tags = tags;
+        assert!(card.has_tag("UX"));
+        assert!(!card.has_tag("frontend"));
+    }
+
+    #[test]
+    fn attachment_target_validation() {
+        let link = CardAttachment::new(" https://example.com/design ", "").unwrap();
+        assert_eq!(link.target, "https://example.com/design");
+        assert_eq!(link.label, "https://example.com/design");
+        assert!(link.is_url());
+
+        let path = CardAttachment::new("docs/adr/0001.md", "ADR 1").unwrap();
+        assert_eq!(path.label, "ADR 1");
+        assert!(!path.is_url());
+
+        assert!(CardAttachment::new("   ", "x").is_err());
+        assert!(CardAttachment::new("javascript://alert(1)", "x").is_err());
+        assert!(CardAttachment::new("https://", "x").is_err());
+        assert!(CardAttachment::new("a\nb", "x").is_err());
     }
 }
@@ -1,14 +1,17 @@
 // ABOUTME: Defines the Command enum representing all write operations that can be applied to a spec.
 // ABOUTME: Commands are intent-based inputs that get validated and converted into events.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
-use crate::transcript::UserQuestion;
+use crate::card::Card;
+use crate::model::SpecCore;
+use crate::transcript::{TranscriptMessage, UserQuestion};
 
 /// A command representing a desired mutation to a spec. Commands are validated
 /// and translated into one or more events by the command handler.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Command {
     CreateSpec {
@@ -16,6 +19,26 @@ pub enum Command {
         one_liner: String,
         goal: String,
     },
+    /// Seed an empty spec with a copy of another spec's core, cards and
+    /// lane order, recording `parent_spec_id` as the lineage link on the
+    /// new spec.
+    ForkSpec {
+        parent_spec_id: Ulid,
+        core: SpecCore,
+        cards: Vec<Card>,
+        lanes: Vec<String>,
+        phase: crate::state::SpecPhase,
+    },
+    /// Copy another spec's cards (under fresh IDs, with refs between them
+    /// remapped) and transcript into this one, recording the source in
+    /// `merged_from`. `lanes` is the source's lane order.
+    MergeFromSpec {
+        source_spec_id: Ulid,
+        source_title: String,
+        cards: Vec<Card>,
+        lanes: Vec<String>,
+        transcript: Vec<TranscriptMessage>,
+    },
     UpdateSpecCore {
         title: Option<String>,
         one_liner: Option<String>,
@@ -26,6 +49,8 @@ pub enum Command {
         risks: Option<String>,
         notes: Option<String>,
     },
+    /// Add a card to `lane` (default "Ideas"). A lane the spec does not have
+    /// yet is added at the end of the board, so imports keep their lanes.
     CreateCard {
         card_type: String,
         title: String,
@@ -42,24 +67,117 @@ pub enum Command {
     UpdateCard {
         card_id: Ulid,
         title: Option<String>,
+        #[serde(default, with = "crate::event::body_change")]
         body: Option<Option<String>>,
         card_type: Option<String>,
         refs: Option<Vec<String>>,
+        /// Replace the card's tags; normalized with `normalize_tags`.
+        #[serde(default)]
+        tags: Option<Vec<String>>,
         updated_by: String,
     },
+    /// Put a card in `lane` at sort position `order`. The lane must exist.
     MoveCard {
         card_id: Ulid,
         lane: String,
         order: f64,
         updated_by: String,
     },
+    /// Move a card to position `index` (0-based, clamped) within `lane`,
+    /// which must exist. The server picks the sort order: the midpoint
+    /// between the new neighbours, or a renumber of the lane when that gap
+    /// has run out.
+    ReorderCard {
+        card_id: Ulid,
+        lane: String,
+        index: usize,
+        updated_by: String,
+    },
     DeleteCard {
         card_id: Ulid,
         updated_by: String,
     },
+    SetCardDone {
+        card_id: Ulid,
+        done: bool,
+        updated_by: String,
+    },
+    /// Hide a card from the board and exports without deleting it. A no-op
+    /// when the card is already archived.
+    ArchiveCard {
+        card_id: Ulid,
+        updated_by: String,
+    },
+    /// Bring an archived card back onto the board. A no-op when the card
+    /// is not archived.
+    UnarchiveCard {
+        card_id: Ulid,
+        updated_by: String,
+    },
+    /// Set or clear (`None`) a card's target date.
+    SetCardDueDate {
+        card_id: Ulid,
+        due_date: Option<DateTime<Utc>>,
+        updated_by: String,
+    },
+    /// Flag a card for human review, replacing any open request on it.
+    RequestCardReview {
+        card_id: Ulid,
+        reason: String,
+        requested_by: String,
+    },
+    /// Close a card's open review request, approving the card or
+    /// dismissing the concern.
+    ResolveCardReview {
+        card_id: Ulid,
+        approved: bool,
+        resolved_by: String,
+    },
+    /// Link a card to an external URL or file path. An empty label falls
+    /// back to the target.
+    AddCardAttachment {
+        card_id: Ulid,
+        target: String,
+        label: String,
+        updated_by: String,
+    },
+    RemoveCardAttachment {
+        card_id: Ulid,
+        attachment_id: Ulid,
+        updated_by: String,
+    },
+    /// Rename a lane and move its cards with it. Renaming onto an existing
+    /// lane merges the two.
+    RenameLane {
+        from: String,
+        to: String,
+        updated_by: String,
+    },
+    /// Add an empty lane at the end of the board.
+    CreateLane {
+        name: String,
+        updated_by: String,
+    },
+    /// Remove a lane. A lane that still holds cards can only be deleted by
+    /// moving them to an existing lane, `move_cards_to`.
+    DeleteLane {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        move_cards_to: Option<String>,
+        updated_by: String,
+    },
+    /// Put the spec's lanes in `order`. Every existing lane must appear
+    /// exactly once.
+    ReorderLanes {
+        order: Vec<String>,
+        updated_by: String,
+    },
     AppendTranscript {
         sender: String,
         content: String,
+        /// Persona name to show instead of the sender's role label.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        display_name: Option<String>,
     },
     AskQuestion {
         question: UserQuestion,
@@ -104,6 +222,9 @@ pub enum Command {
         attachment_id: Ulid,
     },
     Undo,
+    /// Re-apply the most recently undone change. A no-op when nothing has
+    /// been undone since the last change.
+    Redo,
     StreamDelta {
         agent_id: String,
         text: String,
@@ -112,6 +233,108 @@ pub enum Command {
         agent_id: String,
         activity: String,
     },
+    /// Park `command` in the pending-proposals queue for human review
+    /// instead of applying it. The command is validated up front so only
+    /// proposals that could actually be applied are queued.
+    ProposeAction {
+        command: Box<Command>,
+        proposed_by: String,
+        reason: String,
+    },
+    /// Apply a queued proposal's command and remove it from the queue.
+    ApproveProposal {
+        proposal_id: Ulid,
+    },
+    /// Discard a queued proposal without applying it.
+    RejectProposal {
+        proposal_id: Ulid,
+    },
+    /// Record the provider/model agents are running on. A no-op when the
+    /// same pair is already pinned.
+    PinAgentModel {
+        provider: String,
+        model: String,
+    },
+    /// Set the provider/model this spec's agents start on instead of the
+    /// server defaults, or clear it with None. Takes effect the next time
+    /// agents start. A no-op when the override already matches.
+    SetModelOverride {
+        model: Option<crate::state::AgentModel>,
+    },
+    /// Add one agent step's token usage to the spec's totals for `role`.
+    /// A no-op when no tokens were used.
+    RecordUsage {
+        role: String,
+        model: String,
+        input_tokens: u64,
+        output_tokens: u64,
+    },
+    /// Choose which agent roles the swarm runs for this spec, by role label
+    /// (e.g. `critic`), one agent per entry. Takes effect the next time the
+    /// agents start. A no-op when the roster already matches.
+    SetAgentRoster {
+        roles: Vec<String>,
+    },
+    /// Set the hostnames this spec's agents may read with the fetch_url
+    /// tool. An empty list turns the tool off. Takes effect on the next
+    /// agent step. A no-op when the allowlist already matches.
+    SetFetchHosts {
+        hosts: Vec<String>,
+    },
+    /// Turn the final snapshot this spec gets on a clean server shutdown on
+    /// or off (on by default). A no-op when the flag already matches.
+    SetSnapshotOnShutdown {
+        enabled: bool,
+    },
+    /// Archive or unarchive the spec. A no-op when the flag already matches.
+    SetSpecArchived {
+        archived: bool,
+    },
+    /// Append an item to the spec's definition-of-done checklist, unchecked.
+    AddChecklistItem {
+        text: String,
+    },
+    /// Flip a checklist item between checked and unchecked.
+    ToggleChecklistItem {
+        item_id: Ulid,
+    },
+    RemoveChecklistItem {
+        item_id: Ulid,
+    },
+    /// Link the spec to an external tracker item (issue, ticket). `url` must
+    /// be http(s); an empty label falls back to the URL.
+    AddExternalLink {
+        url: String,
+        label: String,
+    },
+    RemoveExternalLink {
+        link_id: Ulid,
+    },
+    /// Hold a release-type `command` (see [`Command::review_gate_action`])
+    /// as a proposal and ask the human to approve it with a
+    /// [`UserQuestion::ReviewGate`] question.
+    RequestReviewGate {
+        command: Box<Command>,
+        requested_by: String,
+        reason: String,
+    },
+}
+
+impl Command {
+    /// Short description of the action when this command needs explicit
+    /// human approval before an agent may apply it, mirroring the DOT
+    /// pipeline's `review_gate`. None for ordinary commands.
+    pub fn review_gate_action(&self) -> Option<&'static str> {
+        match self {
+            Command::TransitionPhase {
+                target: crate::state::SpecPhase::Complete,
+            } => Some("mark the spec complete"),
+            Command::SetSpecArchived { archived: true } => Some("archive the spec"),
+            Command::SetFetchHosts { .. } => Some("change which hosts agents may fetch"),
+            Command::SetModelOverride { .. } => Some("change which model agents run on"),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +381,7 @@ mod tests {
                 body: None,
                 card_type: None,
                 refs: None,
+                tags: Some(vec!["backend".to_string()]),
                 updated_by: "agent-1".to_string(),
             },
             Command::MoveCard {
@@ -166,13 +390,77 @@ mod tests {
                 order: 2.0,
                 updated_by: "human".to_string(),
             },
+            Command::ReorderCard {
+                card_id: Ulid::new(),
+                lane: "Plan".to_string(),
+                index: 1,
+                updated_by: "human".to_string(),
+            },
             Command::DeleteCard {
                 card_id: Ulid::new(),
                 updated_by: "human".to_string(),
             },
+            Command::SetCardDone {
+                card_id: Ulid::new(),
+                done: true,
+                updated_by: "human".to_string(),
+            },
+            Command::ArchiveCard {
+                card_id: Ulid::new(),
+                updated_by: "human".to_string(),
+            },
+            Command::UnarchiveCard {
+                card_id: Ulid::new(),
+                updated_by: "human".to_string(),
+            },
+            Command::SetCardDueDate {
+                card_id: Ulid::new(),
+                due_date: Some(chrono::Utc::now()),
+                updated_by: "human".to_string(),
+            },
+            Command::RequestCardReview {
+                card_id: Ulid::new(),
+                reason: "Contradicts the latency constraint".to_string(),
+                requested_by: "critic-1".to_string(),
+            },
+            Command::ResolveCardReview {
+                card_id: Ulid::new(),
+                approved: false,
+                resolved_by: "human".to_string(),
+            },
+            Command::AddCardAttachment {
+                card_id: Ulid::new(),
+                target: "https://example.com/brief".to_string(),
+                label: "Brief".to_string(),
+                updated_by: "human".to_string(),
+            },
+            Command::RemoveCardAttachment {
+                card_id: Ulid::new(),
+                attachment_id: Ulid::new(),
+                updated_by: "human".to_string(),
+            },
+            Command::RenameLane {
+                from: "Plan".to_string(),
+                to: "Doing".to_string(),
+                updated_by: "human".to_string(),
+            },
+            Command::CreateLane {
+                name: "Review".to_string(),
+                updated_by: "human".to_string(),
+            },
+            Command::DeleteLane {
+                name: "Review".to_string(),
+                move_cards_to: Some("Plan".to_string()),
+                updated_by: "human".to_string(),
+            },
+            Command::ReorderLanes {
+                order: vec!["Spec".to_string(), "Ideas".to_string()],
+                updated_by: "human".to_string(),
+            },
             Command::AppendTranscript {
                 sender: "system".to_string(),
                 content: "Spec created".to_string(),
+                display_name: None,
             },
             Command::AskQuestion {
                 question: UserQuestion::Freeform {
@@ -201,6 +489,7 @@ mod tests {
                 content: "<h1>Hello</h1>".to_string(),
             },
             Command::Undo,
+            Command::Redo,
             Command::StreamDelta {
                 agent_id: "manager-1".to_string(),
                 text: "token".to_string(),
@@ -209,6 +498,37 @@ mod tests {
                 agent_id: "brainstormer-1".to_string(),
                 activity: "creating card".to_string(),
             },
+            Command::PinAgentModel {
+                provider: "openai".to_string(),
+                model: "gpt-4o".to_string(),
+            },
+            Command::RecordUsage {
+                role: "planner".to_string(),
+                model: "gpt-4o".to_string(),
+                input_tokens: 1200,
+                output_tokens: 300,
+            },
+            Command::SetSpecArchived { archived: true },
+            Command::SetAgentRoster {
+                roles: vec!["manager".to_string(), "critic".to_string()],
+            },
+            Command::SetFetchHosts {
+                hosts: vec!["github.com".to_string()],
+            },
+            Command::SetSnapshotOnShutdown { enabled: false },
+            Command::SetModelOverride {
+                model: Some(crate::state::AgentModel {
+                    provider: "ollama".to_string(),
+                    model: "llama3.1".to_string(),
+                }),
+            },
+            Command::AddExternalLink {
+                url: "https://github.com/acme/app/issues/12".to_string(),
+                label: "Tracking issue".to_string(),
+            },
+            Command::RemoveExternalLink {
+                link_id: Ulid::new(),
+            },
         ];
 
         for cmd in &commands {
@@ -220,6 +540,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fork_spec_round_trip() {
+        let parent_spec_id = Ulid::new();
+        let cmd = Command::ForkSpec {
+            parent_spec_id,
+            core: SpecCore::new("Src".to_string(), "One".to_string(), "Goal".to_string()),
+            cards: vec![Card::new(
+                "idea".to_string(),
+                "Copied".to_string(),
+                "human".to_string(),
+            )],
+            lanes: vec!["Spec".to_string(), "Ideas".to_string()],
+            phase: crate::state::SpecPhase::Refining,
+        };
+        let json = serde_json::to_string(&cmd).unwrap();
+        assert!(json.contains("\"type\":\"ForkSpec\""));
+        let back: Command = serde_json::from_str(&json).unwrap();
+        match back {
+            Command::ForkSpec {
+                parent_spec_id: got,
+                cards,
+                lanes,
+                ..
+            } => {
+                assert_eq!(got, parent_spec_id);
+                assert_eq!(cards.len(), 1);
+                assert_eq!(lanes, vec!["Spec", "Ideas"]);
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
     #[test]
     fn transition_phase_round_trip() {
         let cmd = Command::TransitionPhase {
@@ -5,12 +5,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
-use crate::card::Card;
-use crate::state::ContextAttachment;
+use crate::card::{Card, CardAttachment, CardPosition, CardReview, CardReviewResolution};
+use crate::model::ExternalLink;
+use crate::model::SpecCore;
+use crate::state::{AgentModel, ChecklistItem, ContextAttachment, ProposedAction};
 use crate::transcript::{TranscriptMessage, UserQuestion};
 
 /// An event envelope wrapping a timestamped, sequenced payload for a given spec.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Event {
     pub event_id: u64,
     pub spec_id: Ulid,
@@ -20,7 +22,7 @@ pub struct Event {
 
 /// The set of things that can happen to a spec. Each variant captures the
 /// minimum data needed to reconstruct or replay state changes.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum EventPayload {
     SpecCreated {
@@ -28,6 +30,24 @@ pub enum EventPayload {
         one_liner: String,
         goal: String,
     },
+    /// `lanes` is the parent's lane order. Forks recorded before lanes were
+    /// carried have none; the child then keeps the default lanes.
+    SpecForked {
+        parent_spec_id: Ulid,
+        core: SpecCore,
+        cards: Vec<Card>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        lanes: Vec<String>,
+    },
+    /// Cards and transcript merged in from another spec. `cards` already
+    /// carry their new IDs, lanes, and orders; `lanes_added` are source
+    /// lanes the destination did not have, appended in source order.
+    SpecMerged {
+        source_spec_id: Ulid,
+        cards: Vec<Card>,
+        lanes_added: Vec<String>,
+        transcript: Vec<TranscriptMessage>,
+    },
     SpecCoreUpdated {
         title: Option<String>,
         one_liner: Option<String>,
@@ -44,18 +64,85 @@ pub enum EventPayload {
     CardUpdated {
         card_id: Ulid,
         title: Option<String>,
+        #[serde(default, with = "body_change")]
         body: Option<Option<String>>,
         card_type: Option<String>,
         refs: Option<Vec<String>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tags: Option<Vec<String>>,
     },
     CardMoved {
         card_id: Ulid,
         lane: String,
         order: f64,
     },
+    /// One or more cards placed in one step: the reordered card, plus the
+    /// rest of its lane when the lane had to be renumbered.
+    CardsReordered {
+        positions: Vec<CardPosition>,
+    },
     CardDeleted {
         card_id: Ulid,
     },
+    CardDoneSet {
+        card_id: Ulid,
+        done: bool,
+    },
+    CardDueDateSet {
+        card_id: Ulid,
+        due_date: Option<DateTime<Utc>>,
+    },
+    /// A card was archived (hidden from the board and exports) or brought
+    /// back.
+    CardArchivedSet {
+        card_id: Ulid,
+        archived: bool,
+    },
+    /// A card was flagged for human review (`review` set) or its review was
+    /// closed (`None`); `resolution` records how a human closed it.
+    CardReviewSet {
+        card_id: Ulid,
+        review: Option<CardReview>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        resolution: Option<CardReviewResolution>,
+    },
+    /// An attachment was inserted into a card's list at `index` (appended
+    /// when the index is past the end). Undoing a removal re-inserts at the
+    /// original position.
+    CardAttachmentAdded {
+        card_id: Ulid,
+        index: usize,
+        attachment: CardAttachment,
+    },
+    CardAttachmentRemoved {
+        card_id: Ulid,
+        attachment_id: Ulid,
+    },
+    /// A lane was renamed (or merged into another). `lanes` is the full lane
+    /// list afterwards and `card_ids` are the cards moved from `from` to `to`,
+    /// so the lane list and the cards change together.
+    LaneRenamed {
+        from: String,
+        to: String,
+        lanes: Vec<String>,
+        card_ids: Vec<Ulid>,
+    },
+    /// A lane was deleted. `lanes` is the full lane list afterwards and
+    /// `card_ids` are the cards moved from `name` to `moved_to`; it is `None`
+    /// only when the lane was empty.
+    LaneDeleted {
+        name: String,
+        lanes: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        moved_to: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        card_ids: Vec<Ulid>,
+    },
+    /// The lane list was replaced (a lane created or the lanes reordered).
+    /// No card changes lane.
+    LanesChanged {
+        lanes: Vec<String>,
+    },
     TranscriptAppended {
         message: TranscriptMessage,
     },
@@ -78,6 +165,10 @@ pub enum EventPayload {
         target_event_id: u64,
         inverse_events: Vec<EventPayload>,
     },
+    /// The most recently undone change was re-applied.
+    RedoApplied {
+        payloads: Vec<EventPayload>,
+    },
     SnapshotWritten {
         snapshot_id: u64,
     },
@@ -113,6 +204,60 @@ pub enum EventPayload {
     ContextRemoved {
         attachment_id: Ulid,
     },
+    ProposalQueued {
+        proposal: ProposedAction,
+    },
+    ProposalApproved {
+        proposal_id: Ulid,
+    },
+    ProposalRejected {
+        proposal_id: Ulid,
+    },
+    AgentModelPinned {
+        provider: String,
+        model: String,
+    },
+    /// One agent step's token usage.
+    UsageRecorded {
+        role: String,
+        model: String,
+        input_tokens: u64,
+        output_tokens: u64,
+    },
+    AgentRosterSet {
+        roles: Vec<String>,
+    },
+    FetchHostsSet {
+        hosts: Vec<String>,
+    },
+    SnapshotOnShutdownSet {
+        enabled: bool,
+    },
+    ModelOverrideSet {
+        model: Option<AgentModel>,
+    },
+    SpecArchivedSet {
+        archived: bool,
+    },
+    ChecklistItemAdded {
+        item: ChecklistItem,
+    },
+    ChecklistItemToggled {
+        item_id: Ulid,
+        checked: bool,
+    },
+    ChecklistItemRemoved {
+        item_id: Ulid,
+    },
+    /// An external link was inserted at `index` (appended when the index is
+    /// past the end), so undoing a removal restores its position.
+    ExternalLinkAdded {
+        index: usize,
+        link: ExternalLink,
+    },
+    ExternalLinkRemoved {
+        link_id: Ulid,
+    },
 }
 
 impl EventPayload {
@@ -127,6 +272,41 @@ impl EventPayload {
     }
 }
 
+/// Serde for a card body edit (`None` = unchanged, `Some(None)` = cleared).
+/// Plain JSON would write both as `null`, so a cleared body is written as
+/// `{"cleared": true}`; `null` keeps meaning "unchanged" for existing logs.
+pub(crate) mod body_change {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Text(String),
+        Cleared { cleared: bool },
+    }
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Option<String>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            None => serializer.serialize_none(),
+            Some(Some(text)) => Repr::Text(text.clone()).serialize(serializer),
+            Some(None) => Repr::Cleared { cleared: true }.serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Option<String>>, D::Error> {
+        Ok(match Option::<Repr>::deserialize(deserializer)? {
+            None | Some(Repr::Cleared { cleared: false }) => None,
+            Some(Repr::Text(text)) => Some(Some(text)),
+            Some(Repr::Cleared { cleared: true }) => Some(None),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +347,17 @@ mod tests {
         });
     }
 
+    #[test]
+    fn event_serializes_round_trip_external_link() {
+        round_trip_event(EventPayload::ExternalLinkAdded {
+            index: 0,
+            link: ExternalLink::new("https://example.com/issues/1", "Issue 1").unwrap(),
+        });
+        round_trip_event(EventPayload::ExternalLinkRemoved {
+            link_id: Ulid::new(),
+        });
+    }
+
     #[test]
     fn event_serializes_round_trip_card_created() {
         let card = Card::new(
@@ -185,6 +376,7 @@ mod tests {
             body: Some(Some("New body content".to_string())),
             card_type: None,
             refs: Some(vec!["ref-1".to_string()]),
+            tags: Some(vec!["backend".to_string()]),
         });
     }
 
@@ -197,6 +389,37 @@ mod tests {
         });
     }
 
+    #[test]
+    fn event_serializes_round_trip_cards_reordered() {
+        round_trip_event(EventPayload::CardsReordered {
+            positions: vec![CardPosition {
+                card_id: Ulid::new(),
+                lane: "Plan".to_string(),
+                order: 2.5,
+            }],
+        });
+    }
+
+    #[test]
+    fn event_serializes_round_trip_spec_forked() {
+        let core = SpecCore::new(
+            "Source".to_string(),
+            "Parent spec".to_string(),
+            "Branch it".to_string(),
+        );
+        let card = Card::new(
+            "idea".to_string(),
+            "Carried over".to_string(),
+            "human".to_string(),
+        );
+        round_trip_event(EventPayload::SpecForked {
+            parent_spec_id: core.spec_id,
+            core,
+            cards: vec![card],
+            lanes: vec!["Spec".to_string(), "Ideas".to_string()],
+        });
+    }
+
     #[test]
     fn event_serializes_round_trip_card_deleted() {
         round_trip_event(EventPayload::CardDeleted {
@@ -252,6 +475,11 @@ mod tests {
                 card_id: Ulid::new(),
             }],
         });
+        round_trip_event(EventPayload::RedoApplied {
+            payloads: vec![EventPayload::CardDeleted {
+                card_id: Ulid::new(),
+            }],
+        });
     }
 
     #[test]
@@ -321,6 +549,22 @@ mod tests {
         assert!(s.contains("\"type\":\"ContextRemoved\""));
     }
 
+    #[test]
+    fn proposal_queued_round_trip() {
+        round_trip_event(EventPayload::ProposalQueued {
+            proposal: ProposedAction {
+                proposal_id: Ulid::new(),
+                command: crate::command::Command::DeleteCard {
+                    card_id: Ulid::new(),
+                    updated_by: "planner".to_string(),
+                },
+                proposed_by: "planner".to_string(),
+                reason: "destructive".to_string(),
+                proposed_at: Utc::now(),
+            },
+        });
+    }
+
     #[test]
     fn streaming_delta_round_trip() {
         round_trip_event(EventPayload::StreamingDelta {
@@ -337,6 +581,51 @@ mod tests {
         });
     }
 
+    #[test]
+    fn usage_recorded_round_trip() {
+        round_trip_event(EventPayload::UsageRecorded {
+            role: "planner".to_string(),
+            model: "claude-haiku-4-5".to_string(),
+            input_tokens: 12_000,
+            output_tokens: 800,
+        });
+    }
+
+    #[test]
+    fn agent_roster_set_round_trip() {
+        round_trip_event(EventPayload::AgentRosterSet {
+            roles: vec!["manager".to_string(), "critic".to_string()],
+        });
+    }
+
+    #[test]
+    fn cleared_card_body_survives_round_trip() {
+        let card_id = Ulid::new();
+        for body in [None, Some(None), Some(Some("text".to_string()))] {
+            let payload = EventPayload::CardUpdated {
+                card_id,
+                title: None,
+                body: body.clone(),
+                card_type: None,
+                refs: None,
+                tags: None,
+            };
+            let json = serde_json::to_string(&payload).unwrap();
+            let back: EventPayload = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, payload, "{}", json);
+        }
+
+        // Logs written before the cleared marker used null for "unchanged".
+        let legacy = format!(
+            r#"{{"type":"CardUpdated","card_id":"{}","title":"T","body":null,"card_type":null,"refs":null}}"#,
+            card_id
+        );
+        match serde_json::from_str::<EventPayload>(&legacy).unwrap() {
+            EventPayload::CardUpdated { body, .. } => assert_eq!(body, None),
+            other => panic!("wrong variant: {:?}", other),
+        }
+    }
+
     #[test]
     fn is_ephemeral_returns_true_for_streaming_events() {
         assert!(
@@ -9,6 +9,15 @@ use crate::state::SpecState;
 /// Maximum character length for synthesized prompts before truncation.
 const MAX_PROMPT_LEN: usize = 500;
 
+/// Most card titles listed per card type in a prompt; the rest are counted
+/// as "(+K more)".
+const MAX_PROMPT_ITEMS: usize = 6;
+
+/// Character budget for one card-title list in a prompt. Together with
+/// `MAX_PROMPT_ITEMS` this keeps lists short enough that `truncate_prompt`
+/// does not cut off a "(+K more)" note.
+const MAX_PROMPT_LIST_LEN: usize = 160;
+
 /// Export the spec state as a DOT graph conforming to the DOT Runner
 /// constrained runtime DSL.
 ///
@@ -69,7 +78,10 @@ pub fn export_dot(state: &SpecState) -> String {
 
     // Collect cards by type, excluding the Ideas lane (unrefined cards
     // should not feed into the pipeline — only Plan/Spec/other lanes).
-    let cards: Vec<&Card> = state.cards.values().filter(|c| c.lane != "Ideas").collect();
+    // Sorted by lane, then board order, so prompts do not depend on card
+    // IDs and regenerating after unrelated changes yields a minimal diff.
+    let mut cards: Vec<&Card> = state.cards.values().filter(|c| c.lane != "Ideas").collect();
+    cards.sort_by(|a, b| compare_cards(&state.lanes, a, b));
     let ideas: Vec<&str> = cards
         .iter()
         .filter(|c| c.card_type == "idea" || c.card_type == "inspiration" || c.card_type == "vibes")
@@ -260,6 +272,20 @@ pub fn export_dot(state: &SpecState) -> String {
     out
 }
 
+/// Deterministic card ordering for prompt aggregation: lanes in the spec's
+/// board order, then cards in lanes the spec no longer lists alphabetically
+/// by lane (as the board shows them); within a lane by `order`, with title
+/// and card type as tiebreakers.
+fn compare_cards(lanes: &[String], a: &Card, b: &Card) -> std::cmp::Ordering {
+    let lane_rank = |lane: &str| lanes.iter().position(|l| l == lane).unwrap_or(lanes.len());
+    lane_rank(&a.lane)
+        .cmp(&lane_rank(&b.lane))
+        .then_with(|| a.lane.cmp(&b.lane))
+        .then_with(|| a.order.total_cmp(&b.order))
+        .then_with(|| a.title.cmp(&b.title))
+        .then_with(|| a.card_type.cmp(&b.card_type))
+}
+
 /// Build the prompt for the "plan" phase.
 /// Aggregates ideas and constraints into a planning directive.
 fn build_plan_prompt(
@@ -270,11 +296,14 @@ fn build_plan_prompt(
 ) -> String {
     let mut parts = vec![format!("Plan the approach for: {}", goal)];
     if !ideas.is_empty() {
-        parts.push(format!("Key ideas: {}", ideas.join("; ")));
+        parts.push(format!("Key ideas: {}", list_items(ideas)));
+    }
+    let mut all_constraints = Vec::new();
+    if !constraints.is_empty() {
+        all_constraints.push(list_items(constraints));
     }
-    let mut all_constraints: Vec<&str> = constraints.to_vec();
     if !spec_constraints.is_empty() {
-        all_constraints.push(spec_constraints);
+        all_constraints.push(spec_constraints.to_string());
     }
     if !all_constraints.is_empty() {
         parts.push(format!("Constraints: {}", all_constraints.join("; ")));
@@ -292,10 +321,10 @@ fn build_setup_prompt(goal: &str) -> String {
 fn build_tdd_prompt(goal: &str, tasks: &[&str], plans: &[&str]) -> String {
     let mut parts = vec![format!("Write failing tests for: {}", goal)];
     if !tasks.is_empty() {
-        parts.push(format!("Cover: {}", tasks.join("; ")));
+        parts.push(format!("Cover: {}", list_items(tasks)));
     }
     if !plans.is_empty() {
-        parts.push(format!("Following: {}", plans.join("; ")));
+        parts.push(format!("Following: {}", list_items(plans)));
     }
     parts.push("Tests must fail before implementation begins.".to_string());
     truncate_prompt(&parts.join(". "))
@@ -306,10 +335,10 @@ fn build_tdd_prompt(goal: &str, tasks: &[&str], plans: &[&str]) -> String {
 fn build_implement_prompt(goal: &str, tasks: &[&str], plans: &[&str]) -> String {
     let mut parts = vec![format!("Implement: {}", goal)];
     if !tasks.is_empty() {
-        parts.push(format!("Deliver: {}", tasks.join("; ")));
+        parts.push(format!("Deliver: {}", list_items(tasks)));
     }
     if !plans.is_empty() {
-        parts.push(format!("Following: {}", plans.join("; ")));
+        parts.push(format!("Following: {}", list_items(plans)));
     }
     parts.push("Write only enough code to make the failing tests pass.".to_string());
     truncate_prompt(&parts.join(". "))
@@ -321,7 +350,7 @@ fn build_verify_prompt(goal: &str, decisions: &[&str], success_criteria: &str) -
     let mut parts = vec![format!("Verify: {}", goal)];
     parts.push("Run typecheck, lint, unit tests, and integration tests.".to_string());
     if !decisions.is_empty() {
-        parts.push(format!("Validate: {}", decisions.join("; ")));
+        parts.push(format!("Validate: {}", list_items(decisions)));
     }
     if !success_criteria.is_empty() {
         parts.push(format!("Success criteria: {}", success_criteria));
@@ -340,7 +369,7 @@ fn build_scenario_test_prompt(goal: &str, assumptions: &[&str], success_criteria
     )];
     parts.push("No mocks allowed. Exercise real systems end-to-end.".to_string());
     if !assumptions.is_empty() {
-        parts.push(format!("Validate assumptions: {}", assumptions.join("; ")));
+        parts.push(format!("Validate assumptions: {}", list_items(assumptions)));
     }
     if !success_criteria.is_empty() {
         parts.push(format!("Success criteria: {}", success_criteria));
@@ -354,7 +383,7 @@ fn build_scenario_test_prompt(goal: &str, assumptions: &[&str], success_criteria
 fn build_review_prompt(goal: &str, open_questions: &[&str]) -> String {
     let mut parts = vec![format!("Human review: {}", goal)];
     if !open_questions.is_empty() {
-        parts.push(format!("Open questions: {}", open_questions.join("; ")));
+        parts.push(format!("Open questions: {}", list_items(open_questions)));
     }
     parts.push("Approve?".to_string());
     truncate_prompt(&parts.join(". "))
@@ -365,7 +394,7 @@ fn build_review_prompt(goal: &str, open_questions: &[&str]) -> String {
 fn build_polish_prompt(risks: &[&str]) -> String {
     let mut parts = vec!["Apply fixes based on review feedback.".to_string()];
     if !risks.is_empty() {
-        parts.push(format!("Risks: {}", risks.join("; ")));
+        parts.push(format!("Risks: {}", list_items(risks)));
     }
     truncate_prompt(&parts.join(". "))
 }
@@ -375,6 +404,35 @@ fn build_release_prompt(goal: &str) -> String {
     truncate_prompt(&format!("Prepare release: {}", goal))
 }
 
+/// Join card titles for a prompt, in the order given. Stops after
+/// `MAX_PROMPT_ITEMS` titles or once `MAX_PROMPT_LIST_LEN` characters would
+/// be exceeded, and says how many were left out, so no card disappears from
+/// a prompt without a trace. A first title longer than the budget is
+/// shortened rather than dropped.
+fn list_items(items: &[&str]) -> String {
+    let mut listed: Vec<String> = Vec::new();
+    let mut len = 0;
+    for item in items.iter().take(MAX_PROMPT_ITEMS) {
+        let item_len = item.chars().count();
+        let sep_len = if listed.is_empty() { 0 } else { 2 };
+        if len + sep_len + item_len > MAX_PROMPT_LIST_LEN {
+            if listed.is_empty() {
+                let short: String = item.chars().take(MAX_PROMPT_LIST_LEN - 3).collect();
+                listed.push(format!("{}...", short));
+            }
+            break;
+        }
+        len += sep_len + item_len;
+        listed.push(item.to_string());
+    }
+    let mut out = listed.join("; ");
+    let remaining = items.len() - listed.len();
+    if remaining > 0 {
+        write!(out, " (+{} more)", remaining).unwrap();
+    }
+    out
+}
+
 /// Truncate a prompt string to at most `MAX_PROMPT_LEN` characters,
 /// using char-safe indexing.
 fn truncate_prompt(s: &str) -> String {
@@ -459,18 +517,32 @@ mod tests {
             notes: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         };
         SpecState {
             core: Some(core),
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         }
     }
 
@@ -489,6 +561,12 @@ mod tests {
             created_by: created_by.to_string(),
             updated_by: created_by.to_string(),
             source_attachment_id: None,
+            done: false,
+            attachments: Vec::new(),
+            due_date: None,
+            tags: Vec::new(),
+            review: None,
+            archived: false,
         }
     }
 
@@ -1050,18 +1128,32 @@ mod tests {
             notes: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         };
         let state = SpecState {
             core: Some(core),
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         };
         let dot = export_dot(&state);
 
@@ -1078,13 +1170,24 @@ mod tests {
             core: None,
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         };
         let dot = export_dot(&state);
 
@@ -1116,18 +1219,32 @@ mod tests {
             notes: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         };
         let state = SpecState {
             core: Some(core),
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         };
         let dot = export_dot(&state);
 
@@ -1207,6 +1324,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn many_cards_are_capped_with_a_remainder_count() {
+        let mut state = make_state_with_core();
+        for i in 0..50 {
+            let card = make_card(
+                "task",
+                &format!("Very Long Task Name Number {} With Extra Words", i),
+                "Spec",
+                i as f64,
+                "human",
+            );
+            state.cards.insert(card.card_id, card);
+        }
+
+        let dot = export_dot(&state);
+        let implement_line = dot
+            .lines()
+            .find(|l| l.contains("implement [shape=box"))
+            .expect("implement node not found");
+
+        let listed = implement_line.matches("Very Long Task Name Number").count();
+        assert!(
+            listed > 0 && listed <= MAX_PROMPT_ITEMS,
+            "{}",
+            implement_line
+        );
+        // Tasks are listed in board order, so the first ones make the cut.
+        assert!(implement_line.contains("Number 0 With Extra Words"));
+        assert!(
+            implement_line.contains(&format!("(+{} more)", 50 - listed)),
+            "{}",
+            implement_line
+        );
+        assert!(implement_line.contains("Write only enough code"));
+    }
+
+    #[test]
+    fn list_items_notes_the_remainder_and_shortens_oversized_titles() {
+        assert_eq!(list_items(&["a", "b"]), "a; b");
+        let many: Vec<String> = (0..10).map(|i| format!("t{}", i)).collect();
+        let many: Vec<&str> = many.iter().map(String::as_str).collect();
+        assert_eq!(
+            list_items(&many),
+            format!("t0; t1; t2; t3; t4; t5 (+{} more)", 10 - MAX_PROMPT_ITEMS)
+        );
+
+        let huge = "x".repeat(1_000);
+        let out = list_items(&[huge.as_str(), "next"]);
+        assert!(
+            out.starts_with("xxx") && out.ends_with("... (+1 more)"),
+            "{}",
+            out
+        );
+        assert!(out.chars().count() <= MAX_PROMPT_LIST_LEN + " (+1 more)".len());
+    }
+
     // -- Helper unit tests --
 
     #[test]
@@ -1328,6 +1501,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn card_insertion_order_does_not_change_output() {
+        let specs = [
+            ("task", "Write parser", "Plan", 2.0),
+            ("task", "Add CLI", "Plan", 1.0),
+            ("plan", "Ship v1", "Spec", 1.0),
+            ("risk", "Scope creep", "Backlog", 1.0),
+            ("decision", "Use SQLite", "Spec", 0.5),
+        ];
+
+        // Same logical cards, created (and so keyed by ULID) in opposite orders.
+        let mut forward = make_state_with_core();
+        for (ty, title, lane, order) in specs {
+            let card = make_card(ty, title, lane, order, "human");
+            forward.cards.insert(card.card_id, card);
+        }
+        let mut reverse = make_state_with_core();
+        for (ty, title, lane, order) in specs.iter().rev() {
+            let card = make_card(ty, title, lane, *order, "human");
+            reverse.cards.insert(card.card_id, card);
+        }
+
+        let a = export_dot(&forward);
+        let b = export_dot(&reverse);
+        assert_eq!(a, b);
+
+        // Within a lane, board order wins over creation order.
+        let cli = a.find("Add CLI").unwrap();
+        let parser = a.find("Write parser").unwrap();
+        assert!(cli < parser);
+    }
+
+    #[test]
+    fn cards_follow_the_spec_lane_order() {
+        let mut state = make_state_with_core();
+        state.lanes = ["Ideas", "Spec", "Plan", "Review"]
+            .map(String::from)
+            .to_vec();
+        for (title, lane) in [
+            ("Stray task", "Backlog"),
+            ("Review task", "Review"),
+            ("Plan task", "Plan"),
+            ("Spec task", "Spec"),
+        ] {
+            let card = make_card("task", title, lane, 1.0, "human");
+            state.cards.insert(card.card_id, card);
+        }
+
+        let dot = export_dot(&state);
+        let at = |title: &str| dot.find(title).unwrap();
+        assert!(at("Spec task") < at("Plan task"));
+        assert!(at("Plan task") < at("Review task"));
+        // Lanes the spec no longer lists come last.
+        assert!(at("Review task") < at("Stray task"));
+    }
+
     #[test]
     fn node_ids_are_all_snake_case() {
         let state = make_state_with_core();
@@ -0,0 +1,217 @@
+// ABOUTME: Exports a SpecState as a lossless JSON document and imports it back without an LLM.
+// ABOUTME: Cards are listed by lane, then order, then card_id so two exports of the same spec diff cleanly.
+
+use serde::ser::Error as SerError;
+use serde::{Deserialize, Serialize};
+
+use crate::card::Card;
+use crate::model::SpecCore;
+use crate::state::{
+    AgentModel, ChecklistItem, ContextAttachment, ProposedAction, SpecPhase, SpecState,
+};
+use crate::transcript::{TranscriptMessage, UserQuestion};
+
+/// Identifies a barnstormer JSON export.
+pub const JSON_EXPORT_FORMAT: &str = "barnstormer-spec";
+/// Bumped when the export layout changes incompatibly.
+pub const JSON_EXPORT_VERSION: u32 = 1;
+
+/// The exported document: everything in `SpecState` except the undo stack
+/// and event bookkeeping, which only mean something next to an event log.
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonExport {
+    format: String,
+    version: u32,
+    core: SpecCore,
+    phase: SpecPhase,
+    lanes: Vec<String>,
+    cards: Vec<Card>,
+    transcript: Vec<TranscriptMessage>,
+    #[serde(default)]
+    pending_questions: Vec<UserQuestion>,
+    #[serde(default)]
+    canvas_content: Option<String>,
+    #[serde(default)]
+    context_attachments: Vec<ContextAttachment>,
+    #[serde(default)]
+    pending_proposals: Vec<ProposedAction>,
+    #[serde(default)]
+    agent_model: Option<AgentModel>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    done_checklist: Vec<ChecklistItem>,
+}
+
+/// A spec's cards in export order: by lane (the spec's lanes in board order,
+/// then any other lane alphabetically), then by order, then by card_id.
+pub fn cards_in_export_order(state: &SpecState) -> Vec<&Card> {
+    let lane_rank = |lane: &str| {
+        state
+            .lanes
+            .iter()
+            .position(|l| l == lane)
+            .unwrap_or(state.lanes.len())
+    };
+    let mut cards: Vec<&Card> = state.cards.values().collect();
+    cards.sort_by(|a, b| {
+        lane_rank(&a.lane)
+            .cmp(&lane_rank(&b.lane))
+            .then_with(|| a.lane.cmp(&b.lane))
+            .then_with(|| {
+                a.order
+                    .partial_cmp(&b.order)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then(a.card_id.cmp(&b.card_id))
+    });
+    cards
+}
+
+/// Export the complete spec as pretty-printed JSON, card IDs and timestamps
+/// included. Cards are listed in [`cards_in_export_order`].
+pub fn export_json(state: &SpecState) -> Result<String, serde_json::Error> {
+    let core = state
+        .core
+        .clone()
+        .ok_or_else(|| serde_json::Error::custom("SpecState must have a core to export JSON"))?;
+
+    let cards: Vec<Card> = cards_in_export_order(state).into_iter().cloned().collect();
+
+    let export = JsonExport {
+        format: JSON_EXPORT_FORMAT.to_string(),
+        version: JSON_EXPORT_VERSION,
+        core,
+        phase: state.phase.clone(),
+        lanes: state.lanes.clone(),
+        cards,
+        transcript: state.transcript.clone(),
+        pending_questions: state.pending_questions.clone(),
+        canvas_content: state.canvas_content.clone(),
+        context_attachments: state.context_attachments.clone(),
+        pending_proposals: state.pending_proposals.clone(),
+        agent_model: state.agent_model.clone(),
+        archived: state.archived,
+        done_checklist: state.done_checklist.clone(),
+    };
+    serde_json::to_string_pretty(&export)
+}
+
+/// Rebuild a spec from `export_json` output. The result has an empty undo
+/// stack and `last_event_id` 0, like a freshly created spec.
+pub fn import_json(json: &str) -> Result<SpecState, serde_json::Error> {
+    let export: JsonExport = serde_json::from_str(json)?;
+    if export.format != JSON_EXPORT_FORMAT {
+        return Err(<serde_json::Error as serde::de::Error>::custom(format!(
+            "not a barnstormer spec export (format {:?})",
+            export.format
+        )));
+    }
+    if export.version > JSON_EXPORT_VERSION {
+        return Err(<serde_json::Error as serde::de::Error>::custom(format!(
+            "spec export version {} is newer than this build supports ({})",
+            export.version, JSON_EXPORT_VERSION
+        )));
+    }
+
+    Ok(SpecState {
+        core: Some(export.core),
+        cards: export.cards.into_iter().map(|c| (c.card_id, c)).collect(),
+        transcript: export.transcript,
+        pending_questions: export.pending_questions,
+        lanes: export.lanes,
+        phase: export.phase,
+        canvas_content: export.canvas_content,
+        context_attachments: export.context_attachments,
+        pending_proposals: export.pending_proposals,
+        agent_model: export.agent_model,
+        archived: export.archived,
+        done_checklist: export.done_checklist,
+        ..SpecState::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_state() -> SpecState {
+        let mut state = SpecState::new();
+        state.core = Some(SpecCore::new(
+            "JSON Spec".to_string(),
+            "Lossless".to_string(),
+            "Round-trip everything".to_string(),
+        ));
+        state.core.as_mut().unwrap().external_links.push(
+            crate::model::ExternalLink::new("https://example.com/issues/3", "Issue 3").unwrap(),
+        );
+        for (title, lane, order) in [("Second", "Plan", 2.0), ("First", "Ideas", 1.0)] {
+            let mut card = Card::new("idea".to_string(), title.to_string(), "human".to_string());
+            card.lane = lane.to_string();
+            card.order = order;
+            card.refs = vec!["elsewhere".to_string()];
+            state.cards.insert(card.card_id, card);
+        }
+        state.transcript.push(TranscriptMessage::new(
+            "human".to_string(),
+            "hello".to_string(),
+        ));
+        state
+    }
+
+    #[test]
+    fn export_json_round_trips_the_spec() {
+        let state = make_state();
+        let json = export_json(&state).unwrap();
+        let back = import_json(&json).unwrap();
+        assert_eq!(back, state);
+    }
+
+    #[test]
+    fn export_json_is_deterministic_and_orders_cards() {
+        let state = make_state();
+        let json = export_json(&state).unwrap();
+        assert_eq!(json, export_json(&state.clone()).unwrap());
+
+        let first = json.find("\"First\"").unwrap();
+        let second = json.find("\"Second\"").unwrap();
+        assert!(first < second, "cards should be sorted by order");
+        for card_id in state.cards.keys() {
+            assert!(json.contains(&card_id.to_string()));
+        }
+    }
+
+    #[test]
+    fn export_json_sorts_cards_by_lane_before_order() {
+        let mut state = make_state();
+        for (title, lane, order) in [("Parked", "Parking", 0.0), ("Specced", "Spec", 0.5)] {
+            let mut card = Card::new("idea".to_string(), title.to_string(), "human".to_string());
+            card.lane = lane.to_string();
+            card.order = order;
+            state.cards.insert(card.card_id, card);
+        }
+
+        let titles: Vec<&str> = cards_in_export_order(&state)
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["First", "Second", "Specced", "Parked"]);
+
+        let json = export_json(&state).unwrap();
+        let positions: Vec<usize> = titles
+            .iter()
+            .map(|t| json.find(&format!("\"{}\"", t)).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn export_json_requires_a_core() {
+        assert!(export_json(&SpecState::new()).is_err());
+    }
+
+    #[test]
+    fn import_json_rejects_other_documents() {
+        assert!(import_json(r#"{"format":"other","version":1}"#).is_err());
+    }
+}
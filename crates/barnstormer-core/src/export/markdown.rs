@@ -20,6 +20,15 @@ pub fn export_markdown(state: &SpecState) -> String {
         writeln!(out).unwrap();
         writeln!(out, "> {}", core.one_liner).unwrap();
         writeln!(out).unwrap();
+        if !core.external_links.is_empty() {
+            let links: Vec<String> = core
+                .external_links
+                .iter()
+                .map(|l| format!("[{}]({})", l.label, l.url))
+                .collect();
+            writeln!(out, "**Links:** {}", links.join(" · ")).unwrap();
+            writeln!(out).unwrap();
+        }
         writeln!(out, "## Goal").unwrap();
         writeln!(out).unwrap();
         writeln!(out, "{}", core.goal).unwrap();
@@ -58,6 +67,16 @@ pub fn export_markdown(state: &SpecState) -> String {
             writeln!(out).unwrap();
             writeln!(out, "{}", notes).unwrap();
         }
+
+        if !state.done_checklist.is_empty() {
+            writeln!(out).unwrap();
+            writeln!(out, "## Definition of Done").unwrap();
+            writeln!(out).unwrap();
+            for item in &state.done_checklist {
+                let mark = if item.checked { "x" } else { " " };
+                writeln!(out, "- [{}] {}", mark, item.text).unwrap();
+            }
+        }
     }
 
     // Group cards by lane
@@ -77,7 +96,11 @@ pub fn export_markdown(state: &SpecState) -> String {
             if let Some(cards) = cards_by_lane.get(lane.as_str()) {
                 for card in cards {
                     writeln!(out).unwrap();
-                    writeln!(out, "### {} ({})", card.title, card.card_type).unwrap();
+                    if card.done {
+                        writeln!(out, "### [x] {} ({})", card.title, card.card_type).unwrap();
+                    } else {
+                        writeln!(out, "### {} ({})", card.title, card.card_type).unwrap();
+                    }
 
                     if let Some(ref body) = card.body {
                         writeln!(out).unwrap();
@@ -89,6 +112,16 @@ pub fn export_markdown(state: &SpecState) -> String {
                         writeln!(out, "Refs: {}", card.refs.join(", ")).unwrap();
                     }
 
+                    if let Some(due) = card.due_date {
+                        writeln!(out).unwrap();
+                        writeln!(out, "Due: {}", due.format("%Y-%m-%d")).unwrap();
+                    }
+
+                    if !card.tags.is_empty() {
+                        writeln!(out).unwrap();
+                        writeln!(out, "Tags: {}", card.tags.join(", ")).unwrap();
+                    }
+
                     writeln!(
                         out,
                         "Created by: {} at {}",
@@ -176,18 +209,32 @@ mod tests {
             notes: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         };
         SpecState {
             core: Some(core),
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         }
     }
 
@@ -207,6 +254,12 @@ mod tests {
             created_by: created_by.to_string(),
             updated_by: created_by.to_string(),
             source_attachment_id: None,
+            done: false,
+            attachments: Vec::new(),
+            due_date: None,
+            tags: Vec::new(),
+            review: None,
+            archived: false,
         }
     }
 
@@ -221,6 +274,52 @@ mod tests {
         assert!(md.contains("Verify the markdown exporter"));
     }
 
+    #[test]
+    fn export_markdown_checks_off_done_cards() {
+        let mut state = make_state_with_core();
+        let mut done = make_card("task", "Finished", "Plan", 1.0, "human");
+        done.done = true;
+        let open = make_card("task", "Pending", "Plan", 2.0, "human");
+        state.cards.insert(done.card_id, done);
+        state.cards.insert(open.card_id, open);
+
+        let md = export_markdown(&state);
+
+        assert!(md.contains("### [x] Finished (task)"));
+        assert!(md.contains("### Pending (task)"));
+    }
+
+    #[test]
+    fn export_markdown_includes_due_dates() {
+        let mut state = make_state_with_core();
+        let mut card = make_card("task", "Launch", "Plan", 1.0, "human");
+        card.due_date = Some(
+            chrono::DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+        );
+        state.cards.insert(card.card_id, card);
+
+        let md = export_markdown(&state);
+
+        assert!(md.contains("Due: 2026-03-01"), "{}", md);
+    }
+
+    #[test]
+    fn export_markdown_includes_tags_when_present() {
+        let mut state = make_state_with_core();
+        let mut card = make_card("task", "Launch", "Plan", 1.0, "human");
+        card.tags = vec!["backend".to_string(), "ux".to_string()];
+        state.cards.insert(card.card_id, card);
+        let untagged = make_card("task", "Later", "Plan", 2.0, "human");
+        state.cards.insert(untagged.card_id, untagged);
+
+        let md = export_markdown(&state);
+
+        assert!(md.contains("Tags: backend, ux"), "{}", md);
+        assert_eq!(md.matches("Tags:").count(), 1);
+    }
+
     #[test]
     fn export_markdown_groups_cards_by_lane() {
         let mut state = make_state_with_core();
@@ -301,6 +400,42 @@ mod tests {
         assert!(md.contains("Remember to review"));
     }
 
+    #[test]
+    fn export_markdown_renders_done_checklist() {
+        let mut state = make_state_with_core();
+        for (text, checked) in [("Docs written", true), ("Load tested", false)] {
+            state.done_checklist.push(crate::state::ChecklistItem {
+                item_id: ulid::Ulid::new(),
+                text: text.to_string(),
+                checked,
+            });
+        }
+
+        let md = export_markdown(&state);
+
+        assert!(md.contains("## Definition of Done\n\n- [x] Docs written\n- [ ] Load tested\n"));
+    }
+
+    #[test]
+    fn export_markdown_lists_external_links_under_the_header() {
+        let mut state = make_state_with_core();
+        let links = &mut state.core.as_mut().unwrap().external_links;
+        for (url, label) in [
+            ("https://github.com/acme/app/issues/1", "Epic"),
+            ("https://jira.example.com/browse/APP-2", ""),
+        ] {
+            links.push(crate::model::ExternalLink::new(url, label).unwrap());
+        }
+
+        let md = export_markdown(&state);
+
+        assert!(md.contains(
+            "**Links:** [Epic](https://github.com/acme/app/issues/1) · \
+             [https://jira.example.com/browse/APP-2](https://jira.example.com/browse/APP-2)\n\n## Goal"
+        ));
+        assert!(!export_markdown(&make_state_with_core()).contains("**Links:**"));
+    }
+
     #[test]
     fn export_markdown_omits_empty_optional_fields() {
         let state = make_state_with_core();
@@ -312,6 +447,7 @@ mod tests {
         assert!(!md.contains("## Success Criteria"));
         assert!(!md.contains("## Risks"));
         assert!(!md.contains("## Notes"));
+        assert!(!md.contains("## Definition of Done"));
     }
 
     #[test]
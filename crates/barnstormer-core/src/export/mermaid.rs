@@ -0,0 +1,176 @@
+// ABOUTME: Exports a SpecState as a Mermaid flowchart for a quick visual of cards and lanes.
+// ABOUTME: Each lane is a subgraph of card nodes; edges follow each card's refs to other cards.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::card::Card;
+use crate::state::SpecState;
+
+use super::json::cards_in_export_order;
+
+/// Render a SpecState as a Mermaid `flowchart`.
+///
+/// Cards are grouped into one subgraph per lane, in board order, and every
+/// card appears as a node whether or not it has refs. A ref becomes an edge
+/// when it names another card by ID or by title; other refs (URLs, free
+/// text) are ignored. Node IDs are the card ULID behind a `card_` prefix, so
+/// they can't start with a digit or collide with Mermaid keywords such as
+/// `end`. Output is deterministic for a given state.
+pub fn export_mermaid(state: &SpecState) -> String {
+    let mut out = String::new();
+    writeln!(out, "flowchart TD").unwrap();
+
+    let cards = cards_in_export_order(state);
+
+    let mut lane_index = 0;
+    let mut i = 0;
+    while i < cards.len() {
+        let lane = &cards[i].lane;
+        let end = cards[i..]
+            .iter()
+            .position(|c| &c.lane != lane)
+            .map_or(cards.len(), |n| i + n);
+        writeln!(out, "    subgraph lane_{}[\"{}\"]", lane_index, label(lane)).unwrap();
+        for card in &cards[i..end] {
+            writeln!(out, "        {}[\"{}\"]", node_id(card), label(&card.title)).unwrap();
+        }
+        writeln!(out, "    end").unwrap();
+        lane_index += 1;
+        i = end;
+    }
+
+    let mut edges = BTreeSet::new();
+    for card in &cards {
+        for r in &card.refs {
+            let target = cards
+                .iter()
+                .find(|c| c.card_id.to_string() == *r)
+                .or_else(|| cards.iter().find(|c| c.title == *r));
+            if let Some(target) = target
+                && target.card_id != card.card_id
+                && edges.insert((card.card_id, target.card_id))
+            {
+                writeln!(out, "    {} --> {}", node_id(card), node_id(target)).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+/// Mermaid node ID for a card.
+fn node_id(card: &Card) -> String {
+    format!("card_{}", card.card_id)
+}
+
+/// Escape text for a quoted Mermaid label. Quotes and angle brackets become
+/// entity codes and newlines become spaces so a label stays on one line.
+fn label(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("#quot;"),
+            '<' => out.push_str("#lt;"),
+            '>' => out.push_str("#gt;"),
+            '\n' | '\r' => out.push(' '),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_card(title: &str, lane: &str, order: f64) -> Card {
+        let mut card = Card::new("idea".to_string(), title.to_string(), "human".to_string());
+        card.lane = lane.to_string();
+        card.order = order;
+        card
+    }
+
+    fn insert(state: &mut SpecState, card: Card) -> Card {
+        state.cards.insert(card.card_id, card.clone());
+        card
+    }
+
+    #[test]
+    fn empty_state_is_a_bare_flowchart() {
+        assert_eq!(export_mermaid(&SpecState::new()), "flowchart TD\n");
+    }
+
+    #[test]
+    fn cards_are_grouped_into_lane_subgraphs() {
+        let mut state = SpecState::new();
+        let idea = insert(&mut state, make_card("Idea A", "Ideas", 1.0));
+        let plan = insert(&mut state, make_card("Plan B", "Plan", 1.0));
+
+        let out = export_mermaid(&state);
+        let ideas = out.find("subgraph lane_0[\"Ideas\"]").unwrap();
+        let plan_lane = out.find("subgraph lane_1[\"Plan\"]").unwrap();
+        let idea_node = out
+            .find(&format!("card_{}[\"Idea A\"]", idea.card_id))
+            .unwrap();
+        let plan_node = out
+            .find(&format!("card_{}[\"Plan B\"]", plan.card_id))
+            .unwrap();
+        assert!(ideas < idea_node && idea_node < plan_lane && plan_lane < plan_node);
+        assert!(!out.contains("-->"), "cards without refs have no edges");
+    }
+
+    #[test]
+    fn refs_by_id_or_title_become_edges() {
+        let mut state = SpecState::new();
+        let target = insert(&mut state, make_card("Target", "Plan", 1.0));
+        let mut by_id = make_card("By ID", "Plan", 2.0);
+        by_id.refs = vec![target.card_id.to_string()];
+        let by_id = insert(&mut state, by_id);
+        let mut by_title = make_card("By title", "Spec", 1.0);
+        by_title.refs = vec![
+            "Target".to_string(),
+            "https://example.com".to_string(),
+            "By title".to_string(),
+        ];
+        let by_title = insert(&mut state, by_title);
+
+        let out = export_mermaid(&state);
+        assert!(out.contains(&format!(
+            "card_{} --> card_{}",
+            by_id.card_id, target.card_id
+        )));
+        assert!(out.contains(&format!(
+            "card_{} --> card_{}",
+            by_title.card_id, target.card_id
+        )));
+        assert_eq!(
+            out.matches("-->").count(),
+            2,
+            "unknown and self refs are skipped"
+        );
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        let mut state = SpecState::new();
+        insert(
+            &mut state,
+            make_card("Say \"hi\" <b>\nnow", "Lane \"X\"", 1.0),
+        );
+        let out = export_mermaid(&state);
+        assert!(out.contains("subgraph lane_0[\"Lane #quot;X#quot;\"]"));
+        assert!(out.contains("[\"Say #quot;hi#quot; #lt;b#gt; now\"]"));
+    }
+
+    #[test]
+    fn export_is_deterministic() {
+        let mut state = SpecState::new();
+        for (i, title) in ["One", "Two", "Three"].iter().enumerate() {
+            let mut card = make_card(title, "Ideas", i as f64);
+            card.refs = vec!["One".to_string()];
+            insert(&mut state, card);
+        }
+        assert_eq!(export_mermaid(&state), export_mermaid(&state.clone()));
+    }
+}
@@ -1,12 +1,18 @@
-// ABOUTME: Module root for spec state exporters (Markdown, YAML, DOT, Spec).
+// ABOUTME: Module root for spec state exporters (Markdown, YAML, DOT, Mermaid, JSON, Spec, build prompt).
 // ABOUTME: Re-exports all export functions for convenient access.
 
 pub mod dot;
+pub mod json;
 pub mod markdown;
+pub mod mermaid;
+pub mod prompt;
 pub mod spec;
 pub mod yaml;
 
 pub use dot::export_dot;
+pub use json::{cards_in_export_order, export_json, import_json};
 pub use markdown::export_markdown;
+pub use mermaid::export_mermaid;
+pub use prompt::export_build_prompt;
 pub use spec::export_spec;
 pub use yaml::export_yaml;
@@ -0,0 +1,289 @@
+// ABOUTME: Exports a SpecState as a single implementation brief for an external coding agent.
+// ABOUTME: Tasks are grouped under the plan card they reference; unanswered questions are listed last.
+
+use std::fmt::Write;
+
+use crate::card::Card;
+use crate::state::SpecState;
+use crate::transcript::UserQuestion;
+
+use super::json::cards_in_export_order;
+
+/// Render a spec as a plain-text prompt for a coding agent.
+///
+/// Unlike [`export_markdown`](super::export_markdown), this is written as an
+/// instruction: it opens with the task, then lists goal, constraints, success
+/// criteria, tasks grouped by plan, risks and open questions. A task belongs
+/// to a plan when one of its refs is the plan card's ID or title; tasks that
+/// reference no plan are listed under "Unplanned tasks". Done cards stay in,
+/// marked as such, so the agent doesn't redo them. The open questions
+/// section is always present so the agent knows when nothing is left to ask.
+pub fn export_build_prompt(state: &SpecState) -> String {
+    let mut out = String::new();
+    let cards = cards_in_export_order(state);
+    let of_type = |card_type: &str| -> Vec<&Card> {
+        cards
+            .iter()
+            .copied()
+            .filter(|c| c.card_type == card_type)
+            .collect()
+    };
+
+    let title = state
+        .core
+        .as_ref()
+        .map(|c| c.title.as_str())
+        .unwrap_or("Untitled spec");
+    writeln!(
+        out,
+        "You are implementing \"{}\". Build it to satisfy the specification below.",
+        title
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "Follow the constraints, work through the tasks plan by plan, and treat the success criteria as the definition of done. If an open question blocks you, state the assumption you made."
+    )
+    .unwrap();
+
+    if let Some(ref core) = state.core {
+        section(&mut out, "GOAL");
+        writeln!(out, "{}", core.goal).unwrap();
+        if !core.one_liner.is_empty() {
+            writeln!(out, "Summary: {}", core.one_liner).unwrap();
+        }
+
+        if let Some(ref description) = core.description {
+            section(&mut out, "CONTEXT");
+            writeln!(out, "{}", description).unwrap();
+        }
+    }
+
+    let constraint_cards = of_type("constraint");
+    let constraints = state.core.as_ref().and_then(|c| c.constraints.as_deref());
+    if constraints.is_some() || !constraint_cards.is_empty() {
+        section(&mut out, "CONSTRAINTS");
+        if let Some(constraints) = constraints {
+            writeln!(out, "{}", constraints).unwrap();
+        }
+        bullets(&mut out, &constraint_cards);
+    }
+
+    let success_criteria = state
+        .core
+        .as_ref()
+        .and_then(|c| c.success_criteria.as_deref());
+    if success_criteria.is_some() || !state.done_checklist.is_empty() {
+        section(&mut out, "SUCCESS CRITERIA");
+        if let Some(success_criteria) = success_criteria {
+            writeln!(out, "{}", success_criteria).unwrap();
+        }
+        for item in state.done_checklist.iter().filter(|i| !i.checked) {
+            writeln!(out, "- {}", item.text).unwrap();
+        }
+    }
+
+    let decisions = of_type("decision");
+    if !decisions.is_empty() {
+        section(&mut out, "DECISIONS ALREADY MADE");
+        bullets(&mut out, &decisions);
+    }
+
+    let plans = of_type("plan");
+    let tasks = of_type("task");
+    if !plans.is_empty() || !tasks.is_empty() {
+        section(&mut out, "TASKS");
+        let plan_of = |task: &Card| {
+            plans.iter().position(|p| {
+                task.refs
+                    .iter()
+                    .any(|r| r == &p.card_id.to_string() || r == &p.title)
+            })
+        };
+        for (i, plan) in plans.iter().enumerate() {
+            writeln!(out).unwrap();
+            writeln!(out, "Plan {}: {}", i + 1, plan.title).unwrap();
+            if let Some(ref body) = plan.body {
+                writeln!(out, "{}", body).unwrap();
+            }
+            let planned: Vec<&Card> = tasks
+                .iter()
+                .copied()
+                .filter(|t| plan_of(t) == Some(i))
+                .collect();
+            bullets(&mut out, &planned);
+        }
+        let unplanned: Vec<&Card> = tasks
+            .iter()
+            .copied()
+            .filter(|t| plan_of(t).is_none())
+            .collect();
+        if !unplanned.is_empty() {
+            writeln!(out).unwrap();
+            writeln!(out, "Unplanned tasks:").unwrap();
+            bullets(&mut out, &unplanned);
+        }
+    }
+
+    let risk_cards = of_type("risk");
+    let risks = state.core.as_ref().and_then(|c| c.risks.as_deref());
+    if risks.is_some() || !risk_cards.is_empty() {
+        section(&mut out, "RISKS");
+        if let Some(risks) = risks {
+            writeln!(out, "{}", risks).unwrap();
+        }
+        bullets(&mut out, &risk_cards);
+    }
+
+    section(&mut out, "OPEN QUESTIONS");
+    let mut questions: Vec<String> = of_type("open_question")
+        .iter()
+        .map(|c| bullet_text(c))
+        .collect();
+    questions.extend(state.pending_questions.iter().filter_map(|q| match q {
+        UserQuestion::Boolean { question, .. }
+        | UserQuestion::MultipleChoice { question, .. }
+        | UserQuestion::Freeform { question, .. } => Some(question.clone()),
+        // Review gates ask a human to approve an agent action, not about the spec.
+        UserQuestion::ReviewGate { .. } => None,
+    }));
+    if questions.is_empty() {
+        writeln!(out, "None.").unwrap();
+    }
+    for question in questions {
+        writeln!(out, "- {}", question).unwrap();
+    }
+
+    out
+}
+
+fn section(out: &mut String, heading: &str) {
+    writeln!(out).unwrap();
+    writeln!(out, "## {}", heading).unwrap();
+}
+
+fn bullets(out: &mut String, cards: &[&Card]) {
+    for card in cards {
+        writeln!(out, "- {}", bullet_text(card)).unwrap();
+    }
+}
+
+/// A card as one bullet: the title, followed by its body, due date and done
+/// marker when set.
+fn bullet_text(card: &Card) -> String {
+    let mut text = card.title.clone();
+    if let Some(ref body) = card.body {
+        text.push_str(": ");
+        text.push_str(&body.replace('\n', " "));
+    }
+    if let Some(due) = card.due_date {
+        write!(text, " (due {})", due.format("%Y-%m-%d")).unwrap();
+    }
+    if card.done {
+        text.push_str(" [already done]");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SpecCore;
+    use ulid::Ulid;
+
+    fn make_card(card_type: &str, title: &str, order: f64) -> Card {
+        let mut card = Card::new(
+            card_type.to_string(),
+            title.to_string(),
+            "human".to_string(),
+        );
+        card.lane = "Plan".to_string();
+        card.order = order;
+        card
+    }
+
+    fn make_state() -> SpecState {
+        let mut state = SpecState::new();
+        let mut core = SpecCore::new(
+            "Payments".to_string(),
+            "Take card payments".to_string(),
+            "Accept Visa and Mastercard at checkout".to_string(),
+        );
+        core.constraints = Some("No card numbers on our servers".to_string());
+        core.success_criteria = Some("A test charge settles".to_string());
+        state.core = Some(core);
+
+        let plan = make_card("plan", "Gateway integration", 1.0);
+        let mut wired = make_card("task", "Wire up the gateway SDK", 2.0);
+        wired.refs = vec![plan.card_id.to_string()];
+        let mut by_title = make_card("task", "Handle webhooks", 3.0);
+        by_title.refs = vec!["Gateway integration".to_string()];
+        let loose = make_card("task", "Write the runbook", 4.0);
+        let question = make_card("open_question", "Which currencies?", 5.0);
+        for card in [plan, wired, by_title, loose, question] {
+            state.cards.insert(card.card_id, card);
+        }
+        state.pending_questions.push(UserQuestion::Freeform {
+            question_id: Ulid::new(),
+            question: "Do we need refunds in v1?".to_string(),
+            placeholder: None,
+            validation_hint: None,
+        });
+        state
+    }
+
+    #[test]
+    fn prompt_includes_goal_tasks_and_open_questions() {
+        let prompt = export_build_prompt(&make_state());
+        assert!(prompt.contains("Accept Visa and Mastercard at checkout"));
+        assert!(prompt.contains("No card numbers on our servers"));
+        assert!(prompt.contains("A test charge settles"));
+        for title in [
+            "Wire up the gateway SDK",
+            "Handle webhooks",
+            "Write the runbook",
+        ] {
+            assert!(prompt.contains(title), "missing task {title}");
+        }
+        assert!(prompt.contains("## OPEN QUESTIONS"));
+        assert!(prompt.contains("- Which currencies?"));
+        assert!(prompt.contains("- Do we need refunds in v1?"));
+    }
+
+    #[test]
+    fn tasks_are_grouped_under_the_plan_they_reference() {
+        let prompt = export_build_prompt(&make_state());
+        let plan = prompt.find("Plan 1: Gateway integration").unwrap();
+        let unplanned = prompt.find("Unplanned tasks:").unwrap();
+        let wired = prompt.find("Wire up the gateway SDK").unwrap();
+        let webhooks = prompt.find("Handle webhooks").unwrap();
+        let runbook = prompt.find("Write the runbook").unwrap();
+        assert!(plan < wired && wired < webhooks && webhooks < unplanned);
+        assert!(unplanned < runbook);
+    }
+
+    #[test]
+    fn open_questions_section_is_present_when_empty() {
+        let mut state = SpecState::new();
+        state.core = Some(SpecCore::new(
+            "Empty".to_string(),
+            "Nothing yet".to_string(),
+            "Decide later".to_string(),
+        ));
+        let prompt = export_build_prompt(&state);
+        assert!(prompt.contains("## OPEN QUESTIONS\nNone."));
+        assert!(!prompt.contains("## TASKS"));
+    }
+
+    #[test]
+    fn done_tasks_are_marked() {
+        let mut state = make_state();
+        for card in state.cards.values_mut() {
+            if card.title == "Write the runbook" {
+                card.done = true;
+            }
+        }
+        let prompt = export_build_prompt(&state);
+        assert!(prompt.contains("- Write the runbook [already done]"));
+    }
+}
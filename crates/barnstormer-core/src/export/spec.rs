@@ -100,6 +100,21 @@ pub fn export_spec(state: &SpecState) -> String {
                 writeln!(out).unwrap();
                 writeln!(out, "{}", body).unwrap();
             }
+
+            if let Some(due) = card.due_date {
+                writeln!(out).unwrap();
+                writeln!(out, "Due: {}", due.format("%Y-%m-%d")).unwrap();
+            }
+        }
+    }
+
+    if !state.done_checklist.is_empty() {
+        writeln!(out).unwrap();
+        writeln!(out, "## Definition of Done").unwrap();
+        writeln!(out).unwrap();
+        for item in &state.done_checklist {
+            let mark = if item.checked { "x" } else { " " };
+            writeln!(out, "- [{}] {}", mark, item.text).unwrap();
         }
     }
 
@@ -139,18 +154,32 @@ mod tests {
             notes: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         };
         SpecState {
             core: Some(core),
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         }
     }
 
@@ -170,6 +199,12 @@ mod tests {
             created_by: "test".to_string(),
             updated_by: "test".to_string(),
             source_attachment_id: None,
+            done: false,
+            attachments: Vec::new(),
+            due_date: None,
+            tags: Vec::new(),
+            review: None,
+            archived: false,
         }
     }
 
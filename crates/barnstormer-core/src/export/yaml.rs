@@ -22,6 +22,33 @@ struct YamlCard {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     refs: Vec<String>,
     created_by: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<YamlAttachment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due_date: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+/// A serializable YAML representation of an external card attachment.
+#[derive(Debug, Serialize)]
+struct YamlAttachment {
+    label: String,
+    target: String,
+}
+
+/// A serializable YAML representation of a link to an external tracker item.
+#[derive(Debug, Serialize)]
+struct YamlExternalLink {
+    label: String,
+    url: String,
+}
+
+/// A serializable YAML representation of a definition-of-done item.
+#[derive(Debug, Serialize)]
+struct YamlChecklistItem {
+    text: String,
+    checked: bool,
 }
 
 /// A serializable YAML representation of a lane containing cards.
@@ -48,6 +75,10 @@ struct YamlSpec {
     risks: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     notes: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_links: Vec<YamlExternalLink>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    done_checklist: Vec<YamlChecklistItem>,
     lanes: Vec<YamlLane>,
 }
 
@@ -81,6 +112,16 @@ pub fn export_yaml(state: &SpecState) -> Result<String, serde_yaml::Error> {
                             order: card.order,
                             refs: card.refs.clone(),
                             created_by: card.created_by.clone(),
+                            attachments: card
+                                .attachments
+                                .iter()
+                                .map(|a| YamlAttachment {
+                                    label: a.label.clone(),
+                                    target: a.target.clone(),
+                                })
+                                .collect(),
+                            due_date: card.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+                            tags: card.tags.clone(),
                         })
                         .collect()
                 })
@@ -103,6 +144,22 @@ pub fn export_yaml(state: &SpecState) -> Result<String, serde_yaml::Error> {
         success_criteria: core.success_criteria.clone(),
         risks: core.risks.clone(),
         notes: core.notes.clone(),
+        external_links: core
+            .external_links
+            .iter()
+            .map(|l| YamlExternalLink {
+                label: l.label.clone(),
+                url: l.url.clone(),
+            })
+            .collect(),
+        done_checklist: state
+            .done_checklist
+            .iter()
+            .map(|item| YamlChecklistItem {
+                text: item.text.clone(),
+                checked: item.checked,
+            })
+            .collect(),
         lanes: yaml_lanes,
     };
 
@@ -177,18 +234,32 @@ mod tests {
             notes: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         };
         SpecState {
             core: Some(core),
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         }
     }
 
@@ -207,6 +278,12 @@ mod tests {
             created_by: created_by.to_string(),
             updated_by: created_by.to_string(),
             source_attachment_id: None,
+            done: false,
+            attachments: Vec::new(),
+            due_date: None,
+            tags: Vec::new(),
+            review: None,
+            archived: false,
         }
     }
 
@@ -357,6 +434,52 @@ mod tests {
         assert_eq!(total_cards, 3);
     }
 
+    #[test]
+    fn export_yaml_includes_card_attachments() {
+        let mut state = make_state_with_core();
+        let mut card = make_card("task", "Linked", "Spec", 1.0, "human");
+        card.attachments
+            .push(crate::card::CardAttachment::new("https://example.com/rfc", "RFC").unwrap());
+        state.cards.insert(card.card_id, card);
+
+        let yaml_str = export_yaml(&state).expect("export should succeed");
+        assert!(yaml_str.contains("attachments:"));
+        assert!(yaml_str.contains("label: RFC"));
+        assert!(yaml_str.contains("target: https://example.com/rfc"));
+    }
+
+    #[test]
+    fn export_yaml_includes_card_due_date() {
+        let mut state = make_state_with_core();
+        let mut card = make_card("task", "Launch", "Plan", 1.0, "human");
+        card.due_date = Some(
+            chrono::DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+        );
+        state.cards.insert(card.card_id, card);
+
+        let yaml_str = export_yaml(&state).expect("export should succeed");
+        assert!(yaml_str.contains("due_date:"), "{}", yaml_str);
+        assert!(yaml_str.contains("2026-03-01"));
+    }
+
+    #[test]
+    fn export_yaml_includes_card_tags_when_present() {
+        let mut state = make_state_with_core();
+        let untagged = make_card("task", "Untagged", "Plan", 1.0, "human");
+        state.cards.insert(untagged.card_id, untagged);
+        assert!(!export_yaml(&state).unwrap().contains("tags:"));
+
+        let mut card = make_card("task", "Launch", "Plan", 2.0, "human");
+        card.tags = vec!["backend".to_string(), "ux".to_string()];
+        state.cards.insert(card.card_id, card);
+
+        let yaml_str = export_yaml(&state).expect("export should succeed");
+        assert!(yaml_str.contains("tags:"), "{}", yaml_str);
+        assert!(yaml_str.contains("- backend"), "{}", yaml_str);
+    }
+
     #[test]
     fn export_yaml_omits_optional_fields_when_none() {
         let state = make_state_with_core();
@@ -396,4 +519,47 @@ mod tests {
         assert!(yaml_str.contains("constraints:"));
         assert!(yaml_str.contains("Must be fast"));
     }
+
+    #[test]
+    fn export_yaml_includes_done_checklist() {
+        let mut state = make_state_with_core();
+        state.done_checklist.push(crate::state::ChecklistItem {
+            item_id: ulid::Ulid::new(),
+            text: "Docs written".to_string(),
+            checked: true,
+        });
+
+        let yaml_str = export_yaml(&state).expect("export should succeed");
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml_str).unwrap();
+
+        assert_eq!(parsed["done_checklist"][0]["text"], "Docs written");
+        assert_eq!(parsed["done_checklist"][0]["checked"], true);
+        assert!(
+            !export_yaml(&make_state_with_core())
+                .unwrap()
+                .contains("done_checklist")
+        );
+    }
+
+    #[test]
+    fn export_yaml_includes_external_links() {
+        let mut state = make_state_with_core();
+        state.core.as_mut().unwrap().external_links.push(
+            crate::model::ExternalLink::new("https://example.com/issues/7", "Issue 7").unwrap(),
+        );
+
+        let yaml_str = export_yaml(&state).expect("export should succeed");
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml_str).unwrap();
+
+        assert_eq!(parsed["external_links"][0]["label"], "Issue 7");
+        assert_eq!(
+            parsed["external_links"][0]["url"],
+            "https://example.com/issues/7"
+        );
+        assert!(
+            !export_yaml(&make_state_with_core())
+                .unwrap()
+                .contains("external_links")
+        );
+    }
 }
@@ -6,14 +6,20 @@ pub mod card;
 pub mod command;
 pub mod event;
 pub mod export;
+pub mod lint;
 pub mod model;
+pub mod runner_outcome;
 pub mod state;
+pub mod testing;
 pub mod transcript;
 
 pub use actor::{ActorError, SpecActorHandle, spawn};
-pub use card::Card;
+pub use card::{
+    Card, CardAttachment, CardPosition, CardReview, CardReviewResolution, normalize_card_type,
+    normalize_tags,
+};
 pub use command::Command;
 pub use event::{Event, EventPayload};
-pub use model::SpecCore;
-pub use state::{SpecPhase, SpecState, UndoEntry};
+pub use model::{ExternalLink, SpecCore};
+pub use state::{AgentModel, ChecklistItem, SpecPhase, SpecState, TokenStats, UndoEntry};
 pub use transcript::{MessageKind, TranscriptMessage, UserQuestion};
@@ -0,0 +1,226 @@
+// ABOUTME: Deterministic "spec health" checks over a SpecState (no LLM involved).
+// ABOUTME: Flags empty card bodies, stale open questions, unmitigated risks, dangling refs, and an empty goal.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use ulid::Ulid;
+
+use crate::state::SpecState;
+
+/// Open-question cards older than this many days are flagged as stale.
+pub const OPEN_QUESTION_MAX_AGE_DAYS: i64 = 7;
+
+/// Which check produced a finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    EmptyGoal,
+    EmptyCardBody,
+    StaleOpenQuestion,
+    UnmitigatedRisk,
+    DanglingRef,
+}
+
+impl LintRule {
+    /// Stable snake_case identifier, matching the serialized form.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LintRule::EmptyGoal => "empty_goal",
+            LintRule::EmptyCardBody => "empty_card_body",
+            LintRule::StaleOpenQuestion => "stale_open_question",
+            LintRule::UnmitigatedRisk => "unmitigated_risk",
+            LintRule::DanglingRef => "dangling_ref",
+        }
+    }
+
+    /// Short human-readable label for the rule.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LintRule::EmptyGoal => "Empty goal",
+            LintRule::EmptyCardBody => "Empty card body",
+            LintRule::StaleOpenQuestion => "Stale open question",
+            LintRule::UnmitigatedRisk => "Risk without mitigation",
+            LintRule::DanglingRef => "Dangling reference",
+        }
+    }
+}
+
+/// One problem found in a spec. `card_id` is set when the problem belongs
+/// to a specific card.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub card_id: Option<Ulid>,
+    pub message: String,
+}
+
+/// Run every check against `state`. `now` anchors the open-question age
+/// check so results are reproducible. Findings are ordered by rule, then by
+/// card ID.
+pub fn lint(state: &SpecState, now: DateTime<Utc>) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+
+    if let Some(core) = &state.core
+        && core.goal.trim().is_empty()
+    {
+        findings.push(LintFinding {
+            rule: LintRule::EmptyGoal,
+            card_id: None,
+            message: "The spec has no goal.".to_string(),
+        });
+    }
+
+    for card in state.cards.values() {
+        if card.body.as_deref().is_none_or(|b| b.trim().is_empty()) {
+            findings.push(LintFinding {
+                rule: LintRule::EmptyCardBody,
+                card_id: Some(card.card_id),
+                message: format!("{} \"{}\" has no body.", card.card_type, card.title),
+            });
+        }
+    }
+
+    let max_age = Duration::days(OPEN_QUESTION_MAX_AGE_DAYS);
+    for card in state.cards.values() {
+        if card.card_type == "open_question" && !card.done && now - card.created_at > max_age {
+            findings.push(LintFinding {
+                rule: LintRule::StaleOpenQuestion,
+                card_id: Some(card.card_id),
+                message: format!(
+                    "Open question \"{}\" has been unanswered for {} days.",
+                    card.title,
+                    (now - card.created_at).num_days()
+                ),
+            });
+        }
+    }
+
+    // A risk counts as mitigated when its body talks about mitigation or
+    // another card points at it through `refs`.
+    for card in state.cards.values() {
+        if card.card_type != "risk" {
+            continue;
+        }
+        let id = card.card_id.to_string();
+        let mentions_mitigation = card
+            .body
+            .as_deref()
+            .is_some_and(|b| b.to_lowercase().contains("mitigat"));
+        let referenced = state
+            .cards
+            .values()
+            .any(|other| other.card_id != card.card_id && other.refs.contains(&id));
+        if !mentions_mitigation && !referenced {
+            findings.push(LintFinding {
+                rule: LintRule::UnmitigatedRisk,
+                card_id: Some(card.card_id),
+                message: format!("Risk \"{}\" has no mitigation.", card.title),
+            });
+        }
+    }
+
+    // Only refs shaped like card IDs are checked; URLs and free-form
+    // identifiers are left alone.
+    for card in state.cards.values() {
+        for r in &card.refs {
+            if let Ok(target) = r.parse::<Ulid>()
+                && !state.cards.contains_key(&target)
+            {
+                findings.push(LintFinding {
+                    rule: LintRule::DanglingRef,
+                    card_id: Some(card.card_id),
+                    message: format!(
+                        "\"{}\" refers to card {}, which does not exist.",
+                        card.title, target
+                    ),
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Card;
+    use crate::model::SpecCore;
+
+    fn spec_state() -> SpecState {
+        let mut state = SpecState::new();
+        state.core = Some(SpecCore::new(
+            "Spec".to_string(),
+            "One".to_string(),
+            "Ship it".to_string(),
+        ));
+        state
+    }
+
+    fn add_card(state: &mut SpecState, card_type: &str, title: &str, body: Option<&str>) -> Ulid {
+        let mut card = Card::new(
+            card_type.to_string(),
+            title.to_string(),
+            "human".to_string(),
+        );
+        card.body = body.map(str::to_string);
+        let id = card.card_id;
+        state.cards.insert(id, card);
+        id
+    }
+
+    #[test]
+    fn clean_spec_has_no_findings() {
+        let mut state = spec_state();
+        add_card(&mut state, "task", "Write docs", Some("Cover the API"));
+        assert!(lint(&state, Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn empty_task_body_and_dangling_ref_are_the_only_findings() {
+        let mut state = spec_state();
+        let empty = add_card(&mut state, "task", "Do the thing", None);
+        let linked = add_card(&mut state, "idea", "Linked", Some("See the other card"));
+        state
+            .cards
+            .get_mut(&linked)
+            .unwrap()
+            .refs
+            .push(Ulid::new().to_string());
+
+        let findings = lint(&state, Utc::now());
+        assert_eq!(findings.len(), 2, "{:?}", findings);
+        assert_eq!(findings[0].rule, LintRule::EmptyCardBody);
+        assert_eq!(findings[0].card_id, Some(empty));
+        assert_eq!(findings[1].rule, LintRule::DanglingRef);
+        assert_eq!(findings[1].card_id, Some(linked));
+    }
+
+    #[test]
+    fn flags_empty_goal_stale_questions_and_unmitigated_risks() {
+        let mut state = spec_state();
+        state.core.as_mut().unwrap().goal = "  ".to_string();
+        let question = add_card(&mut state, "open_question", "Which DB?", Some("Postgres?"));
+        state.cards.get_mut(&question).unwrap().created_at = Utc::now() - Duration::days(10);
+        add_card(&mut state, "risk", "Vendor lock-in", Some("Hard to leave"));
+        add_card(&mut state, "risk", "Outage", Some("Mitigation: failover"));
+        let covered = add_card(&mut state, "risk", "Cost", Some("Could spike"));
+        let plan = add_card(&mut state, "task", "Budget alerts", Some("Alert at 80%"));
+        state
+            .cards
+            .get_mut(&plan)
+            .unwrap()
+            .refs
+            .push(covered.to_string());
+
+        let rules: Vec<LintRule> = lint(&state, Utc::now()).iter().map(|f| f.rule).collect();
+        assert_eq!(
+            rules,
+            vec![
+                LintRule::EmptyGoal,
+                LintRule::StaleOpenQuestion,
+                LintRule::UnmitigatedRisk
+            ]
+        );
+    }
+}
@@ -1,12 +1,12 @@
 // ABOUTME: Defines the SpecCore struct representing a specification's core metadata.
-// ABOUTME: Contains required fields (title, one_liner, goal) and optional detail fields.
+// ABOUTME: Contains required fields (title, one_liner, goal), optional detail fields, and external links.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
 /// The core specification data, holding all metadata about a single spec.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpecCore {
     pub spec_id: Ulid,
     pub title: String,
@@ -19,6 +19,66 @@ pub struct SpecCore {
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// The spec this one was branched from, if any. Absent on specs created
+    /// from scratch and on specs persisted before lineage tracking existed.
+    #[serde(default)]
+    pub parent_spec_id: Option<Ulid>,
+    /// Specs whose cards and transcript were merged into this one, oldest
+    /// merge first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_from: Vec<Ulid>,
+    /// Links to where the spec's work is tracked elsewhere (issues, tickets),
+    /// in the order they were added.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_links: Vec<ExternalLink>,
+}
+
+/// A labelled http(s) link from the spec to an external tracker item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalLink {
+    pub link_id: Ulid,
+    pub label: String,
+    pub url: String,
+}
+
+impl ExternalLink {
+    /// Build a link after trimming and validating `url`. A blank label falls
+    /// back to the URL itself.
+    pub fn new(url: &str, label: &str) -> Result<Self, String> {
+        let url = url.trim();
+        validate_link_url(url)?;
+        let label = label.trim();
+        Ok(Self {
+            link_id: Ulid::new(),
+            url: url.to_string(),
+            label: if label.is_empty() { url } else { label }.to_string(),
+        })
+    }
+}
+
+/// An external link must be an absolute http(s) URL with a host and no
+/// whitespace or control characters.
+fn validate_link_url(url: &str) -> Result<(), String> {
+    if url.is_empty() {
+        return Err("link URL must not be empty".to_string());
+    }
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err("link URL must not contain whitespace".to_string());
+    }
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err(format!(
+            "link URL must start with http:// or https://: {}",
+            url
+        ));
+    };
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return Err(format!("unsupported URL scheme: {}", scheme));
+    }
+    if rest.is_empty() || rest.starts_with('/') {
+        return Err("URL is missing a host".to_string());
+    }
+    Ok(())
 }
 
 impl SpecCore {
@@ -38,8 +98,30 @@ impl SpecCore {
             notes: None,
             created_at: now,
             updated_at: now,
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         }
     }
+
+    /// Fraction (0.0..=1.0) of the narrative fields that hold non-blank text.
+    /// Counts the three required fields plus the five optional detail fields,
+    /// so a freshly created spec with only a title, one-liner, and goal scores 3/8.
+    pub fn completeness(&self) -> f64 {
+        let filled = |v: Option<&str>| v.is_some_and(|s| !s.trim().is_empty());
+        let fields = [
+            Some(self.title.as_str()),
+            Some(self.one_liner.as_str()),
+            Some(self.goal.as_str()),
+            self.description.as_deref(),
+            self.constraints.as_deref(),
+            self.success_criteria.as_deref(),
+            self.risks.as_deref(),
+            self.notes.as_deref(),
+        ];
+        let count = fields.iter().filter(|v| filled(**v)).count();
+        count as f64 / fields.len() as f64
+    }
 }
 
 #[cfg(test)]
@@ -62,6 +144,7 @@ mod tests {
         assert!(spec.success_criteria.is_none());
         assert!(spec.risks.is_none());
         assert!(spec.notes.is_none());
+        assert!(spec.parent_spec_id.is_none());
         assert!(spec.created_at <= Utc::now());
         assert_eq!(spec.created_at, spec.updated_at);
     }
@@ -82,4 +165,39 @@ mod tests {
         // Each call to new() must produce a distinct ULID
         assert_ne!(spec_a.spec_id, spec_b.spec_id);
     }
+
+    #[test]
+    fn external_link_requires_an_http_url_with_a_host() {
+        let link = ExternalLink::new(" https://example.com/issues/4 ", "").unwrap();
+        assert_eq!(link.url, "https://example.com/issues/4");
+        assert_eq!(link.label, link.url);
+
+        for bad in [
+            "",
+            "example.com/issues/4",
+            "ftp://example.com/file",
+            "javascript:alert(1)",
+            "https://",
+            "https:///path",
+            "https://example.com/a b",
+        ] {
+            assert!(
+                ExternalLink::new(bad, "x").is_err(),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn completeness_counts_non_blank_fields() {
+        let mut spec = SpecCore::new("T".to_string(), "O".to_string(), "G".to_string());
+        assert!((spec.completeness() - 3.0 / 8.0).abs() < f64::EPSILON);
+
+        spec.description = Some("Details".to_string());
+        spec.risks = Some("   ".to_string());
+        assert!((spec.completeness() - 4.0 / 8.0).abs() < f64::EPSILON);
+
+        spec.goal = String::new();
+        assert!((spec.completeness() - 3.0 / 8.0).abs() < f64::EPSILON);
+    }
 }
@@ -0,0 +1,209 @@
+// ABOUTME: Parses a DOT runner result file (one `phase: outcome` line per phase) into phase outcomes.
+// ABOUTME: Turns those outcomes into commands that narrate the run and file cards for failures and releases.
+
+use crate::command::Command;
+
+/// Author recorded on cards and transcript messages written from a runner result.
+pub const RUNNER_SENDER: &str = "runner";
+
+/// How a single pipeline phase ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl PhaseStatus {
+    fn parse(word: &str) -> Option<Self> {
+        match word.to_ascii_lowercase().as_str() {
+            "pass" | "passed" | "ok" | "success" | "succeeded" | "done" => Some(Self::Passed),
+            "fail" | "failed" | "error" | "errored" => Some(Self::Failed),
+            "skip" | "skipped" => Some(Self::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of one phase from the exported DOT pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseOutcome {
+    pub phase: String,
+    pub status: PhaseStatus,
+    pub detail: Option<String>,
+}
+
+/// Parse a runner result file. Each non-blank line that is not a `#`
+/// comment reads `phase: outcome [- detail]`, e.g.
+/// `verify: failed - 3 integration tests failed`.
+pub fn parse_runner_outcome(text: &str) -> Result<Vec<PhaseOutcome>, String> {
+    let mut outcomes = Vec::new();
+    for (index, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_no = index + 1;
+        let (phase, rest) = line
+            .split_once(':')
+            .ok_or_else(|| format!("line {}: expected `phase: outcome`", line_no))?;
+        let phase = phase.trim();
+        if phase.is_empty() || phase.contains(char::is_whitespace) {
+            return Err(format!("line {}: invalid phase name {:?}", line_no, phase));
+        }
+        let rest = rest.trim();
+        let (word, detail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let status = PhaseStatus::parse(word)
+            .ok_or_else(|| format!("line {}: unknown outcome {:?}", line_no, word))?;
+        let detail = detail.trim().trim_start_matches('-').trim();
+        outcomes.push(PhaseOutcome {
+            phase: phase.to_string(),
+            status,
+            detail: (!detail.is_empty()).then(|| detail.to_string()),
+        });
+    }
+    if outcomes.is_empty() {
+        return Err("runner result has no phase outcomes".to_string());
+    }
+    Ok(outcomes)
+}
+
+/// Commands that record a run in a spec: a transcript summary, one risk card
+/// per failed phase, and a decision card when the release phase passed.
+pub fn outcome_commands(outcomes: &[PhaseOutcome]) -> Vec<Command> {
+    let count = |status| outcomes.iter().filter(|o| o.status == status).count();
+    let failed: Vec<&PhaseOutcome> = outcomes
+        .iter()
+        .filter(|o| o.status == PhaseStatus::Failed)
+        .collect();
+
+    let mut summary = format!(
+        "DOT runner finished: {} passed, {} failed, {} skipped.",
+        count(PhaseStatus::Passed),
+        failed.len(),
+        count(PhaseStatus::Skipped)
+    );
+    if !failed.is_empty() {
+        let names: Vec<&str> = failed.iter().map(|o| o.phase.as_str()).collect();
+        summary.push_str(&format!(" Failed phases: {}.", names.join(", ")));
+    }
+
+    let mut commands = vec![Command::AppendTranscript {
+        sender: RUNNER_SENDER.to_string(),
+        content: summary,
+        display_name: None,
+    }];
+    for outcome in &failed {
+        commands.push(Command::CreateCard {
+            card_type: "risk".to_string(),
+            title: format!("Runner phase `{}` failed", outcome.phase),
+            body: Some(
+                outcome.detail.clone().unwrap_or_else(|| {
+                    "The DOT runner reported a failure in this phase.".to_string()
+                }),
+            ),
+            lane: None,
+            created_by: RUNNER_SENDER.to_string(),
+            source_attachment_id: None,
+        });
+    }
+    if let Some(release) = outcomes
+        .iter()
+        .find(|o| o.phase == "release" && o.status == PhaseStatus::Passed)
+    {
+        commands.push(Command::CreateCard {
+            card_type: "decision".to_string(),
+            title: "Released".to_string(),
+            body: Some(
+                release
+                    .detail
+                    .clone()
+                    .unwrap_or_else(|| "The DOT runner completed the release phase.".to_string()),
+            ),
+            lane: None,
+            created_by: RUNNER_SENDER.to_string(),
+            source_attachment_id: None,
+        });
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# result of the last run
+plan: passed
+setup: ok
+tdd: passed
+implement: passed
+verify: failed - 3 integration tests failed
+scenario_test: skipped
+";
+
+    #[test]
+    fn parses_phases_statuses_and_details() {
+        let outcomes = parse_runner_outcome(SAMPLE).unwrap();
+        assert_eq!(outcomes.len(), 6);
+        assert_eq!(outcomes[1].status, PhaseStatus::Passed);
+        assert_eq!(outcomes[4].phase, "verify");
+        assert_eq!(outcomes[4].status, PhaseStatus::Failed);
+        assert_eq!(
+            outcomes[4].detail.as_deref(),
+            Some("3 integration tests failed")
+        );
+        assert_eq!(outcomes[5].status, PhaseStatus::Skipped);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(parse_runner_outcome("verify failed").is_err());
+        assert!(parse_runner_outcome("verify: exploded").is_err());
+        assert!(parse_runner_outcome("# nothing here\n").is_err());
+    }
+
+    #[test]
+    fn failed_verify_becomes_a_risk_card() {
+        let commands = outcome_commands(&parse_runner_outcome(SAMPLE).unwrap());
+        assert_eq!(commands.len(), 2);
+        match &commands[0] {
+            Command::AppendTranscript {
+                sender, content, ..
+            } => {
+                assert_eq!(sender, RUNNER_SENDER);
+                assert!(
+                    content.contains("4 passed, 1 failed, 1 skipped"),
+                    "{}",
+                    content
+                );
+                assert!(content.contains("Failed phases: verify."));
+            }
+            other => panic!("expected AppendTranscript, got {:?}", other),
+        }
+        match &commands[1] {
+            Command::CreateCard {
+                card_type,
+                title,
+                body,
+                ..
+            } => {
+                assert_eq!(card_type, "risk");
+                assert!(title.contains("verify"));
+                assert_eq!(body.as_deref(), Some("3 integration tests failed"));
+            }
+            other => panic!("expected CreateCard, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn passed_release_records_a_decision() {
+        let outcomes = parse_runner_outcome("verify: passed\nrelease: passed").unwrap();
+        let commands = outcome_commands(&outcomes);
+        assert!(matches!(
+            commands.last(),
+            Some(Command::CreateCard { card_type, title, .. })
+                if card_type == "decision" && title == "Released"
+        ));
+    }
+}
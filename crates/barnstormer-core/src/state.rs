@@ -1,22 +1,46 @@
 // ABOUTME: Defines SpecState and UndoEntry for building spec state from an event stream.
 // ABOUTME: The apply() method pattern-matches on EventPayload to fold events into current state.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
-use crate::card::Card;
+use crate::card::{Card, CardAttachment, CardPosition};
+use crate::command::Command;
 use crate::event::{Event, EventPayload};
-use crate::model::SpecCore;
+use crate::model::{ExternalLink, SpecCore};
 use crate::transcript::{MessageKind, TranscriptMessage, UserQuestion};
 
-/// Stores the inverse operations needed to undo a mutation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Stores the inverse operations needed to undo a mutation, and the
+/// original payloads needed to redo it afterwards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UndoEntry {
     pub event_id: u64,
     pub inverse: Vec<EventPayload>,
+    /// The payloads of the event that pushed this entry. Empty for entries
+    /// from snapshots written before redo existed; those can't be redone.
+    #[serde(default)]
+    pub forward: Vec<EventPayload>,
+}
+
+/// The LLM provider and model an agent swarm runs on. Pinned into the event
+/// log when agents start so history shows what generated each change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AgentModel {
+    pub provider: String,
+    pub model: String,
+}
+
+/// Tokens the agents of one role have used on a spec, summed over steps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenStats {
+    /// Model of the most recent recorded step.
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub steps: u64,
 }
 
 /// A file attached as context to the brainstorming phase of a spec.
@@ -37,6 +61,26 @@ pub struct ContextAttachment {
     pub summary_error: Option<String>,
 }
 
+/// An agent command parked for human review instead of being applied.
+/// Risky or low-confidence writes land here; approving the proposal runs
+/// `command` through the normal validation path, rejecting drops it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedAction {
+    pub proposal_id: Ulid,
+    pub command: Command,
+    pub proposed_by: String,
+    pub reason: String,
+    pub proposed_at: DateTime<Utc>,
+}
+
+/// One entry in a spec's "definition of done" checklist.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChecklistItem {
+    pub item_id: Ulid,
+    pub text: String,
+    pub checked: bool,
+}
+
 /// Tracks which lifecycle phase a spec is in.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SpecPhase {
@@ -50,15 +94,61 @@ fn default_phase_refining() -> SpecPhase {
     SpecPhase::Refining
 }
 
+fn default_snapshot_on_shutdown() -> bool {
+    true
+}
+
+/// ID for a transcript line synthesized from an agent-step event. Derived
+/// from the event rather than freshly minted so replay reproduces it.
+fn step_message_id(event: &Event) -> Ulid {
+    Ulid::from_parts(
+        event.timestamp.timestamp_millis() as u64,
+        u128::from(event.event_id),
+    )
+}
+
+/// Accept either the current question queue or the legacy single
+/// `pending_question` (an object or null).
+fn deserialize_question_queue<'de, D>(deserializer: D) -> Result<Vec<UserQuestion>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum QueueOrSingle {
+        Queue(Vec<UserQuestion>),
+        Single(Option<UserQuestion>),
+    }
+    Ok(match QueueOrSingle::deserialize(deserializer)? {
+        QueueOrSingle::Queue(queue) => queue,
+        QueueOrSingle::Single(single) => single.into_iter().collect(),
+    })
+}
+
 /// The full materialized state of a spec, built by replaying events.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SpecState {
     pub core: Option<SpecCore>,
     pub cards: BTreeMap<Ulid, Card>,
     pub transcript: Vec<TranscriptMessage>,
-    pub pending_question: Option<UserQuestion>,
+    /// Questions waiting for the user, oldest first. Only the front one is
+    /// shown; answering it surfaces the next. Older snapshots stored a single
+    /// `pending_question`, which deserializes as a one-element queue.
+    #[serde(
+        default,
+        alias = "pending_question",
+        deserialize_with = "deserialize_question_queue"
+    )]
+    pub pending_questions: Vec<UserQuestion>,
     pub undo_stack: Vec<UndoEntry>,
+    /// Payloads of undone changes, most recent last. `Redo` re-applies the
+    /// top one; any new undoable change clears the stack.
+    #[serde(default)]
+    pub redo_stack: Vec<Vec<EventPayload>>,
     pub last_event_id: u64,
+    /// Board lanes in display order. Every card's lane is listed: a card
+    /// that lands in an unlisted lane (only logs from before lanes were
+    /// managed have these) adds it at the end.
     pub lanes: Vec<String>,
     #[serde(default = "default_phase_refining")]
     pub phase: SpecPhase,
@@ -66,6 +156,42 @@ pub struct SpecState {
     pub canvas_content: Option<String>,
     #[serde(default)]
     pub context_attachments: Vec<ContextAttachment>,
+    #[serde(default)]
+    pub pending_proposals: Vec<ProposedAction>,
+    /// Provider/model most recently pinned by a starting agent swarm.
+    #[serde(default)]
+    pub agent_model: Option<AgentModel>,
+    /// Provider/model this spec's agents start on instead of the server
+    /// defaults. None runs the defaults.
+    #[serde(default)]
+    pub model_override: Option<AgentModel>,
+    /// Archived specs keep their data but are hidden from the default spec
+    /// list and never auto-start agents.
+    #[serde(default)]
+    pub archived: bool,
+    /// Explicit completion criteria; the spec looks complete once every
+    /// item is checked.
+    #[serde(default)]
+    pub done_checklist: Vec<ChecklistItem>,
+    /// Token usage by agent role label (e.g. `planner`).
+    #[serde(default)]
+    pub token_usage: BTreeMap<String, TokenStats>,
+    /// Agent role labels the swarm runs for this spec, one slot each, in
+    /// order. None runs the server's configured roster.
+    #[serde(default)]
+    pub agent_roster: Option<Vec<String>>,
+    /// Hostnames (lowercase) this spec's agents may read with the fetch_url
+    /// tool. Empty keeps the tool out of their registries.
+    #[serde(default)]
+    pub fetch_hosts: Vec<String>,
+    /// Whether a clean server shutdown writes a final snapshot of this spec.
+    #[serde(default = "default_snapshot_on_shutdown")]
+    pub snapshot_on_shutdown: bool,
+    /// Agent steps started over the spec's whole history. Kept here so the
+    /// count survives log compaction; snapshots from before it existed
+    /// start again from zero.
+    #[serde(default)]
+    pub agent_steps: u64,
 }
 
 impl Default for SpecState {
@@ -74,13 +200,24 @@ impl Default for SpecState {
             core: None,
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         }
     }
 }
@@ -91,12 +228,83 @@ impl SpecState {
         Self::default()
     }
 
+    /// The question currently shown to the user (front of the queue).
+    pub fn pending_question(&self) -> Option<&UserQuestion> {
+        self.pending_questions.first()
+    }
+
+    /// Heuristic for finished work: the spec reached the Complete phase,
+    /// every item of a non-empty done checklist is checked, or it has
+    /// unarchived cards and every one of them is marked done.
+    pub fn looks_complete(&self) -> bool {
+        let mut active = self.cards.values().filter(|c| !c.archived).peekable();
+        self.phase == SpecPhase::Complete
+            || (!self.done_checklist.is_empty() && self.done_checklist.iter().all(|i| i.checked))
+            || (active.peek().is_some() && active.all(|c| c.done))
+    }
+
+    /// A copy of this state with archived cards left out, for exports that
+    /// should only show the active board.
+    pub fn without_archived_cards(&self) -> SpecState {
+        let mut state = self.clone();
+        state.cards.retain(|_, card| !card.archived);
+        state
+    }
+
     /// Apply a single event to mutate this state. Each event payload variant
     /// is handled to update the corresponding state fields. Undo entries are
     /// pushed for reversible mutations.
+    ///
+    /// Application is idempotent: a durable event whose ID is not past
+    /// `last_event_id` has already been applied and is ignored. This covers a
+    /// recovery replay followed by the live broadcast delivering a buffered
+    /// event again. Ephemeral events carry ID 0 and never move the mark.
     pub fn apply(&mut self, event: &Event) {
-        self.last_event_id = event.event_id;
+        if !event.payload.is_ephemeral() {
+            if event.event_id <= self.last_event_id {
+                return;
+            }
+            self.last_event_id = event.event_id;
+            if matches!(event.payload, EventPayload::AgentStepStarted { .. }) {
+                self.agent_steps += 1;
+            }
+        }
+        match &event.payload {
+            EventPayload::UndoApplied { .. } | EventPayload::RedoApplied { .. } => {
+                self.apply_payload(event);
+            }
+            _ => {
+                // A fresh change makes anything undone before it stale.
+                if self.apply_recording_forward(event) {
+                    self.redo_stack.clear();
+                }
+            }
+        }
+    }
 
+    /// Apply one event and record its payload on the undo entry it pushed,
+    /// so undoing it can later be redone. Returns whether an entry was pushed.
+    fn apply_recording_forward(&mut self, event: &Event) -> bool {
+        let depth = self.undo_stack.len();
+        self.apply_payload(event);
+        if self.undo_stack.len() <= depth {
+            return false;
+        }
+        // Undoing a merge only removes its cards, so replaying the whole
+        // merge would duplicate its transcript and lineage. Leave it without
+        // a forward payload so it can't be redone.
+        if matches!(event.payload, EventPayload::SpecMerged { .. }) {
+            return true;
+        }
+        if let Some(entry) = self.undo_stack.last_mut() {
+            entry.forward = vec![event.payload.clone()];
+        }
+        true
+    }
+
+    /// Mutate state for one event without the already-applied check. Undo
+    /// replays inverse payloads through here under the undo event's own ID.
+    fn apply_payload(&mut self, event: &Event) {
         match &event.payload {
             EventPayload::SpecCreated {
                 title,
@@ -115,6 +323,64 @@ impl SpecState {
                     notes: None,
                     created_at: event.timestamp,
                     updated_at: event.timestamp,
+                    parent_spec_id: None,
+                    merged_from: Vec::new(),
+                    external_links: Vec::new(),
+                });
+            }
+
+            EventPayload::SpecForked {
+                parent_spec_id,
+                core,
+                cards,
+                lanes,
+            } => {
+                // The child gets its own identity and timestamps; everything
+                // else is carried over from the parent as it was at fork time.
+                let mut core = core.clone();
+                core.spec_id = event.spec_id;
+                core.parent_spec_id = Some(*parent_spec_id);
+                core.created_at = event.timestamp;
+                core.updated_at = event.timestamp;
+                self.core = Some(core);
+                if !lanes.is_empty() {
+                    self.lanes = lanes.clone();
+                }
+                for card in cards {
+                    self.cards.insert(card.card_id, card.clone());
+                }
+                self.adopt_card_lanes();
+                // No undo entry — forking seeds the spec, like SpecCreated
+            }
+
+            EventPayload::SpecMerged {
+                source_spec_id,
+                cards,
+                lanes_added,
+                transcript,
+            } => {
+                if let Some(core) = &mut self.core {
+                    core.merged_from.push(*source_spec_id);
+                    core.updated_at = event.timestamp;
+                }
+                for lane in lanes_added {
+                    self.adopt_lane(lane);
+                }
+                for card in cards {
+                    self.cards.insert(card.card_id, card.clone());
+                }
+                self.transcript.extend(transcript.iter().cloned());
+                // Undo removes the merged cards; lanes, transcript and
+                // lineage stay, so a merge can't be redone.
+                self.undo_stack.push(UndoEntry {
+                    event_id: event.event_id,
+                    forward: Vec::new(),
+                    inverse: cards
+                        .iter()
+                        .map(|card| EventPayload::CardDeleted {
+                            card_id: card.card_id,
+                        })
+                        .collect(),
                 });
             }
 
@@ -163,9 +429,11 @@ impl SpecState {
                 }];
                 self.undo_stack.push(UndoEntry {
                     event_id: event.event_id,
+                    forward: Vec::new(),
                     inverse,
                 });
                 self.cards.insert(card.card_id, card.clone());
+                self.adopt_lane(&card.lane);
             }
 
             EventPayload::CardUpdated {
@@ -174,6 +442,7 @@ impl SpecState {
                 body,
                 card_type,
                 refs,
+                tags,
             } => {
                 if let Some(card) = self.cards.get_mut(card_id) {
                     // Build inverse from old values before mutating
@@ -183,9 +452,11 @@ impl SpecState {
                         body: body.as_ref().map(|_| card.body.clone()),
                         card_type: card_type.as_ref().map(|_| card.card_type.clone()),
                         refs: refs.as_ref().map(|_| card.refs.clone()),
+                        tags: tags.as_ref().map(|_| card.tags.clone()),
                     }];
                     self.undo_stack.push(UndoEntry {
                         event_id: event.event_id,
+                        forward: Vec::new(),
                         inverse,
                     });
 
@@ -201,6 +472,9 @@ impl SpecState {
                     if let Some(r) = refs {
                         card.refs = r.clone();
                     }
+                    if let Some(t) = tags {
+                        card.tags = t.clone();
+                    }
                     card.updated_at = event.timestamp;
                 }
             }
@@ -218,13 +492,201 @@ impl SpecState {
                     }];
                     self.undo_stack.push(UndoEntry {
                         event_id: event.event_id,
+                        forward: Vec::new(),
                         inverse,
                     });
 
                     card.lane = lane.clone();
                     card.order = *order;
                     card.updated_at = event.timestamp;
+                    self.adopt_lane(lane);
+                }
+            }
+
+            EventPayload::CardsReordered { positions } => {
+                let inverse: Vec<CardPosition> = positions
+                    .iter()
+                    .filter_map(|p| self.cards.get(&p.card_id))
+                    .map(|card| CardPosition {
+                        card_id: card.card_id,
+                        lane: card.lane.clone(),
+                        order: card.order,
+                    })
+                    .collect();
+                if !inverse.is_empty() {
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse: vec![EventPayload::CardsReordered { positions: inverse }],
+                    });
                 }
+                self.place_cards(positions, event.timestamp);
+            }
+
+            EventPayload::CardDoneSet { card_id, done } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    let inverse = vec![EventPayload::CardDoneSet {
+                        card_id: *card_id,
+                        done: card.done,
+                    }];
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse,
+                    });
+
+                    card.done = *done;
+                    card.updated_at = event.timestamp;
+                }
+            }
+
+            EventPayload::CardArchivedSet { card_id, archived } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    let inverse = vec![EventPayload::CardArchivedSet {
+                        card_id: *card_id,
+                        archived: card.archived,
+                    }];
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse,
+                    });
+
+                    card.archived = *archived;
+                    card.updated_at = event.timestamp;
+                }
+            }
+
+            EventPayload::CardDueDateSet { card_id, due_date } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    let inverse = vec![EventPayload::CardDueDateSet {
+                        card_id: *card_id,
+                        due_date: card.due_date,
+                    }];
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse,
+                    });
+
+                    card.due_date = *due_date;
+                    card.updated_at = event.timestamp;
+                }
+            }
+
+            EventPayload::CardReviewSet {
+                card_id, review, ..
+            } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    let inverse = vec![EventPayload::CardReviewSet {
+                        card_id: *card_id,
+                        review: card.review.clone(),
+                        resolution: None,
+                    }];
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse,
+                    });
+
+                    card.review = review.clone();
+                    card.updated_at = event.timestamp;
+                }
+            }
+
+            EventPayload::CardAttachmentAdded {
+                card_id,
+                index,
+                attachment,
+            } => {
+                if self.insert_card_attachment(card_id, *index, attachment, event.timestamp) {
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse: vec![EventPayload::CardAttachmentRemoved {
+                            card_id: *card_id,
+                            attachment_id: attachment.attachment_id,
+                        }],
+                    });
+                }
+            }
+
+            EventPayload::CardAttachmentRemoved {
+                card_id,
+                attachment_id,
+            } => {
+                if let Some((index, attachment)) =
+                    self.remove_card_attachment(card_id, attachment_id, event.timestamp)
+                {
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse: vec![EventPayload::CardAttachmentAdded {
+                            card_id: *card_id,
+                            index,
+                            attachment,
+                        }],
+                    });
+                }
+            }
+
+            EventPayload::LaneRenamed {
+                from,
+                to,
+                lanes,
+                card_ids,
+            } => {
+                let inverse = vec![EventPayload::LaneRenamed {
+                    from: to.clone(),
+                    to: from.clone(),
+                    lanes: self.lanes.clone(),
+                    card_ids: card_ids.clone(),
+                }];
+                self.undo_stack.push(UndoEntry {
+                    event_id: event.event_id,
+                    forward: Vec::new(),
+                    inverse,
+                });
+                self.rename_lane(to, lanes, card_ids, event.timestamp);
+            }
+
+            EventPayload::LaneDeleted {
+                name,
+                lanes,
+                moved_to,
+                card_ids,
+            } => {
+                // Undo puts the lane back where it was and moves its cards
+                // home from wherever they were sent.
+                let inverse = vec![match moved_to {
+                    Some(moved_to) => EventPayload::LaneRenamed {
+                        from: moved_to.clone(),
+                        to: name.clone(),
+                        lanes: self.lanes.clone(),
+                        card_ids: card_ids.clone(),
+                    },
+                    None => EventPayload::LanesChanged {
+                        lanes: self.lanes.clone(),
+                    },
+                }];
+                self.undo_stack.push(UndoEntry {
+                    event_id: event.event_id,
+                    forward: Vec::new(),
+                    inverse,
+                });
+                self.delete_lane(lanes, moved_to.as_deref(), card_ids, event.timestamp);
+            }
+
+            EventPayload::LanesChanged { lanes } => {
+                let inverse = vec![EventPayload::LanesChanged {
+                    lanes: self.lanes.clone(),
+                }];
+                self.undo_stack.push(UndoEntry {
+                    event_id: event.event_id,
+                    forward: Vec::new(),
+                    inverse,
+                });
+                self.lanes = lanes.clone();
             }
 
             EventPayload::CardDeleted { card_id } => {
@@ -232,6 +694,7 @@ impl SpecState {
                     let inverse = vec![EventPayload::CardCreated { card }];
                     self.undo_stack.push(UndoEntry {
                         event_id: event.event_id,
+                        forward: Vec::new(),
                         inverse,
                     });
                 }
@@ -242,14 +705,15 @@ impl SpecState {
             }
 
             EventPayload::QuestionAsked { question } => {
-                self.pending_question = Some(question.clone());
+                self.pending_questions.push(question.clone());
             }
 
             EventPayload::QuestionAnswered {
                 question_id,
                 answer,
             } => {
-                self.pending_question = None;
+                self.pending_questions
+                    .retain(|q| q.question_id() != *question_id);
                 self.canvas_content = None;
                 self.transcript.push(TranscriptMessage {
                     message_id: *question_id,
@@ -257,6 +721,8 @@ impl SpecState {
                     content: answer.clone(),
                     kind: MessageKind::Chat,
                     timestamp: event.timestamp,
+                    agent_model: None,
+                    display_name: None,
                 });
             }
 
@@ -265,11 +731,13 @@ impl SpecState {
                 description,
             } => {
                 self.transcript.push(TranscriptMessage {
-                    message_id: Ulid::new(),
+                    message_id: step_message_id(event),
                     sender: agent_id.clone(),
                     content: description.clone(),
                     kind: MessageKind::StepStarted,
                     timestamp: event.timestamp,
+                    agent_model: self.agent_model.clone(),
+                    display_name: None,
                 });
             }
 
@@ -278,11 +746,13 @@ impl SpecState {
                 diff_summary,
             } => {
                 self.transcript.push(TranscriptMessage {
-                    message_id: Ulid::new(),
+                    message_id: step_message_id(event),
                     sender: agent_id.clone(),
                     content: diff_summary.clone(),
                     kind: MessageKind::StepFinished,
                     timestamp: event.timestamp,
+                    agent_model: self.agent_model.clone(),
+                    display_name: None,
                 });
             }
 
@@ -297,11 +767,33 @@ impl SpecState {
                     };
                     self.apply_without_undo(&synthetic_event);
                 }
-                self.undo_stack.pop();
+                match self.undo_stack.pop() {
+                    Some(entry) if !entry.forward.is_empty() => {
+                        self.redo_stack.push(entry.forward);
+                    }
+                    // Without the original payloads this change can't be
+                    // redone, and redoing older ones past it would be wrong.
+                    _ => self.redo_stack.clear(),
+                }
                 // Clear stale canvas content after undo
                 self.canvas_content = None;
             }
 
+            EventPayload::RedoApplied { payloads } => {
+                // Re-apply as ordinary changes so the redo can be undone.
+                for payload in payloads {
+                    let synthetic_event = Event {
+                        event_id: event.event_id,
+                        spec_id: event.spec_id,
+                        timestamp: event.timestamp,
+                        payload: payload.clone(),
+                    };
+                    self.apply_recording_forward(&synthetic_event);
+                }
+                self.redo_stack.pop();
+                self.canvas_content = None;
+            }
+
             EventPayload::SnapshotWritten { .. } => {
                 // No-op on state
             }
@@ -326,6 +818,7 @@ impl SpecState {
                 }];
                 self.undo_stack.push(UndoEntry {
                     event_id: event.event_id,
+                    forward: Vec::new(),
                     inverse,
                 });
                 self.context_attachments.push(attachment.clone());
@@ -374,6 +867,7 @@ impl SpecState {
                     let prior = att.user_notes.clone().unwrap_or_default();
                     self.undo_stack.push(UndoEntry {
                         event_id: event.event_id,
+                        forward: Vec::new(),
                         inverse: vec![EventPayload::ContextNotesUpdated {
                             attachment_id: *attachment_id,
                             notes: prior,
@@ -398,6 +892,7 @@ impl SpecState {
                     restored.removed = false;
                     self.undo_stack.push(UndoEntry {
                         event_id: event.event_id,
+                        forward: Vec::new(),
                         inverse: vec![EventPayload::ContextAttached {
                             attachment: restored,
                         }],
@@ -413,15 +908,285 @@ impl SpecState {
             EventPayload::StreamingToolActivity { .. } => {
                 // Ephemeral — no state mutation
             }
+
+            EventPayload::ProposalQueued { proposal } => {
+                self.pending_proposals.push(proposal.clone());
+                // No undo entry — the queue is resolved by approve/reject
+            }
+
+            EventPayload::ProposalApproved { proposal_id }
+            | EventPayload::ProposalRejected { proposal_id } => {
+                // An approved command's own events follow this one in the
+                // same batch and carry their own undo entries.
+                self.pending_proposals
+                    .retain(|p| p.proposal_id != *proposal_id);
+                // A proposal resolved from the proposals panel no longer
+                // needs its review-gate question.
+                self.pending_questions.retain(|q| {
+                    !matches!(q, UserQuestion::ReviewGate { proposal_id: gated, .. } if gated == proposal_id)
+                });
+            }
+
+            EventPayload::AgentModelPinned { provider, model } => {
+                // Provenance marker, not a user edit — no undo entry
+                self.agent_model = Some(AgentModel {
+                    provider: provider.clone(),
+                    model: model.clone(),
+                });
+            }
+
+            EventPayload::UsageRecorded {
+                role,
+                model,
+                input_tokens,
+                output_tokens,
+            } => {
+                // Accounting, not a spec edit — no undo entry
+                let stats = self.token_usage.entry(role.clone()).or_default();
+                stats.model = model.clone();
+                stats.input_tokens += input_tokens;
+                stats.output_tokens += output_tokens;
+                stats.steps += 1;
+            }
+
+            EventPayload::AgentRosterSet { roles } => {
+                // Swarm configuration, not a spec edit — no undo entry
+                self.agent_roster = Some(roles.clone());
+            }
+
+            EventPayload::FetchHostsSet { hosts } => {
+                // Swarm configuration, not a spec edit — no undo entry
+                self.fetch_hosts = hosts.clone();
+            }
+
+            EventPayload::ModelOverrideSet { model } => {
+                // Swarm configuration, not a spec edit — no undo entry
+                self.model_override = model.clone();
+            }
+
+            EventPayload::SnapshotOnShutdownSet { enabled } => {
+                // Housekeeping flag, not a spec edit — no undo entry
+                self.snapshot_on_shutdown = *enabled;
+            }
+
+            EventPayload::SpecArchivedSet { archived } => {
+                // Housekeeping flag, not a spec edit — no undo entry
+                self.archived = *archived;
+            }
+
+            EventPayload::ChecklistItemAdded { item } => {
+                self.undo_stack.push(UndoEntry {
+                    event_id: event.event_id,
+                    forward: Vec::new(),
+                    inverse: vec![EventPayload::ChecklistItemRemoved {
+                        item_id: item.item_id,
+                    }],
+                });
+                self.done_checklist.push(item.clone());
+            }
+
+            EventPayload::ChecklistItemToggled { item_id, checked } => {
+                if let Some(item) = self
+                    .done_checklist
+                    .iter_mut()
+                    .find(|i| i.item_id == *item_id)
+                {
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse: vec![EventPayload::ChecklistItemToggled {
+                            item_id: *item_id,
+                            checked: item.checked,
+                        }],
+                    });
+                    item.checked = *checked;
+                }
+            }
+
+            EventPayload::ChecklistItemRemoved { item_id } => {
+                if let Some(pos) = self
+                    .done_checklist
+                    .iter()
+                    .position(|i| i.item_id == *item_id)
+                {
+                    let item = self.done_checklist.remove(pos);
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse: vec![EventPayload::ChecklistItemAdded { item }],
+                    });
+                }
+            }
+
+            EventPayload::ExternalLinkAdded { index, link } => {
+                if self.insert_external_link(*index, link, event.timestamp) {
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse: vec![EventPayload::ExternalLinkRemoved {
+                            link_id: link.link_id,
+                        }],
+                    });
+                }
+            }
+
+            EventPayload::ExternalLinkRemoved { link_id } => {
+                if let Some((index, link)) = self.remove_external_link(link_id, event.timestamp) {
+                    self.undo_stack.push(UndoEntry {
+                        event_id: event.event_id,
+                        forward: Vec::new(),
+                        inverse: vec![EventPayload::ExternalLinkAdded { index, link }],
+                    });
+                }
+            }
+        }
+    }
+
+    /// Replace the lane list and move the given cards into lane `to`.
+    fn rename_lane(
+        &mut self,
+        to: &str,
+        lanes: &[String],
+        card_ids: &[Ulid],
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) {
+        self.lanes = lanes.to_vec();
+        for card_id in card_ids {
+            if let Some(card) = self.cards.get_mut(card_id) {
+                card.lane = to.to_string();
+                card.updated_at = timestamp;
+            }
+        }
+    }
+
+    fn delete_lane(
+        &mut self,
+        lanes: &[String],
+        moved_to: Option<&str>,
+        card_ids: &[Ulid],
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) {
+        match moved_to {
+            Some(moved_to) => self.rename_lane(moved_to, lanes, card_ids, timestamp),
+            None => self.lanes = lanes.to_vec(),
+        }
+    }
+
+    /// Move each listed card to its lane and order. Unknown cards are skipped.
+    fn place_cards(
+        &mut self,
+        positions: &[CardPosition],
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) {
+        for position in positions {
+            if let Some(card) = self.cards.get_mut(&position.card_id) {
+                card.lane = position.lane.clone();
+                card.order = position.order;
+                card.updated_at = timestamp;
+                self.adopt_lane(&position.lane);
+            }
+        }
+    }
+
+    /// Append `lane` to the lane list unless it is already there.
+    fn adopt_lane(&mut self, lane: &str) {
+        if !self.lanes.iter().any(|l| l == lane) {
+            self.lanes.push(lane.to_string());
         }
     }
 
+    /// Append every lane a card sits in but the lane list lacks, in
+    /// alphabetical order. Snapshots written before lanes were managed can
+    /// hold such cards; loading one calls this so the board shows them.
+    pub fn adopt_card_lanes(&mut self) {
+        let missing: BTreeSet<String> = self
+            .cards
+            .values()
+            .filter(|c| !self.lanes.contains(&c.lane))
+            .map(|c| c.lane.clone())
+            .collect();
+        self.lanes.extend(missing);
+    }
+
+    /// Insert `attachment` into the card's list at `index` (clamped to the
+    /// end). Returns false when the card does not exist.
+    fn insert_card_attachment(
+        &mut self,
+        card_id: &Ulid,
+        index: usize,
+        attachment: &CardAttachment,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        match self.cards.get_mut(card_id) {
+            Some(card) => {
+                let index = index.min(card.attachments.len());
+                card.attachments.insert(index, attachment.clone());
+                card.updated_at = timestamp;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove an attachment from a card, returning its former position and
+    /// value so the removal can be undone in place.
+    fn remove_card_attachment(
+        &mut self,
+        card_id: &Ulid,
+        attachment_id: &Ulid,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Option<(usize, CardAttachment)> {
+        let card = self.cards.get_mut(card_id)?;
+        let index = card
+            .attachments
+            .iter()
+            .position(|a| a.attachment_id == *attachment_id)?;
+        card.updated_at = timestamp;
+        Some((index, card.attachments.remove(index)))
+    }
+
+    /// Insert `link` into the spec's external links at `index` (clamped to
+    /// the end). Returns false when the spec has no core yet.
+    fn insert_external_link(
+        &mut self,
+        index: usize,
+        link: &ExternalLink,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        match &mut self.core {
+            Some(core) => {
+                let index = index.min(core.external_links.len());
+                core.external_links.insert(index, link.clone());
+                core.updated_at = timestamp;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove an external link, returning its former position and value so
+    /// the removal can be undone in place.
+    fn remove_external_link(
+        &mut self,
+        link_id: &Ulid,
+        timestamp: chrono::DateTime<chrono::Utc>,
+    ) -> Option<(usize, ExternalLink)> {
+        let core = self.core.as_mut()?;
+        let index = core
+            .external_links
+            .iter()
+            .position(|l| l.link_id == *link_id)?;
+        core.updated_at = timestamp;
+        Some((index, core.external_links.remove(index)))
+    }
+
     /// Apply an event's payload effects without pushing undo entries.
     /// Used internally for applying inverse events during undo.
     fn apply_without_undo(&mut self, event: &Event) {
         match &event.payload {
             EventPayload::CardCreated { card } => {
                 self.cards.insert(card.card_id, card.clone());
+                self.adopt_lane(&card.lane);
             }
             EventPayload::CardUpdated {
                 card_id,
@@ -429,6 +1194,7 @@ impl SpecState {
                 body,
                 card_type,
                 refs,
+                tags,
             } => {
                 if let Some(card) = self.cards.get_mut(card_id) {
                     if let Some(t) = title {
@@ -443,6 +1209,9 @@ impl SpecState {
                     if let Some(r) = refs {
                         card.refs = r.clone();
                     }
+                    if let Some(t) = tags {
+                        card.tags = t.clone();
+                    }
                     card.updated_at = event.timestamp;
                 }
             }
@@ -455,8 +1224,76 @@ impl SpecState {
                     card.lane = lane.clone();
                     card.order = *order;
                     card.updated_at = event.timestamp;
+                    self.adopt_lane(lane);
                 }
             }
+            EventPayload::CardsReordered { positions } => {
+                self.place_cards(positions, event.timestamp);
+            }
+            EventPayload::CardDoneSet { card_id, done } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    card.done = *done;
+                    card.updated_at = event.timestamp;
+                }
+            }
+            EventPayload::CardArchivedSet { card_id, archived } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    card.archived = *archived;
+                    card.updated_at = event.timestamp;
+                }
+            }
+            EventPayload::CardDueDateSet { card_id, due_date } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    card.due_date = *due_date;
+                    card.updated_at = event.timestamp;
+                }
+            }
+            EventPayload::CardReviewSet {
+                card_id, review, ..
+            } => {
+                if let Some(card) = self.cards.get_mut(card_id) {
+                    card.review = review.clone();
+                    card.updated_at = event.timestamp;
+                }
+            }
+            EventPayload::CardAttachmentAdded {
+                card_id,
+                index,
+                attachment,
+            } => {
+                self.insert_card_attachment(card_id, *index, attachment, event.timestamp);
+            }
+            EventPayload::CardAttachmentRemoved {
+                card_id,
+                attachment_id,
+            } => {
+                self.remove_card_attachment(card_id, attachment_id, event.timestamp);
+            }
+            EventPayload::ExternalLinkAdded { index, link } => {
+                self.insert_external_link(*index, link, event.timestamp);
+            }
+            EventPayload::ExternalLinkRemoved { link_id } => {
+                self.remove_external_link(link_id, event.timestamp);
+            }
+            EventPayload::LaneRenamed {
+                to,
+                lanes,
+                card_ids,
+                ..
+            } => {
+                self.rename_lane(to, lanes, card_ids, event.timestamp);
+            }
+            EventPayload::LaneDeleted {
+                lanes,
+                moved_to,
+                card_ids,
+                ..
+            } => {
+                self.delete_lane(lanes, moved_to.as_deref(), card_ids, event.timestamp);
+            }
+            EventPayload::LanesChanged { lanes } => {
+                self.lanes = lanes.clone();
+            }
             EventPayload::CardDeleted { card_id } => {
                 self.cards.remove(card_id);
             }
@@ -542,7 +1379,7 @@ impl SpecState {
             }
             // Other event types during undo are applied normally
             _ => {
-                self.apply(event);
+                self.apply_payload(event);
             }
         }
     }
@@ -592,6 +1429,153 @@ mod tests {
         assert_eq!(state.last_event_id, 1);
     }
 
+    #[test]
+    fn applying_the_same_event_twice_is_a_noop() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        let card = Card::new("idea".to_string(), "Once".to_string(), "human".to_string());
+        let created = make_event(1, spec_id, EventPayload::CardCreated { card });
+        let message = make_event(
+            2,
+            spec_id,
+            EventPayload::TranscriptAppended {
+                message: TranscriptMessage::new("human".to_string(), "hi".to_string()),
+            },
+        );
+        state.apply(&created);
+        state.apply(&message);
+        let after_first = serde_json::to_value(&state).unwrap();
+
+        state.apply(&created);
+        state.apply(&message);
+        assert_eq!(serde_json::to_value(&state).unwrap(), after_first);
+        assert_eq!(state.cards.len(), 1);
+        assert_eq!(state.transcript.len(), 1);
+        assert_eq!(state.undo_stack.len(), 1);
+        assert_eq!(state.last_event_id, 2);
+    }
+
+    #[test]
+    fn ephemeral_events_do_not_move_the_applied_mark() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        let card = Card::new("idea".to_string(), "Once".to_string(), "human".to_string());
+        let created = make_event(1, spec_id, EventPayload::CardCreated { card });
+        state.apply(&created);
+        state.apply(&make_event(
+            0,
+            spec_id,
+            EventPayload::StreamingDelta {
+                agent_id: "manager-1".to_string(),
+                text: "...".to_string(),
+            },
+        ));
+        assert_eq!(state.last_event_id, 1);
+
+        state.apply(&created);
+        assert_eq!(state.cards.len(), 1);
+    }
+
+    #[test]
+    fn looks_complete_when_phase_complete_or_all_cards_done() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        assert!(!state.looks_complete(), "no cards is not complete");
+
+        let card = Card::new("task".to_string(), "Ship".to_string(), "human".to_string());
+        let card_id = card.card_id;
+        state.apply(&make_event(1, spec_id, EventPayload::CardCreated { card }));
+        assert!(!state.looks_complete());
+
+        state.apply(&make_event(
+            2,
+            spec_id,
+            EventPayload::CardDoneSet {
+                card_id,
+                done: true,
+            },
+        ));
+        assert!(state.looks_complete());
+
+        let mut finished = SpecState::new();
+        finished.phase = SpecPhase::Complete;
+        assert!(finished.looks_complete());
+    }
+
+    #[test]
+    fn checking_every_checklist_item_looks_complete() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        let items: Vec<ChecklistItem> = ["Docs written", "Load tested"]
+            .iter()
+            .map(|text| ChecklistItem {
+                item_id: Ulid::new(),
+                text: text.to_string(),
+                checked: false,
+            })
+            .collect();
+        for (i, item) in items.iter().enumerate() {
+            state.apply(&make_event(
+                i as u64 + 1,
+                spec_id,
+                EventPayload::ChecklistItemAdded { item: item.clone() },
+            ));
+        }
+        assert_eq!(state.done_checklist.len(), 2);
+        assert!(!state.looks_complete());
+
+        state.apply(&make_event(
+            3,
+            spec_id,
+            EventPayload::ChecklistItemToggled {
+                item_id: items[0].item_id,
+                checked: true,
+            },
+        ));
+        assert!(state.done_checklist[0].checked);
+        assert!(!state.looks_complete(), "one item still open");
+
+        state.apply(&make_event(
+            4,
+            spec_id,
+            EventPayload::ChecklistItemToggled {
+                item_id: items[1].item_id,
+                checked: true,
+            },
+        ));
+        assert!(state.looks_complete());
+
+        state.apply(&make_event(
+            5,
+            spec_id,
+            EventPayload::ChecklistItemRemoved {
+                item_id: items[0].item_id,
+            },
+        ));
+        assert_eq!(state.done_checklist.len(), 1);
+        assert_eq!(state.undo_stack.len(), 5);
+    }
+
+    #[test]
+    fn spec_archived_set_toggles_flag_without_undo() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        state.apply(&make_event(
+            1,
+            spec_id,
+            EventPayload::SpecArchivedSet { archived: true },
+        ));
+        assert!(state.archived);
+        assert!(state.undo_stack.is_empty());
+
+        state.apply(&make_event(
+            2,
+            spec_id,
+            EventPayload::SpecArchivedSet { archived: false },
+        ));
+        assert!(!state.archived);
+    }
+
     #[test]
     fn apply_spec_core_updated_modifies_fields() {
         let mut state = SpecState::new();
@@ -671,6 +1655,7 @@ mod tests {
                 body: Some(Some("New body".to_string())),
                 card_type: None,
                 refs: None,
+                tags: None,
             },
         ));
 
@@ -708,6 +1693,50 @@ mod tests {
         assert_eq!(card.order, 3.5);
     }
 
+    #[test]
+    fn cards_in_unlisted_lanes_add_them_to_the_lane_list() {
+        // Logs from before lanes were managed create and move cards into
+        // lanes that were never created; replay puts those lanes on the board.
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        let mut card = Card::new(
+            "task".to_string(),
+            "Legacy".to_string(),
+            "human".to_string(),
+        );
+        card.lane = "Backlog".to_string();
+        let card_id = card.card_id;
+
+        state.apply(&make_event(1, spec_id, EventPayload::CardCreated { card }));
+        state.apply(&make_event(
+            2,
+            spec_id,
+            EventPayload::CardMoved {
+                card_id,
+                lane: "Later".to_string(),
+                order: 1.0,
+            },
+        ));
+
+        assert_eq!(
+            state.lanes,
+            vec!["Ideas", "Plan", "Spec", "Backlog", "Later"]
+        );
+    }
+
+    #[test]
+    fn adopt_card_lanes_appends_missing_lanes_alphabetically() {
+        let mut state = SpecState::new();
+        for lane in ["Zeta", "Plan", "Alpha"] {
+            let mut card = Card::new("idea".to_string(), lane.to_string(), "human".to_string());
+            card.lane = lane.to_string();
+            state.cards.insert(card.card_id, card);
+        }
+
+        state.adopt_card_lanes();
+        assert_eq!(state.lanes, vec!["Ideas", "Plan", "Spec", "Alpha", "Zeta"]);
+    }
+
     #[test]
     fn apply_card_deleted_removes_card() {
         let mut state = SpecState::new();
@@ -731,6 +1760,36 @@ mod tests {
         assert!(!state.cards.contains_key(&card_id));
     }
 
+    #[test]
+    fn card_archived_set_hides_card_and_undoes() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        let card = Card::new("task".to_string(), "Park".to_string(), "human".to_string());
+        let card_id = card.card_id;
+        state.apply(&make_event(1, spec_id, EventPayload::CardCreated { card }));
+
+        state.apply(&make_event(
+            2,
+            spec_id,
+            EventPayload::CardArchivedSet {
+                card_id,
+                archived: true,
+            },
+        ));
+        assert!(state.cards[&card_id].archived);
+        assert!(state.without_archived_cards().cards.is_empty());
+        assert!(!state.looks_complete(), "archived cards do not count");
+
+        let entry = state.undo_stack.last().unwrap();
+        assert_eq!(
+            entry.inverse,
+            vec![EventPayload::CardArchivedSet {
+                card_id,
+                archived: false,
+            }]
+        );
+    }
+
     #[test]
     fn apply_question_asked_sets_pending() {
         let mut state = SpecState::new();
@@ -749,7 +1808,7 @@ mod tests {
             },
         ));
 
-        assert!(state.pending_question.is_some());
+        assert!(state.pending_question().is_some());
     }
 
     #[test]
@@ -768,7 +1827,7 @@ mod tests {
             spec_id,
             EventPayload::QuestionAsked { question },
         ));
-        assert!(state.pending_question.is_some());
+        assert!(state.pending_question().is_some());
 
         state.apply(&make_event(
             2,
@@ -778,7 +1837,7 @@ mod tests {
                 answer: "Yes".to_string(),
             },
         ));
-        assert!(state.pending_question.is_none());
+        assert!(state.pending_question().is_none());
         // The answer should be in transcript
         assert_eq!(state.transcript.len(), 1);
         assert_eq!(state.transcript[0].content, "Yes");
@@ -806,6 +1865,7 @@ mod tests {
                 body: None,
                 card_type: None,
                 refs: None,
+                tags: None,
             },
         ));
         assert_eq!(state.undo_stack.len(), 2);
@@ -869,6 +1929,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn redo_applied_reapplies_the_undone_change() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        let card = Card::new(
+            "idea".to_string(),
+            "Redo Test".to_string(),
+            "human".to_string(),
+        );
+        let card_id = card.card_id;
+        state.apply(&make_event(
+            1,
+            spec_id,
+            EventPayload::CardCreated { card: card.clone() },
+        ));
+        state.apply(&make_event(
+            2,
+            spec_id,
+            EventPayload::UndoApplied {
+                target_event_id: 1,
+                inverse_events: vec![EventPayload::CardDeleted { card_id }],
+            },
+        ));
+        assert_eq!(
+            state.redo_stack,
+            vec![vec![EventPayload::CardCreated { card }]]
+        );
+
+        let payloads = state.redo_stack[0].clone();
+        state.apply(&make_event(
+            3,
+            spec_id,
+            EventPayload::RedoApplied { payloads },
+        ));
+        assert!(state.cards.contains_key(&card_id));
+        assert!(state.redo_stack.is_empty());
+        // The redo is itself undoable.
+        assert_eq!(state.undo_stack.len(), 1);
+        assert_eq!(state.undo_stack[0].event_id, 3);
+    }
+
+    #[test]
+    fn new_change_clears_redo_stack() {
+        let mut state = SpecState::new();
+        let spec_id = make_spec_id();
+        let card = Card::new("idea".to_string(), "First".to_string(), "human".to_string());
+        let card_id = card.card_id;
+        state.apply(&make_event(1, spec_id, EventPayload::CardCreated { card }));
+        state.apply(&make_event(
+            2,
+            spec_id,
+            EventPayload::UndoApplied {
+                target_event_id: 1,
+                inverse_events: vec![EventPayload::CardDeleted { card_id }],
+            },
+        ));
+        assert_eq!(state.redo_stack.len(), 1);
+
+        // Transcript chatter isn't undoable and leaves the redo stack alone...
+        state.apply(&make_event(
+            3,
+            spec_id,
+            EventPayload::TranscriptAppended {
+                message: TranscriptMessage::new("human".to_string(), "hm".to_string()),
+            },
+        ));
+        assert_eq!(state.redo_stack.len(), 1);
+
+        // ...but a new undoable change makes it stale.
+        let other = Card::new(
+            "idea".to_string(),
+            "Second".to_string(),
+            "human".to_string(),
+        );
+        state.apply(&make_event(
+            4,
+            spec_id,
+            EventPayload::CardCreated { card: other },
+        ));
+        assert!(state.redo_stack.is_empty());
+    }
+
     #[test]
     fn apply_agent_step_started_sets_step_started_kind() {
         let mut state = SpecState::new();
@@ -1163,6 +2305,23 @@ mod tests {
         let json = r#"{"core":null,"cards":{},"transcript":[],"pending_question":null,"undo_stack":[],"last_event_id":0,"lanes":["Ideas","Plan","Spec"]}"#;
         let state: SpecState = serde_json::from_str(json).unwrap();
         assert_eq!(state.phase, SpecPhase::Refining);
+        assert!(state.pending_questions.is_empty());
+    }
+
+    #[test]
+    fn legacy_single_pending_question_loads_as_queue() {
+        let q_id = Ulid::new();
+        let json = format!(
+            r#"{{"core":null,"cards":{{}},"transcript":[],"pending_question":{{"type":"Freeform","question_id":"{}","question":"Why?","placeholder":null,"validation_hint":null}},"undo_stack":[],"last_event_id":0,"lanes":["Ideas","Plan","Spec"]}}"#,
+            q_id
+        );
+        let state: SpecState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state.pending_questions.len(), 1);
+        assert_eq!(state.pending_question().unwrap().question_id(), q_id);
+
+        let roundtrip: SpecState =
+            serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        assert_eq!(roundtrip.pending_questions.len(), 1);
     }
 
     #[test]
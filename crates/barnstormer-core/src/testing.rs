@@ -0,0 +1,463 @@
+// ABOUTME: Test utilities: a seeded generator of random command sequences for replay fuzzing.
+// ABOUTME: Deterministic per seed, so a failing sequence is reproduced from its seed alone.
+
+use ulid::Ulid;
+
+use crate::card::Card;
+use crate::command::Command;
+use crate::state::{SpecPhase, SpecState};
+use crate::transcript::{TranscriptMessage, UserQuestion};
+
+/// Small deterministic PRNG (SplitMix64). Kept in-tree so a seed keeps
+/// producing the same sequence regardless of external RNG crate versions.
+#[derive(Debug, Clone)]
+pub struct SeededRng(u64);
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..n`; `n` must be non-zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// True `percent`% of the time.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    /// A random element of `items`, or None when it is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.below(items.len())])
+        }
+    }
+}
+
+const AGENTS: &[&str] = &["human", "manager-01", "planner-01", "critic-01"];
+const WORDS: &[&str] = &[
+    "cache", "login", "export", "billing", "search", "offline", "audit", "retry",
+];
+const LANES: &[&str] = &["Ideas", "Plan", "Spec", "Later", "Parking"];
+const CARD_TYPES: &[&str] = &["idea", "task", "risk", "decision", "constraint"];
+
+/// Generates plausible commands for a spec. IDs are drawn from the spec's
+/// current state so most commands validate; the ones that don't are simply
+/// rejected by the actor, which is also worth exercising.
+#[derive(Debug, Clone)]
+pub struct CommandFuzzer {
+    rng: SeededRng,
+}
+
+impl CommandFuzzer {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    fn word(&mut self) -> String {
+        self.rng.pick(WORDS).unwrap().to_string()
+    }
+
+    fn agent(&mut self) -> String {
+        self.rng.pick(AGENTS).unwrap().to_string()
+    }
+
+    fn lane(&mut self) -> String {
+        self.rng.pick(LANES).unwrap().to_string()
+    }
+
+    fn maybe_text(&mut self) -> Option<String> {
+        self.rng
+            .chance(50)
+            .then(|| format!("{} {}", self.word(), self.word()))
+    }
+
+    /// The next command to send to a spec currently in `state`.
+    pub fn next_command(&mut self, state: &SpecState) -> Command {
+        if state.core.is_none() {
+            return Command::CreateSpec {
+                title: format!("Spec about {}", self.word()),
+                one_liner: self.word(),
+                goal: format!("Improve {}", self.word()),
+            };
+        }
+
+        let card_ids: Vec<Ulid> = state.cards.keys().copied().collect();
+        let attachment_ids: Vec<Ulid> = state
+            .context_attachments
+            .iter()
+            .filter(|a| !a.removed)
+            .map(|a| a.attachment_id)
+            .collect();
+        let checklist_ids: Vec<Ulid> = state.done_checklist.iter().map(|i| i.item_id).collect();
+        let link_ids: Vec<Ulid> = state
+            .core
+            .iter()
+            .flat_map(|c| c.external_links.iter().map(|l| l.link_id))
+            .collect();
+        let proposal_ids: Vec<Ulid> = state
+            .pending_proposals
+            .iter()
+            .map(|p| p.proposal_id)
+            .collect();
+
+        match self.rng.below(28) {
+            0..=3 => Command::CreateCard {
+                card_type: self.rng.pick(CARD_TYPES).unwrap().to_string(),
+                title: format!("{} {}", self.word(), self.word()),
+                body: self.maybe_text(),
+                lane: self.rng.chance(70).then(|| self.lane()),
+                created_by: self.agent(),
+                source_attachment_id: None,
+            },
+            4 | 5 => match self.rng.pick(&card_ids).copied() {
+                Some(card_id) => Command::UpdateCard {
+                    card_id,
+                    title: self.maybe_text(),
+                    body: self.rng.chance(50).then(|| self.maybe_text()),
+                    card_type: self
+                        .rng
+                        .chance(30)
+                        .then(|| self.rng.pick(CARD_TYPES).unwrap().to_string()),
+                    refs: self.rng.chance(40).then(|| {
+                        card_ids
+                            .iter()
+                            .filter(|_| self.rng.chance(30))
+                            .map(|id| id.to_string())
+                            .collect()
+                    }),
+                    tags: self
+                        .rng
+                        .chance(30)
+                        .then(|| (0..self.rng.below(3)).map(|_| self.word()).collect()),
+                    updated_by: self.agent(),
+                },
+                None => self.next_command_without_cards(),
+            },
+            6 | 7 => match self.rng.pick(&card_ids).copied() {
+                Some(card_id) if self.rng.chance(50) => Command::ReorderCard {
+                    card_id,
+                    lane: self.lane(),
+                    index: self.rng.below(6),
+                    updated_by: self.agent(),
+                },
+                Some(card_id) => Command::MoveCard {
+                    card_id,
+                    lane: self.lane(),
+                    order: self.rng.below(20) as f64 / 2.0,
+                    updated_by: self.agent(),
+                },
+                None => self.next_command_without_cards(),
+            },
+            8 => match self.rng.pick(&card_ids).copied() {
+                Some(card_id) if self.rng.chance(40) => {
+                    if state.cards[&card_id].needs_review() {
+                        Command::ResolveCardReview {
+                            card_id,
+                            approved: self.rng.chance(50),
+                            resolved_by: "human".to_string(),
+                        }
+                    } else {
+                        Command::RequestCardReview {
+                            card_id,
+                            reason: self.word(),
+                            requested_by: self.agent(),
+                        }
+                    }
+                }
+                Some(card_id) => Command::DeleteCard {
+                    card_id,
+                    updated_by: self.agent(),
+                },
+                None => self.next_command_without_cards(),
+            },
+            9 => match self.rng.pick(&card_ids).copied() {
+                Some(card_id) if self.rng.chance(50) => Command::SetCardDueDate {
+                    card_id,
+                    due_date: self.rng.chance(80).then(|| {
+                        chrono::DateTime::from_timestamp(
+                            1_700_000_000 + self.rng.below(1_000) as i64 * 86_400,
+                            0,
+                        )
+                        .unwrap()
+                    }),
+                    updated_by: self.agent(),
+                },
+                Some(card_id) if self.rng.chance(30) => {
+                    if state.cards[&card_id].archived {
+                        Command::UnarchiveCard {
+                            card_id,
+                            updated_by: "human".to_string(),
+                        }
+                    } else {
+                        Command::ArchiveCard {
+                            card_id,
+                            updated_by: self.agent(),
+                        }
+                    }
+                }
+                Some(card_id) => Command::SetCardDone {
+                    card_id,
+                    done: self.rng.chance(70),
+                    updated_by: self.agent(),
+                },
+                None => self.next_command_without_cards(),
+            },
+            10 => match self.rng.pick(&card_ids).copied() {
+                Some(card_id) => {
+                    let existing: Vec<Ulid> = state.cards[&card_id]
+                        .attachments
+                        .iter()
+                        .map(|a| a.attachment_id)
+                        .collect();
+                    match self.rng.pick(&existing).copied() {
+                        Some(attachment_id) if self.rng.chance(40) => {
+                            Command::RemoveCardAttachment {
+                                card_id,
+                                attachment_id,
+                                updated_by: self.agent(),
+                            }
+                        }
+                        _ => Command::AddCardAttachment {
+                            card_id,
+                            target: format!("https://example.com/{}", self.word()),
+                            label: self.word(),
+                            updated_by: self.agent(),
+                        },
+                    }
+                }
+                None => self.next_command_without_cards(),
+            },
+            11 => match self.rng.below(4) {
+                0 => Command::RenameLane {
+                    from: self.lane(),
+                    to: self.lane(),
+                    updated_by: self.agent(),
+                },
+                1 => Command::CreateLane {
+                    name: self.lane(),
+                    updated_by: self.agent(),
+                },
+                2 => Command::DeleteLane {
+                    name: self.lane(),
+                    move_cards_to: self.rng.chance(50).then(|| self.lane()),
+                    updated_by: self.agent(),
+                },
+                _ => {
+                    let mut order = state.lanes.clone();
+                    for i in (1..order.len()).rev() {
+                        order.swap(i, self.rng.below(i + 1));
+                    }
+                    Command::ReorderLanes {
+                        order,
+                        updated_by: self.agent(),
+                    }
+                }
+            },
+            12 if self.rng.chance(30) => match self.rng.pick(&link_ids).copied() {
+                Some(link_id) if self.rng.chance(50) => Command::RemoveExternalLink { link_id },
+                _ => Command::AddExternalLink {
+                    url: format!("https://issues.example.com/{}", self.word()),
+                    label: self.word(),
+                },
+            },
+            12 => Command::UpdateSpecCore {
+                title: self.maybe_text(),
+                one_liner: None,
+                goal: self.maybe_text(),
+                description: self.maybe_text(),
+                constraints: None,
+                success_criteria: self.maybe_text(),
+                risks: None,
+                notes: self.maybe_text(),
+            },
+            13 | 14 => Command::AppendTranscript {
+                sender: self.agent(),
+                content: format!("{} {}", self.word(), self.word()),
+                display_name: None,
+            },
+            15 => match state.pending_questions.first() {
+                Some(question) => Command::AnswerQuestion {
+                    question_id: question.question_id(),
+                    answer: if self.rng.chance(50) { "Yes" } else { "No" }.to_string(),
+                },
+                None => Command::AskQuestion {
+                    question: UserQuestion::Boolean {
+                        question_id: Ulid::new(),
+                        question: format!("Keep {}?", self.word()),
+                        default: None,
+                    },
+                },
+            },
+            16 => Command::TransitionPhase {
+                target: match self.rng.below(3) {
+                    0 => SpecPhase::Brainstorming,
+                    1 => SpecPhase::Refining,
+                    _ => SpecPhase::Complete,
+                },
+            },
+            17 => Command::UpdateCanvas {
+                content: format!("digraph {{ {} }}", self.word()),
+            },
+            18 => match self.rng.pick(&attachment_ids).copied() {
+                Some(attachment_id) => match self.rng.below(4) {
+                    0 => Command::SummarizeContext {
+                        attachment_id,
+                        summary: self.word(),
+                    },
+                    1 => Command::UpdateContextNotes {
+                        attachment_id,
+                        notes: self.word(),
+                    },
+                    2 => Command::MarkContextSummarizeFailed {
+                        attachment_id,
+                        reason: "timeout".to_string(),
+                    },
+                    _ => Command::RemoveContext { attachment_id },
+                },
+                None => Command::AttachContext {
+                    attachment_id: Ulid::new(),
+                    filename: format!("{}.md", self.word()),
+                    mime_type: "text/markdown".to_string(),
+                    size_bytes: self.rng.below(10_000) as u64,
+                },
+            },
+            19 => match self.rng.pick(&card_ids).copied() {
+                Some(card_id) => Command::ProposeAction {
+                    command: Box::new(Command::DeleteCard {
+                        card_id,
+                        updated_by: "critic-01".to_string(),
+                    }),
+                    proposed_by: "critic-01".to_string(),
+                    reason: "duplicate".to_string(),
+                },
+                None => self.next_command_without_cards(),
+            },
+            20 => match self.rng.pick(&proposal_ids).copied() {
+                Some(proposal_id) if self.rng.chance(50) => {
+                    Command::ApproveProposal { proposal_id }
+                }
+                Some(proposal_id) => Command::RejectProposal { proposal_id },
+                None => Command::Undo,
+            },
+            21 => match self.rng.pick(&checklist_ids).copied() {
+                Some(item_id) if self.rng.chance(70) => Command::ToggleChecklistItem { item_id },
+                Some(item_id) if self.rng.chance(50) => Command::RemoveChecklistItem { item_id },
+                _ => Command::AddChecklistItem {
+                    text: format!("{} verified", self.word()),
+                },
+            },
+            22 if self.rng.chance(30) => Command::SetAgentRoster {
+                roles: ["manager", "planner", "critic"]
+                    .into_iter()
+                    .filter(|_| self.rng.chance(70))
+                    .chain(["brainstormer"])
+                    .map(String::from)
+                    .collect(),
+            },
+            22 => Command::SetSpecArchived {
+                archived: self.rng.chance(50),
+            },
+            23 if self.rng.chance(50) => Command::RecordUsage {
+                role: self.rng.pick(&["manager", "planner"]).unwrap().to_string(),
+                model: format!("model-{}", self.rng.below(3)),
+                input_tokens: self.rng.below(5_000) as u64,
+                output_tokens: self.rng.below(500) as u64,
+            },
+            23 => Command::PinAgentModel {
+                provider: "anthropic".to_string(),
+                model: format!("model-{}", self.rng.below(3)),
+            },
+            24 => {
+                let agent_id = self.agent();
+                if self.rng.chance(50) {
+                    Command::StartAgentStep {
+                        agent_id,
+                        description: self.word(),
+                    }
+                } else {
+                    Command::FinishAgentStep {
+                        agent_id,
+                        diff_summary: self.word(),
+                    }
+                }
+            }
+            25 => Command::StreamDelta {
+                agent_id: self.agent(),
+                text: self.word(),
+            },
+            26 => self.merge_command(state),
+            _ if self.rng.chance(40) => Command::Redo,
+            _ => Command::Undo,
+        }
+    }
+
+    /// Fallback for card-targeting picks when the spec has no cards yet.
+    fn next_command_without_cards(&mut self) -> Command {
+        Command::CreateCard {
+            card_type: "idea".to_string(),
+            title: self.word(),
+            body: None,
+            lane: None,
+            created_by: self.agent(),
+            source_attachment_id: None,
+        }
+    }
+
+    /// Merge a copy of up to three of the spec's own cards back in, as if
+    /// they came from another spec.
+    fn merge_command(&mut self, state: &SpecState) -> Command {
+        let cards: Vec<Card> = state
+            .cards
+            .values()
+            .filter(|_| self.rng.chance(30))
+            .take(3)
+            .cloned()
+            .collect();
+        Command::MergeFromSpec {
+            source_spec_id: Ulid::new(),
+            source_title: self.word(),
+            cards,
+            lanes: vec![self.lane()],
+            transcript: vec![TranscriptMessage::new(self.agent(), self.word())],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+        let first: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        let second: Vec<u64> = (0..8).map(|_| b.next_u64()).collect();
+        assert_eq!(first, second);
+        assert_ne!(SeededRng::new(43).next_u64(), first[0]);
+    }
+
+    #[test]
+    fn fuzzer_creates_the_spec_first() {
+        let mut fuzzer = CommandFuzzer::new(7);
+        assert!(matches!(
+            fuzzer.next_command(&SpecState::new()),
+            Command::CreateSpec { .. }
+        ));
+    }
+}
@@ -5,6 +5,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
+use crate::state::AgentModel;
+
 /// Classifies how a transcript message should be displayed.
 /// Chat messages render as full bubbles; step variants render as compact status lines.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -32,7 +34,7 @@ impl MessageKind {
 }
 
 /// A single message in the conversation transcript between humans, agents, and the system.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TranscriptMessage {
     pub message_id: Ulid,
     pub sender: String,
@@ -40,6 +42,14 @@ pub struct TranscriptMessage {
     #[serde(default)]
     pub kind: MessageKind,
     pub timestamp: DateTime<Utc>,
+    /// Provider/model that produced this message, for agent messages sent
+    /// while a model was pinned. None for humans, the system, and old logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_model: Option<AgentModel>,
+    /// Friendly name the sending agent was configured with (e.g. "Ada"),
+    /// shown in place of the role label. None when no persona was set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
 }
 
 impl TranscriptMessage {
@@ -51,13 +61,20 @@ impl TranscriptMessage {
             content,
             kind: MessageKind::Chat,
             timestamp: Utc::now(),
+            agent_model: None,
+            display_name: None,
         }
     }
+
+    /// Whether `sender` is an agent rather than the human or the system.
+    pub fn is_agent_sender(sender: &str) -> bool {
+        !matches!(sender, "human" | "system")
+    }
 }
 
 /// A structured question that an agent can pose to a human, supporting multiple
 /// interaction patterns.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum UserQuestion {
     Boolean {
@@ -77,6 +94,25 @@ pub enum UserQuestion {
         placeholder: Option<String>,
         validation_hint: Option<String>,
     },
+    /// Human sign-off on a gated agent action held as `proposal_id`.
+    /// Answering "Approve" applies the proposal; anything else declines it.
+    ReviewGate {
+        question_id: Ulid,
+        question: String,
+        proposal_id: Ulid,
+    },
+}
+
+impl UserQuestion {
+    /// The ID the answer must carry, whatever the question kind.
+    pub fn question_id(&self) -> Ulid {
+        match self {
+            UserQuestion::Boolean { question_id, .. }
+            | UserQuestion::MultipleChoice { question_id, .. }
+            | UserQuestion::Freeform { question_id, .. }
+            | UserQuestion::ReviewGate { question_id, .. } => *question_id,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -190,6 +226,8 @@ mod tests {
             content: "Reasoning about goals".to_string(),
             kind: MessageKind::StepStarted,
             timestamp: Utc::now(),
+            agent_model: None,
+            display_name: None,
         };
         let json = serde_json::to_string(&msg).expect("serialize");
         let deser: TranscriptMessage = serde_json::from_str(&json).expect("deserialize");
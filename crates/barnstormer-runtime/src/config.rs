@@ -4,7 +4,12 @@
 use std::net::SocketAddr;
 use std::path::PathBuf;
 
+use barnstormer_server::{BarnstormerConfig, PersisterRetry, PriceTable, ProviderStatus};
+
 /// Startup options provided by a frontend before defaults are resolved.
+/// Settings that `barnstormer.toml` and its environment variables cover are
+/// read by [`BarnstormerConfig`] and carried here; they are never looked up
+/// in the environment again.
 #[derive(Debug, Clone, Default)]
 pub struct RuntimeOptions {
     pub home: Option<PathBuf>,
@@ -12,11 +17,35 @@ pub struct RuntimeOptions {
     pub auth_token: Option<String>,
     pub static_dir: Option<PathBuf>,
     pub open_browser: bool,
-    /// When true, do not consult the `BARNSTORMER_AUTH_TOKEN` env var if
-    /// `auth_token` is `None`. Frontends that embed a loopback-only server
-    /// (e.g. the Tauri desktop shell) set this so a user's shell env cannot
-    /// silently flip on bearer auth and break in-process API calls.
-    pub disable_auth_fallback: bool,
+    /// Default LLM provider and model. `None` means `anthropic` and the
+    /// provider's default model.
+    pub default_provider: Option<String>,
+    pub default_model: Option<String>,
+    /// Swarm roster labels for specs without their own roster. Empty means
+    /// the built-in roster.
+    pub agent_roles: Vec<String>,
+    /// Start agents on newly created specs. `None` means on.
+    pub auto_start_agents: Option<bool>,
+    /// Filename stem for export downloads. `None` means the default.
+    pub export_filename_pattern: Option<String>,
+}
+
+impl RuntimeOptions {
+    /// Options carrying every setting `config` resolved. Frontend-only
+    /// fields (`static_dir`, `open_browser`) are left at their defaults.
+    pub fn from_config(config: &BarnstormerConfig) -> Self {
+        Self {
+            home: Some(config.home.clone()),
+            bind: Some(config.bind),
+            auth_token: config.auth_token.clone(),
+            default_provider: Some(config.default_provider.clone()),
+            default_model: config.default_model.clone(),
+            agent_roles: config.agent_roles.clone(),
+            auto_start_agents: Some(config.auto_start_agents),
+            export_filename_pattern: Some(config.export_filename_pattern.clone()),
+            ..Self::default()
+        }
+    }
 }
 
 /// Concrete runtime configuration after resolving defaults.
@@ -27,6 +56,35 @@ pub struct RuntimeConfig {
     pub auth_token: Option<String>,
     pub static_dir: PathBuf,
     pub open_browser: bool,
+    /// Provider agents use unless a spec overrides it (default `anthropic`).
+    pub default_provider: String,
+    pub default_model: Option<String>,
+    /// Roster labels for specs without their own; empty means the built-in
+    /// roster.
+    pub agent_roles: Vec<String>,
+    /// Start agents on newly created specs. On by default; off leaves new
+    /// specs idle until agents are started explicitly.
+    pub auto_start_agents: bool,
+    /// Filename stem for export downloads (default `{slug}-{short_id}`).
+    pub export_filename_pattern: String,
+    /// CSP `frame-ancestors` sources for `/embed/*` pages, from
+    /// `BARNSTORMER_EMBED_FRAME_ANCESTORS` (default `'self'`).
+    pub embed_frame_ancestors: String,
+    /// Per-model prices for the agent cost estimate: the built-in table with
+    /// `BARNSTORMER_MODEL_PRICES` overrides applied.
+    pub model_prices: PriceTable,
+    /// How event persisters retry opening a spec's log, from
+    /// `BARNSTORMER_PERSIST_OPEN_ATTEMPTS` (default 5) and
+    /// `BARNSTORMER_PERSIST_RETRY_DELAY_MS` (default 1000).
+    pub persister_retry: PersisterRetry,
+    /// How often each spec is snapshotted in the background, from
+    /// `BARNSTORMER_SNAPSHOT_INTERVAL_SECS`. Off (None) when unset or 0.
+    pub snapshot_interval: Option<std::time::Duration>,
+    /// Agent steps a swarm may run before pausing itself, from
+    /// `BARNSTORMER_MAX_STEPS_PER_SESSION`. No limit (None) when unset or 0.
+    pub max_steps_per_session: Option<u32>,
+    /// Snapshots kept per spec, from `BARNSTORMER_SNAPSHOT_KEEP` (default 5).
+    pub snapshot_keep: usize,
 }
 
 impl RuntimeConfig {
@@ -35,19 +93,96 @@ impl RuntimeConfig {
         let bind = options
             .bind
             .unwrap_or_else(|| "127.0.0.1:7331".parse().expect("valid default bind"));
-        let auth_token = options
-            .auth_token
-            .or_else(|| {
-                if options.disable_auth_fallback {
-                    None
-                } else {
-                    std::env::var("BARNSTORMER_AUTH_TOKEN").ok()
-                }
-            })
-            .filter(|token| !token.is_empty());
+        let auth_token = options.auth_token.filter(|token| !token.is_empty());
         let static_dir = options
             .static_dir
             .unwrap_or_else(|| PathBuf::from("static"));
+        let default_provider = options
+            .default_provider
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| "anthropic".to_string());
+        let default_model = options.default_model.filter(|m| !m.is_empty());
+        let agent_roles = options
+            .agent_roles
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .collect();
+        let auto_start_agents = options.auto_start_agents.unwrap_or(true);
+        let export_filename_pattern = options
+            .export_filename_pattern
+            .filter(|p| !p.trim().is_empty())
+            .unwrap_or_else(|| barnstormer_server::DEFAULT_EXPORT_FILENAME_PATTERN.to_string());
+        let embed_frame_ancestors = std::env::var("BARNSTORMER_EMBED_FRAME_ANCESTORS")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| barnstormer_server::DEFAULT_EMBED_FRAME_ANCESTORS.to_string());
+        let model_prices = match std::env::var("BARNSTORMER_MODEL_PRICES") {
+            Ok(overrides) => PriceTable::with_overrides(&overrides)
+                .map_err(|err| anyhow::anyhow!("BARNSTORMER_MODEL_PRICES: {}", err))?,
+            Err(_) => PriceTable::default(),
+        };
+        let mut persister_retry = PersisterRetry::default();
+        if let Ok(value) = std::env::var("BARNSTORMER_PERSIST_OPEN_ATTEMPTS") {
+            persister_retry.attempts = value
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|attempts| *attempts > 0)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "BARNSTORMER_PERSIST_OPEN_ATTEMPTS: expected a positive integer, got {:?}",
+                        value
+                    )
+                })?;
+        }
+        if let Ok(value) = std::env::var("BARNSTORMER_PERSIST_RETRY_DELAY_MS") {
+            let millis = value.trim().parse::<u64>().map_err(|_| {
+                anyhow::anyhow!(
+                    "BARNSTORMER_PERSIST_RETRY_DELAY_MS: expected milliseconds, got {:?}",
+                    value
+                )
+            })?;
+            persister_retry.delay = std::time::Duration::from_millis(millis);
+        }
+        let snapshot_interval = match std::env::var("BARNSTORMER_SNAPSHOT_INTERVAL_SECS") {
+            Ok(value) => {
+                let secs = value.trim().parse::<u64>().map_err(|_| {
+                    anyhow::anyhow!(
+                        "BARNSTORMER_SNAPSHOT_INTERVAL_SECS: expected seconds, got {:?}",
+                        value
+                    )
+                })?;
+                (secs > 0).then(|| std::time::Duration::from_secs(secs))
+            }
+            Err(_) => None,
+        };
+        let max_steps_per_session = match std::env::var("BARNSTORMER_MAX_STEPS_PER_SESSION") {
+            Ok(value) => {
+                let steps = value.trim().parse::<u32>().map_err(|_| {
+                    anyhow::anyhow!(
+                        "BARNSTORMER_MAX_STEPS_PER_SESSION: expected a step count, got {:?}",
+                        value
+                    )
+                })?;
+                (steps > 0).then_some(steps)
+            }
+            Err(_) => None,
+        };
+        let snapshot_keep = match std::env::var("BARNSTORMER_SNAPSHOT_KEEP") {
+            Ok(value) => value
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|keep| *keep > 0)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "BARNSTORMER_SNAPSHOT_KEEP: expected a positive integer, got {:?}",
+                        value
+                    )
+                })?,
+            Err(_) => barnstormer_store::DEFAULT_SNAPSHOT_KEEP,
+        };
 
         Ok(Self {
             home,
@@ -55,10 +190,96 @@ impl RuntimeConfig {
             auth_token,
             static_dir,
             open_browser: options.open_browser,
+            default_provider,
+            default_model,
+            agent_roles,
+            auto_start_agents,
+            export_filename_pattern,
+            embed_frame_ancestors,
+            model_prices,
+            persister_retry,
+            snapshot_interval,
+            max_steps_per_session,
+            snapshot_keep,
         })
     }
 }
 
+impl RuntimeConfig {
+    /// Render the effective configuration as `key = value` lines, for
+    /// `barnstormer start --print-config`. The auth token and provider API
+    /// keys are never printed, only whether they are set.
+    pub fn describe(&self, providers: &ProviderStatus) -> String {
+        let mut lines = vec![
+            format!("home = {}", self.home.display()),
+            format!("bind = {}", self.bind),
+            format!(
+                "auth_token = {}",
+                if self.auth_token.is_some() {
+                    "<redacted>"
+                } else {
+                    "<unset>"
+                }
+            ),
+            format!("static_dir = {}", self.static_dir.display()),
+            format!("open_browser = {}", self.open_browser),
+            format!(
+                "agent_roles = {}",
+                if self.agent_roles.is_empty() {
+                    "<default>".to_string()
+                } else {
+                    self.agent_roles.join(",")
+                }
+            ),
+            format!("auto_start_agents = {}", self.auto_start_agents),
+            format!("export_filename_pattern = {}", self.export_filename_pattern),
+            format!("embed_frame_ancestors = {}", self.embed_frame_ancestors),
+            format!("persist_open_attempts = {}", self.persister_retry.attempts),
+            format!(
+                "persist_retry_delay_ms = {}",
+                self.persister_retry.delay.as_millis()
+            ),
+            format!(
+                "snapshot_interval_secs = {}",
+                self.snapshot_interval
+                    .map(|d| d.as_secs().to_string())
+                    .unwrap_or_else(|| "off".to_string())
+            ),
+            format!(
+                "max_steps_per_session = {}",
+                self.max_steps_per_session
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| "unlimited".to_string())
+            ),
+            format!("snapshot_keep = {}", self.snapshot_keep),
+            format!("default_provider = {}", providers.default_provider),
+            format!(
+                "default_model = {}",
+                providers
+                    .default_model
+                    .as_deref()
+                    .unwrap_or("<provider default>")
+            ),
+        ];
+        for provider in &providers.providers {
+            lines.push(format!(
+                "{}.api_key = {}",
+                provider.name,
+                if provider.has_api_key {
+                    "<redacted>"
+                } else {
+                    "<unset>"
+                }
+            ));
+            lines.push(format!("{}.model = {}", provider.name, provider.model));
+            if let Some(base_url) = &provider.base_url {
+                lines.push(format!("{}.base_url = {}", provider.name, base_url));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
 fn default_home() -> PathBuf {
     std::env::var("BARNSTORMER_HOME")
         .map(PathBuf::from)
@@ -78,7 +299,7 @@ mod tests {
 
     // Tests that mutate process env must serialize against each other. cargo
     // test parallelizes by default and a second env-mutating test would race
-    // with `disable_auth_fallback_skips_env_var`.
+    // with `config_file_settings_are_not_read_from_env`.
     static ENV_LOCK: Mutex<()> = Mutex::new(());
 
     #[test]
@@ -89,7 +310,11 @@ mod tests {
             auth_token: None,
             static_dir: None,
             open_browser: false,
-            disable_auth_fallback: false,
+            default_provider: None,
+            default_model: None,
+            agent_roles: Vec::new(),
+            auto_start_agents: None,
+            export_filename_pattern: None,
         })
         .unwrap();
 
@@ -104,7 +329,11 @@ mod tests {
             auth_token: None,
             static_dir: None,
             open_browser: false,
-            disable_auth_fallback: false,
+            default_provider: None,
+            default_model: None,
+            agent_roles: Vec::new(),
+            auto_start_agents: None,
+            export_filename_pattern: None,
         })
         .unwrap();
 
@@ -113,33 +342,166 @@ mod tests {
     }
 
     #[test]
-    fn disable_auth_fallback_skips_env_var() {
+    fn describe_shows_options_and_redacts_secrets() {
         let _env_guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
 
         // SAFETY: the ENV_LOCK above serializes against any other env-mutating
         // test in this crate; this test restores the prior value before
         // returning.
-        let prior = std::env::var("BARNSTORMER_AUTH_TOKEN").ok();
-        unsafe { std::env::set_var("BARNSTORMER_AUTH_TOKEN", "leaked-token") };
+        let prior_key = std::env::var("OPENAI_API_KEY").ok();
+        unsafe { std::env::set_var("OPENAI_API_KEY", "sk-secret-key") };
 
         let config = RuntimeConfig::from_parts(RuntimeOptions {
             home: Some(PathBuf::from("/tmp/barnstormer-test")),
             bind: None,
-            auth_token: None,
+            auth_token: Some("super-secret-token".to_string()),
             static_dir: None,
             open_browser: false,
-            disable_auth_fallback: true,
+            default_provider: None,
+            default_model: None,
+            agent_roles: Vec::new(),
+            auto_start_agents: None,
+            export_filename_pattern: Some("{slug}-override".to_string()),
         })
         .unwrap();
+        let printed = config.describe(&barnstormer_server::ProviderStatus::detect());
+
+        match prior_key {
+            Some(value) => unsafe { std::env::set_var("OPENAI_API_KEY", value) },
+            None => unsafe { std::env::remove_var("OPENAI_API_KEY") },
+        }
+
+        assert!(printed.contains("export_filename_pattern = {slug}-override"));
+        assert!(printed.contains("home = /tmp/barnstormer-test"));
+        assert!(printed.contains("auth_token = <redacted>"));
+        assert!(printed.contains("openai.api_key = <redacted>"));
+        assert!(!printed.contains("super-secret-token"));
+        assert!(!printed.contains("sk-secret-key"));
+    }
+
+    #[test]
+    fn max_steps_per_session_is_parsed_and_rejects_garbage() {
+        let _env_guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let options = || RuntimeOptions {
+            home: Some(PathBuf::from("/tmp/barnstormer-test")),
+            ..RuntimeOptions::default()
+        };
+
+        // SAFETY: the ENV_LOCK above serializes against any other env-mutating
+        // test in this crate; this test restores the prior value before
+        // returning.
+        let prior = std::env::var("BARNSTORMER_MAX_STEPS_PER_SESSION").ok();
+        unsafe { std::env::set_var("BARNSTORMER_MAX_STEPS_PER_SESSION", "25") };
+        let limited = RuntimeConfig::from_parts(options());
+        unsafe { std::env::set_var("BARNSTORMER_MAX_STEPS_PER_SESSION", "0") };
+        let unlimited = RuntimeConfig::from_parts(options());
+        unsafe { std::env::set_var("BARNSTORMER_MAX_STEPS_PER_SESSION", "lots") };
+        let garbage = RuntimeConfig::from_parts(options());
+        match prior {
+            Some(value) => unsafe { std::env::set_var("BARNSTORMER_MAX_STEPS_PER_SESSION", value) },
+            None => unsafe { std::env::remove_var("BARNSTORMER_MAX_STEPS_PER_SESSION") },
+        }
 
+        let limited = limited.unwrap();
+        assert_eq!(limited.max_steps_per_session, Some(25));
+        let providers = barnstormer_server::ProviderStatus::detect();
+        assert!(
+            limited
+                .describe(&providers)
+                .contains("max_steps_per_session = 25")
+        );
+        assert!(
+            unlimited
+                .unwrap()
+                .describe(&providers)
+                .contains("max_steps_per_session = unlimited")
+        );
+        let err = garbage.unwrap_err().to_string();
+        assert!(err.contains("BARNSTORMER_MAX_STEPS_PER_SESSION"), "{err}");
+    }
+
+    #[test]
+    fn snapshot_keep_is_parsed_and_rejects_zero() {
+        let _env_guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let options = || RuntimeOptions {
+            home: Some(PathBuf::from("/tmp/barnstormer-test")),
+            ..RuntimeOptions::default()
+        };
+
+        // SAFETY: the ENV_LOCK above serializes against any other env-mutating
+        // test in this crate; this test restores the prior value before
+        // returning.
+        let prior = std::env::var("BARNSTORMER_SNAPSHOT_KEEP").ok();
+        unsafe { std::env::remove_var("BARNSTORMER_SNAPSHOT_KEEP") };
+        let default = RuntimeConfig::from_parts(options());
+        unsafe { std::env::set_var("BARNSTORMER_SNAPSHOT_KEEP", " 12 ") };
+        let custom = RuntimeConfig::from_parts(options());
+        unsafe { std::env::set_var("BARNSTORMER_SNAPSHOT_KEEP", "0") };
+        let zero = RuntimeConfig::from_parts(options());
         match prior {
-            Some(value) => unsafe { std::env::set_var("BARNSTORMER_AUTH_TOKEN", value) },
-            None => unsafe { std::env::remove_var("BARNSTORMER_AUTH_TOKEN") },
+            Some(value) => unsafe { std::env::set_var("BARNSTORMER_SNAPSHOT_KEEP", value) },
+            None => unsafe { std::env::remove_var("BARNSTORMER_SNAPSHOT_KEEP") },
         }
 
+        assert_eq!(
+            default.unwrap().snapshot_keep,
+            barnstormer_store::DEFAULT_SNAPSHOT_KEEP
+        );
+        let custom = custom.unwrap();
+        assert_eq!(custom.snapshot_keep, 12);
         assert!(
-            config.auth_token.is_none(),
-            "expected auth disabled when fallback is off"
+            custom
+                .describe(&barnstormer_server::ProviderStatus::detect())
+                .contains("snapshot_keep = 12")
+        );
+        let err = zero.unwrap_err().to_string();
+        assert!(err.contains("BARNSTORMER_SNAPSHOT_KEEP"), "{err}");
+    }
+
+    #[test]
+    fn config_file_settings_are_not_read_from_env() {
+        let _env_guard = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        const KEYS: &[(&str, &str)] = &[
+            ("BARNSTORMER_AUTH_TOKEN", "leaked-token"),
+            ("BARNSTORMER_DEFAULT_PROVIDER", "openai"),
+            ("BARNSTORMER_DEFAULT_MODEL", "gpt-4o"),
+            ("BARNSTORMER_AGENT_ROLES", "planner"),
+            ("BARNSTORMER_AUTO_START_AGENTS", "false"),
+            ("BARNSTORMER_EXPORT_FILENAME_PATTERN", "{id}"),
+        ];
+
+        // SAFETY: the ENV_LOCK above serializes against any other env-mutating
+        // test in this crate; this test restores the prior values before
+        // returning.
+        let prior: Vec<_> = KEYS
+            .iter()
+            .map(|(key, _)| (*key, std::env::var(key).ok()))
+            .collect();
+        for (key, value) in KEYS {
+            unsafe { std::env::set_var(key, value) };
+        }
+
+        let config = RuntimeConfig::from_parts(RuntimeOptions {
+            home: Some(PathBuf::from("/tmp/barnstormer-test")),
+            ..RuntimeOptions::default()
+        })
+        .unwrap();
+
+        for (key, value) in prior {
+            match value {
+                Some(value) => unsafe { std::env::set_var(key, value) },
+                None => unsafe { std::env::remove_var(key) },
+            }
+        }
+
+        assert!(config.auth_token.is_none());
+        assert_eq!(config.default_provider, "anthropic");
+        assert!(config.default_model.is_none());
+        assert!(config.agent_roles.is_empty());
+        assert!(config.auto_start_agents);
+        assert_eq!(
+            config.export_filename_pattern,
+            barnstormer_server::DEFAULT_EXPORT_FILENAME_PATTERN
         );
     }
 }
@@ -2,13 +2,22 @@
 // ABOUTME: Launches the Axum app on loopback, reports the local URL, and supports graceful shutdown.
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use barnstormer_server::shutdown::{
+    DEFAULT_SHUTDOWN_PERSIST_TIMEOUT, DEFAULT_SHUTDOWN_SNAPSHOT_TIMEOUT,
+    DEFAULT_SHUTDOWN_STEP_TIMEOUT, close_event_persisters, pause_all_swarms, snapshot_all_specs,
+};
 use barnstormer_server::{AppState, ProviderStatus, create_router_with_static_dir};
-use barnstormer_store::StorageManager;
-use tokio::sync::oneshot;
+use barnstormer_store::{RecoveryRetry, StorageManager};
+use tokio::sync::{Notify, oneshot};
 
 use crate::{RuntimeConfig, RuntimeOptions};
 
+/// How long open connections (SSE streams never finish on their own) get to
+/// close after shutdown starts before the server stops without them.
+const CONNECTION_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub struct ServerHandle {
     local_url: String,
     shutdown_tx: Option<oneshot::Sender<()>>,
@@ -34,6 +43,47 @@ impl ServerHandle {
 
         self.wait().await
     }
+
+    /// Run until the server stops on its own or the process gets SIGINT or
+    /// SIGTERM, in which case it shuts down gracefully.
+    pub async fn wait_for_shutdown_signal(mut self) -> anyhow::Result<()> {
+        tokio::select! {
+            result = &mut self.join_handle => return result?,
+            () = shutdown_signal() => {}
+        }
+        tracing::info!("shutdown signal received, stopping barnstormer");
+        self.shutdown().await
+    }
+}
+
+/// Resolves on Ctrl-C (SIGINT) or, on Unix, SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!("failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!("failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        () = ctrl_c => {}
+        () = terminate => {}
+    }
 }
 
 pub async fn launch(options: RuntimeOptions) -> anyhow::Result<ServerHandle> {
@@ -42,7 +92,7 @@ pub async fn launch(options: RuntimeOptions) -> anyhow::Result<ServerHandle> {
 
     let state = build_state(&runtime_config).await?;
     let app = create_router_with_static_dir(
-        state,
+        Arc::clone(&state),
         runtime_config.auth_token.clone(),
         runtime_config.static_dir.clone(),
     );
@@ -54,12 +104,47 @@ pub async fn launch(options: RuntimeOptions) -> anyhow::Result<ServerHandle> {
     tracing::info!("barnstormer listening on {}", local_url);
 
     let join_handle = tokio::spawn(async move {
-        axum::serve(listener, app)
-            .with_graceful_shutdown(async move {
-                let _ = shutdown_rx.await;
-            })
-            .await
-            .map_err(anyhow::Error::from)
+        let stopping = Arc::new(Notify::new());
+        let stop_signal = Arc::clone(&stopping);
+        let serve = axum::serve(listener, app).with_graceful_shutdown(async move {
+            let _ = shutdown_rx.await;
+            stop_signal.notify_one();
+        });
+        let drain_deadline = async {
+            stopping.notified().await;
+            tokio::time::sleep(CONNECTION_DRAIN_TIMEOUT).await;
+        };
+        tokio::select! {
+            result = serve => result?,
+            () = drain_deadline => {
+                tracing::warn!(
+                    "connections still open after {:?}; stopping without them",
+                    CONNECTION_DRAIN_TIMEOUT
+                );
+            }
+        }
+
+        // No new requests arrive now; let agents finish their current step
+        // so the snapshot below captures it.
+        let busy = pause_all_swarms(&state, DEFAULT_SHUTDOWN_STEP_TIMEOUT).await;
+        if !busy.is_empty() {
+            tracing::warn!("{} specs still had agent steps running", busy.len());
+        }
+
+        let unfinished = close_event_persisters(&state, DEFAULT_SHUTDOWN_PERSIST_TIMEOUT).await;
+        if !unfinished.is_empty() {
+            tracing::warn!(
+                "{} specs still had events to persist at shutdown",
+                unfinished.len()
+            );
+        }
+
+        let missed = snapshot_all_specs(&state, DEFAULT_SHUTDOWN_SNAPSHOT_TIMEOUT).await;
+        if !missed.is_empty() {
+            tracing::warn!("{} specs were not snapshotted on shutdown", missed.len());
+        }
+
+        Ok(())
     });
 
     Ok(ServerHandle {
@@ -70,15 +155,39 @@ pub async fn launch(options: RuntimeOptions) -> anyhow::Result<ServerHandle> {
 }
 
 async fn build_state(runtime_config: &RuntimeConfig) -> anyhow::Result<Arc<AppState>> {
-    let storage = StorageManager::new(runtime_config.home.clone())?;
-    let recovered_specs = storage.recover_all_specs()?;
+    // Replay is file IO and sleeps between retries, so it runs on the
+    // blocking pool rather than stalling a runtime worker.
+    let home = runtime_config.home.clone();
+    let recovered_specs = tokio::task::spawn_blocking(move || {
+        StorageManager::new(home)?.recover_all_specs_with_retry(
+            RecoveryRetry::default(),
+            |spec_id, processed, total| {
+                tracing::info!("spec {}: recovered {}/{} events", spec_id, processed, total);
+            },
+        )
+    })
+    .await??;
 
     tracing::info!("recovered {} specs", recovered_specs.len());
 
-    let state = Arc::new(AppState::new(
-        runtime_config.home.clone(),
-        ProviderStatus::detect(),
-    ));
+    let provider_status = ProviderStatus::detect_with_defaults(
+        &runtime_config.default_provider,
+        runtime_config.default_model.as_deref(),
+    );
+    if let Some(warning) = &provider_status.model_warning {
+        tracing::warn!("{}", warning);
+    }
+    let mut app_state = AppState::new(runtime_config.home.clone(), provider_status);
+    app_state.agent_roles = barnstormer_server::roles_or_default(&runtime_config.agent_roles);
+    app_state.auto_start_agents = runtime_config.auto_start_agents;
+    app_state.export_filename_pattern = runtime_config.export_filename_pattern.clone();
+    app_state.embed_frame_ancestors = runtime_config.embed_frame_ancestors.clone();
+    app_state.model_prices = runtime_config.model_prices.clone();
+    app_state.persister_retry = runtime_config.persister_retry;
+    app_state.snapshot_interval = runtime_config.snapshot_interval;
+    app_state.max_steps_per_session = runtime_config.max_steps_per_session;
+    app_state.snapshot_keep = runtime_config.snapshot_keep;
+    let state = Arc::new(app_state);
 
     {
         let mut actors = state.actors.write().await;
@@ -89,6 +198,12 @@ async fn build_state(runtime_config: &RuntimeConfig) -> anyhow::Result<Arc<AppSt
                 &handle,
                 spec_id,
                 &runtime_config.home,
+                Arc::clone(&state.swarms),
+                state.persister_retry,
+                state.persistence_health.clone(),
+                Arc::clone(&state.metrics),
+                state.snapshot_interval,
+                state.snapshot_keep,
             );
             persisters.insert(spec_id, persister);
             actors.insert(spec_id, handle);
@@ -12,7 +12,11 @@ async fn embedded_server_reports_url_and_shuts_down() {
         auth_token: None,
         static_dir: None,
         open_browser: false,
-        disable_auth_fallback: true,
+        default_provider: None,
+        default_model: None,
+        agent_roles: Vec::new(),
+        auto_start_agents: None,
+        export_filename_pattern: None,
     })
     .await
     .unwrap();
@@ -0,0 +1,151 @@
+// ABOUTME: Card template library API handlers: list, create, read, update, and delete.
+// ABOUTME: Templates are home-wide (not per spec) and persisted through barnstormer-store.
+
+use std::collections::BTreeMap;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use barnstormer_store::{CardTemplateError, CardTemplateFields};
+use ulid::Ulid;
+
+use crate::app_state::SharedState;
+
+/// Longest accepted template name, in characters.
+const MAX_NAME_CHARS: usize = 100;
+
+/// Validate and normalize template fields. Returns errors keyed by field
+/// name; on success the name, type, and title are trimmed and a blank body
+/// becomes None.
+fn validate_fields(
+    mut fields: CardTemplateFields,
+) -> Result<CardTemplateFields, BTreeMap<String, String>> {
+    let mut errors = BTreeMap::new();
+    fields.name = fields.name.trim().to_string();
+    fields.card_type = fields.card_type.trim().to_string();
+    fields.title = fields.title.trim().to_string();
+    fields.body = fields.body.filter(|b| !b.trim().is_empty());
+
+    if fields.name.is_empty() {
+        errors.insert("name".to_string(), "name is required".to_string());
+    } else if fields.name.chars().count() > MAX_NAME_CHARS {
+        errors.insert(
+            "name".to_string(),
+            format!("name must be at most {} characters", MAX_NAME_CHARS),
+        );
+    }
+    if fields.card_type.is_empty() {
+        errors.insert("card_type".to_string(), "card_type is required".to_string());
+    }
+
+    if errors.is_empty() {
+        Ok(fields)
+    } else {
+        Err(errors)
+    }
+}
+
+fn parse_template_id(id: &str) -> Result<Ulid, Box<Response>> {
+    id.parse::<Ulid>().map_err(|_| {
+        Box::new(
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid template id" })),
+            )
+                .into_response(),
+        )
+    })
+}
+
+fn invalid_fields(errors: BTreeMap<String, String>) -> Response {
+    (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        Json(serde_json::json!({ "error": "invalid template fields", "errors": errors })),
+    )
+        .into_response()
+}
+
+fn store_error(e: CardTemplateError) -> Response {
+    let status = match e {
+        CardTemplateError::NotFound(_) => StatusCode::NOT_FOUND,
+        CardTemplateError::DuplicateName(_) => StatusCode::CONFLICT,
+        CardTemplateError::Io(_) | CardTemplateError::Json(_) => {
+            tracing::error!("card template storage error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    };
+    (status, Json(serde_json::json!({ "error": e.to_string() }))).into_response()
+}
+
+/// GET /api/card-templates - List all card templates, sorted by name.
+pub async fn list_templates(State(state): State<SharedState>) -> Response {
+    match state.card_templates.lock().await.list() {
+        Ok(templates) => Json(templates).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+/// POST /api/card-templates - Create a card template.
+pub async fn create_template(
+    State(state): State<SharedState>,
+    Json(fields): Json<CardTemplateFields>,
+) -> Response {
+    let fields = match validate_fields(fields) {
+        Ok(f) => f,
+        Err(errors) => return invalid_fields(errors),
+    };
+    match state.card_templates.lock().await.create(fields) {
+        Ok(template) => (StatusCode::CREATED, Json(template)).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+/// GET /api/card-templates/{template_id} - Read one card template.
+pub async fn get_template(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+    let template_id = match parse_template_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    match state.card_templates.lock().await.get(template_id) {
+        Ok(template) => Json(template).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+/// PUT /api/card-templates/{template_id} - Replace a card template's fields.
+pub async fn update_template(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Json(fields): Json<CardTemplateFields>,
+) -> Response {
+    let template_id = match parse_template_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let fields = match validate_fields(fields) {
+        Ok(f) => f,
+        Err(errors) => return invalid_fields(errors),
+    };
+    match state
+        .card_templates
+        .lock()
+        .await
+        .update(template_id, fields)
+    {
+        Ok(template) => Json(template).into_response(),
+        Err(e) => store_error(e),
+    }
+}
+
+/// DELETE /api/card-templates/{template_id} - Delete a card template.
+pub async fn delete_template(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+    let template_id = match parse_template_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    match state.card_templates.lock().await.delete(template_id) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => store_error(e),
+    }
+}
@@ -1,11 +1,14 @@
 // ABOUTME: Command submission and undo API handlers for spec mutation.
 // ABOUTME: Routes commands to spec actors and returns results. Persistence is handled by background broadcast subscribers.
 
+use std::collections::HashSet;
+
 use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use barnstormer_core::Command;
+use barnstormer_core::{Command, EventPayload};
+use serde::Deserialize;
 use ulid::Ulid;
 
 use crate::app_state::SharedState;
@@ -60,6 +63,200 @@ pub async fn submit_command(
         .into_response()
 }
 
+/// Query parameters for the batch command endpoint.
+#[derive(Debug, Deserialize)]
+pub struct BatchQuery {
+    /// Stop at the first failure and undo every command already applied.
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+/// POST /api/specs/{id}/commands/batch - Apply a JSON array of commands in
+/// order and report one result per command. Without `atomic`, a failing
+/// command does not stop the rest. With `?atomic=true`, the batch stops at
+/// the first failure and its applied commands are undone; commands that
+/// record no undo entry (transcript messages, questions) stay applied.
+pub async fn submit_batch(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<BatchQuery>,
+    Json(commands): Json<Vec<Command>>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "spec not found" })),
+            )
+                .into_response();
+        }
+    };
+
+    let mut results = Vec::with_capacity(commands.len());
+    let mut applied_event_ids = HashSet::new();
+    let mut failed = false;
+    for cmd in commands {
+        if failed && query.atomic {
+            results.push(serde_json::json!({ "ok": false, "skipped": true }));
+            continue;
+        }
+        match handle.send_command(cmd).await {
+            Ok(events) => {
+                applied_event_ids.extend(events.iter().map(|e| e.event_id));
+                results.push(serde_json::json!({ "ok": true, "events": events }));
+            }
+            Err(e) => {
+                failed = true;
+                results.push(serde_json::json!({ "ok": false, "error": format!("{}", e) }));
+            }
+        }
+    }
+
+    if !(failed && query.atomic) {
+        return (
+            StatusCode::OK,
+            Json(serde_json::json!({ "results": results, "rolled_back": false })),
+        )
+            .into_response();
+    }
+
+    // Undo only while the newest undo entry belongs to this batch, so a
+    // change made concurrently by someone else is never reverted.
+    loop {
+        let top = handle
+            .read_state()
+            .await
+            .undo_stack
+            .last()
+            .map(|entry| entry.event_id);
+        match top {
+            Some(event_id) if applied_event_ids.contains(&event_id) => {
+                if let Err(e) = handle.send_command(Command::Undo).await {
+                    tracing::warn!("batch rollback on spec {} stopped: {}", spec_id, e);
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    let rolled_back = !handle
+        .read_state()
+        .await
+        .undo_stack
+        .iter()
+        .any(|entry| applied_event_ids.contains(&entry.event_id));
+
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({ "results": results, "rolled_back": rolled_back })),
+    )
+        .into_response()
+}
+
+/// One card in a bulk create request.
+#[derive(Debug, Deserialize)]
+pub struct BulkCard {
+    pub card_type: String,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Defaults to "Ideas" like a single `CreateCard`.
+    #[serde(default)]
+    pub lane: Option<String>,
+}
+
+/// POST /api/specs/{id}/cards/bulk - Create cards from a JSON array of
+/// `{card_type, title, body, lane}` objects, in order, and return the new
+/// card IDs as a JSON array. Creation stops at the first card that fails
+/// validation; the response names its index and the cards created before
+/// it, which are kept rather than rolled back.
+pub async fn create_cards_bulk(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Json(cards): Json<Vec<BulkCard>>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(serde_json::json!({ "error": "spec not found" })),
+            )
+                .into_response();
+        }
+    };
+
+    let mut card_ids = Vec::with_capacity(cards.len());
+    for (index, card) in cards.into_iter().enumerate() {
+        let result = if card.title.trim().is_empty() {
+            Err("title must not be blank".to_string())
+        } else if card.card_type.trim().is_empty() {
+            Err("card_type must not be blank".to_string())
+        } else {
+            let cmd = Command::CreateCard {
+                card_type: card.card_type,
+                title: card.title,
+                body: card.body,
+                lane: card.lane.filter(|l| !l.trim().is_empty()),
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            };
+            handle
+                .send_command(cmd)
+                .await
+                .map_err(|e| e.to_string())
+                .map(|events| {
+                    events.into_iter().find_map(|e| match e.payload {
+                        EventPayload::CardCreated { card } => Some(card.card_id),
+                        _ => None,
+                    })
+                })
+        };
+        match result {
+            Ok(card_id) => card_ids.extend(card_id),
+            Err(error) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "error": error,
+                        "failed_index": index,
+                        "created": card_ids.len(),
+                        "card_ids": card_ids,
+                    })),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    (StatusCode::OK, Json(card_ids)).into_response()
+}
+
 /// POST /api/specs/{id}/undo - Undo the last undoable operation on a spec.
 pub async fn undo(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
     let spec_id = match id.parse::<Ulid>() {
@@ -124,6 +321,7 @@ mod tests {
             default_model: None,
             providers: vec![],
             any_available: false,
+            model_warning: None,
         };
         Arc::new(AppState::new(dir.keep(), provider_status))
     }
@@ -295,4 +493,192 @@ mod tests {
             assert_eq!(cards.len(), 0, "card should be removed after undo");
         }
     }
+
+    fn create_card(title: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": "CreateCard",
+            "card_type": "idea",
+            "title": title,
+            "body": null,
+            "lane": null,
+            "created_by": "human"
+        })
+    }
+
+    /// A command that always fails: deleting a card that does not exist.
+    fn delete_missing_card() -> serde_json::Value {
+        serde_json::json!({
+            "type": "DeleteCard",
+            "card_id": ulid::Ulid::new().to_string(),
+            "updated_by": "human"
+        })
+    }
+
+    async fn post_batch(
+        state: &SharedState,
+        uri: String,
+        commands: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let resp = create_router(Arc::clone(state), None)
+            .oneshot(
+                Request::post(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&commands).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    async fn card_titles(state: &SharedState, spec_id: &str) -> Vec<String> {
+        let actors = state.actors.read().await;
+        let handle = actors.get(&spec_id.parse().unwrap()).unwrap();
+        let mut titles: Vec<String> = handle
+            .read_state()
+            .await
+            .cards
+            .values()
+            .map(|c| c.title.clone())
+            .collect();
+        titles.sort();
+        titles
+    }
+
+    #[tokio::test]
+    async fn batch_applies_every_command_in_order() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let (status, json) = post_batch(
+            &state,
+            format!("/api/specs/{}/commands/batch", spec_id),
+            serde_json::json!([create_card("One"), create_card("Two")]),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let results = json["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r["ok"] == true));
+        assert_eq!(results[0]["events"].as_array().unwrap().len(), 1);
+        assert_eq!(card_titles(&state, &spec_id).await, vec!["One", "Two"]);
+    }
+
+    #[tokio::test]
+    async fn non_atomic_batch_keeps_commands_around_a_failure() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let (status, json) = post_batch(
+            &state,
+            format!("/api/specs/{}/commands/batch", spec_id),
+            serde_json::json!([
+                create_card("Before"),
+                delete_missing_card(),
+                create_card("After")
+            ]),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let results = json["results"].as_array().unwrap();
+        assert_eq!(results[0]["ok"], true);
+        assert_eq!(results[1]["ok"], false);
+        assert!(results[1]["error"].as_str().unwrap().contains("not found"));
+        assert_eq!(results[2]["ok"], true);
+        assert_eq!(json["rolled_back"], false);
+        assert_eq!(card_titles(&state, &spec_id).await, vec!["After", "Before"]);
+    }
+
+    #[tokio::test]
+    async fn bulk_creates_cards_in_order_and_returns_their_ids() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let (status, json) = post_batch(
+            &state,
+            format!("/api/specs/{}/cards/bulk", spec_id),
+            serde_json::json!([
+                { "card_type": "idea", "title": "One" },
+                { "card_type": "task", "title": "Two", "body": "details", "lane": "Plan" }
+            ]),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let ids: Vec<ulid::Ulid> = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_str().unwrap().parse().unwrap())
+            .collect();
+        assert_eq!(ids.len(), 2);
+        let actors = state.actors.read().await;
+        let spec_state = actors[&spec_id.parse().unwrap()].read_state().await;
+        assert_eq!(spec_state.cards[&ids[0]].title, "One");
+        assert_eq!(spec_state.cards[&ids[0]].lane, "Ideas");
+        assert_eq!(spec_state.cards[&ids[1]].lane, "Plan");
+        assert_eq!(spec_state.cards[&ids[1]].body.as_deref(), Some("details"));
+    }
+
+    #[tokio::test]
+    async fn bulk_stops_at_the_first_invalid_card_and_keeps_earlier_ones() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let (status, json) = post_batch(
+            &state,
+            format!("/api/specs/{}/cards/bulk", spec_id),
+            serde_json::json!([
+                { "card_type": "idea", "title": "Kept" },
+                { "card_type": "idea", "title": "  " },
+                { "card_type": "idea", "title": "Never" }
+            ]),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["failed_index"], 1);
+        assert_eq!(json["created"], 1);
+        assert_eq!(json["card_ids"].as_array().unwrap().len(), 1);
+        assert!(json["error"].as_str().unwrap().contains("title"));
+        assert_eq!(card_titles(&state, &spec_id).await, vec!["Kept"]);
+    }
+
+    #[tokio::test]
+    async fn atomic_batch_rolls_back_on_failure() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        post_batch(
+            &state,
+            format!("/api/specs/{}/commands/batch", spec_id),
+            serde_json::json!([create_card("Existing")]),
+        )
+        .await;
+
+        let (status, json) = post_batch(
+            &state,
+            format!("/api/specs/{}/commands/batch?atomic=true", spec_id),
+            serde_json::json!([
+                create_card("First"),
+                create_card("Second"),
+                delete_missing_card(),
+                create_card("Never")
+            ]),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let results = json["results"].as_array().unwrap();
+        assert_eq!(results[0]["ok"], true);
+        assert_eq!(results[2]["ok"], false);
+        assert_eq!(results[3]["skipped"], true);
+        assert_eq!(json["rolled_back"], true);
+        assert_eq!(card_titles(&state, &spec_id).await, vec!["Existing"]);
+    }
 }
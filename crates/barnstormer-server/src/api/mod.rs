@@ -1,6 +1,7 @@
 // ABOUTME: API module containing all HTTP handler functions for the barnstormer REST API.
-// ABOUTME: Organized into sub-modules for specs CRUD, command submission, and event streaming.
+// ABOUTME: Organized into sub-modules for specs CRUD, card templates, command submission, and event streaming.
 
+pub mod card_templates;
 pub mod commands;
 pub mod specs;
 pub mod stream;
@@ -1,12 +1,15 @@
 // ABOUTME: Spec CRUD API handlers for listing, creating, and reading spec state.
 // ABOUTME: Manages spec lifecycle through actor creation and state materialization.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use axum::Json;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use barnstormer_core::{Command, SpecState, spawn};
-use barnstormer_store::JsonlLog;
+use barnstormer_store::{JsonlLog, prune_after_save, save_snapshot};
 use serde::{Deserialize, Serialize};
 use ulid::Ulid;
 
@@ -19,6 +22,31 @@ pub struct SpecSummary {
     pub title: String,
     pub one_liner: String,
     pub updated_at: String,
+    pub archived: bool,
+    /// The spec's event log could not be opened, so its events are not
+    /// reaching disk. See `/readyz`.
+    pub persistence_degraded: bool,
+}
+
+/// Query parameters for the spec list endpoints. Archived specs are hidden
+/// unless `include_archived=true`; `sort` picks the list order.
+#[derive(Debug, Default, Deserialize)]
+pub struct ListSpecsQuery {
+    #[serde(default)]
+    pub include_archived: bool,
+    #[serde(default)]
+    pub sort: SpecSort,
+}
+
+/// Spec list order. Timestamps sort newest first, titles alphabetically
+/// (case-insensitive); ties fall back to spec ID so the order is stable.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpecSort {
+    #[default]
+    Updated,
+    Created,
+    Title,
 }
 
 /// Request body for creating a new spec.
@@ -27,6 +55,9 @@ pub struct CreateSpecRequest {
     pub title: String,
     pub one_liner: String,
     pub goal: String,
+    /// Overrides the server's auto-start policy for this spec.
+    #[serde(default)]
+    pub auto_start_agents: Option<bool>,
 }
 
 /// Response body after creating a spec.
@@ -36,23 +67,144 @@ pub struct CreateSpecResponse {
 }
 
 /// GET /api/specs - List all specs with summary info.
-pub async fn list_specs(State(state): State<SharedState>) -> Json<Vec<SpecSummary>> {
+pub async fn list_specs(
+    State(state): State<SharedState>,
+    Query(query): Query<ListSpecsQuery>,
+) -> Json<Vec<SpecSummary>> {
+    Json(spec_summaries(&state, &query).await)
+}
+
+/// Summaries of every created spec in the query's sort order (most recently
+/// updated first by default), skipping archived ones unless the query asks
+/// for them. Shared by the JSON and HTML spec lists.
+pub(crate) async fn spec_summaries(
+    state: &SharedState,
+    query: &ListSpecsQuery,
+) -> Vec<SpecSummary> {
     let actors = state.actors.read().await;
-    let mut summaries = Vec::new();
+    let mut rows = Vec::new();
 
     for (spec_id, handle) in actors.iter() {
         let spec_state = handle.read_state().await;
+        if spec_state.archived && !query.include_archived {
+            continue;
+        }
         if let Some(ref core) = spec_state.core {
-            summaries.push(SpecSummary {
-                spec_id: spec_id.to_string(),
-                title: core.title.clone(),
-                one_liner: core.one_liner.clone(),
-                updated_at: core.updated_at.to_rfc3339(),
-            });
+            rows.push((*spec_id, core.clone(), spec_state.archived));
+        }
+    }
+
+    rows.sort_by(|(a_id, a, _), (b_id, b, _)| {
+        match query.sort {
+            SpecSort::Updated => b.updated_at.cmp(&a.updated_at),
+            SpecSort::Created => b.created_at.cmp(&a.created_at),
+            SpecSort::Title => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
+        }
+        .then_with(|| a_id.cmp(b_id))
+    });
+
+    rows.into_iter()
+        .map(|(spec_id, core, archived)| SpecSummary {
+            spec_id: spec_id.to_string(),
+            title: core.title,
+            one_liner: core.one_liner,
+            updated_at: core.updated_at.to_rfc3339(),
+            archived,
+            persistence_degraded: state.persistence_health.is_degraded(spec_id),
+        })
+        .collect()
+}
+
+/// DELETE /api/specs/{id} - Delete a spec: stop its agents, drop it from
+/// memory and move its directory to `trash/` under BARNSTORMER_HOME.
+/// Returns the trash path (null for a spec that was never written to disk).
+pub async fn delete_spec(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+    match crate::web::trash_spec(&state, spec_id).await {
+        Ok(trashed) => Json(serde_json::json!({
+            "spec_id": spec_id,
+            "trash_dir": trashed.map(|path| path.display().to_string()),
+        }))
+        .into_response(),
+        Err(barnstormer_store::ManagerError::SpecNotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "spec not found" })),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("failed to delete spec: {}", e) })),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/specs/archive-completed - Archive every spec that looks
+/// complete (see `SpecState::looks_complete`). Returns the archived IDs.
+pub async fn archive_completed(State(state): State<SharedState>) -> impl IntoResponse {
+    let actors = state.actors.read().await;
+    let mut archived = Vec::new();
+
+    for (spec_id, handle) in actors.iter() {
+        let complete = {
+            let spec_state = handle.read_state().await;
+            spec_state.core.is_some() && !spec_state.archived && spec_state.looks_complete()
+        };
+        if !complete {
+            continue;
+        }
+        match handle
+            .send_command(Command::SetSpecArchived { archived: true })
+            .await
+        {
+            Ok(_) => archived.push(spec_id.to_string()),
+            Err(e) => tracing::error!("failed to archive spec {}: {}", spec_id, e),
         }
     }
 
-    Json(summaries)
+    archived.sort();
+    Json(serde_json::json!({ "archived": archived }))
+}
+
+/// Longest accepted spec title, in characters.
+const MAX_TITLE_CHARS: usize = 200;
+/// Longest accepted one-liner, in characters.
+const MAX_ONE_LINER_CHARS: usize = 500;
+/// Longest accepted goal, in characters.
+const MAX_GOAL_CHARS: usize = 5_000;
+
+/// Validate the fields of a create-spec request. Returns errors keyed by
+/// field name; an empty map means the request is acceptable.
+pub(crate) fn validate_create_spec(req: &CreateSpecRequest) -> BTreeMap<String, String> {
+    let mut errors = BTreeMap::new();
+    if req.title.trim().is_empty() {
+        errors.insert("title".to_string(), "title is required".to_string());
+    }
+    for (field, value, max) in [
+        ("title", &req.title, MAX_TITLE_CHARS),
+        ("one_liner", &req.one_liner, MAX_ONE_LINER_CHARS),
+        ("goal", &req.goal, MAX_GOAL_CHARS),
+    ] {
+        if value.chars().count() > max {
+            errors.insert(
+                field.to_string(),
+                format!("{} must be at most {} characters", field, max),
+            );
+        }
+    }
+    errors
 }
 
 /// POST /api/specs - Create a new spec.
@@ -60,6 +212,15 @@ pub async fn create_spec(
     State(state): State<SharedState>,
     Json(req): Json<CreateSpecRequest>,
 ) -> impl IntoResponse {
+    let errors = validate_create_spec(&req);
+    if !errors.is_empty() {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({ "error": "invalid spec fields", "errors": errors })),
+        )
+            .into_response();
+    }
+
     let spec_id = Ulid::new();
 
     // Create directory structure for this spec
@@ -121,8 +282,17 @@ pub async fn create_spec(
     // Subscribe the event persister BEFORE inserting the actor and starting
     // agents so it catches all subsequent events (agent-produced, etc.).
     // The CreateSpec events above were already persisted inline.
-    let persister_handle =
-        crate::web::spawn_event_persister(&handle, spec_id, &state.barnstormer_home);
+    let persister_handle = crate::web::spawn_event_persister(
+        &handle,
+        spec_id,
+        &state.barnstormer_home,
+        Arc::clone(&state.swarms),
+        state.persister_retry,
+        state.persistence_health.clone(),
+        Arc::clone(&state.metrics),
+        state.snapshot_interval,
+        state.snapshot_keep,
+    );
     state
         .event_persisters
         .write()
@@ -131,13 +301,19 @@ pub async fn create_spec(
 
     // Store actor handle
     state.actors.write().await.insert(spec_id, handle);
+    state.metrics.record_spec_created();
 
-    // Auto-start agents if a provider is available
-    {
+    // Auto-start agents if the policy allows and a provider is available
+    if crate::web::should_auto_start_agents(&state, req.auto_start_agents) {
         let actors = state.actors.read().await;
         if let Some(handle_ref) = actors.get(&spec_id) {
             crate::web::try_start_agents(&state, spec_id, handle_ref).await;
         }
+    } else {
+        tracing::info!(
+            "auto-start disabled, leaving agents stopped for spec {}",
+            spec_id
+        );
     }
 
     (
@@ -178,6 +354,583 @@ pub async fn get_spec_state(
     }
 }
 
+/// GET /api/specs/{id}/board - The board as JSON: lanes in display order,
+/// each with its cards sorted exactly as the web board shows them.
+pub async fn get_spec_board(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    let actors = state.actors.read().await;
+    match actors.get(&spec_id) {
+        Some(handle) => {
+            let spec_state = handle.read_state().await;
+            Json(serde_json::json!({
+                "spec_id": spec_id,
+                "lanes": crate::board::board_lanes(&spec_state),
+            }))
+            .into_response()
+        }
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "spec not found" })),
+        )
+            .into_response(),
+    }
+}
+
+/// Query parameters for the search endpoints.
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+/// GET /api/specs/{id}/search?q=... - Full-text search over the spec's card
+/// titles and bodies and its transcript. Card hits carry `card_id`,
+/// transcript hits `message_id` and `offset`; both carry a `snippet`.
+pub async fn search_spec(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<SearchQuery>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    let actors = state.actors.read().await;
+    let Some(handle) = actors.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "spec not found" })),
+        )
+            .into_response();
+    };
+    let spec_state = handle.read_state().await;
+    match crate::search::search_spec(&state.barnstormer_home, spec_id, &spec_state, &query.q) {
+        Ok(hits) => Json(serde_json::json!({
+            "spec_id": spec_id,
+            "query": query.q,
+            "hits": hits,
+        }))
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": format!("search failed: {}", e) })),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/search?q=... - Full-text search over every loaded spec's cards
+/// and transcript. Hits have the per-spec search shape plus `spec_id` and
+/// `spec_title`, best match first.
+pub async fn search_all(
+    State(state): State<SharedState>,
+    Query(query): Query<SearchQuery>,
+) -> impl IntoResponse {
+    let hits =
+        crate::search::search_all_specs(&state.barnstormer_home, &state.actors, &query.q).await;
+    Json(serde_json::json!({
+        "query": query.q,
+        "hits": hits,
+    }))
+}
+
+/// GET /api/specs/{id}/agents/trace - The swarm's recent agent steps, oldest
+/// first. Empty when no swarm is running; the trace lives in memory only.
+pub async fn get_agent_trace(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    if !state.actors.read().await.contains_key(&spec_id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "spec not found" })),
+        )
+            .into_response();
+    }
+
+    let steps = match state.swarms.read().await.get(&spec_id) {
+        Some(swarm_handle) => swarm_handle.swarm.lock().await.step_trace.entries(),
+        None => Vec::new(),
+    };
+    Json(serde_json::json!({
+        "spec_id": spec_id,
+        "steps": steps,
+    }))
+    .into_response()
+}
+
+/// One agent role's tokens in `GET /api/specs/{id}/usage`.
+#[derive(Debug, Serialize)]
+pub struct RoleUsage {
+    pub role: String,
+    /// Model of the role's most recent recorded step.
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub steps: u64,
+    /// Estimated USD cost, or None when the model has no price.
+    pub estimated_cost: Option<f64>,
+}
+
+/// Response of `GET /api/specs/{id}/usage`.
+#[derive(Debug, Serialize)]
+pub struct SpecUsage {
+    pub spec_id: String,
+    /// Provider the models are priced for.
+    pub provider: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Estimated USD cost of all roles, or None when any model has no price.
+    pub estimated_cost: Option<f64>,
+    pub roles: Vec<RoleUsage>,
+}
+
+/// GET /api/specs/{id}/usage - Tokens the spec's agents have used so far,
+/// in total and per role, with costs estimated from `AppState::model_prices`.
+pub async fn get_spec_usage(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    let Some(handle) = state.actors.read().await.get(&spec_id).cloned() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "spec not found" })),
+        )
+            .into_response();
+    };
+
+    let spec_state = handle.read_state().await;
+    let provider = crate::web::usage_provider(&state, spec_id, &spec_state).await;
+    let roles: Vec<RoleUsage> = spec_state
+        .token_usage
+        .iter()
+        .map(|(role, stats)| RoleUsage {
+            role: role.clone(),
+            model: stats.model.clone(),
+            input_tokens: stats.input_tokens,
+            output_tokens: stats.output_tokens,
+            steps: stats.steps,
+            estimated_cost: state
+                .model_prices
+                .price_for(&provider, &stats.model)
+                .map(|price| price.cost(stats.input_tokens, stats.output_tokens)),
+        })
+        .collect();
+    Json(SpecUsage {
+        spec_id: spec_id.to_string(),
+        input_tokens: roles.iter().map(|r| r.input_tokens).sum(),
+        output_tokens: roles.iter().map(|r| r.output_tokens).sum(),
+        estimated_cost: roles.iter().map(|r| r.estimated_cost).sum(),
+        provider,
+        roles,
+    })
+    .into_response()
+}
+
+/// Characters of an agent's rolling summary included in the roster.
+const ROSTER_SUMMARY_CHARS: usize = 280;
+
+/// One swarm agent as listed by `GET /api/specs/{id}/agents`.
+#[derive(Debug, Serialize)]
+pub struct AgentRosterEntry {
+    pub role: String,
+    pub agent_id: String,
+    pub last_event_seen: u64,
+    /// The first `ROSTER_SUMMARY_CHARS` characters of the rolling summary,
+    /// followed by "..." when cut.
+    pub rolling_summary: String,
+}
+
+/// GET /api/specs/{id}/agents - The swarm's agents in slot order with their
+/// role, ID, last event seen and a shortened rolling summary. An empty
+/// array when no swarm is running, so pollers need no startup special case.
+/// An agent in the middle of a step is left out until it finishes.
+pub async fn get_agents(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    if !state.actors.read().await.contains_key(&spec_id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "spec not found" })),
+        )
+            .into_response();
+    }
+
+    let agents: Vec<AgentRosterEntry> = match state.swarms.read().await.get(&spec_id) {
+        Some(swarm_handle) => swarm_handle
+            .swarm
+            .lock()
+            .await
+            .agents
+            .iter()
+            .flatten()
+            .map(|runner| {
+                let summary = &runner.context.rolling_summary;
+                let mut rolling_summary: String =
+                    summary.chars().take(ROSTER_SUMMARY_CHARS).collect();
+                if rolling_summary.len() < summary.len() {
+                    rolling_summary.push_str("...");
+                }
+                AgentRosterEntry {
+                    role: runner.role.label().to_string(),
+                    agent_id: runner.agent_id.clone(),
+                    last_event_seen: runner.context.last_event_seen,
+                    rolling_summary,
+                }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    Json(agents).into_response()
+}
+
+/// GET /api/specs/{id}/agents/{role}/context - The live memory of the swarm
+/// agent with role label `role` (rolling summary, key decisions, last event
+/// seen, recent window), for debugging. 404 when no swarm is running for the
+/// spec or it has no agent with that role.
+pub async fn get_agent_context(
+    State(state): State<SharedState>,
+    Path((id, role)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let spec_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    let swarms = state.swarms.read().await;
+    let Some(swarm_handle) = swarms.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "no agents running for this spec" })),
+        )
+            .into_response();
+    };
+    let swarm = swarm_handle.swarm.lock().await;
+    match swarm.agent_context(&role) {
+        Some(context) => Json(context.clone()).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": format!("no agent with role {}", role) })),
+        )
+            .into_response(),
+    }
+}
+
+/// Fork `source_id` into a brand-new spec: the child's log starts with a
+/// `SpecForked` event carrying the core, cards and lanes of `source_state`,
+/// so the copy is self-contained and replays without the parent.
+///
+/// The child actor and its event persister are registered in `state` before
+/// returning. Agents are not auto-started on the child.
+pub(crate) async fn fork_spec(
+    state: &SharedState,
+    source_id: Ulid,
+    source_state: &SpecState,
+) -> Result<Ulid, (StatusCode, String)> {
+    let Some(core) = source_state.core.clone() else {
+        return Err((StatusCode::NOT_FOUND, "spec has no core data".to_string()));
+    };
+
+    let child_id = Ulid::new();
+    let spec_dir = state
+        .barnstormer_home
+        .join("specs")
+        .join(child_id.to_string());
+    if let Err(e) = std::fs::create_dir_all(&spec_dir) {
+        tracing::error!("failed to create spec directory: {}", e);
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to create spec directory".to_string(),
+        ));
+    }
+    let mut log = match JsonlLog::open(&spec_dir.join("events.jsonl")) {
+        Ok(l) => l,
+        Err(e) => {
+            tracing::error!("failed to create JSONL log: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to create spec storage".to_string(),
+            ));
+        }
+    };
+
+    let handle = spawn(child_id, SpecState::new());
+    let events = handle
+        .send_command(Command::ForkSpec {
+            parent_spec_id: source_id,
+            core,
+            cards: source_state.cards.values().cloned().collect(),
+            lanes: source_state.lanes.clone(),
+            phase: source_state.phase.clone(),
+        })
+        .await
+        .map_err(|e| {
+            tracing::error!("failed to fork spec {}: {}", source_id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to fork spec: {}", e),
+            )
+        })?;
+
+    for event in &events {
+        if let Err(e) = log.append(event) {
+            tracing::error!("failed to persist event: {}", e);
+        }
+    }
+
+    let persister_handle = crate::web::spawn_event_persister(
+        &handle,
+        child_id,
+        &state.barnstormer_home,
+        Arc::clone(&state.swarms),
+        state.persister_retry,
+        state.persistence_health.clone(),
+        Arc::clone(&state.metrics),
+        state.snapshot_interval,
+        state.snapshot_keep,
+    );
+    state
+        .event_persisters
+        .write()
+        .await
+        .insert(child_id, persister_handle);
+    state.actors.write().await.insert(child_id, handle);
+    state.metrics.record_spec_created();
+
+    Ok(child_id)
+}
+
+/// POST /api/specs/{id}/branch - Snapshot the source spec, then fork it into
+/// a new child spec that records the source as its parent.
+pub async fn branch_spec(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let source_id = match id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": "invalid spec id" })),
+            )
+                .into_response();
+        }
+    };
+
+    let source_state = {
+        let actors = state.actors.read().await;
+        match actors.get(&source_id) {
+            Some(h) => h.read_state().await.clone(),
+            None => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({ "error": "spec not found" })),
+                )
+                    .into_response();
+            }
+        }
+    };
+
+    // Include agent memory when a swarm is running so the branch point can
+    // be restored with the same working context later.
+    let snapshot_dir = state
+        .barnstormer_home
+        .join("specs")
+        .join(source_id.to_string())
+        .join("snapshots");
+    let snap =
+        crate::app_state::spec_snapshot(&state.swarms, source_id, source_state.clone()).await;
+    if let Err(e) = save_snapshot(&snapshot_dir, &snap) {
+        tracing::error!(
+            "failed to snapshot spec {} before branching: {}",
+            source_id,
+            e
+        );
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": "failed to snapshot source spec" })),
+        )
+            .into_response();
+    }
+    prune_after_save(&snapshot_dir, state.snapshot_keep);
+
+    // Fork from the same read the snapshot was taken from, so the branch
+    // point and the child agree even if agents write in between.
+    match fork_spec(&state, source_id, &source_state).await {
+        Ok(child_id) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({
+                "spec_id": child_id.to_string(),
+                "parent_spec_id": source_id.to_string(),
+            })),
+        )
+            .into_response(),
+        Err((status, msg)) => (status, Json(serde_json::json!({ "error": msg }))).into_response(),
+    }
+}
+
+/// Query parameters for the merge endpoint.
+#[derive(Debug, Default, Deserialize)]
+pub struct MergeQuery {
+    /// Archive the source spec once its content has been merged.
+    #[serde(default)]
+    pub archive_source: bool,
+}
+
+/// POST /api/specs/{id}/merge-from/{source_id} - Fold the source spec into
+/// this one: its cards are copied under new IDs (refs between them remapped),
+/// its transcript is appended after a separator, and lanes this spec lacks
+/// are added. The source is left untouched unless `?archive_source=true`.
+pub async fn merge_spec(
+    State(state): State<SharedState>,
+    Path((id, source_id)): Path<(String, String)>,
+    Query(query): Query<MergeQuery>,
+) -> impl IntoResponse {
+    let (Ok(dst_id), Ok(src_id)) = (id.parse::<Ulid>(), source_id.parse::<Ulid>()) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "invalid spec id" })),
+        )
+            .into_response();
+    };
+    if dst_id == src_id {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": "cannot merge a spec into itself" })),
+        )
+            .into_response();
+    }
+
+    let (dst_handle, src_handle) = {
+        let actors = state.actors.read().await;
+        match (actors.get(&dst_id), actors.get(&src_id)) {
+            (Some(d), Some(s)) => (d.clone(), s.clone()),
+            _ => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({ "error": "spec not found" })),
+                )
+                    .into_response();
+            }
+        }
+    };
+
+    let src_state = src_handle.read_state().await.clone();
+    let Some(src_core) = src_state.core.as_ref() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": "source spec has no core data" })),
+        )
+            .into_response();
+    };
+
+    let events = match dst_handle
+        .send_command(Command::MergeFromSpec {
+            source_spec_id: src_id,
+            source_title: src_core.title.clone(),
+            cards: src_state.cards.values().cloned().collect(),
+            lanes: src_state.lanes.clone(),
+            transcript: src_state.transcript.clone(),
+        })
+        .await
+    {
+        Ok(events) => events,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({ "error": e.to_string() })),
+            )
+                .into_response();
+        }
+    };
+    let (cards_merged, lanes_added) = match events.first().map(|e| &e.payload) {
+        Some(barnstormer_core::EventPayload::SpecMerged {
+            cards, lanes_added, ..
+        }) => (cards.len(), lanes_added.clone()),
+        _ => (0, Vec::new()),
+    };
+
+    let mut source_archived = false;
+    if query.archive_source {
+        match src_handle
+            .send_command(Command::SetSpecArchived { archived: true })
+            .await
+        {
+            Ok(_) => source_archived = true,
+            Err(e) => tracing::error!("failed to archive merged spec {}: {}", src_id, e),
+        }
+    }
+
+    Json(serde_json::json!({
+        "spec_id": dst_id.to_string(),
+        "source_spec_id": src_id.to_string(),
+        "cards_merged": cards_merged,
+        "lanes_added": lanes_added,
+        "source_archived": source_archived,
+    }))
+    .into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +949,7 @@ mod tests {
             default_model: None,
             providers: vec![],
             any_available: false,
+            model_warning: None,
         };
         Arc::new(AppState::new(dir.keep(), provider_status))
     }
@@ -329,4 +1083,331 @@ mod tests {
         assert_eq!(json["core"]["one_liner"], "Check state");
         assert_eq!(json["core"]["goal"], "Verify state retrieval");
     }
+
+    #[tokio::test]
+    async fn create_spec_rejects_blank_title_with_field_errors() {
+        let state = test_state();
+        let app = create_router(Arc::clone(&state), None);
+
+        let body = serde_json::json!({
+            "title": "  ",
+            "one_liner": "x".repeat(501),
+            "goal": "Ship it"
+        });
+        let resp = app
+            .oneshot(
+                Request::post("/api/specs")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let resp_body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&resp_body).unwrap();
+        assert_eq!(json["errors"]["title"], "title is required");
+        assert!(json["errors"]["one_liner"].is_string());
+        assert!(json["errors"].get("goal").is_none());
+        assert!(state.actors.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn branch_records_parent_and_copies_cards() {
+        let state = test_state();
+
+        let source_id: String;
+        {
+            let app = create_router(Arc::clone(&state), None);
+            let body = serde_json::json!({
+                "title": "Source Spec",
+                "one_liner": "Branch me",
+                "goal": "Explore alternatives"
+            });
+            let resp = app
+                .oneshot(
+                    Request::post("/api/specs")
+                        .header("content-type", "application/json")
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let resp_body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&resp_body).unwrap();
+            source_id = json["spec_id"].as_str().unwrap().to_string();
+        }
+
+        let source_ulid = source_id.parse::<Ulid>().unwrap();
+        {
+            let actors = state.actors.read().await;
+            let handle = actors.get(&source_ulid).unwrap();
+            for title in ["First idea", "Second idea"] {
+                handle
+                    .send_command(Command::CreateCard {
+                        card_type: "idea".to_string(),
+                        title: title.to_string(),
+                        body: Some("details".to_string()),
+                        lane: Some("Plan".to_string()),
+                        created_by: "human".to_string(),
+                        source_attachment_id: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+            // A custom lane order, including an empty lane, must carry over.
+            handle
+                .send_command(Command::CreateLane {
+                    name: "Parking".to_string(),
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap();
+            handle
+                .send_command(Command::ReorderLanes {
+                    order: ["Spec", "Parking", "Plan", "Ideas"]
+                        .map(String::from)
+                        .to_vec(),
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post(format!("/api/specs/{}/branch", source_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let resp_body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&resp_body).unwrap();
+        assert_eq!(json["parent_spec_id"], source_id.as_str());
+        let child_id = json["spec_id"].as_str().unwrap().parse::<Ulid>().unwrap();
+        assert_ne!(child_id, source_ulid);
+
+        // Cards added to the source after branching must not leak into the child.
+        let actors = state.actors.read().await;
+        actors
+            .get(&source_ulid)
+            .unwrap()
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "After branch".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+
+        let child = actors.get(&child_id).unwrap().read_state().await.clone();
+        let child_core = child.core.as_ref().unwrap();
+        assert_eq!(child_core.parent_spec_id, Some(source_ulid));
+        assert_eq!(child_core.title, "Source Spec");
+
+        let mut child_cards: Vec<(String, String)> = child
+            .cards
+            .values()
+            .map(|c| (c.title.clone(), c.lane.clone()))
+            .collect();
+        child_cards.sort();
+        assert_eq!(
+            child_cards,
+            vec![
+                ("First idea".to_string(), "Plan".to_string()),
+                ("Second idea".to_string(), "Plan".to_string()),
+            ]
+        );
+        assert_eq!(child.lanes, vec!["Spec", "Parking", "Plan", "Ideas"]);
+
+        // The source was snapshotted at the branch point.
+        let snapshots = state
+            .barnstormer_home
+            .join("specs")
+            .join(&source_id)
+            .join("snapshots");
+        assert!(
+            barnstormer_store::load_latest_snapshot(&snapshots)
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_spec_moves_it_to_the_trash() {
+        let state = test_state();
+        let spec_id = create_spec_via_api(&state, "Doomed").await;
+        let delete = |uri: String| {
+            create_router(Arc::clone(&state), None)
+                .oneshot(Request::delete(uri).body(Body::empty()).unwrap())
+        };
+
+        let resp = delete(format!("/api/specs/{}", spec_id)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["spec_id"], spec_id.to_string());
+        let trash_dir = std::path::PathBuf::from(json["trash_dir"].as_str().unwrap());
+        assert_eq!(
+            trash_dir,
+            state
+                .barnstormer_home
+                .join("trash")
+                .join(spec_id.to_string())
+        );
+        assert!(trash_dir.join("events.jsonl").exists());
+        assert!(state.actors.read().await.is_empty());
+
+        let resp = delete(format!("/api/specs/{}", spec_id)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let resp = delete("/api/specs/not-a-ulid".to_string()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    async fn create_spec_via_api(state: &SharedState, title: &str) -> Ulid {
+        let app = create_router(Arc::clone(state), None);
+        let body = serde_json::json!({
+            "title": title,
+            "one_liner": "Merge test",
+            "goal": "Combine work"
+        });
+        let resp = app
+            .oneshot(
+                Request::post("/api/specs")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let resp_body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&resp_body).unwrap();
+        json["spec_id"].as_str().unwrap().parse().unwrap()
+    }
+
+    async fn add_card(state: &SharedState, spec_id: Ulid, title: &str, lane: &str) -> Ulid {
+        let actors = state.actors.read().await;
+        let events = actors[&spec_id]
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: title.to_string(),
+                body: None,
+                lane: Some(lane.to_string()),
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        match &events[0].payload {
+            barnstormer_core::EventPayload::CardCreated { card } => card.card_id,
+            other => panic!("expected CardCreated, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn merge_copies_source_cards_with_remapped_refs() {
+        let state = test_state();
+        let dst_id = create_spec_via_api(&state, "Destination").await;
+        let src_id = create_spec_via_api(&state, "Source").await;
+
+        let kept = add_card(&state, dst_id, "Existing", "Ideas").await;
+        let target = add_card(&state, src_id, "Target", "Parking").await;
+        let pointer = add_card(&state, src_id, "Pointer", "Ideas").await;
+        state.actors.read().await[&src_id]
+            .send_command(Command::UpdateCard {
+                card_id: pointer,
+                title: None,
+                body: None,
+                card_type: None,
+                refs: Some(vec![target.to_string()]),
+                tags: None,
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post(format!(
+                    "/api/specs/{}/merge-from/{}?archive_source=true",
+                    dst_id, src_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&resp_body).unwrap();
+        assert_eq!(json["cards_merged"], 2);
+        assert_eq!(json["lanes_added"], serde_json::json!(["Parking"]));
+        assert_eq!(json["source_archived"], true);
+
+        let actors = state.actors.read().await;
+        let dst = actors[&dst_id].read_state().await.clone();
+        assert_eq!(dst.cards.len(), 3);
+        assert_eq!(dst.cards[&kept].title, "Existing");
+        assert!(!dst.cards.contains_key(&target));
+        assert!(!dst.cards.contains_key(&pointer));
+
+        let by_title = |title: &str| dst.cards.values().find(|c| c.title == title).unwrap();
+        let new_target = by_title("Target");
+        let new_pointer = by_title("Pointer");
+        assert_eq!(new_target.lane, "Parking");
+        assert_eq!(new_pointer.refs, vec![new_target.card_id.to_string()]);
+        assert!(new_pointer.order > dst.cards[&kept].order);
+        assert!(dst.lanes.contains(&"Parking".to_string()));
+
+        assert_eq!(dst.core.as_ref().unwrap().merged_from, vec![src_id]);
+        assert!(
+            dst.transcript
+                .iter()
+                .any(|m| m.content.contains("Merged from \"Source\""))
+        );
+
+        let src = actors[&src_id].read_state().await;
+        assert!(src.archived);
+        assert_eq!(src.cards.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn merge_rejects_self_and_unknown_specs() {
+        let state = test_state();
+        let dst_id = create_spec_via_api(&state, "Destination").await;
+        for (src, expected) in [
+            (dst_id, StatusCode::BAD_REQUEST),
+            (Ulid::new(), StatusCode::NOT_FOUND),
+        ] {
+            let resp = create_router(Arc::clone(&state), None)
+                .oneshot(
+                    Request::post(format!("/api/specs/{}/merge-from/{}", dst_id, src))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), expected);
+        }
+    }
 }
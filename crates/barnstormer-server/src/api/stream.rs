@@ -16,10 +16,22 @@ use crate::app_state::SharedState;
 fn event_type_name(payload: &barnstormer_core::EventPayload) -> &'static str {
     match payload {
         barnstormer_core::EventPayload::SpecCreated { .. } => "spec_created",
+        barnstormer_core::EventPayload::SpecForked { .. } => "spec_forked",
+        barnstormer_core::EventPayload::SpecMerged { .. } => "spec_merged",
         barnstormer_core::EventPayload::SpecCoreUpdated { .. } => "spec_core_updated",
         barnstormer_core::EventPayload::CardCreated { .. } => "card_created",
         barnstormer_core::EventPayload::CardUpdated { .. } => "card_updated",
         barnstormer_core::EventPayload::CardMoved { .. } => "card_moved",
+        barnstormer_core::EventPayload::CardsReordered { .. } => "cards_reordered",
+        barnstormer_core::EventPayload::CardDoneSet { .. } => "card_done_set",
+        barnstormer_core::EventPayload::CardDueDateSet { .. } => "card_due_date_set",
+        barnstormer_core::EventPayload::CardArchivedSet { .. } => "card_archived_set",
+        barnstormer_core::EventPayload::CardReviewSet { .. } => "card_review_set",
+        barnstormer_core::EventPayload::CardAttachmentAdded { .. } => "card_attachment_added",
+        barnstormer_core::EventPayload::CardAttachmentRemoved { .. } => "card_attachment_removed",
+        barnstormer_core::EventPayload::LaneRenamed { .. } => "lane_renamed",
+        barnstormer_core::EventPayload::LaneDeleted { .. } => "lane_deleted",
+        barnstormer_core::EventPayload::LanesChanged { .. } => "lanes_changed",
         barnstormer_core::EventPayload::CardDeleted { .. } => "card_deleted",
         barnstormer_core::EventPayload::TranscriptAppended { .. } => "transcript_appended",
         barnstormer_core::EventPayload::QuestionAsked { .. } => "question_asked",
@@ -27,6 +39,7 @@ fn event_type_name(payload: &barnstormer_core::EventPayload) -> &'static str {
         barnstormer_core::EventPayload::AgentStepStarted { .. } => "agent_step_started",
         barnstormer_core::EventPayload::AgentStepFinished { .. } => "agent_step_finished",
         barnstormer_core::EventPayload::UndoApplied { .. } => "undo_applied",
+        barnstormer_core::EventPayload::RedoApplied { .. } => "redo_applied",
         barnstormer_core::EventPayload::SnapshotWritten { .. } => "snapshot_written",
         barnstormer_core::EventPayload::PhaseTransitioned { .. } => "phase_transitioned",
         barnstormer_core::EventPayload::CanvasUpdated { .. } => "canvas_updated",
@@ -37,6 +50,21 @@ fn event_type_name(payload: &barnstormer_core::EventPayload) -> &'static str {
         barnstormer_core::EventPayload::ContextSummarizeFailed { .. } => "context_summarize_failed",
         barnstormer_core::EventPayload::ContextNotesUpdated { .. } => "context_notes_updated",
         barnstormer_core::EventPayload::ContextRemoved { .. } => "context_removed",
+        barnstormer_core::EventPayload::ProposalQueued { .. } => "proposal_queued",
+        barnstormer_core::EventPayload::ProposalApproved { .. } => "proposal_approved",
+        barnstormer_core::EventPayload::ProposalRejected { .. } => "proposal_rejected",
+        barnstormer_core::EventPayload::AgentModelPinned { .. } => "agent_model_pinned",
+        barnstormer_core::EventPayload::UsageRecorded { .. } => "usage_recorded",
+        barnstormer_core::EventPayload::AgentRosterSet { .. } => "agent_roster_set",
+        barnstormer_core::EventPayload::FetchHostsSet { .. } => "fetch_hosts_set",
+        barnstormer_core::EventPayload::ModelOverrideSet { .. } => "model_override_set",
+        barnstormer_core::EventPayload::SnapshotOnShutdownSet { .. } => "snapshot_on_shutdown_set",
+        barnstormer_core::EventPayload::SpecArchivedSet { .. } => "spec_archived_set",
+        barnstormer_core::EventPayload::ChecklistItemAdded { .. } => "checklist_item_added",
+        barnstormer_core::EventPayload::ChecklistItemToggled { .. } => "checklist_item_toggled",
+        barnstormer_core::EventPayload::ChecklistItemRemoved { .. } => "checklist_item_removed",
+        barnstormer_core::EventPayload::ExternalLinkAdded { .. } => "external_link_added",
+        barnstormer_core::EventPayload::ExternalLinkRemoved { .. } => "external_link_removed",
     }
 }
 
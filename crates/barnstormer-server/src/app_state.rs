@@ -1,15 +1,20 @@
 // ABOUTME: Shared application state for the barnstormer HTTP server.
 // ABOUTME: Contains actor handles, BARNSTORMER_HOME path, and provides constructors for prod and test use.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
-use barnstormer_agent::SwarmOrchestrator;
-use barnstormer_core::SpecActorHandle;
+use barnstormer_agent::cost::PriceTable;
+use barnstormer_agent::{AgentRole, DEFAULT_ROLES, SwarmOrchestrator};
+use barnstormer_core::{SpecActorHandle, SpecState};
+use barnstormer_store::{CardTemplateStore, SnapshotData, UiPrefsStore};
+use chrono::Utc;
 use tokio::sync::{Mutex, RwLock};
 use ulid::Ulid;
 
+use crate::metrics::Metrics;
 use crate::providers::ProviderStatus;
 
 /// Bundles a SwarmOrchestrator with its background task handle so
@@ -19,18 +24,153 @@ pub struct SwarmHandle {
     pub task: tokio::task::JoinHandle<()>,
 }
 
+/// A spec's background event persister plus the signal that tells it to
+/// stop once it has written the events already sent to it.
+pub struct EventPersister {
+    pub task: tokio::task::JoinHandle<()>,
+    pub stop: tokio::sync::oneshot::Sender<()>,
+}
+
+impl EventPersister {
+    /// Signal the persister to stop and wait up to `timeout` for it to write
+    /// out what it was sent. Returns `false`, after aborting the task, when
+    /// it did not finish in time.
+    pub async fn close(self, timeout: std::time::Duration) -> bool {
+        let _ = self.stop.send(());
+        let mut task = self.task;
+        if tokio::time::timeout(timeout, &mut task).await.is_err() {
+            task.abort();
+            return false;
+        }
+        true
+    }
+}
+
+/// Running swarms keyed by spec ULID. Shared with the event persisters so
+/// their recovery snapshots can include agent memory.
+pub type SwarmMap = Arc<RwLock<HashMap<Ulid, SwarmHandle>>>;
+
+/// Build a snapshot of `spec_state`, including the agent contexts of the
+/// spec's swarm when one is running. Every snapshot path goes through here
+/// so none of them silently drops agent memory.
+pub async fn spec_snapshot(
+    swarms: &SwarmMap,
+    spec_id: Ulid,
+    spec_state: SpecState,
+) -> SnapshotData {
+    let agent_contexts = match swarms.read().await.get(&spec_id) {
+        Some(swarm_handle) => swarm_handle.swarm.lock().await.collect_agent_contexts(),
+        None => HashMap::new(),
+    };
+    SnapshotData {
+        last_event_id: spec_state.last_event_id,
+        state: spec_state,
+        agent_contexts,
+        saved_at: Utc::now(),
+    }
+}
+
+/// How an event persister retries opening its JSONL log. After `attempts`
+/// failures the spec is flagged as persistence-degraded, but the persister
+/// keeps retrying every `delay` until the log opens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PersisterRetry {
+    pub attempts: u32,
+    pub delay: Duration,
+}
+
+impl Default for PersisterRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Specs whose event persister could not open its log within the configured
+/// retries. Events for these specs are not reaching disk; `/readyz` and the
+/// spec list report them until a later open succeeds.
+#[derive(Debug, Clone, Default)]
+pub struct PersistenceHealth(Arc<std::sync::Mutex<BTreeSet<Ulid>>>);
+
+impl PersistenceHealth {
+    pub fn mark_degraded(&self, spec_id: Ulid) {
+        self.lock().insert(spec_id);
+    }
+
+    pub fn clear(&self, spec_id: Ulid) {
+        self.lock().remove(&spec_id);
+    }
+
+    pub fn is_degraded(&self, spec_id: Ulid) -> bool {
+        self.lock().contains(&spec_id)
+    }
+
+    /// Degraded spec IDs in ULID order.
+    pub fn degraded_specs(&self) -> Vec<Ulid> {
+        self.lock().iter().copied().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeSet<Ulid>> {
+        // The set stays consistent even if a holder panicked mid-insert.
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
 /// Shared application state accessible by all Axum handlers.
 /// Stores a map of spec actors keyed by their ULID and the BARNSTORMER_HOME directory.
 pub struct AppState {
     pub actors: Arc<RwLock<HashMap<Ulid, SpecActorHandle>>>,
-    pub swarms: Arc<RwLock<HashMap<Ulid, SwarmHandle>>>,
+    pub swarms: SwarmMap,
     /// Background tasks that subscribe to actor broadcast channels and persist
     /// every event to JSONL. Keyed by spec ULID for cleanup on shutdown.
-    pub event_persisters: Arc<RwLock<HashMap<Ulid, tokio::task::JoinHandle<()>>>>,
+    pub event_persisters: Arc<RwLock<HashMap<Ulid, EventPersister>>>,
     pub barnstormer_home: PathBuf,
     pub provider_status: ProviderStatus,
+    /// Roster a swarm runs when its spec has no `agent_roster` of its own.
+    pub agent_roles: Vec<AgentRole>,
+    /// Home-wide card template library. The mutex serializes the
+    /// read-modify-write cycles against its JSON file.
+    pub card_templates: Mutex<CardTemplateStore>,
+    /// Per-user web UI preferences (collapsed document sections). The mutex
+    /// serializes read-modify-write cycles against its JSON file.
+    pub ui_prefs: Mutex<UiPrefsStore>,
+    /// Start the agent swarm as soon as a spec is created (when a provider
+    /// is available). Create requests can override this per spec.
+    pub auto_start_agents: bool,
+    /// Filename stem for export downloads; see [`DEFAULT_EXPORT_FILENAME_PATTERN`]
+    /// for the supported placeholders. The file extension is appended per format.
+    pub export_filename_pattern: String,
+    /// Sources allowed to frame `/embed/*` pages, sent as the CSP
+    /// `frame-ancestors` directive; see [`DEFAULT_EMBED_FRAME_ANCESTORS`].
+    pub embed_frame_ancestors: String,
+    /// Per-model prices behind the agent cost estimate.
+    pub model_prices: PriceTable,
+    /// Retry policy for event persisters opening their logs.
+    pub persister_retry: PersisterRetry,
+    /// How often event persisters snapshot their spec in the background;
+    /// None leaves snapshots to lag recovery, compaction and shutdown.
+    pub snapshot_interval: Option<std::time::Duration>,
+    /// Agent steps a swarm may run per session before pausing itself;
+    /// None means no limit.
+    pub max_steps_per_session: Option<u32>,
+    /// Snapshots kept per spec; older ones are pruned after each save.
+    pub snapshot_keep: usize,
+    /// Specs whose event persister has not been able to open its log.
+    pub persistence_health: PersistenceHealth,
+    /// Spec and card counters served by `/metrics`.
+    pub metrics: Arc<Metrics>,
 }
 
+/// Default export filename stem. `{slug}` is the slugified spec title,
+/// `{short_id}` the last six characters of the spec ULID, and `{id}` the
+/// full ULID.
+pub const DEFAULT_EXPORT_FILENAME_PATTERN: &str = "{slug}-{short_id}";
+
+/// Default `frame-ancestors` sources for embeds: same-origin only.
+pub const DEFAULT_EMBED_FRAME_ANCESTORS: &str = "'self'";
+
 /// Type alias for the Arc-wrapped state used with Axum's State extractor.
 pub type SharedState = Arc<AppState>;
 
@@ -41,8 +181,21 @@ impl AppState {
             actors: Arc::new(RwLock::new(HashMap::new())),
             swarms: Arc::new(RwLock::new(HashMap::new())),
             event_persisters: Arc::new(RwLock::new(HashMap::new())),
+            card_templates: Mutex::new(CardTemplateStore::for_home(&barnstormer_home)),
+            ui_prefs: Mutex::new(UiPrefsStore::for_home(&barnstormer_home)),
             barnstormer_home,
             provider_status,
+            agent_roles: DEFAULT_ROLES.to_vec(),
+            auto_start_agents: true,
+            export_filename_pattern: DEFAULT_EXPORT_FILENAME_PATTERN.to_string(),
+            embed_frame_ancestors: DEFAULT_EMBED_FRAME_ANCESTORS.to_string(),
+            model_prices: PriceTable::default(),
+            persister_retry: PersisterRetry::default(),
+            snapshot_interval: None,
+            max_steps_per_session: None,
+            snapshot_keep: barnstormer_store::DEFAULT_SNAPSHOT_KEEP,
+            persistence_health: PersistenceHealth::default(),
+            metrics: Arc::new(Metrics::default()),
         }
     }
 }
@@ -9,13 +9,14 @@ use std::path::PathBuf;
 use ulid::Ulid;
 
 /// Adapter that wires the agent crate's `retrieve_context` question-mode
-/// dispatch back into the server's summarizer module. Holds only the
-/// barnstormer home directory; everything else (provider config, LLM client
-/// construction) is pulled from env at call time inside `summarize_now`,
-/// matching the upload/notes/resummarize paths.
+/// dispatch back into the server's summarizer module. Holds the barnstormer
+/// home directory and the configured default provider; the LLM client is
+/// built at call time inside `summarize_now`, matching the
+/// upload/notes/resummarize paths.
 #[derive(Debug)]
 pub struct ServerSummarizer {
     pub home: PathBuf,
+    pub provider: String,
 }
 
 #[async_trait]
@@ -30,6 +31,7 @@ impl AttachmentSummarizer for ServerSummarizer {
             .await
             .map_err(|e| format!("could not build summarizer input: {e}"))?;
         crate::summarizer::summarize_now(
+            &self.provider,
             &attachment.filename,
             attachment.user_notes.as_deref(),
             &input,
@@ -79,6 +81,7 @@ mod tests {
         let tmp = tempfile::tempdir().expect("tempdir");
         let summarizer = ServerSummarizer {
             home: tmp.path().to_path_buf(),
+            provider: "anthropic".to_string(),
         };
         let spec_id = Ulid::new();
         let attachment = ContextAttachment {
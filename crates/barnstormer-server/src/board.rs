@@ -0,0 +1,87 @@
+// ABOUTME: Board layout shared by the HTML board partial and the JSON board API.
+// ABOUTME: Groups a spec's cards into the spec's lanes, in lane order, each sorted by card order.
+
+use barnstormer_core::{Card, SpecState};
+use serde::Serialize;
+
+/// One board column: its name and its cards in board order.
+#[derive(Debug, Serialize)]
+pub struct BoardLane<'a> {
+    pub name: String,
+    pub cards: Vec<&'a Card>,
+}
+
+/// Group a spec's cards into board lanes, one per entry of
+/// `SpecState::lanes` in that order (possibly empty). The state keeps every
+/// card's lane in that list, so no card is left out. Cards within a lane are
+/// sorted by `order`. Archived cards are left off the board.
+pub fn board_lanes(spec_state: &SpecState) -> Vec<BoardLane<'_>> {
+    spec_state
+        .lanes
+        .iter()
+        .map(|name| {
+            let mut cards: Vec<&Card> = spec_state
+                .cards
+                .values()
+                .filter(|c| !c.archived && c.lane == *name)
+                .collect();
+            cards.sort_by(|a, b| {
+                a.order
+                    .partial_cmp(&b.order)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            BoardLane {
+                name: name.clone(),
+                cards,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(lane: &str, order: f64, title: &str) -> Card {
+        let mut card = Card::new("idea".to_string(), title.to_string(), "human".to_string());
+        card.lane = lane.to_string();
+        card.order = order;
+        card
+    }
+
+    #[test]
+    fn lanes_follow_the_spec_lane_order() {
+        let mut state = SpecState::new();
+        state.lanes = ["Spec", "Parking", "Plan", "Ideas"]
+            .map(String::from)
+            .to_vec();
+        for c in [
+            card("Spec", 0.0, "s"),
+            card("Plan", 2.0, "second"),
+            card("Plan", 1.0, "first"),
+        ] {
+            state.cards.insert(c.card_id, c);
+        }
+
+        let lanes = board_lanes(&state);
+        let names: Vec<&str> = lanes.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, vec!["Spec", "Parking", "Plan", "Ideas"]);
+        let plan: Vec<&str> = lanes[2].cards.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(plan, vec!["first", "second"]);
+        assert!(lanes[1].cards.is_empty());
+    }
+
+    #[test]
+    fn archived_cards_are_left_off_the_board() {
+        let mut state = SpecState::new();
+        let mut archived = card("Plan", 0.0, "archived");
+        archived.archived = true;
+        for c in [card("Plan", 1.0, "kept"), archived] {
+            state.cards.insert(c.card_id, c);
+        }
+
+        let lanes = board_lanes(&state);
+        let plan: Vec<&str> = lanes[1].cards.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(plan, vec!["kept"]);
+    }
+}
@@ -1,11 +1,15 @@
 // ABOUTME: Configuration loading and validation for the barnstormer server.
-// ABOUTME: Reads environment variables per spec Section 11 and enforces security constraints.
+// ABOUTME: Merges barnstormer.toml from BARNSTORMER_HOME with environment variables and enforces security constraints.
 
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use serde::Deserialize;
 use thiserror::Error;
 
+/// Name of the optional config file read from the barnstormer home.
+pub const CONFIG_FILE_NAME: &str = "barnstormer.toml";
+
 /// Errors that can occur during configuration loading.
 #[derive(Debug, Error)]
 pub enum ConfigError {
@@ -16,6 +20,20 @@ pub enum ConfigError {
         "BARNSTORMER_ALLOW_REMOTE is true but BARNSTORMER_AUTH_TOKEN is not set; refusing to start without authentication"
     )]
     RemoteWithoutToken,
+
+    #[error(
+        "BARNSTORMER_BIND {0} is not a loopback address; set BARNSTORMER_ALLOW_REMOTE=true to accept remote connections"
+    )]
+    RemoteBindNotAllowed(SocketAddr),
+
+    #[error("failed to read {path}: {source}")]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("invalid config file {path}: {message}")]
+    ParseFile { path: PathBuf, message: String },
 }
 
 /// Expand a leading `~` in a path string to the user's home directory.
@@ -32,16 +50,68 @@ fn expand_tilde(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
-/// Server configuration loaded from environment variables.
+/// A non-empty environment variable.
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.is_empty())
+}
+
+/// Settings read from `barnstormer.toml`. Every key is optional, and the
+/// matching environment variable wins when both are set. Unknown keys are
+/// rejected so a typo doesn't silently fall back to a default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    bind: Option<String>,
+    allow_remote: Option<bool>,
+    auth_token: Option<String>,
+    default_provider: Option<String>,
+    default_model: Option<String>,
+    agent_roles: Option<Vec<String>>,
+    auto_start_agents: Option<bool>,
+    export_filename_pattern: Option<String>,
+}
+
+impl ConfigFile {
+    /// Read `path`, or an empty config when the file does not exist.
+    fn read(path: &Path) -> Result<Self, ConfigError> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(ConfigError::ReadFile {
+                    path: path.to_path_buf(),
+                    source,
+                });
+            }
+        };
+        toml::from_str(&text).map_err(|e| ConfigError::ParseFile {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Server configuration merged from `barnstormer.toml` and environment variables.
 #[derive(Debug, Clone)]
 pub struct BarnstormerConfig {
     pub home: PathBuf,
     pub bind: SocketAddr,
+    /// Whether `bind` may be a non-loopback address. Off by default, so a
+    /// typo in `bind` cannot expose the server.
     pub allow_remote: bool,
     pub auth_token: Option<String>,
     pub default_provider: String,
     pub default_model: Option<String>,
-    pub public_base_url: String,
+    /// Agent roles the swarm runs, from `BARNSTORMER_AGENT_ROLES` (comma
+    /// separated) or `agent_roles` in the file. Empty means the default roster.
+    pub agent_roles: Vec<String>,
+    /// Start agents on newly created specs, from
+    /// `BARNSTORMER_AUTO_START_AGENTS` or `auto_start_agents` in the file.
+    pub auto_start_agents: bool,
+    /// Filename stem for export downloads, from
+    /// `BARNSTORMER_EXPORT_FILENAME_PATTERN` or `export_filename_pattern`
+    /// in the file.
+    pub export_filename_pattern: String,
 }
 
 impl BarnstormerConfig {
@@ -50,11 +120,13 @@ impl BarnstormerConfig {
     /// Environment variables:
     /// - BARNSTORMER_HOME: data directory (default: ~/.barnstormer)
     /// - BARNSTORMER_BIND: socket address to bind (default: 127.0.0.1:7331)
-    /// - BARNSTORMER_ALLOW_REMOTE: allow non-loopback connections (default: false)
+    /// - BARNSTORMER_ALLOW_REMOTE: allow a non-loopback bind address (default: false)
     /// - BARNSTORMER_AUTH_TOKEN: bearer token for API auth (optional)
     /// - BARNSTORMER_DEFAULT_PROVIDER: LLM provider (default: anthropic)
     /// - BARNSTORMER_DEFAULT_MODEL: LLM model name (optional)
-    /// - BARNSTORMER_PUBLIC_BASE_URL: public URL for the server (default: http://localhost:7331)
+    /// - BARNSTORMER_AGENT_ROLES: comma-separated swarm roster (optional)
+    /// - BARNSTORMER_AUTO_START_AGENTS: start agents on new specs (default: true)
+    /// - BARNSTORMER_EXPORT_FILENAME_PATTERN: export filename stem (default: {slug}-{short_id})
     pub fn from_env() -> Result<Self, ConfigError> {
         let home = std::env::var("BARNSTORMER_HOME")
             .map(|v| expand_tilde(&v))
@@ -64,35 +136,67 @@ impl BarnstormerConfig {
                     .unwrap_or_else(|_| PathBuf::from("/tmp"))
                     .join(".barnstormer")
             });
+        Self::merge(home, ConfigFile::default())
+    }
+
+    /// Load `barnstormer.toml` from `home` (if present) and merge it with the
+    /// environment variables listed on [`Self::from_env`]; a set environment
+    /// variable overrides the file's value.
+    pub fn load(home: &Path) -> Result<Self, ConfigError> {
+        let file = ConfigFile::read(&home.join(CONFIG_FILE_NAME))?;
+        Self::merge(home.to_path_buf(), file)
+    }
 
-        let bind_str =
-            std::env::var("BARNSTORMER_BIND").unwrap_or_else(|_| "127.0.0.1:7331".to_string());
+    fn merge(home: PathBuf, file: ConfigFile) -> Result<Self, ConfigError> {
+        let bind_str = env_var("BARNSTORMER_BIND")
+            .or(file.bind)
+            .unwrap_or_else(|| "127.0.0.1:7331".to_string());
         let bind: SocketAddr = bind_str
             .parse()
             .map_err(|_| ConfigError::InvalidBind(bind_str))?;
 
-        let allow_remote = std::env::var("BARNSTORMER_ALLOW_REMOTE")
+        let allow_remote = env_var("BARNSTORMER_ALLOW_REMOTE")
             .map(|v| v == "true" || v == "1" || v == "yes")
+            .or(file.allow_remote)
             .unwrap_or(false);
 
-        let auth_token = std::env::var("BARNSTORMER_AUTH_TOKEN")
-            .ok()
+        let auth_token = env_var("BARNSTORMER_AUTH_TOKEN")
+            .or(file.auth_token)
             .filter(|t| !t.is_empty());
 
-        let default_provider = std::env::var("BARNSTORMER_DEFAULT_PROVIDER")
-            .unwrap_or_else(|_| "anthropic".to_string());
+        let default_provider = env_var("BARNSTORMER_DEFAULT_PROVIDER")
+            .or(file.default_provider)
+            .unwrap_or_else(|| "anthropic".to_string());
 
-        let default_model = std::env::var("BARNSTORMER_DEFAULT_MODEL")
-            .ok()
+        let default_model = env_var("BARNSTORMER_DEFAULT_MODEL")
+            .or(file.default_model)
             .filter(|m| !m.is_empty());
 
-        let public_base_url = std::env::var("BARNSTORMER_PUBLIC_BASE_URL")
-            .unwrap_or_else(|_| format!("http://{}", bind));
+        let agent_roles = env_var("BARNSTORMER_AGENT_ROLES")
+            .map(|v| v.split(',').map(|r| r.trim().to_string()).collect())
+            .or(file.agent_roles)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|r: &String| !r.is_empty())
+            .collect();
+
+        let auto_start_agents = env_var("BARNSTORMER_AUTO_START_AGENTS")
+            .map(|v| !matches!(v.trim(), "0" | "false" | "no" | "off"))
+            .or(file.auto_start_agents)
+            .unwrap_or(true);
+
+        let export_filename_pattern = env_var("BARNSTORMER_EXPORT_FILENAME_PATTERN")
+            .or(file.export_filename_pattern)
+            .filter(|p| !p.trim().is_empty())
+            .unwrap_or_else(|| crate::DEFAULT_EXPORT_FILENAME_PATTERN.to_string());
 
         // Security validation: if allowing remote access, require auth token
         if allow_remote && auth_token.is_none() {
             return Err(ConfigError::RemoteWithoutToken);
         }
+        if !allow_remote && !bind.ip().is_loopback() {
+            return Err(ConfigError::RemoteBindNotAllowed(bind));
+        }
 
         Ok(Self {
             home,
@@ -101,7 +205,9 @@ impl BarnstormerConfig {
             auth_token,
             default_provider,
             default_model,
-            public_base_url,
+            agent_roles,
+            auto_start_agents,
+            export_filename_pattern,
         })
     }
 }
@@ -126,8 +232,112 @@ mod tests {
             std::env::remove_var("BARNSTORMER_AUTH_TOKEN");
             std::env::remove_var("BARNSTORMER_DEFAULT_PROVIDER");
             std::env::remove_var("BARNSTORMER_DEFAULT_MODEL");
-            std::env::remove_var("BARNSTORMER_PUBLIC_BASE_URL");
+            std::env::remove_var("BARNSTORMER_AGENT_ROLES");
+            std::env::remove_var("BARNSTORMER_AUTO_START_AGENTS");
+            std::env::remove_var("BARNSTORMER_EXPORT_FILENAME_PATTERN");
+        }
+    }
+
+    const SAMPLE_TOML: &str = r#"
+bind = "0.0.0.0:8080"
+allow_remote = true
+auth_token = "file-token"
+default_provider = "openai"
+default_model = "gpt-4o"
+agent_roles = ["manager", "planner"]
+auto_start_agents = false
+export_filename_pattern = "{id}"
+"#;
+
+    #[test]
+    fn load_reads_barnstormer_toml_from_home() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_barnstormer_env();
+        }
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILE_NAME), SAMPLE_TOML).unwrap();
+
+        let config = BarnstormerConfig::load(dir.path()).unwrap();
+
+        assert_eq!(config.home, dir.path());
+        assert_eq!(config.bind, "0.0.0.0:8080".parse::<SocketAddr>().unwrap());
+        assert!(config.allow_remote);
+        assert_eq!(config.auth_token.as_deref(), Some("file-token"));
+        assert_eq!(config.default_provider, "openai");
+        assert_eq!(config.default_model.as_deref(), Some("gpt-4o"));
+        assert_eq!(config.agent_roles, vec!["manager", "planner"]);
+        assert!(!config.auto_start_agents);
+        assert_eq!(config.export_filename_pattern, "{id}");
+    }
+
+    #[test]
+    fn env_vars_override_barnstormer_toml() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_barnstormer_env();
+            std::env::set_var("BARNSTORMER_BIND", "127.0.0.1:9000");
+            std::env::set_var("BARNSTORMER_DEFAULT_MODEL", "gpt-4.1");
+            std::env::set_var("BARNSTORMER_AGENT_ROLES", "manager, critic");
+            std::env::set_var("BARNSTORMER_AUTO_START_AGENTS", "true");
+        }
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILE_NAME), SAMPLE_TOML).unwrap();
+
+        let config = BarnstormerConfig::load(dir.path());
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_barnstormer_env();
         }
+        let config = config.unwrap();
+
+        assert_eq!(config.bind, "127.0.0.1:9000".parse::<SocketAddr>().unwrap());
+        assert_eq!(config.default_model.as_deref(), Some("gpt-4.1"));
+        assert_eq!(config.agent_roles, vec!["manager", "critic"]);
+        assert!(config.auto_start_agents);
+        // Keys the environment leaves unset still come from the file.
+        assert_eq!(config.default_provider, "openai");
+        assert_eq!(config.auth_token.as_deref(), Some("file-token"));
+    }
+
+    #[test]
+    fn load_without_a_file_matches_env_defaults() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_barnstormer_env();
+        }
+        let dir = tempfile::TempDir::new().unwrap();
+
+        let config = BarnstormerConfig::load(dir.path()).unwrap();
+
+        assert_eq!(config.bind, "127.0.0.1:7331".parse::<SocketAddr>().unwrap());
+        assert_eq!(config.default_provider, "anthropic");
+        assert!(config.agent_roles.is_empty());
+        assert!(config.auto_start_agents);
+        assert_eq!(
+            config.export_filename_pattern,
+            crate::DEFAULT_EXPORT_FILENAME_PATTERN
+        );
+    }
+
+    #[test]
+    fn load_rejects_unknown_keys_and_bad_toml() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+
+        std::fs::write(&path, "bnid = \"0.0.0.0:1\"\n").unwrap();
+        let err = BarnstormerConfig::load(dir.path()).unwrap_err();
+        assert!(matches!(err, ConfigError::ParseFile { .. }), "{}", err);
+        assert!(err.to_string().contains("bnid"), "{}", err);
+
+        std::fs::write(&path, "bind = ").unwrap();
+        assert!(matches!(
+            BarnstormerConfig::load(dir.path()),
+            Err(ConfigError::ParseFile { .. })
+        ));
     }
 
     #[test]
@@ -202,4 +412,32 @@ mod tests {
             err
         );
     }
+
+    #[test]
+    fn config_rejects_remote_bind_unless_allowed() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_barnstormer_env();
+            std::env::set_var("BARNSTORMER_BIND", "0.0.0.0:7331");
+        }
+        let rejected = BarnstormerConfig::from_env();
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            std::env::set_var("BARNSTORMER_ALLOW_REMOTE", "true");
+            std::env::set_var("BARNSTORMER_AUTH_TOKEN", "secret");
+        }
+        let allowed = BarnstormerConfig::from_env();
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_barnstormer_env();
+        }
+
+        assert!(matches!(
+            rejected,
+            Err(ConfigError::RemoteBindNotAllowed(_))
+        ));
+        assert!(allowed.unwrap().allow_remote);
+    }
 }
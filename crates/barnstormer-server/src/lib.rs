@@ -5,16 +5,25 @@ pub mod api;
 pub mod app_state;
 pub mod attachment_summarizer;
 pub mod auth;
+pub mod board;
 pub mod config;
 pub mod context_storage;
+pub mod metrics;
 pub mod providers;
 pub mod routes;
+pub mod search;
+pub mod shutdown;
 pub mod summarizer;
 pub mod svg_raster;
 pub mod web;
 
-pub use app_state::{AppState, SharedState};
+pub use app_state::{
+    AppState, DEFAULT_EMBED_FRAME_ANCESTORS, DEFAULT_EXPORT_FILENAME_PATTERN, PersistenceHealth,
+    PersisterRetry, SharedState,
+};
 pub use auth::AuthLayer;
-pub use config::{BarnstormerConfig, ConfigError};
+pub use barnstormer_agent::cost::PriceTable;
+pub use barnstormer_agent::roles_or_default;
+pub use config::{BarnstormerConfig, CONFIG_FILE_NAME, ConfigError};
 pub use providers::ProviderStatus;
 pub use routes::{create_router, create_router_with_static_dir};
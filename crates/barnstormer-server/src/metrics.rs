@@ -0,0 +1,121 @@
+// ABOUTME: Counters and gauges behind GET /metrics, rendered by hand in the Prometheus text format.
+// ABOUTME: Spec and card counters live on AppState; agent step counters come from the agent crate.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use barnstormer_agent::metrics::AgentStepCounts;
+
+/// Counters incremented by the server as specs and cards are created.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    specs_created: AtomicU64,
+    cards_created: AtomicU64,
+}
+
+impl Metrics {
+    pub fn record_spec_created(&self) {
+        self.specs_created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_card_created(&self) {
+        self.cards_created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn specs_created(&self) -> u64 {
+        self.specs_created.load(Ordering::Relaxed)
+    }
+
+    pub fn cards_created(&self) -> u64 {
+        self.cards_created.load(Ordering::Relaxed)
+    }
+}
+
+/// Point-in-time values read when `/metrics` is scraped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gauges {
+    pub active_swarms: u64,
+    pub pending_questions: u64,
+}
+
+/// Render every metric in the Prometheus text exposition format.
+pub fn render(metrics: &Metrics, agent: AgentStepCounts, gauges: Gauges) -> String {
+    let mut out = String::new();
+    for (name, kind, help, value) in [
+        (
+            "barnstormer_specs_created_total",
+            "counter",
+            "Specs created since the server started.",
+            metrics.specs_created(),
+        ),
+        (
+            "barnstormer_cards_created_total",
+            "counter",
+            "Cards created since the server started.",
+            metrics.cards_created(),
+        ),
+        (
+            "barnstormer_agent_steps_total",
+            "counter",
+            "Agent steps started since the server started.",
+            agent.steps,
+        ),
+        (
+            "barnstormer_agent_errors_total",
+            "counter",
+            "Agent steps that failed since the server started.",
+            agent.errors,
+        ),
+        (
+            "barnstormer_active_swarms",
+            "gauge",
+            "Specs with a running agent swarm.",
+            gauges.active_swarms,
+        ),
+        (
+            "barnstormer_pending_questions",
+            "gauge",
+            "Questions waiting for a human answer across all specs.",
+            gauges.pending_questions,
+        ),
+    ] {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} {kind}");
+        let _ = writeln!(out, "{name} {value}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_emits_help_type_and_value_for_each_metric() {
+        let metrics = Metrics::default();
+        metrics.record_spec_created();
+        metrics.record_card_created();
+        metrics.record_card_created();
+        let text = render(
+            &metrics,
+            AgentStepCounts {
+                steps: 7,
+                errors: 1,
+            },
+            Gauges {
+                active_swarms: 2,
+                pending_questions: 3,
+            },
+        );
+
+        assert!(text.contains("# TYPE barnstormer_specs_created_total counter\n"));
+        assert!(text.contains("\nbarnstormer_specs_created_total 1\n"));
+        assert!(text.contains("\nbarnstormer_cards_created_total 2\n"));
+        assert!(text.contains("\nbarnstormer_agent_steps_total 7\n"));
+        assert!(text.contains("\nbarnstormer_agent_errors_total 1\n"));
+        assert!(text.contains("# TYPE barnstormer_active_swarms gauge\n"));
+        assert!(text.contains("\nbarnstormer_active_swarms 2\n"));
+        assert!(text.contains("\nbarnstormer_pending_questions 3\n"));
+        assert_eq!(text.lines().count(), 18);
+    }
+}
@@ -1,13 +1,22 @@
 // ABOUTME: LLM provider status detection for the barnstormer UI.
-// ABOUTME: Reads environment variables to determine which providers are configured.
+// ABOUTME: Reads environment variables, and probes a local Ollama, to determine which providers are usable.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
 
 use serde::Serialize;
 
+/// How long to wait for the Ollama server to accept a connection.
+const OLLAMA_PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
 /// Status of a single LLM provider.
 #[derive(Debug, Clone, Serialize)]
 pub struct ProviderInfo {
     pub name: String,
     pub has_api_key: bool,
+    /// Whether the provider can be used: its API key is set or, for
+    /// Ollama, which needs no key, its server answered.
+    pub available: bool,
     pub model: String,
     pub base_url: Option<String>,
 }
@@ -19,6 +28,10 @@ pub struct ProviderStatus {
     pub default_model: Option<String>,
     pub providers: Vec<ProviderInfo>,
     pub any_available: bool,
+    /// Set when the default model looks like it belongs to a different
+    /// provider than the default one; a hint, not an error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_warning: Option<String>,
 }
 
 impl ProviderStatus {
@@ -28,10 +41,31 @@ impl ProviderStatus {
     /// - ANTHROPIC_API_KEY / ANTHROPIC_MODEL / ANTHROPIC_BASE_URL
     /// - OPENAI_API_KEY / OPENAI_MODEL / OPENAI_BASE_URL
     /// - GEMINI_API_KEY / GEMINI_MODEL / GEMINI_BASE_URL
+    /// - OLLAMA_MODEL / OLLAMA_BASE_URL (available when the server accepts
+    ///   a connection; no API key involved)
     /// - BARNSTORMER_DEFAULT_PROVIDER / BARNSTORMER_DEFAULT_MODEL
     ///
+    /// `model_warning` flags a default model that looks like it belongs to
+    /// another provider.
+    ///
     /// Never exposes actual API key values.
     pub fn detect() -> Self {
+        Self::detect_with(ollama_reachable)
+    }
+
+    /// Like [`detect`](Self::detect), but with the default provider and
+    /// model already resolved by the caller (from `barnstormer.toml` merged
+    /// with the environment) instead of read from the environment here.
+    pub fn detect_with_defaults(default_provider: &str, default_model: Option<&str>) -> Self {
+        Self::detect_from(
+            default_provider.to_string(),
+            default_model.map(str::to_string),
+            ollama_reachable,
+        )
+    }
+
+    /// `detect`, with the Ollama reachability probe supplied by the caller.
+    fn detect_with(reachable: impl Fn(&str) -> bool) -> Self {
         let default_provider = std::env::var("BARNSTORMER_DEFAULT_PROVIDER")
             .ok()
             .filter(|p| !p.is_empty())
@@ -39,6 +73,15 @@ impl ProviderStatus {
         let default_model = std::env::var("BARNSTORMER_DEFAULT_MODEL")
             .ok()
             .filter(|m| !m.is_empty());
+        Self::detect_from(default_provider, default_model, reachable)
+    }
+
+    fn detect_from(
+        default_provider: String,
+        default_model: Option<String>,
+        reachable: impl Fn(&str) -> bool,
+    ) -> Self {
+        let default_model = default_model.filter(|m| !m.is_empty());
 
         let providers = vec![
             Self::check_provider(
@@ -62,15 +105,28 @@ impl ProviderStatus {
                 "GEMINI_BASE_URL",
                 "gemini-2.0-flash",
             ),
+            Self::check_ollama(reachable),
         ];
 
-        let any_available = providers.iter().any(|p| p.has_api_key);
+        let any_available = providers.iter().any(|p| p.available);
+        let model_warning = default_model
+            .clone()
+            .or_else(|| {
+                providers
+                    .iter()
+                    .find(|p| p.name == default_provider)
+                    .map(|p| p.model.clone())
+            })
+            .and_then(|model| {
+                barnstormer_agent::client::model_mismatch_warning(&default_provider, &model)
+            });
 
         Self {
             default_provider,
             default_model,
             providers,
             any_available,
+            model_warning,
         }
     }
 
@@ -94,12 +150,57 @@ impl ProviderStatus {
         ProviderInfo {
             name: name.to_string(),
             has_api_key,
+            available: has_api_key,
+            model,
+            base_url,
+        }
+    }
+
+    fn check_ollama(reachable: impl Fn(&str) -> bool) -> ProviderInfo {
+        let model = std::env::var("OLLAMA_MODEL")
+            .ok()
+            .filter(|m| !m.is_empty())
+            .unwrap_or_else(|| "llama3.1".to_string());
+        let base_url = std::env::var("OLLAMA_BASE_URL")
+            .ok()
+            .filter(|u| !u.is_empty());
+
+        ProviderInfo {
+            name: "ollama".to_string(),
+            has_api_key: false,
+            // Setting OLLAMA_BASE_URL is an explicit opt-in; otherwise only
+            // offer Ollama when something answers at the default address.
+            available: base_url.is_some()
+                || reachable(&barnstormer_agent::client::ollama_base_url()),
             model,
             base_url,
         }
     }
 }
 
+/// Whether something accepts TCP connections at `base_url`'s host and port.
+/// Only the connection is checked, so any server there counts.
+fn ollama_reachable(base_url: &str) -> bool {
+    let (default_port, rest) = match base_url.split_once("://") {
+        Some(("https", rest)) => (443, rest),
+        Some((_, rest)) => (80, rest),
+        None => (80, base_url),
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    let addr = if authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+    {
+        authority.to_string()
+    } else {
+        format!("{}:{}", authority, default_port)
+    };
+    addr.to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .is_some_and(|addr| TcpStream::connect_timeout(&addr, OLLAMA_PROBE_TIMEOUT).is_ok())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,6 +226,8 @@ mod tests {
             std::env::remove_var("GEMINI_API_KEY");
             std::env::remove_var("GEMINI_MODEL");
             std::env::remove_var("GEMINI_BASE_URL");
+            std::env::remove_var("OLLAMA_MODEL");
+            std::env::remove_var("OLLAMA_BASE_URL");
         }
     }
 
@@ -137,7 +240,7 @@ mod tests {
             clear_provider_env();
         }
 
-        let status = ProviderStatus::detect();
+        let status = ProviderStatus::detect_with(|_| false);
 
         assert_eq!(status.default_provider, "anthropic");
         assert!(status.default_model.is_none());
@@ -145,7 +248,7 @@ mod tests {
             !status.any_available,
             "no providers should be available without API keys"
         );
-        assert_eq!(status.providers.len(), 3);
+        assert_eq!(status.providers.len(), 4);
 
         // Verify default models are set even without env vars
         let anthropic = &status.providers[0];
@@ -165,6 +268,66 @@ mod tests {
         assert!(!gemini.has_api_key);
         assert_eq!(gemini.model, "gemini-2.0-flash");
         assert!(gemini.base_url.is_none());
+
+        let ollama = &status.providers[3];
+        assert_eq!(ollama.name, "ollama");
+        assert!(!ollama.available);
+        assert_eq!(ollama.model, "llama3.1");
+    }
+
+    #[test]
+    fn detect_marks_reachable_ollama_available_without_api_key() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+            std::env::set_var("OLLAMA_BASE_URL", "http://gpu-box:11434");
+        }
+
+        let status = ProviderStatus::detect_with(|url| url == "http://gpu-box:11434");
+        let ollama = &status.providers[3];
+        assert!(ollama.available);
+        assert!(!ollama.has_api_key);
+        assert_eq!(ollama.base_url.as_deref(), Some("http://gpu-box:11434"));
+        assert!(status.any_available);
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+        }
+    }
+
+    #[test]
+    fn detect_marks_configured_ollama_available_even_when_unreachable() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+            std::env::set_var("OLLAMA_BASE_URL", "http://localhost:8080/v1");
+        }
+
+        let status = ProviderStatus::detect_with(|_| false);
+        let ollama = &status.providers[3];
+        assert!(ollama.available);
+        assert!(!ollama.has_api_key);
+        assert!(status.any_available);
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+        }
+    }
+
+    #[test]
+    fn ollama_probe_needs_a_listening_server() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(ollama_reachable(&format!("http://127.0.0.1:{}", port)));
+        assert!(ollama_reachable(&format!("127.0.0.1:{}/", port)));
+        drop(listener);
+        assert!(!ollama_reachable(&format!("http://127.0.0.1:{}", port)));
     }
 
     #[test]
@@ -176,7 +339,7 @@ mod tests {
             clear_provider_env();
         }
 
-        let status = ProviderStatus::detect();
+        let status = ProviderStatus::detect_with(|_| false);
         assert_eq!(
             status.default_provider, "anthropic",
             "default provider should be 'anthropic' when BARNSTORMER_DEFAULT_PROVIDER is not set"
@@ -188,7 +351,7 @@ mod tests {
             std::env::set_var("BARNSTORMER_DEFAULT_PROVIDER", "openai");
         }
 
-        let status = ProviderStatus::detect();
+        let status = ProviderStatus::detect_with(|_| false);
         assert_eq!(status.default_provider, "openai");
 
         // Clean up
@@ -210,7 +373,7 @@ mod tests {
             std::env::set_var("OPENAI_BASE_URL", "https://custom.openai.example.com");
         }
 
-        let status = ProviderStatus::detect();
+        let status = ProviderStatus::detect_with(|_| false);
 
         assert!(
             status.any_available,
@@ -249,7 +412,7 @@ mod tests {
             std::env::set_var("ANTHROPIC_API_KEY", "");
         }
 
-        let status = ProviderStatus::detect();
+        let status = ProviderStatus::detect_with(|_| false);
 
         assert!(
             !status.any_available,
@@ -274,7 +437,7 @@ mod tests {
             std::env::set_var("BARNSTORMER_DEFAULT_MODEL", "claude-opus-4-20250918");
         }
 
-        let status = ProviderStatus::detect();
+        let status = ProviderStatus::detect_with(|_| false);
         assert_eq!(
             status.default_model.as_deref(),
             Some("claude-opus-4-20250918")
@@ -297,7 +460,7 @@ mod tests {
             std::env::set_var("BARNSTORMER_DEFAULT_PROVIDER", "");
         }
 
-        let status = ProviderStatus::detect();
+        let status = ProviderStatus::detect_with(|_| false);
         assert_eq!(
             status.default_provider, "anthropic",
             "empty BARNSTORMER_DEFAULT_PROVIDER should fall back to anthropic"
@@ -309,4 +472,71 @@ mod tests {
             std::env::remove_var("BARNSTORMER_DEFAULT_PROVIDER");
         }
     }
+
+    #[test]
+    fn detect_warns_on_mismatched_default_model() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+            std::env::set_var("BARNSTORMER_DEFAULT_PROVIDER", "anthropic");
+            std::env::set_var("BARNSTORMER_DEFAULT_MODEL", "gpt-4o");
+        }
+        let status = ProviderStatus::detect_with(|_| false);
+        let warning = status.model_warning.expect("mismatch should warn");
+        assert!(warning.contains("gpt-4o"), "{}", warning);
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            std::env::set_var("BARNSTORMER_DEFAULT_MODEL", "claude-opus-4-20250918");
+        }
+        assert!(
+            ProviderStatus::detect_with(|_| false)
+                .model_warning
+                .is_none()
+        );
+
+        // A provider-specific model is checked when no default model is set.
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            std::env::remove_var("BARNSTORMER_DEFAULT_MODEL");
+            std::env::set_var("ANTHROPIC_MODEL", "gemini-2.0-flash");
+        }
+        assert!(
+            ProviderStatus::detect_with(|_| false)
+                .model_warning
+                .is_some()
+        );
+
+        // Clean up
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+        }
+    }
+
+    #[test]
+    fn detect_with_defaults_ignores_the_default_provider_env() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+            std::env::set_var("BARNSTORMER_DEFAULT_PROVIDER", "openai");
+        }
+        let status = ProviderStatus::detect_from(
+            "gemini".to_string(),
+            Some("gemini-2.0-flash".to_string()),
+            |_| false,
+        );
+        // SAFETY: holding ENV_MUTEX, no concurrent env var access
+        unsafe {
+            clear_provider_env();
+        }
+
+        assert_eq!(status.default_provider, "gemini");
+        assert_eq!(status.default_model.as_deref(), Some("gemini-2.0-flash"));
+        assert!(status.model_warning.is_none());
+    }
 }
@@ -2,8 +2,10 @@
 // ABOUTME: Assembles all API routes, web UI routes, and static file serving into a single Axum Router.
 
 use axum::Router;
-use axum::extract::DefaultBodyLimit;
-use axum::routing::{get, post, put};
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post, put};
 use std::path::PathBuf;
 use tower_http::services::ServeDir;
 
@@ -28,21 +30,64 @@ pub fn create_router_with_static_dir(
     let router = Router::new()
         // Health check
         .route("/health", get(health))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(metrics))
         // API routes (JSON)
         .route(
             "/api/specs",
             get(api::specs::list_specs).post(api::specs::create_spec),
         )
+        .route(
+            "/api/specs/archive-completed",
+            post(api::specs::archive_completed),
+        )
+        .route("/api/specs/{id}", delete(api::specs::delete_spec))
         .route("/api/specs/{id}/state", get(api::specs::get_spec_state))
+        .route("/api/specs/{id}/board", get(api::specs::get_spec_board))
+        .route("/api/search", get(api::specs::search_all))
+        .route("/api/specs/{id}/search", get(api::specs::search_spec))
+        .route("/api/specs/{id}/usage", get(api::specs::get_spec_usage))
+        .route("/api/specs/{id}/agents", get(api::specs::get_agents))
+        .route(
+            "/api/specs/{id}/agents/trace",
+            get(api::specs::get_agent_trace),
+        )
+        .route(
+            "/api/specs/{id}/agents/{role}/context",
+            get(api::specs::get_agent_context),
+        )
         .route(
             "/api/specs/{id}/commands",
             post(api::commands::submit_command),
         )
+        .route(
+            "/api/specs/{id}/commands/batch",
+            post(api::commands::submit_batch),
+        )
+        .route(
+            "/api/specs/{id}/cards/bulk",
+            post(api::commands::create_cards_bulk),
+        )
         .route(
             "/api/specs/{id}/events/stream",
             get(api::stream::event_stream),
         )
         .route("/api/specs/{id}/undo", post(api::commands::undo))
+        .route("/api/specs/{id}/branch", post(api::specs::branch_spec))
+        .route(
+            "/api/specs/{id}/merge-from/{source_id}",
+            post(api::specs::merge_spec),
+        )
+        .route(
+            "/api/card-templates",
+            get(api::card_templates::list_templates).post(api::card_templates::create_template),
+        )
+        .route(
+            "/api/card-templates/{template_id}",
+            get(api::card_templates::get_template)
+                .put(api::card_templates::update_template)
+                .delete(api::card_templates::delete_template),
+        )
         // Web UI routes (HTML)
         .route("/", get(web::index))
         .route(
@@ -55,26 +100,63 @@ pub fn create_router_with_static_dir(
                 .layer(DefaultBodyLimit::max(100 * 1024 * 1024)),
         )
         .route("/web/specs/new", get(web::create_spec_form))
-        .route("/web/specs/{id}", get(web::spec_view))
+        .route(
+            "/web/specs/{id}",
+            get(web::spec_view).delete(web::delete_spec),
+        )
+        .route("/web/specs/{id}/archive", get(web::archived_cards))
+        .route(
+            "/web/specs/{id}/cold-storage",
+            post(web::move_spec_to_cold_storage),
+        )
+        .route("/web/specs/{id}/restore", post(web::restore_spec))
         .route("/web/specs/{id}/board", get(web::board))
+        .route("/web/search", get(web::search_all))
+        .route("/web/specs/{id}/search", get(web::search))
+        .route("/web/specs/{id}/lanes", put(web::update_lanes))
         .route("/web/specs/{id}/document", get(web::document))
+        .route("/web/specs/{id}/notes", post(web::update_spec_notes))
+        .route("/web/specs/{id}/ui-prefs", post(web::save_ui_prefs))
+        .route("/web/specs/{id}/checklist", post(web::add_checklist_item))
+        .route(
+            "/web/specs/{id}/checklist/{item_id}/toggle",
+            post(web::toggle_checklist_item),
+        )
+        .route(
+            "/web/specs/{id}/checklist/{item_id}/remove",
+            post(web::remove_checklist_item),
+        )
+        .route("/web/specs/{id}/print", get(web::print_view))
+        .route("/web/specs/{id}/lint", get(web::lint))
         .route("/web/specs/{id}/activity", get(web::activity))
         .route(
             "/web/specs/{id}/activity/transcript",
             get(web::activity_transcript),
         )
         .route("/web/specs/{id}/answer", post(web::answer_question))
+        .route("/web/specs/{id}/proposals", get(web::proposals))
+        .route(
+            "/web/specs/{id}/proposals/{proposal_id}/approve",
+            post(web::approve_proposal),
+        )
+        .route(
+            "/web/specs/{id}/proposals/{proposal_id}/reject",
+            post(web::reject_proposal),
+        )
         .route("/web/specs/{id}/chat", post(web::chat))
         .route("/web/specs/{id}/chat-panel", get(web::chat_panel))
         .route("/web/specs/{id}/spec", get(web::spec))
         .route("/web/specs/{id}/artifacts", get(web::artifacts))
         .route("/web/specs/{id}/export/markdown", get(web::export_markdown))
         .route("/web/specs/{id}/export/yaml", get(web::export_yaml))
+        .route("/web/specs/{id}/export/json", get(web::export_json))
         .route("/web/specs/{id}/export/dot", get(web::export_dot))
+        .route("/web/specs/{id}/export/mermaid", get(web::export_mermaid))
         .route(
             "/web/specs/{id}/export/spec",
             get(web::export_spec_download),
         )
+        .route("/web/specs/{id}/export/prompt", get(web::export_prompt))
         .route("/web/specs/{id}/phase", post(web::transition_phase))
         .route("/web/specs/{id}/phase-check", get(web::phase_check))
         .route("/web/specs/{id}/cards-feed", get(web::cards_feed))
@@ -101,12 +183,35 @@ pub fn create_router_with_static_dir(
             get(web::download_context),
         )
         .route("/web/specs/{id}/undo", post(web::undo))
+        .route("/web/specs/{id}/redo", post(web::redo))
         .route("/web/specs/{id}/regenerate", post(web::regenerate))
         .route("/web/provider-status", get(web::provider_status))
         .route("/web/specs/{id}/agents/start", post(web::start_agents))
         .route("/web/specs/{id}/agents/pause", post(web::pause_agents))
         .route("/web/specs/{id}/agents/resume", post(web::resume_agents))
+        .route("/web/specs/{id}/agents/run-for", post(web::run_agents_for))
         .route("/web/specs/{id}/agents/status", get(web::agent_status))
+        .route(
+            "/web/specs/{id}/agents/roster/{role}/toggle",
+            post(web::toggle_roster_role),
+        )
+        .route("/web/specs/{id}/usage", get(web::usage))
+        .route(
+            "/web/specs/{id}/agents/cost-estimate",
+            get(web::agent_cost_estimate),
+        )
+        .route(
+            "/web/specs/{id}/agents/{role}/toggle",
+            post(web::toggle_agent),
+        )
+        .route(
+            "/web/specs/{id}/agents/{role}/pause",
+            post(web::pause_agent),
+        )
+        .route(
+            "/web/specs/{id}/agents/{role}/resume",
+            post(web::resume_agent),
+        )
         .route("/web/specs/{id}/ticker", get(web::ticker))
         .route("/web/specs/{id}/agents/leds", get(web::agent_leds))
         .route("/web/specs/{id}/cards/new", get(web::create_card_form))
@@ -119,6 +224,29 @@ pub fn create_router_with_static_dir(
             "/web/specs/{id}/cards/{card_id}",
             put(web::update_card).delete(web::delete_card),
         )
+        .route(
+            "/web/specs/{id}/cards/{card_id}/done",
+            post(web::set_card_done),
+        )
+        .route(
+            "/web/specs/{id}/cards/{card_id}/archive",
+            post(web::archive_card),
+        )
+        .route(
+            "/web/specs/{id}/cards/{card_id}/unarchive",
+            post(web::unarchive_card),
+        )
+        .route(
+            "/web/specs/{id}/cards/{card_id}/review",
+            post(web::resolve_card_review),
+        )
+        .route("/web/specs/{id}/cards/{card_id}/move", put(web::move_card))
+        .route(
+            "/web/specs/{id}/cards/{card_id}/duplicate",
+            post(web::duplicate_card),
+        )
+        // Read-only embeds, kept outside the main app chrome
+        .route("/embed/specs/{id}/board", get(web::embed_board))
         // Static file serving
         .nest_service("/static", ServeDir::new(static_dir))
         .with_state(state);
@@ -135,6 +263,50 @@ async fn health() -> axum::Json<serde_json::Value> {
     axum::Json(serde_json::json!({ "status": "ok" }))
 }
 
+/// Readiness check. Returns 503 listing the specs whose event log could not
+/// be opened, since their events are not reaching disk.
+async fn readyz(State(state): State<SharedState>) -> Response {
+    let degraded = state.persistence_health.degraded_specs();
+    if degraded.is_empty() {
+        return axum::Json(serde_json::json!({ "status": "ready" })).into_response();
+    }
+    let ids: Vec<String> = degraded.iter().map(|id| id.to_string()).collect();
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        axum::Json(serde_json::json!({
+            "status": "degraded",
+            "persistence_degraded": ids,
+        })),
+    )
+        .into_response()
+}
+
+/// Prometheus scrape endpoint. Counters come from AppState and the agent
+/// crate; the gauges are computed from the live actor and swarm maps.
+async fn metrics(State(state): State<SharedState>) -> Response {
+    let active_swarms = state.swarms.read().await.len() as u64;
+    let mut pending_questions = 0u64;
+    for handle in state.actors.read().await.values() {
+        pending_questions += handle.read_state().await.pending_questions.len() as u64;
+    }
+    let body = crate::metrics::render(
+        &state.metrics,
+        barnstormer_agent::metrics::agent_step_counts(),
+        crate::metrics::Gauges {
+            active_swarms,
+            pending_questions,
+        },
+    );
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +326,7 @@ mod tests {
             default_model: None,
             providers: vec![],
             any_available: false,
+            model_warning: None,
         };
         Arc::new(AppState::new(
             std::env::temp_dir().join("barnstormer-test"),
@@ -178,6 +351,111 @@ mod tests {
         assert_eq!(json["status"], "ok");
     }
 
+    #[tokio::test]
+    async fn readyz_reports_persistence_degraded_specs() {
+        let state = test_state();
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = ulid::Ulid::new();
+        state.persistence_health.mark_degraded(spec_id);
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 503);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], "degraded");
+        assert_eq!(json["persistence_degraded"][0], spec_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn metrics_count_specs_and_cards_outside_auth() {
+        let state = Arc::new(AppState::new(
+            tempdir().unwrap().keep(),
+            ProviderStatus {
+                default_provider: "anthropic".to_string(),
+                default_model: None,
+                providers: vec![],
+                any_available: false,
+                model_warning: None,
+            },
+        ));
+        let token = Some("secret-token".to_string());
+        let body = serde_json::json!({
+            "title": "Metrics",
+            "one_liner": "Count things",
+            "goal": "Observe"
+        });
+        let resp = create_router(Arc::clone(&state), token.clone())
+            .oneshot(
+                Request::post("/api/specs")
+                    .header("content-type", "application/json")
+                    .header("authorization", "Bearer secret-token")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 201);
+        let spec_id = *state.actors.read().await.keys().next().unwrap();
+        state.actors.read().await[&spec_id]
+            .send_command(barnstormer_core::Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "Counted".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+
+        // The card is counted by the event persister, which runs in the background.
+        let mut text = String::new();
+        for _ in 0..50 {
+            let resp = create_router(Arc::clone(&state), token.clone())
+                .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), 200);
+            assert!(
+                resp.headers()["content-type"]
+                    .to_str()
+                    .unwrap()
+                    .starts_with("text/plain")
+            );
+            let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            text = String::from_utf8(body.to_vec()).unwrap();
+            if text.contains("\nbarnstormer_cards_created_total 1\n") {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(
+            text.contains("\nbarnstormer_specs_created_total 1\n"),
+            "{text}"
+        );
+        assert!(
+            text.contains("\nbarnstormer_cards_created_total 1\n"),
+            "{text}"
+        );
+        assert!(text.contains("\nbarnstormer_active_swarms 0\n"), "{text}");
+        assert!(
+            text.contains("\nbarnstormer_pending_questions 0\n"),
+            "{text}"
+        );
+        assert!(text.contains("# TYPE barnstormer_agent_steps_total counter\n"));
+    }
+
     #[tokio::test]
     async fn auth_middleware_wired_when_token_provided() {
         let app = create_router(test_state(), Some("secret-token".to_string()));
@@ -0,0 +1,178 @@
+// ABOUTME: Full-text search over cards and transcripts, per spec or across all specs, for the JSON API and web partials.
+// ABOUTME: Queries each spec's SQLite FTS index off the async runtime and resolves hits against live state.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use barnstormer_core::{SpecActorHandle, SpecState};
+use barnstormer_store::{SearchHit, SearchHitKind, SqliteError, SqliteIndex};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use ulid::Ulid;
+
+/// Most hits returned for one query.
+pub const SEARCH_LIMIT: usize = 50;
+
+/// One search result, pointing at a card or at a transcript message.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SearchResult {
+    Card {
+        card_id: String,
+        title: String,
+        lane: String,
+        snippet: String,
+    },
+    Transcript {
+        message_id: String,
+        /// Position of the message in the spec's transcript.
+        offset: usize,
+        sender: String,
+        snippet: String,
+    },
+}
+
+/// One cross-spec search result: a [`SearchResult`] plus the spec it is in.
+#[derive(Debug, Serialize)]
+pub struct SpecSearchResult {
+    pub spec_id: String,
+    pub spec_title: String,
+    #[serde(flatten)]
+    pub hit: SearchResult,
+}
+
+/// Search a spec's cards and transcript for `query`, best match first.
+/// Hits are checked against `spec_state` so a card or message the index
+/// still lists but the spec no longer has is dropped.
+pub fn search_spec(
+    barnstormer_home: &Path,
+    spec_id: Ulid,
+    spec_state: &SpecState,
+    query: &str,
+) -> Result<Vec<SearchResult>, SqliteError> {
+    Ok(ranked_hits(barnstormer_home, spec_id, spec_state, query)?
+        .into_iter()
+        .map(|(_, hit)| hit)
+        .collect())
+}
+
+/// Search every loaded spec's cards and transcript for `query`, best match
+/// first, up to [`SEARCH_LIMIT`]. A spec whose index cannot be read is
+/// logged and skipped rather than failing the whole search.
+///
+/// Each spec keeps its own FTS index, and bm25 ranks from different indexes
+/// are not comparable, so every hit is scored relative to the best hit in
+/// its own spec before the lists are merged.
+pub async fn search_all_specs(
+    barnstormer_home: &Path,
+    actors: &RwLock<HashMap<Ulid, SpecActorHandle>>,
+    query: &str,
+) -> Vec<SpecSearchResult> {
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    let handles: Vec<(Ulid, SpecActorHandle)> = actors
+        .read()
+        .await
+        .iter()
+        .map(|(spec_id, handle)| (*spec_id, handle.clone()))
+        .collect();
+
+    let mut scored = Vec::new();
+    for (spec_id, handle) in handles {
+        let index_path = index_path(barnstormer_home, spec_id);
+        let text = query.to_string();
+        let hits = tokio::task::spawn_blocking(move || {
+            SqliteIndex::open(&index_path)?.search(&spec_id, &text, SEARCH_LIMIT)
+        })
+        .await;
+        let hits = match hits {
+            Ok(Ok(hits)) => hits,
+            Ok(Err(e)) => {
+                tracing::warn!("search skipped spec {}: {}", spec_id, e);
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!("search task for spec {} failed: {}", spec_id, e);
+                continue;
+            }
+        };
+
+        let spec_state = handle.read_state().await;
+        let spec_title = spec_state
+            .core
+            .as_ref()
+            .map(|core| core.title.clone())
+            .unwrap_or_default();
+        let ranked = resolve_hits(hits, &spec_state);
+        // bm25 ranks are negative, best first; dividing by the best one
+        // puts every spec's hits on a 0..=1 scale with its top hit at 1.
+        let best = ranked.first().map(|(rank, _)| *rank).unwrap_or_default();
+        scored.extend(ranked.into_iter().map(|(rank, hit)| {
+            let score = if best < 0.0 { rank / best } else { 1.0 };
+            let result = SpecSearchResult {
+                spec_id: spec_id.to_string(),
+                spec_title: spec_title.clone(),
+                hit,
+            };
+            (score, result)
+        }));
+    }
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.truncate(SEARCH_LIMIT);
+    scored.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Path of a spec's SQLite index.
+fn index_path(barnstormer_home: &Path, spec_id: Ulid) -> PathBuf {
+    barnstormer_home
+        .join("specs")
+        .join(spec_id.to_string())
+        .join("index.db")
+}
+
+/// One spec's hits with their FTS rank, resolved against `spec_state`.
+fn ranked_hits(
+    barnstormer_home: &Path,
+    spec_id: Ulid,
+    spec_state: &SpecState,
+    query: &str,
+) -> Result<Vec<(f64, SearchResult)>, SqliteError> {
+    let index = SqliteIndex::open(&index_path(barnstormer_home, spec_id))?;
+    let hits = index.search(&spec_id, query, SEARCH_LIMIT)?;
+    Ok(resolve_hits(hits, spec_state))
+}
+
+/// Pair index hits with their rank and the live card or message they point
+/// at, dropping hits the spec no longer has.
+fn resolve_hits(hits: Vec<SearchHit>, spec_state: &SpecState) -> Vec<(f64, SearchResult)> {
+    hits.into_iter()
+        .filter_map(|hit| {
+            let result = match hit.kind {
+                SearchHitKind::Card => {
+                    let card = spec_state.cards.get(&hit.ref_id.parse::<Ulid>().ok()?)?;
+                    SearchResult::Card {
+                        card_id: hit.ref_id,
+                        title: card.title.clone(),
+                        lane: card.lane.clone(),
+                        snippet: hit.snippet,
+                    }
+                }
+                SearchHitKind::Transcript => {
+                    let message_id = hit.ref_id.parse::<Ulid>().ok()?;
+                    let offset = spec_state
+                        .transcript
+                        .iter()
+                        .position(|m| m.message_id == message_id)?;
+                    SearchResult::Transcript {
+                        message_id: hit.ref_id,
+                        offset,
+                        sender: hit.title,
+                        snippet: hit.snippet,
+                    }
+                }
+            };
+            Some((hit.rank, result))
+        })
+        .collect()
+}
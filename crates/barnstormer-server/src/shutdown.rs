@@ -0,0 +1,191 @@
+// ABOUTME: Clean-shutdown helpers that stop swarms, flush event persisters, and snapshot every active spec.
+// ABOUTME: Captures actor state plus agent contexts so the next startup recovers quickly.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use barnstormer_agent::SwarmOrchestrator;
+use barnstormer_store::{prune_after_save, save_snapshot};
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use ulid::Ulid;
+
+use crate::app_state::{SharedState, spec_snapshot};
+
+/// Default upper bound on the whole shutdown snapshot pass.
+pub const DEFAULT_SHUTDOWN_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default upper bound on waiting for in-flight agent steps at shutdown.
+pub const DEFAULT_SHUTDOWN_STEP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default upper bound on event persisters writing out their queued events.
+pub const DEFAULT_SHUTDOWN_PERSIST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often `wait_for_steps` checks whether agent steps have finished.
+const STEP_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Pause every running swarm, then wait for agent steps already in flight to
+/// finish so their work and agent memory make it into the final snapshot.
+///
+/// The wait is bounded by `timeout`; after it every swarm task is aborted so
+/// no agent keeps running into the snapshot pass. Returns the IDs of specs
+/// that still had a step running at the deadline, each of which has already
+/// been logged.
+pub async fn pause_all_swarms(state: &SharedState, timeout: Duration) -> Vec<Ulid> {
+    let deadline = Instant::now() + timeout;
+    let swarms: Vec<_> = state
+        .swarms
+        .read()
+        .await
+        .iter()
+        .map(|(spec_id, handle)| (*spec_id, Arc::clone(&handle.swarm)))
+        .collect();
+    for (_, swarm) in &swarms {
+        swarm.lock().await.pause();
+    }
+
+    let mut busy = Vec::new();
+    for (spec_id, swarm) in swarms {
+        let in_flight = wait_for_steps(&swarm, deadline).await;
+        if in_flight > 0 {
+            tracing::warn!(
+                "shutdown stopped waiting on {} agent step(s) for spec {}",
+                in_flight,
+                spec_id
+            );
+            busy.push(spec_id);
+        }
+    }
+
+    for handle in state.swarms.write().await.values_mut() {
+        if !handle.task.is_finished() {
+            handle.task.abort();
+            let _ = (&mut handle.task).await;
+        }
+    }
+
+    busy
+}
+
+/// Wait until `swarm` has no agent step in flight or `deadline` passes.
+/// Returns the number of steps still running.
+pub async fn wait_for_steps(swarm: &Mutex<SwarmOrchestrator>, deadline: Instant) -> usize {
+    loop {
+        let in_flight = swarm.lock().await.steps_in_flight();
+        if in_flight == 0 || Instant::now() >= deadline {
+            return in_flight;
+        }
+        tokio::time::sleep(STEP_POLL_INTERVAL).await;
+    }
+}
+
+/// Stop every event persister once it has written the events already sent
+/// to it, so the log is complete before the final snapshots are taken.
+///
+/// The wait is bounded by `timeout`; a persister still writing at the
+/// deadline is aborted. Returns the IDs of specs whose persister did not
+/// finish, each of which has already been logged.
+pub async fn close_event_persisters(state: &SharedState, timeout: Duration) -> Vec<Ulid> {
+    let deadline = Instant::now() + timeout;
+    let persisters: Vec<_> = state.event_persisters.write().await.drain().collect();
+    let mut unfinished = Vec::new();
+
+    for (spec_id, persister) in persisters {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if !persister.close(remaining).await {
+            tracing::error!(
+                "event persister for spec {} did not finish before shutdown",
+                spec_id
+            );
+            unfinished.push(spec_id);
+        }
+    }
+
+    unfinished
+}
+
+/// Write a final snapshot (including agent contexts when a swarm is running)
+/// for every spec with a live actor, except specs that turned it off with
+/// `SetSnapshotOnShutdown`.
+///
+/// The pass is bounded by `timeout`: a spec whose swarm lock or disk write
+/// cannot finish before the deadline is skipped. Returns the IDs of specs
+/// that were not snapshotted, each of which has already been logged.
+pub async fn snapshot_all_specs(state: &SharedState, timeout: Duration) -> Vec<Ulid> {
+    let deadline = Instant::now() + timeout;
+    let spec_ids: Vec<Ulid> = state.actors.read().await.keys().copied().collect();
+    let mut missed = Vec::new();
+
+    for spec_id in spec_ids {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            tracing::error!(
+                "shutdown snapshot deadline passed before spec {} could be saved",
+                spec_id
+            );
+            missed.push(spec_id);
+            continue;
+        }
+        let outcome = tokio::time::timeout(remaining, snapshot_spec(state, spec_id)).await;
+        match outcome {
+            Ok(Ok(None)) => {
+                tracing::debug!("spec {} opted out of the shutdown snapshot", spec_id);
+            }
+            Ok(Ok(Some(last_event_id))) => {
+                tracing::info!(
+                    "shutdown snapshot saved for spec {} at event {}",
+                    spec_id,
+                    last_event_id
+                );
+            }
+            Ok(Err(e)) => {
+                tracing::error!("shutdown snapshot failed for spec {}: {}", spec_id, e);
+                missed.push(spec_id);
+            }
+            Err(_) => {
+                tracing::error!(
+                    "shutdown snapshot timed out before spec {} could be saved",
+                    spec_id
+                );
+                missed.push(spec_id);
+            }
+        }
+    }
+
+    missed
+}
+
+/// Snapshot one spec. Returns the last_event_id captured in the snapshot,
+/// or None when the spec has shutdown snapshots turned off.
+async fn snapshot_spec(state: &SharedState, spec_id: Ulid) -> Result<Option<u64>, String> {
+    let spec_state = {
+        let actors = state.actors.read().await;
+        let handle = actors
+            .get(&spec_id)
+            .ok_or_else(|| "actor no longer registered".to_string())?;
+        handle.read_state().await.clone()
+    };
+    if !spec_state.snapshot_on_shutdown {
+        return Ok(None);
+    }
+
+    let snapshot_dir = state
+        .barnstormer_home
+        .join("specs")
+        .join(spec_id.to_string())
+        .join("snapshots");
+    let snap = spec_snapshot(&state.swarms, spec_id, spec_state).await;
+    let last_event_id = snap.last_event_id;
+    let keep = state.snapshot_keep;
+
+    tokio::task::spawn_blocking(move || {
+        save_snapshot(&snapshot_dir, &snap)?;
+        prune_after_save(&snapshot_dir, keep);
+        Ok::<_, barnstormer_store::SnapshotError>(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    Ok(Some(last_event_id))
+}
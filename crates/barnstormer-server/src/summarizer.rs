@@ -246,8 +246,8 @@ fn format_text_envelope(
 
 /// Awaitable LLM call that produces a summary or question-answer text.
 ///
-/// - Reads the configured provider via `BARNSTORMER_DEFAULT_PROVIDER` env
-///   (default `anthropic`), same as the rest of the agent stack.
+/// - Runs against `provider`, the server's configured default provider
+///   (`AppState::provider_status`), same as the rest of the agent stack.
 /// - Capability-gates media inputs via `client.supports_media(kind)`. Returns
 ///   `Err` with a provider-named reason if the configured provider can't
 ///   handle the kind — caller can convert to `MarkContextSummarizeFailed` or
@@ -257,14 +257,13 @@ fn format_text_envelope(
 /// Used by `spawn_summarize` (with `question = None`) and by the
 /// `retrieve_context(id, question)` tool.
 pub async fn summarize_now(
+    provider: &str,
     filename: &str,
     notes: Option<&str>,
     input: &SummarizerInput,
     question: Option<&str>,
 ) -> anyhow::Result<String> {
-    let provider =
-        std::env::var("BARNSTORMER_DEFAULT_PROVIDER").unwrap_or_else(|_| "anthropic".into());
-    let (client, model) = barnstormer_agent::client::create_llm_client(&provider, None)?;
+    let (client, model) = barnstormer_agent::client::create_llm_client(provider, None)?;
 
     if let Some(kind) = input.media_kind()
         && !client.supports_media(kind)
@@ -299,9 +298,8 @@ pub static SUMMARIZE_SPAWN_COUNT: std::sync::atomic::AtomicUsize =
 
 /// Fire-and-forget summarization of an uploaded context attachment.
 ///
-/// Spawns a tokio task that runs `summarize_now` against the configured
-/// provider for the supplied `SummarizerInput` and routes the outcome back to
-/// the actor:
+/// Spawns a tokio task that runs `summarize_now` against `provider` for the
+/// supplied `SummarizerInput` and routes the outcome back to the actor:
 ///
 /// - **Ok(summary)** → `Command::SummarizeContext { attachment_id, summary }`.
 /// - **Err(e)** → `Command::MarkContextSummarizeFailed { attachment_id,
@@ -311,6 +309,7 @@ pub static SUMMARIZE_SPAWN_COUNT: std::sync::atomic::AtomicUsize =
 /// Send failures on the actor channel itself are still only logged — at that
 /// point the actor is gone and there's nowhere to record the outcome.
 pub fn spawn_summarize(
+    provider: String,
     actor: SpecActorHandle,
     attachment_id: Ulid,
     filename: String,
@@ -320,7 +319,7 @@ pub fn spawn_summarize(
     SUMMARIZE_SPAWN_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
     tokio::spawn(async move {
-        match summarize_now(&filename, notes.as_deref(), &input, None).await {
+        match summarize_now(&provider, &filename, notes.as_deref(), &input, None).await {
             Ok(summary) => {
                 if let Err(e) = actor
                     .send_command(Command::SummarizeContext {
@@ -655,12 +654,13 @@ mod tests {
     fn summarize_now_signature_compiles() {
         // Compile-only smoke — actually awaiting requires an LLM client.
         fn _check<'a>(
+            provider: &'a str,
             filename: &'a str,
             notes: Option<&'a str>,
             input: &'a SummarizerInput,
             question: Option<&'a str>,
         ) -> impl std::future::Future<Output = anyhow::Result<String>> + 'a {
-            summarize_now(filename, notes, input, question)
+            summarize_now(provider, filename, notes, input, question)
         }
     }
 
@@ -691,6 +691,7 @@ mod tests {
         let before = SUMMARIZE_SPAWN_COUNT.load(std::sync::atomic::Ordering::Relaxed);
         let actor = barnstormer_core::actor::spawn(Ulid::new(), barnstormer_core::SpecState::new());
         spawn_summarize(
+            "anthropic".into(),
             actor,
             Ulid::new(),
             "x.md".into(),
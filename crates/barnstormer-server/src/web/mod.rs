@@ -1,14 +1,21 @@
 // ABOUTME: Web UI route handlers serving HTML via Askama templates and HTMX.
 // ABOUTME: Provides browser-friendly views for spec management, board, documents, and activity.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use axum::extract::{Form, Path, Query, State};
 use axum::http::StatusCode;
 use axum::response::{Html, IntoResponse, Response};
-use barnstormer_agent::SwarmOrchestrator;
-use barnstormer_core::{ActorError, Command, SpecPhase, SpecState, spawn};
-use barnstormer_store::{JsonlLog, SnapshotData, save_snapshot};
+use barnstormer_agent::cost::{CycleCost, estimate_step_cost};
+use barnstormer_agent::{
+    AgentRole, ContextWindows, SwarmOrchestrator, env_model_overrides, estimated_prompt_chars,
+    roster_roles,
+};
+use barnstormer_core::{
+    ActorError, Card, ChecklistItem, Command, ExternalLink, SpecPhase, SpecState, spawn,
+};
+use barnstormer_store::{JsonlLog, SqliteIndex, prune_after_save, save_snapshot};
 use chrono::Utc;
 use serde::Deserialize;
 use ulid::Ulid;
@@ -17,6 +24,7 @@ use pulldown_cmark::{Event, Options, Parser, html};
 
 use crate::api::specs::SpecSummary;
 use crate::app_state::SharedState;
+use crate::search::{SearchResult, SpecSearchResult};
 
 use askama::Template;
 use askama_derive_axum::IntoResponse as AskamaIntoResponse;
@@ -80,36 +88,348 @@ pub async fn index() -> IndexTemplate {
 #[template(path = "partials/spec_list.html")]
 pub struct SpecListTemplate {
     pub specs: Vec<SpecSummary>,
+    /// Specs moved to cold storage in `specs/cold/`, listed with a restore
+    /// button.
+    pub cold_specs: Vec<barnstormer_store::ColdSpec>,
 }
 
-/// GET /web/specs - Return the spec list as an HTML partial.
-pub async fn spec_list(State(state): State<SharedState>) -> impl IntoResponse {
-    let actors = state.actors.read().await;
-    let mut specs = Vec::new();
-
-    for (spec_id, handle) in actors.iter() {
-        let spec_state = handle.read_state().await;
-        if let Some(ref core) = spec_state.core {
-            specs.push(SpecSummary {
-                spec_id: spec_id.to_string(),
-                title: core.title.clone(),
-                one_liner: core.one_liner.clone(),
-                updated_at: core.updated_at.to_rfc3339(),
+impl SpecListTemplate {
+    /// The spec list for `query`, plus every spec in cold storage.
+    async fn load(state: &SharedState, query: &crate::api::specs::ListSpecsQuery) -> Self {
+        let specs = crate::api::specs::spec_summaries(state, query).await;
+        let cold_specs = barnstormer_store::StorageManager::new(state.barnstormer_home.clone())
+            .and_then(|storage| storage.list_cold_specs())
+            .unwrap_or_else(|e| {
+                tracing::warn!("failed to list specs in cold storage: {}", e);
+                Vec::new()
             });
+        Self { specs, cold_specs }
+    }
+}
+
+/// GET /web/specs - Return the spec list as an HTML partial. Archived specs
+/// are hidden unless `include_archived=true`.
+pub async fn spec_list(
+    State(state): State<SharedState>,
+    Query(query): Query<crate::api::specs::ListSpecsQuery>,
+) -> impl IntoResponse {
+    SpecListTemplate::load(&state, &query).await
+}
+
+/// Stop a spec and drop it from memory: its swarm, actor and event
+/// persister. Returns a snapshot of its final state, including agent
+/// contexts, or `None` when the spec had no actor.
+async fn unload_spec(
+    state: &SharedState,
+    spec_id: Ulid,
+) -> Option<barnstormer_store::SnapshotData> {
+    // Pause the swarm and give steps already in flight a bounded wait, so
+    // their work and agent memory make it into the snapshot; a step still
+    // running at the deadline is cut off.
+    let mut agent_contexts = std::collections::HashMap::new();
+    let swarm_handle = state.swarms.write().await.remove(&spec_id);
+    if let Some(swarm_handle) = swarm_handle {
+        swarm_handle.swarm.lock().await.pause();
+        let deadline = tokio::time::Instant::now() + crate::shutdown::DEFAULT_SHUTDOWN_STEP_TIMEOUT;
+        let in_flight = crate::shutdown::wait_for_steps(&swarm_handle.swarm, deadline).await;
+        if in_flight > 0 {
+            tracing::warn!(
+                "unloading spec {} cut off {} agent step(s)",
+                spec_id,
+                in_flight
+            );
+        }
+        let swarm = swarm_handle.swarm.lock().await;
+        swarm_handle.task.abort();
+        agent_contexts = swarm.collect_agent_contexts();
+        drop(swarm);
+        let _ = swarm_handle.task.await;
+    }
+    let actor = state.actors.write().await.remove(&spec_id);
+    let snapshot = match actor {
+        Some(actor) => {
+            let spec_state = actor.read_state().await.clone();
+            Some(barnstormer_store::SnapshotData {
+                last_event_id: spec_state.last_event_id,
+                state: spec_state,
+                agent_contexts,
+                saved_at: Utc::now(),
+            })
+        }
+        None => None,
+    };
+    let persister = state.event_persisters.write().await.remove(&spec_id);
+    if let Some(persister) = persister {
+        let closed = persister
+            .close(crate::shutdown::DEFAULT_SHUTDOWN_PERSIST_TIMEOUT)
+            .await;
+        if !closed {
+            tracing::error!(
+                "event persister for spec {} did not finish before unload",
+                spec_id
+            );
+        }
+    }
+    state.persistence_health.clear(spec_id);
+    snapshot
+}
+
+/// Spawn an actor and event persister for a spec whose directory is under
+/// `specs/`. The reverse of [`unload_spec`], minus the swarm.
+async fn load_spec(state: &SharedState, spec_id: Ulid, spec_state: SpecState) {
+    let handle = spawn(spec_id, spec_state);
+    let persister_handle = spawn_event_persister(
+        &handle,
+        spec_id,
+        &state.barnstormer_home,
+        Arc::clone(&state.swarms),
+        state.persister_retry,
+        state.persistence_health.clone(),
+        Arc::clone(&state.metrics),
+        state.snapshot_interval,
+        state.snapshot_keep,
+    );
+    state
+        .event_persisters
+        .write()
+        .await
+        .insert(spec_id, persister_handle);
+    state.actors.write().await.insert(spec_id, handle);
+}
+
+/// Delete a spec: stop its agents, drop its actor and event persister, and
+/// move its directory to `trash/`. Returns the spec's path in the trash, or
+/// `None` for a spec that only lived in memory. Fails with `SpecNotFound`
+/// when there is neither an actor nor a directory for `spec_id`.
+pub(crate) async fn trash_spec(
+    state: &SharedState,
+    spec_id: Ulid,
+) -> Result<Option<std::path::PathBuf>, barnstormer_store::ManagerError> {
+    let storage = barnstormer_store::StorageManager::new(state.barnstormer_home.clone())?;
+    if !state.actors.read().await.contains_key(&spec_id) && !storage.get_spec_dir(&spec_id).is_dir()
+    {
+        return Err(barnstormer_store::ManagerError::SpecNotFound(spec_id));
+    }
+
+    unload_spec(state, spec_id).await;
+
+    match storage.trash_spec(&spec_id) {
+        Ok(trashed) => Ok(Some(trashed)),
+        Err(barnstormer_store::ManagerError::SpecNotFound(_)) => Ok(None),
+        Err(e) => {
+            tracing::error!("failed to delete spec {}: {}", spec_id, e);
+            Err(e)
+        }
+    }
+}
+
+/// DELETE /web/specs/{id} - Delete a spec (see [`trash_spec`]) and return
+/// the refreshed spec list.
+pub async fn delete_spec(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    match trash_spec(&state, spec_id).await {
+        Ok(_) => SpecListTemplate::load(&state, &crate::api::specs::ListSpecsQuery::default())
+            .await
+            .into_response(),
+        Err(barnstormer_store::ManagerError::SpecNotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to delete spec: {}</p>",
+                e
+            )),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /web/specs/{id}/cold-storage - Snapshot the spec, drop its agents,
+/// actor and persister to free memory, and move its directory under
+/// `specs/cold/`. Unlike `SetSpecArchived`, the spec leaves memory entirely.
+/// Returns the refreshed spec list.
+pub async fn move_spec_to_cold_storage(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let fail = |e: &dyn std::fmt::Display| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to move spec to cold storage: {}</p>",
+                e
+            )),
+        )
+            .into_response()
+    };
+    let storage = match barnstormer_store::StorageManager::new(state.barnstormer_home.clone()) {
+        Ok(storage) => storage,
+        Err(e) => return fail(&e),
+    };
+    if !state.actors.read().await.contains_key(&spec_id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
+    }
+
+    // The final snapshot means a restore does not depend on the persister
+    // having written every event before it was stopped.
+    // Without it the spec is loaded again and stays where it is rather than
+    // going cold with a stale snapshot.
+    if let Some(snap) = unload_spec(&state, spec_id).await {
+        let snapshot_dir = storage.get_spec_dir(&spec_id).join("snapshots");
+        if let Err(e) = save_snapshot(&snapshot_dir, &snap) {
+            tracing::error!(
+                "failed to snapshot spec {} before cold storage: {}",
+                spec_id,
+                e
+            );
+            load_spec(&state, spec_id, snap.state).await;
+            return fail(&e);
         }
+        prune_after_save(&snapshot_dir, state.snapshot_keep);
+    }
+    if let Err(e) = storage.move_to_cold_storage(&spec_id) {
+        tracing::error!("failed to move spec {} to cold storage: {}", spec_id, e);
+        return fail(&e);
+    }
+
+    SpecListTemplate::load(&state, &crate::api::specs::ListSpecsQuery::default())
+        .await
+        .into_response()
+}
+
+/// POST /web/specs/{id}/restore - Move a spec out of cold storage back under
+/// `specs/`, recover it, and spawn its actor and event persister again.
+/// Agents are left stopped. Returns the refreshed spec list.
+pub async fn restore_spec(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let fail = |status: StatusCode, e: &dyn std::fmt::Display| {
+        (
+            status,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to restore spec: {}</p>",
+                e
+            )),
+        )
+            .into_response()
+    };
+    let storage = match barnstormer_store::StorageManager::new(state.barnstormer_home.clone()) {
+        Ok(storage) => storage,
+        Err(e) => return fail(StatusCode::INTERNAL_SERVER_ERROR, &e),
+    };
+    if state.actors.read().await.contains_key(&spec_id) {
+        return fail(StatusCode::CONFLICT, &"the spec is already loaded");
     }
+    let spec_dir = match storage.restore_from_cold_storage(&spec_id) {
+        Ok(dir) => dir,
+        Err(e @ barnstormer_store::ManagerError::SpecNotFound(_)) => {
+            return fail(StatusCode::NOT_FOUND, &e);
+        }
+        Err(e @ barnstormer_store::ManagerError::SpecExists(_)) => {
+            return fail(StatusCode::CONFLICT, &e);
+        }
+        Err(e) => return fail(StatusCode::INTERNAL_SERVER_ERROR, &e),
+    };
+    let spec_state = match barnstormer_store::recover_spec(&spec_dir) {
+        Ok((spec_state, _)) => spec_state,
+        Err(e) => {
+            tracing::error!("failed to recover restored spec {}: {}", spec_id, e);
+            return fail(StatusCode::INTERNAL_SERVER_ERROR, &e);
+        }
+    };
+
+    load_spec(&state, spec_id, spec_state).await;
+    tracing::info!("restored spec {}", spec_id);
 
-    SpecListTemplate { specs }
+    SpecListTemplate::load(&state, &crate::api::specs::ListSpecsQuery::default())
+        .await
+        .into_response()
 }
 
-/// Partial: create spec form.
+/// Partial: create spec form. `description` and `errors` are populated when
+/// the form is re-rendered after a failed submission; `errors` maps a form
+/// field name to the message shown beneath it.
 #[derive(Template, AskamaIntoResponse)]
 #[template(path = "partials/create_spec_form.html")]
-pub struct CreateSpecFormTemplate {}
+pub struct CreateSpecFormTemplate {
+    pub description: String,
+    pub errors: BTreeMap<String, String>,
+}
 
 /// GET /web/specs/new - Render the create spec form.
 pub async fn create_spec_form() -> CreateSpecFormTemplate {
-    CreateSpecFormTemplate {}
+    CreateSpecFormTemplate {
+        description: String::new(),
+        errors: BTreeMap::new(),
+    }
+}
+
+/// Longest free-text description accepted by the create form, in characters.
+const MAX_DESCRIPTION_CHARS: usize = 20_000;
+
+/// Validate the create form's description, returning field errors keyed by
+/// form field name. An empty map means the input is acceptable.
+fn validate_description(description: &str) -> BTreeMap<String, String> {
+    let mut errors = BTreeMap::new();
+    let trimmed = description.trim();
+    if trimmed.is_empty() {
+        errors.insert(
+            "description".to_string(),
+            "Describe what you want to build.".to_string(),
+        );
+    } else if trimmed.chars().count() > MAX_DESCRIPTION_CHARS {
+        errors.insert(
+            "description".to_string(),
+            format!(
+                "Keep the description under {} characters; attach longer material as a context file.",
+                MAX_DESCRIPTION_CHARS
+            ),
+        );
+    } else if !extract_placeholder_title(trimmed)
+        .chars()
+        .any(char::is_alphanumeric)
+    {
+        errors.insert(
+            "description".to_string(),
+            "Start with a short sentence naming what you're building.".to_string(),
+        );
+    }
+    errors
+}
+
+/// Re-render the create form with inline errors instead of creating a spec.
+/// Responds 422 (swapped via the form's `hx-target-422`) and
+/// suppresses the form's URL push so the browser stays on the form.
+fn create_spec_form_with_errors(description: String, errors: BTreeMap<String, String>) -> Response {
+    let mut response = (
+        StatusCode::UNPROCESSABLE_ENTITY,
+        CreateSpecFormTemplate {
+            description,
+            errors,
+        },
+    )
+        .into_response();
+    response.headers_mut().insert(
+        axum::http::HeaderName::from_static("hx-push-url"),
+        axum::http::HeaderValue::from_static("false"),
+    );
+    response
 }
 
 /// Extract a placeholder title from free-text description.
@@ -149,8 +469,10 @@ pub async fn create_spec(
     State(state): State<SharedState>,
     mut multipart: axum::extract::Multipart,
 ) -> Response {
-    // 1. Parse fields: description (required) + zero-or-more `files`.
+    // 1. Parse fields: description (required) + zero-or-more `files`, plus
+    // an optional `auto_start_agents` override of the server policy.
     let mut description: Option<String> = None;
+    let mut auto_start_override: Option<bool> = None;
     let mut files: Vec<(String, String, Vec<u8>)> = Vec::new(); // (filename, mime, bytes)
 
     loop {
@@ -166,6 +488,9 @@ pub async fn create_spec(
                             .into_response();
                     }
                 },
+                Some("auto_start_agents") => {
+                    auto_start_override = field.text().await.ok().and_then(|v| parse_flag(&v));
+                }
                 Some("files") => {
                     let filename = field
                         .file_name()
@@ -199,16 +524,11 @@ pub async fn create_spec(
         }
     }
 
-    let description = match description {
-        Some(d) if !d.trim().is_empty() => d,
-        _ => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Html("<p class=\"error-msg\">Description is required.</p>".to_string()),
-            )
-                .into_response();
-        }
-    };
+    let description = description.unwrap_or_default();
+    let errors = validate_description(&description);
+    if !errors.is_empty() {
+        return create_spec_form_with_errors(description, errors);
+    }
 
     // 2. Validate files upfront so we fail before creating the spec. Better
     // UX than writing a spec then bouncing on file #3. Per-file size was
@@ -304,6 +624,7 @@ pub async fn create_spec(
         .send_command(Command::AppendTranscript {
             sender: "human".to_string(),
             content: description,
+            display_name: None,
         })
         .await
     {
@@ -410,7 +731,17 @@ pub async fn create_spec(
     // agents, OR firing the summarizer — so it catches every subsequent event
     // (agent-produced, summarizer-produced, etc.). The events produced above
     // were already persisted inline.
-    let persister_handle = spawn_event_persister(&handle, spec_id, &state.barnstormer_home);
+    let persister_handle = spawn_event_persister(
+        &handle,
+        spec_id,
+        &state.barnstormer_home,
+        Arc::clone(&state.swarms),
+        state.persister_retry,
+        state.persistence_health.clone(),
+        Arc::clone(&state.metrics),
+        state.snapshot_interval,
+        state.snapshot_keep,
+    );
     state
         .event_persisters
         .write()
@@ -441,6 +772,7 @@ pub async fn create_spec(
             {
                 Ok(input) => {
                     crate::summarizer::spawn_summarize(
+                        state.provider_status.default_provider.clone(),
                         handle.clone(),
                         attachment_id,
                         filename,
@@ -456,13 +788,19 @@ pub async fn create_spec(
     }
 
     state.actors.write().await.insert(spec_id, handle);
+    state.metrics.record_spec_created();
 
-    // Auto-start agents if a provider is available
-    {
+    // Auto-start agents if the policy allows and a provider is available
+    if should_auto_start_agents(&state, auto_start_override) {
         let actors = state.actors.read().await;
         if let Some(handle_ref) = actors.get(&spec_id) {
             try_start_agents(&state, spec_id, handle_ref).await;
         }
+    } else {
+        tracing::info!(
+            "auto-start disabled, leaving agents stopped for spec {}",
+            spec_id
+        );
     }
 
     // Return the spec view so HTMX navigates directly into the new spec
@@ -507,6 +845,7 @@ pub async fn create_spec(
         goal: core.goal.clone(),
         phase,
         lanes,
+        branched_from: None,
     }
     .into_response();
 
@@ -533,58 +872,15 @@ fn parse_spec_id(id: &str) -> Result<Ulid, Box<Response>> {
 }
 
 /// Helper to collect cards sorted by lane and order for template rendering.
+/// Uses the same lane layout as the JSON board API.
 fn cards_by_lane(spec_state: &SpecState) -> Vec<LaneData> {
-    let default_lanes = ["Ideas", "Plan", "Spec"];
-    let mut lanes: Vec<LaneData> = Vec::new();
-
-    // Default lanes first
-    for lane_name in &default_lanes {
-        let mut cards: Vec<CardData> = spec_state
-            .cards
-            .values()
-            .filter(|c| c.lane == *lane_name)
-            .map(CardData::from_card)
-            .collect();
-        cards.sort_by(|a, b| {
-            a.order
-                .partial_cmp(&b.order)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-        lanes.push(LaneData {
-            name: lane_name.to_string(),
-            cards,
-        });
-    }
-
-    // Any extra lanes with cards, alphabetically
-    let extra_lane_names: Vec<String> = spec_state
-        .cards
-        .values()
-        .map(|c| c.lane.clone())
-        .filter(|l| !default_lanes.contains(&l.as_str()))
-        .collect::<std::collections::BTreeSet<_>>()
+    crate::board::board_lanes(spec_state)
         .into_iter()
-        .collect();
-
-    for lane_name in extra_lane_names {
-        let mut cards: Vec<CardData> = spec_state
-            .cards
-            .values()
-            .filter(|c| c.lane == lane_name)
-            .map(CardData::from_card)
-            .collect();
-        cards.sort_by(|a, b| {
-            a.order
-                .partial_cmp(&b.order)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-        lanes.push(LaneData {
-            name: lane_name,
-            cards,
-        });
-    }
-
-    lanes
+        .map(|lane| {
+            let cards = lane.cards.into_iter().map(CardData::from_card).collect();
+            LaneData::new(lane.name, cards)
+        })
+        .collect()
 }
 
 /// Serializable card data for templates.
@@ -599,6 +895,14 @@ pub struct CardData {
     pub order: f64,
     pub created_by: String,
     pub updated_at: String,
+    pub done: bool,
+    pub attachments: Vec<barnstormer_core::CardAttachment>,
+    /// Due date as `YYYY-MM-DD`, if one is set.
+    pub due_date: Option<String>,
+    pub overdue: bool,
+    pub tags: Vec<String>,
+    /// An agent's open request for human review, if any.
+    pub review: Option<barnstormer_core::CardReview>,
 }
 
 impl CardData {
@@ -614,14 +918,67 @@ impl CardData {
             order: card.order,
             created_by: card.created_by.clone(),
             updated_at: card.updated_at.format("%H:%M:%S").to_string(),
+            done: card.done,
+            attachments: card.attachments.clone(),
+            due_date: card.due_date.map(|d| d.format("%Y-%m-%d").to_string()),
+            overdue: card.is_overdue(Utc::now()),
+            tags: card.tags.clone(),
+            review: card.review.clone(),
         }
     }
 }
 
-/// Lane data for templates: lane name and its sorted cards.
+/// Lane data for templates: lane name, its sorted cards, and how many of
+/// those cards are marked done.
 pub struct LaneData {
     pub name: String,
     pub cards: Vec<CardData>,
+    pub done_count: usize,
+}
+
+impl LaneData {
+    fn new(name: String, cards: Vec<CardData>) -> Self {
+        let done_count = cards.iter().filter(|c| c.done).count();
+        Self {
+            name,
+            cards,
+            done_count,
+        }
+    }
+
+    /// Cards in this lane flagged for human review.
+    pub fn review_count(&self) -> usize {
+        self.cards.iter().filter(|c| c.review.is_some()).count()
+    }
+}
+
+/// The spec a branched spec was forked from, for the command bar header.
+pub struct LineageData {
+    pub spec_id: String,
+    pub title: String,
+}
+
+/// Resolve a spec's parent into display data. Falls back to the bare ID when
+/// the parent is no longer loaded (e.g. it was deleted after branching).
+async fn lineage_for(
+    actors: &std::collections::HashMap<Ulid, barnstormer_core::SpecActorHandle>,
+    core: &barnstormer_core::SpecCore,
+) -> Option<LineageData> {
+    let parent_id = core.parent_spec_id?;
+    let title = match actors.get(&parent_id) {
+        Some(h) => h
+            .read_state()
+            .await
+            .core
+            .as_ref()
+            .map(|c| c.title.clone())
+            .unwrap_or_else(|| parent_id.to_string()),
+        None => parent_id.to_string(),
+    };
+    Some(LineageData {
+        spec_id: parent_id.to_string(),
+        title,
+    })
 }
 
 /// Full spec view: header + tab bar + board.
@@ -634,6 +991,7 @@ pub struct SpecViewTemplate {
     pub goal: String,
     pub phase: String,
     pub lanes: Vec<LaneData>,
+    pub branched_from: Option<LineageData>,
 }
 
 impl SpecViewTemplate {
@@ -668,6 +1026,7 @@ pub struct SpecPageTemplate {
     pub goal: String,
     pub phase: String,
     pub lanes: Vec<LaneData>,
+    pub branched_from: Option<LineageData>,
 }
 
 impl SpecPageTemplate {
@@ -736,6 +1095,7 @@ pub async fn spec_view(
         SpecPhase::Refining => "refining".to_string(),
         SpecPhase::Complete => "complete".to_string(),
     };
+    let branched_from = lineage_for(&actors, core).await;
 
     if is_htmx {
         SpecViewTemplate {
@@ -745,6 +1105,7 @@ pub async fn spec_view(
             goal: core.goal.clone(),
             phase,
             lanes,
+            branched_from,
         }
         .into_response()
     } else {
@@ -755,6 +1116,7 @@ pub async fn spec_view(
             goal: core.goal.clone(),
             phase,
             lanes,
+            branched_from,
         }
         .into_response()
     }
@@ -768,8 +1130,24 @@ pub struct BoardTemplate {
     pub lanes: Vec<LaneData>,
 }
 
-/// GET /web/specs/{id}/board - Render the board partial.
-pub async fn board(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
+/// Query parameters for the board partial.
+#[derive(Deserialize)]
+pub struct BoardQuery {
+    /// Show only cards whose due date has passed.
+    #[serde(default)]
+    pub overdue: bool,
+    /// Show only cards carrying this tag (case-insensitive).
+    pub tag: Option<String>,
+}
+
+/// GET /web/specs/{id}/board - Render the board partial. `?overdue=true`
+/// keeps every lane but shows only overdue cards; `?tag=foo` likewise
+/// shows only cards tagged `foo`.
+pub async fn board(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<BoardQuery>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
@@ -788,56 +1166,55 @@ pub async fn board(State(state): State<SharedState>, Path(id): Path<String>) ->
     };
 
     let spec_state = handle.read_state().await;
-    let lanes = cards_by_lane(&spec_state);
+    let mut lanes = cards_by_lane(&spec_state);
+    let tag = query
+        .tag
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    if query.overdue || tag.is_some() {
+        let keep = |c: &CardData| {
+            (!query.overdue || c.overdue)
+                && tag.is_none_or(|tag| c.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        };
+        lanes = lanes
+            .into_iter()
+            .map(|lane| LaneData::new(lane.name, lane.cards.into_iter().filter(keep).collect()))
+            .collect();
+    }
 
     BoardTemplate { spec_id: id, lanes }.into_response()
 }
 
-/// Card edit form template.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/card_form.html")]
-pub struct CardFormTemplate {
-    pub spec_id: String,
-    pub card_id: Option<String>,
-    pub title: String,
-    pub card_type: String,
-    pub body: String,
-    pub lane: String,
-}
-
-/// GET /web/specs/{id}/cards/new - Render the create card form.
-pub async fn create_card_form(Path(id): Path<String>) -> CardFormTemplate {
-    CardFormTemplate {
-        spec_id: id,
-        card_id: None,
-        title: String::new(),
-        card_type: "idea".to_string(),
-        body: String::new(),
-        lane: "Ideas".to_string(),
-    }
+/// Form data for `PUT /web/specs/{id}/lanes`. `action` picks the change:
+/// `create` uses `name`, `rename` uses `from` and `to`, and `reorder` uses
+/// `order` (lane names separated by commas).
+#[derive(Debug, Deserialize)]
+pub struct LanesForm {
+    pub action: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    #[serde(default)]
+    pub order: Option<String>,
 }
 
-/// GET /web/specs/{id}/cards/{card_id}/edit - Render the edit card form.
-pub async fn edit_card_form(
+/// PUT /web/specs/{id}/lanes - Create, rename, delete or reorder lanes, then
+/// return the refreshed board. Renaming moves the lane's cards in the same
+/// event; deleting a lane with cards needs a `to` lane to move them to.
+pub async fn update_lanes(
     State(state): State<SharedState>,
-    Path((id, card_id_str)): Path<(String, String)>,
+    Path(id): Path<String>,
+    Form(form): Form<LanesForm>,
 ) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    let card_id = match card_id_str.parse::<Ulid>() {
-        Ok(id) => id,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Html("<p class=\"error-msg\">Invalid card ID.</p>".to_string()),
-            )
-                .into_response();
-        }
-    };
-
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
         Some(h) => h,
@@ -850,15 +1227,223 @@ pub async fn edit_card_form(
         }
     };
 
-    let spec_state = handle.read_state().await;
-    let card = match spec_state.cards.get(&card_id) {
-        Some(c) => c,
-        None => {
+    let updated_by = "human".to_string();
+    let cmd = match form.action.as_str() {
+        "create" => Command::CreateLane {
+            name: form.name.unwrap_or_default(),
+            updated_by,
+        },
+        "rename" => Command::RenameLane {
+            from: form.from.unwrap_or_default(),
+            to: form.to.unwrap_or_default(),
+            updated_by,
+        },
+        "delete" => Command::DeleteLane {
+            name: form.name.unwrap_or_default(),
+            move_cards_to: form.to.filter(|t| !t.trim().is_empty()),
+            updated_by,
+        },
+        "reorder" => Command::ReorderLanes {
+            order: form
+                .order
+                .unwrap_or_default()
+                .split(',')
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect(),
+            updated_by,
+        },
+        other => {
             return (
-                StatusCode::NOT_FOUND,
-                Html("<p class=\"error-msg\">Card not found.</p>".to_string()),
-            )
-                .into_response();
+                StatusCode::BAD_REQUEST,
+                Html(format!(
+                    "<p class=\"error-msg\">Unknown lane action: {}</p>",
+                    html_escape(other)
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    if let Err(e) = handle.send_command(cmd).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to update lanes: {}</p>",
+                html_escape(&e.to_string())
+            )),
+        )
+            .into_response();
+    }
+
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
+}
+
+/// Card edit form template.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/card_form.html")]
+pub struct CardFormTemplate {
+    pub spec_id: String,
+    pub card_id: Option<String>,
+    pub title: String,
+    pub card_type: String,
+    pub body: String,
+    pub lane: String,
+    /// Due date as `YYYY-MM-DD` for the date input; empty when unset.
+    pub due_date: String,
+    /// Comma-separated tags for the edit form.
+    pub tags: String,
+    /// Card templates offered in the create form's picker. Empty for edits.
+    pub templates: Vec<CardTemplateOption>,
+    pub selected_template: Option<String>,
+}
+
+impl CardFormTemplate {
+    /// Options for the type picker: every known card type, plus the card's
+    /// own type when it predates validation so an edit does not silently
+    /// change it.
+    pub fn card_type_options(&self) -> Vec<CardTypeOption> {
+        let mut types: Vec<&str> = Card::known_types().to_vec();
+        if !types.contains(&self.card_type.as_str()) {
+            types.push(&self.card_type);
+        }
+        types
+            .into_iter()
+            .map(|card_type| {
+                let label = card_type.replace('_', " ");
+                let mut chars = label.chars();
+                CardTypeOption {
+                    value: card_type.to_string(),
+                    label: chars
+                        .next()
+                        .map(|first| first.to_uppercase().chain(chars).collect())
+                        .unwrap_or_default(),
+                    selected: card_type == self.card_type,
+                }
+            })
+            .collect()
+    }
+}
+
+/// One card type in the card form's type picker.
+pub struct CardTypeOption {
+    pub value: String,
+    /// Display name, e.g. "Open question".
+    pub label: String,
+    pub selected: bool,
+}
+
+/// A card template entry in the create form's picker.
+pub struct CardTemplateOption {
+    pub template_id: String,
+    pub name: String,
+}
+
+/// Query parameters for the create card form.
+#[derive(Deserialize)]
+pub struct CreateCardFormQuery {
+    /// Card template to pre-fill the form from. Empty means none.
+    pub template: Option<String>,
+}
+
+/// GET /web/specs/{id}/cards/new - Render the create card form, pre-filled
+/// from a card template when `?template=<id>` is given.
+pub async fn create_card_form(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<CreateCardFormQuery>,
+) -> CardFormTemplate {
+    let mut form = CardFormTemplate {
+        spec_id: id,
+        card_id: None,
+        title: String::new(),
+        card_type: "idea".to_string(),
+        body: String::new(),
+        lane: "Ideas".to_string(),
+        due_date: String::new(),
+        tags: String::new(),
+        templates: Vec::new(),
+        selected_template: None,
+    };
+
+    let store = state.card_templates.lock().await;
+    match store.list() {
+        Ok(templates) => {
+            form.templates = templates
+                .into_iter()
+                .map(|t| CardTemplateOption {
+                    template_id: t.template_id.to_string(),
+                    name: t.name,
+                })
+                .collect();
+        }
+        Err(e) => tracing::warn!("failed to list card templates: {}", e),
+    }
+
+    let chosen = query
+        .template
+        .as_deref()
+        .filter(|t| !t.is_empty())
+        .and_then(|t| t.parse::<Ulid>().ok());
+    if let Some(template_id) = chosen {
+        match store.get(template_id) {
+            Ok(t) => {
+                form.title = t.title;
+                form.card_type = t.card_type;
+                form.body = t.body.unwrap_or_default();
+                form.selected_template = Some(template_id.to_string());
+            }
+            Err(e) => tracing::warn!("card template {} unavailable: {}", template_id, e),
+        }
+    }
+
+    form
+}
+
+/// GET /web/specs/{id}/cards/{card_id}/edit - Render the edit card form.
+pub async fn edit_card_form(
+    State(state): State<SharedState>,
+    Path((id, card_id_str)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let card_id = match card_id_str.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid card ID.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
+    let spec_state = handle.read_state().await;
+    let card = match spec_state.cards.get(&card_id) {
+        Some(c) => c,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Card not found.</p>".to_string()),
+            )
+                .into_response();
         }
     };
 
@@ -869,6 +1454,13 @@ pub async fn edit_card_form(
         card_type: card.card_type.clone(),
         body: card.body.clone().unwrap_or_default(),
         lane: card.lane.clone(),
+        due_date: card
+            .due_date
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        tags: card.tags.join(", "),
+        templates: Vec::new(),
+        selected_template: None,
     }
     .into_response()
 }
@@ -880,6 +1472,23 @@ pub struct CardForm {
     pub card_type: String,
     pub body: Option<String>,
     pub lane: Option<String>,
+    /// `YYYY-MM-DD` from the date input; empty clears the due date.
+    pub due_date: Option<String>,
+    /// Comma-separated tags; empty clears them. Absent leaves tags alone.
+    pub tags: Option<String>,
+}
+
+/// Parse a date input value into a due date at the end of that day (UTC),
+/// so a card due today only turns overdue once the day is over. Blank
+/// means no due date.
+fn parse_due_date(raw: &str) -> Result<Option<chrono::DateTime<Utc>>, String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    let date = chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map_err(|_| format!("invalid due date {:?}, expected YYYY-MM-DD", raw))?;
+    Ok(Some(date.and_hms_opt(23, 59, 59).unwrap().and_utc()))
 }
 
 /// POST /web/specs/{id}/cards - Create a card, return updated board.
@@ -905,6 +1514,17 @@ pub async fn create_card(
         }
     };
 
+    let due_date = match parse_due_date(form.due_date.as_deref().unwrap_or_default()) {
+        Ok(due) => due,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html(format!("<p class=\"error-msg\">{}</p>", e)),
+            )
+                .into_response();
+        }
+    };
+
     let cmd = Command::CreateCard {
         card_type: form.card_type,
         title: form.title,
@@ -914,7 +1534,7 @@ pub async fn create_card(
         source_attachment_id: None,
     };
 
-    let _events = match handle.send_command(cmd).await {
+    let events = match handle.send_command(cmd).await {
         Ok(events) => events,
         Err(e) => {
             return (
@@ -931,6 +1551,21 @@ pub async fn create_card(
     // Events are persisted by the background broadcast subscriber
     // (spawned via spawn_event_persister when the actor was created).
 
+    let created = events.iter().find_map(|e| match &e.payload {
+        barnstormer_core::EventPayload::CardCreated { card } => Some(card.card_id),
+        _ => None,
+    });
+    if let (Some(card_id), Some(due_date)) = (created, due_date) {
+        let cmd = Command::SetCardDueDate {
+            card_id,
+            due_date: Some(due_date),
+            updated_by: "human".to_string(),
+        };
+        if let Err(e) = handle.send_command(cmd).await {
+            tracing::warn!("failed to set due date on new card {}: {}", card_id, e);
+        }
+    }
+
     // Return refreshed board
     let spec_state = handle.read_state().await;
     let lanes = cards_by_lane(&spec_state);
@@ -971,12 +1606,27 @@ pub async fn update_card(
         }
     };
 
+    let due_date = match form.due_date.as_deref().map(parse_due_date).transpose() {
+        Ok(due) => due,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html(format!("<p class=\"error-msg\">{}</p>", e)),
+            )
+                .into_response();
+        }
+    };
+
     let cmd = Command::UpdateCard {
         card_id,
         title: Some(form.title),
         body: Some(form.body.filter(|b| !b.is_empty())),
         card_type: Some(form.card_type),
         refs: None,
+        tags: form
+            .tags
+            .as_deref()
+            .map(|raw| raw.split(',').map(str::to_string).collect()),
         updated_by: "human".to_string(),
     };
 
@@ -994,6 +1644,32 @@ pub async fn update_card(
         }
     };
 
+    // Only touch the due date when the form sent one and it changed, so
+    // saving an untouched form doesn't add an undo step.
+    let current_due = handle
+        .read_state()
+        .await
+        .cards
+        .get(&card_id)
+        .and_then(|c| c.due_date);
+    if let Some(due_date) = due_date.filter(|d| *d != current_due) {
+        let cmd = Command::SetCardDueDate {
+            card_id,
+            due_date,
+            updated_by: "human".to_string(),
+        };
+        if let Err(e) = handle.send_command(cmd).await {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html(format!(
+                    "<p class=\"error-msg\">Failed to set due date: {}</p>",
+                    e
+                )),
+            )
+                .into_response();
+        }
+    }
+
     // Events are persisted by the background broadcast subscriber.
 
     // Return the updated card HTML
@@ -1081,25 +1757,35 @@ pub async fn delete_card(
     Html(String::new()).into_response()
 }
 
-/// Cards feed partial: reverse-chronological list of all captured cards for the
-/// brainstorming sidebar. Self-refreshes on card SSE events.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/cards_feed.html")]
-pub struct CardsFeedTemplate {
-    pub spec_id: String,
-    pub cards: Vec<CardData>,
+/// Form data for toggling a card's done flag.
+#[derive(Deserialize)]
+pub struct CardDoneForm {
+    pub done: bool,
 }
 
-/// GET /web/specs/{id}/cards-feed - Render the flat card list for the brainstorm sidebar.
-pub async fn cards_feed(
+/// POST /web/specs/{id}/cards/{card_id}/done - Mark a card done or not done,
+/// return the refreshed board so lane done-counts stay in sync.
+pub async fn set_card_done(
     State(state): State<SharedState>,
-    Path(id): Path<String>,
+    Path((id, card_id_str)): Path<(String, String)>,
+    Form(form): Form<CardDoneForm>,
 ) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
+    let card_id = match card_id_str.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid card ID.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
         Some(h) => h,
@@ -1112,387 +1798,401 @@ pub async fn cards_feed(
         }
     };
 
-    let spec_state = handle.read_state().await;
-    // Newest-first: sort by updated_at descending. Break ties with card_id
-    // (ULID) descending so ordering is deterministic even when cards created
-    // in the same clock tick share a formatted updated_at string.
-    let mut sorted: Vec<&barnstormer_core::Card> = spec_state.cards.values().collect();
-    sorted.sort_by(|a, b| {
-        b.updated_at
-            .cmp(&a.updated_at)
-            .then_with(|| b.card_id.cmp(&a.card_id))
-    });
-    let cards: Vec<CardData> = sorted.into_iter().map(CardData::from_card).collect();
+    let cmd = Command::SetCardDone {
+        card_id,
+        done: form.done,
+        updated_by: "human".to_string(),
+    };
 
-    CardsFeedTemplate { spec_id: id, cards }.into_response()
-}
+    if let Err(e) = handle.send_command(cmd).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to update card: {}</p>",
+                e
+            )),
+        )
+            .into_response();
+    }
 
-/// Document view template.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/document.html")]
-pub struct DocumentTemplate {
-    pub spec_id: String,
-    pub title: String,
-    pub title_slug: String,
-    pub one_liner: String,
-    pub goal: String,
-    pub goal_html: String,
-    pub description: Option<String>,
-    pub description_html: Option<String>,
-    pub constraints: Option<String>,
-    pub constraints_html: Option<String>,
-    pub success_criteria: Option<String>,
-    pub success_criteria_html: Option<String>,
-    pub risks: Option<String>,
-    pub risks_html: Option<String>,
-    pub notes: Option<String>,
-    pub notes_html: Option<String>,
-    pub lanes: Vec<LaneData>,
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
 }
 
-/// GET /web/specs/{id}/document - Render the spec as a narrative document.
-pub async fn document(
-    State(state): State<SharedState>,
-    Path(id): Path<String>,
-) -> impl IntoResponse {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
-    };
+/// Send an archive or unarchive command for a card and return the spec's
+/// handle, or the error response to send instead.
+async fn send_card_archived(
+    state: &SharedState,
+    id: &str,
+    card_id_str: &str,
+    archived: bool,
+) -> Result<barnstormer_core::SpecActorHandle, Box<Response>> {
+    let spec_id = parse_spec_id(id)?;
+    let card_id = card_id_str.parse::<Ulid>().map_err(|_| {
+        Box::new(
+            (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid card ID.</p>".to_string()),
+            )
+                .into_response(),
+        )
+    })?;
 
-    let actors = state.actors.read().await;
-    let handle = match actors.get(&spec_id) {
-        Some(h) => h,
-        None => {
-            return (
+    let handle = state.actors.read().await.get(&spec_id).cloned();
+    let Some(handle) = handle else {
+        return Err(Box::new(
+            (
                 StatusCode::NOT_FOUND,
                 Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
             )
-                .into_response();
-        }
+                .into_response(),
+        ));
     };
 
-    let spec_state = handle.read_state().await;
-    let core = match &spec_state.core {
-        Some(c) => c,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Html("<p class=\"error-msg\">Spec has no core data.</p>".to_string()),
-            )
-                .into_response();
+    let updated_by = "human".to_string();
+    let cmd = if archived {
+        Command::ArchiveCard {
+            card_id,
+            updated_by,
+        }
+    } else {
+        Command::UnarchiveCard {
+            card_id,
+            updated_by,
         }
     };
+    if let Err(e) = handle.send_command(cmd).await {
+        return Err(Box::new(
+            (
+                StatusCode::BAD_REQUEST,
+                Html(format!(
+                    "<p class=\"error-msg\">Failed to update card: {}</p>",
+                    e
+                )),
+            )
+                .into_response(),
+        ));
+    }
+    Ok(handle)
+}
 
-    let lanes = cards_by_lane(&spec_state);
-
-    DocumentTemplate {
+/// POST /web/specs/{id}/cards/{card_id}/archive - Hide a card from the board
+/// without deleting it; return the refreshed board.
+pub async fn archive_card(
+    State(state): State<SharedState>,
+    Path((id, card_id_str)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let handle = match send_card_archived(&state, &id, &card_id_str, true).await {
+        Ok(handle) => handle,
+        Err(resp) => return *resp,
+    };
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
+}
+
+/// POST /web/specs/{id}/cards/{card_id}/unarchive - Put an archived card
+/// back on the board; return the refreshed archive list.
+pub async fn unarchive_card(
+    State(state): State<SharedState>,
+    Path((id, card_id_str)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let handle = match send_card_archived(&state, &id, &card_id_str, false).await {
+        Ok(handle) => handle,
+        Err(resp) => return *resp,
+    };
+    let spec_state = handle.read_state().await;
+    ArchivedCardsTemplate {
         spec_id: id,
-        title_slug: slugify(&core.title),
-        title: core.title.clone(),
-        one_liner: core.one_liner.clone(),
-        goal: core.goal.clone(),
-        goal_html: render_markdown(&core.goal),
-        description: core.description.clone(),
-        description_html: core.description.as_ref().map(|d| render_markdown(d)),
-        constraints: core.constraints.clone(),
-        constraints_html: core.constraints.as_ref().map(|c| render_markdown(c)),
-        success_criteria: core.success_criteria.clone(),
-        success_criteria_html: core.success_criteria.as_ref().map(|s| render_markdown(s)),
-        risks: core.risks.clone(),
-        risks_html: core.risks.as_ref().map(|r| render_markdown(r)),
-        notes: core.notes.clone(),
-        notes_html: core.notes.as_ref().map(|n| render_markdown(n)),
-        lanes,
+        cards: archived_cards_of(&spec_state),
     }
     .into_response()
 }
 
-/// Activity transcript data for templates.
-pub struct TranscriptEntry {
-    pub sender: String,
-    pub sender_label: String,
-    pub initial: String,
-    pub is_human: bool,
-    pub is_step: bool,
-    pub is_continuation: bool,
-    pub role_class: String,
-    pub content: String,
-    /// Pre-rendered markdown→HTML for template use with `|safe`.
-    pub content_html: String,
-    pub timestamp: String,
-    /// Number of consecutive identical step messages collapsed into this one.
-    pub repeat_count: u32,
+/// Archived cards partial: cards hidden from the board, each with an
+/// Unarchive button.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/archived_cards.html")]
+pub struct ArchivedCardsTemplate {
+    pub spec_id: String,
+    pub cards: Vec<CardData>,
 }
 
-/// Render markdown content to HTML, stripping raw HTML tags from input
-/// to prevent XSS. Handles paragraphs, bold, italic, lists, code blocks,
-/// and links.
-/// Convert a spec title into a URL/filename-safe slug.
-fn slugify(title: &str) -> String {
-    let slug: String = title
-        .to_lowercase()
-        .chars()
-        .map(|c| {
-            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
-                c
-            } else {
-                '-'
-            }
-        })
-        .collect();
-    // Collapse multiple dashes and trim leading/trailing dashes
-    let mut result = String::new();
-    let mut prev_dash = false;
-    for c in slug.chars() {
-        if c == '-' {
-            if !prev_dash && !result.is_empty() {
-                result.push('-');
-            }
-            prev_dash = true;
-        } else {
-            result.push(c);
-            prev_dash = false;
+/// A spec's archived cards, most recently changed first.
+fn archived_cards_of(spec_state: &SpecState) -> Vec<CardData> {
+    let mut cards: Vec<&barnstormer_core::Card> =
+        spec_state.cards.values().filter(|c| c.archived).collect();
+    cards.sort_by(|a, b| {
+        b.updated_at
+            .cmp(&a.updated_at)
+            .then_with(|| b.card_id.cmp(&a.card_id))
+    });
+    cards.into_iter().map(CardData::from_card).collect()
+}
+
+/// GET /web/specs/{id}/archive - List the spec's archived cards.
+pub async fn archived_cards(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
         }
+    };
+
+    let spec_state = handle.read_state().await;
+    ArchivedCardsTemplate {
+        spec_id: id,
+        cards: archived_cards_of(&spec_state),
     }
-    result.trim_end_matches('-').to_string()
+    .into_response()
 }
 
-fn render_markdown(content: &str) -> String {
-    let options = Options::empty();
-    let parser = Parser::new_ext(content, options)
-        .filter(|event| !matches!(event, Event::Html(_) | Event::InlineHtml(_)));
-    let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
-    html_output
+/// Form data for closing a card's review request.
+#[derive(Deserialize)]
+pub struct CardReviewForm {
+    pub approved: bool,
 }
 
-/// Convert a TranscriptMessage to a TranscriptEntry for template rendering.
-fn to_transcript_entry(m: &barnstormer_core::TranscriptMessage) -> TranscriptEntry {
-    let (sender_label, is_human, role_class) = sender_display(&m.sender);
-    let initial = sender_label.chars().next().unwrap_or('?').to_string();
-    let content_html = render_markdown(&m.content);
-    TranscriptEntry {
-        sender: m.sender.clone(),
-        sender_label,
-        initial,
-        is_human,
-        is_step: m.kind.is_step(),
-        is_continuation: false,
-        role_class,
-        content: m.content.clone(),
-        content_html,
-        timestamp: m.timestamp.format("%H:%M:%S").to_string(),
-        repeat_count: 1,
-    }
-}
+/// POST /web/specs/{id}/cards/{card_id}/review - Approve a card an agent
+/// flagged for review, or dismiss the concern. Either way the flag is
+/// cleared; returns the refreshed board.
+pub async fn resolve_card_review(
+    State(state): State<SharedState>,
+    Path((id, card_id_str)): Path<(String, String)>,
+    Form(form): Form<CardReviewForm>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
 
-/// Mark consecutive entries from the same sender as continuations.
-/// The first entry in a run keeps `is_continuation = false`; subsequent
-/// entries from the same sender get `is_continuation = true` so the
-/// template can skip the avatar/name row.
-fn mark_continuations(entries: &mut [TranscriptEntry]) {
-    for i in 1..entries.len() {
-        if entries[i].sender == entries[i - 1].sender
-            && !entries[i].is_step
-            && !entries[i - 1].is_step
-        {
-            entries[i].is_continuation = true;
+    let card_id = match card_id_str.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid card ID.</p>".to_string()),
+            )
+                .into_response();
         }
-    }
-}
+    };
 
-/// Collapse consecutive identical step messages into a single entry with
-/// a repeat_count, so the UI can show "(x3)" instead of three identical lines.
-fn collapse_repeated_steps(entries: &mut Vec<TranscriptEntry>) {
-    let mut i = 0;
-    while i < entries.len() {
-        if entries[i].is_step {
-            let mut j = i + 1;
-            while j < entries.len()
-                && entries[j].is_step
-                && entries[j].content == entries[i].content
-            {
-                entries[i].repeat_count += 1;
-                j += 1;
-            }
-            if entries[i].repeat_count > 1 {
-                entries.drain((i + 1)..j);
-            }
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
         }
-        i += 1;
+    };
+
+    let cmd = Command::ResolveCardReview {
+        card_id,
+        approved: form.approved,
+        resolved_by: "human".to_string(),
+    };
+
+    if let Err(e) = handle.send_command(cmd).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to resolve review: {}</p>",
+                e
+            )),
+        )
+            .into_response();
     }
+
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
 }
 
-/// Returns true if the sender is part of the human ↔ manager conversation.
-/// Used to filter the chat tab to only show direct messages between the
-/// human and the manager agent, keeping other agents in the activity feed.
-fn is_chat_participant(sender: &str) -> bool {
-    sender == "human" || sender.starts_with("manager-")
+/// Form data for `PUT /web/specs/{id}/cards/{card_id}/move`: the lane to
+/// drop the card into and either its 0-based position there or an explicit
+/// sort order. `after` names the card it was dropped after (empty for the
+/// top of the lane) and wins over `index`, which a filtered board can only
+/// count among the cards it shows.
+#[derive(Deserialize)]
+pub struct MoveCardForm {
+    pub lane: String,
+    #[serde(default)]
+    pub index: Option<usize>,
+    #[serde(default)]
+    pub order: Option<f64>,
+    #[serde(default)]
+    pub after: Option<String>,
 }
 
-/// Derive a display label and CSS class from a raw sender ID.
-/// "human" → ("You", true, "human"), "manager-01J..." → ("Manager", false, "manager"), etc.
-fn sender_display(sender: &str) -> (String, bool, String) {
-    if sender == "human" {
-        return ("You".to_string(), true, "human".to_string());
-    }
-    // Agent IDs look like "manager-01JTEST..." or "brainstormer-01JTEST..."
-    let role = sender.split('-').next().unwrap_or(sender);
-    let label = match role {
-        "manager" => "Orchestrator",
-        "brainstormer" => "Researcher",
-        "planner" => "Architect",
-        "dot_generator" => "Dot Generator",
-        "critic" => "Critic",
-        _ => {
-            let mut capitalized = String::new();
-            for (i, ch) in role.chars().enumerate() {
-                if i == 0 {
-                    capitalized.extend(ch.to_uppercase());
-                } else {
-                    capitalized.push(ch);
-                }
-            }
-            return (capitalized, false, normalize_css_class(role));
-        }
-    };
-    let role_class = normalize_css_class(role);
-    (label.to_string(), false, role_class)
+/// Position just past `after` among the other active cards of `lane`, in
+/// the order `Command::ReorderCard` counts them. None when `after` is not
+/// one of them.
+fn index_after(spec_state: &SpecState, card_id: Ulid, lane: &str, after: Ulid) -> Option<usize> {
+    let mut siblings: Vec<&barnstormer_core::Card> = spec_state
+        .cards
+        .values()
+        .filter(|c| c.lane == lane && c.card_id != card_id && !c.archived)
+        .collect();
+    siblings.sort_by(|a, b| a.order.total_cmp(&b.order));
+    siblings
+        .iter()
+        .position(|c| c.card_id == after)
+        .map(|i| i + 1)
 }
 
-/// Normalize a string into a valid CSS class name: lowercase, replacing
-/// any character that is not `[a-z0-9_-]` with a hyphen.
-fn normalize_css_class(raw: &str) -> String {
-    raw.to_lowercase()
-        .chars()
-        .map(|c| {
-            if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-' {
-                c
-            } else {
-                '-'
-            }
-        })
-        .collect()
-}
+/// PUT /web/specs/{id}/cards/{card_id}/move - Drop a card in a lane, return
+/// the refreshed board. With `index` the server works out the sort order
+/// (see `Command::ReorderCard`); with `order` the card takes that order as is.
+pub async fn move_card(
+    State(state): State<SharedState>,
+    Path((id, card_id_str)): Path<(String, String)>,
+    Form(form): Form<MoveCardForm>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
 
-/// Question data for templates.
-pub enum QuestionData {
-    Boolean {
-        question_id: String,
-        question: String,
-        default: Option<bool>,
-    },
-    MultipleChoice {
-        question_id: String,
-        question: String,
-        choices: Vec<String>,
-        allow_multi: bool,
-    },
-    Freeform {
-        question_id: String,
-        question: String,
-        placeholder: String,
-    },
-}
+    let card_id = match card_id_str.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid card ID.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
 
-/// Convert a core UserQuestion into the template-friendly QuestionData.
-fn question_to_view_data(q: &barnstormer_core::UserQuestion) -> QuestionData {
-    match q {
-        barnstormer_core::UserQuestion::Boolean {
-            question_id,
-            question,
-            default,
-        } => QuestionData::Boolean {
-            question_id: question_id.to_string(),
-            question: render_markdown(question),
-            default: *default,
-        },
-        barnstormer_core::UserQuestion::MultipleChoice {
-            question_id,
-            question,
-            choices,
-            allow_multi,
-        } => QuestionData::MultipleChoice {
-            question_id: question_id.to_string(),
-            question: render_markdown(question),
-            choices: choices.clone(),
-            allow_multi: *allow_multi,
-        },
-        barnstormer_core::UserQuestion::Freeform {
-            question_id,
-            question,
-            placeholder,
-            ..
-        } => QuestionData::Freeform {
-            question_id: question_id.to_string(),
-            question: render_markdown(question),
-            placeholder: placeholder.clone().unwrap_or_default(),
-        },
-    }
-}
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
 
-/// Query parameters for the transcript endpoint, allowing callers to specify
-/// which container the response should target (activity panel vs chat tab).
-/// The optional `part` field selects a sub-section: "feed" for messages only,
-/// "question" for the question card only, or omitted for the full transcript.
-#[derive(Deserialize)]
-pub struct TranscriptQuery {
-    pub container_id: Option<String>,
-    pub part: Option<String>,
-}
+    let lane = form.lane.trim().to_string();
+    if lane.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html("<p class=\"error-msg\">Give a lane to move the card to.</p>".to_string()),
+        )
+            .into_response();
+    }
 
-/// Validate and sanitize a container_id value. Only known IDs are accepted;
-/// anything else falls back to "chat-transcript" to prevent XSS via
-/// user-controlled values rendered into script tags and HTMX attributes.
-///
-/// Allowed IDs and where they are used:
-/// - "activity-transcript" -- activity panel transcript (default for activity handlers)
-/// - "chat-transcript"     -- chat panel transcript in refining phase
-/// - "brainstorm-chat"     -- chat panel transcript in brainstorming phase
-/// - "mission-ticker"      -- compact ticker strip; also the hx-target for answer forms
-fn sanitize_container_id(raw: &str) -> String {
-    match raw {
-        "activity-transcript" | "chat-transcript" | "mission-ticker" | "brainstorm-chat" => {
-            raw.to_string()
+    let index = match form.after.as_deref().map(str::trim) {
+        Some("") => Some(0),
+        Some(after) => {
+            let spec_state = handle.read_state().await;
+            after
+                .parse::<Ulid>()
+                .ok()
+                .and_then(|after| index_after(&spec_state, card_id, &lane, after))
+                .or(form.index)
         }
-        _ => "chat-transcript".to_string(),
+        None => form.index,
+    };
+    let cmd = match (index, form.order) {
+        (Some(index), _) => Command::ReorderCard {
+            card_id,
+            lane,
+            index,
+            updated_by: "human".to_string(),
+        },
+        (None, Some(order)) if order.is_finite() => Command::MoveCard {
+            card_id,
+            lane,
+            order,
+            updated_by: "human".to_string(),
+        },
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Give an index or a finite order.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
+    if let Err(e) = handle.send_command(cmd).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to move card: {}</p>",
+                html_escape(&e.to_string())
+            )),
+        )
+            .into_response();
     }
-}
 
-/// Activity panel template.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/activity.html")]
-pub struct ActivityTemplate {
-    pub spec_id: String,
-    pub container_id: String,
-    pub transcript: Vec<TranscriptEntry>,
-    pub pending_question: Option<QuestionData>,
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
 }
 
-/// Activity transcript partial template (transcript entries + question widget only).
-/// Used by the SSE refresh target so that chat input is not wiped on updates.
-/// The `container_id` field controls the DOM IDs so the same template can serve
-/// both the mission ticker and the full-width chat tab.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/activity_transcript.html")]
-pub struct ActivityTranscriptTemplate {
-    pub spec_id: String,
-    pub container_id: String,
-    pub transcript: Vec<TranscriptEntry>,
-    pub pending_question: Option<QuestionData>,
+/// Order that places a card directly after `card` in its lane: halfway to
+/// the next card, or one step past `card` when it is last.
+fn order_after(spec_state: &SpecState, card: &barnstormer_core::Card) -> f64 {
+    let next = spec_state
+        .cards
+        .values()
+        .filter(|c| c.lane == card.lane && c.card_id != card.card_id && c.order > card.order)
+        .map(|c| c.order)
+        .fold(None, |min: Option<f64>, o| {
+            Some(min.map_or(o, |m| m.min(o)))
+        });
+    match next {
+        Some(next) => (card.order + next) / 2.0,
+        None => card.order + 1.0,
+    }
 }
 
-/// GET /web/specs/{id}/activity - Render the activity panel.
-pub async fn activity(
+/// POST /web/specs/{id}/cards/{card_id}/duplicate - Copy a card's type,
+/// title (with " (copy)"), and body into a new card placed right after it
+/// in the same lane, then return the refreshed board. Refs are not copied so
+/// the duplicate does not silently share references with the original.
+pub async fn duplicate_card(
     State(state): State<SharedState>,
-    Path(id): Path<String>,
+    Path((id, card_id_str)): Path<(String, String)>,
 ) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
+    let card_id = match card_id_str.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid card ID.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
         Some(h) => h,
@@ -1505,36 +2205,81 @@ pub async fn activity(
         }
     };
 
-    let spec_state = handle.read_state().await;
+    let (source, order) = {
+        let spec_state = handle.read_state().await;
+        match spec_state.cards.get(&card_id) {
+            Some(card) => (card.clone(), order_after(&spec_state, card)),
+            None => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Html("<p class=\"error-msg\">Card not found.</p>".to_string()),
+                )
+                    .into_response();
+            }
+        }
+    };
 
-    let mut transcript: Vec<TranscriptEntry> = spec_state
-        .transcript
-        .iter()
-        .map(to_transcript_entry)
-        .collect();
-    mark_continuations(&mut transcript);
-    collapse_repeated_steps(&mut transcript);
+    // The copy is created at the head of the lane, then moved into place.
+    let result = async {
+        let events = handle
+            .send_command(Command::CreateCard {
+                card_type: source.card_type,
+                title: format!("{} (copy)", source.title),
+                body: source.body,
+                lane: Some(source.lane.clone()),
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await?;
+        let new_card_id = events.iter().find_map(|event| match &event.payload {
+            barnstormer_core::EventPayload::CardCreated { card } => Some(card.card_id),
+            _ => None,
+        });
+        if let Some(new_card_id) = new_card_id {
+            handle
+                .send_command(Command::MoveCard {
+                    card_id: new_card_id,
+                    lane: source.lane,
+                    order,
+                    updated_by: "human".to_string(),
+                })
+                .await?;
+        }
+        Ok::<(), ActorError>(())
+    }
+    .await;
+    if let Err(e) = result {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to duplicate card: {}</p>",
+                e
+            )),
+        )
+            .into_response();
+    }
 
-    let pending_question = spec_state
-        .pending_question
-        .as_ref()
-        .map(question_to_view_data);
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
+}
 
-    ActivityTemplate {
-        spec_id: id,
-        container_id: "activity-transcript".to_string(),
-        transcript,
-        pending_question,
-    }
-    .into_response()
+/// Search results partial: links that scroll the board to a matching card
+/// or the chat to a matching message.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/search_results.html")]
+pub struct SearchResultsTemplate {
+    pub spec_id: String,
+    pub query: String,
+    pub hits: Vec<SearchResult>,
 }
 
-/// GET /web/specs/{id}/activity/transcript - Render only the transcript + question widget.
-/// Used as the SSE refresh target so chat input is preserved during live updates.
-pub async fn activity_transcript(
+/// GET /web/specs/{id}/search?q=... - Render full-text search results for
+/// the spec's cards and transcript. An empty query renders nothing.
+pub async fn search(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-    Query(query): Query<TranscriptQuery>,
+    Query(query): Query<crate::api::specs::SearchQuery>,
 ) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
@@ -1554,111 +2299,52 @@ pub async fn activity_transcript(
     };
 
     let spec_state = handle.read_state().await;
+    let q = query.q.trim().to_string();
+    match crate::search::search_spec(&state.barnstormer_home, spec_id, &spec_state, &q) {
+        Ok(hits) => SearchResultsTemplate {
+            spec_id: id,
+            query: q,
+            hits,
+        }
+        .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html(format!("<p class=\"error-msg\">Search failed: {}</p>", e)),
+        )
+            .into_response(),
+    }
+}
 
-    let pending_question = spec_state
-        .pending_question
-        .as_ref()
-        .map(question_to_view_data);
-
-    let container_id = sanitize_container_id(
-        query
-            .container_id
-            .as_deref()
-            .unwrap_or("activity-transcript"),
-    );
+/// Cross-spec search results partial: links into the spec holding each hit.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/global_search_results.html")]
+pub struct GlobalSearchResultsTemplate {
+    pub query: String,
+    pub hits: Vec<SpecSearchResult>,
+}
 
-    // Chat containers only show human + manager messages (filtered by
-    // is_chat_participant) so the user sees a clean conversation thread.
-    // The activity-transcript and mission-ticker containers show all senders.
-    let is_chat = container_id == "chat-transcript" || container_id == "brainstorm-chat";
+/// GET /web/search?q=... - Render full-text search results across every
+/// loaded spec. An empty query renders nothing.
+pub async fn search_all(
+    State(state): State<SharedState>,
+    Query(query): Query<crate::api::specs::SearchQuery>,
+) -> impl IntoResponse {
+    let q = query.q.trim().to_string();
+    let hits = crate::search::search_all_specs(&state.barnstormer_home, &state.actors, &q).await;
+    GlobalSearchResultsTemplate { query: q, hits }
+}
 
-    let mut transcript: Vec<TranscriptEntry> = spec_state
-        .transcript
-        .iter()
-        .filter(|m| !is_chat || is_chat_participant(&m.sender))
-        .map(to_transcript_entry)
-        .collect();
-    mark_continuations(&mut transcript);
-    collapse_repeated_steps(&mut transcript);
-
-    let part = query.part.as_deref().unwrap_or("");
-
-    if is_chat && part == "feed" {
-        ChatFeedTemplate {
-            spec_id: id,
-            container_id,
-            transcript,
-        }
-        .into_response()
-    } else if is_chat && part == "question" {
-        ChatQuestionTemplate {
-            spec_id: id,
-            container_id,
-            pending_question,
-        }
-        .into_response()
-    } else if is_chat {
-        ChatTranscriptTemplate {
-            spec_id: id,
-            container_id,
-            transcript,
-            pending_question,
-        }
-        .into_response()
-    } else {
-        ActivityTranscriptTemplate {
-            spec_id: id,
-            container_id,
-            transcript,
-            pending_question,
-        }
-        .into_response()
-    }
-}
-
-/// Chat-style transcript for SSE refresh in the Chat tab.
-/// Uses distinct markup from ActivityTranscriptTemplate — avatars, larger bubbles.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/chat_transcript.html")]
-pub struct ChatTranscriptTemplate {
-    pub spec_id: String,
-    pub container_id: String,
-    pub transcript: Vec<TranscriptEntry>,
-    pub pending_question: Option<QuestionData>,
-}
-
-/// Chat message feed partial — messages, throbber, streaming, empty state.
-/// Rendered independently so transcript refreshes don't disturb the question card.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/chat_feed.html")]
-pub struct ChatFeedTemplate {
-    pub spec_id: String,
-    pub container_id: String,
-    pub transcript: Vec<TranscriptEntry>,
-}
-
-/// Chat question card partial — pending question with answer form.
-/// Rendered independently so question refreshes don't disturb the message feed.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/chat_question.html")]
-pub struct ChatQuestionTemplate {
-    pub spec_id: String,
-    pub container_id: String,
-    pub pending_question: Option<QuestionData>,
-}
-
-/// Chat panel template for the full-width Chat tab.
+/// Cards feed partial: reverse-chronological list of all captured cards for the
+/// brainstorming sidebar. Self-refreshes on card SSE events.
 #[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/chat_panel.html")]
-pub struct ChatPanelTemplate {
+#[template(path = "partials/cards_feed.html")]
+pub struct CardsFeedTemplate {
     pub spec_id: String,
-    pub container_id: String,
-    pub transcript: Vec<TranscriptEntry>,
-    pub pending_question: Option<QuestionData>,
+    pub cards: Vec<CardData>,
 }
 
-/// GET /web/specs/{id}/chat-panel - Render the Chat tab content.
-pub async fn chat_panel(
+/// GET /web/specs/{id}/cards-feed - Render the flat card list for the brainstorm sidebar.
+pub async fn cards_feed(
     State(state): State<SharedState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
@@ -1680,51 +2366,84 @@ pub async fn chat_panel(
     };
 
     let spec_state = handle.read_state().await;
+    // Newest-first: sort by updated_at descending. Break ties with card_id
+    // (ULID) descending so ordering is deterministic even when cards created
+    // in the same clock tick share a formatted updated_at string.
+    let mut sorted: Vec<&barnstormer_core::Card> =
+        spec_state.cards.values().filter(|c| !c.archived).collect();
+    sorted.sort_by(|a, b| {
+        b.updated_at
+            .cmp(&a.updated_at)
+            .then_with(|| b.card_id.cmp(&a.card_id))
+    });
+    let cards: Vec<CardData> = sorted.into_iter().map(CardData::from_card).collect();
 
-    let container_id = if spec_state.phase == SpecPhase::Brainstorming {
-        "brainstorm-chat".to_string()
-    } else {
-        "chat-transcript".to_string()
-    };
-
-    let mut transcript: Vec<TranscriptEntry> = spec_state
-        .transcript
-        .iter()
-        .filter(|m| is_chat_participant(&m.sender))
-        .map(to_transcript_entry)
-        .collect();
-    mark_continuations(&mut transcript);
-    collapse_repeated_steps(&mut transcript);
-
-    let pending_question = spec_state
-        .pending_question
-        .as_ref()
-        .map(question_to_view_data);
-
-    ChatPanelTemplate {
-        spec_id: id,
-        container_id,
-        transcript,
-        pending_question,
-    }
-    .into_response()
+    CardsFeedTemplate { spec_id: id, cards }.into_response()
 }
 
-/// Artifacts tab template showing exported spec content in multiple formats.
+/// Document view template.
 #[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/artifacts.html")]
-pub struct ArtifactsTemplate {
+#[template(path = "partials/document.html")]
+pub struct DocumentTemplate {
     pub spec_id: String,
+    pub title: String,
     pub title_slug: String,
-    pub markdown_content: String,
-    pub yaml_content: String,
-    pub dot_content: String,
+    pub one_liner: String,
+    /// Links to external trackers, shown under the one-liner.
+    pub external_links: Vec<ExternalLink>,
+    pub goal: String,
+    pub goal_html: String,
+    pub description: Option<String>,
+    pub description_html: Option<String>,
+    pub constraints: Option<String>,
+    pub constraints_html: Option<String>,
+    pub success_criteria: Option<String>,
+    pub success_criteria_html: Option<String>,
+    pub risks: Option<String>,
+    pub risks_html: Option<String>,
+    pub notes: Option<String>,
+    pub notes_html: Option<String>,
+    pub done_checklist: Vec<ChecklistItem>,
+    pub lanes: Vec<LaneData>,
+    /// Section IDs (see [`DOCUMENT_SECTIONS`]) the user has collapsed.
+    pub collapsed_sections: Vec<String>,
 }
 
-/// GET /web/specs/{id}/artifacts - Render the Artifacts tab with all three export formats.
-pub async fn artifacts(
+impl DocumentTemplate {
+    fn is_collapsed(&self, section: &str) -> bool {
+        self.collapsed_sections.iter().any(|s| s == section)
+    }
+}
+
+/// IDs of the collapsible document sections, in page order.
+pub const DOCUMENT_SECTIONS: &[&str] = &[
+    "goal",
+    "description",
+    "constraints",
+    "success-criteria",
+    "done-checklist",
+    "risks",
+    "notes",
+    "cards",
+];
+
+/// The bearer token a request was made with, used to key per-user UI
+/// preferences. The web UI itself is unauthenticated, so browser requests
+/// usually carry none and share the anonymous preferences.
+fn bearer_token(headers: &axum::http::HeaderMap) -> Option<String> {
+    headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| t.trim().to_string())
+}
+
+/// GET /web/specs/{id}/document - Render the spec as a narrative document,
+/// with the requesting user's collapsed sections applied.
+pub async fn document(
     State(state): State<SharedState>,
     Path(id): Path<String>,
+    headers: axum::http::HeaderMap,
 ) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
@@ -1744,48 +2463,127 @@ pub async fn artifacts(
     };
 
     let spec_state = handle.read_state().await;
+    let core = match &spec_state.core {
+        Some(c) => c,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec has no core data.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
 
-    let markdown_content = barnstormer_core::export::export_markdown(&spec_state);
-    let yaml_content = barnstormer_core::export::export_yaml(&spec_state)
-        .unwrap_or_else(|e| format!("# YAML export error: {}", e));
-    let dot_content = barnstormer_core::export::export_dot(&spec_state);
-
-    let title_slug = spec_state
-        .core
-        .as_ref()
-        .map(|c| slugify(&c.title))
-        .unwrap_or_else(|| "spec".to_string());
+    let lanes = cards_by_lane(&spec_state);
+    let collapsed_sections = match state
+        .ui_prefs
+        .lock()
+        .await
+        .get(bearer_token(&headers).as_deref(), spec_id)
+    {
+        Ok(prefs) => prefs.collapsed_sections,
+        Err(e) => {
+            tracing::warn!("failed to read UI preferences: {}", e);
+            Vec::new()
+        }
+    };
 
-    ArtifactsTemplate {
+    DocumentTemplate {
         spec_id: id,
-        title_slug,
-        markdown_content,
-        yaml_content,
-        dot_content,
+        title_slug: slugify(&core.title),
+        title: core.title.clone(),
+        one_liner: core.one_liner.clone(),
+        external_links: core.external_links.clone(),
+        goal: core.goal.clone(),
+        goal_html: render_markdown(&core.goal),
+        description: core.description.clone(),
+        description_html: core.description.as_ref().map(|d| render_markdown(d)),
+        constraints: core.constraints.clone(),
+        constraints_html: core.constraints.as_ref().map(|c| render_markdown(c)),
+        success_criteria: core.success_criteria.clone(),
+        success_criteria_html: core.success_criteria.as_ref().map(|s| render_markdown(s)),
+        risks: core.risks.clone(),
+        risks_html: core.risks.as_ref().map(|r| render_markdown(r)),
+        notes: core.notes.clone(),
+        notes_html: core
+            .notes
+            .as_deref()
+            .filter(|n| !n.trim().is_empty())
+            .map(render_markdown),
+        done_checklist: spec_state.done_checklist.clone(),
+        lanes,
+        collapsed_sections,
     }
     .into_response()
 }
 
-/// Spec tab template showing a synthesized specification document.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/spec.html")]
-pub struct SpecTabTemplate {
-    pub spec_id: String,
-    pub title_slug: String,
-    pub spec_html: String,
-    pub spec_markdown: String,
+/// Form data for saving UI preferences: comma-separated collapsed section IDs.
+#[derive(Debug, Deserialize)]
+pub struct UiPrefsForm {
+    #[serde(default)]
+    pub collapsed: String,
 }
 
-/// GET /web/specs/{id}/spec - Render the synthesized Spec tab.
-pub async fn spec(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
+/// POST /web/specs/{id}/ui-prefs - Remember which document sections the
+/// requesting user has collapsed. Unknown section IDs are dropped.
+pub async fn save_ui_prefs(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    headers: axum::http::HeaderMap,
+    Form(form): Form<UiPrefsForm>,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    if !state.actors.read().await.contains_key(&spec_id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
+    }
+
+    let collapsed_sections: Vec<String> = DOCUMENT_SECTIONS
+        .iter()
+        .filter(|section| form.collapsed.split(',').any(|s| s.trim() == **section))
+        .map(|section| section.to_string())
+        .collect();
+    let prefs = barnstormer_store::UiPrefs { collapsed_sections };
+    match state
+        .ui_prefs
+        .lock()
+        .await
+        .set(bearer_token(&headers).as_deref(), spec_id, prefs)
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            tracing::error!("failed to save UI preferences for spec {}: {}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("<p class=\"error-msg\">Failed to save preferences.</p>".to_string()),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// POST /web/specs/{id}/notes - Replace the spec's freeform notes from the
+/// document view's inline editor, then return the refreshed document. Notes
+/// live on the spec core, so they never show up on the board.
+pub async fn update_spec_notes(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    headers: axum::http::HeaderMap,
+    Form(form): Form<NotesForm>,
+) -> Response {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    let actors = state.actors.read().await;
-    let handle = match actors.get(&spec_id) {
-        Some(h) => h,
+    let handle = match state.actors.read().await.get(&spec_id) {
+        Some(h) => h.clone(),
         None => {
             return (
                 StatusCode::NOT_FOUND,
@@ -1795,37 +2593,53 @@ pub async fn spec(State(state): State<SharedState>, Path(id): Path<String>) -> i
         }
     };
 
-    let spec_state = handle.read_state().await;
-    let title_slug = spec_state
-        .core
-        .as_ref()
-        .map(|c| slugify(&c.title))
-        .unwrap_or_else(|| "spec".to_string());
-    let spec_markdown = barnstormer_core::export::export_spec(&spec_state);
-    let spec_html = render_markdown(&spec_markdown);
-
-    SpecTabTemplate {
-        spec_id: id,
-        title_slug,
-        spec_html,
-        spec_markdown,
+    let cmd = Command::UpdateSpecCore {
+        title: None,
+        one_liner: None,
+        goal: None,
+        description: None,
+        constraints: None,
+        success_criteria: None,
+        risks: None,
+        notes: Some(form.notes),
+    };
+    if let Err(e) = handle.send_command(cmd).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to save notes: {}</p>",
+                e
+            )),
+        )
+            .into_response();
     }
-    .into_response()
+
+    document(State(state), Path(id), headers)
+        .await
+        .into_response()
 }
 
-/// GET /web/specs/{id}/export/markdown - Download spec as Markdown file.
-pub async fn export_markdown(
-    State(state): State<SharedState>,
-    Path(id): Path<String>,
-) -> impl IntoResponse {
+/// Form data for adding a definition-of-done item.
+#[derive(Debug, Deserialize)]
+pub struct ChecklistItemForm {
+    pub text: String,
+}
+
+/// Send a checklist command from the document view, then return the
+/// refreshed document.
+async fn apply_checklist_command(
+    state: SharedState,
+    id: String,
+    headers: axum::http::HeaderMap,
+    cmd: Command,
+) -> Response {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    let actors = state.actors.read().await;
-    let handle = match actors.get(&spec_id) {
-        Some(h) => h,
+    let handle = match state.actors.read().await.get(&spec_id) {
+        Some(h) => h.clone(),
         None => {
             return (
                 StatusCode::NOT_FOUND,
@@ -1834,77 +2648,94 @@ pub async fn export_markdown(
                 .into_response();
         }
     };
-
-    let spec_state = handle.read_state().await;
-    let slug = spec_state
-        .core
-        .as_ref()
-        .map(|c| slugify(&c.title))
-        .unwrap_or_else(|| "spec".to_string());
-    let content = barnstormer_core::export::export_markdown(&spec_state);
-
-    Response::builder()
-        .header("content-type", "text/markdown; charset=utf-8")
-        .header(
-            "content-disposition",
-            format!("attachment; filename=\"{}-spec.md\"", slug),
+    if let Err(e) = handle.send_command(cmd).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to update checklist: {}</p>",
+                e
+            )),
         )
-        .body(axum::body::Body::from(content))
-        .unwrap()
+            .into_response();
+    }
+
+    document(State(state), Path(id), headers)
+        .await
         .into_response()
 }
 
-/// GET /web/specs/{id}/export/yaml - Download spec as YAML file.
-pub async fn export_yaml(
+/// POST /web/specs/{id}/checklist - Add an unchecked definition-of-done item.
+pub async fn add_checklist_item(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-) -> impl IntoResponse {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
+    headers: axum::http::HeaderMap,
+    Form(form): Form<ChecklistItemForm>,
+) -> Response {
+    apply_checklist_command(
+        state,
+        id,
+        headers,
+        Command::AddChecklistItem { text: form.text },
+    )
+    .await
+}
+
+/// POST /web/specs/{id}/checklist/{item_id}/toggle - Check or uncheck an item.
+pub async fn toggle_checklist_item(
+    State(state): State<SharedState>,
+    Path((id, item_id)): Path<(String, String)>,
+    headers: axum::http::HeaderMap,
+) -> Response {
+    let Ok(item_id) = item_id.parse::<Ulid>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html("<p class=\"error-msg\">Invalid checklist item ID.</p>".to_string()),
+        )
+            .into_response();
     };
+    apply_checklist_command(state, id, headers, Command::ToggleChecklistItem { item_id }).await
+}
 
-    let actors = state.actors.read().await;
-    let handle = match actors.get(&spec_id) {
-        Some(h) => h,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
-            )
-                .into_response();
-        }
+/// POST /web/specs/{id}/checklist/{item_id}/remove - Delete an item.
+pub async fn remove_checklist_item(
+    State(state): State<SharedState>,
+    Path((id, item_id)): Path<(String, String)>,
+    headers: axum::http::HeaderMap,
+) -> Response {
+    let Ok(item_id) = item_id.parse::<Ulid>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html("<p class=\"error-msg\">Invalid checklist item ID.</p>".to_string()),
+        )
+            .into_response();
     };
+    apply_checklist_command(state, id, headers, Command::RemoveChecklistItem { item_id }).await
+}
 
-    let spec_state = handle.read_state().await;
-    let slug = spec_state
-        .core
-        .as_ref()
-        .map(|c| slugify(&c.title))
-        .unwrap_or_else(|| "spec".to_string());
-    match barnstormer_core::export::export_yaml(&spec_state) {
-        Ok(content) => Response::builder()
-            .header("content-type", "text/yaml; charset=utf-8")
-            .header(
-                "content-disposition",
-                format!("attachment; filename=\"{}-spec.yaml\"", slug),
-            )
-            .body(axum::body::Body::from(content))
-            .unwrap()
-            .into_response(),
-        Err(e) => {
-            tracing::error!("YAML export failed for spec {}: {}", id, e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Html("<p class=\"error-msg\">Failed to export YAML.</p>".to_string()),
-            )
-                .into_response()
-        }
-    }
+/// A titled narrative section of the printable spec (Description, Risks, ...).
+pub struct PrintSection {
+    pub heading: String,
+    pub html: String,
 }
 
-/// GET /web/specs/{id}/export/dot - Download spec as DOT graph file.
-pub async fn export_dot(
+/// Printer-friendly full page: document narrative, board summary, and
+/// decisions log with print CSS and no interactive controls.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "print.html")]
+pub struct PrintTemplate {
+    pub title: String,
+    pub one_liner: String,
+    pub phase: String,
+    pub goal_html: String,
+    pub sections: Vec<PrintSection>,
+    pub lanes: Vec<LaneData>,
+    pub decisions: Vec<CardData>,
+    pub printed_at: String,
+}
+
+/// GET /web/specs/{id}/print - Full-page print view, suitable for the
+/// browser's "Save as PDF".
+pub async fn print_view(
     State(state): State<SharedState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
@@ -1926,70 +2757,81 @@ pub async fn export_dot(
     };
 
     let spec_state = handle.read_state().await;
-    let slug = spec_state
-        .core
-        .as_ref()
-        .map(|c| slugify(&c.title))
-        .unwrap_or_else(|| "spec".to_string());
-    let content = barnstormer_core::export::export_dot(&spec_state);
-
-    Response::builder()
-        .header("content-type", "text/plain; charset=utf-8")
-        .header(
-            "content-disposition",
-            format!("attachment; filename=\"{}-spec.dot\"", slug),
-        )
-        .body(axum::body::Body::from(content))
-        .unwrap()
-        .into_response()
-}
-
-/// GET /web/specs/{id}/export/spec - Download synthesized spec as Markdown file.
-pub async fn export_spec_download(
-    State(state): State<SharedState>,
-    Path(id): Path<String>,
-) -> impl IntoResponse {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
-    };
-
-    let actors = state.actors.read().await;
-    let handle = match actors.get(&spec_id) {
-        Some(h) => h,
+    let core = match &spec_state.core {
+        Some(c) => c,
         None => {
             return (
                 StatusCode::NOT_FOUND,
-                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+                Html("<p class=\"error-msg\">Spec has no core data.</p>".to_string()),
             )
                 .into_response();
         }
     };
 
-    let spec_state = handle.read_state().await;
-    let slug = spec_state
-        .core
-        .as_ref()
-        .map(|c| slugify(&c.title))
-        .unwrap_or_else(|| "spec".to_string());
-    let content = barnstormer_core::export::export_spec(&spec_state);
-    let filename = format!("{}-spec.md", slug);
+    let sections = [
+        ("Description", &core.description),
+        ("Constraints", &core.constraints),
+        ("Success Criteria", &core.success_criteria),
+        ("Risks", &core.risks),
+        ("Notes", &core.notes),
+    ]
+    .into_iter()
+    .filter_map(|(heading, text)| {
+        text.as_ref().map(|t| PrintSection {
+            heading: heading.to_string(),
+            html: render_markdown(t),
+        })
+    })
+    .collect();
 
-    Response::builder()
-        .header("content-type", "text/markdown; charset=utf-8")
-        .header(
-            "content-disposition",
-            format!("attachment; filename=\"{}\"", filename),
-        )
-        .body(axum::body::Body::from(content))
-        .unwrap()
-        .into_response()
+    let mut decision_cards: Vec<&barnstormer_core::Card> = spec_state
+        .cards
+        .values()
+        .filter(|c| c.card_type == "decision")
+        .collect();
+    decision_cards.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.card_id.cmp(&b.card_id))
+    });
+
+    PrintTemplate {
+        title: core.title.clone(),
+        one_liner: core.one_liner.clone(),
+        phase: match spec_state.phase {
+            SpecPhase::Brainstorming => "brainstorming".to_string(),
+            SpecPhase::Refining => "refining".to_string(),
+            SpecPhase::Complete => "complete".to_string(),
+        },
+        goal_html: render_markdown(&core.goal),
+        sections,
+        lanes: cards_by_lane(&spec_state),
+        decisions: decision_cards
+            .into_iter()
+            .map(CardData::from_card)
+            .collect(),
+        printed_at: chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string(),
+    }
+    .into_response()
 }
 
-/// POST /web/specs/{id}/regenerate - Regenerate exports and save to disk.
-/// Writes markdown, YAML, and DOT files to $BARNSTORMER_HOME/<spec_id>/exports/.
-/// Returns an HTML snippet confirming the export.
-pub async fn regenerate(
+/// How often the embedded board re-fetches its lanes.
+const EMBED_REFRESH_SECONDS: u32 = 30;
+
+/// Standalone read-only board for embedding one spec in an iframe.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "embed_board.html")]
+pub struct EmbedBoardTemplate {
+    pub spec_id: String,
+    pub title: String,
+    pub lanes: Vec<LaneData>,
+    pub refresh_seconds: u32,
+}
+
+/// GET /embed/specs/{id}/board - Read-only board outside the app chrome,
+/// for iframes. Framing is limited by `Content-Security-Policy:
+/// frame-ancestors`, taken from `AppState::embed_frame_ancestors`.
+pub async fn embed_board(
     State(state): State<SharedState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
@@ -2011,265 +2853,425 @@ pub async fn regenerate(
     };
 
     let spec_state = handle.read_state().await;
+    let title = spec_state
+        .core
+        .as_ref()
+        .map(|c| c.title.clone())
+        .unwrap_or_default();
+
+    (
+        [(
+            axum::http::header::CONTENT_SECURITY_POLICY,
+            format!("frame-ancestors {}", state.embed_frame_ancestors),
+        )],
+        EmbedBoardTemplate {
+            spec_id: id,
+            title,
+            lanes: cards_by_lane(&spec_state),
+            refresh_seconds: EMBED_REFRESH_SECONDS,
+        },
+    )
+        .into_response()
+}
 
-    // Export all formats
-    let markdown_content = barnstormer_core::export::export_markdown(&spec_state);
-    let yaml_content = barnstormer_core::export::export_yaml(&spec_state)
-        .unwrap_or_else(|e| format!("# YAML export error: {}", e));
-    let dot_content = barnstormer_core::export::export_dot(&spec_state);
+/// Activity transcript data for templates.
+pub struct TranscriptEntry {
+    /// The message's ULID, used for its `msg-<id>` anchor.
+    pub message_id: String,
+    pub sender: String,
+    pub sender_label: String,
+    pub initial: String,
+    pub is_human: bool,
+    pub is_step: bool,
+    pub is_continuation: bool,
+    pub role_class: String,
+    pub content: String,
+    /// Pre-rendered markdown→HTML for template use with `|safe`.
+    pub content_html: String,
+    pub timestamp: String,
+    /// Number of consecutive identical step messages collapsed into this one.
+    pub repeat_count: u32,
+    /// Provider/model that produced an agent message, when recorded.
+    pub agent_model: Option<barnstormer_core::AgentModel>,
+    /// Whether this is the message a permalink pointed at.
+    pub is_highlighted: bool,
+}
 
-    // Write to $BARNSTORMER_HOME/specs/<spec_id>/exports/
-    let exports_dir = state
-        .barnstormer_home
-        .join("specs")
-        .join(spec_id.to_string())
-        .join("exports");
-    if let Err(e) = std::fs::create_dir_all(&exports_dir) {
-        tracing::error!("failed to create exports directory: {}", e);
-    } else {
-        let slug = spec_state
-            .core
-            .as_ref()
-            .map(|c| slugify(&c.title))
-            .unwrap_or_else(|| "spec".to_string());
+/// Characters of a message shown before compact mode folds the rest away.
+const COMPACT_PREVIEW_CHARS: usize = 280;
 
-        if let Err(e) = std::fs::write(exports_dir.join(format!("{}.md", slug)), &markdown_content)
-        {
-            tracing::error!("failed to write markdown export: {}", e);
-        }
-        if let Err(e) = std::fs::write(exports_dir.join(format!("{}.yaml", slug)), &yaml_content) {
-            tracing::error!("failed to write YAML export: {}", e);
-        }
-        if let Err(e) = std::fs::write(exports_dir.join(format!("{}.dot", slug)), &dot_content) {
-            tracing::error!("failed to write DOT export: {}", e);
+impl TranscriptEntry {
+    /// The start of a long message as plain text, for compact mode's
+    /// "show more" toggle. None when the message is short enough to show whole.
+    pub fn compact_preview(&self) -> Option<String> {
+        if self.content.chars().count() <= COMPACT_PREVIEW_CHARS {
+            return None;
         }
-        tracing::info!(
-            "regenerated exports for spec {} at {}",
-            spec_id,
-            exports_dir.display()
-        );
+        let preview: String = self.content.chars().take(COMPACT_PREVIEW_CHARS).collect();
+        Some(preview.trim_end().to_string())
     }
-
-    Html("<span class=\"regen-confirm\">Exports saved successfully.</span>".to_string())
-        .into_response()
 }
 
-/// Form data for sending a chat message.
-#[derive(Deserialize)]
-pub struct ChatForm {
-    pub message: String,
+/// Render markdown content to HTML, stripping raw HTML tags from input
+/// to prevent XSS. Handles paragraphs, bold, italic, lists, code blocks,
+/// and links.
+/// Convert a spec title into a URL/filename-safe slug.
+fn slugify(title: &str) -> String {
+    let slug: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    // Collapse multiple dashes and trim leading/trailing dashes
+    let mut result = String::new();
+    let mut prev_dash = false;
+    for c in slug.chars() {
+        if c == '-' {
+            if !prev_dash && !result.is_empty() {
+                result.push('-');
+            }
+            prev_dash = true;
+        } else {
+            result.push(c);
+            prev_dash = false;
+        }
+    }
+    result.trim_end_matches('-').to_string()
 }
 
-/// Form data for answering a question.
-#[derive(Deserialize)]
-pub struct AnswerForm {
-    pub question_id: String,
-    pub answer: String,
+/// Build a download filename from the configured export pattern. The
+/// placeholders are filled in first and the whole stem is then restricted to
+/// filename-safe characters, so neither the title nor the pattern can inject
+/// path separators or quotes into the `content-disposition` header.
+fn export_filename(pattern: &str, spec_id: Ulid, title: Option<&str>, extension: &str) -> String {
+    let id = spec_id.to_string();
+    let slug = title.map(slugify).filter(|s| !s.is_empty());
+    let stem = pattern
+        .replace("{slug}", slug.as_deref().unwrap_or("spec"))
+        .replace("{short_id}", &id[id.len() - 6..])
+        .replace("{id}", &id);
+    let safe: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let safe = safe.trim_matches(|c| c == '-' || c == '.');
+    let stem = if safe.is_empty() { "spec" } else { safe };
+    format!("{}.{}", stem, extension)
 }
 
-/// POST /web/specs/{id}/answer - Submit an answer to a pending question.
-pub async fn answer_question(
-    State(state): State<SharedState>,
-    Path(id): Path<String>,
-    headers: axum::http::HeaderMap,
-    Form(form): Form<AnswerForm>,
-) -> impl IntoResponse {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
-    };
+fn render_markdown(content: &str) -> String {
+    let options = Options::empty();
+    let parser = Parser::new_ext(content, options)
+        .filter(|event| !matches!(event, Event::Html(_) | Event::InlineHtml(_)));
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, parser);
+    html_output
+}
 
-    let question_id = match form.question_id.parse::<Ulid>() {
-        Ok(qid) => qid,
-        Err(_) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Html("<p class=\"error-msg\">Invalid question ID.</p>".to_string()),
-            )
-                .into_response();
-        }
-    };
+/// Convert a TranscriptMessage to a TranscriptEntry for template rendering.
+fn to_transcript_entry(m: &barnstormer_core::TranscriptMessage) -> TranscriptEntry {
+    let (sender_label, is_human, role_class) = sender_display(&m.sender, m.display_name.as_deref());
+    let initial = sender_label.chars().next().unwrap_or('?').to_string();
+    let content_html = render_markdown(&m.content);
+    TranscriptEntry {
+        message_id: m.message_id.to_string(),
+        sender: m.sender.clone(),
+        sender_label,
+        initial,
+        is_human,
+        is_step: m.kind.is_step(),
+        is_continuation: false,
+        role_class,
+        content: m.content.clone(),
+        content_html,
+        timestamp: m.timestamp.format("%H:%M:%S").to_string(),
+        repeat_count: 1,
+        agent_model: m.agent_model.clone(),
+        is_highlighted: false,
+    }
+}
 
-    let actors = state.actors.read().await;
-    let handle = match actors.get(&spec_id) {
-        Some(h) => h,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
-            )
-                .into_response();
+/// Mark consecutive entries from the same sender as continuations.
+/// The first entry in a run keeps `is_continuation = false`; subsequent
+/// entries from the same sender get `is_continuation = true` so the
+/// template can skip the avatar/name row.
+fn mark_continuations(entries: &mut [TranscriptEntry]) {
+    for i in 1..entries.len() {
+        if entries[i].sender == entries[i - 1].sender
+            && !entries[i].is_step
+            && !entries[i - 1].is_step
+        {
+            entries[i].is_continuation = true;
         }
-    };
+    }
+}
 
-    let cmd = Command::AnswerQuestion {
-        question_id,
-        answer: form.answer,
+/// Flag the entry a permalink points at. `target` is the `msg-<id>` anchor
+/// or the bare message ID; anything that matches no entry is ignored.
+fn mark_highlighted(entries: &mut [TranscriptEntry], target: Option<&str>) {
+    let Some(target) = target else {
+        return;
     };
+    let target = target.strip_prefix("msg-").unwrap_or(target);
+    for entry in entries.iter_mut() {
+        entry.is_highlighted = entry.message_id == target;
+    }
+}
 
-    let _events = match handle.send_command(cmd).await {
-        Ok(events) => events,
-        Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Html(format!(
-                    "<p class=\"error-msg\">Failed to answer: {}</p>",
-                    e
-                )),
-            )
-                .into_response();
+/// Collapse consecutive identical step messages into a single entry with
+/// a repeat_count, so the UI can show "(x3)" instead of three identical lines.
+fn collapse_repeated_steps(entries: &mut Vec<TranscriptEntry>) {
+    let mut i = 0;
+    while i < entries.len() {
+        if entries[i].is_step {
+            let mut j = i + 1;
+            while j < entries.len()
+                && entries[j].is_step
+                && entries[j].content == entries[i].content
+            {
+                entries[i].repeat_count += 1;
+                // Keep a permalink to a collapsed step pointing at its run.
+                if entries[j].is_highlighted {
+                    entries[i].is_highlighted = true;
+                }
+                j += 1;
+            }
+            if entries[i].repeat_count > 1 {
+                entries.drain((i + 1)..j);
+            }
         }
-    };
+        i += 1;
+    }
+}
 
-    // Events are persisted by the background broadcast subscriber.
-    // Drop actors lock before acquiring swarms to avoid deadlock.
-    drop(actors);
+/// Returns true if the sender is part of the human ↔ manager conversation.
+/// Used to filter the chat tab to only show direct messages between the
+/// human and the manager agent, keeping other agents in the activity feed.
+fn is_chat_participant(sender: &str) -> bool {
+    sender == "human" || sender.starts_with("manager-")
+}
 
-    // Wake the agent loop so agents resume promptly after an answer.
-    {
-        let swarms = state.swarms.read().await;
-        if let Some(swarm_handle) = swarms.get(&spec_id) {
-            let swarm = swarm_handle.swarm.lock().await;
-            swarm.notify_human_message();
-        }
+/// Derive a display label and CSS class from a raw sender ID.
+/// "human" → ("You", true, "human"), "manager-01J..." → ("Manager", false, "manager"), etc.
+/// An agent's persona name, when it has one, is used as the label instead.
+fn sender_display(sender: &str, display_name: Option<&str>) -> (String, bool, String) {
+    if sender == "human" {
+        return ("You".to_string(), true, "human".to_string());
     }
-
-    // Re-acquire actors to read transcript for response
-    let actors = state.actors.read().await;
-    let handle = match actors.get(&spec_id) {
-        Some(h) => h,
-        None => {
-            return (
-                StatusCode::NOT_FOUND,
-                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
-            )
-                .into_response();
+    // Generic sender for one-shot helpers that are not a swarm agent.
+    if sender == "assistant" {
+        return ("Assistant".to_string(), false, "assistant".to_string());
+    }
+    // Agent IDs look like "manager-01JTEST..." or "brainstormer-01JTEST..."
+    let role = sender.split('-').next().unwrap_or(sender);
+    // A configured persona replaces the role label but keeps the role's styling.
+    if let Some(name) = display_name.map(str::trim).filter(|n| !n.is_empty()) {
+        return (name.to_string(), false, normalize_css_class(role));
+    }
+    let label = match role {
+        "manager" => "Orchestrator",
+        "brainstormer" => "Researcher",
+        "planner" => "Architect",
+        "dot_generator" => "Dot Generator",
+        "critic" => "Critic",
+        _ => {
+            let mut capitalized = String::new();
+            for (i, ch) in role.chars().enumerate() {
+                if i == 0 {
+                    capitalized.extend(ch.to_uppercase());
+                } else {
+                    capitalized.push(ch);
+                }
+            }
+            return (capitalized, false, normalize_css_class(role));
         }
     };
+    let role_class = normalize_css_class(role);
+    (label.to_string(), false, role_class)
+}
 
-    // Determine container_id from HX-Target header so the response replaces
-    // the correct transcript container (activity panel vs chat tab).
-    // If the target ends with "-question", we return only the question card
-    // partial so the message feed is untouched.
-    let raw_target = headers
-        .get("HX-Target")
-        .and_then(|v| v.to_str().ok())
-        .map(|s| s.trim_start_matches('#'))
-        .unwrap_or("activity-transcript");
-
-    let is_question_target = raw_target.ends_with("-question");
-    let base_target = if is_question_target {
-        raw_target.trim_end_matches("-question")
-    } else {
-        raw_target
-    };
-    let container_id = sanitize_container_id(base_target);
-
-    // Return refreshed transcript partial
-    let spec_state = handle.read_state().await;
-
-    // Chat containers only show human + manager messages; see sanitize_container_id docs.
-    let is_chat = container_id == "chat-transcript" || container_id == "brainstorm-chat";
-    let is_ticker = container_id == "mission-ticker";
+/// Normalize a string into a valid CSS class name: lowercase, replacing
+/// any character that is not `[a-z0-9_-]` with a hyphen.
+fn normalize_css_class(raw: &str) -> String {
+    raw.to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
 
-    // Read actual pending question from state instead of assuming None
-    let pending_question = spec_state
-        .pending_question
-        .as_ref()
-        .map(question_to_view_data);
+/// Question data for templates.
+pub enum QuestionData {
+    Boolean {
+        question_id: String,
+        question: String,
+        default: Option<bool>,
+    },
+    MultipleChoice {
+        question_id: String,
+        question: String,
+        choices: Vec<String>,
+        allow_multi: bool,
+    },
+    Freeform {
+        question_id: String,
+        question: String,
+        placeholder: String,
+    },
+    ReviewGate {
+        question_id: String,
+        question: String,
+    },
+}
 
-    // If the answer form targeted the question card directly, return only
-    // the question partial so the message feed and any user input are preserved.
-    if is_question_target && is_chat {
-        return ChatQuestionTemplate {
-            spec_id: id,
-            container_id,
-            pending_question,
-        }
-        .into_response();
+/// Convert a core UserQuestion into the template-friendly QuestionData.
+fn question_to_view_data(q: &barnstormer_core::UserQuestion) -> QuestionData {
+    match q {
+        barnstormer_core::UserQuestion::Boolean {
+            question_id,
+            question,
+            default,
+        } => QuestionData::Boolean {
+            question_id: question_id.to_string(),
+            question: render_markdown(question),
+            default: *default,
+        },
+        barnstormer_core::UserQuestion::MultipleChoice {
+            question_id,
+            question,
+            choices,
+            allow_multi,
+        } => QuestionData::MultipleChoice {
+            question_id: question_id.to_string(),
+            question: render_markdown(question),
+            choices: choices.clone(),
+            allow_multi: *allow_multi,
+        },
+        barnstormer_core::UserQuestion::Freeform {
+            question_id,
+            question,
+            placeholder,
+            ..
+        } => QuestionData::Freeform {
+            question_id: question_id.to_string(),
+            question: render_markdown(question),
+            placeholder: placeholder.clone().unwrap_or_default(),
+        },
+        barnstormer_core::UserQuestion::ReviewGate {
+            question_id,
+            question,
+            ..
+        } => QuestionData::ReviewGate {
+            question_id: question_id.to_string(),
+            question: render_markdown(question),
+        },
     }
+}
 
-    let mut transcript: Vec<TranscriptEntry> = spec_state
-        .transcript
-        .iter()
-        .filter(|m| !is_chat || is_chat_participant(&m.sender))
-        .map(to_transcript_entry)
-        .collect();
-    mark_continuations(&mut transcript);
-    collapse_repeated_steps(&mut transcript);
+/// Query parameters for the transcript endpoint, allowing callers to specify
+/// which container the response should target (activity panel vs chat tab).
+/// The optional `part` field selects a sub-section: "feed" for messages only,
+/// "question" for the question card only, or omitted for the full transcript.
+#[derive(Deserialize)]
+pub struct TranscriptQuery {
+    pub container_id: Option<String>,
+    pub part: Option<String>,
+    /// Message to highlight, as `msg-<id>` or a bare message ULID.
+    pub highlight: Option<String>,
+    /// Group consecutive messages from one sender and fold long ones.
+    #[serde(default)]
+    pub compact: bool,
+}
 
-    if is_ticker {
-        // For mission ticker, show only last 10 entries
-        let ticker_entries: Vec<TranscriptEntry> = spec_state
-            .transcript
-            .iter()
-            .rev()
-            .take(10)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .map(to_transcript_entry)
-            .collect();
-        MissionTickerTemplate {
-            spec_id: id,
-            ticker_entries,
-            pending_question,
-        }
-        .into_response()
-    } else if is_chat {
-        ChatTranscriptTemplate {
-            spec_id: id,
-            container_id,
-            transcript,
-            pending_question,
-        }
-        .into_response()
-    } else {
-        ActivityTranscriptTemplate {
-            spec_id: id,
-            container_id,
-            transcript,
-            pending_question,
+/// Query parameters for `GET /web/specs/{id}/activity`.
+#[derive(Deserialize)]
+pub struct ActivityQuery {
+    /// Message to highlight, as `msg-<id>` or a bare message ULID.
+    pub highlight: Option<String>,
+    /// Start in the compact transcript view.
+    #[serde(default)]
+    pub compact: bool,
+}
+
+/// Validate and sanitize a container_id value. Only known IDs are accepted;
+/// anything else falls back to "chat-transcript" to prevent XSS via
+/// user-controlled values rendered into script tags and HTMX attributes.
+///
+/// Allowed IDs and where they are used:
+/// - "activity-transcript" -- activity panel transcript (default for activity handlers)
+/// - "chat-transcript"     -- chat panel transcript in refining phase
+/// - "brainstorm-chat"     -- chat panel transcript in brainstorming phase
+/// - "mission-ticker"      -- compact ticker strip; also the hx-target for answer forms
+fn sanitize_container_id(raw: &str) -> String {
+    match raw {
+        "activity-transcript" | "chat-transcript" | "mission-ticker" | "brainstorm-chat" => {
+            raw.to_string()
         }
-        .into_response()
+        _ => "chat-transcript".to_string(),
     }
 }
 
-/// Maximum allowed length for a chat message (in characters).
-const CHAT_MAX_LENGTH: usize = 10_000;
+/// Activity panel template.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/activity.html")]
+pub struct ActivityTemplate {
+    pub spec_id: String,
+    pub container_id: String,
+    pub transcript: Vec<TranscriptEntry>,
+    pub pending_question: Option<QuestionData>,
+    /// How many more questions wait behind `pending_question`.
+    pub queued_questions: usize,
+    /// Compact density: same-sender runs share one header and long
+    /// messages collapse behind "show more".
+    pub compact: bool,
+}
 
-/// POST /web/specs/{id}/chat - Send a free-text message as the human.
-pub async fn chat(
+/// Activity transcript partial template (transcript entries + question widget only).
+/// Used by the SSE refresh target so that chat input is not wiped on updates.
+/// The `container_id` field controls the DOM IDs so the same template can serve
+/// both the mission ticker and the full-width chat tab.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/activity_transcript.html")]
+pub struct ActivityTranscriptTemplate {
+    pub spec_id: String,
+    pub container_id: String,
+    pub transcript: Vec<TranscriptEntry>,
+    pub pending_question: Option<QuestionData>,
+    /// How many more questions wait behind `pending_question`.
+    pub queued_questions: usize,
+    /// Compact density: same-sender runs share one header and long
+    /// messages collapse behind "show more".
+    pub compact: bool,
+}
+
+/// GET /web/specs/{id}/activity - Render the activity panel.
+pub async fn activity(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-    headers: axum::http::HeaderMap,
-    Form(form): Form<ChatForm>,
+    Query(query): Query<ActivityQuery>,
 ) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    // Validate message: trim whitespace, reject empty, cap length
-    let message = form.message.trim().to_string();
-    if message.is_empty() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Html("<p class=\"error-msg\">Message cannot be empty.</p>".to_string()),
-        )
-            .into_response();
-    }
-    if message.chars().count() > CHAT_MAX_LENGTH {
-        return (
-            StatusCode::BAD_REQUEST,
-            Html(format!(
-                "<p class=\"error-msg\">Message too long (max {} characters).</p>",
-                CHAT_MAX_LENGTH
-            )),
-        )
-            .into_response();
-    }
-
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
         Some(h) => h,
@@ -2282,38 +3284,43 @@ pub async fn chat(
         }
     };
 
-    let cmd = Command::AppendTranscript {
-        sender: "human".to_string(),
-        content: message,
-    };
+    let spec_state = handle.read_state().await;
 
-    let _events = match handle.send_command(cmd).await {
-        Ok(events) => events,
-        Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Html(format!(
-                    "<p class=\"error-msg\">Failed to send message: {}</p>",
-                    e
-                )),
-            )
-                .into_response();
-        }
-    };
-    // Drop actors read lock before acquiring swarms
-    drop(actors);
+    let mut transcript: Vec<TranscriptEntry> = spec_state
+        .transcript
+        .iter()
+        .map(to_transcript_entry)
+        .collect();
+    mark_continuations(&mut transcript);
+    mark_highlighted(&mut transcript, query.highlight.as_deref());
+    collapse_repeated_steps(&mut transcript);
 
-    // Wake the agent loop so the manager responds to the human message promptly
-    // instead of waiting for the next idle-cycle poll (up to 5 seconds).
-    {
-        let swarms = state.swarms.read().await;
-        if let Some(swarm_handle) = swarms.get(&spec_id) {
-            let swarm = swarm_handle.swarm.lock().await;
-            swarm.notify_human_message();
-        }
+    let pending_question = spec_state.pending_question().map(question_to_view_data);
+    let queued_questions = spec_state.pending_questions.len().saturating_sub(1);
+
+    ActivityTemplate {
+        spec_id: id,
+        container_id: "activity-transcript".to_string(),
+        transcript,
+        pending_question,
+        queued_questions,
+        compact: query.compact,
     }
+    .into_response()
+}
+
+/// GET /web/specs/{id}/activity/transcript - Render only the transcript + question widget.
+/// Used as the SSE refresh target so chat input is preserved during live updates.
+pub async fn activity_transcript(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<TranscriptQuery>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
 
-    // Re-acquire actors to read transcript for response
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
         Some(h) => h,
@@ -2326,24 +3333,22 @@ pub async fn chat(
         }
     };
 
-    // Events are persisted by the background broadcast subscriber.
+    let spec_state = handle.read_state().await;
+
+    let pending_question = spec_state.pending_question().map(question_to_view_data);
+    let queued_questions = spec_state.pending_questions.len().saturating_sub(1);
 
-    // Determine container_id from HX-Target header so the response replaces
-    // the correct transcript container (activity panel vs chat tab).
     let container_id = sanitize_container_id(
-        headers
-            .get("HX-Target")
-            .and_then(|v| v.to_str().ok())
-            .map(|s| s.trim_start_matches('#'))
+        query
+            .container_id
+            .as_deref()
             .unwrap_or("activity-transcript"),
     );
 
-    // Return refreshed transcript partial
-    let spec_state = handle.read_state().await;
-
-    // Chat containers only show human + manager messages; see sanitize_container_id docs.
+    // Chat containers only show human + manager messages (filtered by
+    // is_chat_participant) so the user sees a clean conversation thread.
+    // The activity-transcript and mission-ticker containers show all senders.
     let is_chat = container_id == "chat-transcript" || container_id == "brainstorm-chat";
-    let is_ticker = container_id == "mission-ticker";
 
     let mut transcript: Vec<TranscriptEntry> = spec_state
         .transcript
@@ -2352,29 +3357,24 @@ pub async fn chat(
         .map(to_transcript_entry)
         .collect();
     mark_continuations(&mut transcript);
+    mark_highlighted(&mut transcript, query.highlight.as_deref());
     collapse_repeated_steps(&mut transcript);
 
-    let pending_question = spec_state
-        .pending_question
-        .as_ref()
-        .map(question_to_view_data);
+    let part = query.part.as_deref().unwrap_or("");
 
-    if is_ticker {
-        // For mission ticker, show only last 10 entries
-        let ticker_entries: Vec<TranscriptEntry> = spec_state
-            .transcript
-            .iter()
-            .rev()
-            .take(10)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .map(to_transcript_entry)
-            .collect();
-        MissionTickerTemplate {
+    if is_chat && part == "feed" {
+        ChatFeedTemplate {
             spec_id: id,
-            ticker_entries,
+            container_id,
+            transcript,
+        }
+        .into_response()
+    } else if is_chat && part == "question" {
+        ChatQuestionTemplate {
+            spec_id: id,
+            container_id,
             pending_question,
+            queued_questions,
         }
         .into_response()
     } else if is_chat {
@@ -2383,6 +3383,7 @@ pub async fn chat(
             container_id,
             transcript,
             pending_question,
+            queued_questions,
         }
         .into_response()
     } else {
@@ -2391,13 +3392,65 @@ pub async fn chat(
             container_id,
             transcript,
             pending_question,
+            queued_questions,
+            compact: query.compact,
         }
         .into_response()
     }
 }
 
-/// POST /web/specs/{id}/undo - Undo last operation, return refreshed board.
-pub async fn undo(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
+/// Chat-style transcript for SSE refresh in the Chat tab.
+/// Uses distinct markup from ActivityTranscriptTemplate — avatars, larger bubbles.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/chat_transcript.html")]
+pub struct ChatTranscriptTemplate {
+    pub spec_id: String,
+    pub container_id: String,
+    pub transcript: Vec<TranscriptEntry>,
+    pub pending_question: Option<QuestionData>,
+    /// How many more questions wait behind `pending_question`.
+    pub queued_questions: usize,
+}
+
+/// Chat message feed partial — messages, throbber, streaming, empty state.
+/// Rendered independently so transcript refreshes don't disturb the question card.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/chat_feed.html")]
+pub struct ChatFeedTemplate {
+    pub spec_id: String,
+    pub container_id: String,
+    pub transcript: Vec<TranscriptEntry>,
+}
+
+/// Chat question card partial — pending question with answer form.
+/// Rendered independently so question refreshes don't disturb the message feed.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/chat_question.html")]
+pub struct ChatQuestionTemplate {
+    pub spec_id: String,
+    pub container_id: String,
+    pub pending_question: Option<QuestionData>,
+    /// How many more questions wait behind `pending_question`.
+    pub queued_questions: usize,
+}
+
+/// Chat panel template for the full-width Chat tab.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/chat_panel.html")]
+pub struct ChatPanelTemplate {
+    pub spec_id: String,
+    pub container_id: String,
+    pub transcript: Vec<TranscriptEntry>,
+    pub pending_question: Option<QuestionData>,
+    /// How many more questions wait behind `pending_question`.
+    pub queued_questions: usize,
+}
+
+/// GET /web/specs/{id}/chat-panel - Render the Chat tab content.
+pub async fn chat_panel(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
@@ -2415,264 +3468,222 @@ pub async fn undo(State(state): State<SharedState>, Path(id): Path<String>) -> i
         }
     };
 
-    let _events = match handle.send_command(Command::Undo).await {
-        Ok(events) => events,
-        Err(e) => {
+    let spec_state = handle.read_state().await;
+
+    let container_id = if spec_state.phase == SpecPhase::Brainstorming {
+        "brainstorm-chat".to_string()
+    } else {
+        "chat-transcript".to_string()
+    };
+
+    let mut transcript: Vec<TranscriptEntry> = spec_state
+        .transcript
+        .iter()
+        .filter(|m| is_chat_participant(&m.sender))
+        .map(to_transcript_entry)
+        .collect();
+    mark_continuations(&mut transcript);
+    collapse_repeated_steps(&mut transcript);
+
+    let pending_question = spec_state.pending_question().map(question_to_view_data);
+    let queued_questions = spec_state.pending_questions.len().saturating_sub(1);
+
+    ChatPanelTemplate {
+        spec_id: id,
+        container_id,
+        transcript,
+        pending_question,
+        queued_questions,
+    }
+    .into_response()
+}
+
+/// Artifacts tab template showing exported spec content in multiple formats.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/artifacts.html")]
+pub struct ArtifactsTemplate {
+    pub spec_id: String,
+    pub title_slug: String,
+    pub markdown_content: String,
+    pub yaml_content: String,
+    pub dot_content: String,
+    pub mermaid_content: String,
+    pub json_content: String,
+}
+
+/// GET /web/specs/{id}/artifacts - Render the Artifacts tab with every export format.
+pub async fn artifacts(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
             return (
-                StatusCode::BAD_REQUEST,
-                Html(format!("<p class=\"error-msg\">Undo failed: {}</p>", e)),
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
             )
                 .into_response();
         }
     };
 
-    // Events are persisted by the background broadcast subscriber.
-
-    // Return refreshed board
     let spec_state = handle.read_state().await;
-    let lanes = cards_by_lane(&spec_state);
-    BoardTemplate { spec_id: id, lanes }.into_response()
+    let spec_state = query.view(&spec_state);
+
+    let markdown_content = barnstormer_core::export::export_markdown(&spec_state);
+    let yaml_content = barnstormer_core::export::export_yaml(&spec_state)
+        .unwrap_or_else(|e| format!("# YAML export error: {}", e));
+    let dot_content = barnstormer_core::export::export_dot(&spec_state);
+    let mermaid_content = barnstormer_core::export::export_mermaid(&spec_state);
+    let json_content = barnstormer_core::export::export_json(&spec_state)
+        .unwrap_or_else(|e| format!("{{\"error\": \"JSON export error: {}\"}}", e));
+
+    let title_slug = spec_state
+        .core
+        .as_ref()
+        .map(|c| slugify(&c.title))
+        .unwrap_or_else(|| "spec".to_string());
+
+    ArtifactsTemplate {
+        spec_id: id,
+        title_slug,
+        markdown_content,
+        yaml_content,
+        dot_content,
+        mermaid_content,
+        json_content,
+    }
+    .into_response()
 }
 
-#[derive(Deserialize)]
-pub struct PhaseForm {
-    target: String,
+/// Spec tab template showing a synthesized specification document.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/spec.html")]
+pub struct SpecTabTemplate {
+    pub spec_id: String,
+    pub title_slug: String,
+    pub spec_html: String,
+    pub spec_markdown: String,
 }
 
-/// POST /web/specs/{id}/phase - Transition a spec between phases.
-pub async fn transition_phase(
+/// GET /web/specs/{id}/spec - Render the synthesized Spec tab.
+pub async fn spec(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-    Form(form): Form<PhaseForm>,
-) -> Response {
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    let target = match form.target.as_str() {
-        "brainstorming" => SpecPhase::Brainstorming,
-        "refining" => SpecPhase::Refining,
-        "complete" => SpecPhase::Complete,
-        _ => {
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
             return (
-                StatusCode::BAD_REQUEST,
-                Html("<p class=\"error-msg\">Invalid phase target.</p>".to_string()),
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
             )
                 .into_response();
         }
     };
 
-    let actors = state.actors.read().await;
-    let Some(handle) = actors.get(&spec_id) else {
-        return (
-            StatusCode::NOT_FOUND,
-            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
-        )
-            .into_response();
-    };
+    let spec_state = handle.read_state().await;
+    let spec_state = query.view(&spec_state);
+    let title_slug = spec_state
+        .core
+        .as_ref()
+        .map(|c| slugify(&c.title))
+        .unwrap_or_else(|| "spec".to_string());
+    let spec_markdown = barnstormer_core::export::export_spec(&spec_state);
+    let spec_html = render_markdown(&spec_markdown);
 
-    match handle
-        .send_command(Command::TransitionPhase {
-            target: target.clone(),
-        })
-        .await
-    {
-        Ok(_) => {
-            // Phase transition triggers SSE phase_transitioned event,
-            // which causes the client to reload the entire workspace.
-            (StatusCode::OK, Html("<span>OK</span>".to_string())).into_response()
-        }
-        Err(ActorError::AlreadyInPhase) => (
-            StatusCode::CONFLICT,
-            Html("<p class=\"error-msg\">Already in target phase.</p>".to_string()),
-        )
-            .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Html(format!("<p class=\"error-msg\">Error: {}</p>", e)),
-        )
-            .into_response(),
+    SpecTabTemplate {
+        spec_id: id,
+        title_slug,
+        spec_html,
+        spec_markdown,
     }
+    .into_response()
 }
 
-/// Context panel partial template — rendered HTML for the brainstorming
-/// right-rail panel showing all live (non-removed) context attachments.
-#[derive(Template)]
-#[template(path = "partials/context_panel.html")]
-struct ContextPanelTemplate {
-    spec_id: String,
-    attachments: Vec<ContextPanelItem>,
+/// Query parameters shared by the export endpoints. Archived cards are left
+/// out unless `?include_archived=true` is passed.
+#[derive(Deserialize, Default)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub include_archived: bool,
 }
 
-/// Coarse-grained kind of a context attachment, derived from its sniffed
-/// MIME type. Used by the context panel template to branch between
-/// browser-native preview affordances (raster `<img>`, SVG `<img>`, `<audio>`,
-/// `<video>`, PDF icon) and to pick a per-kind badge color in the collapsed
-/// header. `Text` is the catch-all for anything that doesn't render natively
-/// in a preview block (markdown, plaintext, code, etc.).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AttachmentKind {
-    Text,
-    ImageRaster,
-    ImageSvg,
-    Pdf,
-    Audio,
-    Video,
-}
-
-impl AttachmentKind {
-    /// Classify a stored MIME string. Any parameters after the first `;`
-    /// (e.g. `; charset=utf-8`) are dropped before matching, and the result
-    /// is lowercased so casing variation in the upload pipeline doesn't
-    /// flip an attachment between branches.
-    fn from_mime(mime: &str) -> Self {
-        let normalized = mime
-            .split(';')
-            .next()
-            .unwrap_or(mime)
-            .trim()
-            .to_ascii_lowercase();
-        if normalized == "image/svg+xml" {
-            Self::ImageSvg
-        } else if normalized.starts_with("image/") {
-            Self::ImageRaster
-        } else if normalized == "application/pdf" {
-            Self::Pdf
-        } else if normalized.starts_with("audio/") {
-            Self::Audio
-        } else if normalized.starts_with("video/") {
-            Self::Video
+impl ExportQuery {
+    /// The state to export: as-is, or without its archived cards.
+    fn view<'a>(&self, spec_state: &'a SpecState) -> std::borrow::Cow<'a, SpecState> {
+        if self.include_archived {
+            std::borrow::Cow::Borrowed(spec_state)
         } else {
-            Self::Text
+            std::borrow::Cow::Owned(spec_state.without_archived_cards())
         }
     }
 }
 
-/// View model for a single context attachment row in the panel.
-///
-/// `summary` holds the raw (plain-text) summary used for the collapsed-state
-/// tooltip (the native `title` attribute on the `<summary>` element). It's
-/// also used as the presence flag for rendering the "in context" pill.
-/// `summary_html` holds the pre-rendered HTML produced by `render_markdown`,
-/// displayed only when the card is expanded. `summary_error` is the
-/// last-attempt failure reason, used to drive the four-state summary UI
-/// (pending / ok / stale-with-error / failed).
-struct ContextPanelItem {
-    attachment_id: String,
-    filename: String,
-    extension: String,
-    size_display: String,
-    added_display: String,
-    summary: Option<String>,
-    summary_html: Option<String>,
-    summary_error: Option<String>,
-    user_notes: Option<String>,
-    /// Number of non-removed cards whose `source_attachment_id` points at this
-    /// attachment. Rendered in the collapsed header so the user can see at a
-    /// glance how much of an attachment the Manager has synthesized.
-    card_count: usize,
-    /// Coarse classification of the attachment for per-kind preview rendering
-    /// and badge coloring in the template.
-    kind: AttachmentKind,
-    /// Original sniffed MIME type, forwarded to `<source type=...>` on the
-    /// `<audio>` / `<video>` preview elements.
-    mime_type: String,
-    /// `/web/specs/{spec_id}/context/{attachment_id}/raw` — the source URL for
-    /// inline `<img>` / `<audio>` / `<video>` previews.
-    raw_url: String,
-}
-
-/// Human-readable file size (B / KB / MB) for display in the context panel.
-fn format_size(bytes: u64) -> String {
-    if bytes < 1024 {
-        format!("{bytes} B")
-    } else if bytes < 1024 * 1024 {
-        format!("{:.1} KB", bytes as f64 / 1024.0)
-    } else {
-        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
-    }
-}
+/// GET /web/specs/{id}/export/markdown - Download spec as Markdown file.
+pub async fn export_markdown(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
 
-/// Shared helper: builds the context panel HTML for a given spec. Returns
-/// 404 if the spec is unknown, 500 on render failure. All four context
-/// handlers (upload/notes/delete/GET panel) route through this helper so
-/// they return identical HTML on success.
-async fn render_context_panel_for(state: &SharedState, spec_id: Ulid) -> Response {
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
-        Some(h) => h.clone(),
-        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
-    drop(actors);
 
     let spec_state = handle.read_state().await;
-    let attachments: Vec<ContextPanelItem> = spec_state
-        .context_attachments
-        .iter()
-        .filter(|a| !a.removed)
-        .map(|a| {
-            let card_count = spec_state
-                .cards
-                .values()
-                .filter(|c| c.source_attachment_id == Some(a.attachment_id))
-                .count();
-            ContextPanelItem {
-                attachment_id: a.attachment_id.to_string(),
-                filename: a.filename.clone(),
-                extension: std::path::Path::new(&a.filename)
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .unwrap_or("txt")
-                    .to_string(),
-                size_display: format_size(a.size_bytes),
-                added_display: a.added_at.format("%H:%M").to_string(),
-                summary: a.summary.clone(),
-                summary_html: a.summary.as_deref().map(render_markdown),
-                summary_error: a.summary_error.clone(),
-                user_notes: a.user_notes.clone(),
-                card_count,
-                kind: AttachmentKind::from_mime(&a.mime_type),
-                mime_type: a.mime_type.clone(),
-                raw_url: format!("/web/specs/{}/context/{}/raw", spec_id, a.attachment_id),
-            }
-        })
-        .collect();
-    drop(spec_state);
-
-    let tmpl = ContextPanelTemplate {
-        spec_id: spec_id.to_string(),
-        attachments,
-    };
-    match tmpl.render() {
-        Ok(html) => Html(html).into_response(),
-        Err(e) => {
-            tracing::error!("context_panel render failed: {e}");
-            (StatusCode::INTERNAL_SERVER_ERROR, "render failed").into_response()
-        }
-    }
-}
+    let spec_state = query.view(&spec_state);
+    let filename = export_filename(
+        &state.export_filename_pattern,
+        spec_id,
+        spec_state.core.as_ref().map(|c| c.title.as_str()),
+        "md",
+    );
+    let content = barnstormer_core::export::export_markdown(&spec_state);
 
-/// GET /web/specs/{id}/context-panel - Render the context panel partial.
-///
-/// Returns the full `<div id="context-panel">` partial; the brainstorming
-/// view and Task 16 SSE wiring swap this element via HTMX.
-pub async fn context_panel(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
-    };
-    render_context_panel_for(&state, spec_id).await
+    Response::builder()
+        .header("content-type", "text/markdown; charset=utf-8")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(axum::body::Body::from(content))
+        .unwrap()
+        .into_response()
 }
 
-/// GET /web/specs/{id}/context-preview - Render a read-only preview of the
-/// "## Context Files" section of the agent's current task prompt.
-///
-/// Uses `barnstormer_agent::render_context_files_section` so what the user
-/// sees exactly matches what the Manager is being told about attached files.
-/// Non-removed attachments only. Returns a small `.card` wrapper for drop-in
-/// swap into the panel; empty state shows "No context files attached."
-pub async fn context_preview(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+/// GET /web/specs/{id}/export/yaml - Download spec as YAML file.
+pub async fn export_yaml(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
@@ -2680,66 +3691,52 @@ pub async fn context_preview(State(state): State<SharedState>, Path(id): Path<St
 
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
-        Some(h) => h.clone(),
-        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
-    drop(actors);
 
     let spec_state = handle.read_state().await;
-    let live: Vec<barnstormer_core::state::ContextAttachment> = spec_state
-        .context_attachments
-        .iter()
-        .filter(|a| !a.removed)
-        .cloned()
-        .collect();
-    drop(spec_state);
-
-    let rendered = barnstormer_agent::render_context_files_section(&live);
-
-    // Minimal HTML: escape the rendered markdown so the preview is read-only
-    // and can't inject markup through attachment fields.
-    let inner = if rendered.is_empty() {
-        "No context files attached.".to_string()
-    } else {
-        html_escape(&rendered)
-    };
-    let body = format!(
-        r#"<div class="card" style="margin: var(--spacing-md); white-space: pre-wrap; font-family: monospace; font-size: 0.78rem;">{inner}</div>"#,
+    let spec_state = query.view(&spec_state);
+    let filename = export_filename(
+        &state.export_filename_pattern,
+        spec_id,
+        spec_state.core.as_ref().map(|c| c.title.as_str()),
+        "yaml",
     );
-    Html(body).into_response()
-}
-
-/// Minimal HTML-escape for the context preview body. Only escapes the five
-/// characters that matter for HTML text content; the wrapper uses
-/// `white-space: pre-wrap` so newlines and spaces are preserved as-is.
-fn html_escape(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '&' => out.push_str("&amp;"),
-            '<' => out.push_str("&lt;"),
-            '>' => out.push_str("&gt;"),
-            '"' => out.push_str("&quot;"),
-            '\'' => out.push_str("&#39;"),
-            _ => out.push(c),
+    match barnstormer_core::export::export_yaml(&spec_state) {
+        Ok(content) => Response::builder()
+            .header("content-type", "text/yaml; charset=utf-8")
+            .header(
+                "content-disposition",
+                format!("attachment; filename=\"{}\"", filename),
+            )
+            .body(axum::body::Body::from(content))
+            .unwrap()
+            .into_response(),
+        Err(e) => {
+            tracing::error!("YAML export failed for spec {}: {}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("<p class=\"error-msg\">Failed to export YAML.</p>".to_string()),
+            )
+                .into_response()
         }
     }
-    out
 }
 
-/// POST /web/specs/{id}/context - Upload a context file during brainstorming.
-///
-/// Accepts `multipart/form-data` with a single `file` part. Writes the file
-/// to disk under the spec's context directory and emits a `ContextAttached`
-/// event via `Command::AttachContext`. Gated to `SpecPhase::Brainstorming`;
-/// outside that phase returns 409 CONFLICT. The MIME type is server-sniffed
-/// from the bytes (browser-claimed Content-Type is ignored); unrecognized
-/// or non-whitelisted types return 415. Uploads over 20MB return 413.
-pub async fn upload_context(
+/// GET /web/specs/{id}/export/json - Download the full spec as JSON,
+/// card IDs included, for lossless re-import.
+pub async fn export_json(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-    mut multipart: axum::extract::Multipart,
-) -> Response {
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
@@ -2747,589 +3744,557 @@ pub async fn upload_context(
 
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
-        Some(h) => h.clone(),
+        Some(h) => h,
         None => {
-            return (StatusCode::NOT_FOUND, "spec not found").into_response();
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
         }
     };
-    drop(actors);
-
-    // Gate: brainstorming only.
-    let phase = handle.read_state().await.phase.clone();
-    if phase != SpecPhase::Brainstorming {
-        return (
-            StatusCode::CONFLICT,
-            "context files can only be attached during brainstorming",
-        )
-            .into_response();
-    }
 
-    // Extract first `file` part. Streamed-and-capped so a request can't
-    // buffer up to the configured global body cap before we reject it.
-    let mut file_bytes: Option<Vec<u8>> = None;
-    let mut filename: Option<String> = None;
-    loop {
-        match multipart.next_field().await {
-            Ok(Some(mut field)) => {
-                if field.name() == Some("file") {
-                    filename = field.file_name().map(str::to_string);
-                    // Browser-claimed content type is intentionally ignored;
-                    // the server sniffs the bytes below.
-                    let bytes = match read_field_capped(&mut field).await {
-                        Ok(Some(b)) => b,
-                        Ok(None) => {
-                            return (StatusCode::BAD_REQUEST, "empty file part").into_response();
-                        }
-                        Err(resp) => return resp,
-                    };
-                    file_bytes = Some(bytes);
-                    break;
-                }
-            }
-            Ok(None) => break,
-            Err(e) => {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    format!("multipart parse error: {e}"),
-                )
-                    .into_response();
-            }
+    let spec_state = handle.read_state().await;
+    let spec_state = query.view(&spec_state);
+    let filename = export_filename(
+        &state.export_filename_pattern,
+        spec_id,
+        spec_state.core.as_ref().map(|c| c.title.as_str()),
+        "json",
+    );
+    match barnstormer_core::export::export_json(&spec_state) {
+        Ok(content) => Response::builder()
+            .header("content-type", "application/json")
+            .header(
+                "content-disposition",
+                format!("attachment; filename=\"{}\"", filename),
+            )
+            .body(axum::body::Body::from(content))
+            .unwrap()
+            .into_response(),
+        Err(e) => {
+            tracing::error!("JSON export failed for spec {}: {}", id, e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html("<p class=\"error-msg\">Failed to export JSON.</p>".to_string()),
+            )
+                .into_response()
         }
     }
+}
 
-    let Some(bytes) = file_bytes else {
-        return (StatusCode::BAD_REQUEST, "missing file part").into_response();
+/// GET /web/specs/{id}/export/dot - Download spec as DOT graph file.
+pub async fn export_dot(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
     };
 
-    // Sniff MIME from bytes. Browser-supplied content-type is not trusted —
-    // a client can claim anything, but we serve, summarize, and gate on what
-    // the bytes actually are.
-    let detected_mime = match crate::context_storage::sniff_mime(
-        &bytes,
-        filename.as_deref().unwrap_or("file"),
-    ) {
-        Some(m) => m,
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
         None => {
             return (
-                StatusCode::UNSUPPORTED_MEDIA_TYPE,
-                "couldn't identify file type — uploads must be a recognized image, document, audio, video, or UTF-8 text file",
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
             )
                 .into_response();
         }
     };
-    if !crate::context_storage::is_whitelisted_mime(&detected_mime) {
-        return (
-            StatusCode::UNSUPPORTED_MEDIA_TYPE,
-            format!("file type '{detected_mime}' is not supported"),
-        )
-            .into_response();
-    }
-
-    let filename = crate::context_storage::sanitize_filename(filename.as_deref().unwrap_or("file"));
-    let attachment_id = Ulid::new();
 
-    let path = crate::context_storage::attachment_path(
-        &state.barnstormer_home,
+    let spec_state = handle.read_state().await;
+    let spec_state = query.view(&spec_state);
+    let filename = export_filename(
+        &state.export_filename_pattern,
         spec_id,
-        attachment_id,
-        &filename,
+        spec_state.core.as_ref().map(|c| c.title.as_str()),
+        "dot",
     );
-    if let Err(e) = crate::context_storage::write_bytes(&path, &bytes) {
-        tracing::error!("failed to write attachment: {e}");
-        return (StatusCode::INTERNAL_SERVER_ERROR, "write failed").into_response();
-    }
+    let content = barnstormer_core::export::export_dot(&spec_state);
 
-    // SVG-specific: rasterize and cache alongside the original. Failure
-    // degrades to markup-only summarization; the original SVG is still on
-    // disk and the upload still lands.
-    if detected_mime == "image/svg+xml"
-        && let Ok(markup) = std::str::from_utf8(&bytes)
-    {
-        match crate::svg_raster::rasterize_svg(markup) {
-            Ok(png) => {
-                let raster_path = crate::context_storage::attachment_dir(
-                    &state.barnstormer_home,
-                    spec_id,
-                    attachment_id,
-                )
-                .join("rasterized.png");
-                if let Err(e) = crate::context_storage::write_bytes(&raster_path, &png) {
-                    tracing::warn!("failed to cache rasterized SVG: {e}");
-                }
-            }
-            Err(e) => {
-                tracing::warn!("SVG rasterization failed for {filename}: {e}");
-            }
-        }
-    }
+    Response::builder()
+        .header("content-type", "text/plain; charset=utf-8")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(axum::body::Body::from(content))
+        .unwrap()
+        .into_response()
+}
 
-    let size_bytes = bytes.len() as u64;
-    let cmd = Command::AttachContext {
-        attachment_id,
-        filename: filename.clone(),
-        mime_type: detected_mime.clone(),
-        size_bytes,
+/// GET /web/specs/{id}/export/mermaid - Download cards and lanes as a
+/// Mermaid flowchart.
+pub async fn export_mermaid(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
     };
-    if let Err(e) = handle.send_command(cmd).await {
-        // Bytes already on disk — without the event referencing them they'd
-        // leak and the filesystem would drift from actor state. Best-effort
-        // cleanup (failure is logged but doesn't block the error response).
-        // Remove the whole attachment dir so we also drop SVG's
-        // `rasterized.png` sibling instead of leaving it orphaned.
-        let dir =
-            crate::context_storage::attachment_dir(&state.barnstormer_home, spec_id, attachment_id);
-        if let Err(remove_err) = std::fs::remove_dir_all(&dir) {
-            tracing::warn!("failed to clean up orphaned context dir {dir:?}: {remove_err}");
-        }
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("command failed: {e}"),
-        )
-            .into_response();
-    }
 
-    // Build per-kind SummarizerInput from disk and dispatch — fire-and-forget.
-    // Summary will land via SSE when done. Notes aren't available at upload
-    // time; they're populated later via PATCH.
-    let attachment_opt = handle
-        .read_state()
-        .await
-        .context_attachments
-        .iter()
-        .find(|a| a.attachment_id == attachment_id)
-        .cloned();
-    if let Some(att) = attachment_opt {
-        match crate::context_storage::build_summarizer_input(&state.barnstormer_home, spec_id, &att)
-            .await
-        {
-            Ok(input) => {
-                crate::summarizer::spawn_summarize(
-                    handle.clone(),
-                    attachment_id,
-                    filename.clone(),
-                    None,
-                    input,
-                );
-            }
-            Err(e) => {
-                tracing::warn!("could not build summarizer input for {attachment_id}: {e}");
-            }
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
         }
-    }
+    };
 
-    // Return the re-rendered panel partial so HTMX can swap it in place.
-    render_context_panel_for(&state, spec_id).await
-}
+    let spec_state = handle.read_state().await;
+    let spec_state = query.view(&spec_state);
+    let filename = export_filename(
+        &state.export_filename_pattern,
+        spec_id,
+        spec_state.core.as_ref().map(|c| c.title.as_str()),
+        "mmd",
+    );
+    let content = barnstormer_core::export::export_mermaid(&spec_state);
 
-/// Form body for PATCH notes — HTMX submits form-encoded by default.
-#[derive(Debug, Deserialize)]
-pub struct NotesForm {
-    pub notes: String,
+    Response::builder()
+        .header("content-type", "text/plain; charset=utf-8")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(axum::body::Body::from(content))
+        .unwrap()
+        .into_response()
 }
 
-/// PATCH /web/specs/{id}/context/{att_id}/notes - Update user-authored notes
-/// for a context attachment. Sends `Command::UpdateContextNotes` to the actor;
-/// returns 404 if the attachment is unknown, 409 if it has already been
-/// removed (soft-delete tombstone).
-pub async fn update_context_notes(
+/// GET /web/specs/{id}/export/spec - Download synthesized spec as Markdown file.
+pub async fn export_spec_download(
     State(state): State<SharedState>,
-    Path((id, att_id)): Path<(String, String)>,
-    Form(form): Form<NotesForm>,
-) -> Response {
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
-    let attachment_id = match att_id.parse::<Ulid>() {
-        Ok(id) => id,
-        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
-    };
 
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
-        Some(h) => h.clone(),
-        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
-    drop(actors);
 
-    let notes_for_summarizer = form.notes.clone();
-    let cmd = Command::UpdateContextNotes {
-        attachment_id,
-        notes: form.notes,
-    };
-    match handle.send_command(cmd).await {
-        Ok(_) => {
-            // Re-fire summarizer with the new notes. Latest-wins concurrency:
-            // if multiple PATCHes land in quick succession, each spawns its
-            // own task and whichever Command::SummarizeContext arrives last
-            // is the summary that sticks.
-            let attachment = handle
-                .read_state()
-                .await
-                .context_attachments
-                .iter()
-                .find(|a| a.attachment_id == attachment_id && !a.removed)
-                .cloned();
-            if let Some(att) = attachment {
-                match crate::context_storage::build_summarizer_input(
-                    &state.barnstormer_home,
-                    spec_id,
-                    &att,
-                )
-                .await
-                {
-                    Ok(input) => {
-                        crate::summarizer::spawn_summarize(
-                            handle.clone(),
-                            attachment_id,
-                            att.filename.clone(),
-                            Some(notes_for_summarizer),
-                            input,
-                        );
-                    }
-                    Err(e) => {
-                        tracing::warn!("could not build summarizer input on notes update: {e}")
-                    }
-                }
-            }
-            render_context_panel_for(&state, spec_id).await
-        }
-        Err(ActorError::AttachmentNotFound(_)) => {
-            (StatusCode::NOT_FOUND, "attachment not found").into_response()
-        }
-        Err(ActorError::AttachmentAlreadyRemoved(_)) => {
-            (StatusCode::CONFLICT, "attachment is removed").into_response()
-        }
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}")).into_response(),
-    }
+    let spec_state = handle.read_state().await;
+    let spec_state = query.view(&spec_state);
+    let filename = export_filename(
+        &state.export_filename_pattern,
+        spec_id,
+        spec_state.core.as_ref().map(|c| c.title.as_str()),
+        "spec.md",
+    );
+    let content = barnstormer_core::export::export_spec(&spec_state);
+
+    Response::builder()
+        .header("content-type", "text/markdown; charset=utf-8")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(axum::body::Body::from(content))
+        .unwrap()
+        .into_response()
 }
 
-/// DELETE /web/specs/{id}/context/{att_id} - Soft-remove a context attachment.
-/// Emits `Command::RemoveContext`; the on-disk file is preserved so undo can
-/// restore it. Returns 404 for unknown ids and 409 when the attachment has
-/// already been removed.
-pub async fn remove_context(
+/// GET /web/specs/{id}/export/prompt - Download the spec as a plain-text
+/// implementation brief to hand to an external coding agent.
+pub async fn export_prompt(
     State(state): State<SharedState>,
-    Path((id, att_id)): Path<(String, String)>,
-) -> Response {
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
-    let attachment_id = match att_id.parse::<Ulid>() {
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
+    let spec_state = handle.read_state().await;
+    let spec_state = query.view(&spec_state);
+    let filename = export_filename(
+        &state.export_filename_pattern,
+        spec_id,
+        spec_state.core.as_ref().map(|c| c.title.as_str()),
+        "build-prompt.txt",
+    );
+    let content = barnstormer_core::export::export_build_prompt(&spec_state);
+
+    Response::builder()
+        .header("content-type", "text/plain; charset=utf-8")
+        .header(
+            "content-disposition",
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(axum::body::Body::from(content))
+        .unwrap()
+        .into_response()
+}
+
+/// POST /web/specs/{id}/regenerate - Regenerate exports and save to disk.
+/// Writes markdown, YAML, and DOT files to $BARNSTORMER_HOME/<spec_id>/exports/.
+/// Returns an HTML snippet confirming the export.
+pub async fn regenerate(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
-        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
+        Err(resp) => return *resp,
     };
 
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
-        Some(h) => h.clone(),
-        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
-    drop(actors);
 
-    match handle
-        .send_command(Command::RemoveContext { attachment_id })
-        .await
-    {
-        Ok(_) => render_context_panel_for(&state, spec_id).await,
-        Err(ActorError::AttachmentNotFound(_)) => {
-            (StatusCode::NOT_FOUND, "attachment not found").into_response()
+    let spec_state = handle.read_state().await;
+    let spec_state = query.view(&spec_state);
+
+    // Export all formats
+    let markdown_content = barnstormer_core::export::export_markdown(&spec_state);
+    let yaml_content = barnstormer_core::export::export_yaml(&spec_state)
+        .unwrap_or_else(|e| format!("# YAML export error: {}", e));
+    let dot_content = barnstormer_core::export::export_dot(&spec_state);
+
+    // Write to $BARNSTORMER_HOME/specs/<spec_id>/exports/
+    let exports_dir = state
+        .barnstormer_home
+        .join("specs")
+        .join(spec_id.to_string())
+        .join("exports");
+    if let Err(e) = std::fs::create_dir_all(&exports_dir) {
+        tracing::error!("failed to create exports directory: {}", e);
+    } else {
+        let slug = spec_state
+            .core
+            .as_ref()
+            .map(|c| slugify(&c.title))
+            .unwrap_or_else(|| "spec".to_string());
+
+        if let Err(e) = std::fs::write(exports_dir.join(format!("{}.md", slug)), &markdown_content)
+        {
+            tracing::error!("failed to write markdown export: {}", e);
         }
-        Err(ActorError::AttachmentAlreadyRemoved(_)) => {
-            (StatusCode::CONFLICT, "attachment already removed").into_response()
+        if let Err(e) = std::fs::write(exports_dir.join(format!("{}.yaml", slug)), &yaml_content) {
+            tracing::error!("failed to write YAML export: {}", e);
         }
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}")).into_response(),
+        if let Err(e) = std::fs::write(exports_dir.join(format!("{}.dot", slug)), &dot_content) {
+            tracing::error!("failed to write DOT export: {}", e);
+        }
+        tracing::info!(
+            "regenerated exports for spec {} at {}",
+            spec_id,
+            exports_dir.display()
+        );
     }
+
+    Html("<span class=\"regen-confirm\">Exports saved successfully.</span>".to_string())
+        .into_response()
 }
 
-/// POST /web/specs/{id}/context/{att_id}/resummarize - Manually trigger a
-/// fresh summarizer pass for an attachment. The summary command itself lands
-/// asynchronously; this returns the panel partial with the spinner state.
-///
-/// The 410-vs-404 ordering matters: a soft-removed attachment is "gone" (we
-/// know it existed) so we surface 410 Gone rather than collapsing to 404.
-/// `att.user_notes.clone()` is forwarded to the summarizer so manual rerun
-/// uses the same notes context as the auto-spawn from upload/notes-update.
-pub async fn resummarize_context(
+/// Form data for sending a chat message.
+#[derive(Deserialize)]
+pub struct ChatForm {
+    pub message: String,
+}
+
+/// Form data for answering a question.
+#[derive(Deserialize)]
+pub struct AnswerForm {
+    pub question_id: String,
+    pub answer: String,
+}
+
+/// POST /web/specs/{id}/answer - Submit an answer to a pending question.
+pub async fn answer_question(
     State(state): State<SharedState>,
-    Path((id, att_id)): Path<(String, String)>,
-) -> Response {
+    Path(id): Path<String>,
+    headers: axum::http::HeaderMap,
+    Form(form): Form<AnswerForm>,
+) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
-    let attachment_id = match att_id.parse::<Ulid>() {
-        Ok(id) => id,
-        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
+
+    let question_id = match form.question_id.parse::<Ulid>() {
+        Ok(qid) => qid,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid question ID.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
 
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
-        Some(h) => h.clone(),
-        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
-    drop(actors);
 
-    let attachment_opt = handle
-        .read_state()
-        .await
-        .context_attachments
-        .iter()
-        .find(|a| a.attachment_id == attachment_id)
-        .cloned();
-    let att = match attachment_opt {
-        // Order matters: removed-but-known must return 410 before falling
-        // through to the generic Some(a) live path. Reordering these arms
-        // would surface 200 for soft-deleted attachments.
-        Some(a) if a.removed => {
-            return (StatusCode::GONE, "attachment is removed").into_response();
-        }
-        Some(a) => a,
-        None => return (StatusCode::NOT_FOUND, "attachment not found").into_response(),
+    let cmd = Command::AnswerQuestion {
+        question_id,
+        answer: form.answer,
     };
 
-    match crate::context_storage::build_summarizer_input(&state.barnstormer_home, spec_id, &att)
-        .await
-    {
-        Ok(input) => {
-            crate::summarizer::spawn_summarize(
-                handle.clone(),
-                attachment_id,
-                att.filename.clone(),
-                att.user_notes.clone(),
-                input,
-            );
-            render_context_panel_for(&state, spec_id).await
-        }
+    let _events = match handle.send_command(cmd).await {
+        Ok(events) => events,
         Err(e) => {
-            tracing::warn!("could not build summarizer input for resummarize: {e}");
-            (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}")).into_response()
+            return (
+                StatusCode::BAD_REQUEST,
+                Html(format!(
+                    "<p class=\"error-msg\">Failed to answer: {}</p>",
+                    e
+                )),
+            )
+                .into_response();
         }
-    }
-}
-
-/// GET /web/specs/{id}/context/{att_id}/raw - Stream the raw bytes of a
-/// context attachment. Only live (non-removed) attachments are served; both
-/// "unknown" and "soft-removed" cases return 404 so callers can't distinguish
-/// them.
-///
-/// Security: the stored `mime_type` came from server-side magic-byte sniffing
-/// in the upload pipeline (see `context_storage::sniff_mime`) and was checked
-/// against a whitelist before it ever landed in state, so it's mostly
-/// trustworthy as the `Content-Type`. We still send
-/// `X-Content-Type-Options: nosniff` as defense-in-depth so the browser
-/// doesn't override the declared type.
-///
-/// Two narrow exceptions to neuter direct-navigation script execution:
-///
-/// - `text/html` is downgraded to `text/plain; charset=utf-8` on the wire.
-///   HTML is on the upload whitelist (so users can attach pages they want to
-///   reference), but serving it back as `text/html` would let any `<script>`
-///   inside execute in this origin when a user navigates directly to the
-///   `/raw` URL — a stored-XSS foothold.
-/// - `image/svg+xml` is served as `image/png` by reading the cached
-///   `rasterized.png` produced at upload time. SVG is a script-executable
-///   format (`<script>`, `<foreignObject>` w/ HTML, JS event handlers), so
-///   serving the original SVG bytes with `image/svg+xml` would have the same
-///   stored-XSS exposure as HTML. The original SVG is preserved on disk; this
-///   only affects what `/raw` returns. If the rasterized cache is missing
-///   (rasterization failed at upload), we fall back to
-///   `text/plain; charset=utf-8` so the bytes still surface but can't run.
-///
-/// Other sniffed mimes (`image/png`, `image/jpeg`, `application/pdf`,
-/// `audio/*`, `video/*`, `text/plain`, `text/markdown`) don't execute JS, so
-/// they pass through with their real `Content-Type` so `<img>`, `<audio>`,
-/// `<video>`, and PDF viewers render correctly.
-pub async fn download_context(
-    State(state): State<SharedState>,
-    Path((id, att_id)): Path<(String, String)>,
-) -> Response {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
-    };
-    let attachment_id = match att_id.parse::<Ulid>() {
-        Ok(id) => id,
-        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
     };
 
+    // Events are persisted by the background broadcast subscriber.
+    // Drop actors lock before acquiring swarms to avoid deadlock.
+    drop(actors);
+
+    // Wake the agent loop so agents resume promptly after an answer.
+    {
+        let swarms = state.swarms.read().await;
+        if let Some(swarm_handle) = swarms.get(&spec_id) {
+            let swarm = swarm_handle.swarm.lock().await;
+            swarm.notify_human_message();
+        }
+    }
+
+    // Re-acquire actors to read transcript for response
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
-        Some(h) => h.clone(),
-        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
-    drop(actors);
 
+    // Determine container_id from HX-Target header so the response replaces
+    // the correct transcript container (activity panel vs chat tab).
+    // If the target ends with "-question", we return only the question card
+    // partial so the message feed is untouched.
+    let raw_target = headers
+        .get("HX-Target")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.trim_start_matches('#'))
+        .unwrap_or("activity-transcript");
+
+    let is_question_target = raw_target.ends_with("-question");
+    let base_target = if is_question_target {
+        raw_target.trim_end_matches("-question")
+    } else {
+        raw_target
+    };
+    let container_id = sanitize_container_id(base_target);
+
+    // Return refreshed transcript partial
     let spec_state = handle.read_state().await;
-    let att = match spec_state
-        .context_attachments
+
+    // Chat containers only show human + manager messages; see sanitize_container_id docs.
+    let is_chat = container_id == "chat-transcript" || container_id == "brainstorm-chat";
+    let is_ticker = container_id == "mission-ticker";
+
+    // Read actual pending question from state instead of assuming None
+    let pending_question = spec_state.pending_question().map(question_to_view_data);
+    let queued_questions = spec_state.pending_questions.len().saturating_sub(1);
+
+    // If the answer form targeted the question card directly, return only
+    // the question partial so the message feed and any user input are preserved.
+    if is_question_target && is_chat {
+        return ChatQuestionTemplate {
+            spec_id: id,
+            container_id,
+            pending_question,
+            queued_questions,
+        }
+        .into_response();
+    }
+
+    let mut transcript: Vec<TranscriptEntry> = spec_state
+        .transcript
         .iter()
-        .find(|a| a.attachment_id == attachment_id && !a.removed)
-    {
-        Some(a) => a.clone(),
-        None => return (StatusCode::NOT_FOUND, "attachment not found").into_response(),
-    };
-    drop(spec_state);
+        .filter(|m| !is_chat || is_chat_participant(&m.sender))
+        .map(to_transcript_entry)
+        .collect();
+    mark_continuations(&mut transcript);
+    collapse_repeated_steps(&mut transcript);
 
-    let path = crate::context_storage::attachment_path(
-        &state.barnstormer_home,
-        spec_id,
-        attachment_id,
-        &att.filename,
-    );
-    match std::fs::read(&path) {
-        Ok(bytes) => {
-            let stored = att.mime_type.clone();
-            let normalized = stored
-                .split(';')
-                .next()
-                .unwrap_or(&stored)
-                .trim()
-                .to_ascii_lowercase();
-            // SVG and HTML are both script-executable formats. We never serve
-            // them back with their original mime on /raw — direct navigation
-            // would give stored XSS in this origin.
-            //
-            // - SVG: serve the cached rasterized PNG (produced at upload
-            //   time). If the raster is missing (rasterization failed),
-            //   fall back to text/plain so bytes still surface but can't run.
-            // - HTML: downgrade to text/plain (no rasterized form exists).
-            //
-            // Other types (image/png, image/jpeg, application/pdf, audio/*,
-            // video/*, text/plain, text/markdown) don't execute JS so we
-            // serve their real mime so `<img>`, `<audio>`, `<video>`, and
-            // PDF viewers render correctly.
-            let (final_bytes, final_mime): (Vec<u8>, String) = if normalized == "image/svg+xml" {
-                let raster_path = crate::context_storage::attachment_dir(
-                    &state.barnstormer_home,
-                    spec_id,
-                    attachment_id,
-                )
-                .join("rasterized.png");
-                if raster_path.exists() {
-                    match std::fs::read(&raster_path) {
-                        Ok(b) => (b, "image/png".to_string()),
-                        Err(_) => (bytes, "text/plain; charset=utf-8".to_string()),
-                    }
-                } else {
-                    (bytes, "text/plain; charset=utf-8".to_string())
-                }
-            } else if normalized == "text/html" {
-                (bytes, "text/plain; charset=utf-8".to_string())
-            } else {
-                (bytes, stored)
-            };
-            (
-                [
-                    (axum::http::header::CONTENT_TYPE, final_mime.as_str()),
-                    (axum::http::header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
-                ],
-                final_bytes,
-            )
-                .into_response()
+    if is_ticker {
+        // For mission ticker, show only last 10 entries
+        let ticker_entries: Vec<TranscriptEntry> = spec_state
+            .transcript
+            .iter()
+            .rev()
+            .take(10)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .map(to_transcript_entry)
+            .collect();
+        MissionTickerTemplate {
+            spec_id: id,
+            ticker_entries,
+            pending_question,
+            queued_questions,
         }
-        Err(_) => (StatusCode::NOT_FOUND, "file not found on disk").into_response(),
+        .into_response()
+    } else if is_chat {
+        ChatTranscriptTemplate {
+            spec_id: id,
+            container_id,
+            transcript,
+            pending_question,
+            queued_questions,
+        }
+        .into_response()
+    } else {
+        ActivityTranscriptTemplate {
+            spec_id: id,
+            container_id,
+            transcript,
+            pending_question,
+            queued_questions,
+            compact: false,
+        }
+        .into_response()
     }
 }
 
-/// Returns the current phase as plain text — used by the client-side
-/// polling fallback when SSE might be disconnected.
-pub async fn phase_check(
+/// Maximum allowed length for a chat message (in characters).
+const CHAT_MAX_LENGTH: usize = 10_000;
+
+/// POST /web/specs/{id}/chat - Send a free-text message as the human.
+pub async fn chat(
     State(state): State<SharedState>,
     Path(id): Path<String>,
+    headers: axum::http::HeaderMap,
+    Form(form): Form<ChatForm>,
 ) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
+
+    // Validate message: trim whitespace, reject empty, cap length
+    let message = form.message.trim().to_string();
+    if message.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html("<p class=\"error-msg\">Message cannot be empty.</p>".to_string()),
+        )
+            .into_response();
+    }
+    if message.chars().count() > CHAT_MAX_LENGTH {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Message too long (max {} characters).</p>",
+                CHAT_MAX_LENGTH
+            )),
+        )
+            .into_response();
+    }
+
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
         Some(h) => h,
         None => {
-            return (StatusCode::NOT_FOUND, "not_found").into_response();
-        }
-    };
-    let spec_state = handle.read_state().await;
-    let phase_str = match spec_state.phase {
-        SpecPhase::Brainstorming => "brainstorming",
-        SpecPhase::Refining => "refining",
-        SpecPhase::Complete => "complete",
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
     };
-    phase_str.into_response()
-}
 
-/// Provider status partial template.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/provider_status.html")]
-pub struct ProviderStatusTemplate {
-    pub default_provider: String,
-    pub default_model: Option<String>,
-    pub providers: Vec<ProviderInfoView>,
-    pub any_available: bool,
-}
+    let cmd = Command::AppendTranscript {
+        sender: "human".to_string(),
+        content: message,
+        display_name: None,
+    };
 
-/// Provider info view for template rendering.
-pub struct ProviderInfoView {
-    pub name: String,
-    pub has_api_key: bool,
-    pub model: String,
-}
+    let _events = match handle.send_command(cmd).await {
+        Ok(events) => events,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html(format!(
+                    "<p class=\"error-msg\">Failed to send message: {}</p>",
+                    e
+                )),
+            )
+                .into_response();
+        }
+    };
+    // Drop actors read lock before acquiring swarms
+    drop(actors);
 
-/// GET /web/provider-status - Provider status partial.
-pub async fn provider_status(State(state): State<SharedState>) -> ProviderStatusTemplate {
-    let ps = &state.provider_status;
-    ProviderStatusTemplate {
-        default_provider: ps.default_provider.clone(),
-        default_model: ps.default_model.clone(),
-        providers: ps
-            .providers
-            .iter()
-            .map(|p| ProviderInfoView {
-                name: p.name.clone(),
-                has_api_key: p.has_api_key,
-                model: p.model.clone(),
-            })
-            .collect(),
-        any_available: ps.any_available,
+    // Wake the agent loop so the manager responds to the human message promptly
+    // instead of waiting for the next idle-cycle poll (up to 5 seconds).
+    {
+        let swarms = state.swarms.read().await;
+        if let Some(swarm_handle) = swarms.get(&spec_id) {
+            let swarm = swarm_handle.swarm.lock().await;
+            swarm.notify_human_message();
+        }
     }
-}
-
-/// Mission ticker template — compact activity list for the mission strip.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/mission_ticker.html")]
-pub struct MissionTickerTemplate {
-    pub spec_id: String,
-    pub ticker_entries: Vec<TranscriptEntry>,
-    pub pending_question: Option<QuestionData>,
-}
-
-/// Agent LED indicators template for the command bar.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/agent_leds.html")]
-pub struct AgentLedsTemplate {
-    pub spec_id: String,
-    pub running: bool,
-    pub started: bool,
-}
-
-/// Agent status partial template.
-#[derive(Template, AskamaIntoResponse)]
-#[template(path = "partials/agent_status.html")]
-pub struct AgentStatusTemplate {
-    pub spec_id: String,
-    pub running: bool,
-    pub started: bool,
-    pub agent_count: usize,
-}
-
-/// GET /web/specs/{id}/ticker - Render the mission strip ticker content.
-pub async fn ticker(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
-    };
 
+    // Re-acquire actors to read transcript for response
     let actors = state.actors.read().await;
     let handle = match actors.get(&spec_id) {
         Some(h) => h,
@@ -3342,76 +4307,87 @@ pub async fn ticker(State(state): State<SharedState>, Path(id): Path<String>) ->
         }
     };
 
+    // Events are persisted by the background broadcast subscriber.
+
+    // Determine container_id from HX-Target header so the response replaces
+    // the correct transcript container (activity panel vs chat tab).
+    let container_id = sanitize_container_id(
+        headers
+            .get("HX-Target")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.trim_start_matches('#'))
+            .unwrap_or("activity-transcript"),
+    );
+
+    // Return refreshed transcript partial
     let spec_state = handle.read_state().await;
 
-    // Show last 10 transcript entries
-    let ticker_entries: Vec<TranscriptEntry> = spec_state
+    // Chat containers only show human + manager messages; see sanitize_container_id docs.
+    let is_chat = container_id == "chat-transcript" || container_id == "brainstorm-chat";
+    let is_ticker = container_id == "mission-ticker";
+
+    let mut transcript: Vec<TranscriptEntry> = spec_state
         .transcript
         .iter()
-        .rev()
-        .take(10)
-        .collect::<Vec<_>>()
-        .into_iter()
-        .rev()
+        .filter(|m| !is_chat || is_chat_participant(&m.sender))
         .map(to_transcript_entry)
         .collect();
+    mark_continuations(&mut transcript);
+    collapse_repeated_steps(&mut transcript);
 
-    let pending_question = spec_state
-        .pending_question
-        .as_ref()
-        .map(question_to_view_data);
-
-    MissionTickerTemplate {
-        spec_id: id,
-        ticker_entries,
-        pending_question,
-    }
-    .into_response()
-}
-
-/// GET /web/specs/{id}/agents/leds - Render agent LED indicators.
-pub async fn agent_leds(
-    State(state): State<SharedState>,
-    Path(id): Path<String>,
-) -> impl IntoResponse {
-    let spec_id = match parse_spec_id(&id) {
-        Ok(id) => id,
-        Err(resp) => return *resp,
-    };
+    let pending_question = spec_state.pending_question().map(question_to_view_data);
+    let queued_questions = spec_state.pending_questions.len().saturating_sub(1);
 
-    let swarms = state.swarms.read().await;
-    match swarms.get(&spec_id) {
-        Some(swarm_handle) => {
-            let swarm = swarm_handle.swarm.lock().await;
-            AgentLedsTemplate {
-                spec_id: id,
-                running: !swarm.is_paused(),
-                started: true,
-            }
-            .into_response()
+    if is_ticker {
+        // For mission ticker, show only last 10 entries
+        let ticker_entries: Vec<TranscriptEntry> = spec_state
+            .transcript
+            .iter()
+            .rev()
+            .take(10)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .map(to_transcript_entry)
+            .collect();
+        MissionTickerTemplate {
+            spec_id: id,
+            ticker_entries,
+            pending_question,
+            queued_questions,
         }
-        None => AgentLedsTemplate {
+        .into_response()
+    } else if is_chat {
+        ChatTranscriptTemplate {
             spec_id: id,
-            running: false,
-            started: false,
+            container_id,
+            transcript,
+            pending_question,
+            queued_questions,
         }
-        .into_response(),
+        .into_response()
+    } else {
+        ActivityTranscriptTemplate {
+            spec_id: id,
+            container_id,
+            transcript,
+            pending_question,
+            queued_questions,
+            compact: false,
+        }
+        .into_response()
     }
 }
 
-/// POST /web/specs/{id}/agents/start - Start agents for a spec.
-pub async fn start_agents(
-    State(state): State<SharedState>,
-    Path(id): Path<String>,
-) -> impl IntoResponse {
+/// POST /web/specs/{id}/undo - Undo last operation, return refreshed board.
+pub async fn undo(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    // Get actor handle first (read lock), then drop before acquiring swarms write lock
     let actors = state.actors.read().await;
-    let actor_handle = match actors.get(&spec_id) {
+    let handle = match actors.get(&spec_id) {
         Some(h) => h,
         None => {
             return (
@@ -3422,140 +4398,312 @@ pub async fn start_agents(
         }
     };
 
-    // Clone the existing actor handle so the swarm uses the same actor,
-    // ensuring events flow through the server's main event bus.
-    let swarm_actor_handle = actor_handle.clone();
-    drop(actors);
-
-    // Atomic check-and-insert: hold write lock to prevent TOCTOU race
-    // where two concurrent requests both pass the existence check and
-    // create duplicate swarms.
-    let mut swarms = state.swarms.write().await;
-    if let Some(swarm_handle) = swarms.get(&spec_id) {
-        let swarm = swarm_handle.swarm.lock().await;
-        return AgentStatusTemplate {
-            spec_id: id,
-            running: !swarm.is_paused(),
-            started: true,
-            agent_count: swarm.agent_count(),
-        }
-        .into_response();
-    }
-
-    // Create swarm (sync operation, safe to hold write lock)
-    let swarm = match SwarmOrchestrator::with_defaults(
-        spec_id,
-        swarm_actor_handle,
-        state.barnstormer_home.clone(),
-        Arc::new(crate::attachment_summarizer::ServerSummarizer {
-            home: state.barnstormer_home.clone(),
-        }),
-    ) {
-        Ok(s) => Arc::new(tokio::sync::Mutex::new(s)),
+    let _events = match handle.send_command(Command::Undo).await {
+        Ok(events) => events,
         Err(e) => {
             return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Html(format!(
-                    "<p class=\"error-msg\">Failed to start agents: {}</p>",
-                    e
-                )),
+                StatusCode::BAD_REQUEST,
+                Html(format!("<p class=\"error-msg\">Undo failed: {}</p>", e)),
             )
                 .into_response();
         }
     };
 
-    let agent_count = {
-        // This lock is uncontested since the swarm was just created
-        let s = swarm.lock().await;
-        s.agent_count()
-    };
-
-    // Spawn agent loop task and store the handle for cancellation.
-    // The loop lives in the agent crate; each agent gets its own
-    // broadcast receiver so events are never lost.
-    let task = tokio::spawn(barnstormer_agent::run_loop(Arc::clone(&swarm)));
+    // Events are persisted by the background broadcast subscriber.
 
-    // Insert into swarms map while still holding write lock
-    swarms.insert(spec_id, crate::app_state::SwarmHandle { swarm, task });
-    drop(swarms);
-
-    AgentStatusTemplate {
-        spec_id: id,
-        running: true,
-        started: true,
-        agent_count,
-    }
-    .into_response()
+    // Return refreshed board
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
 }
 
-/// POST /web/specs/{id}/agents/pause - Pause agents.
-pub async fn pause_agents(
-    State(state): State<SharedState>,
-    Path(id): Path<String>,
-) -> impl IntoResponse {
+/// POST /web/specs/{id}/redo - Redo the last undone operation, return the
+/// refreshed board. With nothing to redo the board is returned unchanged.
+pub async fn redo(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    let swarms = state.swarms.read().await;
-    match swarms.get(&spec_id) {
-        Some(swarm_handle) => {
-            let swarm = swarm_handle.swarm.lock().await;
-            swarm.pause();
-            AgentStatusTemplate {
-                spec_id: id,
-                running: false,
-                started: true,
-                agent_count: swarm.agent_count(),
-            }
-            .into_response()
-        }
-        None => AgentStatusTemplate {
-            spec_id: id,
-            running: false,
-            started: false,
-            agent_count: 0,
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
         }
-        .into_response(),
+    };
+
+    if let Err(e) = handle.send_command(Command::Redo).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!("<p class=\"error-msg\">Redo failed: {}</p>", e)),
+        )
+            .into_response();
     }
+
+    // Events are persisted by the background broadcast subscriber.
+
+    // Return refreshed board
+    let spec_state = handle.read_state().await;
+    let lanes = cards_by_lane(&spec_state);
+    BoardTemplate { spec_id: id, lanes }.into_response()
 }
 
-/// POST /web/specs/{id}/agents/resume - Resume agents.
-pub async fn resume_agents(
+#[derive(Deserialize)]
+pub struct PhaseForm {
+    target: String,
+}
+
+/// POST /web/specs/{id}/phase - Transition a spec between phases.
+pub async fn transition_phase(
     State(state): State<SharedState>,
     Path(id): Path<String>,
-) -> impl IntoResponse {
+    Form(form): Form<PhaseForm>,
+) -> Response {
     let spec_id = match parse_spec_id(&id) {
         Ok(id) => id,
         Err(resp) => return *resp,
     };
 
-    let swarms = state.swarms.read().await;
-    match swarms.get(&spec_id) {
-        Some(swarm_handle) => {
-            let swarm = swarm_handle.swarm.lock().await;
-            swarm.resume();
-            AgentStatusTemplate {
-                spec_id: id,
-                running: true,
-                started: true,
-                agent_count: swarm.agent_count(),
-            }
-            .into_response()
+    let target = match form.target.as_str() {
+        "brainstorming" => SpecPhase::Brainstorming,
+        "refining" => SpecPhase::Refining,
+        "complete" => SpecPhase::Complete,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Html("<p class=\"error-msg\">Invalid phase target.</p>".to_string()),
+            )
+                .into_response();
         }
-        None => AgentStatusTemplate {
-            spec_id: id,
-            running: false,
-            started: false,
-            agent_count: 0,
+    };
+
+    let actors = state.actors.read().await;
+    let Some(handle) = actors.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
+    };
+
+    match handle
+        .send_command(Command::TransitionPhase {
+            target: target.clone(),
+        })
+        .await
+    {
+        Ok(_) => {
+            // Phase transition triggers SSE phase_transitioned event,
+            // which causes the client to reload the entire workspace.
+            (StatusCode::OK, Html("<span>OK</span>".to_string())).into_response()
         }
-        .into_response(),
+        Err(ActorError::AlreadyInPhase) => (
+            StatusCode::CONFLICT,
+            Html("<p class=\"error-msg\">Already in target phase.</p>".to_string()),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Html(format!("<p class=\"error-msg\">Error: {}</p>", e)),
+        )
+            .into_response(),
     }
 }
 
-/// GET /web/specs/{id}/agents/status - Get current agent status.
-pub async fn agent_status(
+/// Context panel partial template — rendered HTML for the brainstorming
+/// right-rail panel showing all live (non-removed) context attachments.
+#[derive(Template)]
+#[template(path = "partials/context_panel.html")]
+struct ContextPanelTemplate {
+    spec_id: String,
+    attachments: Vec<ContextPanelItem>,
+}
+
+/// Coarse-grained kind of a context attachment, derived from its sniffed
+/// MIME type. Used by the context panel template to branch between
+/// browser-native preview affordances (raster `<img>`, SVG `<img>`, `<audio>`,
+/// `<video>`, PDF icon) and to pick a per-kind badge color in the collapsed
+/// header. `Text` is the catch-all for anything that doesn't render natively
+/// in a preview block (markdown, plaintext, code, etc.).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentKind {
+    Text,
+    ImageRaster,
+    ImageSvg,
+    Pdf,
+    Audio,
+    Video,
+}
+
+impl AttachmentKind {
+    /// Classify a stored MIME string. Any parameters after the first `;`
+    /// (e.g. `; charset=utf-8`) are dropped before matching, and the result
+    /// is lowercased so casing variation in the upload pipeline doesn't
+    /// flip an attachment between branches.
+    fn from_mime(mime: &str) -> Self {
+        let normalized = mime
+            .split(';')
+            .next()
+            .unwrap_or(mime)
+            .trim()
+            .to_ascii_lowercase();
+        if normalized == "image/svg+xml" {
+            Self::ImageSvg
+        } else if normalized.starts_with("image/") {
+            Self::ImageRaster
+        } else if normalized == "application/pdf" {
+            Self::Pdf
+        } else if normalized.starts_with("audio/") {
+            Self::Audio
+        } else if normalized.starts_with("video/") {
+            Self::Video
+        } else {
+            Self::Text
+        }
+    }
+}
+
+/// View model for a single context attachment row in the panel.
+///
+/// `summary` holds the raw (plain-text) summary used for the collapsed-state
+/// tooltip (the native `title` attribute on the `<summary>` element). It's
+/// also used as the presence flag for rendering the "in context" pill.
+/// `summary_html` holds the pre-rendered HTML produced by `render_markdown`,
+/// displayed only when the card is expanded. `summary_error` is the
+/// last-attempt failure reason, used to drive the four-state summary UI
+/// (pending / ok / stale-with-error / failed).
+struct ContextPanelItem {
+    attachment_id: String,
+    filename: String,
+    extension: String,
+    size_display: String,
+    added_display: String,
+    summary: Option<String>,
+    summary_html: Option<String>,
+    summary_error: Option<String>,
+    user_notes: Option<String>,
+    /// Number of non-removed cards whose `source_attachment_id` points at this
+    /// attachment. Rendered in the collapsed header so the user can see at a
+    /// glance how much of an attachment the Manager has synthesized.
+    card_count: usize,
+    /// Coarse classification of the attachment for per-kind preview rendering
+    /// and badge coloring in the template.
+    kind: AttachmentKind,
+    /// Original sniffed MIME type, forwarded to `<source type=...>` on the
+    /// `<audio>` / `<video>` preview elements.
+    mime_type: String,
+    /// `/web/specs/{spec_id}/context/{attachment_id}/raw` — the source URL for
+    /// inline `<img>` / `<audio>` / `<video>` previews.
+    raw_url: String,
+}
+
+/// A pending proposal prepared for display in the proposals widget.
+pub struct ProposalView {
+    pub proposal_id: String,
+    pub summary: String,
+    pub proposed_by: String,
+    pub reason: String,
+}
+
+/// Pending-proposals widget: agent commands awaiting human approval.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/proposals.html")]
+pub struct ProposalsTemplate {
+    pub spec_id: String,
+    pub proposals: Vec<ProposalView>,
+}
+
+/// One-line description of a proposed command, naming cards by title where
+/// they still exist so the human can judge it without opening the board.
+fn describe_proposed_command(cmd: &Command, spec_state: &SpecState) -> String {
+    let card_title = |card_id: &Ulid| {
+        spec_state
+            .cards
+            .get(card_id)
+            .map(|c| format!("\"{}\"", c.title))
+            .unwrap_or_else(|| "a card".to_string())
+    };
+    match cmd {
+        Command::CreateCard {
+            card_type, title, ..
+        } => format!("Create {} card \"{}\"", card_type, title),
+        Command::UpdateCard { card_id, .. } => format!("Edit card {}", card_title(card_id)),
+        Command::MoveCard { card_id, lane, .. } | Command::ReorderCard { card_id, lane, .. } => {
+            format!("Move card {} to {}", card_title(card_id), lane)
+        }
+        Command::DeleteCard { card_id, .. } => format!("Delete card {}", card_title(card_id)),
+        Command::SetCardDone { card_id, done, .. } => format!(
+            "Mark card {} {}",
+            card_title(card_id),
+            if *done { "done" } else { "not done" }
+        ),
+        Command::ArchiveCard { card_id, .. } => format!("Archive card {}", card_title(card_id)),
+        Command::UnarchiveCard { card_id, .. } => {
+            format!("Unarchive card {}", card_title(card_id))
+        }
+        Command::AddCardAttachment {
+            card_id,
+            label,
+            target,
+            ..
+        } => format!(
+            "Attach {} to card {}",
+            if label.trim().is_empty() {
+                target
+            } else {
+                label
+            },
+            card_title(card_id)
+        ),
+        Command::RemoveCardAttachment { card_id, .. } => {
+            format!("Remove attachment from card {}", card_title(card_id))
+        }
+        Command::RenameLane { from, to, .. } => format!("Rename lane {} to {}", from, to),
+        Command::CreateLane { name, .. } => format!("Create lane {}", name),
+        Command::DeleteLane {
+            name,
+            move_cards_to,
+            ..
+        } => match move_cards_to {
+            Some(to) => format!("Delete lane {} and move its cards to {}", name, to),
+            None => format!("Delete lane {}", name),
+        },
+        Command::ReorderLanes { order, .. } => format!("Reorder lanes: {}", order.join(", ")),
+        Command::UpdateSpecCore { .. } => "Update spec details".to_string(),
+        Command::TransitionPhase { target } => format!("Move the spec to {:?}", target),
+        Command::SetSpecArchived { archived } => if *archived {
+            "Archive the spec"
+        } else {
+            "Unarchive the spec"
+        }
+        .to_string(),
+        other => serde_json::to_value(other)
+            .ok()
+            .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(str::to_string))
+            .unwrap_or_else(|| "Unknown change".to_string()),
+    }
+}
+
+fn proposals_view(spec_state: &SpecState) -> Vec<ProposalView> {
+    spec_state
+        .pending_proposals
+        .iter()
+        .map(|p| ProposalView {
+            proposal_id: p.proposal_id.to_string(),
+            summary: describe_proposed_command(&p.command, spec_state),
+            proposed_by: sender_display(&p.proposed_by, None).0,
+            reason: p.reason.clone(),
+        })
+        .collect()
+}
+
+/// GET /web/specs/{id}/proposals - Render the pending-proposals widget.
+pub async fn proposals(
     State(state): State<SharedState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
@@ -3564,1847 +4712,5316 @@ pub async fn agent_status(
         Err(resp) => return *resp,
     };
 
-    let swarms = state.swarms.read().await;
-    match swarms.get(&spec_id) {
-        Some(swarm_handle) => {
-            let swarm = swarm_handle.swarm.lock().await;
-            AgentStatusTemplate {
-                spec_id: id,
-                running: !swarm.is_paused(),
-                started: true,
-                agent_count: swarm.agent_count(),
-            }
-            .into_response()
-        }
-        None => AgentStatusTemplate {
-            spec_id: id,
-            running: false,
-            started: false,
-            agent_count: 0,
-        }
-        .into_response(),
-    }
-}
+    let actors = state.actors.read().await;
+    let Some(handle) = actors.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
+    };
 
-/// Helper to start the agent swarm for a spec, if a provider is available.
-/// Returns silently if no provider is configured, if the swarm already exists,
-/// or if swarm creation fails. Used by both web and API create_spec handlers.
-pub async fn try_start_agents(
-    state: &SharedState,
-    spec_id: Ulid,
-    actor_handle: &barnstormer_core::SpecActorHandle,
-) {
-    if !state.provider_status.any_available {
-        tracing::info!(
-            "no LLM provider configured, skipping agent start for spec {}",
-            spec_id
-        );
-        return;
+    let spec_state = handle.read_state().await;
+    ProposalsTemplate {
+        spec_id: id,
+        proposals: proposals_view(&spec_state),
     }
+    .into_response()
+}
 
-    // Clone the existing actor handle so the swarm uses the same actor,
-    // ensuring events flow through the server's main event bus.
-    let swarm_actor_handle = actor_handle.clone();
+/// One lint finding prepared for display.
+pub struct LintFindingView {
+    /// Rule identifier, used as a CSS modifier (e.g. `dangling_ref`).
+    pub rule: String,
+    pub label: String,
+    pub message: String,
+}
 
-    // Atomic check-and-insert: hold write lock to prevent TOCTOU race
-    // where two concurrent requests both pass the existence check and
-    // create duplicate swarms.
-    let mut swarms = state.swarms.write().await;
-    if swarms.contains_key(&spec_id) {
-        return;
-    }
+/// Spec health check results.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/lint.html")]
+pub struct LintTemplate {
+    pub findings: Vec<LintFindingView>,
+}
 
-    // Create swarm (sync operation, safe to hold write lock)
-    let swarm = match SwarmOrchestrator::with_defaults(
-        spec_id,
-        swarm_actor_handle,
-        state.barnstormer_home.clone(),
-        Arc::new(crate::attachment_summarizer::ServerSummarizer {
-            home: state.barnstormer_home.clone(),
-        }),
-    ) {
-        Ok(s) => Arc::new(tokio::sync::Mutex::new(s)),
-        Err(e) => {
-            tracing::warn!("failed to auto-start agents for spec {}: {}", spec_id, e);
-            return;
-        }
+/// GET /web/specs/{id}/lint - Run the spec health checks and render findings.
+pub async fn lint(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
     };
 
-    let agent_count = {
-        // This lock is uncontested since the swarm was just created
-        let s = swarm.lock().await;
-        s.agent_count()
+    let actors = state.actors.read().await;
+    let Some(handle) = actors.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
     };
 
-    // Spawn background agent loop and store the handle for cancellation.
-    // The loop lives in the agent crate; each agent gets its own
-    // broadcast receiver so events are never lost.
-    let task = tokio::spawn(barnstormer_agent::run_loop(Arc::clone(&swarm)));
-
-    // Insert into swarms map while still holding write lock
-    swarms.insert(spec_id, crate::app_state::SwarmHandle { swarm, task });
-    drop(swarms);
-    tracing::info!("auto-started {} agents for spec {}", agent_count, spec_id);
+    let findings = barnstormer_core::lint::lint(&*handle.read_state().await, Utc::now());
+    LintTemplate {
+        findings: findings
+            .into_iter()
+            .map(|f| LintFindingView {
+                rule: f.rule.as_str().to_string(),
+                label: f.rule.label().to_string(),
+                message: f.message,
+            })
+            .collect(),
+    }
+    .into_response()
 }
 
-/// Spawn a background task that subscribes to an actor's broadcast channel
-/// and persists every event to JSONL. This catches ALL events including
-/// those produced by agents, which bypass the inline `persist_events` path.
-///
-/// On broadcast lag (missed events), saves a state snapshot so crash recovery
-/// can restore from the snapshot rather than relying on a gapped JSONL log.
-///
-/// Returns the JoinHandle so the caller can store it for cleanup.
-pub fn spawn_event_persister(
-    actor: &barnstormer_core::SpecActorHandle,
-    spec_id: Ulid,
-    barnstormer_home: &std::path::Path,
-) -> tokio::task::JoinHandle<()> {
-    let mut rx = actor.subscribe();
-    let actor_handle = actor.clone();
-    let log_path = barnstormer_home
-        .join("specs")
-        .join(spec_id.to_string())
-        .join("events.jsonl");
-    let snapshot_dir = barnstormer_home
-        .join("specs")
-        .join(spec_id.to_string())
-        .join("snapshots");
-
-    tokio::spawn(async move {
-        // Retry opening the JSONL log a few times before giving up, in case
-        // the directory or filesystem is temporarily unavailable at startup.
-        const MAX_OPEN_RETRIES: u32 = 5;
-        const RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
-
-        let mut log = None;
-        for attempt in 1..=MAX_OPEN_RETRIES {
-            match JsonlLog::open(&log_path) {
-                Ok(l) => {
-                    log = Some(l);
-                    break;
-                }
-                Err(e) => {
-                    tracing::error!(
-                        "event persister failed to open log for spec {} at {} (attempt {}/{}): {}",
-                        spec_id,
-                        log_path.display(),
-                        attempt,
-                        MAX_OPEN_RETRIES,
-                        e
-                    );
-                    if attempt < MAX_OPEN_RETRIES {
-                        tokio::time::sleep(RETRY_DELAY).await;
-                    }
-                }
-            }
-        }
-        let Some(mut log) = log else {
-            tracing::error!(
-                "event persister giving up on spec {} after {} retries",
-                spec_id,
-                MAX_OPEN_RETRIES,
-            );
-            return;
-        };
+/// POST /web/specs/{id}/proposals/{proposal_id}/approve - Apply a proposal.
+pub async fn approve_proposal(
+    State(state): State<SharedState>,
+    Path((id, proposal_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    resolve_proposal(state, id, proposal_id, true).await
+}
 
-        loop {
-            match rx.recv().await {
-                Ok(event) => {
-                    if event.payload.is_ephemeral() {
-                        continue;
-                    }
-                    if let Err(e) = log.append(&event) {
-                        tracing::error!(
-                            "event persister failed to write event for spec {}: {}",
-                            spec_id,
-                            e
-                        );
-                    }
-                }
-                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                    tracing::warn!(
-                        "event persister for spec {} lagged, missed {} events — saving snapshot",
-                        spec_id,
-                        n
-                    );
-                    // Save a snapshot so crash recovery can restore from it
-                    // rather than relying on the gapped JSONL log.
-                    let state = actor_handle.read_state().await.clone();
-                    let snap = SnapshotData {
-                        last_event_id: state.last_event_id,
-                        state: state.clone(),
-                        agent_contexts: std::collections::HashMap::new(),
-                        saved_at: Utc::now(),
-                    };
-                    if let Err(e) = save_snapshot(&snapshot_dir, &snap) {
-                        tracing::error!(
-                            "event persister for spec {} failed to save recovery snapshot: {}",
-                            spec_id,
-                            e
-                        );
-                    }
-                }
-                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-                    tracing::debug!(
-                        "event persister for spec {} shutting down (channel closed)",
-                        spec_id
-                    );
-                    break;
-                }
-            }
-        }
-    })
+/// POST /web/specs/{id}/proposals/{proposal_id}/reject - Discard a proposal.
+pub async fn reject_proposal(
+    State(state): State<SharedState>,
+    Path((id, proposal_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    resolve_proposal(state, id, proposal_id, false).await
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::app_state::AppState;
-    use crate::providers::ProviderStatus;
-    use crate::routes::create_router;
-    use axum::body::Body;
-    use http::Request;
-    use std::sync::Arc;
-    use tower::ServiceExt;
+/// Shared body of the approve/reject handlers. Re-renders the widget so the
+/// resolved proposal disappears; a failed approval leaves it listed with an
+/// error above it.
+async fn resolve_proposal(
+    state: SharedState,
+    id: String,
+    proposal_id: String,
+    approve: bool,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let Ok(proposal_id) = proposal_id.parse::<Ulid>() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html("<p class=\"error-msg\">Invalid proposal ID.</p>".to_string()),
+        )
+            .into_response();
+    };
 
-    fn test_state() -> SharedState {
-        let dir = tempfile::TempDir::new().unwrap();
-        let provider_status = ProviderStatus {
-            default_provider: "anthropic".to_string(),
-            default_model: None,
-            providers: vec![],
-            any_available: false,
-        };
-        Arc::new(AppState::new(dir.keep(), provider_status))
-    }
+    let actors = state.actors.read().await;
+    let Some(handle) = actors.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
+    };
 
-    /// Test multipart boundary used by `mp_description_body`. Tests that
-    /// POST to `/web/specs` use this to construct the request body, since
-    /// the endpoint switched from form-encoded to multipart in Task 18.
-    const MP_BOUNDARY: &str = "----BarnstormerInlineTest";
+    let cmd = if approve {
+        Command::ApproveProposal { proposal_id }
+    } else {
+        Command::RejectProposal { proposal_id }
+    };
+    let error = handle.send_command(cmd).await.err();
 
-    /// Build a `Body` containing a multipart/form-data payload with just a
-    /// `description` field. Pair with `MP_CONTENT_TYPE` as the
-    /// `content-type` header. The description is embedded verbatim — do
-    /// not URL-encode it.
-    fn mp_description_body(description: &str) -> Body {
-        let payload = format!(
-            "--{MP_BOUNDARY}\r\n\
-             Content-Disposition: form-data; name=\"description\"\r\n\r\n\
-             {description}\r\n\
-             --{MP_BOUNDARY}--\r\n"
-        );
-        Body::from(payload)
+    let spec_state = handle.read_state().await;
+    let widget = ProposalsTemplate {
+        spec_id: id,
+        proposals: proposals_view(&spec_state),
+    };
+    match error {
+        None => widget.into_response(),
+        Some(e) => {
+            let body = widget.render().unwrap_or_default();
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Html(format!(
+                    "<p class=\"error-msg\">Could not apply proposal: {}</p>{}",
+                    html_escape(&e.to_string()),
+                    body
+                )),
+            )
+                .into_response()
+        }
     }
+}
 
-    /// Content-type header value matching `mp_description_body`.
-    const MP_CONTENT_TYPE: &str = "multipart/form-data; boundary=----BarnstormerInlineTest";
-
-    #[test]
-    fn index_template_renders() {
-        let tmpl = IndexTemplate {};
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("barnstormer"));
-        assert!(rendered.contains("Select a spec"));
+/// Human-readable file size (B / KB / MB) for display in the context panel.
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// Shared helper: builds the context panel HTML for a given spec. Returns
+/// 404 if the spec is unknown, 500 on render failure. All four context
+/// handlers (upload/notes/delete/GET panel) route through this helper so
+/// they return identical HTML on success.
+async fn render_context_panel_for(state: &SharedState, spec_id: Ulid) -> Response {
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+    };
+    drop(actors);
+
+    let spec_state = handle.read_state().await;
+    let attachments: Vec<ContextPanelItem> = spec_state
+        .context_attachments
+        .iter()
+        .filter(|a| !a.removed)
+        .map(|a| {
+            let card_count = spec_state
+                .cards
+                .values()
+                .filter(|c| c.source_attachment_id == Some(a.attachment_id))
+                .count();
+            ContextPanelItem {
+                attachment_id: a.attachment_id.to_string(),
+                filename: a.filename.clone(),
+                extension: std::path::Path::new(&a.filename)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("txt")
+                    .to_string(),
+                size_display: format_size(a.size_bytes),
+                added_display: a.added_at.format("%H:%M").to_string(),
+                summary: a.summary.clone(),
+                summary_html: a.summary.as_deref().map(render_markdown),
+                summary_error: a.summary_error.clone(),
+                user_notes: a.user_notes.clone(),
+                card_count,
+                kind: AttachmentKind::from_mime(&a.mime_type),
+                mime_type: a.mime_type.clone(),
+                raw_url: format!("/web/specs/{}/context/{}/raw", spec_id, a.attachment_id),
+            }
+        })
+        .collect();
+    drop(spec_state);
+
+    let tmpl = ContextPanelTemplate {
+        spec_id: spec_id.to_string(),
+        attachments,
+    };
+    match tmpl.render() {
+        Ok(html) => Html(html).into_response(),
+        Err(e) => {
+            tracing::error!("context_panel render failed: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, "render failed").into_response()
+        }
+    }
+}
+
+/// GET /web/specs/{id}/context-panel - Render the context panel partial.
+///
+/// Returns the full `<div id="context-panel">` partial; the brainstorming
+/// view and Task 16 SSE wiring swap this element via HTMX.
+pub async fn context_panel(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    render_context_panel_for(&state, spec_id).await
+}
+
+/// GET /web/specs/{id}/context-preview - Render a read-only preview of the
+/// "## Context Files" section of the agent's current task prompt.
+///
+/// Uses `barnstormer_agent::render_context_files_section` so what the user
+/// sees exactly matches what the Manager is being told about attached files.
+/// Non-removed attachments only. Returns a small `.card` wrapper for drop-in
+/// swap into the panel; empty state shows "No context files attached."
+pub async fn context_preview(State(state): State<SharedState>, Path(id): Path<String>) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+    };
+    drop(actors);
+
+    let spec_state = handle.read_state().await;
+    let live: Vec<barnstormer_core::state::ContextAttachment> = spec_state
+        .context_attachments
+        .iter()
+        .filter(|a| !a.removed)
+        .cloned()
+        .collect();
+    drop(spec_state);
+
+    let rendered = barnstormer_agent::render_context_files_section(&live);
+
+    // Minimal HTML: escape the rendered markdown so the preview is read-only
+    // and can't inject markup through attachment fields.
+    let inner = if rendered.is_empty() {
+        "No context files attached.".to_string()
+    } else {
+        html_escape(&rendered)
+    };
+    let body = format!(
+        r#"<div class="card" style="margin: var(--spacing-md); white-space: pre-wrap; font-family: monospace; font-size: 0.78rem;">{inner}</div>"#,
+    );
+    Html(body).into_response()
+}
+
+/// Minimal HTML-escape for the context preview body. Only escapes the five
+/// characters that matter for HTML text content; the wrapper uses
+/// `white-space: pre-wrap` so newlines and spaces are preserved as-is.
+fn html_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// POST /web/specs/{id}/context - Upload a context file during brainstorming.
+///
+/// Accepts `multipart/form-data` with a single `file` part. Writes the file
+/// to disk under the spec's context directory and emits a `ContextAttached`
+/// event via `Command::AttachContext`. Gated to `SpecPhase::Brainstorming`;
+/// outside that phase returns 409 CONFLICT. The MIME type is server-sniffed
+/// from the bytes (browser-claimed Content-Type is ignored); unrecognized
+/// or non-whitelisted types return 415. Uploads over 20MB return 413.
+pub async fn upload_context(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    mut multipart: axum::extract::Multipart,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => {
+            return (StatusCode::NOT_FOUND, "spec not found").into_response();
+        }
+    };
+    drop(actors);
+
+    // Gate: brainstorming only.
+    let phase = handle.read_state().await.phase.clone();
+    if phase != SpecPhase::Brainstorming {
+        return (
+            StatusCode::CONFLICT,
+            "context files can only be attached during brainstorming",
+        )
+            .into_response();
+    }
+
+    // Extract first `file` part. Streamed-and-capped so a request can't
+    // buffer up to the configured global body cap before we reject it.
+    let mut file_bytes: Option<Vec<u8>> = None;
+    let mut filename: Option<String> = None;
+    loop {
+        match multipart.next_field().await {
+            Ok(Some(mut field)) => {
+                if field.name() == Some("file") {
+                    filename = field.file_name().map(str::to_string);
+                    // Browser-claimed content type is intentionally ignored;
+                    // the server sniffs the bytes below.
+                    let bytes = match read_field_capped(&mut field).await {
+                        Ok(Some(b)) => b,
+                        Ok(None) => {
+                            return (StatusCode::BAD_REQUEST, "empty file part").into_response();
+                        }
+                        Err(resp) => return resp,
+                    };
+                    file_bytes = Some(bytes);
+                    break;
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    format!("multipart parse error: {e}"),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    let Some(bytes) = file_bytes else {
+        return (StatusCode::BAD_REQUEST, "missing file part").into_response();
+    };
+
+    // Sniff MIME from bytes. Browser-supplied content-type is not trusted —
+    // a client can claim anything, but we serve, summarize, and gate on what
+    // the bytes actually are.
+    let detected_mime = match crate::context_storage::sniff_mime(
+        &bytes,
+        filename.as_deref().unwrap_or("file"),
+    ) {
+        Some(m) => m,
+        None => {
+            return (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "couldn't identify file type — uploads must be a recognized image, document, audio, video, or UTF-8 text file",
+            )
+                .into_response();
+        }
+    };
+    if !crate::context_storage::is_whitelisted_mime(&detected_mime) {
+        return (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            format!("file type '{detected_mime}' is not supported"),
+        )
+            .into_response();
+    }
+
+    let filename = crate::context_storage::sanitize_filename(filename.as_deref().unwrap_or("file"));
+    let attachment_id = Ulid::new();
+
+    let path = crate::context_storage::attachment_path(
+        &state.barnstormer_home,
+        spec_id,
+        attachment_id,
+        &filename,
+    );
+    if let Err(e) = crate::context_storage::write_bytes(&path, &bytes) {
+        tracing::error!("failed to write attachment: {e}");
+        return (StatusCode::INTERNAL_SERVER_ERROR, "write failed").into_response();
+    }
+
+    // SVG-specific: rasterize and cache alongside the original. Failure
+    // degrades to markup-only summarization; the original SVG is still on
+    // disk and the upload still lands.
+    if detected_mime == "image/svg+xml"
+        && let Ok(markup) = std::str::from_utf8(&bytes)
+    {
+        match crate::svg_raster::rasterize_svg(markup) {
+            Ok(png) => {
+                let raster_path = crate::context_storage::attachment_dir(
+                    &state.barnstormer_home,
+                    spec_id,
+                    attachment_id,
+                )
+                .join("rasterized.png");
+                if let Err(e) = crate::context_storage::write_bytes(&raster_path, &png) {
+                    tracing::warn!("failed to cache rasterized SVG: {e}");
+                }
+            }
+            Err(e) => {
+                tracing::warn!("SVG rasterization failed for {filename}: {e}");
+            }
+        }
+    }
+
+    let size_bytes = bytes.len() as u64;
+    let cmd = Command::AttachContext {
+        attachment_id,
+        filename: filename.clone(),
+        mime_type: detected_mime.clone(),
+        size_bytes,
+    };
+    if let Err(e) = handle.send_command(cmd).await {
+        // Bytes already on disk — without the event referencing them they'd
+        // leak and the filesystem would drift from actor state. Best-effort
+        // cleanup (failure is logged but doesn't block the error response).
+        // Remove the whole attachment dir so we also drop SVG's
+        // `rasterized.png` sibling instead of leaving it orphaned.
+        let dir =
+            crate::context_storage::attachment_dir(&state.barnstormer_home, spec_id, attachment_id);
+        if let Err(remove_err) = std::fs::remove_dir_all(&dir) {
+            tracing::warn!("failed to clean up orphaned context dir {dir:?}: {remove_err}");
+        }
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("command failed: {e}"),
+        )
+            .into_response();
+    }
+
+    // Build per-kind SummarizerInput from disk and dispatch — fire-and-forget.
+    // Summary will land via SSE when done. Notes aren't available at upload
+    // time; they're populated later via PATCH.
+    let attachment_opt = handle
+        .read_state()
+        .await
+        .context_attachments
+        .iter()
+        .find(|a| a.attachment_id == attachment_id)
+        .cloned();
+    if let Some(att) = attachment_opt {
+        match crate::context_storage::build_summarizer_input(&state.barnstormer_home, spec_id, &att)
+            .await
+        {
+            Ok(input) => {
+                crate::summarizer::spawn_summarize(
+                    state.provider_status.default_provider.clone(),
+                    handle.clone(),
+                    attachment_id,
+                    filename.clone(),
+                    None,
+                    input,
+                );
+            }
+            Err(e) => {
+                tracing::warn!("could not build summarizer input for {attachment_id}: {e}");
+            }
+        }
+    }
+
+    // Return the re-rendered panel partial so HTMX can swap it in place.
+    render_context_panel_for(&state, spec_id).await
+}
+
+/// Form body for the notes editors (context attachment notes and spec
+/// notes) — HTMX submits form-encoded by default.
+#[derive(Debug, Deserialize)]
+pub struct NotesForm {
+    pub notes: String,
+}
+
+/// PATCH /web/specs/{id}/context/{att_id}/notes - Update user-authored notes
+/// for a context attachment. Sends `Command::UpdateContextNotes` to the actor;
+/// returns 404 if the attachment is unknown, 409 if it has already been
+/// removed (soft-delete tombstone).
+pub async fn update_context_notes(
+    State(state): State<SharedState>,
+    Path((id, att_id)): Path<(String, String)>,
+    Form(form): Form<NotesForm>,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let attachment_id = match att_id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+    };
+    drop(actors);
+
+    let notes_for_summarizer = form.notes.clone();
+    let cmd = Command::UpdateContextNotes {
+        attachment_id,
+        notes: form.notes,
+    };
+    match handle.send_command(cmd).await {
+        Ok(_) => {
+            // Re-fire summarizer with the new notes. Latest-wins concurrency:
+            // if multiple PATCHes land in quick succession, each spawns its
+            // own task and whichever Command::SummarizeContext arrives last
+            // is the summary that sticks.
+            let attachment = handle
+                .read_state()
+                .await
+                .context_attachments
+                .iter()
+                .find(|a| a.attachment_id == attachment_id && !a.removed)
+                .cloned();
+            if let Some(att) = attachment {
+                match crate::context_storage::build_summarizer_input(
+                    &state.barnstormer_home,
+                    spec_id,
+                    &att,
+                )
+                .await
+                {
+                    Ok(input) => {
+                        crate::summarizer::spawn_summarize(
+                            state.provider_status.default_provider.clone(),
+                            handle.clone(),
+                            attachment_id,
+                            att.filename.clone(),
+                            Some(notes_for_summarizer),
+                            input,
+                        );
+                    }
+                    Err(e) => {
+                        tracing::warn!("could not build summarizer input on notes update: {e}")
+                    }
+                }
+            }
+            render_context_panel_for(&state, spec_id).await
+        }
+        Err(ActorError::AttachmentNotFound(_)) => {
+            (StatusCode::NOT_FOUND, "attachment not found").into_response()
+        }
+        Err(ActorError::AttachmentAlreadyRemoved(_)) => {
+            (StatusCode::CONFLICT, "attachment is removed").into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}")).into_response(),
+    }
+}
+
+/// DELETE /web/specs/{id}/context/{att_id} - Soft-remove a context attachment.
+/// Emits `Command::RemoveContext`; the on-disk file is preserved so undo can
+/// restore it. Returns 404 for unknown ids and 409 when the attachment has
+/// already been removed.
+pub async fn remove_context(
+    State(state): State<SharedState>,
+    Path((id, att_id)): Path<(String, String)>,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let attachment_id = match att_id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+    };
+    drop(actors);
+
+    match handle
+        .send_command(Command::RemoveContext { attachment_id })
+        .await
+    {
+        Ok(_) => render_context_panel_for(&state, spec_id).await,
+        Err(ActorError::AttachmentNotFound(_)) => {
+            (StatusCode::NOT_FOUND, "attachment not found").into_response()
+        }
+        Err(ActorError::AttachmentAlreadyRemoved(_)) => {
+            (StatusCode::CONFLICT, "attachment already removed").into_response()
+        }
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}")).into_response(),
+    }
+}
+
+/// POST /web/specs/{id}/context/{att_id}/resummarize - Manually trigger a
+/// fresh summarizer pass for an attachment. The summary command itself lands
+/// asynchronously; this returns the panel partial with the spinner state.
+///
+/// The 410-vs-404 ordering matters: a soft-removed attachment is "gone" (we
+/// know it existed) so we surface 410 Gone rather than collapsing to 404.
+/// `att.user_notes.clone()` is forwarded to the summarizer so manual rerun
+/// uses the same notes context as the auto-spawn from upload/notes-update.
+pub async fn resummarize_context(
+    State(state): State<SharedState>,
+    Path((id, att_id)): Path<(String, String)>,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let attachment_id = match att_id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+    };
+    drop(actors);
+
+    let attachment_opt = handle
+        .read_state()
+        .await
+        .context_attachments
+        .iter()
+        .find(|a| a.attachment_id == attachment_id)
+        .cloned();
+    let att = match attachment_opt {
+        // Order matters: removed-but-known must return 410 before falling
+        // through to the generic Some(a) live path. Reordering these arms
+        // would surface 200 for soft-deleted attachments.
+        Some(a) if a.removed => {
+            return (StatusCode::GONE, "attachment is removed").into_response();
+        }
+        Some(a) => a,
+        None => return (StatusCode::NOT_FOUND, "attachment not found").into_response(),
+    };
+
+    match crate::context_storage::build_summarizer_input(&state.barnstormer_home, spec_id, &att)
+        .await
+    {
+        Ok(input) => {
+            crate::summarizer::spawn_summarize(
+                state.provider_status.default_provider.clone(),
+                handle.clone(),
+                attachment_id,
+                att.filename.clone(),
+                att.user_notes.clone(),
+                input,
+            );
+            render_context_panel_for(&state, spec_id).await
+        }
+        Err(e) => {
+            tracing::warn!("could not build summarizer input for resummarize: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("{e}")).into_response()
+        }
+    }
+}
+
+/// GET /web/specs/{id}/context/{att_id}/raw - Stream the raw bytes of a
+/// context attachment. Only live (non-removed) attachments are served; both
+/// "unknown" and "soft-removed" cases return 404 so callers can't distinguish
+/// them.
+///
+/// Security: the stored `mime_type` came from server-side magic-byte sniffing
+/// in the upload pipeline (see `context_storage::sniff_mime`) and was checked
+/// against a whitelist before it ever landed in state, so it's mostly
+/// trustworthy as the `Content-Type`. We still send
+/// `X-Content-Type-Options: nosniff` as defense-in-depth so the browser
+/// doesn't override the declared type.
+///
+/// Two narrow exceptions to neuter direct-navigation script execution:
+///
+/// - `text/html` is downgraded to `text/plain; charset=utf-8` on the wire.
+///   HTML is on the upload whitelist (so users can attach pages they want to
+///   reference), but serving it back as `text/html` would let any `<script>`
+///   inside execute in this origin when a user navigates directly to the
+///   `/raw` URL — a stored-XSS foothold.
+/// - `image/svg+xml` is served as `image/png` by reading the cached
+///   `rasterized.png` produced at upload time. SVG is a script-executable
+///   format (`<script>`, `<foreignObject>` w/ HTML, JS event handlers), so
+///   serving the original SVG bytes with `image/svg+xml` would have the same
+///   stored-XSS exposure as HTML. The original SVG is preserved on disk; this
+///   only affects what `/raw` returns. If the rasterized cache is missing
+///   (rasterization failed at upload), we fall back to
+///   `text/plain; charset=utf-8` so the bytes still surface but can't run.
+///
+/// Other sniffed mimes (`image/png`, `image/jpeg`, `application/pdf`,
+/// `audio/*`, `video/*`, `text/plain`, `text/markdown`) don't execute JS, so
+/// they pass through with their real `Content-Type` so `<img>`, `<audio>`,
+/// `<video>`, and PDF viewers render correctly.
+pub async fn download_context(
+    State(state): State<SharedState>,
+    Path((id, att_id)): Path<(String, String)>,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let attachment_id = match att_id.parse::<Ulid>() {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::BAD_REQUEST, "bad attachment id").into_response(),
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => return (StatusCode::NOT_FOUND, "spec not found").into_response(),
+    };
+    drop(actors);
+
+    let spec_state = handle.read_state().await;
+    let att = match spec_state
+        .context_attachments
+        .iter()
+        .find(|a| a.attachment_id == attachment_id && !a.removed)
+    {
+        Some(a) => a.clone(),
+        None => return (StatusCode::NOT_FOUND, "attachment not found").into_response(),
+    };
+    drop(spec_state);
+
+    let path = crate::context_storage::attachment_path(
+        &state.barnstormer_home,
+        spec_id,
+        attachment_id,
+        &att.filename,
+    );
+    match std::fs::read(&path) {
+        Ok(bytes) => {
+            let stored = att.mime_type.clone();
+            let normalized = stored
+                .split(';')
+                .next()
+                .unwrap_or(&stored)
+                .trim()
+                .to_ascii_lowercase();
+            // SVG and HTML are both script-executable formats. We never serve
+            // them back with their original mime on /raw — direct navigation
+            // would give stored XSS in this origin.
+            //
+            // - SVG: serve the cached rasterized PNG (produced at upload
+            //   time). If the raster is missing (rasterization failed),
+            //   fall back to text/plain so bytes still surface but can't run.
+            // - HTML: downgrade to text/plain (no rasterized form exists).
+            //
+            // Other types (image/png, image/jpeg, application/pdf, audio/*,
+            // video/*, text/plain, text/markdown) don't execute JS so we
+            // serve their real mime so `<img>`, `<audio>`, `<video>`, and
+            // PDF viewers render correctly.
+            let (final_bytes, final_mime): (Vec<u8>, String) = if normalized == "image/svg+xml" {
+                let raster_path = crate::context_storage::attachment_dir(
+                    &state.barnstormer_home,
+                    spec_id,
+                    attachment_id,
+                )
+                .join("rasterized.png");
+                if raster_path.exists() {
+                    match std::fs::read(&raster_path) {
+                        Ok(b) => (b, "image/png".to_string()),
+                        Err(_) => (bytes, "text/plain; charset=utf-8".to_string()),
+                    }
+                } else {
+                    (bytes, "text/plain; charset=utf-8".to_string())
+                }
+            } else if normalized == "text/html" {
+                (bytes, "text/plain; charset=utf-8".to_string())
+            } else {
+                (bytes, stored)
+            };
+            (
+                [
+                    (axum::http::header::CONTENT_TYPE, final_mime.as_str()),
+                    (axum::http::header::X_CONTENT_TYPE_OPTIONS, "nosniff"),
+                ],
+                final_bytes,
+            )
+                .into_response()
+        }
+        Err(_) => (StatusCode::NOT_FOUND, "file not found on disk").into_response(),
+    }
+}
+
+/// Returns the current phase as plain text — used by the client-side
+/// polling fallback when SSE might be disconnected.
+pub async fn phase_check(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (StatusCode::NOT_FOUND, "not_found").into_response();
+        }
+    };
+    let spec_state = handle.read_state().await;
+    let phase_str = match spec_state.phase {
+        SpecPhase::Brainstorming => "brainstorming",
+        SpecPhase::Refining => "refining",
+        SpecPhase::Complete => "complete",
+    };
+    phase_str.into_response()
+}
+
+/// Provider status partial template.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/provider_status.html")]
+pub struct ProviderStatusTemplate {
+    pub default_provider: String,
+    pub default_model: Option<String>,
+    pub providers: Vec<ProviderInfoView>,
+    pub any_available: bool,
+    pub model_warning: Option<String>,
+}
+
+/// Provider info view for template rendering.
+pub struct ProviderInfoView {
+    pub name: String,
+    pub available: bool,
+    pub model: String,
+}
+
+/// GET /web/provider-status - Provider status partial.
+pub async fn provider_status(State(state): State<SharedState>) -> ProviderStatusTemplate {
+    let ps = &state.provider_status;
+    ProviderStatusTemplate {
+        default_provider: ps.default_provider.clone(),
+        default_model: ps.default_model.clone(),
+        providers: ps
+            .providers
+            .iter()
+            .map(|p| ProviderInfoView {
+                name: p.name.clone(),
+                available: p.available,
+                model: p.model.clone(),
+            })
+            .collect(),
+        any_available: ps.any_available,
+        model_warning: ps.model_warning.clone(),
+    }
+}
+
+/// Mission ticker template — compact activity list for the mission strip.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/mission_ticker.html")]
+pub struct MissionTickerTemplate {
+    pub spec_id: String,
+    pub ticker_entries: Vec<TranscriptEntry>,
+    pub pending_question: Option<QuestionData>,
+    /// How many more questions wait behind `pending_question`.
+    pub queued_questions: usize,
+}
+
+/// Agent LED indicators template for the command bar.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/agent_leds.html")]
+pub struct AgentLedsTemplate {
+    pub spec_id: String,
+    pub running: bool,
+    pub started: bool,
+}
+
+/// Agent status partial template. `agents` is empty until the swarm starts.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/agent_status.html")]
+pub struct AgentStatusTemplate {
+    pub spec_id: String,
+    pub running: bool,
+    pub started: bool,
+    /// The swarm paused itself after using up its step budget.
+    pub budget_reached: bool,
+    pub agent_count: usize,
+    pub agents: Vec<AgentToggleView>,
+    /// Roles to pick from before the agents start; empty once they have.
+    pub roster: Vec<RosterOptionView>,
+}
+
+/// One per-agent on/off switch in the agent controls.
+pub struct AgentToggleView {
+    /// Role label used in the toggle URL, e.g. `dot_generator`.
+    pub role: String,
+    pub name: String,
+    pub enabled: bool,
+}
+
+/// One role checkbox in the pre-start roster picker.
+pub struct RosterOptionView {
+    pub role: String,
+    pub name: String,
+    pub selected: bool,
+}
+
+/// Every role as a roster checkbox, checked when the spec's roster (see
+/// [`roster_roles`]) includes it. Empty when the spec does not exist.
+async fn roster_options(state: &SharedState, spec_id: Ulid) -> Vec<RosterOptionView> {
+    let Some(handle) = state.actors.read().await.get(&spec_id).cloned() else {
+        return Vec::new();
+    };
+    let roles = roster_roles(&*handle.read_state().await, &state.agent_roles);
+    AgentRole::ALL
+        .iter()
+        .map(|role| RosterOptionView {
+            role: role.label().to_string(),
+            name: sender_display(role.label(), None).0,
+            selected: roles.contains(role),
+        })
+        .collect()
+}
+
+/// The swarm's roles as toggle switches, in slot order.
+fn agent_toggles(swarm: &SwarmOrchestrator) -> Vec<AgentToggleView> {
+    swarm
+        .agent_roles_enabled()
+        .into_iter()
+        .map(|(role, enabled)| AgentToggleView {
+            role: role.label().to_string(),
+            name: sender_display(role.label(), None).0,
+            enabled,
+        })
+        .collect()
+}
+
+/// GET /web/specs/{id}/ticker - Render the mission strip ticker content.
+pub async fn ticker(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let actors = state.actors.read().await;
+    let handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
+    let spec_state = handle.read_state().await;
+
+    // Show last 10 transcript entries
+    let ticker_entries: Vec<TranscriptEntry> = spec_state
+        .transcript
+        .iter()
+        .rev()
+        .take(10)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .map(to_transcript_entry)
+        .collect();
+
+    let pending_question = spec_state.pending_question().map(question_to_view_data);
+    let queued_questions = spec_state.pending_questions.len().saturating_sub(1);
+
+    MissionTickerTemplate {
+        spec_id: id,
+        ticker_entries,
+        pending_question,
+        queued_questions,
+    }
+    .into_response()
+}
+
+/// GET /web/specs/{id}/agents/leds - Render agent LED indicators.
+pub async fn agent_leds(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let swarms = state.swarms.read().await;
+    match swarms.get(&spec_id) {
+        Some(swarm_handle) => {
+            let swarm = swarm_handle.swarm.lock().await;
+            AgentLedsTemplate {
+                spec_id: id,
+                running: !swarm.is_paused(),
+                started: true,
+            }
+            .into_response()
+        }
+        None => AgentLedsTemplate {
+            spec_id: id,
+            running: false,
+            started: false,
+        }
+        .into_response(),
+    }
+}
+
+/// Optional per-spec provider override for starting agents. Blank values
+/// fall back to the spec's saved override, then the server-wide defaults
+/// (`BARNSTORMER_DEFAULT_PROVIDER` / `BARNSTORMER_DEFAULT_MODEL`).
+#[derive(Debug, Default, Deserialize)]
+pub struct StartAgentsQuery {
+    pub provider: Option<String>,
+    pub model: Option<String>,
+}
+
+impl StartAgentsQuery {
+    fn is_empty(&self) -> bool {
+        non_blank(&self.provider).is_none() && non_blank(&self.model).is_none()
+    }
+}
+
+fn non_blank(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(String::from)
+}
+
+/// Resolve the provider and model a spec's swarm should use: the request's
+/// override when given, then the one saved on the spec (`model_override`),
+/// otherwise the server-wide defaults.
+fn resolve_agent_provider(
+    state: &SharedState,
+    spec_state: &SpecState,
+    overrides: &StartAgentsQuery,
+) -> (String, Option<String>) {
+    let saved = spec_state.model_override.as_ref();
+    let provider = non_blank(&overrides.provider)
+        .or_else(|| saved.map(|m| m.provider.clone()))
+        .unwrap_or_else(|| state.provider_status.default_provider.clone());
+    let model = non_blank(&overrides.model)
+        .or_else(|| saved.map(|m| m.model.clone()))
+        .or_else(|| state.provider_status.default_model.clone());
+    (provider, model)
+}
+
+/// POST /web/specs/{id}/agents/start - Start agents for a spec, optionally
+/// on a specific provider/model (`?provider=openai&model=llama3.1`). An
+/// override is saved on the spec, so later starts use it too.
+pub async fn start_agents(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Query(overrides): Query<StartAgentsQuery>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    // Get actor handle first (read lock), then drop before acquiring swarms write lock
+    let actors = state.actors.read().await;
+    let actor_handle = match actors.get(&spec_id) {
+        Some(h) => h,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
+    // Clone the existing actor handle so the swarm uses the same actor,
+    // ensuring events flow through the server's main event bus.
+    let swarm_actor_handle = actor_handle.clone();
+    let (roles, (provider, model)) = {
+        let spec_state = actor_handle.read_state().await;
+        (
+            roster_roles(&spec_state, &state.agent_roles),
+            resolve_agent_provider(&state, &spec_state, &overrides),
+        )
+    };
+    drop(actors);
+
+    // Atomic check-and-insert: hold write lock to prevent TOCTOU race
+    // where two concurrent requests both pass the existence check and
+    // create duplicate swarms.
+    let mut swarms = state.swarms.write().await;
+    if let Some(swarm_handle) = swarms.get(&spec_id) {
+        let swarm = swarm_handle.swarm.lock().await;
+        return AgentStatusTemplate {
+            spec_id: id,
+            running: !swarm.is_paused(),
+            started: true,
+            budget_reached: swarm.step_budget_reached(),
+            agent_count: swarm.agent_count(),
+            agents: agent_toggles(&swarm),
+            roster: Vec::new(),
+        }
+        .into_response();
+    }
+
+    // Create swarm (sync operation, safe to hold write lock)
+    let swarm = match SwarmOrchestrator::with_roles(
+        spec_id,
+        swarm_actor_handle,
+        &roles,
+        state.barnstormer_home.clone(),
+        Arc::new(crate::attachment_summarizer::ServerSummarizer {
+            home: state.barnstormer_home.clone(),
+            provider: state.provider_status.default_provider.clone(),
+        }),
+        &provider,
+        model.as_deref(),
+    ) {
+        Ok(mut s) => {
+            s.max_steps = state.max_steps_per_session;
+            Arc::new(tokio::sync::Mutex::new(s))
+        }
+        Err(e) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Html(format!(
+                    "<p class=\"error-msg\">Failed to start agents: {}</p>",
+                    e
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    let (agent_count, agents, resolved) = {
+        // This lock is uncontested since the swarm was just created
+        let s = swarm.lock().await;
+        let resolved = barnstormer_core::AgentModel {
+            provider: s.provider.clone(),
+            model: s.model.clone(),
+        };
+        (s.agent_count(), agent_toggles(&s), resolved)
+    };
+
+    // Spawn agent loop task and store the handle for cancellation.
+    // The loop lives in the agent crate; each agent gets its own
+    // broadcast receiver so events are never lost.
+    let task = tokio::spawn(barnstormer_agent::run_loop(Arc::clone(&swarm)));
+
+    // Insert into swarms map while still holding write lock
+    swarms.insert(spec_id, crate::app_state::SwarmHandle { swarm, task });
+    drop(swarms);
+
+    // Save the override with the model the provider resolved to, so
+    // restarts and auto-starts keep this spec on it.
+    if !overrides.is_empty() {
+        let save = Command::SetModelOverride {
+            model: Some(resolved),
+        };
+        let handle = state.actors.read().await.get(&spec_id).cloned();
+        if let Some(handle) = handle
+            && let Err(e) = handle.send_command(save).await
+        {
+            tracing::warn!("failed to save model override for spec {}: {}", spec_id, e);
+        }
+    }
+
+    AgentStatusTemplate {
+        spec_id: id,
+        running: true,
+        started: true,
+        budget_reached: false,
+        agent_count,
+        agents,
+        roster: Vec::new(),
+    }
+    .into_response()
+}
+
+/// Longest focus run accepted by `run_agents_for`, in minutes.
+const MAX_FOCUS_RUN_MINUTES: u64 = 240;
+
+/// Form data for a bounded agent run.
+#[derive(Debug, Deserialize)]
+pub struct RunForForm {
+    pub minutes: u64,
+}
+
+/// POST /web/specs/{id}/agents/run-for - Start (or resume) agents for
+/// `minutes`, after which the swarm pauses itself.
+pub async fn run_agents_for(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+    Form(form): Form<RunForForm>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    if form.minutes == 0 || form.minutes > MAX_FOCUS_RUN_MINUTES {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Run length must be between 1 and {} minutes.</p>",
+                MAX_FOCUS_RUN_MINUTES
+            )),
+        )
+            .into_response();
+    }
+
+    let actor_handle = match state.actors.read().await.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+    try_start_agents(&state, spec_id, &actor_handle).await;
+
+    let swarms = state.swarms.read().await;
+    let Some(swarm_handle) = swarms.get(&spec_id) else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Html("<p class=\"error-msg\">Agents could not be started.</p>".to_string()),
+        )
+            .into_response();
+    };
+    let mut swarm = swarm_handle.swarm.lock().await;
+    swarm
+        .run_for(std::time::Duration::from_secs(form.minutes * 60))
+        .await;
+    AgentStatusTemplate {
+        spec_id: id,
+        running: true,
+        started: true,
+        budget_reached: swarm.step_budget_reached(),
+        agent_count: swarm.agent_count(),
+        agents: agent_toggles(&swarm),
+        roster: Vec::new(),
+    }
+    .into_response()
+}
+
+/// POST /web/specs/{id}/agents/pause - Pause agents.
+pub async fn pause_agents(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let swarms = state.swarms.read().await;
+    match swarms.get(&spec_id) {
+        Some(swarm_handle) => {
+            let swarm = swarm_handle.swarm.lock().await;
+            swarm.pause();
+            AgentStatusTemplate {
+                spec_id: id,
+                running: false,
+                started: true,
+                budget_reached: swarm.step_budget_reached(),
+                agent_count: swarm.agent_count(),
+                agents: agent_toggles(&swarm),
+                roster: Vec::new(),
+            }
+            .into_response()
+        }
+        None => AgentStatusTemplate {
+            spec_id: id,
+            running: false,
+            started: false,
+            budget_reached: false,
+            agent_count: 0,
+            agents: Vec::new(),
+            roster: roster_options(&state, spec_id).await,
+        }
+        .into_response(),
+    }
+}
+
+/// POST /web/specs/{id}/agents/resume - Resume agents.
+pub async fn resume_agents(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let swarms = state.swarms.read().await;
+    match swarms.get(&spec_id) {
+        Some(swarm_handle) => {
+            let swarm = swarm_handle.swarm.lock().await;
+            swarm.resume();
+            AgentStatusTemplate {
+                spec_id: id,
+                running: true,
+                started: true,
+                budget_reached: swarm.step_budget_reached(),
+                agent_count: swarm.agent_count(),
+                agents: agent_toggles(&swarm),
+                roster: Vec::new(),
+            }
+            .into_response()
+        }
+        None => AgentStatusTemplate {
+            spec_id: id,
+            running: false,
+            started: false,
+            budget_reached: false,
+            agent_count: 0,
+            agents: Vec::new(),
+            roster: roster_options(&state, spec_id).await,
+        }
+        .into_response(),
+    }
+}
+
+/// POST /web/specs/{id}/agents/{role}/toggle - Switch one agent on or off
+/// within a running swarm. A disabled agent keeps its context but is skipped
+/// until it is switched back on.
+pub async fn toggle_agent(
+    State(state): State<SharedState>,
+    Path((id, role)): Path<(String, String)>,
+) -> impl IntoResponse {
+    switch_agent(&state, id, &role, |swarm, role| {
+        swarm.toggle_agent(role).is_some()
+    })
+    .await
+}
+
+/// POST /web/specs/{id}/agents/{role}/pause - Pause one agent while the rest
+/// of the swarm keeps working. The swarm-wide pause still overrides it.
+pub async fn pause_agent(
+    State(state): State<SharedState>,
+    Path((id, role)): Path<(String, String)>,
+) -> impl IntoResponse {
+    switch_agent(&state, id, &role, SwarmOrchestrator::pause_agent).await
+}
+
+/// POST /web/specs/{id}/agents/{role}/resume - Resume one paused agent.
+pub async fn resume_agent(
+    State(state): State<SharedState>,
+    Path((id, role)): Path<(String, String)>,
+) -> impl IntoResponse {
+    switch_agent(&state, id, &role, SwarmOrchestrator::resume_agent).await
+}
+
+/// Apply a per-agent switch to the spec's running swarm and re-render the
+/// agent controls. `switch` returns false when no agent has `role`.
+async fn switch_agent(
+    state: &SharedState,
+    id: String,
+    role: &str,
+    switch: impl FnOnce(&mut SwarmOrchestrator, &str) -> bool,
+) -> Response {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let swarms = state.swarms.read().await;
+    let Some(swarm_handle) = swarms.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Agents are not running for this spec.</p>".to_string()),
+        )
+            .into_response();
+    };
+    let mut swarm = swarm_handle.swarm.lock().await;
+    if !switch(&mut swarm, role) {
+        return (
+            StatusCode::NOT_FOUND,
+            Html(format!(
+                "<p class=\"error-msg\">No {} agent in this swarm.</p>",
+                html_escape(role)
+            )),
+        )
+            .into_response();
+    }
+    AgentStatusTemplate {
+        spec_id: id,
+        running: !swarm.is_paused(),
+        started: true,
+        budget_reached: swarm.step_budget_reached(),
+        agent_count: swarm.agent_count(),
+        agents: agent_toggles(&swarm),
+        roster: Vec::new(),
+    }
+    .into_response()
+}
+
+/// POST /web/specs/{id}/agents/roster/{role}/toggle - Add `role` to the
+/// spec's agent roster, or take it out, ahead of the next agent start. The
+/// roster keeps roles in the agent controls' order and at least one role.
+pub async fn toggle_roster_role(
+    State(state): State<SharedState>,
+    Path((id, role)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+    let Some(role) = AgentRole::from_label(&role) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html(format!(
+                "<p class=\"error-msg\">Unknown agent role {}.</p>",
+                html_escape(&role)
+            )),
+        )
+            .into_response();
+    };
+    let handle = match state.actors.read().await.get(&spec_id) {
+        Some(h) => h.clone(),
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+            )
+                .into_response();
+        }
+    };
+
+    let mut roles = roster_roles(&*handle.read_state().await, &state.agent_roles);
+    if roles.contains(&role) {
+        roles.retain(|r| *r != role);
+    } else {
+        roles.push(role);
+    }
+    if roles.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html("<p class=\"error-msg\">Keep at least one agent.</p>".to_string()),
+        )
+            .into_response();
+    }
+    roles.sort_by_key(|r| AgentRole::ALL.iter().position(|a| a == r));
+    let roles = roles.iter().map(|r| r.label().to_string()).collect();
+    if let Err(e) = handle.send_command(Command::SetAgentRoster { roles }).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Html(format!(
+                "<p class=\"error-msg\">Failed to update agents: {}</p>",
+                e
+            )),
+        )
+            .into_response();
+    }
+
+    agent_status(State(state), Path(id)).await.into_response()
+}
+
+/// GET /web/specs/{id}/agents/status - Get current agent status.
+pub async fn agent_status(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let swarms = state.swarms.read().await;
+    match swarms.get(&spec_id) {
+        Some(swarm_handle) => {
+            let swarm = swarm_handle.swarm.lock().await;
+            AgentStatusTemplate {
+                spec_id: id,
+                running: !swarm.is_paused(),
+                started: true,
+                budget_reached: swarm.step_budget_reached(),
+                agent_count: swarm.agent_count(),
+                agents: agent_toggles(&swarm),
+                roster: Vec::new(),
+            }
+            .into_response()
+        }
+        None => AgentStatusTemplate {
+            spec_id: id,
+            running: false,
+            started: false,
+            budget_reached: false,
+            agent_count: 0,
+            agents: Vec::new(),
+            roster: roster_options(&state, spec_id).await,
+        }
+        .into_response(),
+    }
+}
+
+/// Agent cost estimate partial template.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/agent_cost.html")]
+pub struct AgentCostTemplate {
+    /// "≈ $X/hour at current settings", or why no estimate is possible.
+    pub summary: String,
+    pub detail: String,
+}
+
+/// GET /web/specs/{id}/agents/cost-estimate - Rough hourly cost of the
+/// agent swarm. A running swarm is estimated with its enabled agents,
+/// models and context windows; otherwise with what starting agents now
+/// would use. Prices come from `AppState::model_prices`.
+pub async fn agent_cost_estimate(
+    State(state): State<SharedState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let actors = state.actors.read().await;
+    let Some(handle) = actors.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
+    };
+
+    let (provider, agents, windows) = match state.swarms.read().await.get(&spec_id) {
+        Some(swarm_handle) => {
+            let swarm = swarm_handle.swarm.lock().await;
+            let agents: Vec<(AgentRole, String)> = swarm
+                .enabled_slot_roles()
+                .into_iter()
+                .map(|role| {
+                    let model = swarm
+                        .agent_models
+                        .get(role.label())
+                        .cloned()
+                        .unwrap_or_else(|| swarm.model.clone());
+                    (role, model)
+                })
+                .collect();
+            (swarm.provider.clone(), agents, swarm.context_windows)
+        }
+        None => {
+            let (provider, model) = resolve_agent_provider(
+                &state,
+                &*handle.read_state().await,
+                &StartAgentsQuery::default(),
+            );
+            let model = model
+                .or_else(|| {
+                    state
+                        .provider_status
+                        .providers
+                        .iter()
+                        .find(|p| p.name == provider)
+                        .map(|p| p.model.clone())
+                })
+                .unwrap_or_default();
+            let roles = roster_roles(&*handle.read_state().await, &state.agent_roles);
+            let overrides = env_model_overrides(&roles);
+            let agents = roles
+                .into_iter()
+                .map(|role| {
+                    let model = overrides.get(role.label()).unwrap_or(&model).clone();
+                    (role, model)
+                })
+                .collect();
+            (provider, agents, ContextWindows::from_env())
+        }
+    };
+
+    let spec_state = handle.read_state().await;
+    let steps: Vec<_> = agents
+        .iter()
+        .map(|(role, model)| {
+            let context_size = estimated_prompt_chars(&spec_state, *role, windows);
+            estimate_step_cost(&provider, model, context_size, &state.model_prices)
+        })
+        .collect();
+    let cycle = CycleCost::from_steps(&steps);
+    AgentCostTemplate {
+        summary: cycle.to_string(),
+        detail: format!(
+            "{} agent{}, about {} tokens per cycle",
+            cycle.agent_count,
+            if cycle.agent_count == 1 { "" } else { "s" },
+            cycle.tokens_per_cycle
+        ),
+    }
+    .into_response()
+}
+
+/// Token usage partial template: tokens and estimated cost per agent role.
+#[derive(Template, AskamaIntoResponse)]
+#[template(path = "partials/agent_usage.html")]
+pub struct UsageTemplate {
+    pub summary: String,
+    pub rows: Vec<UsageRowView>,
+}
+
+/// One agent role's row in the usage table.
+pub struct UsageRowView {
+    pub name: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Dollar cost, or "unknown" when the model has no price.
+    pub cost: String,
+}
+
+/// GET /web/specs/{id}/usage - Tokens the spec's agents have used so far,
+/// per role, with an estimated cost from `AppState::model_prices`. Models
+/// are priced for the running swarm's provider, else the pinned one.
+pub async fn usage(State(state): State<SharedState>, Path(id): Path<String>) -> impl IntoResponse {
+    let spec_id = match parse_spec_id(&id) {
+        Ok(id) => id,
+        Err(resp) => return *resp,
+    };
+
+    let actors = state.actors.read().await;
+    let Some(handle) = actors.get(&spec_id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Html("<p class=\"error-msg\">Spec not found.</p>".to_string()),
+        )
+            .into_response();
+    };
+    let spec_state = handle.read_state().await;
+    let provider = usage_provider(&state, spec_id, &spec_state).await;
+    let mut total_tokens = 0;
+    let mut total_cost = Some(0.0);
+    let rows: Vec<UsageRowView> = spec_state
+        .token_usage
+        .iter()
+        .map(|(role, stats)| {
+            total_tokens += stats.input_tokens + stats.output_tokens;
+            let cost = state
+                .model_prices
+                .price_for(&provider, &stats.model)
+                .map(|price| price.cost(stats.input_tokens, stats.output_tokens));
+            total_cost = total_cost.zip(cost).map(|(total, cost)| total + cost);
+            UsageRowView {
+                name: sender_display(role, None).0,
+                model: stats.model.clone(),
+                input_tokens: stats.input_tokens,
+                output_tokens: stats.output_tokens,
+                cost: cost.map_or_else(|| "unknown".to_string(), |c| format!("${:.2}", c)),
+            }
+        })
+        .collect();
+
+    let summary = match (rows.is_empty(), total_cost) {
+        (true, _) => "No tokens used yet".to_string(),
+        (false, Some(cost)) => format!("{} tokens used, ≈ ${:.2}", total_tokens, cost),
+        (false, None) => format!("{} tokens used", total_tokens),
+    };
+    UsageTemplate { summary, rows }.into_response()
+}
+
+/// The provider a spec's recorded usage is priced for: the running swarm's,
+/// else the one pinned on the spec, else the server default.
+pub(crate) async fn usage_provider(
+    state: &SharedState,
+    spec_id: Ulid,
+    spec_state: &SpecState,
+) -> String {
+    let running_provider = match state.swarms.read().await.get(&spec_id) {
+        Some(swarm_handle) => Some(swarm_handle.swarm.lock().await.provider.clone()),
+        None => None,
+    };
+    running_provider
+        .or_else(|| spec_state.agent_model.as_ref().map(|m| m.provider.clone()))
+        .unwrap_or_else(|| state.provider_status.default_provider.clone())
+}
+
+/// Parse a boolean form/query flag ("true"/"false", "1"/"0", "on"/"off",
+/// "yes"/"no"). Returns None for anything else so the default applies.
+pub(crate) fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether a newly created spec should start its agents: the per-request
+/// override when given, otherwise the server's `auto_start_agents` policy.
+pub(crate) fn should_auto_start_agents(state: &SharedState, override_flag: Option<bool>) -> bool {
+    override_flag.unwrap_or(state.auto_start_agents)
+}
+
+/// Helper to start the agent swarm for a spec, if a provider is available.
+/// Returns silently if no provider is configured, if the swarm already exists,
+/// or if swarm creation fails. Used by both web and API create_spec handlers.
+pub async fn try_start_agents(
+    state: &SharedState,
+    spec_id: Ulid,
+    actor_handle: &barnstormer_core::SpecActorHandle,
+) {
+    if !state.provider_status.any_available {
+        tracing::info!(
+            "no LLM provider configured, skipping agent start for spec {}",
+            spec_id
+        );
+        return;
+    }
+    let (roles, (provider, model)) = {
+        let spec_state = actor_handle.read_state().await;
+        if spec_state.archived {
+            tracing::info!("spec {} is archived, skipping agent start", spec_id);
+            return;
+        }
+        (
+            roster_roles(&spec_state, &state.agent_roles),
+            resolve_agent_provider(state, &spec_state, &StartAgentsQuery::default()),
+        )
+    };
+
+    // Clone the existing actor handle so the swarm uses the same actor,
+    // ensuring events flow through the server's main event bus.
+    let swarm_actor_handle = actor_handle.clone();
+
+    // Atomic check-and-insert: hold write lock to prevent TOCTOU race
+    // where two concurrent requests both pass the existence check and
+    // create duplicate swarms.
+    let mut swarms = state.swarms.write().await;
+    if swarms.contains_key(&spec_id) {
+        return;
+    }
+
+    // Create swarm (sync operation, safe to hold write lock)
+    let swarm = match SwarmOrchestrator::with_roles(
+        spec_id,
+        swarm_actor_handle,
+        &roles,
+        state.barnstormer_home.clone(),
+        Arc::new(crate::attachment_summarizer::ServerSummarizer {
+            home: state.barnstormer_home.clone(),
+            provider: state.provider_status.default_provider.clone(),
+        }),
+        &provider,
+        model.as_deref(),
+    ) {
+        Ok(mut s) => {
+            s.max_steps = state.max_steps_per_session;
+            Arc::new(tokio::sync::Mutex::new(s))
+        }
+        Err(e) => {
+            tracing::warn!("failed to auto-start agents for spec {}: {}", spec_id, e);
+            return;
+        }
+    };
+
+    let agent_count = {
+        // This lock is uncontested since the swarm was just created
+        let s = swarm.lock().await;
+        s.agent_count()
+    };
+
+    // Spawn background agent loop and store the handle for cancellation.
+    // The loop lives in the agent crate; each agent gets its own
+    // broadcast receiver so events are never lost.
+    let task = tokio::spawn(barnstormer_agent::run_loop(Arc::clone(&swarm)));
+
+    // Insert into swarms map while still holding write lock
+    swarms.insert(spec_id, crate::app_state::SwarmHandle { swarm, task });
+    drop(swarms);
+    tracing::info!("auto-started {} agents for spec {}", agent_count, spec_id);
+}
+
+/// Spawn a background task that subscribes to an actor's broadcast channel
+/// and persists every event to JSONL. This catches ALL events including
+/// those produced by agents, which bypass the inline `persist_events` path.
+///
+/// On broadcast lag (missed events), saves a state snapshot so crash recovery
+/// can restore from the snapshot rather than relying on a gapped JSONL log.
+/// With a `snapshot_interval`, it also snapshots on that period whenever
+/// the spec has moved on since the last one, so recovery replays only the
+/// tail of the log.
+///
+/// Returns the persister's task and stop signal so the caller can store them
+/// for cleanup. Once stopped, it writes the events already in its channel
+/// and exits.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_event_persister(
+    actor: &barnstormer_core::SpecActorHandle,
+    spec_id: Ulid,
+    barnstormer_home: &std::path::Path,
+    swarms: crate::app_state::SwarmMap,
+    retry: crate::app_state::PersisterRetry,
+    health: crate::app_state::PersistenceHealth,
+    metrics: Arc<crate::metrics::Metrics>,
+    snapshot_interval: Option<std::time::Duration>,
+    snapshot_keep: usize,
+) -> crate::app_state::EventPersister {
+    let mut rx = actor.subscribe();
+    let (stop_tx, mut stop_rx) = tokio::sync::oneshot::channel::<()>();
+    let actor_handle = actor.clone();
+    let log_path = barnstormer_home
+        .join("specs")
+        .join(spec_id.to_string())
+        .join("events.jsonl");
+    let snapshot_dir = barnstormer_home
+        .join("specs")
+        .join(spec_id.to_string())
+        .join("snapshots");
+    let index_path = barnstormer_home
+        .join("specs")
+        .join(spec_id.to_string())
+        .join("index.db");
+
+    let task = tokio::spawn(async move {
+        // Retry opening the JSONL log in case the directory or filesystem is
+        // temporarily unavailable. Once the configured attempts are used up
+        // the spec is flagged as degraded, but retries continue in the
+        // background; events wait in the broadcast channel meanwhile.
+        let mut attempt: u32 = 0;
+        let mut log = loop {
+            match JsonlLog::open(&log_path) {
+                Ok(l) => {
+                    if health.is_degraded(spec_id) {
+                        tracing::info!(
+                            "event persister opened log for spec {} after {} failed attempts",
+                            spec_id,
+                            attempt
+                        );
+                        health.clear(spec_id);
+                    }
+                    break l;
+                }
+                Err(e) => {
+                    attempt = attempt.saturating_add(1);
+                    if attempt <= retry.attempts {
+                        tracing::error!(
+                            "event persister failed to open log for spec {} at {} (attempt {}/{}): {}",
+                            spec_id,
+                            log_path.display(),
+                            attempt,
+                            retry.attempts,
+                            e
+                        );
+                    } else {
+                        tracing::debug!(
+                            "event persister still cannot open log for spec {}: {}",
+                            spec_id,
+                            e
+                        );
+                    }
+                    if attempt == retry.attempts {
+                        tracing::error!(
+                            "event persister for spec {} marked degraded after {} attempts; retrying in the background",
+                            spec_id,
+                            retry.attempts,
+                        );
+                        health.mark_degraded(spec_id);
+                    }
+                    tokio::time::sleep(retry.delay).await;
+                }
+            }
+        };
+
+        // The SQLite index (which backs search) is best-effort: without it
+        // events are still logged, search just goes stale until recovery.
+        let index = match SqliteIndex::open(&index_path) {
+            Ok(index) => Some(index),
+            Err(e) => {
+                tracing::warn!("failed to open index for spec {}: {}", spec_id, e);
+                None
+            }
+        };
+        let spec_state = actor_handle.read_state().await.clone();
+        let mut indexed_through = match &index {
+            Some(index) => sync_index(index, &spec_state, spec_id),
+            None => 0,
+        };
+
+        // The first periodic snapshot comes one full interval after startup.
+        let mut snapshot_timer = snapshot_interval.map(|period| {
+            let mut timer = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            timer
+        });
+        let mut snapshotted_through = 0;
+        let mut stopping = false;
+
+        loop {
+            // After a stop request, drain what is already queued, then exit.
+            let received = if stopping {
+                match rx.try_recv() {
+                    Ok(event) => Ok(event),
+                    Err(tokio::sync::broadcast::error::TryRecvError::Lagged(n)) => {
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n))
+                    }
+                    Err(_) => break,
+                }
+            } else {
+                tokio::select! {
+                received = rx.recv() => received,
+                _ = &mut stop_rx => {
+                    stopping = true;
+                    continue;
+                }
+                () = periodic_tick(&mut snapshot_timer) => {
+                    let last_event_id = actor_handle.read_state().await.last_event_id;
+                    if last_event_id > snapshotted_through {
+                        save_recovery_snapshot(&actor_handle, &swarms, spec_id, &snapshot_dir, snapshot_keep)
+                            .await;
+                        snapshotted_through = last_event_id;
+                    }
+                    continue;
+                }
+                }
+            };
+            match received {
+                Ok(event) => {
+                    if event.payload.is_ephemeral() {
+                        continue;
+                    }
+                    if matches!(
+                        event.payload,
+                        barnstormer_core::EventPayload::CardCreated { .. }
+                    ) {
+                        metrics.record_card_created();
+                    }
+                    if let Err(e) = log.append(&event) {
+                        tracing::error!(
+                            "event persister failed to write event for spec {}: {}",
+                            spec_id,
+                            e
+                        );
+                    }
+                    // Events already covered by the initial sync are skipped.
+                    if let Some(index) = &index
+                        && event.event_id > indexed_through
+                    {
+                        match index.apply_event(&event) {
+                            Ok(()) => indexed_through = event.event_id,
+                            Err(e) => tracing::error!(
+                                "failed to index event {} for spec {}: {}",
+                                event.event_id,
+                                spec_id,
+                                e
+                            ),
+                        }
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                    tracing::warn!(
+                        "event persister for spec {} lagged, missed {} events — saving snapshot",
+                        spec_id,
+                        n
+                    );
+                    // Save a snapshot so crash recovery can restore from it
+                    // rather than relying on the gapped JSONL log.
+                    save_recovery_snapshot(
+                        &actor_handle,
+                        &swarms,
+                        spec_id,
+                        &snapshot_dir,
+                        snapshot_keep,
+                    )
+                    .await;
+                    let spec_state = actor_handle.read_state().await.clone();
+                    if let Some(index) = &index {
+                        indexed_through = sync_index(index, &spec_state, spec_id);
+                    }
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                    tracing::debug!(
+                        "event persister for spec {} shutting down (channel closed)",
+                        spec_id
+                    );
+                    break;
+                }
+            }
+        }
+    });
+
+    crate::app_state::EventPersister {
+        task,
+        stop: stop_tx,
+    }
+}
+
+/// Bring a spec's SQLite index up to `state` when it has fallen behind,
+/// returning the event ID the index now covers.
+fn sync_index(index: &SqliteIndex, state: &SpecState, spec_id: Ulid) -> u64 {
+    if index.get_last_event_id().ok().flatten() == Some(state.last_event_id) {
+        return state.last_event_id;
+    }
+    if let Err(e) = index.sync_from_state(state) {
+        tracing::error!("failed to sync index for spec {}: {}", spec_id, e);
+    }
+    state.last_event_id
+}
+
+/// Wait for the next periodic snapshot tick, or forever when periodic
+/// snapshots are off.
+async fn periodic_tick(timer: &mut Option<tokio::time::Interval>) {
+    match timer {
+        Some(timer) => {
+            timer.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Snapshot a spec after its persister lagged or on the periodic interval,
+/// including the swarm's agent contexts when one is running. Failures are
+/// logged, not returned: the persister keeps going either way.
+async fn save_recovery_snapshot(
+    actor: &barnstormer_core::SpecActorHandle,
+    swarms: &crate::app_state::SwarmMap,
+    spec_id: Ulid,
+    snapshot_dir: &std::path::Path,
+    keep: usize,
+) {
+    let state = actor.read_state().await.clone();
+    let snap = crate::app_state::spec_snapshot(swarms, spec_id, state).await;
+    let snapshot_dir = snapshot_dir.to_path_buf();
+    let saved = tokio::task::spawn_blocking(move || {
+        save_snapshot(&snapshot_dir, &snap)?;
+        prune_after_save(&snapshot_dir, keep);
+        Ok::<_, barnstormer_store::SnapshotError>(())
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|result| result.map_err(|e| e.to_string()));
+    if let Err(e) = saved {
+        tracing::error!(
+            "event persister for spec {} failed to save recovery snapshot: {}",
+            spec_id,
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::AppState;
+    use crate::providers::ProviderStatus;
+    use crate::routes::create_router;
+    use axum::body::Body;
+    use http::Request;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    fn test_state() -> SharedState {
+        let dir = tempfile::TempDir::new().unwrap();
+        let provider_status = ProviderStatus {
+            default_provider: "anthropic".to_string(),
+            default_model: None,
+            providers: vec![],
+            any_available: false,
+            model_warning: None,
+        };
+        Arc::new(AppState::new(dir.keep(), provider_status))
+    }
+
+    /// Test multipart boundary used by `mp_description_body`. Tests that
+    /// POST to `/web/specs` use this to construct the request body, since
+    /// the endpoint switched from form-encoded to multipart in Task 18.
+    const MP_BOUNDARY: &str = "----BarnstormerInlineTest";
+
+    /// Build a `Body` containing a multipart/form-data payload with just a
+    /// `description` field. Pair with `MP_CONTENT_TYPE` as the
+    /// `content-type` header. The description is embedded verbatim — do
+    /// not URL-encode it.
+    fn mp_description_body(description: &str) -> Body {
+        let payload = format!(
+            "--{MP_BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"description\"\r\n\r\n\
+             {description}\r\n\
+             --{MP_BOUNDARY}--\r\n"
+        );
+        Body::from(payload)
+    }
+
+    /// Content-type header value matching `mp_description_body`.
+    const MP_CONTENT_TYPE: &str = "multipart/form-data; boundary=----BarnstormerInlineTest";
+
+    #[test]
+    fn index_template_renders() {
+        let tmpl = IndexTemplate {};
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("barnstormer"));
+        assert!(rendered.contains("Select a spec"));
+    }
+
+    #[test]
+    fn spec_list_template_renders_empty() {
+        let tmpl = SpecListTemplate {
+            specs: vec![],
+            cold_specs: vec![],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("No specs yet"));
+    }
+
+    #[tokio::test]
+    async fn persister_flags_degraded_until_the_log_opens() {
+        let home = tempfile::TempDir::new().unwrap();
+        let spec_id = Ulid::new();
+        // A file where the spec directory belongs makes every open fail.
+        let blocker = home.path().join("specs").join(spec_id.to_string());
+        std::fs::create_dir_all(blocker.parent().unwrap()).unwrap();
+        std::fs::write(&blocker, "not a directory").unwrap();
+
+        let actor = barnstormer_core::spawn(spec_id, barnstormer_core::SpecState::new());
+        let health = crate::app_state::PersistenceHealth::default();
+        let retry = crate::app_state::PersisterRetry {
+            attempts: 2,
+            delay: std::time::Duration::from_millis(10),
+        };
+        let persister = spawn_event_persister(
+            &actor,
+            spec_id,
+            home.path(),
+            Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            retry,
+            health.clone(),
+            Arc::new(crate::metrics::Metrics::default()),
+            None,
+            barnstormer_store::DEFAULT_SNAPSHOT_KEEP,
+        );
+
+        let wait_for = |want: bool| {
+            let health = health.clone();
+            async move {
+                for _ in 0..200 {
+                    if health.is_degraded(spec_id) == want {
+                        return true;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+                false
+            }
+        };
+        assert!(wait_for(true).await, "spec should be flagged degraded");
+        assert!(
+            !persister.task.is_finished(),
+            "persister should keep retrying"
+        );
+
+        std::fs::remove_file(&blocker).unwrap();
+        assert!(
+            wait_for(false).await,
+            "a successful open should clear the flag"
+        );
+        assert!(blocker.join("events.jsonl").exists());
+        persister.task.abort();
+    }
+
+    #[tokio::test]
+    async fn periodic_snapshots_let_recovery_replay_only_the_tail() {
+        let home = tempfile::TempDir::new().unwrap();
+        let spec_id = Ulid::new();
+        let spec_dir = home.path().join("specs").join(spec_id.to_string());
+        let actor = barnstormer_core::spawn(spec_id, barnstormer_core::SpecState::new());
+        let persister = spawn_event_persister(
+            &actor,
+            spec_id,
+            home.path(),
+            Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
+            crate::app_state::PersisterRetry::default(),
+            crate::app_state::PersistenceHealth::default(),
+            Arc::new(crate::metrics::Metrics::default()),
+            Some(std::time::Duration::from_millis(20)),
+            barnstormer_store::DEFAULT_SNAPSHOT_KEEP,
+        );
+        let create_card = |title: &str| Command::CreateCard {
+            card_type: "idea".to_string(),
+            title: title.to_string(),
+            body: None,
+            lane: None,
+            created_by: "human".to_string(),
+            source_attachment_id: None,
+        };
+
+        actor
+            .send_command(Command::CreateSpec {
+                title: "Snapshotted".to_string(),
+                one_liner: String::new(),
+                goal: String::new(),
+            })
+            .await
+            .unwrap();
+        actor.send_command(create_card("Before")).await.unwrap();
+        let mid_stream = actor.read_state().await.last_event_id;
+
+        let mut snapshot = None;
+        for _ in 0..200 {
+            snapshot =
+                barnstormer_store::load_latest_snapshot(&spec_dir.join("snapshots")).unwrap();
+            if snapshot
+                .as_ref()
+                .is_some_and(|s| s.last_event_id == mid_stream)
+            {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(snapshot.is_some(), "a periodic snapshot should be saved");
+
+        actor.send_command(create_card("After")).await.unwrap();
+        let live = actor.read_state().await.clone();
+        for _ in 0..200 {
+            let logged = JsonlLog::replay(&spec_dir.join("events.jsonl")).unwrap();
+            if logged.last().map(|e| e.event_id) == Some(live.last_event_id) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        persister.task.abort();
+
+        let (recovered, last_event_id) = barnstormer_store::recover_spec(&spec_dir).unwrap();
+        assert_eq!(last_event_id, live.last_event_id);
+        assert_eq!(recovered.cards, live.cards);
+        assert_eq!(recovered.core, live.core);
+    }
+
+    #[test]
+    fn spec_list_template_renders_with_specs() {
+        let tmpl = SpecListTemplate {
+            specs: vec![SpecSummary {
+                spec_id: "01HTEST".to_string(),
+                title: "My Spec".to_string(),
+                one_liner: "A test spec".to_string(),
+                updated_at: "2025-01-01T00:00:00Z".to_string(),
+                archived: false,
+                persistence_degraded: false,
+            }],
+            cold_specs: vec![],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("My Spec"));
+        assert!(rendered.contains("A test spec"));
+    }
+
+    #[test]
+    fn create_spec_form_template_renders() {
+        let tmpl = CreateSpecFormTemplate {
+            description: String::new(),
+            errors: BTreeMap::new(),
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("description"));
+        assert!(rendered.contains("What do you want to build?"));
+        assert!(rendered.contains("Start Building"));
+    }
+
+    #[test]
+    fn create_spec_form_template_renders_field_errors_and_keeps_input() {
+        let mut errors = BTreeMap::new();
+        errors.insert(
+            "description".to_string(),
+            "Describe what you want to build.".to_string(),
+        );
+        let tmpl = CreateSpecFormTemplate {
+            description: "draft <text>".to_string(),
+            errors,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("field-error"));
+        assert!(rendered.contains("Describe what you want to build."));
+        assert!(rendered.contains("draft "), "submitted text should be kept");
+        assert!(
+            !rendered.contains("<text>"),
+            "submitted text must be escaped"
+        );
+    }
+
+    #[test]
+    fn validate_description_rejects_blank_overlong_and_wordless_input() {
+        assert!(validate_description("   ").contains_key("description"));
+        assert!(
+            validate_description(&"a".repeat(MAX_DESCRIPTION_CHARS + 1))
+                .contains_key("description")
+        );
+        assert!(validate_description("... !!!").contains_key("description"));
+        assert!(validate_description("A recipe sharing app.").is_empty());
+    }
+
+    #[test]
+    fn extract_placeholder_title_first_sentence() {
+        assert_eq!(
+            extract_placeholder_title("Build a todo app. With tags and filters."),
+            "Build a todo app."
+        );
+    }
+
+    #[test]
+    fn extract_placeholder_title_truncates_long_text() {
+        let long = "a".repeat(80);
+        let result = extract_placeholder_title(&long);
+        assert_eq!(result.chars().count(), 63); // 60 chars + "..."
+        assert!(result.ends_with("..."));
+    }
+
+    #[test]
+    fn extract_placeholder_title_short_text() {
+        assert_eq!(extract_placeholder_title("Todo app"), "Todo app");
+    }
+
+    #[test]
+    fn extract_placeholder_title_empty() {
+        assert_eq!(extract_placeholder_title(""), "Untitled Spec");
+        assert_eq!(extract_placeholder_title("   "), "Untitled Spec");
+    }
+
+    #[test]
+    fn extract_placeholder_title_question_mark() {
+        assert_eq!(
+            extract_placeholder_title("Can we build this? I think so."),
+            "Can we build this?"
+        );
+    }
+
+    #[test]
+    fn extract_placeholder_title_exclamation() {
+        assert_eq!(
+            extract_placeholder_title("Build this now! It's urgent."),
+            "Build this now!"
+        );
+    }
+
+    #[test]
+    fn extract_placeholder_title_multibyte_utf8() {
+        // Ensure we don't panic on multi-byte characters near the 60-byte boundary
+        let emoji_text = format!("{}🚀🚀🚀🚀🚀 more text after emojis", "a".repeat(55));
+        let result = extract_placeholder_title(&emoji_text);
+        // Should truncate at a character boundary, not panic
+        assert!(result.chars().count() <= 63); // max 60 + "..."
+        assert!(result.ends_with("..."));
+
+        // CJK characters (3 bytes each) — 40 chars fits within the 60-char limit
+        let cjk_short =
+            "你好世界你好世界你好世界你好世界你好世界你好世界你好世界你好世界你好世界你好世界";
+        let result = extract_placeholder_title(cjk_short);
+        assert_eq!(result, cjk_short); // 40 chars, no truncation needed
+
+        // CJK characters exceeding 60-char limit (65 chars)
+        let cjk_long: String = "你好世界你".repeat(13); // 65 chars
+        let result = extract_placeholder_title(&cjk_long);
+        assert!(result.ends_with("..."));
+        assert!(result.chars().count() <= 63); // max 60 + "..."
+    }
+
+    #[test]
+    fn board_template_renders_empty_lanes() {
+        let tmpl = BoardTemplate {
+            spec_id: "01HTEST".to_string(),
+            lanes: vec![
+                LaneData {
+                    name: "Ideas".to_string(),
+                    cards: vec![],
+                    done_count: 0,
+                },
+                LaneData {
+                    name: "Plan".to_string(),
+                    cards: vec![],
+                    done_count: 0,
+                },
+                LaneData {
+                    name: "Spec".to_string(),
+                    cards: vec![],
+                    done_count: 0,
+                },
+            ],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Ideas"));
+        assert!(rendered.contains("Plan"));
+        assert!(rendered.contains("Spec"));
+    }
+
+    #[test]
+    fn board_template_renders_with_cards() {
+        let tmpl = BoardTemplate {
+            spec_id: "01HTEST".to_string(),
+            lanes: vec![LaneData {
+                name: "Ideas".to_string(),
+                cards: vec![CardData {
+                    card_id: "01HCARD".to_string(),
+                    card_type: "idea".to_string(),
+                    title: "My Idea".to_string(),
+                    body: Some("An interesting idea".to_string()),
+                    body_html: Some("<p>An interesting idea</p>\n".to_string()),
+                    lane: "Ideas".to_string(),
+                    order: 1.0,
+                    created_by: "human".to_string(),
+                    updated_at: "12:00:00".to_string(),
+                    done: false,
+                    attachments: vec![
+                        barnstormer_core::CardAttachment::new("https://example.com/brief", "Brief")
+                            .unwrap(),
+                    ],
+                    due_date: None,
+                    overdue: false,
+                    tags: vec!["backend".to_string()],
+                    review: None,
+                }],
+                done_count: 0,
+            }],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("My Idea"));
+        assert!(rendered.contains("An interesting idea"));
+        assert!(rendered.contains("href=\"https://example.com/brief\""));
+        assert!(rendered.contains("<li class=\"card-tag\">backend</li>"));
+    }
+
+    #[tokio::test]
+    async fn marking_card_done_updates_lane_done_count() {
+        let state = test_state();
+        let spec_id = Ulid::new();
+        let handle = spawn(spec_id, SpecState::new());
+        let mut card_ids = Vec::new();
+        for title in ["First", "Second"] {
+            let events = handle
+                .send_command(Command::CreateCard {
+                    card_type: "task".to_string(),
+                    title: title.to_string(),
+                    body: None,
+                    lane: Some("Plan".to_string()),
+                    created_by: "human".to_string(),
+                    source_attachment_id: None,
+                })
+                .await
+                .unwrap();
+            match &events[0].payload {
+                barnstormer_core::EventPayload::CardCreated { card } => card_ids.push(card.card_id),
+                _ => panic!("wrong event"),
+            }
+        }
+        state.actors.write().await.insert(spec_id, handle);
+        let app = create_router(Arc::clone(&state), None);
+
+        let resp = app
+            .oneshot(
+                Request::post(format!("/web/specs/{}/cards/{}/done", spec_id, card_ids[0]))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("done=true"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let html = body_text(resp).await;
+        assert!(
+            html.contains("1/2 done"),
+            "lane header should count done cards: {html}"
+        );
+        assert!(html.contains("card is-done"));
+
+        let actors = state.actors.read().await;
+        let spec_state = actors[&spec_id].read_state().await;
+        assert!(spec_state.cards[&card_ids[0]].done);
+        assert!(!spec_state.cards[&card_ids[1]].done);
+    }
+
+    #[test]
+    fn card_form_template_renders_create() {
+        let tmpl = CardFormTemplate {
+            spec_id: "01HTEST".to_string(),
+            card_id: None,
+            title: String::new(),
+            card_type: "idea".to_string(),
+            body: String::new(),
+            lane: "Ideas".to_string(),
+            due_date: String::new(),
+            tags: String::new(),
+            templates: Vec::new(),
+            selected_template: None,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Create Card"));
+    }
+
+    #[test]
+    fn card_form_template_renders_edit() {
+        let tmpl = CardFormTemplate {
+            spec_id: "01HTEST".to_string(),
+            card_id: Some("01HCARD".to_string()),
+            title: "Existing Card".to_string(),
+            card_type: "task".to_string(),
+            body: "Some body".to_string(),
+            lane: "Plan".to_string(),
+            due_date: "2026-03-01".to_string(),
+            tags: "backend, ux".to_string(),
+            templates: Vec::new(),
+            selected_template: None,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Edit Card"));
+        assert!(rendered.contains("Existing Card"));
+        assert!(rendered.contains("value=\"backend, ux\""));
+        assert!(rendered.contains(r#"<option value="task" selected>Task</option>"#));
+        assert!(rendered.contains(r#"<option value="open_question" >Open question</option>"#));
+    }
+
+    #[test]
+    fn card_form_keeps_a_card_type_from_before_validation() {
+        let tmpl = CardFormTemplate {
+            spec_id: "01HTEST".to_string(),
+            card_id: Some("01HCARD".to_string()),
+            title: "Old Card".to_string(),
+            card_type: "brainwave".to_string(),
+            body: String::new(),
+            lane: "Ideas".to_string(),
+            due_date: String::new(),
+            tags: String::new(),
+            templates: Vec::new(),
+            selected_template: None,
+        };
+        let options = tmpl.card_type_options();
+        assert_eq!(options.len(), Card::known_types().len() + 1);
+        let last = options.last().unwrap();
+        assert_eq!(
+            (last.value.as_str(), last.label.as_str()),
+            ("brainwave", "Brainwave")
+        );
+        assert!(last.selected);
+        assert_eq!(options.iter().filter(|o| o.selected).count(), 1);
+    }
+
+    #[test]
+    fn document_template_renders() {
+        let tmpl = DocumentTemplate {
+            spec_id: "01HTEST".to_string(),
+            title: "Test Doc".to_string(),
+            title_slug: "test-doc".to_string(),
+            one_liner: "A test document".to_string(),
+            external_links: vec![
+                ExternalLink::new("https://github.com/acme/app/issues/9", "Epic #9").unwrap(),
+            ],
+            goal: "Verify rendering".to_string(),
+            goal_html: "<p>Verify rendering</p>\n".to_string(),
+            description: Some("A detailed description".to_string()),
+            description_html: Some("<p>A detailed description</p>\n".to_string()),
+            constraints: None,
+            constraints_html: None,
+            success_criteria: None,
+            success_criteria_html: None,
+            risks: None,
+            risks_html: None,
+            notes: None,
+            notes_html: None,
+            done_checklist: vec![],
+            lanes: vec![],
+            collapsed_sections: vec![],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Test Doc"));
+        assert!(rendered.contains("A test document"));
+        assert!(rendered.contains("Verify rendering"));
+        assert!(rendered.contains("A detailed description"));
+        assert!(rendered.contains(
+            r#"<a href="https://github.com/acme/app/issues/9" target="_blank" rel="noopener noreferrer">Epic #9</a>"#
+        ));
+        assert!(
+            rendered.contains("Auto-generated from spec data"),
+            "document should contain auto-generated notice"
+        );
+    }
+
+    #[test]
+    fn activity_template_renders_empty() {
+        let tmpl = ActivityTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("activity-transcript-feed"));
+    }
+
+    #[test]
+    fn activity_template_renders_with_entries() {
+        let tmpl = ActivityTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![TranscriptEntry {
+                message_id: String::new(),
+                sender: "agent-1".to_string(),
+                sender_label: "Agent-1".to_string(),
+                initial: "A".to_string(),
+                is_human: false,
+                is_step: false,
+                is_continuation: false,
+                role_class: "agent".to_string(),
+                content: "Started analysis".to_string(),
+                content_html: "<p>Started analysis</p>\n".to_string(),
+                timestamp: "12:34:56".to_string(),
+                repeat_count: 1,
+                agent_model: None,
+                is_highlighted: false,
+            }],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Agent-1"), "should contain sender_label");
+        assert!(rendered.contains("Started analysis"));
+    }
+
+    #[test]
+    fn activity_template_renders_boolean_question() {
+        let tmpl = ActivityTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: Some(QuestionData::Boolean {
+                question_id: "01HQID".to_string(),
+                question: "Proceed with this?".to_string(),
+                default: Some(true),
+            }),
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Proceed with this?"));
+        assert!(rendered.contains("Yes"));
+        assert!(rendered.contains("No"));
+    }
+
+    #[test]
+    fn activity_template_renders_freeform_question() {
+        let tmpl = ActivityTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: Some(QuestionData::Freeform {
+                question_id: "01HQID".to_string(),
+                question: "Describe the feature".to_string(),
+                placeholder: "Type here...".to_string(),
+            }),
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Describe the feature"));
+        assert!(rendered.contains("Type here..."));
+    }
+
+    #[test]
+    fn activity_template_renders_multiple_choice_question() {
+        let tmpl = ActivityTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: Some(QuestionData::MultipleChoice {
+                question_id: "01HQID".to_string(),
+                question: "Pick a color".to_string(),
+                choices: vec!["Red".to_string(), "Blue".to_string(), "Green".to_string()],
+                allow_multi: false,
+            }),
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Pick a color"));
+        assert!(rendered.contains("Red"));
+        assert!(rendered.contains("Blue"));
+        assert!(rendered.contains("Green"));
+    }
+
+    /// The "Something else..." fallback textarea must NOT start as `required` in the
+    /// rendered HTML — otherwise Chromium refuses to submit the Yes/No buttons because
+    /// the hidden textarea fails form validation ("invalid form control is not focusable").
+    /// The onclick handler is responsible for setting `required=true` when the textarea
+    /// becomes visible.
+    #[test]
+    fn chat_transcript_boolean_question_textarea_is_not_required_initially() {
+        let tmpl = ChatTranscriptTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: Some(QuestionData::Boolean {
+                question_id: "01HQID".to_string(),
+                question: "Proceed with this?".to_string(),
+                default: Some(true),
+            }),
+        };
+        let rendered = tmpl.render().unwrap();
+
+        // Locate the "Something else..." fallback textarea by its placeholder.
+        let placeholder = "Describe what you mean...";
+        let idx = rendered
+            .find(placeholder)
+            .expect("rendered HTML should contain the fallback textarea");
+
+        // Find the bounds of the <textarea ...> tag that contains this placeholder.
+        let tag_start = rendered[..idx].rfind("<textarea").expect("<textarea tag");
+        let tag_end_rel = rendered[tag_start..].find('>').expect("closing '>'");
+        let textarea_tag = &rendered[tag_start..tag_start + tag_end_rel + 1];
+
+        assert!(
+            !textarea_tag.contains("required"),
+            "fallback textarea must not have `required` at initial render; got: {textarea_tag}"
+        );
+
+        // The onclick handler must flip required=true when the user clicks "Something else...".
+        assert!(
+            rendered.contains("ta.required=true") || rendered.contains("ta.required = true"),
+            "onclick handler must set textarea.required=true when fallback is revealed"
+        );
+    }
+
+    /// Same guarantee for the MultipleChoice branch of the chat transcript.
+    #[test]
+    fn chat_transcript_multiple_choice_question_textarea_is_not_required_initially() {
+        let tmpl = ChatTranscriptTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: Some(QuestionData::MultipleChoice {
+                question_id: "01HQID".to_string(),
+                question: "Pick a color".to_string(),
+                choices: vec!["Red".to_string(), "Blue".to_string()],
+                allow_multi: false,
+            }),
+        };
+        let rendered = tmpl.render().unwrap();
+
+        let placeholder = "Describe what you mean...";
+        let idx = rendered
+            .find(placeholder)
+            .expect("rendered HTML should contain the fallback textarea");
+
+        let tag_start = rendered[..idx].rfind("<textarea").expect("<textarea tag");
+        let tag_end_rel = rendered[tag_start..].find('>').expect("closing '>'");
+        let textarea_tag = &rendered[tag_start..tag_start + tag_end_rel + 1];
+
+        assert!(
+            !textarea_tag.contains("required"),
+            "fallback textarea must not have `required` at initial render; got: {textarea_tag}"
+        );
+
+        assert!(
+            rendered.contains("ta.required=true") || rendered.contains("ta.required = true"),
+            "onclick handler must set textarea.required=true when fallback is revealed"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_index_returns_html() {
+        let state = test_state();
+        let app = create_router(state, None);
+
+        let resp = app
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), 200);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(html.contains("<!DOCTYPE html>"));
+        assert!(html.contains("barnstormer"));
+    }
+
+    #[tokio::test]
+    async fn get_web_specs_returns_html() {
+        let state = test_state();
+        let app = create_router(state, None);
+
+        let resp = app
+            .oneshot(Request::get("/web/specs").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), 200);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(html.contains("No specs yet"));
+    }
+
+    #[tokio::test]
+    async fn post_web_specs_creates_and_returns_spec_view() {
+        let state = test_state();
+        let app = create_router(Arc::clone(&state), None);
+
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build a test spec for testing"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), 200);
+        // Verify HX-Push-Url header is set for auto-navigation
+        let hx_push = resp.headers().get("hx-push-url");
+        assert!(
+            hx_push.is_some(),
+            "response should include HX-Push-Url header"
+        );
+        let url = hx_push.unwrap().to_str().unwrap();
+        assert!(
+            url.starts_with("/web/specs/"),
+            "HX-Push-Url should point to spec view"
+        );
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(html.contains("Build a test spec for testing"));
+    }
+
+    #[test]
+    fn activity_transcript_template_renders_empty() {
+        let tmpl = ActivityTranscriptTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(
+            rendered.contains("activity-transcript"),
+            "should contain activity-transcript id"
+        );
+        assert!(
+            rendered.contains("activity-transcript-feed"),
+            "should contain activity-transcript-feed div"
+        );
+    }
+
+    #[test]
+    fn activity_transcript_template_renders_with_entries() {
+        let tmpl = ActivityTranscriptTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![TranscriptEntry {
+                message_id: String::new(),
+                sender: "agent-1".to_string(),
+                sender_label: "Agent-1".to_string(),
+                initial: "A".to_string(),
+                is_human: false,
+                is_step: false,
+                is_continuation: false,
+                role_class: "agent".to_string(),
+                content: "Started analysis".to_string(),
+                content_html: "<p>Started analysis</p>\n".to_string(),
+                timestamp: "12:34:56".to_string(),
+                repeat_count: 1,
+                agent_model: None,
+                is_highlighted: false,
+            }],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Agent-1"), "should contain sender_label");
+        assert!(rendered.contains("Started analysis"));
+        assert!(
+            !rendered.contains("chat-input"),
+            "transcript template should not contain chat input"
+        );
+    }
+
+    #[test]
+    fn activity_transcript_shows_agent_model() {
+        let tmpl = ActivityTranscriptTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![TranscriptEntry {
+                message_id: String::new(),
+                sender: "planner-1".to_string(),
+                sender_label: "Planner".to_string(),
+                initial: "P".to_string(),
+                is_human: false,
+                is_step: false,
+                is_continuation: false,
+                role_class: "planner".to_string(),
+                content: "Drafted plan".to_string(),
+                content_html: "<p>Drafted plan</p>\n".to_string(),
+                timestamp: "12:34:56".to_string(),
+                repeat_count: 1,
+                agent_model: Some(barnstormer_core::AgentModel {
+                    provider: "openai".to_string(),
+                    model: "llama3.1:8b".to_string(),
+                }),
+                is_highlighted: false,
+            }],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("title=\"openai\">llama3.1:8b</span>"));
+    }
+
+    #[test]
+    fn transcript_template_renders_with_custom_container_id() {
+        let tmpl = ActivityTranscriptTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
+            transcript: vec![TranscriptEntry {
+                message_id: String::new(),
+                sender: "human".to_string(),
+                sender_label: "You".to_string(),
+                initial: "Y".to_string(),
+                is_human: true,
+                is_step: false,
+                is_continuation: false,
+                role_class: "human".to_string(),
+                content: "Hello chat".to_string(),
+                content_html: "<p>Hello chat</p>\n".to_string(),
+                timestamp: "12:00:00".to_string(),
+                repeat_count: 1,
+                agent_model: None,
+                is_highlighted: false,
+            }],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(
+            rendered.contains("id=\"chat-transcript\""),
+            "should use chat-transcript as container id"
+        );
+        assert!(
+            rendered.contains("id=\"chat-transcript-feed\""),
+            "should use chat-transcript-feed as feed id"
+        );
+        assert!(
+            rendered.contains("hx-target=\"#chat-transcript\""),
+            "should target chat-transcript"
+        );
+        assert!(
+            rendered.contains("container_id=chat-transcript"),
+            "hx-get should include container_id param"
+        );
+        assert!(
+            !rendered.contains("id=\"activity-transcript\""),
+            "should not contain activity-transcript id"
+        );
+        assert!(
+            rendered.contains("Hello chat"),
+            "should render transcript content"
+        );
+    }
+
+    #[test]
+    fn activity_template_does_not_contain_chat_input() {
+        let tmpl = ActivityTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(
+            !rendered.contains("chat-input"),
+            "activity should not contain chat input div"
+        );
+        assert!(
+            !rendered.contains("Send a message"),
+            "activity should not contain chat placeholder"
+        );
+    }
+
+    #[test]
+    fn activity_template_contains_agent_controls() {
+        let tmpl = ActivityTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "activity-transcript".to_string(),
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
+            compact: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(
+            rendered.contains("agent-controls"),
+            "activity should contain agent controls"
+        );
+        assert!(
+            rendered.contains("agent-status"),
+            "activity should contain agent status"
+        );
+        assert!(
+            rendered.contains("Undo"),
+            "activity should contain undo button"
+        );
+    }
+
+    #[test]
+    fn spec_view_template_contains_mission_control_layout() {
+        let tmpl = SpecViewTemplate {
+            spec_id: "01HTEST".to_string(),
+            title: "Test Spec".to_string(),
+            one_liner: "A test spec".to_string(),
+            goal: "Test goal".to_string(),
+            phase: "refining".to_string(),
+            lanes: vec![],
+            branched_from: None,
+        };
+        let rendered = tmpl.render().unwrap();
+        // Command bar with title and subtitle
+        assert!(
+            rendered.contains("command-bar"),
+            "should contain command-bar"
+        );
+        assert!(rendered.contains("Test Spec"), "should contain spec title");
+        assert!(rendered.contains("A test spec"), "should contain one-liner");
+        // Capsule view toggles for document, board, spec
+        assert!(
+            rendered.contains("view-toggles-capsule"),
+            "should contain capsule view toggles"
+        );
+        assert!(
+            rendered.contains("data-view=\"document\""),
+            "should contain document toggle"
+        );
+        assert!(
+            rendered.contains("data-view=\"board\""),
+            "should contain board toggle"
+        );
+        assert!(
+            rendered.contains("data-view=\"spec\""),
+            "should contain spec toggle"
+        );
+        assert!(
+            rendered.contains("view-toggle active"),
+            "document toggle should be active"
+        );
+        // Canvas and chat rail
+        assert!(
+            rendered.contains("id=\"canvas\""),
+            "should contain canvas element"
+        );
+        assert!(
+            rendered.contains("spec-body"),
+            "should contain spec-body row"
+        );
+        assert!(rendered.contains("chat-rail"), "should contain chat-rail");
+        assert!(rendered.contains("chat-panel"), "should load chat panel");
+        // Agent controls in command bar
+        assert!(
+            rendered.contains("agent-controls"),
+            "should contain agent-controls"
+        );
+        // SSE on spec-compositor
+        assert!(
+            rendered.contains("sse-connect"),
+            "should have SSE connection"
+        );
+        // Old layout elements should NOT be present
+        assert!(
+            !rendered.contains("mission-strip"),
+            "should not contain mission-strip"
+        );
+        assert!(
+            !rendered.contains("mission-ticker"),
+            "should not contain mission-ticker"
+        );
+        assert!(
+            !rendered.contains("tab-bar"),
+            "should not contain old tab-bar"
+        );
+        assert!(
+            !rendered.contains("right-rail"),
+            "should not contain right-rail references"
+        );
     }
 
     #[test]
-    fn spec_list_template_renders_empty() {
-        let tmpl = SpecListTemplate { specs: vec![] };
+    fn spec_view_phase_check_polling_is_singleton_per_phase() {
+        // Regression: the polling fallback used a per-render `setInterval(...)`
+        // captured as a closure local. Each #workspace innerHTML swap therefore
+        // installed a fresh timer without clearing the previous one, and the old
+        // timer's stale `currentPhase` would race ahead of a real phase change
+        // and trigger another workspace refetch every 15s — wiping in-progress
+        // chat / Q&A input state. Each phase template MUST guard with
+        // `clearInterval(window.__bsPhase.timerId)` and store the timer id on
+        // the `window.__bsPhase` singleton.
+        for phase in ["brainstorming", "refining", "complete"] {
+            let tmpl = SpecViewTemplate {
+                spec_id: "01HTEST".to_string(),
+                title: "Test".to_string(),
+                one_liner: "t".to_string(),
+                goal: "g".to_string(),
+                phase: phase.to_string(),
+                lanes: vec![],
+                branched_from: None,
+            };
+            let rendered = tmpl.render().unwrap();
+
+            assert!(
+                rendered.contains("window.__bsPhase"),
+                "phase={phase}: rendered HTML should declare window.__bsPhase singleton"
+            );
+            assert!(
+                rendered.contains("clearInterval(window.__bsPhase.timerId)"),
+                "phase={phase}: rendered HTML should clear the prior phase-check timer before installing a new one"
+            );
+            assert!(
+                rendered.contains("window.__bsPhase.timerId = setInterval"),
+                "phase={phase}: rendered HTML should assign the new timer id to the singleton slot"
+            );
+            // The buggy pattern was a bare `var currentPhase = '...'` followed
+            // by an unguarded `setInterval`. Make sure it's gone.
+            assert!(
+                !rendered.contains("var currentPhase ="),
+                "phase={phase}: bare `var currentPhase = ...` is the leaky pattern; it must be replaced by the window.__bsPhase singleton"
+            );
+        }
+    }
+
+    #[test]
+    fn spec_view_brainstorming_wires_context_sse_via_hx_trigger() {
+        // The context rail must rely on the declarative `hx-trigger="sse:..."` pattern
+        // (which htmx-ext-sse 2.2.2 actually supports) rather than a JS listener on
+        // `sse:<event>` DOM events, which that extension does NOT dispatch.
+        let tmpl = SpecViewTemplate {
+            spec_id: "01HTEST".to_string(),
+            title: "Brainstorm Spec".to_string(),
+            one_liner: "A brainstorming spec".to_string(),
+            goal: "Think big".to_string(),
+            phase: "brainstorming".to_string(),
+            lanes: vec![],
+            branched_from: None,
+        };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("No specs yet"));
+        assert!(
+            rendered.contains(r#"hx-trigger="load, sse:context_attached, sse:context_summarized, sse:context_summarize_failed, sse:context_notes_updated, sse:context_removed""#),
+            "context rail must declare SSE triggers"
+        );
+        assert!(
+            !rendered.contains("'sse:' + evt"),
+            "dead JS listener pattern for context events must be removed"
+        );
+    }
+
+    /// Register a spec with one card and a queued DeleteCard proposal.
+    /// Returns (spec_id, card_id, proposal_id).
+    async fn seed_delete_proposal(state: &SharedState) -> (Ulid, Ulid, Ulid) {
+        let spec_id = Ulid::new();
+        let handle = spawn(spec_id, SpecState::new());
+        handle
+            .send_command(Command::CreateSpec {
+                title: "Proposals".to_string(),
+                one_liner: "Review queue".to_string(),
+                goal: "Approve or reject".to_string(),
+            })
+            .await
+            .unwrap();
+        let events = handle
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: "Questionable idea".to_string(),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        let card_id = match &events[0].payload {
+            barnstormer_core::EventPayload::CardCreated { card } => card.card_id,
+            _ => panic!("wrong event"),
+        };
+        let events = handle
+            .send_command(Command::ProposeAction {
+                command: Box::new(Command::DeleteCard {
+                    card_id,
+                    updated_by: "planner-01HTEST".to_string(),
+                }),
+                proposed_by: "planner-01HTEST".to_string(),
+                reason: "card deletion needs human approval".to_string(),
+            })
+            .await
+            .unwrap();
+        let proposal_id = match &events[0].payload {
+            barnstormer_core::EventPayload::ProposalQueued { proposal } => proposal.proposal_id,
+            _ => panic!("wrong event"),
+        };
+        state.actors.write().await.insert(spec_id, handle);
+        (spec_id, card_id, proposal_id)
+    }
+
+    async fn body_text(resp: Response) -> String {
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn proposals_widget_lists_pending_proposal() {
+        let state = test_state();
+        let (spec_id, _, proposal_id) = seed_delete_proposal(&state).await;
+        let app = create_router(state, None);
+
+        let resp = app
+            .oneshot(
+                Request::get(format!("/web/specs/{}/proposals", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let html = body_text(resp).await;
+        assert!(html.contains("Awaiting your approval (1)"));
+        assert!(html.contains("Delete card &#34;Questionable idea&#34;"));
+        assert!(html.contains("Architect"));
+        assert!(html.contains(&format!("/proposals/{}/approve", proposal_id)));
+    }
+
+    #[tokio::test]
+    async fn approving_proposal_via_web_applies_command() {
+        let state = test_state();
+        let (spec_id, card_id, proposal_id) = seed_delete_proposal(&state).await;
+        let app = create_router(Arc::clone(&state), None);
+
+        let resp = app
+            .oneshot(
+                Request::post(format!(
+                    "/web/specs/{}/proposals/{}/approve",
+                    spec_id, proposal_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let html = body_text(resp).await;
+        assert!(html.contains("is-empty"));
+
+        let actors = state.actors.read().await;
+        let spec_state = actors[&spec_id].read_state().await;
+        assert!(spec_state.pending_proposals.is_empty());
+        assert!(!spec_state.cards.contains_key(&card_id));
+    }
+
+    #[tokio::test]
+    async fn rejecting_proposal_via_web_leaves_state_unchanged() {
+        let state = test_state();
+        let (spec_id, card_id, proposal_id) = seed_delete_proposal(&state).await;
+        let app = create_router(Arc::clone(&state), None);
+
+        let resp = app
+            .oneshot(
+                Request::post(format!(
+                    "/web/specs/{}/proposals/{}/reject",
+                    spec_id, proposal_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let actors = state.actors.read().await;
+        let spec_state = actors[&spec_id].read_state().await;
+        assert!(spec_state.pending_proposals.is_empty());
+        assert!(spec_state.cards.contains_key(&card_id));
+    }
+
+    #[tokio::test]
+    async fn resolving_unknown_proposal_returns_error_and_widget() {
+        let state = test_state();
+        let (spec_id, _, _) = seed_delete_proposal(&state).await;
+        let app = create_router(state, None);
+
+        let resp = app
+            .oneshot(
+                Request::post(format!(
+                    "/web/specs/{}/proposals/{}/approve",
+                    spec_id,
+                    Ulid::new()
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let html = body_text(resp).await;
+        assert!(html.contains("proposal not found"));
+        assert!(html.contains("Awaiting your approval (1)"));
     }
 
     #[test]
-    fn spec_list_template_renders_with_specs() {
-        let tmpl = SpecListTemplate {
-            specs: vec![SpecSummary {
-                spec_id: "01HTEST".to_string(),
-                title: "My Spec".to_string(),
-                one_liner: "A test spec".to_string(),
-                updated_at: "2025-01-01T00:00:00Z".to_string(),
+    fn spec_view_shows_branch_lineage_when_forked() {
+        let tmpl = SpecViewTemplate {
+            spec_id: "01HCHILD".to_string(),
+            title: "Child".to_string(),
+            one_liner: "c".to_string(),
+            goal: "g".to_string(),
+            phase: "refining".to_string(),
+            lanes: vec![],
+            branched_from: Some(LineageData {
+                spec_id: "01HPARENT".to_string(),
+                title: "Parent Spec".to_string(),
+            }),
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("branched from"));
+        assert!(rendered.contains("/web/specs/01HPARENT"));
+        assert!(rendered.contains("Parent Spec"));
+    }
+
+    #[test]
+    fn context_panel_shows_in_context_badge_when_summary_present() {
+        // An attachment that has a summary should render the "in context" pill —
+        // signalling to the user that it's been included in the agent's prompt.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "notes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "1.2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("a short summary".to_string()),
+                summary_html: Some("<p>a short summary</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
             }],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("My Spec"));
-        assert!(rendered.contains("A test spec"));
+        assert!(
+            rendered.contains("in context"),
+            "summary present should show 'in context' pill"
+        );
+        // The hover hint must use the shared .tooltip component rather than a
+        // native `title=` attribute, so it renders as a styled popover.
+        assert!(
+            rendered.contains(r#"class="has-tooltip""#),
+            "in-context pill must be wrapped in .has-tooltip"
+        );
+        assert!(
+            rendered.contains(r#"class="tooltip context-pill-tooltip""#),
+            "in-context hint must use the .tooltip component"
+        );
+        assert!(
+            rendered.contains("Included in agent context"),
+            "tooltip text must be present"
+        );
+    }
+
+    #[test]
+    fn context_panel_shows_summarizing_badge_when_summary_pending() {
+        // An attachment without a summary yet should render the "summarizing…" pill.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "pending.txt".to_string(),
+                extension: "txt".to_string(),
+                size_display: "500 B".to_string(),
+                added_display: "12:35".to_string(),
+                summary: None,
+                summary_html: None,
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
+            }],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(
+            rendered.contains("summarizing"),
+            "summary pending should show 'summarizing' pill"
+        );
+        assert!(
+            rendered.contains("Summary in progress"),
+            "tooltip text must be present on the summarizing pill"
+        );
     }
 
     #[test]
-    fn create_spec_form_template_renders() {
-        let tmpl = CreateSpecFormTemplate {};
+    fn context_panel_uses_details_summary_for_collapsible_card() {
+        // Each attachment card must use native <details>/<summary> so users can
+        // click the header to expand/collapse without any JS.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "notes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "1.2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("a short summary".to_string()),
+                summary_html: Some("<p>a short summary</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
+            }],
+        };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("description"));
-        assert!(rendered.contains("What do you want to build?"));
-        assert!(rendered.contains("Start Building"));
-    }
-
-    #[test]
-    fn extract_placeholder_title_first_sentence() {
-        assert_eq!(
-            extract_placeholder_title("Build a todo app. With tags and filters."),
-            "Build a todo app."
+        assert!(rendered.contains("<details"), "card must use <details>");
+        assert!(rendered.contains("<summary"), "card must use <summary>");
+        // <summary> accepts only phrasing content — a nested <div> is invalid HTML5
+        // and causes browsers to eject the div from the summary context, breaking
+        // the native toggle. The header must use <span> with display:flex instead.
+        let summary_start = rendered.find("<summary").expect("summary exists");
+        let summary_end = rendered[summary_start..]
+            .find("</summary>")
+            .map(|e| summary_start + e)
+            .expect("summary closes");
+        let summary_block = &rendered[summary_start..summary_end];
+        assert!(
+            !summary_block.contains("<div"),
+            "<summary> must not contain <div> (block content breaks the toggle); got:\n{summary_block}"
         );
     }
 
     #[test]
-    fn extract_placeholder_title_truncates_long_text() {
-        let long = "a".repeat(80);
-        let result = extract_placeholder_title(&long);
-        assert_eq!(result.chars().count(), 63); // 60 chars + "..."
-        assert!(result.ends_with("..."));
-    }
-
-    #[test]
-    fn extract_placeholder_title_short_text() {
-        assert_eq!(extract_placeholder_title("Todo app"), "Todo app");
-    }
-
-    #[test]
-    fn extract_placeholder_title_empty() {
-        assert_eq!(extract_placeholder_title(""), "Untitled Spec");
-        assert_eq!(extract_placeholder_title("   "), "Untitled Spec");
-    }
-
-    #[test]
-    fn extract_placeholder_title_question_mark() {
-        assert_eq!(
-            extract_placeholder_title("Can we build this? I think so."),
-            "Can we build this?"
+    fn context_panel_expanded_renders_markdown_summary_as_html() {
+        // When the card is open, the summary should be rendered as HTML
+        // from the `summary_html` field (bold, etc.), not as plain text.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "notes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "1.2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("**bold**".to_string()),
+                summary_html: Some(render_markdown("**bold**")),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
+            }],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(
+            rendered.contains("<strong>bold</strong>"),
+            "expanded card should contain rendered HTML, got: {}",
+            rendered
         );
     }
 
     #[test]
-    fn extract_placeholder_title_exclamation() {
-        assert_eq!(
-            extract_placeholder_title("Build this now! It's urgent."),
-            "Build this now!"
+    fn context_panel_delete_button_is_outside_summary() {
+        // The delete button should live in the expanded section so clicking it
+        // doesn't toggle the card. Concretely: the `hx-delete` attribute must
+        // appear AFTER the closing `</summary>` tag.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "notes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "1.2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("a summary".to_string()),
+                summary_html: Some("<p>a summary</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
+            }],
+        };
+        let rendered = tmpl.render().unwrap();
+        let delete_idx = rendered
+            .find("hx-delete")
+            .expect("should have delete button");
+        let summary_close_idx = rendered
+            .find("</summary>")
+            .expect("should have </summary> tag");
+        assert!(
+            delete_idx > summary_close_idx,
+            "delete button (hx-delete) must appear after </summary>, got delete at {} vs </summary> at {}",
+            delete_idx,
+            summary_close_idx,
         );
     }
 
     #[test]
-    fn extract_placeholder_title_multibyte_utf8() {
-        // Ensure we don't panic on multi-byte characters near the 60-byte boundary
-        let emoji_text = format!("{}🚀🚀🚀🚀🚀 more text after emojis", "a".repeat(55));
-        let result = extract_placeholder_title(&emoji_text);
-        // Should truncate at a character boundary, not panic
-        assert!(result.chars().count() <= 63); // max 60 + "..."
-        assert!(result.ends_with("..."));
-
-        // CJK characters (3 bytes each) — 40 chars fits within the 60-char limit
-        let cjk_short =
-            "你好世界你好世界你好世界你好世界你好世界你好世界你好世界你好世界你好世界你好世界";
-        let result = extract_placeholder_title(cjk_short);
-        assert_eq!(result, cjk_short); // 40 chars, no truncation needed
-
-        // CJK characters exceeding 60-char limit (65 chars)
-        let cjk_long: String = "你好世界你".repeat(13); // 65 chars
-        let result = extract_placeholder_title(&cjk_long);
-        assert!(result.ends_with("..."));
-        assert!(result.chars().count() <= 63); // max 60 + "..."
+    fn context_panel_shows_card_count_when_cards_sourced() {
+        // When the Manager has synthesized cards from an attachment, the
+        // collapsed header should show a small count so the user can see
+        // at a glance how much of a file has been processed.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "vibes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("design vibes".to_string()),
+                summary_html: Some("<p>design vibes</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 3,
+            }],
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(
+            rendered.contains("3 cards"),
+            "panel should show card count when > 0, got: {rendered}"
+        );
     }
 
     #[test]
-    fn board_template_renders_empty_lanes() {
-        let tmpl = BoardTemplate {
+    fn context_panel_pluralizes_single_card_count() {
+        // Singular form when exactly one card references the attachment.
+        let tmpl = ContextPanelTemplate {
             spec_id: "01HTEST".to_string(),
-            lanes: vec![
-                LaneData {
-                    name: "Ideas".to_string(),
-                    cards: vec![],
-                },
-                LaneData {
-                    name: "Plan".to_string(),
-                    cards: vec![],
-                },
-                LaneData {
-                    name: "Spec".to_string(),
-                    cards: vec![],
-                },
-            ],
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "vibes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("design vibes".to_string()),
+                summary_html: Some("<p>design vibes</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 1,
+            }],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Ideas"));
-        assert!(rendered.contains("Plan"));
-        assert!(rendered.contains("Spec"));
+        assert!(
+            rendered.contains("1 card") && !rendered.contains("1 cards"),
+            "panel should use singular 'card' for count of 1, got: {rendered}"
+        );
     }
 
     #[test]
-    fn board_template_renders_with_cards() {
-        let tmpl = BoardTemplate {
+    fn context_panel_shows_not_yet_synthesized_when_summarized_but_no_cards() {
+        // An attachment that has a summary but hasn't been ingested yet should
+        // display a subtle hint so the user knows the Manager is expected to
+        // process it.
+        let tmpl = ContextPanelTemplate {
             spec_id: "01HTEST".to_string(),
-            lanes: vec![LaneData {
-                name: "Ideas".to_string(),
-                cards: vec![CardData {
-                    card_id: "01HCARD".to_string(),
-                    card_type: "idea".to_string(),
-                    title: "My Idea".to_string(),
-                    body: Some("An interesting idea".to_string()),
-                    body_html: Some("<p>An interesting idea</p>\n".to_string()),
-                    lane: "Ideas".to_string(),
-                    order: 1.0,
-                    created_by: "human".to_string(),
-                    updated_at: "12:00:00".to_string(),
-                }],
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "vibes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("design vibes".to_string()),
+                summary_html: Some("<p>design vibes</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
             }],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("My Idea"));
-        assert!(rendered.contains("An interesting idea"));
+        assert!(
+            rendered.contains("Not yet synthesized"),
+            "panel should show 'Not yet synthesized' when summary is Some and card_count is 0"
+        );
     }
 
     #[test]
-    fn card_form_template_renders_create() {
-        let tmpl = CardFormTemplate {
+    fn context_panel_hides_not_yet_synthesized_when_summary_pending() {
+        // Before a summary exists, the hint would be confusing — hide it.
+        let tmpl = ContextPanelTemplate {
             spec_id: "01HTEST".to_string(),
-            card_id: None,
-            title: String::new(),
-            card_type: "idea".to_string(),
-            body: String::new(),
-            lane: "Ideas".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "pending.txt".to_string(),
+                extension: "txt".to_string(),
+                size_display: "500 B".to_string(),
+                added_display: "12:35".to_string(),
+                summary: None,
+                summary_html: None,
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
+            }],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Create Card"));
+        assert!(
+            !rendered.contains("Not yet synthesized"),
+            "panel should not show the synthesis hint before a summary exists"
+        );
     }
 
     #[test]
-    fn card_form_template_renders_edit() {
-        let tmpl = CardFormTemplate {
+    fn context_panel_hides_not_yet_synthesized_when_cards_present() {
+        // Once the Manager has sourced cards from the attachment, the hint
+        // should disappear.
+        let tmpl = ContextPanelTemplate {
             spec_id: "01HTEST".to_string(),
-            card_id: Some("01HCARD".to_string()),
-            title: "Existing Card".to_string(),
-            card_type: "task".to_string(),
-            body: "Some body".to_string(),
-            lane: "Plan".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "vibes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("design vibes".to_string()),
+                summary_html: Some("<p>design vibes</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 2,
+            }],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Edit Card"));
-        assert!(rendered.contains("Existing Card"));
+        assert!(
+            !rendered.contains("Not yet synthesized"),
+            "panel should not show the synthesis hint once cards reference the attachment"
+        );
     }
 
     #[test]
-    fn document_template_renders() {
-        let tmpl = DocumentTemplate {
+    fn context_panel_hides_card_count_when_zero() {
+        // Don't clutter the collapsed header with "0 cards" — just omit the
+        // pill entirely when no cards reference the attachment.
+        let tmpl = ContextPanelTemplate {
             spec_id: "01HTEST".to_string(),
-            title: "Test Doc".to_string(),
-            title_slug: "test-doc".to_string(),
-            one_liner: "A test document".to_string(),
-            goal: "Verify rendering".to_string(),
-            goal_html: "<p>Verify rendering</p>\n".to_string(),
-            description: Some("A detailed description".to_string()),
-            description_html: Some("<p>A detailed description</p>\n".to_string()),
-            constraints: None,
-            constraints_html: None,
-            success_criteria: None,
-            success_criteria_html: None,
-            risks: None,
-            risks_html: None,
-            notes: None,
-            notes_html: None,
-            lanes: vec![],
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "vibes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "2 KB".to_string(),
+                added_display: "12:34".to_string(),
+                summary: Some("design vibes".to_string()),
+                summary_html: Some("<p>design vibes</p>\n".to_string()),
+                user_notes: None,
+                summary_error: None,
+                kind: AttachmentKind::Text,
+                mime_type: "text/plain".to_string(),
+                raw_url: "/raw".to_string(),
+                card_count: 0,
+            }],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Test Doc"));
-        assert!(rendered.contains("A test document"));
-        assert!(rendered.contains("Verify rendering"));
-        assert!(rendered.contains("A detailed description"));
         assert!(
-            rendered.contains("Auto-generated from spec data"),
-            "document should contain auto-generated notice"
+            !rendered.contains("0 cards") && !rendered.contains("0 card"),
+            "panel should omit the count pill entirely when card_count is 0"
         );
     }
 
     #[test]
-    fn activity_template_renders_empty() {
-        let tmpl = ActivityTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![],
-            pending_question: None,
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("activity-transcript-feed"));
+    fn attachment_kind_classifies_common_mimes() {
+        assert_eq!(
+            AttachmentKind::from_mime("image/png"),
+            AttachmentKind::ImageRaster
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("image/JPEG"),
+            AttachmentKind::ImageRaster
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("image/svg+xml"),
+            AttachmentKind::ImageSvg
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("application/pdf"),
+            AttachmentKind::Pdf
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("audio/mpeg"),
+            AttachmentKind::Audio
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("video/mp4"),
+            AttachmentKind::Video
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("text/markdown; charset=utf-8"),
+            AttachmentKind::Text
+        );
+        // Unknown / weird types fall through to Text.
+        assert_eq!(
+            AttachmentKind::from_mime("application/octet-stream"),
+            AttachmentKind::Text
+        );
     }
 
     #[test]
-    fn activity_template_renders_with_entries() {
-        let tmpl = ActivityTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![TranscriptEntry {
-                sender: "agent-1".to_string(),
-                sender_label: "Agent-1".to_string(),
-                initial: "A".to_string(),
-                is_human: false,
-                is_step: false,
-                is_continuation: false,
-                role_class: "agent".to_string(),
-                content: "Started analysis".to_string(),
-                content_html: "<p>Started analysis</p>\n".to_string(),
-                timestamp: "12:34:56".to_string(),
-                repeat_count: 1,
+    fn context_panel_renders_image_preview_for_raster_attachment() {
+        // Raster image attachments should render an inline `<img>` preview using
+        // the raw URL — that's what makes the panel multimodal-aware.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HSPEC".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "diagram.png".to_string(),
+                extension: "png".to_string(),
+                size_display: "10 KB".to_string(),
+                added_display: "12:00".to_string(),
+                summary: Some("a diagram".to_string()),
+                summary_html: Some("<p>a diagram</p>\n".to_string()),
+                summary_error: None,
+                user_notes: None,
+                card_count: 0,
+                kind: AttachmentKind::ImageRaster,
+                mime_type: "image/png".to_string(),
+                raw_url: "/web/specs/01HSPEC/context/01HATT/raw".to_string(),
             }],
-            pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Agent-1"), "should contain sender_label");
-        assert!(rendered.contains("Started analysis"));
+        assert!(
+            rendered.contains(r#"class="context-preview-image""#),
+            "raster image should produce an <img> preview"
+        );
+        assert!(
+            rendered.contains(r#"src="/web/specs/01HSPEC/context/01HATT/raw""#),
+            "preview <img> must point at the /raw URL"
+        );
+        // Per-kind badge: image variant, not the generic note variant.
+        assert!(
+            rendered.contains("badge-image"),
+            "image attachments should use badge-image"
+        );
     }
 
     #[test]
-    fn activity_template_renders_boolean_question() {
-        let tmpl = ActivityTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![],
-            pending_question: Some(QuestionData::Boolean {
-                question_id: "01HQID".to_string(),
-                question: "Proceed with this?".to_string(),
-                default: Some(true),
-            }),
+    fn context_panel_renders_pdf_icon_for_pdf_attachment() {
+        // PDF previews are an icon + filename rather than an inline render —
+        // browser PDF viewers are heavy and rarely useful at thumbnail size.
+        // Guards against a regression that lets PDFs fall through to <img src>.
+        let att_id = Ulid::new();
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HSPEC".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: att_id.to_string(),
+                filename: "spec-draft.pdf".to_string(),
+                extension: "pdf".to_string(),
+                size_display: "12.0 KB".to_string(),
+                added_display: "12:00".to_string(),
+                summary: Some("a brief draft".to_string()),
+                summary_html: Some("a brief draft".to_string()),
+                summary_error: None,
+                user_notes: None,
+                card_count: 0,
+                kind: AttachmentKind::Pdf,
+                mime_type: "application/pdf".to_string(),
+                raw_url: format!("/web/specs/01HSPEC/context/{}/raw", att_id),
+            }],
         };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Proceed with this?"));
-        assert!(rendered.contains("Yes"));
-        assert!(rendered.contains("No"));
-    }
+        let rendered = tmpl.render().expect("template renders");
+
+        // PDF icon block present.
+        assert!(
+            rendered.contains("context-preview-pdf-icon"),
+            "expected PDF icon class in rendered HTML"
+        );
+        assert!(
+            rendered.contains("spec-draft.pdf"),
+            "expected filename in PDF icon block"
+        );
+
+        // No <img> tag — PDFs must not fall through to raster preview path.
+        assert!(
+            !rendered.contains("<img"),
+            "PDF preview must not render an <img> tag (would indicate kind classification regression)"
+        );
 
-    #[test]
-    fn activity_template_renders_freeform_question() {
-        let tmpl = ActivityTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![],
-            pending_question: Some(QuestionData::Freeform {
-                question_id: "01HQID".to_string(),
-                question: "Describe the feature".to_string(),
-                placeholder: "Type here...".to_string(),
-            }),
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Describe the feature"));
-        assert!(rendered.contains("Type here..."));
+        // The doc badge is used for PDFs.
+        assert!(
+            rendered.contains("badge-doc"),
+            "expected badge-doc on PDF attachment header"
+        );
     }
 
     #[test]
-    fn activity_template_renders_multiple_choice_question() {
-        let tmpl = ActivityTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![],
-            pending_question: Some(QuestionData::MultipleChoice {
-                question_id: "01HQID".to_string(),
-                question: "Pick a color".to_string(),
-                choices: vec!["Red".to_string(), "Blue".to_string(), "Green".to_string()],
-                allow_multi: false,
-            }),
+    fn context_panel_renders_audio_preview_for_audio_attachment() {
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HSPEC".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "voice.mp3".to_string(),
+                extension: "mp3".to_string(),
+                size_display: "100 KB".to_string(),
+                added_display: "12:00".to_string(),
+                summary: Some("a voice memo".to_string()),
+                summary_html: Some("<p>a voice memo</p>\n".to_string()),
+                summary_error: None,
+                user_notes: None,
+                card_count: 0,
+                kind: AttachmentKind::Audio,
+                mime_type: "audio/mpeg".to_string(),
+                raw_url: "/web/specs/01HSPEC/context/01HATT/raw".to_string(),
+            }],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Pick a color"));
-        assert!(rendered.contains("Red"));
-        assert!(rendered.contains("Blue"));
-        assert!(rendered.contains("Green"));
+        assert!(
+            rendered.contains("<audio") && rendered.contains("controls"),
+            "audio attachment should produce an <audio controls> element"
+        );
+        assert!(
+            rendered.contains(r#"type="audio/mpeg""#),
+            "audio <source> must declare the original mime type"
+        );
+        assert!(rendered.contains("badge-audio"));
     }
 
-    /// The "Something else..." fallback textarea must NOT start as `required` in the
-    /// rendered HTML — otherwise Chromium refuses to submit the Yes/No buttons because
-    /// the hidden textarea fails form validation ("invalid form control is not focusable").
-    /// The onclick handler is responsible for setting `required=true` when the textarea
-    /// becomes visible.
     #[test]
-    fn chat_transcript_boolean_question_textarea_is_not_required_initially() {
-        let tmpl = ChatTranscriptTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
-            transcript: vec![],
-            pending_question: Some(QuestionData::Boolean {
-                question_id: "01HQID".to_string(),
-                question: "Proceed with this?".to_string(),
-                default: Some(true),
-            }),
+    fn context_panel_resummarize_button_targets_correct_endpoint() {
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HSPEC".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "notes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "1 KB".to_string(),
+                added_display: "12:00".to_string(),
+                summary: Some("a summary".to_string()),
+                summary_html: Some("<p>a summary</p>\n".to_string()),
+                summary_error: None,
+                user_notes: None,
+                card_count: 0,
+                kind: AttachmentKind::Text,
+                mime_type: "text/markdown".to_string(),
+                raw_url: "/raw".to_string(),
+            }],
         };
         let rendered = tmpl.render().unwrap();
-
-        // Locate the "Something else..." fallback textarea by its placeholder.
-        let placeholder = "Describe what you mean...";
-        let idx = rendered
-            .find(placeholder)
-            .expect("rendered HTML should contain the fallback textarea");
-
-        // Find the bounds of the <textarea ...> tag that contains this placeholder.
-        let tag_start = rendered[..idx].rfind("<textarea").expect("<textarea tag");
-        let tag_end_rel = rendered[tag_start..].find('>').expect("closing '>'");
-        let textarea_tag = &rendered[tag_start..tag_start + tag_end_rel + 1];
-
         assert!(
-            !textarea_tag.contains("required"),
-            "fallback textarea must not have `required` at initial render; got: {textarea_tag}"
+            rendered.contains(r#"hx-post="/web/specs/01HSPEC/context/01HATT/resummarize""#),
+            "Resummarize button must POST to the resummarize endpoint"
         );
-
-        // The onclick handler must flip required=true when the user clicks "Something else...".
         assert!(
-            rendered.contains("ta.required=true") || rendered.contains("ta.required = true"),
-            "onclick handler must set textarea.required=true when fallback is revealed"
+            rendered.contains(">Resummarize<"),
+            "button label must be 'Resummarize'"
         );
     }
 
-    /// Same guarantee for the MultipleChoice branch of the chat transcript.
     #[test]
-    fn chat_transcript_multiple_choice_question_textarea_is_not_required_initially() {
-        let tmpl = ChatTranscriptTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
-            transcript: vec![],
-            pending_question: Some(QuestionData::MultipleChoice {
-                question_id: "01HQID".to_string(),
-                question: "Pick a color".to_string(),
-                choices: vec!["Red".to_string(), "Blue".to_string()],
-                allow_multi: false,
-            }),
+    fn context_panel_failure_state_no_summary_shows_card_error() {
+        // None summary + Some error: hard-failure state, no prior summary to
+        // fall back on. The error text should be the dominant content of the
+        // card body so the user knows to act.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HSPEC".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "broken.bin".to_string(),
+                extension: "bin".to_string(),
+                size_display: "1 KB".to_string(),
+                added_display: "12:00".to_string(),
+                summary: None,
+                summary_html: None,
+                summary_error: Some("provider rejected payload".to_string()),
+                user_notes: None,
+                card_count: 0,
+                kind: AttachmentKind::Text,
+                mime_type: "application/octet-stream".to_string(),
+                raw_url: "/raw".to_string(),
+            }],
         };
         let rendered = tmpl.render().unwrap();
-
-        let placeholder = "Describe what you mean...";
-        let idx = rendered
-            .find(placeholder)
-            .expect("rendered HTML should contain the fallback textarea");
-
-        let tag_start = rendered[..idx].rfind("<textarea").expect("<textarea tag");
-        let tag_end_rel = rendered[tag_start..].find('>').expect("closing '>'");
-        let textarea_tag = &rendered[tag_start..tag_start + tag_end_rel + 1];
-
         assert!(
-            !textarea_tag.contains("required"),
-            "fallback textarea must not have `required` at initial render; got: {textarea_tag}"
+            rendered.contains(r#"class="card-error""#),
+            "hard failure must render the .card-error block"
         );
-
         assert!(
-            rendered.contains("ta.required=true") || rendered.contains("ta.required = true"),
-            "onclick handler must set textarea.required=true when fallback is revealed"
+            rendered.contains("provider rejected payload"),
+            "error text must be visible to the user"
+        );
+        assert!(
+            rendered.contains("summary failed"),
+            "header pill should read 'summary failed'"
+        );
+        assert!(
+            !rendered.contains("Summarizing&hellip;"),
+            "must not show the pending spinner when there's an error"
         );
     }
 
-    #[tokio::test]
-    async fn get_index_returns_html() {
-        let state = test_state();
-        let app = create_router(state, None);
-
-        let resp = app
-            .oneshot(Request::get("/").body(Body::empty()).unwrap())
-            .await
-            .unwrap();
-
-        assert_eq!(resp.status(), 200);
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(html.contains("<!DOCTYPE html>"));
-        assert!(html.contains("barnstormer"));
-    }
-
-    #[tokio::test]
-    async fn get_web_specs_returns_html() {
-        let state = test_state();
-        let app = create_router(state, None);
-
-        let resp = app
-            .oneshot(Request::get("/web/specs").body(Body::empty()).unwrap())
-            .await
-            .unwrap();
-
-        assert_eq!(resp.status(), 200);
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(html.contains("No specs yet"));
-    }
-
-    #[tokio::test]
-    async fn post_web_specs_creates_and_returns_spec_view() {
-        let state = test_state();
-        let app = create_router(Arc::clone(&state), None);
-
-        let resp = app
-            .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build a test spec for testing"))
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(resp.status(), 200);
-        // Verify HX-Push-Url header is set for auto-navigation
-        let hx_push = resp.headers().get("hx-push-url");
+    #[test]
+    fn context_panel_stale_state_shows_summary_and_error_footnote() {
+        // Some summary + Some error: stale-with-error. Both the prior summary
+        // and the failure footnote must render so the user understands what's
+        // currently in context.
+        let tmpl = ContextPanelTemplate {
+            spec_id: "01HSPEC".to_string(),
+            attachments: vec![ContextPanelItem {
+                attachment_id: "01HATT".to_string(),
+                filename: "notes.md".to_string(),
+                extension: "md".to_string(),
+                size_display: "1 KB".to_string(),
+                added_display: "12:00".to_string(),
+                summary: Some("the original summary".to_string()),
+                summary_html: Some("<p>the original summary</p>\n".to_string()),
+                summary_error: Some("transient LLM error".to_string()),
+                user_notes: None,
+                card_count: 0,
+                kind: AttachmentKind::Text,
+                mime_type: "text/markdown".to_string(),
+                raw_url: "/raw".to_string(),
+            }],
+        };
+        let rendered = tmpl.render().unwrap();
         assert!(
-            hx_push.is_some(),
-            "response should include HX-Push-Url header"
+            rendered.contains("the original summary"),
+            "stale state must still render the prior summary"
         );
-        let url = hx_push.unwrap().to_str().unwrap();
         assert!(
-            url.starts_with("/web/specs/"),
-            "HX-Push-Url should point to spec view"
+            rendered.contains("context-summary-stale-error"),
+            "stale state must render the small footnote"
         );
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(html.contains("Build a test spec for testing"));
-    }
-
-    #[test]
-    fn activity_transcript_template_renders_empty() {
-        let tmpl = ActivityTranscriptTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![],
-            pending_question: None,
-        };
-        let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("activity-transcript"),
-            "should contain activity-transcript id"
+            rendered.contains("transient LLM error"),
+            "footnote must include the error text"
         );
+        // Header pill flips to 'stale' for this combo.
         assert!(
-            rendered.contains("activity-transcript-feed"),
-            "should contain activity-transcript-feed div"
+            rendered.contains(">stale<"),
+            "header pill should read 'stale'"
         );
     }
 
     #[test]
-    fn activity_transcript_template_renders_with_entries() {
-        let tmpl = ActivityTranscriptTemplate {
+    fn context_panel_upload_form_lives_in_footer_not_header() {
+        // The "+ Add file" upload form belongs in the panel footer (under the
+        // attachment list), not in the header. Structural check: the upload
+        // form must appear after </div> that closes the chat-panel-header, AND
+        // must be inside a .context-panel-footer element.
+        let tmpl = ContextPanelTemplate {
             spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![TranscriptEntry {
-                sender: "agent-1".to_string(),
-                sender_label: "Agent-1".to_string(),
-                initial: "A".to_string(),
-                is_human: false,
-                is_step: false,
-                is_continuation: false,
-                role_class: "agent".to_string(),
-                content: "Started analysis".to_string(),
-                content_html: "<p>Started analysis</p>\n".to_string(),
-                timestamp: "12:34:56".to_string(),
-                repeat_count: 1,
-            }],
-            pending_question: None,
+            attachments: vec![],
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Agent-1"), "should contain sender_label");
-        assert!(rendered.contains("Started analysis"));
+        let form_idx = rendered
+            .find(r#"id="context-upload-form""#)
+            .expect("upload form exists");
+        let footer_idx = rendered
+            .find(r#"class="context-panel-footer""#)
+            .expect("footer exists");
+        let header_end = rendered
+            .find("chat-panel-header")
+            .and_then(|i| rendered[i..].find("</div>").map(|e| i + e + "</div>".len()))
+            .expect("header exists and closes");
         assert!(
-            !rendered.contains("chat-input"),
-            "transcript template should not contain chat input"
+            form_idx > header_end,
+            "upload form must be after the header"
+        );
+        assert!(
+            form_idx > footer_idx,
+            "upload form must be inside the footer"
         );
     }
 
     #[test]
-    fn transcript_template_renders_with_custom_container_id() {
-        let tmpl = ActivityTranscriptTemplate {
+    fn context_panel_preview_toggle_updates_button_text() {
+        // The preview toggle's onclick handler must also update the button's
+        // text so the label matches its state ("Preview context" / "Hide preview").
+        let tmpl = ContextPanelTemplate {
             spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
-            transcript: vec![TranscriptEntry {
-                sender: "human".to_string(),
-                sender_label: "You".to_string(),
-                initial: "Y".to_string(),
-                is_human: true,
-                is_step: false,
-                is_continuation: false,
-                role_class: "human".to_string(),
-                content: "Hello chat".to_string(),
-                content_html: "<p>Hello chat</p>\n".to_string(),
-                timestamp: "12:00:00".to_string(),
-                repeat_count: 1,
-            }],
-            pending_question: None,
+            attachments: vec![],
         };
         let rendered = tmpl.render().unwrap();
+        // Find the preview toggle button's onclick attribute.
         assert!(
-            rendered.contains("id=\"chat-transcript\""),
-            "should use chat-transcript as container id"
-        );
-        assert!(
-            rendered.contains("id=\"chat-transcript-feed\""),
-            "should use chat-transcript-feed as feed id"
-        );
-        assert!(
-            rendered.contains("hx-target=\"#chat-transcript\""),
-            "should target chat-transcript"
+            rendered.contains("id=\"context-preview-toggle\""),
+            "preview toggle should have a stable id"
         );
         assert!(
-            rendered.contains("container_id=chat-transcript"),
-            "hx-get should include container_id param"
+            rendered.contains("this.textContent ="),
+            "preview toggle onclick must update textContent"
         );
         assert!(
-            !rendered.contains("id=\"activity-transcript\""),
-            "should not contain activity-transcript id"
+            rendered.contains("'Hide preview'"),
+            "preview toggle onclick must include the open-state label"
         );
+    }
+
+    #[test]
+    fn render_markdown_does_not_passthrough_raw_script_tag() {
+        // Explicit safety test: pulldown-cmark is configured so raw HTML in
+        // markdown input does NOT reach the output as HTML tags.
+        let out = render_markdown("<script>x</script>");
         assert!(
-            rendered.contains("Hello chat"),
-            "should render transcript content"
+            !out.contains("<script>"),
+            "raw <script> must not pass through: {}",
+            out
         );
     }
 
     #[test]
-    fn activity_template_does_not_contain_chat_input() {
-        let tmpl = ActivityTemplate {
+    fn render_markdown_headers_list_and_paragraph() {
+        // Smoke-cover a few markdown features the summary panel will rely on.
+        assert!(render_markdown("# Heading").contains("<h1>Heading</h1>"));
+        let list = render_markdown("- a\n- b");
+        assert!(list.contains("<ul>") && list.contains("<li>a</li>"));
+        assert!(render_markdown("plain").contains("<p>plain</p>"));
+    }
+
+    #[test]
+    fn mission_ticker_template_renders_empty() {
+        let tmpl = MissionTickerTemplate {
             spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![],
+            ticker_entries: vec![],
+            queued_questions: 0,
             pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            !rendered.contains("chat-input"),
-            "activity should not contain chat input div"
+            rendered.contains("Awaiting activity"),
+            "should show empty state"
         );
         assert!(
-            !rendered.contains("Send a message"),
-            "activity should not contain chat placeholder"
+            rendered.contains("mission-ticker-feed"),
+            "should contain ticker feed id"
         );
     }
 
     #[test]
-    fn activity_template_contains_agent_controls() {
-        let tmpl = ActivityTemplate {
+    fn mission_ticker_template_renders_with_entries() {
+        let tmpl = MissionTickerTemplate {
             spec_id: "01HTEST".to_string(),
-            container_id: "activity-transcript".to_string(),
-            transcript: vec![],
+            ticker_entries: vec![TranscriptEntry {
+                message_id: String::new(),
+                sender: "manager-01JTEST".to_string(),
+                sender_label: "Manager".to_string(),
+                initial: "M".to_string(),
+                is_human: false,
+                is_step: false,
+                is_continuation: false,
+                role_class: "manager".to_string(),
+                content: "Analyzing requirements".to_string(),
+                content_html: "<p>Analyzing requirements</p>\n".to_string(),
+                timestamp: "12:34:56".to_string(),
+                repeat_count: 1,
+                agent_model: None,
+                is_highlighted: false,
+            }],
+            queued_questions: 0,
             pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("Manager"), "should contain sender label");
         assert!(
-            rendered.contains("agent-controls"),
-            "activity should contain agent controls"
-        );
-        assert!(
-            rendered.contains("agent-status"),
-            "activity should contain agent status"
+            rendered.contains("Analyzing requirements"),
+            "should contain message content"
         );
         assert!(
-            rendered.contains("Undo"),
-            "activity should contain undo button"
+            rendered.contains("ticker-entry"),
+            "should contain ticker entry class"
         );
     }
 
     #[test]
-    fn spec_view_template_contains_mission_control_layout() {
-        let tmpl = SpecViewTemplate {
+    fn mission_ticker_template_renders_with_question() {
+        let tmpl = MissionTickerTemplate {
             spec_id: "01HTEST".to_string(),
-            title: "Test Spec".to_string(),
-            one_liner: "A test spec".to_string(),
-            goal: "Test goal".to_string(),
-            phase: "refining".to_string(),
-            lanes: vec![],
+            ticker_entries: vec![],
+            queued_questions: 0,
+            pending_question: Some(QuestionData::Boolean {
+                question_id: "01HQID".to_string(),
+                question: "Should we proceed?".to_string(),
+                default: None,
+            }),
         };
         let rendered = tmpl.render().unwrap();
-        // Command bar with title and subtitle
         assert!(
-            rendered.contains("command-bar"),
-            "should contain command-bar"
-        );
-        assert!(rendered.contains("Test Spec"), "should contain spec title");
-        assert!(rendered.contains("A test spec"), "should contain one-liner");
-        // Capsule view toggles for document, board, spec
-        assert!(
-            rendered.contains("view-toggles-capsule"),
-            "should contain capsule view toggles"
-        );
-        assert!(
-            rendered.contains("data-view=\"document\""),
-            "should contain document toggle"
-        );
-        assert!(
-            rendered.contains("data-view=\"board\""),
-            "should contain board toggle"
+            rendered.contains("Should we proceed?"),
+            "should contain question text"
         );
+        assert!(rendered.contains("Yes"), "should contain Yes button");
+        assert!(rendered.contains("No"), "should contain No button");
+    }
+
+    #[test]
+    fn agent_leds_template_renders_running() {
+        let tmpl = AgentLedsTemplate {
+            spec_id: "01HTEST".to_string(),
+            running: true,
+            started: true,
+        };
+        let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("data-view=\"spec\""),
-            "should contain spec toggle"
+            rendered.contains("led-active"),
+            "should contain active LED class"
         );
         assert!(
-            rendered.contains("view-toggle active"),
-            "document toggle should be active"
+            rendered.contains("led-manager"),
+            "should contain manager LED"
         );
-        // Canvas and chat rail
         assert!(
-            rendered.contains("id=\"canvas\""),
-            "should contain canvas element"
+            rendered.contains("led-brainstormer"),
+            "should contain brainstormer LED"
         );
         assert!(
-            rendered.contains("spec-body"),
-            "should contain spec-body row"
+            rendered.contains("led-planner"),
+            "should contain planner LED"
         );
-        assert!(rendered.contains("chat-rail"), "should contain chat-rail");
-        assert!(rendered.contains("chat-panel"), "should load chat panel");
-        // Agent controls in command bar
+    }
+
+    #[test]
+    fn agent_leds_template_renders_paused() {
+        let tmpl = AgentLedsTemplate {
+            spec_id: "01HTEST".to_string(),
+            running: false,
+            started: true,
+        };
+        let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("agent-controls"),
-            "should contain agent-controls"
+            rendered.contains("led-paused"),
+            "should contain paused LED class"
         );
-        // SSE on spec-compositor
         assert!(
-            rendered.contains("sse-connect"),
-            "should have SSE connection"
+            !rendered.contains("led-active"),
+            "should not contain active LED class"
         );
-        // Old layout elements should NOT be present
+    }
+
+    #[test]
+    fn agent_leds_template_renders_stopped() {
+        let tmpl = AgentLedsTemplate {
+            spec_id: "01HTEST".to_string(),
+            running: false,
+            started: false,
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("led-off"), "should contain off LED class");
         assert!(
-            !rendered.contains("mission-strip"),
-            "should not contain mission-strip"
+            !rendered.contains("led-active"),
+            "should not contain active LED class"
         );
         assert!(
-            !rendered.contains("mission-ticker"),
-            "should not contain mission-ticker"
+            !rendered.contains("led-paused"),
+            "should not contain paused LED class"
         );
+    }
+
+    #[tokio::test]
+    async fn post_chat_sends_message() {
+        let state = test_state();
+
+        // First create a spec
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build a chat testing system"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Get the spec_id from the actors
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
+        };
+
+        // Post a chat message
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::post(format!("/web/specs/{}/chat", spec_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("message=Hello+from+chat"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
         assert!(
-            !rendered.contains("tab-bar"),
-            "should not contain old tab-bar"
+            html.contains("Hello from chat"),
+            "chat message should appear in activity: {}",
+            html
         );
         assert!(
-            !rendered.contains("right-rail"),
-            "should not contain right-rail references"
+            html.contains("human"),
+            "sender should be 'human' in activity: {}",
+            html
         );
     }
 
-    #[test]
-    fn spec_view_phase_check_polling_is_singleton_per_phase() {
-        // Regression: the polling fallback used a per-render `setInterval(...)`
-        // captured as a closure local. Each #workspace innerHTML swap therefore
-        // installed a fresh timer without clearing the previous one, and the old
-        // timer's stale `currentPhase` would race ahead of a real phase change
-        // and trigger another workspace refetch every 15s — wiping in-progress
-        // chat / Q&A input state. Each phase template MUST guard with
-        // `clearInterval(window.__bsPhase.timerId)` and store the timer id on
-        // the `window.__bsPhase` singleton.
-        for phase in ["brainstorming", "refining", "complete"] {
-            let tmpl = SpecViewTemplate {
-                spec_id: "01HTEST".to_string(),
-                title: "Test".to_string(),
-                one_liner: "t".to_string(),
-                goal: "g".to_string(),
-                phase: phase.to_string(),
-                lanes: vec![],
-            };
-            let rendered = tmpl.render().unwrap();
-
-            assert!(
-                rendered.contains("window.__bsPhase"),
-                "phase={phase}: rendered HTML should declare window.__bsPhase singleton"
-            );
-            assert!(
-                rendered.contains("clearInterval(window.__bsPhase.timerId)"),
-                "phase={phase}: rendered HTML should clear the prior phase-check timer before installing a new one"
-            );
-            assert!(
-                rendered.contains("window.__bsPhase.timerId = setInterval"),
-                "phase={phase}: rendered HTML should assign the new timer id to the singleton slot"
-            );
-            // The buggy pattern was a bare `var currentPhase = '...'` followed
-            // by an unguarded `setInterval`. Make sure it's gone.
-            assert!(
-                !rendered.contains("var currentPhase ="),
-                "phase={phase}: bare `var currentPhase = ...` is the leaky pattern; it must be replaced by the window.__bsPhase singleton"
-            );
-        }
+    #[tokio::test]
+    async fn post_chat_to_nonexistent_spec_returns_404() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let fake_id = ulid::Ulid::new();
+        let resp = app
+            .oneshot(
+                Request::post(format!("/web/specs/{}/chat", fake_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("message=Hello"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 404);
     }
 
     #[test]
-    fn spec_view_brainstorming_wires_context_sse_via_hx_trigger() {
-        // The context rail must rely on the declarative `hx-trigger="sse:..."` pattern
-        // (which htmx-ext-sse 2.2.2 actually supports) rather than a JS listener on
-        // `sse:<event>` DOM events, which that extension does NOT dispatch.
-        let tmpl = SpecViewTemplate {
+    fn agent_status_template_renders_stopped() {
+        let tmpl = AgentStatusTemplate {
             spec_id: "01HTEST".to_string(),
-            title: "Brainstorm Spec".to_string(),
-            one_liner: "A brainstorming spec".to_string(),
-            goal: "Think big".to_string(),
-            phase: "brainstorming".to_string(),
-            lanes: vec![],
+            running: false,
+            started: false,
+            budget_reached: false,
+            agent_count: 0,
+            agents: Vec::new(),
+            roster: vec![
+                RosterOptionView {
+                    role: "manager".to_string(),
+                    name: "Orchestrator".to_string(),
+                    selected: true,
+                },
+                RosterOptionView {
+                    role: "critic".to_string(),
+                    name: "Critic".to_string(),
+                    selected: false,
+                },
+            ],
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains(r#"hx-trigger="load, sse:context_attached, sse:context_summarized, sse:context_summarize_failed, sse:context_notes_updated, sse:context_removed""#),
-            "context rail must declare SSE triggers"
+            rendered.contains("agent-status"),
+            "should contain agent-status id"
         );
         assert!(
-            !rendered.contains("'sse:' + evt"),
-            "dead JS listener pattern for context events must be removed"
+            rendered.contains("agent-pill-stopped"),
+            "should have stopped pill class"
+        );
+        assert!(
+            rendered.contains("Start agents"),
+            "should show start agents text"
+        );
+        assert!(
+            rendered.contains("/agents/start"),
+            "should have start action URL"
+        );
+        assert!(
+            rendered.contains(r#"hx-post="/web/specs/01HTEST/agents/roster/critic/toggle""#),
+            "should offer a roster checkbox per role"
         );
+        assert_eq!(rendered.matches(" checked").count(), 1);
     }
 
     #[test]
-    fn context_panel_shows_in_context_badge_when_summary_present() {
-        // An attachment that has a summary should render the "in context" pill —
-        // signalling to the user that it's been included in the agent's prompt.
-        let tmpl = ContextPanelTemplate {
+    fn agent_status_template_renders_running() {
+        let tmpl = AgentStatusTemplate {
             spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "notes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "1.2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("a short summary".to_string()),
-                summary_html: Some("<p>a short summary</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
+            running: true,
+            started: true,
+            budget_reached: false,
+            agent_count: 4,
+            agents: vec![
+                AgentToggleView {
+                    role: "manager".to_string(),
+                    name: "Orchestrator".to_string(),
+                    enabled: true,
+                },
+                AgentToggleView {
+                    role: "dot_generator".to_string(),
+                    name: "Dot Generator".to_string(),
+                    enabled: false,
+                },
+            ],
+            roster: Vec::new(),
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("in context"),
-            "summary present should show 'in context' pill"
+            rendered.contains("/web/specs/01HTEST/agents/dot_generator/toggle"),
+            "should offer a per-agent toggle"
         );
-        // The hover hint must use the shared .tooltip component rather than a
-        // native `title=` attribute, so it renders as a styled popover.
         assert!(
-            rendered.contains(r#"class="has-tooltip""#),
-            "in-context pill must be wrapped in .has-tooltip"
+            rendered.contains("agent-toggle agent-toggle-off"),
+            "disabled agent should render as off"
         );
         assert!(
-            rendered.contains(r#"class="tooltip context-pill-tooltip""#),
-            "in-context hint must use the .tooltip component"
+            rendered.contains("agent-pill-running"),
+            "should have running pill class"
         );
         assert!(
-            rendered.contains("Included in agent context"),
-            "tooltip text must be present"
+            rendered.contains("Agents active"),
+            "should show active state"
+        );
+        assert!(
+            rendered.contains("/agents/pause"),
+            "should have pause action URL"
         );
     }
 
     #[test]
-    fn context_panel_shows_summarizing_badge_when_summary_pending() {
-        // An attachment without a summary yet should render the "summarizing…" pill.
-        let tmpl = ContextPanelTemplate {
+    fn agent_status_template_flags_an_exhausted_step_budget() {
+        let tmpl = AgentStatusTemplate {
             spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "pending.txt".to_string(),
-                extension: "txt".to_string(),
-                size_display: "500 B".to_string(),
-                added_display: "12:35".to_string(),
-                summary: None,
-                summary_html: None,
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
+            running: false,
+            started: true,
+            budget_reached: true,
+            agent_count: 4,
+            agents: Vec::new(),
+            roster: Vec::new(),
+        };
+        let rendered = tmpl.render().unwrap();
+        assert!(rendered.contains("agent-budget-note"));
+        assert!(rendered.contains("Step budget reached"));
+        assert!(rendered.contains("/agents/resume"));
+    }
+
+    #[test]
+    fn agent_status_template_renders_paused_as_stopped() {
+        let tmpl = AgentStatusTemplate {
+            spec_id: "01HTEST".to_string(),
+            running: false,
+            started: true,
+            budget_reached: false,
+            agent_count: 4,
+            agents: Vec::new(),
+            roster: Vec::new(),
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("summarizing"),
-            "summary pending should show 'summarizing' pill"
+            rendered.contains("agent-pill-stopped"),
+            "paused should render as stopped pill"
         );
         assert!(
-            rendered.contains("Summary in progress"),
-            "tooltip text must be present on the summarizing pill"
+            rendered.contains("Start agents"),
+            "paused should show start agents text"
+        );
+        assert!(
+            rendered.contains("/agents/resume"),
+            "paused should resume on click"
+        );
+        assert!(
+            !rendered.contains("agent-pill-paused"),
+            "should not have separate paused state"
         );
     }
 
-    #[test]
-    fn context_panel_uses_details_summary_for_collapsible_card() {
-        // Each attachment card must use native <details>/<summary> so users can
-        // click the header to expand/collapse without any JS.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "notes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "1.2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("a short summary".to_string()),
-                summary_html: Some("<p>a short summary</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
+    #[tokio::test]
+    async fn get_agent_status_returns_stopped_when_no_swarm() {
+        let state = test_state();
+
+        // Create a spec first
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build an agent testing system"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
         };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("<details"), "card must use <details>");
-        assert!(rendered.contains("<summary"), "card must use <summary>");
-        // <summary> accepts only phrasing content — a nested <div> is invalid HTML5
-        // and causes browsers to eject the div from the summary context, breaking
-        // the native toggle. The header must use <span> with display:flex instead.
-        let summary_start = rendered.find("<summary").expect("summary exists");
-        let summary_end = rendered[summary_start..]
-            .find("</summary>")
-            .map(|e| summary_start + e)
-            .expect("summary closes");
-        let summary_block = &rendered[summary_start..summary_end];
+
+        // Get agent status (no swarm started)
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::get(format!("/web/specs/{}/agents/status", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
         assert!(
-            !summary_block.contains("<div"),
-            "<summary> must not contain <div> (block content breaks the toggle); got:\n{summary_block}"
+            html.contains("Start agents"),
+            "should show stopped pill when no swarm: {}",
+            html
         );
     }
 
-    #[test]
-    fn context_panel_expanded_renders_markdown_summary_as_html() {
-        // When the card is open, the summary should be rendered as HTML
-        // from the `summary_html` field (bold, etc.), not as plain text.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "notes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "1.2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("**bold**".to_string()),
-                summary_html: Some(render_markdown("**bold**")),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
+    #[tokio::test]
+    async fn pause_agents_returns_stopped_when_no_swarm() {
+        let state = test_state();
+
+        // Create a spec
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build a pause testing system"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
         };
-        let rendered = tmpl.render().unwrap();
+
+        // Pause without starting returns stopped state
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::post(format!("/web/specs/{}/agents/pause", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
         assert!(
-            rendered.contains("<strong>bold</strong>"),
-            "expanded card should contain rendered HTML, got: {}",
-            rendered
+            html.contains("Start agents"),
+            "pause with no swarm should show stopped pill: {}",
+            html
         );
     }
 
-    #[test]
-    fn context_panel_delete_button_is_outside_summary() {
-        // The delete button should live in the expanded section so clicking it
-        // doesn't toggle the card. Concretely: the `hx-delete` attribute must
-        // appear AFTER the closing `</summary>` tag.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "notes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "1.2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("a summary".to_string()),
-                summary_html: Some("<p>a summary</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
+    #[tokio::test]
+    async fn resume_agents_returns_stopped_when_no_swarm() {
+        let state = test_state();
+
+        // Create a spec
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build a resume testing system"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
         };
-        let rendered = tmpl.render().unwrap();
-        let delete_idx = rendered
-            .find("hx-delete")
-            .expect("should have delete button");
-        let summary_close_idx = rendered
-            .find("</summary>")
-            .expect("should have </summary> tag");
+
+        // Resume without starting returns stopped state
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::post(format!("/web/specs/{}/agents/resume", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            html.contains("Start agents"),
+            "resume with no swarm should show stopped pill: {}",
+            html
+        );
+    }
+
+    #[tokio::test]
+    async fn agent_status_for_nonexistent_spec_returns_stopped() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let fake_id = ulid::Ulid::new();
+
+        let resp = app
+            .oneshot(
+                Request::get(format!("/web/specs/{}/agents/status", fake_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), 200);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
         assert!(
-            delete_idx > summary_close_idx,
-            "delete button (hx-delete) must appear after </summary>, got delete at {} vs </summary> at {}",
-            delete_idx,
-            summary_close_idx,
+            html.contains("Start agents"),
+            "nonexistent spec should show stopped pill: {}",
+            html
         );
     }
 
     #[test]
-    fn context_panel_shows_card_count_when_cards_sourced() {
-        // When the Manager has synthesized cards from an attachment, the
-        // collapsed header should show a small count so the user can see
-        // at a glance how much of a file has been processed.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "vibes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("design vibes".to_string()),
-                summary_html: Some("<p>design vibes</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 3,
-            }],
+    fn provider_status_template_renders_no_providers() {
+        let tmpl = ProviderStatusTemplate {
+            default_provider: "anthropic".to_string(),
+            default_model: None,
+            providers: vec![
+                ProviderInfoView {
+                    name: "anthropic".to_string(),
+                    available: false,
+                    model: "claude-sonnet-4-5-20250929".to_string(),
+                },
+                ProviderInfoView {
+                    name: "openai".to_string(),
+                    available: false,
+                    model: "gpt-4o".to_string(),
+                },
+                ProviderInfoView {
+                    name: "gemini".to_string(),
+                    available: false,
+                    model: "gemini-2.0-flash".to_string(),
+                },
+            ],
+            any_available: false,
+            model_warning: None,
         };
         let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("3 cards"),
-            "panel should show card count when > 0, got: {rendered}"
-        );
+        assert!(rendered.contains("No provider configured"));
+        assert!(!rendered.contains("provider-warning"));
+        assert!(rendered.contains("disconnected"));
     }
 
     #[test]
-    fn context_panel_pluralizes_single_card_count() {
-        // Singular form when exactly one card references the attachment.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "vibes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("design vibes".to_string()),
-                summary_html: Some("<p>design vibes</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 1,
-            }],
+    fn provider_status_template_renders_with_provider() {
+        let tmpl = ProviderStatusTemplate {
+            default_provider: "anthropic".to_string(),
+            default_model: Some("claude-sonnet-4-5-20250929".to_string()),
+            providers: vec![
+                ProviderInfoView {
+                    name: "anthropic".to_string(),
+                    available: true,
+                    model: "claude-sonnet-4-5-20250929".to_string(),
+                },
+                ProviderInfoView {
+                    name: "openai".to_string(),
+                    available: false,
+                    model: "gpt-4o".to_string(),
+                },
+            ],
+            any_available: true,
+            model_warning: None,
         };
         let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("1 card") && !rendered.contains("1 cards"),
-            "panel should use singular 'card' for count of 1, got: {rendered}"
-        );
+        assert!(rendered.contains("anthropic"));
+        assert!(rendered.contains("connected"));
+        assert!(rendered.contains("claude-sonnet-4-5-20250929"));
     }
 
     #[test]
-    fn context_panel_shows_not_yet_synthesized_when_summarized_but_no_cards() {
-        // An attachment that has a summary but hasn't been ingested yet should
-        // display a subtle hint so the user knows the Manager is expected to
-        // process it.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "vibes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("design vibes".to_string()),
-                summary_html: Some("<p>design vibes</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
+    fn provider_status_template_shows_model_warning() {
+        let tmpl = ProviderStatusTemplate {
+            default_provider: "anthropic".to_string(),
+            default_model: Some("gpt-4o".to_string()),
+            providers: vec![],
+            any_available: true,
+            model_warning: Some(
+                "model 'gpt-4o' looks like a openai model, but the provider is anthropic"
+                    .to_string(),
+            ),
         };
         let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("Not yet synthesized"),
-            "panel should show 'Not yet synthesized' when summary is Some and card_count is 0"
-        );
+        assert!(rendered.contains("provider-warning"));
+        assert!(rendered.contains("looks like a openai model"));
     }
 
-    #[test]
-    fn context_panel_hides_not_yet_synthesized_when_summary_pending() {
-        // Before a summary exists, the hint would be confusing — hide it.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "pending.txt".to_string(),
-                extension: "txt".to_string(),
-                size_display: "500 B".to_string(),
-                added_display: "12:35".to_string(),
-                summary: None,
-                summary_html: None,
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            !rendered.contains("Not yet synthesized"),
-            "panel should not show the synthesis hint before a summary exists"
-        );
+    #[tokio::test]
+    async fn get_provider_status_returns_html() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let resp = app
+            .oneshot(
+                Request::get("/web/provider-status")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(html.contains("provider-status"));
     }
 
-    #[test]
-    fn context_panel_hides_not_yet_synthesized_when_cards_present() {
-        // Once the Manager has sourced cards from the attachment, the hint
-        // should disappear.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "vibes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("design vibes".to_string()),
-                summary_html: Some("<p>design vibes</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 2,
-            }],
+    /// Create a test state that explicitly has no LLM providers configured,
+    /// regardless of the actual environment variables on the machine.
+    fn test_state_no_provider() -> SharedState {
+        let dir = tempfile::TempDir::new().unwrap();
+        let provider_status = ProviderStatus {
+            default_provider: "anthropic".to_string(),
+            default_model: None,
+            providers: vec![],
+            any_available: false,
+            model_warning: None,
         };
-        let rendered = tmpl.render().unwrap();
+        Arc::new(AppState::new(dir.keep(), provider_status))
+    }
+
+    #[tokio::test]
+    async fn create_spec_with_no_provider_does_not_start_agents() {
+        let state = test_state_no_provider();
+        let app = create_router(Arc::clone(&state), None);
+
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build a system without agents"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        // Since provider_status.any_available is false, try_start_agents should
+        // return early and no swarm should be created.
+        let swarms = state.swarms.read().await;
         assert!(
-            !rendered.contains("Not yet synthesized"),
-            "panel should not show the synthesis hint once cards reference the attachment"
+            swarms.is_empty(),
+            "no swarm should be created without provider"
         );
     }
 
-    #[test]
-    fn context_panel_hides_card_count_when_zero() {
-        // Don't clutter the collapsed header with "0 cards" — just omit the
-        // pill entirely when no cards reference the attachment.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "vibes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "2 KB".to_string(),
-                added_display: "12:34".to_string(),
-                summary: Some("design vibes".to_string()),
-                summary_html: Some("<p>design vibes</p>\n".to_string()),
-                user_notes: None,
-                summary_error: None,
-                kind: AttachmentKind::Text,
-                mime_type: "text/plain".to_string(),
-                raw_url: "/raw".to_string(),
-                card_count: 0,
-            }],
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            !rendered.contains("0 cards") && !rendered.contains("0 card"),
-            "panel should omit the count pill entirely when card_count is 0"
+    #[tokio::test]
+    async fn start_agents_for_nonexistent_spec_returns_404() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let fake_id = ulid::Ulid::new();
+
+        let resp = app
+            .oneshot(
+                Request::post(format!("/web/specs/{}/agents/start", fake_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn start_agents_uses_provider_override_from_query() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        // An unknown provider can only come from the override (the default is
+        // "anthropic"), so the error proves the override reached the swarm.
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(
+                Request::post(format!(
+                    "/web/specs/{}/agents/start?provider=bogus&model=tiny",
+                    spec_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 500);
+        let body = body_text(resp).await;
+        assert!(body.contains("unsupported LLM provider: bogus"), "{body}");
+        assert!(state.swarms.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn start_agents_saves_provider_override_on_the_spec() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        // Ollama needs no API key, so the swarm builds and starts.
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(
+                Request::post(format!(
+                    "/web/specs/{}/agents/start?provider=ollama&model=tiny",
+                    spec_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        if let Some(swarm) = state.swarms.write().await.remove(&spec_id) {
+            swarm.task.abort();
+        }
+
+        let handle = state.actors.read().await.get(&spec_id).cloned().unwrap();
+        let saved = handle.read_state().await.model_override.clone();
+        assert_eq!(
+            saved,
+            Some(barnstormer_core::AgentModel {
+                provider: "ollama".to_string(),
+                model: "tiny".to_string(),
+            })
         );
     }
 
     #[test]
-    fn attachment_kind_classifies_common_mimes() {
-        assert_eq!(
-            AttachmentKind::from_mime("image/png"),
-            AttachmentKind::ImageRaster
-        );
-        assert_eq!(
-            AttachmentKind::from_mime("image/JPEG"),
-            AttachmentKind::ImageRaster
-        );
-        assert_eq!(
-            AttachmentKind::from_mime("image/svg+xml"),
-            AttachmentKind::ImageSvg
-        );
-        assert_eq!(
-            AttachmentKind::from_mime("application/pdf"),
-            AttachmentKind::Pdf
-        );
-        assert_eq!(
-            AttachmentKind::from_mime("audio/mpeg"),
-            AttachmentKind::Audio
-        );
+    fn saved_model_override_applies_when_the_request_has_none() {
+        let state = test_state();
+        let mut spec_state = SpecState::new();
         assert_eq!(
-            AttachmentKind::from_mime("video/mp4"),
-            AttachmentKind::Video
+            resolve_agent_provider(&state, &spec_state, &StartAgentsQuery::default()),
+            ("anthropic".to_string(), None)
         );
+
+        spec_state.model_override = Some(barnstormer_core::AgentModel {
+            provider: "ollama".to_string(),
+            model: "tiny".to_string(),
+        });
         assert_eq!(
-            AttachmentKind::from_mime("text/markdown; charset=utf-8"),
-            AttachmentKind::Text
+            resolve_agent_provider(&state, &spec_state, &StartAgentsQuery::default()),
+            ("ollama".to_string(), Some("tiny".to_string()))
         );
-        // Unknown / weird types fall through to Text.
+
+        let request = StartAgentsQuery {
+            provider: Some("openai".to_string()),
+            model: Some("gpt-4o".to_string()),
+        };
         assert_eq!(
-            AttachmentKind::from_mime("application/octet-stream"),
-            AttachmentKind::Text
+            resolve_agent_provider(&state, &spec_state, &request),
+            ("openai".to_string(), Some("gpt-4o".to_string()))
         );
     }
 
+    // ---- Chat panel tests ----
+
     #[test]
-    fn context_panel_renders_image_preview_for_raster_attachment() {
-        // Raster image attachments should render an inline `<img>` preview using
-        // the raw URL — that's what makes the panel multimodal-aware.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HSPEC".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "diagram.png".to_string(),
-                extension: "png".to_string(),
-                size_display: "10 KB".to_string(),
-                added_display: "12:00".to_string(),
-                summary: Some("a diagram".to_string()),
-                summary_html: Some("<p>a diagram</p>\n".to_string()),
-                summary_error: None,
-                user_notes: None,
-                card_count: 0,
-                kind: AttachmentKind::ImageRaster,
-                mime_type: "image/png".to_string(),
-                raw_url: "/web/specs/01HSPEC/context/01HATT/raw".to_string(),
-            }],
+    fn chat_panel_template_renders() {
+        let tmpl = ChatPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
+
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains(r#"class="context-preview-image""#),
-            "raster image should produce an <img> preview"
-        );
-        assert!(
-            rendered.contains(r#"src="/web/specs/01HSPEC/context/01HATT/raw""#),
-            "preview <img> must point at the /raw URL"
-        );
-        // Per-kind badge: image variant, not the generic note variant.
-        assert!(
-            rendered.contains("badge-image"),
-            "image attachments should use badge-image"
+            rendered.contains("chat-panel"),
+            "should contain chat-panel div"
         );
     }
 
     #[test]
-    fn context_panel_renders_pdf_icon_for_pdf_attachment() {
-        // PDF previews are an icon + filename rather than an inline render —
-        // browser PDF viewers are heavy and rarely useful at thumbnail size.
-        // Guards against a regression that lets PDFs fall through to <img src>.
-        let att_id = Ulid::new();
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HSPEC".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: att_id.to_string(),
-                filename: "spec-draft.pdf".to_string(),
-                extension: "pdf".to_string(),
-                size_display: "12.0 KB".to_string(),
-                added_display: "12:00".to_string(),
-                summary: Some("a brief draft".to_string()),
-                summary_html: Some("a brief draft".to_string()),
-                summary_error: None,
-                user_notes: None,
-                card_count: 0,
-                kind: AttachmentKind::Pdf,
-                mime_type: "application/pdf".to_string(),
-                raw_url: format!("/web/specs/01HSPEC/context/{}/raw", att_id),
-            }],
-        };
-        let rendered = tmpl.render().expect("template renders");
+    fn chat_panel_renders_with_transcript_entries() {
+        let tmpl = ChatPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
 
-        // PDF icon block present.
+            transcript: vec![
+                TranscriptEntry {
+                    message_id: String::new(),
+                    sender: "human".to_string(),
+                    sender_label: "You".to_string(),
+                    initial: "Y".to_string(),
+                    is_human: true,
+                    is_step: false,
+                    is_continuation: false,
+                    role_class: "human".to_string(),
+                    content: "Hello from human".to_string(),
+                    content_html: "<p>Hello from human</p>\n".to_string(),
+                    timestamp: "12:34:56".to_string(),
+                    repeat_count: 1,
+                    agent_model: None,
+                    is_highlighted: false,
+                },
+                TranscriptEntry {
+                    message_id: String::new(),
+                    sender: "manager-01HAGENT".to_string(),
+                    sender_label: "Manager".to_string(),
+                    initial: "M".to_string(),
+                    is_human: false,
+                    is_step: false,
+                    is_continuation: false,
+                    role_class: "manager".to_string(),
+                    content: "Agent response here".to_string(),
+                    content_html: "<p>Agent response here</p>\n".to_string(),
+                    timestamp: "12:35:00".to_string(),
+                    repeat_count: 1,
+                    agent_model: None,
+                    is_highlighted: false,
+                },
+            ],
+            queued_questions: 0,
+            pending_question: None,
+        };
+        let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("context-preview-pdf-icon"),
-            "expected PDF icon class in rendered HTML"
+            rendered.contains("Hello from human"),
+            "should contain human message content"
         );
         assert!(
-            rendered.contains("spec-draft.pdf"),
-            "expected filename in PDF icon block"
+            rendered.contains("Agent response here"),
+            "should contain agent message content"
         );
-
-        // No <img> tag — PDFs must not fall through to raster preview path.
         assert!(
-            !rendered.contains("<img"),
-            "PDF preview must not render an <img> tag (would indicate kind classification regression)"
+            rendered.contains("chat-message"),
+            "should have chat-message class"
         );
-
-        // The doc badge is used for PDFs.
         assert!(
-            rendered.contains("badge-doc"),
-            "expected badge-doc on PDF attachment header"
+            rendered.contains("chat-avatar"),
+            "should have avatar element"
         );
-    }
-
-    #[test]
-    fn context_panel_renders_audio_preview_for_audio_attachment() {
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HSPEC".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "voice.mp3".to_string(),
-                extension: "mp3".to_string(),
-                size_display: "100 KB".to_string(),
-                added_display: "12:00".to_string(),
-                summary: Some("a voice memo".to_string()),
-                summary_html: Some("<p>a voice memo</p>\n".to_string()),
-                summary_error: None,
-                user_notes: None,
-                card_count: 0,
-                kind: AttachmentKind::Audio,
-                mime_type: "audio/mpeg".to_string(),
-                raw_url: "/web/specs/01HSPEC/context/01HATT/raw".to_string(),
-            }],
-        };
-        let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("<audio") && rendered.contains("controls"),
-            "audio attachment should produce an <audio controls> element"
+            rendered.contains("chat-sender"),
+            "should have sender label element"
         );
         assert!(
-            rendered.contains(r#"type="audio/mpeg""#),
-            "audio <source> must declare the original mime type"
+            !rendered.contains("No messages yet"),
+            "should not show empty state when entries exist"
         );
-        assert!(rendered.contains("badge-audio"));
     }
 
     #[test]
-    fn context_panel_resummarize_button_targets_correct_endpoint() {
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HSPEC".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "notes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "1 KB".to_string(),
-                added_display: "12:00".to_string(),
-                summary: Some("a summary".to_string()),
-                summary_html: Some("<p>a summary</p>\n".to_string()),
-                summary_error: None,
-                user_notes: None,
-                card_count: 0,
-                kind: AttachmentKind::Text,
-                mime_type: "text/markdown".to_string(),
-                raw_url: "/raw".to_string(),
-            }],
+    fn chat_panel_renders_empty_transcript() {
+        let tmpl = ChatPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
+
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains(r#"hx-post="/web/specs/01HSPEC/context/01HATT/resummarize""#),
-            "Resummarize button must POST to the resummarize endpoint"
+            rendered.contains("No messages yet"),
+            "should show empty state message"
         );
         assert!(
-            rendered.contains(">Resummarize<"),
-            "button label must be 'Resummarize'"
+            rendered.contains("chat-empty"),
+            "should have chat-empty class"
+        );
+        assert!(
+            rendered.contains("Type below to start a conversation"),
+            "should show hint text"
         );
     }
 
     #[test]
-    fn context_panel_failure_state_no_summary_shows_card_error() {
-        // None summary + Some error: hard-failure state, no prior summary to
-        // fall back on. The error text should be the dominant content of the
-        // card body so the user knows to act.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HSPEC".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "broken.bin".to_string(),
-                extension: "bin".to_string(),
-                size_display: "1 KB".to_string(),
-                added_display: "12:00".to_string(),
-                summary: None,
-                summary_html: None,
-                summary_error: Some("provider rejected payload".to_string()),
-                user_notes: None,
-                card_count: 0,
-                kind: AttachmentKind::Text,
-                mime_type: "application/octet-stream".to_string(),
-                raw_url: "/raw".to_string(),
-            }],
+    fn chat_panel_contains_chat_input_form() {
+        let tmpl = ChatPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
+
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains(r#"class="card-error""#),
-            "hard failure must render the .card-error block"
+            rendered.contains("chat-input-area"),
+            "should contain chat-input-area div"
         );
         assert!(
-            rendered.contains("provider rejected payload"),
-            "error text must be visible to the user"
+            rendered.contains("chat-input-row"),
+            "should contain chat-input-row div"
         );
         assert!(
-            rendered.contains("summary failed"),
-            "header pill should read 'summary failed'"
+            rendered.contains(r#"hx-post="/web/specs/01HTEST/chat""#),
+            "should post to chat endpoint"
         );
         assert!(
-            !rendered.contains("Summarizing&hellip;"),
-            "must not show the pending spinner when there's an error"
+            rendered.contains(r##"hx-target="#chat-transcript""##),
+            "chat form should target chat-transcript"
+        );
+        assert!(
+            rendered.contains("Ask the agents anything"),
+            "should have placeholder text"
         );
     }
 
     #[test]
-    fn context_panel_stale_state_shows_summary_and_error_footnote() {
-        // Some summary + Some error: stale-with-error. Both the prior summary
-        // and the failure footnote must render so the user understands what's
-        // currently in context.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HSPEC".to_string(),
-            attachments: vec![ContextPanelItem {
-                attachment_id: "01HATT".to_string(),
-                filename: "notes.md".to_string(),
-                extension: "md".to_string(),
-                size_display: "1 KB".to_string(),
-                added_display: "12:00".to_string(),
-                summary: Some("the original summary".to_string()),
-                summary_html: Some("<p>the original summary</p>\n".to_string()),
-                summary_error: Some("transient LLM error".to_string()),
-                user_notes: None,
-                card_count: 0,
-                kind: AttachmentKind::Text,
-                mime_type: "text/markdown".to_string(),
-                raw_url: "/raw".to_string(),
-            }],
+    fn chat_panel_contains_transcript_and_input() {
+        let tmpl = ChatPanelTemplate {
+            spec_id: "01HTEST".to_string(),
+            container_id: "chat-transcript".to_string(),
+
+            transcript: vec![],
+            queued_questions: 0,
+            pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("the original summary"),
-            "stale state must still render the prior summary"
+            rendered.contains("chat-panel"),
+            "should contain chat-panel wrapper"
         );
         assert!(
-            rendered.contains("context-summary-stale-error"),
-            "stale state must render the small footnote"
+            rendered.contains("sse:transcript_appended"),
+            "should listen for transcript_appended event"
         );
         assert!(
-            rendered.contains("transient LLM error"),
-            "footnote must include the error text"
+            rendered.contains("chat-input-area"),
+            "should contain input area"
         );
-        // Header pill flips to 'stale' for this combo.
+    }
+
+    #[tokio::test]
+    async fn chat_panel_handler_returns_200() {
+        let state = test_state();
+
+        // Create a spec first
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build a chat panel testing system"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
+        };
+
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::get(&format!("/web/specs/{}/chat-panel", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
         assert!(
-            rendered.contains(">stale<"),
-            "header pill should read 'stale'"
+            html.contains("chat-panel"),
+            "chat panel response should contain chat-panel: {}",
+            html
         );
     }
 
-    #[test]
-    fn context_panel_upload_form_lives_in_footer_not_header() {
-        // The "+ Add file" upload form belongs in the panel footer (under the
-        // attachment list), not in the header. Structural check: the upload
-        // form must appear after </div> that closes the chat-panel-header, AND
-        // must be inside a .context-panel-footer element.
-        let tmpl = ContextPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            attachments: vec![],
+    #[tokio::test]
+    async fn chat_panel_for_nonexistent_spec_returns_404() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let fake_id = ulid::Ulid::new();
+        let resp = app
+            .oneshot(
+                Request::get(format!("/web/specs/{}/chat-panel", fake_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn chat_panel_brainstorming_targets_brainstorm_chat() {
+        let state = test_state();
+        let app = create_router(Arc::clone(&state), None);
+        app.oneshot(
+            Request::post("/web/specs")
+                .header("content-type", MP_CONTENT_TYPE)
+                .body(mp_description_body("Chat brainstorm test"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().unwrap()
         };
-        let rendered = tmpl.render().unwrap();
-        let form_idx = rendered
-            .find(r#"id="context-upload-form""#)
-            .expect("upload form exists");
-        let footer_idx = rendered
-            .find(r#"class="context-panel-footer""#)
-            .expect("footer exists");
-        let header_end = rendered
-            .find("chat-panel-header")
-            .and_then(|i| rendered[i..].find("</div>").map(|e| i + e + "</div>".len()))
-            .expect("header exists and closes");
+
+        // Spec starts in Brainstorming
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::get(&format!("/web/specs/{}/chat-panel", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            html.contains("brainstorm-chat"),
+            "should target brainstorm-chat container in brainstorming"
+        );
+        assert!(
+            !html.contains("chat-fullwidth"),
+            "should not have chat-fullwidth class (removed)"
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_panel_refining_targets_chat_transcript() {
+        let state = test_state();
+        let app = create_router(Arc::clone(&state), None);
+        app.oneshot(
+            Request::post("/web/specs")
+                .header("content-type", MP_CONTENT_TYPE)
+                .body(mp_description_body("Chat refining test"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().unwrap()
+        };
+
+        // Transition to Refining
+        let actors = state.actors.read().await;
+        let handle = actors.get(&spec_id).unwrap();
+        handle
+            .send_command(Command::TransitionPhase {
+                target: SpecPhase::Refining,
+            })
+            .await
+            .unwrap();
+        drop(actors);
+
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::get(format!("/web/specs/{}/chat-panel", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
         assert!(
-            form_idx > header_end,
-            "upload form must be after the header"
+            html.contains("chat-transcript"),
+            "should target chat-transcript container in refining"
         );
         assert!(
-            form_idx > footer_idx,
-            "upload form must be inside the footer"
+            !html.contains("chat-fullwidth"),
+            "should not have chat-fullwidth class (removed)"
         );
     }
 
+    // ---- Artifacts tests ----
+
     #[test]
-    fn context_panel_preview_toggle_updates_button_text() {
-        // The preview toggle's onclick handler must also update the button's
-        // text so the label matches its state ("Preview context" / "Hide preview").
-        let tmpl = ContextPanelTemplate {
+    fn artifacts_template_renders() {
+        let tmpl = ArtifactsTemplate {
             spec_id: "01HTEST".to_string(),
-            attachments: vec![],
+            title_slug: "my-spec".to_string(),
+            markdown_content: "# My Spec".to_string(),
+            yaml_content: "title: My Spec".to_string(),
+            dot_content: "digraph {}".to_string(),
+            mermaid_content: "flowchart TD".to_string(),
+            json_content: "{}".to_string(),
         };
         let rendered = tmpl.render().unwrap();
-        // Find the preview toggle button's onclick attribute.
-        assert!(
-            rendered.contains("id=\"context-preview-toggle\""),
-            "preview toggle should have a stable id"
-        );
-        assert!(
-            rendered.contains("this.textContent ="),
-            "preview toggle onclick must update textContent"
-        );
-        assert!(
-            rendered.contains("'Hide preview'"),
-            "preview toggle onclick must include the open-state label"
-        );
-    }
-
-    #[test]
-    fn render_markdown_does_not_passthrough_raw_script_tag() {
-        // Explicit safety test: pulldown-cmark is configured so raw HTML in
-        // markdown input does NOT reach the output as HTML tags.
-        let out = render_markdown("<script>x</script>");
         assert!(
-            !out.contains("<script>"),
-            "raw <script> must not pass through: {}",
-            out
+            rendered.contains("artifacts-panel"),
+            "should contain artifacts-panel div"
         );
     }
 
     #[test]
-    fn render_markdown_headers_list_and_paragraph() {
-        // Smoke-cover a few markdown features the summary panel will rely on.
-        assert!(render_markdown("# Heading").contains("<h1>Heading</h1>"));
-        let list = render_markdown("- a\n- b");
-        assert!(list.contains("<ul>") && list.contains("<li>a</li>"));
-        assert!(render_markdown("plain").contains("<p>plain</p>"));
-    }
-
-    #[test]
-    fn mission_ticker_template_renders_empty() {
-        let tmpl = MissionTickerTemplate {
+    fn artifacts_template_contains_all_content_sections() {
+        let tmpl = ArtifactsTemplate {
             spec_id: "01HTEST".to_string(),
-            ticker_entries: vec![],
-            pending_question: None,
+            title_slug: "my-spec".to_string(),
+            markdown_content: "# My Spec".to_string(),
+            yaml_content: "title: My Spec".to_string(),
+            dot_content: "digraph {}".to_string(),
+            mermaid_content: "flowchart TD".to_string(),
+            json_content: "{}".to_string(),
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("Awaiting activity"),
-            "should show empty state"
+            rendered.contains("id=\"markdown-source\""),
+            "should contain markdown-source section"
         );
         assert!(
-            rendered.contains("mission-ticker-feed"),
-            "should contain ticker feed id"
+            rendered.contains("id=\"yaml-source\""),
+            "should contain yaml-source section"
         );
-    }
-
-    #[test]
-    fn mission_ticker_template_renders_with_entries() {
-        let tmpl = MissionTickerTemplate {
-            spec_id: "01HTEST".to_string(),
-            ticker_entries: vec![TranscriptEntry {
-                sender: "manager-01JTEST".to_string(),
-                sender_label: "Manager".to_string(),
-                initial: "M".to_string(),
-                is_human: false,
-                is_step: false,
-                is_continuation: false,
-                role_class: "manager".to_string(),
-                content: "Analyzing requirements".to_string(),
-                content_html: "<p>Analyzing requirements</p>\n".to_string(),
-                timestamp: "12:34:56".to_string(),
-                repeat_count: 1,
-            }],
-            pending_question: None,
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("Manager"), "should contain sender label");
         assert!(
-            rendered.contains("Analyzing requirements"),
-            "should contain message content"
+            rendered.contains("id=\"dot-source\""),
+            "should contain dot-source section"
         );
         assert!(
-            rendered.contains("ticker-entry"),
-            "should contain ticker entry class"
+            rendered.contains("# My Spec"),
+            "should render markdown content"
         );
-    }
-
-    #[test]
-    fn mission_ticker_template_renders_with_question() {
-        let tmpl = MissionTickerTemplate {
-            spec_id: "01HTEST".to_string(),
-            ticker_entries: vec![],
-            pending_question: Some(QuestionData::Boolean {
-                question_id: "01HQID".to_string(),
-                question: "Should we proceed?".to_string(),
-                default: None,
-            }),
-        };
-        let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("Should we proceed?"),
-            "should contain question text"
+            rendered.contains("title: My Spec"),
+            "should render yaml content"
+        );
+        assert!(rendered.contains("digraph {}"), "should render dot content");
+        assert!(
+            rendered.contains("id=\"mermaid-source\""),
+            "should contain mermaid-source section"
+        );
+        assert!(
+            rendered.contains("flowchart TD"),
+            "should render mermaid content"
         );
-        assert!(rendered.contains("Yes"), "should contain Yes button");
-        assert!(rendered.contains("No"), "should contain No button");
     }
 
     #[test]
-    fn agent_leds_template_renders_running() {
-        let tmpl = AgentLedsTemplate {
+    fn artifacts_template_contains_download_links() {
+        let tmpl = ArtifactsTemplate {
             spec_id: "01HTEST".to_string(),
-            running: true,
-            started: true,
+            title_slug: "test".to_string(),
+            markdown_content: "# Test".to_string(),
+            yaml_content: "title: Test".to_string(),
+            dot_content: "digraph {}".to_string(),
+            mermaid_content: "flowchart TD".to_string(),
+            json_content: "{}".to_string(),
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("led-active"),
-            "should contain active LED class"
+            rendered.contains("/web/specs/01HTEST/export/markdown"),
+            "should contain markdown download link"
         );
         assert!(
-            rendered.contains("led-manager"),
-            "should contain manager LED"
+            rendered.contains("/web/specs/01HTEST/export/yaml"),
+            "should contain yaml download link"
         );
         assert!(
-            rendered.contains("led-brainstormer"),
-            "should contain brainstormer LED"
+            rendered.contains("/web/specs/01HTEST/export/dot"),
+            "should contain dot download link"
         );
         assert!(
-            rendered.contains("led-planner"),
-            "should contain planner LED"
+            rendered.contains("download=\"test-spec.md\""),
+            "should have slugged .md download attribute"
         );
-    }
-
-    #[test]
-    fn agent_leds_template_renders_paused() {
-        let tmpl = AgentLedsTemplate {
-            spec_id: "01HTEST".to_string(),
-            running: false,
-            started: true,
-        };
-        let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("led-paused"),
-            "should contain paused LED class"
+            rendered.contains("download=\"test-spec.yaml\""),
+            "should have slugged .yaml download attribute"
         );
         assert!(
-            !rendered.contains("led-active"),
-            "should not contain active LED class"
+            rendered.contains("download=\"test-spec.dot\""),
+            "should have slugged .dot download attribute"
+        );
+        assert!(
+            rendered.contains("/web/specs/01HTEST/export/mermaid"),
+            "should contain mermaid download link"
+        );
+        assert!(
+            rendered.contains("download=\"test-spec.mmd\""),
+            "should have slugged .mmd download attribute"
         );
     }
 
     #[test]
-    fn agent_leds_template_renders_stopped() {
-        let tmpl = AgentLedsTemplate {
+    fn artifacts_template_contains_copy_buttons() {
+        let tmpl = ArtifactsTemplate {
             spec_id: "01HTEST".to_string(),
-            running: false,
-            started: false,
+            title_slug: "test".to_string(),
+            markdown_content: "# Test".to_string(),
+            yaml_content: "title: Test".to_string(),
+            dot_content: "digraph {}".to_string(),
+            mermaid_content: "flowchart TD".to_string(),
+            json_content: "{}".to_string(),
         };
         let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("led-off"), "should contain off LED class");
-        assert!(
-            !rendered.contains("led-active"),
-            "should not contain active LED class"
-        );
-        assert!(
-            !rendered.contains("led-paused"),
-            "should not contain paused LED class"
+        // Count actual copy button elements by matching the class attribute on button tags,
+        // not bare "btn-copy" which also matches JS selector references.
+        let copy_count = rendered.matches("class=\"btn btn-sm btn-copy\"").count();
+        assert_eq!(
+            copy_count, 5,
+            "should have exactly 5 copy buttons, found {}",
+            copy_count
         );
     }
 
     #[tokio::test]
-    async fn post_chat_sends_message() {
+    async fn artifacts_handler_returns_200() {
         let state = test_state();
 
-        // First create a spec
+        // Create a spec first
         let app = create_router(Arc::clone(&state), None);
         let resp = app
             .oneshot(
                 Request::post("/web/specs")
                     .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build a chat testing system"))
+                    .body(mp_description_body("Build an artifacts testing system"))
                     .unwrap(),
             )
             .await
             .unwrap();
         assert_eq!(resp.status(), 200);
 
-        // Get the spec_id from the actors
         let spec_id = {
             let actors = state.actors.read().await;
             *actors.keys().next().expect("should have a spec")
         };
 
-        // Post a chat message
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::get(format!("/web/specs/{}/artifacts", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            html.contains("artifacts-panel"),
+            "artifacts response should contain artifacts-panel: {}",
+            html
+        );
+    }
+
+    #[tokio::test]
+    async fn artifacts_for_nonexistent_spec_returns_404() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let fake_id = ulid::Ulid::new();
+        let resp = app
+            .oneshot(
+                Request::get(format!("/web/specs/{}/artifacts", fake_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 404);
+    }
+
+    // ---- Spec tab tests ----
+
+    #[tokio::test]
+    async fn spec_handler_returns_200() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
             .oneshot(
-                Request::post(format!("/web/specs/{}/chat", spec_id))
-                    .header("content-type", "application/x-www-form-urlencoded")
-                    .body(Body::from("message=Hello+from+chat"))
+                Request::get(format!("/web/specs/{}/spec", spec_id))
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
@@ -5416,27 +10033,21 @@ mod tests {
             .unwrap();
         let html = String::from_utf8(body.to_vec()).unwrap();
         assert!(
-            html.contains("Hello from chat"),
-            "chat message should appear in activity: {}",
-            html
-        );
-        assert!(
-            html.contains("human"),
-            "sender should be 'human' in activity: {}",
+            html.contains("spec-document"),
+            "spec response should contain spec-document class: {}",
             html
         );
     }
 
     #[tokio::test]
-    async fn post_chat_to_nonexistent_spec_returns_404() {
+    async fn spec_for_nonexistent_spec_returns_404() {
         let state = test_state();
         let app = create_router(state, None);
         let fake_id = ulid::Ulid::new();
         let resp = app
             .oneshot(
-                Request::post(format!("/web/specs/{}/chat", fake_id))
-                    .header("content-type", "application/x-www-form-urlencoded")
-                    .body(Body::from("message=Hello"))
+                Request::get(format!("/web/specs/{}/spec", fake_id))
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
@@ -5445,960 +10056,1379 @@ mod tests {
     }
 
     #[test]
-    fn agent_status_template_renders_stopped() {
-        let tmpl = AgentStatusTemplate {
+    fn spec_template_renders_with_content() {
+        let tmpl = SpecTabTemplate {
             spec_id: "01HTEST".to_string(),
-            running: false,
-            started: false,
-            agent_count: 0,
+            title_slug: "test".to_string(),
+            spec_html: "<h1>Test</h1>".to_string(),
+            spec_markdown: "# Test".to_string(),
         };
         let rendered = tmpl.render().unwrap();
         assert!(
-            rendered.contains("agent-status"),
-            "should contain agent-status id"
-        );
-        assert!(
-            rendered.contains("agent-pill-stopped"),
-            "should have stopped pill class"
-        );
-        assert!(
-            rendered.contains("Start agents"),
-            "should show start agents text"
+            rendered.contains("spec-document"),
+            "should contain spec-document class"
         );
         assert!(
-            rendered.contains("/agents/start"),
-            "should have start action URL"
+            rendered.contains("spec-copy-md"),
+            "should contain copy markdown button"
         );
     }
 
     #[test]
-    fn agent_status_template_renders_running() {
-        let tmpl = AgentStatusTemplate {
+    fn spec_template_renders_empty_state() {
+        let tmpl = SpecTabTemplate {
             spec_id: "01HTEST".to_string(),
-            running: true,
-            started: true,
-            agent_count: 4,
+            title_slug: "test".to_string(),
+            spec_html: String::new(),
+            spec_markdown: String::new(),
         };
         let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("agent-pill-running"),
-            "should have running pill class"
-        );
-        assert!(
-            rendered.contains("Agents active"),
-            "should show active state"
-        );
-        assert!(
-            rendered.contains("/agents/pause"),
-            "should have pause action URL"
-        );
+        assert!(rendered.contains("spec-document"));
     }
 
-    #[test]
-    fn agent_status_template_renders_paused_as_stopped() {
-        let tmpl = AgentStatusTemplate {
-            spec_id: "01HTEST".to_string(),
-            running: false,
-            started: true,
-            agent_count: 4,
+    // ---- Export download tests ----
+
+    /// Helper to create a spec and return its ULID.
+    async fn create_test_spec(state: &SharedState) -> ulid::Ulid {
+        let app = create_router(Arc::clone(state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Build an export testing system"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let actors = state.actors.read().await;
+        *actors.keys().next().expect("should have a spec")
+    }
+
+    /// Create a task card titled `title` in the Plan lane of `spec_id`.
+    async fn create_plain_card(state: &SharedState, spec_id: Ulid, title: &str) -> Ulid {
+        let actors = state.actors.read().await;
+        let events = actors
+            .get(&spec_id)
+            .unwrap()
+            .send_command(Command::CreateCard {
+                card_type: "task".to_string(),
+                title: title.to_string(),
+                body: None,
+                lane: Some("Plan".to_string()),
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+        match &events[0].payload {
+            barnstormer_core::EventPayload::CardCreated { card } => card.card_id,
+            _ => panic!("wrong event"),
+        }
+    }
+
+    /// Create a card titled `title` in `spec_id` due `days_from_now` days out.
+    async fn create_due_card(
+        state: &SharedState,
+        spec_id: Ulid,
+        title: &str,
+        days_from_now: i64,
+    ) -> Ulid {
+        let card_id = create_plain_card(state, spec_id, title).await;
+        let actors = state.actors.read().await;
+        actors
+            .get(&spec_id)
+            .unwrap()
+            .send_command(Command::SetCardDueDate {
+                card_id,
+                due_date: Some(Utc::now() + chrono::Duration::days(days_from_now)),
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap();
+        card_id
+    }
+
+    async fn get_board(state: &SharedState, uri: String) -> String {
+        let resp = create_router(Arc::clone(state), None)
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        body_text(resp).await
+    }
+
+    async fn put_lanes(state: &SharedState, spec_id: Ulid, form: &str) -> Response {
+        create_router(Arc::clone(state), None)
+            .oneshot(
+                Request::put(format!("/web/specs/{}/lanes", spec_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from(form.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn put_move(state: &SharedState, spec_id: Ulid, card_id: Ulid, form: &str) -> Response {
+        create_router(Arc::clone(state), None)
+            .oneshot(
+                Request::put(format!("/web/specs/{}/cards/{}/move", spec_id, card_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from(form.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn archived_cards_leave_the_board_and_exports_until_unarchived() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Parked idea", 3).await;
+        let post = |path: String| {
+            create_router(Arc::clone(&state), None)
+                .oneshot(Request::post(path).body(Body::empty()).unwrap())
         };
-        let rendered = tmpl.render().unwrap();
+
+        let resp = post(format!("/web/specs/{}/cards/{}/archive", spec_id, card_id))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(!body_text(resp).await.contains("Parked idea"));
+        let board = get_board(&state, format!("/web/specs/{}/board", spec_id)).await;
+        assert!(!board.contains("Parked idea"));
+
+        let archive = get_board(&state, format!("/web/specs/{}/archive", spec_id)).await;
+        assert!(archive.contains("Parked idea"));
+        assert!(archive.contains(&format!("/cards/{}/unarchive", card_id)));
+
+        let export_uri = format!("/web/specs/{}/export/markdown", spec_id);
         assert!(
-            rendered.contains("agent-pill-stopped"),
-            "paused should render as stopped pill"
+            !get_board(&state, export_uri.clone())
+                .await
+                .contains("Parked idea")
         );
-        assert!(
-            rendered.contains("Start agents"),
-            "paused should show start agents text"
+        let with_archived =
+            get_board(&state, format!("{}?include_archived=true", export_uri)).await;
+        assert!(with_archived.contains("Parked idea"));
+
+        let resp = post(format!(
+            "/web/specs/{}/cards/{}/unarchive",
+            spec_id, card_id
+        ))
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(!body_text(resp).await.contains("Parked idea"));
+        let board = get_board(&state, format!("/web/specs/{}/board", spec_id)).await;
+        assert!(board.contains("Parked idea"));
+
+        let resp = post(format!(
+            "/web/specs/{}/cards/{}/archive",
+            spec_id,
+            Ulid::new()
+        ))
+        .await
+        .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn move_card_places_card_at_index_and_returns_board() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let first = create_due_card(&state, spec_id, "First task", 3).await;
+        let second = create_due_card(&state, spec_id, "Second task", 3).await;
+        let third = create_due_card(&state, spec_id, "Third task", 3).await;
+
+        let resp = put_move(&state, spec_id, third, "lane=Plan&index=0").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = body_text(resp).await;
+        assert!(body.contains("id=\"board\""));
+        let third_at = body.find("Third task").unwrap();
+        assert!(third_at < body.find("First task").unwrap(), "{body}");
+        assert!(third_at < body.find("Second task").unwrap(), "{body}");
+
+        let resp = put_move(&state, spec_id, first, "lane=Spec&index=0").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let actors = state.actors.read().await;
+        let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+        assert_eq!(spec_state.cards[&first].lane, "Spec");
+        assert_eq!(spec_state.cards[&second].lane, "Plan");
+    }
+
+    #[tokio::test]
+    async fn move_card_inserts_between_neighbours_or_at_an_explicit_order() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let first = create_plain_card(&state, spec_id, "First task").await;
+        let second = create_plain_card(&state, spec_id, "Second task").await;
+        let third = create_plain_card(&state, spec_id, "Third task").await;
+        let moved = create_plain_card(&state, spec_id, "Moved task").await;
+        for (card_id, order) in [(first, 1.0), (second, 2.0), (third, 3.0)] {
+            let resp = put_move(
+                &state,
+                spec_id,
+                card_id,
+                &format!("lane=Plan&order={order}"),
+            )
+            .await;
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+        async fn order_of(state: &SharedState, spec_id: Ulid, card_id: Ulid) -> (String, f64) {
+            let actors = state.actors.read().await;
+            let spec_state = actors[&spec_id].read_state().await;
+            let card = &spec_state.cards[&card_id];
+            (card.lane.clone(), card.order)
+        }
+
+        // Dropped between the first two cards: the midpoint, nothing renumbered.
+        let resp = put_move(&state, spec_id, moved, "lane=Plan&index=1").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            order_of(&state, spec_id, moved).await,
+            ("Plan".to_string(), 1.5)
         );
-        assert!(
-            rendered.contains("/agents/resume"),
-            "paused should resume on click"
+        assert_eq!(order_of(&state, spec_id, second).await.1, 2.0);
+
+        // An explicit order is taken as is, in any lane.
+        let resp = put_move(&state, spec_id, moved, "lane=Spec&order=-4.25").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            order_of(&state, spec_id, moved).await,
+            ("Spec".to_string(), -4.25)
         );
-        assert!(
-            !rendered.contains("agent-pill-paused"),
-            "should not have separate paused state"
+
+        for form in [
+            "lane=Plan",
+            "lane=Plan&order=NaN",
+            "lane=+&order=1",
+            "lane=+&index=0",
+        ] {
+            let resp = put_move(&state, spec_id, moved, form).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{form}");
+        }
+
+        // A filtered board hiding the first card shows the second at index 0,
+        // so a drop after it must go by the named neighbour, not the index.
+        let form = format!("lane=Plan&index=1&after={second}");
+        let resp = put_move(&state, spec_id, moved, &form).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            order_of(&state, spec_id, moved).await,
+            ("Plan".to_string(), 2.5)
+        );
+        // An empty `after` is the top of the lane; the lane name is trimmed.
+        let resp = put_move(&state, spec_id, moved, "lane=+Plan+&index=2&after=").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            order_of(&state, spec_id, moved).await,
+            ("Plan".to_string(), 0.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn move_card_rejects_unknown_card_and_spec() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Ship it", 3).await;
+
+        let resp = put_move(&state, spec_id, Ulid::new(), "lane=Plan&index=0").await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp = put_move(&state, Ulid::new(), card_id, "lane=Plan&index=0").await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn lanes_can_be_created_renamed_and_reordered() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Ship it", 3).await;
+
+        let resp = put_lanes(&state, spec_id, "action=create&name=Review").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains(r#"data-lane="Review""#), "{}", html);
+
+        let resp = put_lanes(&state, spec_id, "action=rename&from=Plan&to=Doing").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let resp = put_lanes(
+            &state,
+            spec_id,
+            "action=reorder&order=Review%2CDoing%2CIdeas%2CSpec",
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        let positions: Vec<usize> = ["Review", "Doing", "Ideas", "Spec"]
+            .iter()
+            .map(|l| html.find(&format!(r#"data-lane="{}""#, l)).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", html);
+
+        let actors = state.actors.read().await;
+        let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+        assert_eq!(spec_state.lanes, vec!["Review", "Doing", "Ideas", "Spec"]);
+        assert_eq!(spec_state.cards[&card_id].lane, "Doing");
+    }
+
+    #[tokio::test]
+    async fn deleting_a_lane_needs_a_target_when_it_holds_cards() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Keep me", 3).await;
+
+        let resp = put_lanes(&state, spec_id, "action=delete&name=Plan").await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert!(body_text(resp).await.contains("still holds cards"));
+
+        let resp = put_lanes(&state, spec_id, "action=delete&name=Plan&to=Spec").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(!html.contains(r#"data-lane="Plan""#), "{}", html);
+
+        // Empty lanes offer a delete button; "Spec" now holds the card.
+        assert!(html.contains(r#"<input type="hidden" name="name" value="Ideas">"#));
+        assert!(!html.contains(r#"<input type="hidden" name="name" value="Spec">"#));
+
+        let resp = put_lanes(&state, spec_id, "action=delete&name=Ideas").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let actors = state.actors.read().await;
+        let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+        assert_eq!(spec_state.lanes, vec!["Spec"]);
+        assert_eq!(spec_state.cards[&card_id].lane, "Spec");
+    }
+
+    async fn post_empty(state: &SharedState, uri: String) -> Response {
+        create_router(Arc::clone(state), None)
+            .oneshot(Request::post(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn redo_restores_an_undone_card_and_is_a_noop_when_empty() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let resp = post_empty(&state, format!("/web/specs/{}/redo", spec_id)).await;
+        assert_eq!(
+            resp.status(),
+            StatusCode::OK,
+            "empty redo returns the board"
         );
+        assert!(body_text(resp).await.contains(r#"id="board""#));
+
+        create_due_card(&state, spec_id, "Bring me back", 1).await;
+        // Undo the due date, then the card itself.
+        for _ in 0..2 {
+            let resp = post_empty(&state, format!("/web/specs/{}/undo", spec_id)).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+        let html = get_board(&state, format!("/web/specs/{}/board", spec_id)).await;
+        assert!(!html.contains("Bring me back"));
+
+        let resp = post_empty(&state, format!("/web/specs/{}/redo", spec_id)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains("Bring me back"), "{}", html);
     }
 
     #[tokio::test]
-    async fn get_agent_status_returns_stopped_when_no_swarm() {
+    async fn invalid_lane_updates_return_400() {
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
 
-        // Create a spec first
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
-            .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build an agent testing system"))
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 200);
+        for form in [
+            "action=create&name=Plan",
+            "action=rename&from=Nope&to=Doing",
+            "action=reorder&order=Spec",
+            "action=explode",
+        ] {
+            let resp = put_lanes(&state, spec_id, form).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", form);
+        }
+    }
 
-        let spec_id = {
-            let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
-        };
+    #[tokio::test]
+    async fn overdue_card_is_flagged_on_the_board() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        create_due_card(&state, spec_id, "Late launch", -2).await;
+        create_due_card(&state, spec_id, "Future launch", 5).await;
 
-        // Get agent status (no swarm started)
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
-            .oneshot(
-                Request::get(format!("/web/specs/{}/agents/status", spec_id))
-                    .body(Body::empty())
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 200);
+        let html = get_board(&state, format!("/web/specs/{}/board", spec_id)).await;
+        assert_eq!(html.matches("is-overdue").count(), 1, "{}", html);
+        assert!(html.contains("Overdue &middot; Due"));
+        assert!(html.contains("Late launch"));
+        assert!(html.contains("Future launch"));
+    }
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains("Start agents"),
-            "should show stopped pill when no swarm: {}",
-            html
-        );
+    #[tokio::test]
+    async fn overdue_filter_shows_only_overdue_cards() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        create_due_card(&state, spec_id, "Late launch", -2).await;
+        create_due_card(&state, spec_id, "Future launch", 5).await;
+        let finished = create_due_card(&state, spec_id, "Shipped late", -3).await;
+        {
+            let actors = state.actors.read().await;
+            actors
+                .get(&spec_id)
+                .unwrap()
+                .send_command(Command::SetCardDone {
+                    card_id: finished,
+                    done: true,
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let html = get_board(&state, format!("/web/specs/{}/board?overdue=true", spec_id)).await;
+        assert!(html.contains("Late launch"), "{}", html);
+        assert!(!html.contains("Future launch"));
+        assert!(!html.contains("Shipped late"));
     }
 
     #[tokio::test]
-    async fn pause_agents_returns_stopped_when_no_swarm() {
+    async fn tag_filter_shows_only_cards_with_that_tag() {
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let tagged = create_due_card(&state, spec_id, "Wire the API", 5).await;
+        create_due_card(&state, spec_id, "Pick colours", 5).await;
 
-        // Create a spec
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build a pause testing system"))
+                Request::put(format!("/web/specs/{}/cards/{}", spec_id, tagged))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from(
+                        "title=Wire+the+API&card_type=task&body=&tags=Backend%2C+api%2C+",
+                    ))
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
-
-        let spec_id = {
+        assert_eq!(resp.status(), StatusCode::OK);
+        {
             let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
-        };
+            let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+            assert_eq!(spec_state.cards[&tagged].tags, vec!["backend", "api"]);
+        }
 
-        // Pause without starting returns stopped state
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
-            .oneshot(
-                Request::post(format!("/web/specs/{}/agents/pause", spec_id))
-                    .body(Body::empty())
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 200);
+        let html = get_board(&state, format!("/web/specs/{}/board?tag=BACKEND", spec_id)).await;
+        assert!(html.contains("Wire the API"), "{}", html);
+        assert!(html.contains("<li class=\"card-tag\">api</li>"));
+        assert!(!html.contains("Pick colours"));
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains("Start agents"),
-            "pause with no swarm should show stopped pill: {}",
-            html
-        );
+        let html = get_board(&state, format!("/web/specs/{}/board", spec_id)).await;
+        assert!(html.contains("Pick colours"));
     }
 
     #[tokio::test]
-    async fn resume_agents_returns_stopped_when_no_swarm() {
+    async fn flagged_card_shows_review_banner_until_dismissed() {
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Cache everything", 5).await;
+        {
+            let actors = state.actors.read().await;
+            actors
+                .get(&spec_id)
+                .unwrap()
+                .send_command(Command::RequestCardReview {
+                    card_id,
+                    reason: "Conflicts with the freshness constraint".to_string(),
+                    requested_by: "critic-1".to_string(),
+                })
+                .await
+                .unwrap();
+        }
 
-        // Create a spec
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
+        let html = get_board(&state, format!("/web/specs/{}/board", spec_id)).await;
+        assert!(html.contains("needs-review"), "{}", html);
+        assert!(html.contains("Conflicts with the freshness constraint"));
+        assert!(html.contains("1 to review"));
+
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build a resume testing system"))
+                Request::post(format!("/web/specs/{}/cards/{}/review", spec_id, card_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("approved=false"))
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
-
-        let spec_id = {
-            let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
-        };
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(!html.contains("card-review"), "{}", html);
 
-        // Resume without starting returns stopped state
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
+        // A second resolution has nothing to close.
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::post(format!("/web/specs/{}/agents/resume", spec_id))
-                    .body(Body::empty())
+                Request::post(format!("/web/specs/{}/cards/{}/review", spec_id, card_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("approved=true"))
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
-
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains("Start agents"),
-            "resume with no swarm should show stopped pill: {}",
-            html
-        );
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
-    async fn agent_status_for_nonexistent_spec_returns_stopped() {
+    async fn delete_spec_stops_the_spec_and_moves_it_to_the_trash() {
         let state = test_state();
-        let app = create_router(state, None);
-        let fake_id = ulid::Ulid::new();
-
-        let resp = app
-            .oneshot(
-                Request::get(format!("/web/specs/{}/agents/status", fake_id))
+        let spec_id = create_test_spec(&state).await;
+        let spec_dir = state
+            .barnstormer_home
+            .join("specs")
+            .join(spec_id.to_string());
+        assert!(spec_dir.join("events.jsonl").exists());
+
+        let delete = |state: &SharedState| {
+            create_router(Arc::clone(state), None).oneshot(
+                Request::delete(format!("/web/specs/{}", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
-            .await
-            .unwrap();
+        };
+        let resp = delete(&state).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(body_text(resp).await.contains("No specs yet"));
 
-        assert_eq!(resp.status(), 200);
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!spec_dir.exists());
         assert!(
-            html.contains("Start agents"),
-            "nonexistent spec should show stopped pill: {}",
-            html
+            state
+                .barnstormer_home
+                .join("trash")
+                .join(spec_id.to_string())
+                .join("events.jsonl")
+                .exists()
         );
-    }
-
-    #[test]
-    fn provider_status_template_renders_no_providers() {
-        let tmpl = ProviderStatusTemplate {
-            default_provider: "anthropic".to_string(),
-            default_model: None,
-            providers: vec![
-                ProviderInfoView {
-                    name: "anthropic".to_string(),
-                    has_api_key: false,
-                    model: "claude-sonnet-4-5-20250929".to_string(),
-                },
-                ProviderInfoView {
-                    name: "openai".to_string(),
-                    has_api_key: false,
-                    model: "gpt-4o".to_string(),
-                },
-                ProviderInfoView {
-                    name: "gemini".to_string(),
-                    has_api_key: false,
-                    model: "gemini-2.0-flash".to_string(),
-                },
-            ],
-            any_available: false,
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("No provider configured"));
-        assert!(rendered.contains("disconnected"));
-    }
+        assert!(state.actors.read().await.is_empty());
+        assert!(state.event_persisters.read().await.is_empty());
+        assert!(state.swarms.read().await.is_empty());
 
-    #[test]
-    fn provider_status_template_renders_with_provider() {
-        let tmpl = ProviderStatusTemplate {
-            default_provider: "anthropic".to_string(),
-            default_model: Some("claude-sonnet-4-5-20250929".to_string()),
-            providers: vec![
-                ProviderInfoView {
-                    name: "anthropic".to_string(),
-                    has_api_key: true,
-                    model: "claude-sonnet-4-5-20250929".to_string(),
-                },
-                ProviderInfoView {
-                    name: "openai".to_string(),
-                    has_api_key: false,
-                    model: "gpt-4o".to_string(),
-                },
-            ],
-            any_available: true,
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("anthropic"));
-        assert!(rendered.contains("connected"));
-        assert!(rendered.contains("claude-sonnet-4-5-20250929"));
+        let resp = delete(&state).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn get_provider_status_returns_html() {
+    async fn cold_storage_unloads_the_spec_and_restore_brings_it_back() {
         let state = test_state();
-        let app = create_router(state, None);
-        let resp = app
-            .oneshot(
-                Request::get("/web/provider-status")
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Survives cold storage", 5).await;
+        let spec_dir = state
+            .barnstormer_home
+            .join("specs")
+            .join(spec_id.to_string());
+        let cold_dir = state
+            .barnstormer_home
+            .join("specs")
+            .join("cold")
+            .join(spec_id.to_string());
+
+        let post = |state: &SharedState, action: &str| {
+            create_router(Arc::clone(state), None).oneshot(
+                Request::post(format!("/web/specs/{}/{}", spec_id, action))
                     .body(Body::empty())
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 200);
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(html.contains("provider-status"));
-    }
-
-    /// Create a test state that explicitly has no LLM providers configured,
-    /// regardless of the actual environment variables on the machine.
-    fn test_state_no_provider() -> SharedState {
-        let dir = tempfile::TempDir::new().unwrap();
-        let provider_status = ProviderStatus {
-            default_provider: "anthropic".to_string(),
-            default_model: None,
-            providers: vec![],
-            any_available: false,
-        };
-        Arc::new(AppState::new(dir.keep(), provider_status))
-    }
-
-    #[tokio::test]
-    async fn create_spec_with_no_provider_does_not_start_agents() {
-        let state = test_state_no_provider();
-        let app = create_router(Arc::clone(&state), None);
-
-        let resp = app
-            .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build a system without agents"))
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 200);
+                    .unwrap(),
+            )
+        };
+        let resp = post(&state, "cold-storage").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains("Cold storage"), "{}", html);
+        assert!(html.contains(&format!("/web/specs/{}/restore", spec_id)));
+        assert!(!spec_dir.exists());
+        assert!(cold_dir.join("events.jsonl").exists());
+        assert!(state.actors.read().await.is_empty());
+        assert!(state.event_persisters.read().await.is_empty());
+        assert_eq!(
+            post(&state, "cold-storage").await.unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
 
-        // Since provider_status.any_available is false, try_start_agents should
-        // return early and no swarm should be created.
-        let swarms = state.swarms.read().await;
-        assert!(
-            swarms.is_empty(),
-            "no swarm should be created without provider"
+        let resp = post(&state, "restore").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(!body_text(resp).await.contains("/restore"));
+        assert!(spec_dir.join("events.jsonl").exists());
+        assert!(!cold_dir.exists());
+        {
+            let actors = state.actors.read().await;
+            let spec_state = actors[&spec_id].read_state().await;
+            assert!(spec_state.cards.contains_key(&card_id));
+        }
+        assert!(state.event_persisters.read().await.contains_key(&spec_id));
+        assert_eq!(
+            post(&state, "restore").await.unwrap().status(),
+            StatusCode::CONFLICT
         );
     }
 
     #[tokio::test]
-    async fn start_agents_for_nonexistent_spec_returns_404() {
+    async fn cold_storage_keeps_the_spec_when_its_snapshot_fails() {
         let state = test_state();
-        let app = create_router(state, None);
-        let fake_id = ulid::Ulid::new();
-
-        let resp = app
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Stays warm", 5).await;
+        let spec_dir = state
+            .barnstormer_home
+            .join("specs")
+            .join(spec_id.to_string());
+        let snapshot_dir = spec_dir.join("snapshots");
+        let _ = std::fs::remove_dir_all(&snapshot_dir);
+        std::fs::write(&snapshot_dir, "not a directory").unwrap();
+
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::post(format!("/web/specs/{}/agents/start", fake_id))
+                Request::post(format!("/web/specs/{}/cold-storage", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-
-        assert_eq!(resp.status(), 404);
-    }
-
-    // ---- Chat panel tests ----
-
-    #[test]
-    fn chat_panel_template_renders() {
-        let tmpl = ChatPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
-
-            transcript: vec![],
-            pending_question: None,
-        };
-        let rendered = tmpl.render().unwrap();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(spec_dir.join("events.jsonl").exists());
         assert!(
-            rendered.contains("chat-panel"),
-            "should contain chat-panel div"
+            !state
+                .barnstormer_home
+                .join("specs")
+                .join("cold")
+                .join(spec_id.to_string())
+                .exists()
         );
+        {
+            let actors = state.actors.read().await;
+            let spec_state = actors[&spec_id].read_state().await;
+            assert!(spec_state.cards.contains_key(&card_id));
+        }
+        assert!(state.event_persisters.read().await.contains_key(&spec_id));
     }
 
-    #[test]
-    fn chat_panel_renders_with_transcript_entries() {
-        let tmpl = ChatPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
-
-            transcript: vec![
-                TranscriptEntry {
+    #[tokio::test]
+    async fn search_finds_cards_and_transcript_messages() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Offline synchronisation", 5).await;
+        {
+            let actors = state.actors.read().await;
+            actors
+                .get(&spec_id)
+                .unwrap()
+                .send_command(Command::AppendTranscript {
                     sender: "human".to_string(),
-                    sender_label: "You".to_string(),
-                    initial: "Y".to_string(),
-                    is_human: true,
-                    is_step: false,
-                    is_continuation: false,
-                    role_class: "human".to_string(),
-                    content: "Hello from human".to_string(),
-                    content_html: "<p>Hello from human</p>\n".to_string(),
-                    timestamp: "12:34:56".to_string(),
-                    repeat_count: 1,
-                },
-                TranscriptEntry {
-                    sender: "manager-01HAGENT".to_string(),
-                    sender_label: "Manager".to_string(),
-                    initial: "M".to_string(),
-                    is_human: false,
-                    is_step: false,
-                    is_continuation: false,
-                    role_class: "manager".to_string(),
-                    content: "Agent response here".to_string(),
-                    content_html: "<p>Agent response here</p>\n".to_string(),
-                    timestamp: "12:35:00".to_string(),
-                    repeat_count: 1,
-                },
-            ],
-            pending_question: None,
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("Hello from human"),
-            "should contain human message content"
-        );
-        assert!(
-            rendered.contains("Agent response here"),
-            "should contain agent message content"
-        );
-        assert!(
-            rendered.contains("chat-message"),
-            "should have chat-message class"
-        );
-        assert!(
-            rendered.contains("chat-avatar"),
-            "should have avatar element"
-        );
-        assert!(
-            rendered.contains("chat-sender"),
-            "should have sender label element"
-        );
-        assert!(
-            !rendered.contains("No messages yet"),
-            "should not show empty state when entries exist"
-        );
-    }
-
-    #[test]
-    fn chat_panel_renders_empty_transcript() {
-        let tmpl = ChatPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
+                    content: "Sync should survive flaky networks".to_string(),
+                    display_name: None,
+                })
+                .await
+                .unwrap();
+        }
 
-            transcript: vec![],
-            pending_question: None,
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("No messages yet"),
-            "should show empty state message"
-        );
-        assert!(
-            rendered.contains("chat-empty"),
-            "should have chat-empty class"
+        // The persister indexes events in the background.
+        let uri = format!("/api/specs/{}/search?q=sync", spec_id);
+        let mut hits = serde_json::Value::Null;
+        for _ in 0..100 {
+            let body = get_board(&state, uri.clone()).await;
+            hits = serde_json::from_str::<serde_json::Value>(&body).unwrap()["hits"].clone();
+            if hits.as_array().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let hits = hits.as_array().unwrap();
+        assert_eq!(hits.len(), 2, "{:?}", hits);
+        let card_hit = hits.iter().find(|h| h["kind"] == "card").unwrap();
+        assert_eq!(card_hit["card_id"], card_id.to_string());
+        assert_eq!(card_hit["lane"], "Plan");
+        let message_hit = hits.iter().find(|h| h["kind"] == "transcript").unwrap();
+        assert!(message_hit["offset"].as_u64().is_some());
+        assert!(
+            message_hit["snippet"]
+                .as_str()
+                .unwrap()
+                .contains("flaky networks")
         );
+
+        let html = get_board(&state, format!("/web/specs/{}/search?q=offline", spec_id)).await;
         assert!(
-            rendered.contains("Type below to start a conversation"),
-            "should show hint text"
+            html.contains(&format!("data-card-id=\"{}\"", card_id)),
+            "{}",
+            html
         );
+        assert!(!html.contains("data-message-id"));
+
+        let html = get_board(&state, format!("/web/specs/{}/search?q=zebra", spec_id)).await;
+        assert!(html.contains("No matches"));
     }
 
-    #[test]
-    fn chat_panel_contains_chat_input_form() {
-        let tmpl = ChatPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
+    #[tokio::test]
+    async fn search_all_merges_hits_from_every_spec() {
+        let state = test_state();
+        let first = create_test_spec(&state).await;
+        create_test_spec(&state).await;
+        let second = *state
+            .actors
+            .read()
+            .await
+            .keys()
+            .find(|id| **id != first)
+            .unwrap();
+        let first_card = create_due_card(&state, first, "Offline synchronisation", 5).await;
+        let second_card = create_due_card(&state, second, "Sync conflicts", 5).await;
+        create_due_card(&state, second, "Billing", 5).await;
+
+        // The persisters index events in the background.
+        let mut hits = serde_json::Value::Null;
+        for _ in 0..100 {
+            let body = get_board(&state, "/api/search?q=sync".to_string()).await;
+            hits = serde_json::from_str::<serde_json::Value>(&body).unwrap()["hits"].clone();
+            if hits.as_array().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let hits = hits.as_array().unwrap();
+        assert_eq!(hits.len(), 2, "{:?}", hits);
+        for (spec_id, card_id) in [(first, first_card), (second, second_card)] {
+            let hit = hits
+                .iter()
+                .find(|h| h["card_id"] == card_id.to_string())
+                .unwrap();
+            assert_eq!(hit["spec_id"], spec_id.to_string());
+            assert_eq!(hit["kind"], "card");
+            assert!(hit["spec_title"].is_string());
+        }
 
-            transcript: vec![],
-            pending_question: None,
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("chat-input-area"),
-            "should contain chat-input-area div"
-        );
-        assert!(
-            rendered.contains("chat-input-row"),
-            "should contain chat-input-row div"
-        );
-        assert!(
-            rendered.contains(r#"hx-post="/web/specs/01HTEST/chat""#),
-            "should post to chat endpoint"
-        );
-        assert!(
-            rendered.contains(r##"hx-target="#chat-transcript""##),
-            "chat form should target chat-transcript"
-        );
+        let html = get_board(&state, "/web/search?q=conflicts".to_string()).await;
         assert!(
-            rendered.contains("Ask the agents anything"),
-            "should have placeholder text"
+            html.contains(&format!("href=\"/web/specs/{}\"", second)),
+            "{}",
+            html
         );
-    }
+        assert!(!html.contains(&first.to_string()), "{}", html);
 
-    #[test]
-    fn chat_panel_contains_transcript_and_input() {
-        let tmpl = ChatPanelTemplate {
-            spec_id: "01HTEST".to_string(),
-            container_id: "chat-transcript".to_string(),
+        let html = get_board(&state, "/web/search?q=zebra".to_string()).await;
+        assert!(html.contains("No matches"));
+        let html = get_board(&state, "/web/search?q=".to_string()).await;
+        assert!(!html.contains("search-hit") && !html.contains("No matches"));
+    }
 
-            transcript: vec![],
-            pending_question: None,
+    #[tokio::test]
+    async fn search_all_ranks_hits_against_their_own_spec() {
+        let state = test_state();
+        let first = create_test_spec(&state).await;
+        create_test_spec(&state).await;
+        let second = *state
+            .actors
+            .read()
+            .await
+            .keys()
+            .find(|id| **id != first)
+            .unwrap();
+        let add = |spec_id: Ulid, title: &'static str, body: &'static str| {
+            let state = Arc::clone(&state);
+            async move {
+                state.actors.read().await[&spec_id]
+                    .send_command(Command::CreateCard {
+                        card_type: "idea".to_string(),
+                        title: title.to_string(),
+                        body: Some(body.to_string()),
+                        lane: None,
+                        created_by: "human".to_string(),
+                        source_attachment_id: None,
+                    })
+                    .await
+                    .unwrap();
+            }
         };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("chat-panel"),
-            "should contain chat-panel wrapper"
-        );
-        assert!(
-            rendered.contains("sse:transcript_appended"),
-            "should listen for transcript_appended event"
-        );
-        assert!(
-            rendered.contains("chat-input-area"),
-            "should contain input area"
-        );
+        add(first, "Sync", "sync sync sync").await;
+        add(
+            first,
+            "Roadmap",
+            "a long list of themes for the quarter where sync shows up once",
+        )
+        .await;
+        add(
+            second,
+            "Exports",
+            "exports that also mention sync in passing",
+        )
+        .await;
+
+        let mut hits = serde_json::Value::Null;
+        for _ in 0..100 {
+            let body = get_board(&state, "/api/search?q=sync".to_string()).await;
+            hits = serde_json::from_str::<serde_json::Value>(&body).unwrap()["hits"].clone();
+            if hits.as_array().unwrap().len() == 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let titles: Vec<_> = hits
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["title"].as_str().unwrap().to_string())
+            .collect();
+        // Each spec's best hit outranks a weaker hit from another spec.
+        assert_eq!(titles.len(), 3, "{:?}", titles);
+        assert_eq!(titles[2], "Roadmap", "{:?}", titles);
     }
 
     #[tokio::test]
-    async fn chat_panel_handler_returns_200() {
+    async fn card_edit_form_sets_and_clears_due_date() {
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let card_id = create_due_card(&state, spec_id, "Plan review", 5).await;
 
-        // Create a spec first
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
-            .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build a chat panel testing system"))
-                    .unwrap(),
-            )
+        let put = |due: &str| {
+            Request::put(format!("/web/specs/{}/cards/{}", spec_id, card_id))
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(format!(
+                    "title=Plan+review&card_type=task&body=&due_date={}",
+                    due
+                )))
+                .unwrap()
+        };
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(put("2020-01-31"))
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains("Due 2020-01-31"), "{}", html);
+        assert!(html.contains("is-overdue"));
 
-        let spec_id = {
-            let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
-        };
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(put(""))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let actors = state.actors.read().await;
+        let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+        assert_eq!(spec_state.cards[&card_id].due_date, None);
+        drop(spec_state);
 
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
-            .oneshot(
-                Request::get(format!("/web/specs/{}/chat-panel", spec_id))
-                    .body(Body::empty())
-                    .unwrap(),
-            )
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(put("next+week"))
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    /// Hourly dollars from an agent cost estimate partial.
+    fn estimated_dollars(html: &str) -> f64 {
+        let start = html.find("≈ $").expect("estimate in partial") + "≈ $".len();
+        let end = start + html[start..].find('/').unwrap();
+        html[start..end].parse().unwrap()
+    }
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains("chat-panel"),
-            "chat panel response should contain chat-panel: {}",
-            html
+    fn test_swarm(
+        state: &SharedState,
+        spec_id: Ulid,
+        handle: &barnstormer_core::SpecActorHandle,
+        agents: usize,
+    ) -> crate::app_state::SwarmHandle {
+        use barnstormer_agent::AgentRunner;
+
+        let runners = (0..agents)
+            .map(|_| AgentRunner::new(spec_id, AgentRole::Planner))
+            .collect();
+        let swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle.clone(),
+            runners,
+            Arc::new(barnstormer_agent::testing::StubLlmClient::done()),
+            "house-model".to_string(),
+            state.barnstormer_home.clone(),
+            Arc::new(crate::attachment_summarizer::ServerSummarizer {
+                home: state.barnstormer_home.clone(),
+                provider: state.provider_status.default_provider.clone(),
+            }),
         );
+        crate::app_state::SwarmHandle {
+            swarm: Arc::new(tokio::sync::Mutex::new(swarm)),
+            task: tokio::spawn(async {}),
+        }
     }
 
     #[tokio::test]
-    async fn chat_panel_for_nonexistent_spec_returns_404() {
+    async fn single_agent_pause_survives_swarm_resume() {
         let state = test_state();
-        let app = create_router(state, None);
-        let fake_id = ulid::Ulid::new();
-        let resp = app
-            .oneshot(
-                Request::get(format!("/web/specs/{}/chat-panel", fake_id))
+        let spec_id = create_test_spec(&state).await;
+        let handle = state.actors.read().await[&spec_id].clone();
+        state
+            .swarms
+            .write()
+            .await
+            .insert(spec_id, test_swarm(&state, spec_id, &handle, 2));
+        let post = |path: &str| {
+            create_router(Arc::clone(&state), None).oneshot(
+                Request::post(format!("/web/specs/{}/agents/{}", spec_id, path))
                     .body(Body::empty())
                     .unwrap(),
             )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 404);
+        };
+        let planner_enabled = || async {
+            let swarms = state.swarms.read().await;
+            let swarm = swarms[&spec_id].swarm.lock().await;
+            swarm.is_role_enabled("planner")
+        };
+
+        let html = body_text(post("planner/pause").await.unwrap()).await;
+        assert!(html.contains("agent-toggle-off"), "{}", html);
+        assert_eq!(planner_enabled().await, Some(false));
+
+        // The swarm-wide switch does not undo a single agent's pause.
+        post("pause").await.unwrap();
+        post("resume").await.unwrap();
+        assert_eq!(planner_enabled().await, Some(false));
+
+        let html = body_text(post("planner/resume").await.unwrap()).await;
+        assert!(html.contains("agent-toggle-on"), "{}", html);
+        assert_eq!(planner_enabled().await, Some(true));
+
+        let missing = post("critic/pause").await.unwrap();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
     }
 
     #[tokio::test]
-    async fn chat_panel_brainstorming_targets_brainstorm_chat() {
-        let state = test_state();
-        let app = create_router(Arc::clone(&state), None);
-        app.oneshot(
-            Request::post("/web/specs")
-                .header("content-type", MP_CONTENT_TYPE)
-                .body(mp_description_body("Chat brainstorm test"))
-                .unwrap(),
-        )
-        .await
-        .unwrap();
-
-        let spec_id = {
-            let actors = state.actors.read().await;
-            *actors.keys().next().unwrap()
+    async fn agent_cost_estimate_uses_price_table_and_scales_with_agents_and_context() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let provider_status = ProviderStatus {
+            default_provider: "openai".to_string(),
+            default_model: Some("house-model".to_string()),
+            providers: vec![],
+            any_available: false,
+            model_warning: None,
         };
+        let mut app_state = AppState::new(dir.keep(), provider_status);
+        let unpriced = Arc::new(AppState::new(
+            app_state.barnstormer_home.clone(),
+            app_state.provider_status.clone(),
+        ));
+        app_state.model_prices =
+            barnstormer_agent::cost::PriceTable::with_overrides("house-model=1000/0").unwrap();
+        let state = Arc::new(app_state);
+        let spec_id = create_test_spec(&state).await;
+        let handle = state.actors.read().await[&spec_id].clone();
+        let uri = format!("/web/specs/{}/agents/cost-estimate", spec_id);
 
-        // Spec starts in Brainstorming
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
-            .oneshot(
-                Request::get(&format!("/web/specs/{}/chat-panel", spec_id))
-                    .body(Body::empty())
-                    .unwrap(),
-            )
+        // Before agents start, the estimate covers the default roles.
+        let idle = get_board(&state, uri.clone()).await;
+        assert!(idle.contains("/hour at current settings"), "{}", idle);
+        assert!(idle.contains("4 agents"), "{}", idle);
+
+        state
+            .swarms
+            .write()
             .await
-            .unwrap();
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .insert(spec_id, test_swarm(&state, spec_id, &handle, 1));
+        let one_agent = estimated_dollars(&get_board(&state, uri.clone()).await);
+        state
+            .swarms
+            .write()
+            .await
+            .insert(spec_id, test_swarm(&state, spec_id, &handle, 2));
+        let two_agents = estimated_dollars(&get_board(&state, uri.clone()).await);
+        assert!(one_agent > 0.0);
+        assert!(
+            (two_agents - one_agent * 2.0).abs() < 0.05,
+            "{one_agent} vs {two_agents}"
+        );
+
+        handle
+            .send_command(Command::AppendTranscript {
+                sender: "human".to_string(),
+                content: "x".repeat(40_000),
+                display_name: None,
+            })
             .await
             .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
+        let bigger_context = estimated_dollars(&get_board(&state, uri.clone()).await);
         assert!(
-            html.contains("brainstorm-chat"),
-            "should target brainstorm-chat container in brainstorming"
+            bigger_context > two_agents,
+            "{two_agents} vs {bigger_context}"
         );
+
+        // The built-in table has no price for the model, so no estimate.
+        let unpriced_id = create_test_spec(&unpriced).await;
+        let html = get_board(
+            &unpriced,
+            format!("/web/specs/{}/agents/cost-estimate", unpriced_id),
+        )
+        .await;
         assert!(
-            !html.contains("chat-fullwidth"),
-            "should not have chat-fullwidth class (removed)"
+            html.contains("Cost unknown: no price for house-model"),
+            "{}",
+            html
         );
     }
 
     #[tokio::test]
-    async fn chat_panel_refining_targets_chat_transcript() {
-        let state = test_state();
-        let app = create_router(Arc::clone(&state), None);
-        app.oneshot(
-            Request::post("/web/specs")
-                .header("content-type", MP_CONTENT_TYPE)
-                .body(mp_description_body("Chat refining test"))
-                .unwrap(),
-        )
-        .await
-        .unwrap();
+    async fn usage_partial_lists_tokens_per_role_with_cost() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut app_state = AppState::new(
+            dir.keep(),
+            ProviderStatus {
+                default_provider: "openai".to_string(),
+                default_model: None,
+                providers: vec![],
+                any_available: false,
+                model_warning: None,
+            },
+        );
+        app_state.model_prices =
+            barnstormer_agent::cost::PriceTable::with_overrides("house-model=10/20").unwrap();
+        let state = Arc::new(app_state);
+        let spec_id = create_test_spec(&state).await;
+        let handle = state.actors.read().await[&spec_id].clone();
+        let uri = format!("/web/specs/{}/usage", spec_id);
 
-        let spec_id = {
-            let actors = state.actors.read().await;
-            *actors.keys().next().unwrap()
-        };
+        let empty = get_board(&state, uri.clone()).await;
+        assert!(empty.contains("No tokens used yet"), "{}", empty);
+
+        for _ in 0..2 {
+            handle
+                .send_command(Command::RecordUsage {
+                    role: "planner".to_string(),
+                    model: "house-model".to_string(),
+                    input_tokens: 50_000,
+                    output_tokens: 25_000,
+                })
+                .await
+                .unwrap();
+        }
+        let html = get_board(&state, uri.clone()).await;
+        // 100k input at $10/M plus 50k output at $20/M.
+        assert!(html.contains("150000 tokens used, ≈ $2.00"), "{}", html);
+        assert!(html.contains("<td>100000</td>"), "{}", html);
+        assert!(html.contains("<td>50000</td>"), "{}", html);
+        assert!(html.contains("<td>$2.00</td>"), "{}", html);
 
-        // Transition to Refining
-        let actors = state.actors.read().await;
-        let handle = actors.get(&spec_id).unwrap();
         handle
-            .send_command(Command::TransitionPhase {
-                target: SpecPhase::Refining,
+            .send_command(Command::RecordUsage {
+                role: "critic".to_string(),
+                model: "mystery-model".to_string(),
+                input_tokens: 10,
+                output_tokens: 0,
             })
             .await
             .unwrap();
-        drop(actors);
+        let html = get_board(&state, uri).await;
+        assert!(html.contains("150010 tokens used</summary>"), "{}", html);
+        assert!(html.contains("<td>unknown</td>"), "{}", html);
+    }
 
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
-            .oneshot(
-                Request::get(&format!("/web/specs/{}/chat-panel", spec_id))
+    #[tokio::test]
+    async fn roster_checkboxes_choose_which_agents_start() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let state = Arc::new(AppState::new(
+            dir.keep(),
+            ProviderStatus {
+                default_provider: "ollama".to_string(),
+                default_model: None,
+                providers: vec![],
+                any_available: false,
+                model_warning: None,
+            },
+        ));
+        let spec_id = create_test_spec(&state).await;
+        let post = |path: &str| {
+            create_router(Arc::clone(&state), None).oneshot(
+                Request::post(format!("/web/specs/{}/agents/{}", spec_id, path))
                     .body(Body::empty())
                     .unwrap(),
             )
-            .await
-            .unwrap();
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains("chat-transcript"),
-            "should target chat-transcript container in refining"
-        );
-        assert!(
-            !html.contains("chat-fullwidth"),
-            "should not have chat-fullwidth class (removed)"
-        );
-    }
-
-    // ---- Artifacts tests ----
-
-    #[test]
-    fn artifacts_template_renders() {
-        let tmpl = ArtifactsTemplate {
-            spec_id: "01HTEST".to_string(),
-            title_slug: "my-spec".to_string(),
-            markdown_content: "# My Spec".to_string(),
-            yaml_content: "title: My Spec".to_string(),
-            dot_content: "digraph {}".to_string(),
         };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("artifacts-panel"),
-            "should contain artifacts-panel div"
-        );
-    }
 
-    #[test]
-    fn artifacts_template_contains_all_content_sections() {
-        let tmpl = ArtifactsTemplate {
-            spec_id: "01HTEST".to_string(),
-            title_slug: "my-spec".to_string(),
-            markdown_content: "# My Spec".to_string(),
-            yaml_content: "title: My Spec".to_string(),
-            dot_content: "digraph {}".to_string(),
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("id=\"markdown-source\""),
-            "should contain markdown-source section"
-        );
-        assert!(
-            rendered.contains("id=\"yaml-source\""),
-            "should contain yaml-source section"
-        );
-        assert!(
-            rendered.contains("id=\"dot-source\""),
-            "should contain dot-source section"
-        );
-        assert!(
-            rendered.contains("# My Spec"),
-            "should render markdown content"
-        );
+        let status = get_board(&state, format!("/web/specs/{}/agents/status", spec_id)).await;
         assert!(
-            rendered.contains("title: My Spec"),
-            "should render yaml content"
+            status.contains("/agents/roster/critic/toggle"),
+            "{}",
+            status
         );
-        assert!(rendered.contains("digraph {}"), "should render dot content");
-    }
 
-    #[test]
-    fn artifacts_template_contains_download_links() {
-        let tmpl = ArtifactsTemplate {
-            spec_id: "01HTEST".to_string(),
-            title_slug: "test".to_string(),
-            markdown_content: "# Test".to_string(),
-            yaml_content: "title: Test".to_string(),
-            dot_content: "digraph {}".to_string(),
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("/web/specs/01HTEST/export/markdown"),
-            "should contain markdown download link"
-        );
-        assert!(
-            rendered.contains("/web/specs/01HTEST/export/yaml"),
-            "should contain yaml download link"
-        );
-        assert!(
-            rendered.contains("/web/specs/01HTEST/export/dot"),
-            "should contain dot download link"
-        );
-        assert!(
-            rendered.contains("download=\"test-spec.md\""),
-            "should have slugged .md download attribute"
-        );
-        assert!(
-            rendered.contains("download=\"test-spec.yaml\""),
-            "should have slugged .yaml download attribute"
-        );
-        assert!(
-            rendered.contains("download=\"test-spec.dot\""),
-            "should have slugged .dot download attribute"
+        // Add the critic, then drop everyone but the manager and critic.
+        for role in ["critic", "brainstormer", "planner", "dot_generator"] {
+            let resp = post(&format!("roster/{}/toggle", role)).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+        let handle = state.actors.read().await[&spec_id].clone();
+        assert_eq!(
+            handle.read_state().await.agent_roster,
+            Some(vec!["manager".to_string(), "critic".to_string()])
         );
-    }
-
-    #[test]
-    fn artifacts_template_contains_copy_buttons() {
-        let tmpl = ArtifactsTemplate {
-            spec_id: "01HTEST".to_string(),
-            title_slug: "test".to_string(),
-            markdown_content: "# Test".to_string(),
-            yaml_content: "title: Test".to_string(),
-            dot_content: "digraph {}".to_string(),
-        };
-        let rendered = tmpl.render().unwrap();
-        // Count actual copy button elements by matching the class attribute on button tags,
-        // not bare "btn-copy" which also matches JS selector references.
-        let copy_count = rendered.matches("class=\"btn btn-sm btn-copy\"").count();
+        post("roster/manager/toggle").await.unwrap();
+        let last = post("roster/critic/toggle").await.unwrap();
+        assert_eq!(last.status(), StatusCode::BAD_REQUEST);
         assert_eq!(
-            copy_count, 3,
-            "should have exactly 3 copy buttons, found {}",
-            copy_count
+            post("roster/wizard/toggle").await.unwrap().status(),
+            StatusCode::NOT_FOUND
         );
+
+        let started = body_text(post("start").await.unwrap()).await;
+        assert!(!started.contains("/agents/roster/"), "{}", started);
+        let swarm_handle = state.swarms.write().await.remove(&spec_id).unwrap();
+        swarm_handle.task.abort();
+        let swarm = swarm_handle.swarm.lock().await;
+        let roles: Vec<AgentRole> = swarm.agents.iter().flatten().map(|r| r.role).collect();
+        assert_eq!(roles, vec![AgentRole::Critic]);
     }
 
     #[tokio::test]
-    async fn artifacts_handler_returns_200() {
+    async fn lag_snapshot_includes_running_swarm_agent_contexts() {
+        use barnstormer_agent::{AgentRole, AgentRunner};
+
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let handle = state.actors.read().await[&spec_id].clone();
+        let mut runner = AgentRunner::new(spec_id, AgentRole::Planner);
+        runner.context.add_decision("Ship weekly".to_string());
+        let swarm = SwarmOrchestrator::with_agents(
+            spec_id,
+            handle.clone(),
+            vec![runner],
+            Arc::new(barnstormer_agent::testing::StubLlmClient::done()),
+            "test-model".to_string(),
+            state.barnstormer_home.clone(),
+            Arc::new(crate::attachment_summarizer::ServerSummarizer {
+                home: state.barnstormer_home.clone(),
+                provider: state.provider_status.default_provider.clone(),
+            }),
+        );
+        state.swarms.write().await.insert(
+            spec_id,
+            crate::app_state::SwarmHandle {
+                swarm: Arc::new(tokio::sync::Mutex::new(swarm)),
+                task: tokio::spawn(async {}),
+            },
+        );
 
-        // Create a spec first
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
-            .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build an artifacts testing system"))
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 200);
+        let snapshot_dir = tempfile::TempDir::new().unwrap();
+        save_recovery_snapshot(
+            &handle,
+            &state.swarms,
+            spec_id,
+            snapshot_dir.path(),
+            state.snapshot_keep,
+        )
+        .await;
+
+        let snap = barnstormer_store::load_latest_snapshot(snapshot_dir.path())
+            .unwrap()
+            .expect("lag should write a snapshot");
+        assert_eq!(snap.agent_contexts.len(), 1);
+        let context = snap.agent_contexts.values().next().unwrap().to_string();
+        assert!(context.contains("Ship weekly"), "{}", context);
+    }
 
-        let spec_id = {
+    #[tokio::test]
+    async fn lint_view_lists_findings() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        {
             let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
-        };
+            actors
+                .get(&spec_id)
+                .unwrap()
+                .send_command(Command::CreateCard {
+                    card_type: "task".to_string(),
+                    title: "Bodiless".to_string(),
+                    body: None,
+                    lane: None,
+                    created_by: "human".to_string(),
+                    source_attachment_id: None,
+                })
+                .await
+                .unwrap();
+        }
 
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::get(format!("/web/specs/{}/artifacts", spec_id))
+                Request::get(format!("/web/specs/{}/lint", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
         assert_eq!(resp.status(), 200);
-
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains("artifacts-panel"),
-            "artifacts response should contain artifacts-panel: {}",
-            html
-        );
+        let body = body_text(resp).await;
+        assert!(body.contains("lint-empty_card_body"), "body: {body}");
+        assert!(body.contains("Bodiless"));
     }
 
     #[tokio::test]
-    async fn artifacts_for_nonexistent_spec_returns_404() {
+    async fn print_view_renders_full_page_without_htmx() {
         let state = test_state();
-        let app = create_router(state, None);
-        let fake_id = ulid::Ulid::new();
-        let resp = app
+        let spec_id = create_test_spec(&state).await;
+        {
+            let actors = state.actors.read().await;
+            let handle = actors.get(&spec_id).unwrap();
+            handle
+                .send_command(Command::UpdateSpecCore {
+                    title: Some("Printable Spec".to_string()),
+                    one_liner: None,
+                    goal: None,
+                    description: None,
+                    constraints: Some("Must work offline".to_string()),
+                    success_criteria: None,
+                    risks: Some("Paper jams".to_string()),
+                    notes: None,
+                })
+                .await
+                .unwrap();
+            for (card_type, title) in [("idea", "Toner budget"), ("decision", "Use A4 paper")] {
+                handle
+                    .send_command(Command::CreateCard {
+                        card_type: card_type.to_string(),
+                        title: title.to_string(),
+                        body: Some("Agreed in standup".to_string()),
+                        lane: None,
+                        created_by: "human".to_string(),
+                        source_attachment_id: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::get(format!("/web/specs/{}/artifacts", fake_id))
+                Request::get(format!("/web/specs/{}/print", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 404);
+        assert_eq!(resp.status(), 200);
+        let body = body_text(resp).await;
+
+        assert!(body.contains("<!DOCTYPE html>"));
+        assert!(body.contains("</html>"));
+        assert!(body.contains("<h1>Printable Spec</h1>"));
+        assert!(body.contains("<h2>Constraints</h2>"));
+        assert!(body.contains("Must work offline"));
+        assert!(body.contains("<h2>Risks</h2>"));
+        assert!(body.contains("Toner budget"));
+        assert!(body.contains("<h2>Decisions</h2>"));
+        assert!(body.contains("Use A4 paper"));
+        assert!(body.contains("@media print"));
+        for interactive in ["hx-get", "hx-post", "hx-ext", "sse-connect", "htmx.org"] {
+            assert!(
+                !body.contains(interactive),
+                "print view should not contain {interactive}"
+            );
+        }
     }
 
-    // ---- Spec tab tests ----
-
     #[tokio::test]
-    async fn spec_handler_returns_200() {
+    async fn embed_board_is_a_standalone_read_only_document() {
         let state = test_state();
         let spec_id = create_test_spec(&state).await;
+        {
+            let actors = state.actors.read().await;
+            actors
+                .get(&spec_id)
+                .unwrap()
+                .send_command(Command::CreateCard {
+                    card_type: "idea".to_string(),
+                    title: "Wiki-visible idea".to_string(),
+                    body: None,
+                    lane: None,
+                    created_by: "human".to_string(),
+                    source_attachment_id: None,
+                })
+                .await
+                .unwrap();
+        }
 
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::get(format!("/web/specs/{}/spec", spec_id))
+                Request::get(format!("/embed/specs/{}/board", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
         assert_eq!(resp.status(), 200);
-
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains("spec-document"),
-            "spec response should contain spec-document class: {}",
-            html
-        );
+        assert_eq!(
+            resp.headers()
+                .get("content-security-policy")
+                .and_then(|v| v.to_str().ok()),
+            Some("frame-ancestors 'self'")
+        );
+        let body = body_text(resp).await;
+
+        assert!(body.contains("<!DOCTYPE html>"));
+        assert!(body.contains("<head>"));
+        assert!(body.contains("</html>"));
+        assert!(body.contains("Wiki-visible idea"));
+        assert!(body.contains(r#"hx-trigger="every 30s""#));
+        for control in [
+            "hx-post",
+            "hx-put",
+            "hx-delete",
+            "<form",
+            "<button",
+            "contenteditable",
+            "/cards/new",
+            "board.js",
+            "<nav",
+        ] {
+            assert!(
+                !body.contains(control),
+                "embed view should not contain {control}"
+            );
+        }
     }
 
     #[tokio::test]
-    async fn spec_for_nonexistent_spec_returns_404() {
+    async fn export_markdown_returns_200_with_correct_headers() {
         let state = test_state();
-        let app = create_router(state, None);
-        let fake_id = ulid::Ulid::new();
+        let spec_id = create_test_spec(&state).await;
+
+        let app = create_router(Arc::clone(&state), None);
         let resp = app
             .oneshot(
-                Request::get(format!("/web/specs/{}/spec", fake_id))
+                Request::get(format!("/web/specs/{}/export/markdown", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 404);
-    }
 
-    #[test]
-    fn spec_template_renders_with_content() {
-        let tmpl = SpecTabTemplate {
-            spec_id: "01HTEST".to_string(),
-            title_slug: "test".to_string(),
-            spec_html: "<h1>Test</h1>".to_string(),
-            spec_markdown: "# Test".to_string(),
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(
-            rendered.contains("spec-document"),
-            "should contain spec-document class"
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "text/markdown; charset=utf-8"
         );
+        let disposition = resp
+            .headers()
+            .get("content-disposition")
+            .unwrap()
+            .to_str()
+            .unwrap();
         assert!(
-            rendered.contains("spec-copy-md"),
-            "should contain copy markdown button"
+            disposition.contains("attachment") && disposition.ends_with(".md\""),
+            "should have slugged filename in content-disposition, got: {}",
+            disposition
         );
     }
 
-    #[test]
-    fn spec_template_renders_empty_state() {
-        let tmpl = SpecTabTemplate {
-            spec_id: "01HTEST".to_string(),
-            title_slug: "test".to_string(),
-            spec_html: String::new(),
-            spec_markdown: String::new(),
-        };
-        let rendered = tmpl.render().unwrap();
-        assert!(rendered.contains("spec-document"));
-    }
-
-    // ---- Export download tests ----
-
-    /// Helper to create a spec and return its ULID.
-    async fn create_test_spec(state: &SharedState) -> ulid::Ulid {
-        let app = create_router(Arc::clone(state), None);
-        let resp = app
-            .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Build an export testing system"))
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), 200);
-        let actors = state.actors.read().await;
-        *actors.keys().next().expect("should have a spec")
-    }
-
     #[tokio::test]
-    async fn export_markdown_returns_200_with_correct_headers() {
+    async fn export_yaml_returns_200_with_correct_headers() {
         let state = test_state();
         let spec_id = create_test_spec(&state).await;
 
         let app = create_router(Arc::clone(&state), None);
         let resp = app
             .oneshot(
-                Request::get(format!("/web/specs/{}/export/markdown", spec_id))
+                Request::get(format!("/web/specs/{}/export/yaml", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -6408,7 +11438,7 @@ mod tests {
         assert_eq!(resp.status(), 200);
         assert_eq!(
             resp.headers().get("content-type").unwrap(),
-            "text/markdown; charset=utf-8"
+            "text/yaml; charset=utf-8"
         );
         let disposition = resp
             .headers()
@@ -6417,21 +11447,21 @@ mod tests {
             .to_str()
             .unwrap();
         assert!(
-            disposition.contains("attachment") && disposition.contains("-spec.md"),
+            disposition.contains("attachment") && disposition.ends_with(".yaml\""),
             "should have slugged filename in content-disposition, got: {}",
             disposition
         );
     }
 
     #[tokio::test]
-    async fn export_yaml_returns_200_with_correct_headers() {
+    async fn export_json_returns_200_and_round_trips() {
         let state = test_state();
         let spec_id = create_test_spec(&state).await;
 
         let app = create_router(Arc::clone(&state), None);
         let resp = app
             .oneshot(
-                Request::get(format!("/web/specs/{}/export/yaml", spec_id))
+                Request::get(format!("/web/specs/{}/export/json", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -6441,19 +11471,28 @@ mod tests {
         assert_eq!(resp.status(), 200);
         assert_eq!(
             resp.headers().get("content-type").unwrap(),
-            "text/yaml; charset=utf-8"
+            "application/json"
         );
         let disposition = resp
             .headers()
             .get("content-disposition")
             .unwrap()
             .to_str()
-            .unwrap();
+            .unwrap()
+            .to_string();
         assert!(
-            disposition.contains("attachment") && disposition.contains("-spec.yaml"),
-            "should have slugged filename in content-disposition, got: {}",
+            disposition.contains("attachment") && disposition.ends_with(".json\""),
+            "should have a .json filename, got: {}",
             disposition
         );
+
+        let body = body_text(resp).await;
+        let imported = barnstormer_core::export::import_json(&body).unwrap();
+        let actors = state.actors.read().await;
+        let live = actors.get(&spec_id).unwrap().read_state().await;
+        assert_eq!(imported.core, live.core);
+        assert_eq!(imported.cards, live.cards);
+        assert_eq!(imported.transcript, live.transcript);
     }
 
     #[tokio::test]
@@ -6483,12 +11522,95 @@ mod tests {
             .to_str()
             .unwrap();
         assert!(
-            disposition.contains("attachment") && disposition.contains("-spec.dot"),
+            disposition.contains("attachment") && disposition.ends_with(".dot\""),
             "should have slugged filename in content-disposition, got: {}",
             disposition
         );
     }
 
+    async fn set_spec_title(state: &SharedState, spec_id: ulid::Ulid, title: &str) {
+        let actors = state.actors.read().await;
+        actors
+            .get(&spec_id)
+            .unwrap()
+            .send_command(Command::UpdateSpecCore {
+                title: Some(title.to_string()),
+                one_liner: None,
+                goal: None,
+                description: None,
+                constraints: None,
+                success_criteria: None,
+                risks: None,
+                notes: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn export_disposition(state: &SharedState, spec_id: ulid::Ulid, format: &str) -> String {
+        let resp = create_router(Arc::clone(state), None)
+            .oneshot(
+                Request::get(format!("/web/specs/{}/export/{}", spec_id, format))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        resp.headers()
+            .get("content-disposition")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn export_filename_uses_slugified_title_and_short_id() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        set_spec_title(&state, spec_id, "Payment Gateway").await;
+        let id = spec_id.to_string();
+        let short_id = &id[id.len() - 6..];
+
+        for (format, ext) in [
+            ("markdown", "md"),
+            ("yaml", "yaml"),
+            ("dot", "dot"),
+            ("mermaid", "mmd"),
+            ("spec", "spec.md"),
+            ("prompt", "build-prompt.txt"),
+        ] {
+            assert_eq!(
+                export_disposition(&state, spec_id, format).await,
+                format!(
+                    "attachment; filename=\"payment-gateway-{}.{}\"",
+                    short_id, ext
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn export_filename_is_safe_for_slashes_and_quotes() {
+        let mut state = Arc::into_inner(test_state()).unwrap();
+        state.export_filename_pattern = "{slug}/\"{id}\"".to_string();
+        let state = Arc::new(state);
+        let spec_id = create_test_spec(&state).await;
+        set_spec_title(&state, spec_id, "../etc/\"passwd\" \\ q3").await;
+
+        let disposition = export_disposition(&state, spec_id, "markdown").await;
+        assert_eq!(
+            disposition,
+            format!("attachment; filename=\"etc-passwd-q3--{}.md\"", spec_id)
+        );
+        let filename = disposition
+            .strip_prefix("attachment; filename=\"")
+            .and_then(|f| f.strip_suffix('"'))
+            .unwrap();
+        assert!(!filename.contains(['/', '\\', '"']));
+    }
+
     #[tokio::test]
     async fn export_markdown_for_nonexistent_spec_returns_404() {
         let state = test_state();
@@ -6506,29 +11628,109 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn export_yaml_for_nonexistent_spec_returns_404() {
+    async fn export_yaml_for_nonexistent_spec_returns_404() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let fake_id = ulid::Ulid::new();
+        let resp = app
+            .oneshot(
+                Request::get(format!("/web/specs/{}/export/yaml", fake_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn export_dot_for_nonexistent_spec_returns_404() {
+        let state = test_state();
+        let app = create_router(state, None);
+        let fake_id = ulid::Ulid::new();
+        let resp = app
+            .oneshot(
+                Request::get(format!("/web/specs/{}/export/dot", fake_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn export_mermaid_returns_flowchart() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::get(format!("/web/specs/{}/export/mermaid", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "text/plain; charset=utf-8"
+        );
+        let body = body_text(resp).await;
+        assert!(body.starts_with("flowchart TD\n"), "got: {}", body);
+    }
+
+    #[tokio::test]
+    async fn export_prompt_returns_plain_text_brief() {
         let state = test_state();
-        let app = create_router(state, None);
-        let fake_id = ulid::Ulid::new();
+        let spec_id = create_test_spec(&state).await;
+        let goal = {
+            let actors = state.actors.read().await;
+            let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+            spec_state.core.as_ref().unwrap().goal.clone()
+        };
+
+        let app = create_router(Arc::clone(&state), None);
         let resp = app
             .oneshot(
-                Request::get(format!("/web/specs/{}/export/yaml", fake_id))
+                Request::get(format!("/web/specs/{}/export/prompt", spec_id))
                     .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 404);
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "text/plain; charset=utf-8"
+        );
+        let disposition = resp
+            .headers()
+            .get("content-disposition")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(
+            disposition.ends_with(".build-prompt.txt\""),
+            "should offer a build-prompt.txt download, got: {}",
+            disposition
+        );
+
+        let body = body_text(resp).await;
+        assert!(body.contains(&goal));
+        assert!(body.contains("## OPEN QUESTIONS"));
     }
 
     #[tokio::test]
-    async fn export_dot_for_nonexistent_spec_returns_404() {
+    async fn export_prompt_for_nonexistent_spec_returns_404() {
         let state = test_state();
         let app = create_router(state, None);
-        let fake_id = ulid::Ulid::new();
         let resp = app
             .oneshot(
-                Request::get(format!("/web/specs/{}/export/dot", fake_id))
+                Request::get(format!("/web/specs/{}/export/prompt", ulid::Ulid::new()))
                     .body(Body::empty())
                     .unwrap(),
             )
@@ -6572,7 +11774,7 @@ mod tests {
             .to_str()
             .unwrap();
         assert!(
-            disposition.contains("spec.md"),
+            disposition.contains(".spec.md"),
             "should offer spec.md download, got: {}",
             disposition
         );
@@ -6713,15 +11915,23 @@ mod tests {
 
     #[test]
     fn sender_display_human() {
-        let (label, is_human, role_class) = sender_display("human");
+        let (label, is_human, role_class) = sender_display("human", None);
         assert_eq!(label, "You");
         assert!(is_human, "human should be flagged as is_human");
         assert_eq!(role_class, "human");
     }
 
+    #[test]
+    fn sender_display_assistant() {
+        let (label, is_human, role_class) = sender_display("assistant", None);
+        assert_eq!(label, "Assistant");
+        assert!(!is_human);
+        assert_eq!(role_class, "assistant");
+    }
+
     #[test]
     fn sender_display_manager_role() {
-        let (label, is_human, role_class) = sender_display("manager-01JTESTID123");
+        let (label, is_human, role_class) = sender_display("manager-01JTESTID123", None);
         assert_eq!(label, "Orchestrator");
         assert!(!is_human, "agent should not be flagged as human");
         assert_eq!(role_class, "manager");
@@ -6729,7 +11939,7 @@ mod tests {
 
     #[test]
     fn sender_display_brainstormer_role() {
-        let (label, is_human, role_class) = sender_display("brainstormer-01JTESTID456");
+        let (label, is_human, role_class) = sender_display("brainstormer-01JTESTID456", None);
         assert_eq!(label, "Researcher");
         assert!(!is_human);
         assert_eq!(role_class, "brainstormer");
@@ -6737,7 +11947,7 @@ mod tests {
 
     #[test]
     fn sender_display_dot_generator_role() {
-        let (label, is_human, role_class) = sender_display("dot_generator-01JTESTID789");
+        let (label, is_human, role_class) = sender_display("dot_generator-01JTESTID789", None);
         assert_eq!(label, "Dot Generator");
         assert!(!is_human);
         assert_eq!(role_class, "dot_generator");
@@ -6745,7 +11955,7 @@ mod tests {
 
     #[test]
     fn sender_display_unknown_sender() {
-        let (label, is_human, role_class) = sender_display("CustomRole-01JTESTID");
+        let (label, is_human, role_class) = sender_display("CustomRole-01JTESTID", None);
         // The capitalization loop uppercases only the first character and keeps
         // the rest as-is, so "CustomRole" becomes "CustomRole" (already capitalized).
         assert_eq!(
@@ -6761,7 +11971,7 @@ mod tests {
 
     #[test]
     fn sender_display_unusual_characters() {
-        let (_label, is_human, role_class) = sender_display("My Agent!@#");
+        let (_label, is_human, role_class) = sender_display("My Agent!@#", None);
         assert!(!is_human);
         // No '-' separator, so the entire string is the role. Normalization:
         // lowercase + replace space/!/@ /# with hyphens → "my-agent---"
@@ -6771,6 +11981,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sender_display_prefers_persona_name() {
+        let (label, is_human, role_class) = sender_display("planner-01JTESTID", Some("Ada"));
+        assert_eq!(label, "Ada");
+        assert!(!is_human);
+        assert_eq!(role_class, "planner", "persona keeps the role's styling");
+
+        let (label, _, _) = sender_display("planner-01JTESTID", Some("  "));
+        assert_eq!(
+            label, "Architect",
+            "blank persona falls back to the role label"
+        );
+    }
+
     // ---- is_chat_participant tests ----
 
     #[test]
@@ -6965,6 +12189,7 @@ mod tests {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
             transcript: vec![TranscriptEntry {
+                message_id: String::new(),
                 sender: "human".to_string(),
                 sender_label: "You".to_string(),
                 initial: "Y".to_string(),
@@ -6976,6 +12201,8 @@ mod tests {
                 content_html: "<p>Hello world</p>\n".to_string(),
                 timestamp: "12:00:00".to_string(),
                 repeat_count: 1,
+                agent_model: None,
+                is_highlighted: false,
             }],
         };
         let rendered = tmpl.render().unwrap();
@@ -7010,6 +12237,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
@@ -7032,6 +12260,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: Some(QuestionData::Boolean {
                 question_id: "01HQID".to_string(),
                 question: "Continue?".to_string(),
@@ -7053,6 +12282,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: Some(QuestionData::Freeform {
                 question_id: "01HQID".to_string(),
                 question: "Describe the goal".to_string(),
@@ -7069,6 +12299,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: Some(QuestionData::MultipleChoice {
                 question_id: "01HQID".to_string(),
                 question: "Pick a language".to_string(),
@@ -7087,6 +12318,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: Some(QuestionData::Boolean {
                 question_id: "01HQID".to_string(),
                 question: "Proceed?".to_string(),
@@ -7106,6 +12338,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
@@ -7120,6 +12353,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: Some(QuestionData::Boolean {
                 question_id: "01HQID".to_string(),
                 question: "Continue?".to_string(),
@@ -7144,6 +12378,7 @@ mod tests {
         let tmpl = ChatQuestionTemplate {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
+            queued_questions: 0,
             pending_question: Some(QuestionData::MultipleChoice {
                 question_id: "01HQID".to_string(),
                 question: "Pick one".to_string(),
@@ -7170,6 +12405,7 @@ mod tests {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
             transcript: vec![],
+            queued_questions: 0,
             pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
@@ -7186,6 +12422,7 @@ mod tests {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
             transcript: vec![TranscriptEntry {
+                message_id: String::new(),
                 sender: "human".to_string(),
                 sender_label: "You".to_string(),
                 initial: "Y".to_string(),
@@ -7197,7 +12434,10 @@ mod tests {
                 content_html: "<p>Test message</p>\n".to_string(),
                 timestamp: "12:00:00".to_string(),
                 repeat_count: 1,
+                agent_model: None,
+                is_highlighted: false,
             }],
+            queued_questions: 0,
             pending_question: Some(QuestionData::Boolean {
                 question_id: "01HQID".to_string(),
                 question: "Ready?".to_string(),
@@ -7234,6 +12474,7 @@ mod tests {
             spec_id: "01HTEST".to_string(),
             container_id: "chat-transcript".to_string(),
             transcript: vec![],
+            queued_questions: 0,
             pending_question: None,
         };
         let rendered = tmpl.render().unwrap();
@@ -7243,167 +12484,686 @@ mod tests {
             rendered.contains("sse:transcript_appended"),
             "feed should have transcript_appended trigger"
         );
-        // Question triggers on question_asked and question_answered
+        // Question triggers on question_asked and question_answered
+        assert!(
+            rendered.contains("sse:question_asked"),
+            "question should have question_asked trigger"
+        );
+        assert!(
+            rendered.contains("sse:question_answered"),
+            "question should have question_answered trigger"
+        );
+    }
+
+    // ---- Handler tests for part=feed and part=question ----
+
+    #[tokio::test]
+    async fn activity_transcript_with_part_feed_returns_feed_only() {
+        let state = test_state();
+
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Feed part test"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
+        };
+
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::get(format!(
+                    "/web/specs/{}/activity/transcript?container_id=chat-transcript&part=feed",
+                    spec_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            html.contains(r#"id="chat-transcript-feed""#),
+            "should return feed container: {}",
+            html
+        );
+        assert!(
+            !html.contains(r#"id="chat-transcript-question""#),
+            "should not include question container in feed-only response"
+        );
+    }
+
+    #[tokio::test]
+    async fn activity_transcript_with_part_question_returns_question_only() {
+        let state = test_state();
+
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Question part test"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
+        };
+
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::get(format!(
+                    "/web/specs/{}/activity/transcript?container_id=chat-transcript&part=question",
+                    spec_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            html.contains(r#"id="chat-transcript-question""#),
+            "should return question container: {}",
+            html
+        );
+        assert!(
+            !html.contains(r#"id="chat-transcript-feed""#),
+            "should not include feed container in question-only response"
+        );
+    }
+
+    #[tokio::test]
+    async fn post_answer_targeting_question_container_returns_question_only() {
+        let state = test_state();
+
+        let app = create_router(Arc::clone(&state), None);
+        let resp = app
+            .oneshot(
+                Request::post("/web/specs")
+                    .header("content-type", MP_CONTENT_TYPE)
+                    .body(mp_description_body("Answer question target test"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let spec_id = {
+            let actors = state.actors.read().await;
+            *actors.keys().next().expect("should have a spec")
+        };
+
+        // Ask a question so we can answer it
+        let question_id = ulid::Ulid::new();
+        {
+            let actors = state.actors.read().await;
+            let handle = actors.get(&spec_id).expect("actor should exist");
+            handle
+                .send_command(Command::AskQuestion {
+                    question: barnstormer_core::UserQuestion::Freeform {
+                        question_id,
+                        question: "What color?".to_string(),
+                        placeholder: None,
+                        validation_hint: None,
+                    },
+                })
+                .await
+                .unwrap();
+        }
+
+        // POST to /answer with HX-Target: #chat-transcript-question
+        let app2 = create_router(Arc::clone(&state), None);
+        let resp = app2
+            .oneshot(
+                Request::post(format!("/web/specs/{}/answer", spec_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .header("HX-Target", "#chat-transcript-question")
+                    .body(Body::from(format!(
+                        "question_id={}&answer=Blue",
+                        question_id
+                    )))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            html.contains(r#"id="chat-transcript-question""#),
+            "response should be the question container only: {}",
+            html
+        );
+        assert!(
+            !html.contains(r#"id="chat-transcript-feed""#),
+            "response should not include the feed container"
+        );
+    }
+
+    #[tokio::test]
+    async fn answering_first_queued_question_shows_the_next_one() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let first = ulid::Ulid::new();
+        let second = ulid::Ulid::new();
+        {
+            let actors = state.actors.read().await;
+            let handle = actors.get(&spec_id).expect("actor should exist");
+            for (question_id, text) in [(first, "What color?"), (second, "What size?")] {
+                handle
+                    .send_command(Command::AskQuestion {
+                        question: barnstormer_core::UserQuestion::Freeform {
+                            question_id,
+                            question: text.to_string(),
+                            placeholder: None,
+                            validation_hint: None,
+                        },
+                    })
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let answer = |question_id: ulid::Ulid| {
+            Request::post(format!("/web/specs/{}/answer", spec_id))
+                .header("content-type", "application/x-www-form-urlencoded")
+                .header("HX-Target", "#chat-transcript-question")
+                .body(Body::from(format!(
+                    "question_id={}&answer=Blue",
+                    question_id
+                )))
+                .unwrap()
+        };
+
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(
+                Request::get(format!(
+                    "/web/specs/{}/activity/transcript?container_id=chat-transcript&part=question",
+                    spec_id
+                ))
+                .body(Body::empty())
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(
+            html.contains("What color?"),
+            "front question shown: {}",
+            html
+        );
         assert!(
-            rendered.contains("sse:question_asked"),
-            "question should have question_asked trigger"
+            html.contains("+1 more queued"),
+            "queue count shown: {}",
+            html
         );
+
+        // Answering out of order is rejected; the first question stays up.
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(answer(second))
+            .await
+            .unwrap();
+        assert_ne!(resp.status(), 200);
+
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(answer(first))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let html = String::from_utf8(body.to_vec()).unwrap();
+        assert!(html.contains("What size?"), "next question shown: {}", html);
         assert!(
-            rendered.contains("sse:question_answered"),
-            "question should have question_answered trigger"
+            !html.contains("more queued"),
+            "queue is now empty: {}",
+            html
         );
     }
 
-    // ---- Handler tests for part=feed and part=question ----
-
     #[tokio::test]
-    async fn activity_transcript_with_part_feed_returns_feed_only() {
+    async fn duplicating_a_card_copies_it_right_after_the_original() {
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let handle = state.actors.read().await.get(&spec_id).unwrap().clone();
+        let mut ids = Vec::new();
+        for (title, order) in [("Original", 1.0), ("Next", 2.0)] {
+            let events = handle
+                .send_command(Command::CreateCard {
+                    card_type: "task".to_string(),
+                    title: title.to_string(),
+                    body: Some("Some **body**".to_string()),
+                    lane: Some("Plan".to_string()),
+                    created_by: "human".to_string(),
+                    source_attachment_id: None,
+                })
+                .await
+                .unwrap();
+            let card_id = match &events[0].payload {
+                barnstormer_core::EventPayload::CardCreated { card } => card.card_id,
+                other => panic!("expected CardCreated, got {:?}", other),
+            };
+            handle
+                .send_command(Command::MoveCard {
+                    card_id,
+                    lane: "Plan".to_string(),
+                    order,
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap();
+            ids.push(card_id);
+        }
+        handle
+            .send_command(Command::UpdateCard {
+                card_id: ids[0],
+                title: None,
+                body: None,
+                card_type: None,
+                refs: Some(vec!["REQ-1".to_string()]),
+                tags: None,
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap();
 
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Feed part test"))
+                Request::post(format!("/web/specs/{}/cards/{}/duplicate", spec_id, ids[0]))
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains("Original (copy)"), "{}", html);
 
-        let spec_id = {
-            let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
-        };
+        let spec_state = handle.read_state().await;
+        assert_eq!(spec_state.cards.len(), 3);
+        let copy = spec_state
+            .cards
+            .values()
+            .find(|c| c.title == "Original (copy)")
+            .expect("duplicate card");
+        assert!(!ids.contains(&copy.card_id), "duplicate gets a fresh ID");
+        assert_eq!(copy.card_type, "task");
+        assert_eq!(copy.body.as_deref(), Some("Some **body**"));
+        assert_eq!(copy.lane, "Plan");
+        assert!(copy.refs.is_empty(), "refs are not copied");
+        assert!(copy.order > 1.0 && copy.order < 2.0, "order {}", copy.order);
+    }
 
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
+    #[tokio::test]
+    async fn posting_notes_updates_core_and_document() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::get(format!(
-                    "/web/specs/{}/activity/transcript?container_id=chat-transcript&part=feed",
-                    spec_id
-                ))
-                .body(Body::empty())
-                .unwrap(),
+                Request::post(format!("/web/specs/{}/notes", spec_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("notes=Ask+about+**pricing**"))
+                    .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains("<strong>pricing</strong>"), "{}", html);
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        {
+            let actors = state.actors.read().await;
+            let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+            let core = spec_state.core.as_ref().unwrap();
+            assert_eq!(core.notes.as_deref(), Some("Ask about **pricing**"));
+            assert!(spec_state.cards.is_empty(), "notes must not create cards");
+        }
+
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(
+                Request::get(format!("/web/specs/{}/document", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
             .await
             .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
+        let html = body_text(resp).await;
+        assert!(html.contains("<strong>pricing</strong>"));
         assert!(
-            html.contains(r#"id="chat-transcript-feed""#),
-            "should return feed container: {}",
+            html.contains(">Ask about **pricing**</textarea>"),
+            "{}",
             html
         );
+    }
+
+    async fn get_document(state: &SharedState, spec_id: Ulid, token: Option<&str>) -> String {
+        let mut req = Request::get(format!("/web/specs/{}/document", spec_id));
+        if let Some(token) = token {
+            req = req.header("authorization", format!("Bearer {}", token));
+        }
+        let resp = create_router(Arc::clone(state), None)
+            .oneshot(req.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        body_text(resp).await
+    }
+
+    #[tokio::test]
+    async fn collapsed_section_preference_applies_to_next_document_render() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+
+        let html = get_document(&state, spec_id, Some("alice")).await;
         assert!(
-            !html.contains(r#"id="chat-transcript-question""#),
-            "should not include question container in feed-only response"
+            html.contains(r#"data-section="goal" open>"#),
+            "sections default to expanded: {}",
+            html
         );
+
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(
+                Request::post(format!("/web/specs/{}/ui-prefs", spec_id))
+                    .header("authorization", "Bearer alice")
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("collapsed=goal%2Cbogus"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        let html = get_document(&state, spec_id, Some("alice")).await;
+        assert!(html.contains(r#"data-section="goal">"#), "{}", html);
+        assert!(html.contains(r#"data-section="done-checklist" open>"#));
+
+        // Other users (and anonymous requests) keep their own preferences.
+        let html = get_document(&state, spec_id, Some("bob")).await;
+        assert!(html.contains(r#"data-section="goal" open>"#));
+        let html = get_document(&state, spec_id, None).await;
+        assert!(html.contains(r#"data-section="goal" open>"#));
     }
 
     #[tokio::test]
-    async fn activity_transcript_with_part_question_returns_question_only() {
+    async fn ui_prefs_for_unknown_spec_returns_404() {
+        let state = test_state();
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(
+                Request::post(format!("/web/specs/{}/ui-prefs", Ulid::new()))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("collapsed=goal"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn checklist_items_are_added_and_toggled_from_the_document() {
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
 
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Question part test"))
+                Request::post(format!("/web/specs/{}/checklist", spec_id))
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("text=Docs+written"))
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains("Docs written"), "{}", html);
 
-        let spec_id = {
+        let item_id = {
             let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
+            let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+            assert!(!spec_state.looks_complete());
+            spec_state.done_checklist[0].item_id
         };
+        assert!(html.contains(&format!("/checklist/{}/toggle", item_id)));
 
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::get(format!(
-                    "/web/specs/{}/activity/transcript?container_id=chat-transcript&part=question",
-                    spec_id
+                Request::post(format!(
+                    "/web/specs/{}/checklist/{}/toggle",
+                    spec_id, item_id
                 ))
                 .body(Body::empty())
                 .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let html = body_text(resp).await;
+        assert!(html.contains("doc-checklist-item checked"), "{}", html);
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains(r#"id="chat-transcript-question""#),
-            "should return question container: {}",
-            html
-        );
-        assert!(
-            !html.contains(r#"id="chat-transcript-feed""#),
-            "should not include feed container in question-only response"
-        );
+        let actors = state.actors.read().await;
+        let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+        assert!(spec_state.done_checklist[0].checked);
+        assert!(spec_state.looks_complete());
     }
 
     #[tokio::test]
-    async fn post_answer_targeting_question_container_returns_question_only() {
+    async fn activity_panel_shows_agent_persona_name() {
         let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        {
+            let actors = state.actors.read().await;
+            actors
+                .get(&spec_id)
+                .unwrap()
+                .send_command(Command::AppendTranscript {
+                    sender: "planner-01HTEST".to_string(),
+                    content: "Drafting the milestones.".to_string(),
+                    display_name: Some("Ada".to_string()),
+                })
+                .await
+                .unwrap();
+        }
 
-        let app = create_router(Arc::clone(&state), None);
-        let resp = app
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
-                Request::post("/web/specs")
-                    .header("content-type", MP_CONTENT_TYPE)
-                    .body(mp_description_body("Answer question target test"))
+                Request::get(format!("/web/specs/{}/activity/transcript", spec_id))
+                    .body(Body::empty())
                     .unwrap(),
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), 200);
+        let html = body_text(resp).await;
+        assert!(
+            html.contains(r#"<span class="sender-badge badge-planner">Ada</span>"#),
+            "{}",
+            html
+        );
+        assert!(!html.contains(">Architect<"));
+    }
 
-        let spec_id = {
+    #[tokio::test]
+    async fn compact_transcript_groups_same_sender_runs_and_folds_long_messages() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let long = "Lots of narration. ".repeat(30);
+        {
             let actors = state.actors.read().await;
-            *actors.keys().next().expect("should have a spec")
-        };
+            let handle = actors.get(&spec_id).unwrap();
+            for (sender, content) in [
+                ("manager-1", "First thought."),
+                ("manager-1", "Second thought."),
+                ("brainstormer-1", "A different voice."),
+                ("manager-1", long.as_str()),
+            ] {
+                handle
+                    .send_command(Command::AppendTranscript {
+                        sender: sender.to_string(),
+                        content: content.to_string(),
+                        display_name: None,
+                    })
+                    .await
+                    .unwrap();
+            }
+        }
 
-        // Ask a question so we can answer it
-        let question_id = ulid::Ulid::new();
+        let full = get_board(
+            &state,
+            format!("/web/specs/{}/activity/transcript", spec_id),
+        )
+        .await;
+        assert!(!full.contains("message-grouped"), "{full}");
+        assert!(!full.contains("show more"));
+        assert!(full.contains("Compact view"));
+
+        let compact = get_board(
+            &state,
+            format!("/web/specs/{}/activity/transcript?compact=true", spec_id),
+        )
+        .await;
+        // Only "Second thought." continues a run; the other senders alternate.
+        assert_eq!(compact.matches(" message-grouped").count(), 1, "{compact}");
+        let grouped = compact.find(" message-grouped").unwrap();
+        let second = compact.find("Second thought.").unwrap();
+        let different = compact.find("A different voice.").unwrap();
+        assert!(grouped < second && second < different);
+        assert_eq!(
+            compact.matches("sender-badge").count() + 1,
+            full.matches("sender-badge").count()
+        );
+        assert_eq!(compact.matches("show more").count(), 1);
+        assert!(
+            compact.contains("&compact=true\""),
+            "refresh keeps the density"
+        );
+        assert!(compact.contains("Full view"));
+    }
+
+    #[tokio::test]
+    async fn activity_highlights_exactly_the_permalinked_message() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        let mut message_ids = Vec::new();
         {
             let actors = state.actors.read().await;
-            let handle = actors.get(&spec_id).expect("actor should exist");
-            handle
-                .send_command(Command::AskQuestion {
-                    question: barnstormer_core::UserQuestion::Freeform {
-                        question_id,
-                        question: "What color?".to_string(),
-                        placeholder: None,
-                        validation_hint: None,
-                    },
+            let handle = actors.get(&spec_id).unwrap();
+            for content in ["First point.", "Second point.", "Third point."] {
+                let events = handle
+                    .send_command(Command::AppendTranscript {
+                        sender: "human".to_string(),
+                        content: content.to_string(),
+                        display_name: None,
+                    })
+                    .await
+                    .unwrap();
+                match &events[0].payload {
+                    barnstormer_core::EventPayload::TranscriptAppended { message } => {
+                        message_ids.push(message.message_id)
+                    }
+                    other => panic!("expected TranscriptAppended, got {:?}", other),
+                }
+            }
+        }
+        let target = message_ids[1];
+
+        for uri in [
+            format!("/web/specs/{}/activity?highlight=msg-{}", spec_id, target),
+            format!(
+                "/web/specs/{}/activity/transcript?highlight={}",
+                spec_id, target
+            ),
+        ] {
+            let html = get_board(&state, uri).await;
+            for id in &message_ids {
+                assert!(html.contains(&format!("id=\"msg-{}\"", id)), "{html}");
+                assert!(html.contains(&format!("href=\"#msg-{}\"", id)));
+            }
+            assert_eq!(html.matches(" message-highlight\"").count(), 1, "{html}");
+            let highlighted = html.find(" message-highlight\"").unwrap();
+            let anchor = html.find(&format!("id=\"msg-{}\"", target)).unwrap();
+            assert!(html[highlighted..anchor].find("<div").is_none());
+        }
+
+        // Without a target nothing is highlighted.
+        let html = get_board(&state, format!("/web/specs/{}/activity", spec_id)).await;
+        assert!(!html.contains(" message-highlight\""));
+    }
+
+    #[tokio::test]
+    async fn review_gate_is_approved_from_the_activity_panel() {
+        let state = test_state();
+        let spec_id = create_test_spec(&state).await;
+        {
+            let actors = state.actors.read().await;
+            actors
+                .get(&spec_id)
+                .unwrap()
+                .send_command(Command::RequestReviewGate {
+                    command: Box::new(Command::SetSpecArchived { archived: true }),
+                    requested_by: "manager-01HTEST".to_string(),
+                    reason: "spec is done".to_string(),
                 })
                 .await
                 .unwrap();
         }
+        let question_id = {
+            let actors = state.actors.read().await;
+            let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+            spec_state.pending_question().unwrap().question_id()
+        };
 
-        // POST to /answer with HX-Target: #chat-transcript-question
-        let app2 = create_router(Arc::clone(&state), None);
-        let resp = app2
+        let resp = create_router(Arc::clone(&state), None)
+            .oneshot(
+                Request::get(format!("/web/specs/{}/activity/transcript", spec_id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let html = body_text(resp).await;
+        assert!(html.contains("Review gate:"), "{}", html);
+        assert!(html.contains(r#"value="Approve""#));
+        assert!(html.contains(r#"value="Decline""#));
+
+        let resp = create_router(Arc::clone(&state), None)
             .oneshot(
                 Request::post(format!("/web/specs/{}/answer", spec_id))
                     .header("content-type", "application/x-www-form-urlencoded")
-                    .header("HX-Target", "#chat-transcript-question")
                     .body(Body::from(format!(
-                        "question_id={}&answer=Blue",
+                        "question_id={}&answer=Approve",
                         question_id
                     )))
                     .unwrap(),
@@ -7412,19 +13172,10 @@ mod tests {
             .unwrap();
         assert_eq!(resp.status(), 200);
 
-        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let html = String::from_utf8(body.to_vec()).unwrap();
-        assert!(
-            html.contains(r#"id="chat-transcript-question""#),
-            "response should be the question container only: {}",
-            html
-        );
-        assert!(
-            !html.contains(r#"id="chat-transcript-feed""#),
-            "response should not include the feed container"
-        );
+        let actors = state.actors.read().await;
+        let spec_state = actors.get(&spec_id).unwrap().read_state().await;
+        assert!(spec_state.archived);
+        assert!(spec_state.pending_proposals.is_empty());
     }
 
     // ---- render_markdown tests ----
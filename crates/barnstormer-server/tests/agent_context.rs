@@ -0,0 +1,102 @@
+// ABOUTME: Integration tests for the agent memory debug view at /api/specs/{id}/agents/{role}/context.
+// ABOUTME: Covers specs without a swarm, unknown roles, and a context populated by a real agent step.
+
+use std::sync::Arc;
+
+use barnstormer_agent::testing::StubLlmClient;
+use barnstormer_agent::{AgentRole, AgentRunner, AttachmentSummarizer, SwarmOrchestrator};
+use barnstormer_core::{Command, SpecPhase};
+use barnstormer_server::attachment_summarizer::ServerSummarizer;
+use http::StatusCode;
+use mux::llm::LlmClient;
+
+mod common;
+
+use common::get_json;
+
+#[tokio::test]
+async fn context_is_not_found_without_a_swarm() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (status, body) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents/planner/context", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].is_string());
+}
+
+#[tokio::test]
+async fn context_reflects_the_agent_after_a_step() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let handle = ctx.state.actors.read().await[&ctx.spec_id].clone();
+    let actor = Arc::new(handle.clone());
+    let summarizer = Arc::new(ServerSummarizer {
+        home: ctx._tmp.path().to_path_buf(),
+        provider: "anthropic".to_string(),
+    });
+
+    let mut runner = AgentRunner::new(ctx.spec_id, AgentRole::Planner);
+    let mut events = handle.subscribe();
+    handle
+        .send_command(Command::CreateCard {
+            card_type: "task".to_string(),
+            title: "Draft milestones".to_string(),
+            body: None,
+            lane: None,
+            created_by: "human".to_string(),
+            source_attachment_id: None,
+        })
+        .await
+        .unwrap();
+    SwarmOrchestrator::refresh_context(&mut runner, &handle, &mut events).await;
+    runner.context.add_decision("Ship weekly".to_string());
+    let client: Arc<dyn LlmClient> = Arc::new(StubLlmClient::done());
+    SwarmOrchestrator::run_agent_step(
+        &mut runner,
+        &actor,
+        &Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        &Arc::new(std::sync::Mutex::new(None)),
+        &client,
+        "test-model",
+        &SpecPhase::Brainstorming,
+        ctx._tmp.path(),
+        &(summarizer as Arc<dyn AttachmentSummarizer>),
+        None,
+        2,
+        barnstormer_agent::swarm::DEFAULT_AGENT_STEP_TIMEOUT,
+        false,
+    )
+    .await;
+    let agent_id = runner.agent_id.clone();
+    let last_event_seen = runner.context.last_event_seen;
+    assert!(last_event_seen > 0);
+
+    common::spawn_test_swarm(&ctx, vec![runner]).await;
+
+    let (status, body) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents/planner/context", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["agent_id"], agent_id.as_str());
+    assert_eq!(body["agent_role"], "Planner");
+    assert_eq!(body["last_event_seen"], last_event_seen);
+    assert!(
+        body["rolling_summary"]
+            .as_str()
+            .unwrap()
+            .contains("Draft milestones"),
+        "{}",
+        body
+    );
+    assert_eq!(body["key_decisions"], serde_json::json!(["Ship weekly"]));
+
+    let (status, _) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents/critic/context", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
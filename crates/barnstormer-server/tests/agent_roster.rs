@@ -0,0 +1,59 @@
+// ABOUTME: Integration tests for the agent roster at /api/specs/{id}/agents.
+// ABOUTME: Covers specs without a swarm, unknown specs, and per-agent fields with a long summary.
+
+use barnstormer_agent::{AgentRole, AgentRunner};
+use http::StatusCode;
+use ulid::Ulid;
+
+mod common;
+
+use common::get_json;
+
+#[tokio::test]
+async fn roster_is_empty_without_a_swarm() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (status, body) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!([]));
+
+    let (status, _) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents", Ulid::new()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn roster_lists_each_agent_with_a_shortened_summary() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let manager = AgentRunner::new(ctx.spec_id, AgentRole::Manager);
+    let mut planner = AgentRunner::new(ctx.spec_id, AgentRole::Planner);
+    planner.context.rolling_summary = "p".repeat(1_000);
+    planner.context.last_event_seen = 7;
+    let ids = [manager.agent_id.clone(), planner.agent_id.clone()];
+
+    common::spawn_test_swarm(&ctx, vec![manager, planner]).await;
+
+    let (status, body) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let agents = body.as_array().unwrap();
+    assert_eq!(agents.len(), 2);
+    assert_eq!(agents[0]["role"], "manager");
+    assert_eq!(agents[0]["agent_id"], ids[0].as_str());
+    assert_eq!(agents[0]["rolling_summary"], "");
+    assert_eq!(agents[1]["role"], "planner");
+    assert_eq!(agents[1]["agent_id"], ids[1].as_str());
+    assert_eq!(agents[1]["last_event_seen"], 7);
+    let summary = agents[1]["rolling_summary"].as_str().unwrap();
+    assert!(summary.ends_with("..."), "{}", summary);
+    assert!(summary.len() < 1_000);
+}
@@ -0,0 +1,104 @@
+// ABOUTME: Integration tests for switching single agents on and off via
+// ABOUTME: POST /web/specs/{id}/agents/{role}/toggle while the rest of the swarm keeps running.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use barnstormer_agent::testing::StubLlmClient;
+use barnstormer_agent::{AgentRole, AgentRunner, SwarmOrchestrator};
+use barnstormer_server::app_state::SwarmHandle;
+use barnstormer_server::attachment_summarizer::ServerSummarizer;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+mod common;
+
+async fn toggle(router: axum::Router, uri: &str) -> (StatusCode, String) {
+    let resp = router
+        .oneshot(Request::post(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn toggle_switches_one_agent_and_leaves_the_swarm_running() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let handle = ctx.state.actors.read().await[&ctx.spec_id].clone();
+    let swarm = SwarmOrchestrator::with_agents(
+        ctx.spec_id,
+        handle,
+        vec![
+            AgentRunner::new(ctx.spec_id, AgentRole::Manager),
+            AgentRunner::new(ctx.spec_id, AgentRole::DotGenerator),
+        ],
+        Arc::new(StubLlmClient::done()),
+        "test-model".to_string(),
+        ctx._tmp.path().to_path_buf(),
+        Arc::new(ServerSummarizer {
+            home: ctx._tmp.path().to_path_buf(),
+            provider: "anthropic".to_string(),
+        }),
+    );
+    let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+    ctx.state.swarms.write().await.insert(
+        ctx.spec_id,
+        SwarmHandle {
+            swarm: Arc::clone(&swarm),
+            task: tokio::spawn(async {}),
+        },
+    );
+    let uri = format!("/web/specs/{}/agents/dot_generator/toggle", ctx.spec_id);
+
+    let (status, html) = toggle(ctx.router.clone(), &uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(html.contains("agent-toggle agent-toggle-off"), "{html}");
+    {
+        let swarm = swarm.lock().await;
+        assert_eq!(swarm.is_role_enabled("dot_generator"), Some(false));
+        assert_eq!(swarm.is_role_enabled("manager"), Some(true));
+        assert!(!swarm.is_paused());
+    }
+
+    let (status, _) = toggle(ctx.router.clone(), &uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        swarm.lock().await.is_role_enabled("dot_generator"),
+        Some(true)
+    );
+}
+
+#[tokio::test]
+async fn toggle_404s_without_a_swarm_or_for_unknown_roles() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let uri = format!("/web/specs/{}/agents/planner/toggle", ctx.spec_id);
+    let (status, _) = toggle(ctx.router.clone(), &uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let handle = ctx.state.actors.read().await[&ctx.spec_id].clone();
+    let swarm = SwarmOrchestrator::with_agents(
+        ctx.spec_id,
+        handle,
+        vec![AgentRunner::new(ctx.spec_id, AgentRole::Manager)],
+        Arc::new(StubLlmClient::done()),
+        "test-model".to_string(),
+        ctx._tmp.path().to_path_buf(),
+        Arc::new(ServerSummarizer {
+            home: ctx._tmp.path().to_path_buf(),
+            provider: "anthropic".to_string(),
+        }),
+    );
+    ctx.state.swarms.write().await.insert(
+        ctx.spec_id,
+        SwarmHandle {
+            swarm: Arc::new(tokio::sync::Mutex::new(swarm)),
+            task: tokio::spawn(async {}),
+        },
+    );
+    let (status, _) = toggle(ctx.router.clone(), &uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,56 @@
+// ABOUTME: Integration tests for the agent step trace at /api/specs/{id}/agents/trace.
+// ABOUTME: Covers specs without a swarm, an unknown spec, and steps recorded by a running swarm.
+
+use barnstormer_agent::StepTraceEntry;
+use http::StatusCode;
+use ulid::Ulid;
+
+mod common;
+
+use common::get_json;
+
+#[tokio::test]
+async fn trace_is_empty_without_a_swarm() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (status, body) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents/trace", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["steps"], serde_json::json!([]));
+
+    let (status, _) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents/trace", Ulid::new()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn trace_lists_recorded_steps() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let swarm = common::spawn_test_swarm(&ctx, Vec::new()).await;
+    swarm.lock().await.step_trace.record(StepTraceEntry {
+        agent_id: "planner-01HTEST".to_string(),
+        timestamp: chrono::Utc::now(),
+        prompt_chars: 1200,
+        tool_calls: 3,
+        did_work: true,
+        reason: "tools used".to_string(),
+    });
+
+    let (status, body) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/agents/trace", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let steps = body["steps"].as_array().unwrap();
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0]["agent_id"], "planner-01HTEST");
+    assert_eq!(steps[0]["did_work"], true);
+    assert_eq!(steps[0]["tool_calls"], 3);
+    assert_eq!(steps[0]["reason"], "tools used");
+}
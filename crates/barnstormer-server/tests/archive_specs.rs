@@ -0,0 +1,131 @@
+// ABOUTME: Integration tests for archiving completed specs via /api/specs/archive-completed.
+// ABOUTME: Archived specs drop out of the default spec lists but return with include_archived=true.
+
+use axum::body::Body;
+use barnstormer_core::{Command, SpecPhase};
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+mod common;
+
+async fn send(router: axum::Router, method: &str, uri: &str) -> (StatusCode, String) {
+    let resp = router
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+async fn listed_ids(router: axum::Router, uri: &str) -> Vec<String> {
+    let (status, body) = send(router, "GET", uri).await;
+    assert_eq!(status, StatusCode::OK);
+    let specs: serde_json::Value = serde_json::from_str(&body).unwrap();
+    specs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["spec_id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn archiving_hides_spec_until_include_archived() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let done_id = ctx.spec_id.to_string();
+
+    // A second, unfinished spec must survive the bulk archive.
+    let resp = ctx
+        .router
+        .clone()
+        .oneshot(
+            Request::post("/api/specs")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "title": "Open", "one_liner": "o", "goal": "g" })
+                        .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let handle = {
+        let actors = ctx.state.actors.read().await;
+        actors.get(&ctx.spec_id).unwrap().clone()
+    };
+    handle
+        .send_command(Command::TransitionPhase {
+            target: SpecPhase::Complete,
+        })
+        .await
+        .unwrap();
+
+    let (status, body) = send(ctx.router.clone(), "POST", "/api/specs/archive-completed").await;
+    assert_eq!(status, StatusCode::OK);
+    let result: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(result["archived"], serde_json::json!([done_id]));
+
+    let default_list = listed_ids(ctx.router.clone(), "/api/specs").await;
+    assert_eq!(default_list.len(), 1);
+    assert!(!default_list.contains(&done_id));
+
+    let full_list = listed_ids(ctx.router.clone(), "/api/specs?include_archived=true").await;
+    assert_eq!(full_list.len(), 2);
+    assert!(full_list.contains(&done_id));
+
+    // The HTML spec list follows the same filter.
+    let (_, html) = send(ctx.router.clone(), "GET", "/web/specs").await;
+    assert!(!html.contains(&done_id));
+    let (_, html) = send(
+        ctx.router.clone(),
+        "GET",
+        "/web/specs?include_archived=true",
+    )
+    .await;
+    assert!(html.contains(&done_id));
+    assert!(html.contains("spec-archived"));
+
+    // Data is kept: the archived spec's state is still readable.
+    let (status, _) = send(
+        ctx.router.clone(),
+        "GET",
+        &format!("/api/specs/{}/state", done_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn archive_completed_is_idempotent() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (_, body) = send(ctx.router.clone(), "POST", "/api/specs/archive-completed").await;
+    let result: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(result["archived"], serde_json::json!([]));
+
+    let handle = {
+        let actors = ctx.state.actors.read().await;
+        actors.get(&ctx.spec_id).unwrap().clone()
+    };
+    handle
+        .send_command(Command::TransitionPhase {
+            target: SpecPhase::Complete,
+        })
+        .await
+        .unwrap();
+    send(ctx.router.clone(), "POST", "/api/specs/archive-completed").await;
+    let (_, body) = send(ctx.router.clone(), "POST", "/api/specs/archive-completed").await;
+    let result: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(result["archived"], serde_json::json!([]));
+    assert!(handle.read_state().await.archived);
+}
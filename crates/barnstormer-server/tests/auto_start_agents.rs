@@ -0,0 +1,120 @@
+// ABOUTME: Integration tests for the auto-start-agents policy on spec creation.
+// ABOUTME: Covers the server-wide switch and the per-request override on web and API create.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use http::{Request, StatusCode};
+use tempfile::TempDir;
+use tower::ServiceExt;
+
+use barnstormer_server::{AppState, ProviderStatus, SharedState, create_router};
+
+/// State whose default provider is Ollama, which needs no API key, so a
+/// swarm can actually be built when auto-start is allowed without touching
+/// the process environment.
+fn state_with_provider(auto_start_agents: bool) -> (SharedState, TempDir) {
+    let tmp = TempDir::new().unwrap();
+    let mut state = AppState::new(
+        tmp.path().to_path_buf(),
+        ProviderStatus {
+            default_provider: "ollama".to_string(),
+            default_model: None,
+            providers: vec![],
+            any_available: true,
+            model_warning: None,
+        },
+    );
+    state.auto_start_agents = auto_start_agents;
+    (Arc::new(state), tmp)
+}
+
+async fn create_via_web(state: &SharedState, auto_start: Option<&str>) -> StatusCode {
+    let boundary = "----BarnstormerAutoStart";
+    let mut body = format!(
+        "--{boundary}\r\n\
+         Content-Disposition: form-data; name=\"description\"\r\n\r\n\
+         A spec that should stay quiet\r\n"
+    );
+    if let Some(flag) = auto_start {
+        body.push_str(&format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"auto_start_agents\"\r\n\r\n\
+             {flag}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{boundary}--\r\n"));
+
+    create_router(Arc::clone(state), None)
+        .oneshot(
+            Request::post("/web/specs")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={boundary}"),
+                )
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+async fn create_via_api(state: &SharedState, body: serde_json::Value) -> StatusCode {
+    create_router(Arc::clone(state), None)
+        .oneshot(
+            Request::post("/api/specs")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+fn api_spec(auto_start: Option<bool>) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "title": "Quiet spec",
+        "one_liner": "No agents yet",
+        "goal": "Save tokens",
+    });
+    if let Some(flag) = auto_start {
+        body["auto_start_agents"] = serde_json::json!(flag);
+    }
+    body
+}
+
+#[tokio::test]
+async fn policy_off_creates_specs_without_swarms() {
+    let (state, _tmp) = state_with_provider(false);
+
+    assert_eq!(create_via_web(&state, None).await, StatusCode::OK);
+    assert_eq!(
+        create_via_api(&state, api_spec(None)).await,
+        StatusCode::CREATED
+    );
+
+    assert_eq!(state.actors.read().await.len(), 2);
+    assert!(state.swarms.read().await.is_empty());
+}
+
+#[tokio::test]
+async fn request_override_beats_policy() {
+    // Policy on, request opts out.
+    let (state, _tmp) = state_with_provider(true);
+    assert_eq!(create_via_web(&state, Some("false")).await, StatusCode::OK);
+    assert_eq!(
+        create_via_api(&state, api_spec(Some(false))).await,
+        StatusCode::CREATED
+    );
+    assert!(state.swarms.read().await.is_empty());
+
+    // Policy off, request opts in.
+    let (state, _tmp) = state_with_provider(false);
+    assert_eq!(create_via_web(&state, Some("true")).await, StatusCode::OK);
+    assert_eq!(state.swarms.read().await.len(), 1);
+    for (_, handle) in state.swarms.write().await.drain() {
+        handle.task.abort();
+    }
+}
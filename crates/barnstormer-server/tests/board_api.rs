@@ -0,0 +1,127 @@
+// ABOUTME: Integration tests for the JSON board API at /api/specs/{id}/board.
+// ABOUTME: Checks that its lanes and card order match the HTML board for the same spec.
+
+use axum::body::Body;
+use barnstormer_core::Command;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+mod common;
+
+async fn get_text(router: axum::Router, uri: &str) -> (StatusCode, String) {
+    let resp = router
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+/// Values of `attr="..."` in document order.
+fn attr_values(html: &str, attr: &str) -> Vec<String> {
+    let needle = format!("{attr}=\"");
+    html.split(needle.as_str())
+        .skip(1)
+        .map(|rest| rest.split('"').next().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn board_json_matches_html_board() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    {
+        let actors = ctx.state.actors.read().await;
+        let handle = actors.get(&ctx.spec_id).unwrap();
+        let mut ids = Vec::new();
+        for title in ["Later", "Sooner", "Elsewhere"] {
+            let events = handle
+                .send_command(Command::CreateCard {
+                    card_type: "task".to_string(),
+                    title: title.to_string(),
+                    body: None,
+                    lane: Some("Plan".to_string()),
+                    created_by: "human".to_string(),
+                    source_attachment_id: None,
+                })
+                .await
+                .unwrap();
+            match &events[0].payload {
+                barnstormer_core::EventPayload::CardCreated { card } => ids.push(card.card_id),
+                other => panic!("expected CardCreated, got {:?}", other),
+            }
+        }
+        handle
+            .send_command(Command::CreateLane {
+                name: "Backlog".to_string(),
+                updated_by: "human".to_string(),
+            })
+            .await
+            .unwrap();
+        for (card_id, lane, order) in [
+            (ids[0], "Plan", 5.0),
+            (ids[1], "Plan", 1.0),
+            (ids[2], "Backlog", 0.0),
+        ] {
+            handle
+                .send_command(Command::MoveCard {
+                    card_id,
+                    lane: lane.to_string(),
+                    order,
+                    updated_by: "human".to_string(),
+                })
+                .await
+                .unwrap();
+        }
+    }
+
+    let (status, body) = get_text(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/board", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let board: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let lanes = board["lanes"].as_array().unwrap();
+    let json_lanes: Vec<&str> = lanes.iter().map(|l| l["name"].as_str().unwrap()).collect();
+    assert_eq!(json_lanes, vec!["Ideas", "Plan", "Spec", "Backlog"]);
+    let plan_titles: Vec<&str> = lanes[1]["cards"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["title"].as_str().unwrap())
+        .collect();
+    assert_eq!(plan_titles, vec!["Sooner", "Later"]);
+    let json_card_ids: Vec<String> = lanes
+        .iter()
+        .flat_map(|l| l["cards"].as_array().unwrap())
+        .map(|c| c["card_id"].as_str().unwrap().to_string())
+        .collect();
+
+    let (status, html) = get_text(
+        ctx.router.clone(),
+        &format!("/web/specs/{}/board", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let html_lanes: Vec<String> = html
+        .split("class=\"lane-cards\" data-lane=\"")
+        .skip(1)
+        .map(|rest| rest.split('"').next().unwrap().to_string())
+        .collect();
+    assert_eq!(html_lanes, json_lanes);
+    assert_eq!(attr_values(&html, "data-card-id"), json_card_ids);
+}
+
+#[tokio::test]
+async fn board_json_unknown_spec_is_404() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (status, _) = get_text(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/board", ulid::Ulid::new()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,187 @@
+// ABOUTME: Integration tests for the card template library — JSON CRUD under
+// ABOUTME: /api/card-templates and pre-filling the create-card form from a template.
+
+use axum::body::Body;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+mod common;
+
+async fn send_json(
+    router: axum::Router,
+    method: &str,
+    uri: &str,
+    body: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let resp = router
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let json = if bytes.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+    (status, json)
+}
+
+async fn get_text(router: axum::Router, uri: &str) -> (StatusCode, String) {
+    let resp = router
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+fn user_story() -> serde_json::Value {
+    serde_json::json!({
+        "name": "User Story",
+        "card_type": "task",
+        "title": "As a user, I want",
+        "body": "**So that** ...\n\n**Acceptance criteria**"
+    })
+}
+
+#[tokio::test]
+async fn create_then_list_templates() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+
+    let (status, created) = send_json(
+        ctx.router.clone(),
+        "POST",
+        "/api/card-templates",
+        user_story(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(created["name"], "User Story");
+    assert!(created["template_id"].as_str().is_some());
+
+    let (status, body) = get_text(ctx.router.clone(), "/api/card-templates").await;
+    assert_eq!(status, StatusCode::OK);
+    let listed: serde_json::Value = serde_json::from_str(&body).unwrap();
+    let listed = listed.as_array().unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["template_id"], created["template_id"]);
+    assert_eq!(listed[0]["card_type"], "task");
+
+    // The library is persisted under the home directory.
+    assert!(ctx._tmp.path().join("card_templates.json").exists());
+}
+
+#[tokio::test]
+async fn template_validation_and_conflicts() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+
+    let (status, body) = send_json(
+        ctx.router.clone(),
+        "POST",
+        "/api/card-templates",
+        serde_json::json!({ "name": "  ", "card_type": "" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body["errors"]["name"].is_string());
+    assert!(body["errors"]["card_type"].is_string());
+
+    send_json(
+        ctx.router.clone(),
+        "POST",
+        "/api/card-templates",
+        user_story(),
+    )
+    .await;
+    let (status, _) = send_json(
+        ctx.router.clone(),
+        "POST",
+        "/api/card-templates",
+        user_story(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn update_and_delete_template() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (_, created) = send_json(
+        ctx.router.clone(),
+        "POST",
+        "/api/card-templates",
+        user_story(),
+    )
+    .await;
+    let uri = format!(
+        "/api/card-templates/{}",
+        created["template_id"].as_str().unwrap()
+    );
+
+    let (status, updated) = send_json(
+        ctx.router.clone(),
+        "PUT",
+        &uri,
+        serde_json::json!({ "name": "API Endpoint", "card_type": "plan", "title": "GET /" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["name"], "API Endpoint");
+    assert_eq!(updated["body"], serde_json::Value::Null);
+
+    let (status, _) = send_json(ctx.router.clone(), "DELETE", &uri, serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = get_text(ctx.router.clone(), &uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn selecting_template_prefills_card_form() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (_, created) = send_json(
+        ctx.router.clone(),
+        "POST",
+        "/api/card-templates",
+        user_story(),
+    )
+    .await;
+    let template_id = created["template_id"].as_str().unwrap();
+
+    // Without a selection the picker lists the template but the form is blank.
+    let (status, blank) = get_text(
+        ctx.router.clone(),
+        &format!("/web/specs/{}/cards/new", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(blank.contains("id=\"card-template\""));
+    assert!(blank.contains("User Story"));
+    assert!(blank.contains("name=\"title\" value=\"\""));
+
+    let (status, filled) = get_text(
+        ctx.router.clone(),
+        &format!(
+            "/web/specs/{}/cards/new?template={}",
+            ctx.spec_id, template_id
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(filled.contains("value=\"As a user, I want\""));
+    assert!(filled.contains("<option value=\"task\" selected>"));
+    assert!(filled.contains("**Acceptance criteria**"));
+    assert!(filled.contains(&format!("<option value=\"{}\" selected>", template_id)));
+}
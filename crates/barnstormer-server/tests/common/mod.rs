@@ -7,12 +7,16 @@ use std::sync::Arc;
 
 use axum::Router;
 use axum::body::Body;
-use http::Request;
+use http::{Request, StatusCode};
 use tempfile::TempDir;
 use tower::ServiceExt;
 use ulid::Ulid;
 
+use barnstormer_agent::testing::StubLlmClient;
+use barnstormer_agent::{AgentRunner, SwarmOrchestrator};
 use barnstormer_core::{Command, SpecPhase};
+use barnstormer_server::app_state::SwarmHandle;
+use barnstormer_server::attachment_summarizer::ServerSummarizer;
 use barnstormer_server::{AppState, ProviderStatus, SharedState, create_router};
 
 /// Context returned from `setup_*` helpers. Holds the assembled router, the
@@ -31,6 +35,7 @@ fn empty_provider_status() -> ProviderStatus {
         default_model: None,
         providers: vec![],
         any_available: false,
+        model_warning: None,
     }
 }
 
@@ -306,6 +311,50 @@ pub async fn setup_with_attachment_bytes(
     }
 }
 
+/// GET a JSON endpoint and return its status with the parsed body.
+pub async fn get_json(router: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let resp = router
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = resp.status();
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+/// Register a swarm of `agents` on a stub LLM client for the ctx's spec, as
+/// if agents had been started, and return it. Its task does nothing, so the
+/// agents only change when the test drives them.
+pub async fn spawn_test_swarm(
+    ctx: &TestCtx,
+    agents: Vec<AgentRunner>,
+) -> Arc<tokio::sync::Mutex<SwarmOrchestrator>> {
+    let handle = ctx.state.actors.read().await[&ctx.spec_id].clone();
+    let swarm = SwarmOrchestrator::with_agents(
+        ctx.spec_id,
+        handle,
+        agents,
+        Arc::new(StubLlmClient::done()),
+        "test-model".to_string(),
+        ctx._tmp.path().to_path_buf(),
+        Arc::new(ServerSummarizer {
+            home: ctx._tmp.path().to_path_buf(),
+            provider: "anthropic".to_string(),
+        }),
+    );
+    let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+    ctx.state.swarms.write().await.insert(
+        ctx.spec_id,
+        SwarmHandle {
+            swarm: Arc::clone(&swarm),
+            task: tokio::spawn(async {}),
+        },
+    );
+    swarm
+}
+
 /// Snapshot the test-only `SUMMARIZE_SPAWN_COUNT` atomic. Lets event-driven
 /// tests (notes-update fan-out, manual Resummarize) assert that a summarize
 /// task was actually spawned without standing up a real LLM client.
@@ -18,6 +18,7 @@ fn empty_provider_status() -> ProviderStatus {
         default_model: None,
         providers: vec![],
         any_available: false,
+        model_warning: None,
     }
 }
 
@@ -466,3 +467,85 @@ async fn create_spec_with_no_files_works_as_before() {
         "no attachments when no files were sent"
     );
 }
+
+#[tokio::test]
+async fn create_spec_with_empty_description_rerenders_form_with_error() {
+    let (state, _tmp) = fresh_state();
+    let app = create_router(Arc::clone(&state), None);
+
+    let (ct, body) = common::multipart_description_body("   ");
+
+    let resp = app
+        .oneshot(
+            Request::post("/web/specs")
+                .header("content-type", ct)
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .expect("request");
+
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        resp.headers().get("hx-push-url").unwrap(),
+        "false",
+        "a rejected submission must not push the spec URL"
+    );
+    let html = String::from_utf8(
+        axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec(),
+    )
+    .unwrap();
+    assert!(
+        html.contains("create-spec-form"),
+        "form should be re-rendered"
+    );
+    assert!(html.contains("field-error"), "inline field error expected");
+    assert!(html.contains("Describe what you want to build."));
+
+    assert!(
+        state.actors.read().await.is_empty(),
+        "no spec should be created for invalid input"
+    );
+    assert!(
+        !state.barnstormer_home.join("specs").exists()
+            || std::fs::read_dir(state.barnstormer_home.join("specs"))
+                .unwrap()
+                .next()
+                .is_none(),
+        "no spec directory should be written for invalid input"
+    );
+}
+
+#[tokio::test]
+async fn create_spec_with_overlong_description_keeps_input_and_creates_nothing() {
+    let (state, _tmp) = fresh_state();
+    let app = create_router(Arc::clone(&state), None);
+
+    let long = "x".repeat(20_001);
+    let (ct, body) = common::multipart_description_body(&long);
+
+    let resp = app
+        .oneshot(
+            Request::post("/web/specs")
+                .header("content-type", ct)
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .expect("request");
+
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let html = String::from_utf8(
+        axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec(),
+    )
+    .unwrap();
+    assert!(html.contains("20000 characters"));
+    assert!(html.contains(&long), "submitted text should be preserved");
+    assert!(state.actors.read().await.is_empty());
+}
@@ -0,0 +1,90 @@
+// ABOUTME: Integration tests for bounded agent runs via POST /web/specs/{id}/agents/run-for.
+// ABOUTME: Covers run-length validation and resuming a paused swarm with a deadline.
+
+use std::sync::Arc;
+
+use axum::body::Body;
+use barnstormer_agent::SwarmOrchestrator;
+use barnstormer_agent::testing::StubLlmClient;
+use barnstormer_server::app_state::SwarmHandle;
+use barnstormer_server::attachment_summarizer::ServerSummarizer;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+
+mod common;
+
+async fn post_minutes(router: axum::Router, uri: &str, minutes: &str) -> StatusCode {
+    router
+        .oneshot(
+            Request::post(uri)
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(format!("minutes={}", minutes)))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn run_for_rejects_out_of_range_lengths() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let uri = format!("/web/specs/{}/agents/run-for", ctx.spec_id);
+    for minutes in ["0", "241"] {
+        assert_eq!(
+            post_minutes(ctx.router.clone(), &uri, minutes).await,
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
+
+#[tokio::test]
+async fn run_for_resumes_a_paused_swarm_until_the_deadline() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let handle = ctx.state.actors.read().await[&ctx.spec_id].clone();
+    let swarm = SwarmOrchestrator::with_agents(
+        ctx.spec_id,
+        handle.clone(),
+        Vec::new(),
+        Arc::new(StubLlmClient::done()),
+        "test-model".to_string(),
+        ctx._tmp.path().to_path_buf(),
+        Arc::new(ServerSummarizer {
+            home: ctx._tmp.path().to_path_buf(),
+            provider: "anthropic".to_string(),
+        }),
+    );
+    swarm.pause();
+    let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+    ctx.state.swarms.write().await.insert(
+        ctx.spec_id,
+        SwarmHandle {
+            swarm: Arc::clone(&swarm),
+            task: tokio::spawn(async {}),
+        },
+    );
+
+    let status = post_minutes(
+        ctx.router.clone(),
+        &format!("/web/specs/{}/agents/run-for", ctx.spec_id),
+        "10",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let swarm = swarm.lock().await;
+    assert!(!swarm.is_paused());
+    let remaining = swarm
+        .run_deadline
+        .expect("deadline should be set")
+        .saturating_duration_since(tokio::time::Instant::now());
+    assert!(remaining > std::time::Duration::from_secs(9 * 60));
+    assert!(
+        handle
+            .read_state()
+            .await
+            .transcript
+            .iter()
+            .any(|m| m.content.contains("pause in 10 minutes"))
+    );
+}
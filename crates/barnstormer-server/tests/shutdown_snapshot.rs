@@ -0,0 +1,214 @@
+// ABOUTME: Integration tests for the clean-shutdown pass — swarms stop once in-flight steps finish,
+// ABOUTME: persisters flush their queued events, and every live spec gets a snapshot at its last event.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use barnstormer_agent::testing::StubLlmClient;
+use barnstormer_agent::{AgentRole, AgentRunner, SwarmOrchestrator};
+use barnstormer_core::{Command, SpecState};
+use barnstormer_server::app_state::SwarmHandle;
+use barnstormer_server::attachment_summarizer::ServerSummarizer;
+use barnstormer_server::shutdown::{close_event_persisters, pause_all_swarms, snapshot_all_specs};
+use barnstormer_server::{AppState, ProviderStatus};
+use barnstormer_store::load_latest_snapshot;
+use tempfile::TempDir;
+use ulid::Ulid;
+
+fn make_state(tmp: &TempDir) -> Arc<AppState> {
+    Arc::new(AppState::new(
+        tmp.path().to_path_buf(),
+        ProviderStatus {
+            default_provider: "anthropic".to_string(),
+            default_model: None,
+            providers: vec![],
+            any_available: false,
+            model_warning: None,
+        },
+    ))
+}
+
+async fn add_spec(state: &Arc<AppState>, title: &str, extra_cards: usize) -> Ulid {
+    let spec_id = Ulid::new();
+    let handle = barnstormer_core::spawn(spec_id, SpecState::new());
+    handle
+        .send_command(Command::CreateSpec {
+            title: title.to_string(),
+            one_liner: "Shutdown test".to_string(),
+            goal: "Survive a restart".to_string(),
+        })
+        .await
+        .unwrap();
+    for i in 0..extra_cards {
+        handle
+            .send_command(Command::CreateCard {
+                card_type: "idea".to_string(),
+                title: format!("Card {i}"),
+                body: None,
+                lane: None,
+                created_by: "human".to_string(),
+                source_attachment_id: None,
+            })
+            .await
+            .unwrap();
+    }
+    state.actors.write().await.insert(spec_id, handle);
+    spec_id
+}
+
+#[tokio::test]
+async fn every_spec_gets_snapshot_at_its_last_event() {
+    let tmp = TempDir::new().unwrap();
+    let state = make_state(&tmp);
+    let a = add_spec(&state, "Alpha", 0).await;
+    let b = add_spec(&state, "Beta", 3).await;
+
+    let missed = snapshot_all_specs(&state, Duration::from_secs(5)).await;
+    assert!(missed.is_empty(), "unexpected misses: {missed:?}");
+
+    for spec_id in [a, b] {
+        let expected = {
+            let actors = state.actors.read().await;
+            actors[&spec_id].read_state().await.last_event_id
+        };
+        let dir = tmp
+            .path()
+            .join("specs")
+            .join(spec_id.to_string())
+            .join("snapshots");
+        let snap = load_latest_snapshot(&dir)
+            .unwrap()
+            .expect("snapshot should exist");
+        assert_eq!(snap.last_event_id, expected);
+        assert_eq!(snap.state.last_event_id, expected);
+    }
+}
+
+#[tokio::test]
+async fn specs_that_opt_out_are_skipped() {
+    let tmp = TempDir::new().unwrap();
+    let state = make_state(&tmp);
+    let kept = add_spec(&state, "Alpha", 0).await;
+    let skipped = add_spec(&state, "Beta", 1).await;
+    state.actors.read().await[&skipped]
+        .send_command(Command::SetSnapshotOnShutdown { enabled: false })
+        .await
+        .unwrap();
+
+    let missed = snapshot_all_specs(&state, Duration::from_secs(5)).await;
+    assert!(missed.is_empty(), "unexpected misses: {missed:?}");
+
+    let snapshots = |spec_id: Ulid| {
+        let dir = tmp
+            .path()
+            .join("specs")
+            .join(spec_id.to_string())
+            .join("snapshots");
+        load_latest_snapshot(&dir).unwrap()
+    };
+    assert!(snapshots(kept).is_some());
+    assert!(snapshots(skipped).is_none());
+}
+
+#[tokio::test]
+async fn zero_timeout_reports_every_spec_as_missed() {
+    let tmp = TempDir::new().unwrap();
+    let state = make_state(&tmp);
+    let a = add_spec(&state, "Alpha", 1).await;
+
+    let missed = snapshot_all_specs(&state, Duration::ZERO).await;
+    assert_eq!(missed, vec![a]);
+}
+
+#[tokio::test]
+async fn pause_all_swarms_waits_for_in_flight_steps() {
+    let tmp = TempDir::new().unwrap();
+    let state = make_state(&tmp);
+    let spec_id = add_spec(&state, "Alpha", 0).await;
+    let handle = state.actors.read().await[&spec_id].clone();
+    let swarm = SwarmOrchestrator::with_agents(
+        spec_id,
+        handle,
+        vec![AgentRunner::new(spec_id, AgentRole::Manager)],
+        Arc::new(StubLlmClient::done()),
+        "test-model".to_string(),
+        tmp.path().to_path_buf(),
+        Arc::new(ServerSummarizer {
+            home: tmp.path().to_path_buf(),
+            provider: "anthropic".to_string(),
+        }),
+    );
+    let swarm = Arc::new(tokio::sync::Mutex::new(swarm));
+    state.swarms.write().await.insert(
+        spec_id,
+        SwarmHandle {
+            swarm: Arc::clone(&swarm),
+            task: tokio::spawn(std::future::pending()),
+        },
+    );
+
+    // Take the runner out of its slot the way a running step does.
+    let runner = swarm.lock().await.agents[0].take().unwrap();
+    let busy = pause_all_swarms(&state, Duration::from_millis(100)).await;
+    assert_eq!(busy, vec![spec_id]);
+    assert!(swarm.lock().await.is_paused());
+
+    let finisher = Arc::clone(&swarm);
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        finisher.lock().await.agents[0] = Some(runner);
+    });
+    let busy = pause_all_swarms(&state, Duration::from_secs(5)).await;
+    assert!(busy.is_empty(), "step should have finished: {busy:?}");
+    assert_eq!(swarm.lock().await.steps_in_flight(), 0);
+    assert!(state.swarms.read().await[&spec_id].task.is_finished());
+}
+
+#[tokio::test]
+async fn close_event_persisters_writes_queued_events_first() {
+    let tmp = TempDir::new().unwrap();
+    let state = make_state(&tmp);
+    let spec_id = Ulid::new();
+    let handle = barnstormer_core::spawn(spec_id, SpecState::new());
+    let persister = barnstormer_server::web::spawn_event_persister(
+        &handle,
+        spec_id,
+        tmp.path(),
+        Arc::clone(&state.swarms),
+        state.persister_retry,
+        state.persistence_health.clone(),
+        Arc::clone(&state.metrics),
+        None,
+        state.snapshot_keep,
+    );
+    state
+        .event_persisters
+        .write()
+        .await
+        .insert(spec_id, persister);
+    handle
+        .send_command(Command::CreateSpec {
+            title: "Alpha".to_string(),
+            one_liner: "Shutdown test".to_string(),
+            goal: "Keep every event".to_string(),
+        })
+        .await
+        .unwrap();
+    let last_event_id = handle.read_state().await.last_event_id;
+
+    let unfinished = close_event_persisters(&state, Duration::from_secs(5)).await;
+    assert!(
+        unfinished.is_empty(),
+        "unexpected stragglers: {unfinished:?}"
+    );
+    assert!(state.event_persisters.read().await.is_empty());
+
+    let log = std::fs::read_to_string(
+        tmp.path()
+            .join("specs")
+            .join(spec_id.to_string())
+            .join("events.jsonl"),
+    )
+    .unwrap();
+    assert_eq!(log.lines().count() as u64, last_event_id);
+}
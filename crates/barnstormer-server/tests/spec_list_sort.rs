@@ -0,0 +1,141 @@
+// ABOUTME: Integration tests for spec list ordering on /api/specs and /web/specs.
+// ABOUTME: The default is most recently updated first; ?sort=created and ?sort=title reorder it.
+
+use std::time::Duration;
+
+use axum::body::Body;
+use barnstormer_core::Command;
+use http::{Request, StatusCode};
+use tower::ServiceExt;
+use ulid::Ulid;
+
+mod common;
+
+async fn get(router: axum::Router, uri: &str) -> String {
+    let resp = router
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+async fn listed_titles(router: axum::Router, uri: &str) -> Vec<String> {
+    let specs: serde_json::Value = serde_json::from_str(&get(router, uri).await).unwrap();
+    specs
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+async fn create_spec(router: axum::Router, title: &str) -> Ulid {
+    let resp = router
+        .oneshot(
+            Request::post("/api/specs")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::json!({ "title": title, "one_liner": "o", "goal": "g" })
+                        .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    body["spec_id"].as_str().unwrap().parse().unwrap()
+}
+
+async fn rename(ctx: &common::TestCtx, spec_id: Ulid, title: &str) {
+    let handle = {
+        let actors = ctx.state.actors.read().await;
+        actors.get(&spec_id).unwrap().clone()
+    };
+    handle
+        .send_command(Command::UpdateSpecCore {
+            title: Some(title.to_string()),
+            one_liner: None,
+            goal: None,
+            description: None,
+            constraints: None,
+            success_criteria: None,
+            risks: None,
+            notes: None,
+        })
+        .await
+        .unwrap();
+}
+
+/// Three specs created oldest to newest as Bravo, Charlie, Alpha, then Bravo
+/// updated last, so updated/created/title orders all differ.
+async fn setup_three_specs() -> common::TestCtx {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    rename(&ctx, ctx.spec_id, "Bravo").await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    create_spec(ctx.router.clone(), "Charlie").await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    create_spec(ctx.router.clone(), "Alpha").await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    rename(&ctx, ctx.spec_id, "Bravo").await;
+    ctx
+}
+
+#[tokio::test]
+async fn spec_list_defaults_to_most_recently_updated_first() {
+    let ctx = setup_three_specs().await;
+
+    assert_eq!(
+        listed_titles(ctx.router.clone(), "/api/specs").await,
+        vec!["Bravo", "Alpha", "Charlie"]
+    );
+    assert_eq!(
+        listed_titles(ctx.router.clone(), "/api/specs?sort=updated").await,
+        vec!["Bravo", "Alpha", "Charlie"]
+    );
+
+    // The HTML spec list uses the same order.
+    let html = get(ctx.router.clone(), "/web/specs").await;
+    let positions: Vec<usize> = ["Bravo", "Alpha", "Charlie"]
+        .iter()
+        .map(|t| html.find(t).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]), "{html}");
+}
+
+#[tokio::test]
+async fn spec_list_sorts_by_created_or_title_on_request() {
+    let ctx = setup_three_specs().await;
+
+    assert_eq!(
+        listed_titles(ctx.router.clone(), "/api/specs?sort=created").await,
+        vec!["Alpha", "Charlie", "Bravo"]
+    );
+    assert_eq!(
+        listed_titles(ctx.router.clone(), "/api/specs?sort=title").await,
+        vec!["Alpha", "Bravo", "Charlie"]
+    );
+}
+
+#[tokio::test]
+async fn spec_list_rejects_unknown_sort() {
+    let ctx = setup_three_specs().await;
+    let resp = ctx
+        .router
+        .clone()
+        .oneshot(
+            Request::get("/api/specs?sort=size")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,84 @@
+// ABOUTME: Integration tests for cumulative token usage at /api/specs/{id}/usage.
+// ABOUTME: Covers an unused spec, priced and unpriced models, and unknown or malformed spec IDs.
+
+use barnstormer_core::Command;
+use http::StatusCode;
+use ulid::Ulid;
+
+mod common;
+
+use common::get_json;
+
+async fn record_usage(ctx: &common::TestCtx, role: &str, model: &str, input: u64, output: u64) {
+    let handle = ctx.state.actors.read().await[&ctx.spec_id].clone();
+    handle
+        .send_command(Command::RecordUsage {
+            role: role.to_string(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn usage_is_zero_before_agents_run() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (status, body) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/usage", ctx.spec_id),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["input_tokens"], 0);
+    assert_eq!(body["output_tokens"], 0);
+    assert_eq!(body["estimated_cost"], 0.0);
+    assert_eq!(body["roles"], serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn usage_sums_steps_and_prices_them() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    record_usage(&ctx, "manager", "claude-sonnet-4-5", 600_000, 40_000).await;
+    record_usage(&ctx, "manager", "claude-sonnet-4-5", 400_000, 60_000).await;
+
+    let uri = format!("/api/specs/{}/usage", ctx.spec_id);
+    let (status, body) = get_json(ctx.router.clone(), &uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["provider"], "anthropic");
+    assert_eq!(body["input_tokens"], 1_000_000);
+    assert_eq!(body["output_tokens"], 100_000);
+    assert_eq!(body["roles"][0]["role"], "manager");
+    assert_eq!(body["roles"][0]["steps"], 2);
+    // $3 per million input tokens plus $15 per million output tokens.
+    let cost = body["estimated_cost"].as_f64().unwrap();
+    assert!((cost - 4.5).abs() < 1e-9, "{}", cost);
+
+    // One unpriced model makes the total unknown; priced roles keep theirs.
+    record_usage(&ctx, "planner", "my-proxy-model", 1_000, 100).await;
+    let (_, body) = get_json(ctx.router.clone(), &uri).await;
+    assert_eq!(body["input_tokens"], 1_001_000);
+    assert!(body["estimated_cost"].is_null());
+    let planner = body["roles"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["role"] == "planner")
+        .unwrap();
+    assert!(planner["estimated_cost"].is_null());
+}
+
+#[tokio::test]
+async fn usage_rejects_unknown_and_malformed_specs() {
+    let ctx = common::setup_with_spec_in_brainstorming().await;
+    let (status, _) = get_json(
+        ctx.router.clone(),
+        &format!("/api/specs/{}/usage", Ulid::new()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = get_json(ctx.router.clone(), "/api/specs/not-a-ulid/usage").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
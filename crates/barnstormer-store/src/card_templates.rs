@@ -0,0 +1,253 @@
+// ABOUTME: Named, reusable card templates stored per barnstormer home in a JSON file.
+// ABOUTME: Provides CRUD over the template list with atomic writes so a crash never truncates it.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ulid::Ulid;
+
+/// File name of the template library inside a barnstormer home.
+pub const CARD_TEMPLATES_FILE: &str = "card_templates.json";
+
+/// Errors that can occur while reading or changing card templates.
+#[derive(Debug, Error)]
+pub enum CardTemplateError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("card template not found: {0}")]
+    NotFound(Ulid),
+
+    #[error("a card template named '{0}' already exists")]
+    DuplicateName(String),
+}
+
+/// A named starting point for new cards, e.g. "API Endpoint" or "User Story".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardTemplate {
+    pub template_id: Ulid,
+    pub name: String,
+    pub card_type: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The user-editable fields of a card template, used for create and update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardTemplateFields {
+    pub name: String,
+    pub card_type: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// Card template library backed by a single JSON file. Each call reads the
+/// file afresh; callers that mutate concurrently must serialize access.
+#[derive(Debug, Clone)]
+pub struct CardTemplateStore {
+    path: PathBuf,
+}
+
+impl CardTemplateStore {
+    /// Create a store backed by the given file. Nothing is read or written
+    /// until the first operation; a missing file is an empty library.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create a store at the standard location inside a barnstormer home.
+    pub fn for_home(home: &Path) -> Self {
+        Self::new(home.join(CARD_TEMPLATES_FILE))
+    }
+
+    /// All templates, sorted by name (case-insensitive).
+    pub fn list(&self) -> Result<Vec<CardTemplate>, CardTemplateError> {
+        let mut templates = self.load()?;
+        templates.sort_by_key(|t| t.name.to_lowercase());
+        Ok(templates)
+    }
+
+    /// Look up a single template by ID.
+    pub fn get(&self, template_id: Ulid) -> Result<CardTemplate, CardTemplateError> {
+        self.load()?
+            .into_iter()
+            .find(|t| t.template_id == template_id)
+            .ok_or(CardTemplateError::NotFound(template_id))
+    }
+
+    /// Add a new template. Names must be unique (case-insensitive).
+    pub fn create(&self, fields: CardTemplateFields) -> Result<CardTemplate, CardTemplateError> {
+        let mut templates = self.load()?;
+        ensure_unique_name(&templates, &fields.name, None)?;
+
+        let now = Utc::now();
+        let template = CardTemplate {
+            template_id: Ulid::new(),
+            name: fields.name,
+            card_type: fields.card_type,
+            title: fields.title,
+            body: fields.body,
+            created_at: now,
+            updated_at: now,
+        };
+        templates.push(template.clone());
+        self.save(&templates)?;
+        Ok(template)
+    }
+
+    /// Replace the fields of an existing template.
+    pub fn update(
+        &self,
+        template_id: Ulid,
+        fields: CardTemplateFields,
+    ) -> Result<CardTemplate, CardTemplateError> {
+        let mut templates = self.load()?;
+        ensure_unique_name(&templates, &fields.name, Some(template_id))?;
+
+        let template = templates
+            .iter_mut()
+            .find(|t| t.template_id == template_id)
+            .ok_or(CardTemplateError::NotFound(template_id))?;
+        template.name = fields.name;
+        template.card_type = fields.card_type;
+        template.title = fields.title;
+        template.body = fields.body;
+        template.updated_at = Utc::now();
+        let updated = template.clone();
+
+        self.save(&templates)?;
+        Ok(updated)
+    }
+
+    /// Remove a template.
+    pub fn delete(&self, template_id: Ulid) -> Result<(), CardTemplateError> {
+        let mut templates = self.load()?;
+        let before = templates.len();
+        templates.retain(|t| t.template_id != template_id);
+        if templates.len() == before {
+            return Err(CardTemplateError::NotFound(template_id));
+        }
+        self.save(&templates)
+    }
+
+    fn load(&self) -> Result<Vec<CardTemplate>, CardTemplateError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let raw = fs::read_to_string(&self.path)?;
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    /// Atomic write: write to a sibling .tmp file, fsync, rename.
+    fn save(&self, templates: &[CardTemplate]) -> Result<(), CardTemplateError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(templates)?;
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+fn ensure_unique_name(
+    templates: &[CardTemplate],
+    name: &str,
+    except: Option<Ulid>,
+) -> Result<(), CardTemplateError> {
+    let clash = templates
+        .iter()
+        .any(|t| Some(t.template_id) != except && t.name.eq_ignore_ascii_case(name));
+    if clash {
+        return Err(CardTemplateError::DuplicateName(name.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fields(name: &str) -> CardTemplateFields {
+        CardTemplateFields {
+            name: name.to_string(),
+            card_type: "task".to_string(),
+            title: format!("{name}: "),
+            body: Some("## Details".to_string()),
+        }
+    }
+
+    #[test]
+    fn missing_file_is_empty_library() {
+        let dir = TempDir::new().unwrap();
+        let store = CardTemplateStore::for_home(dir.path());
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn create_persists_and_lists_sorted_by_name() {
+        let dir = TempDir::new().unwrap();
+        let store = CardTemplateStore::for_home(dir.path());
+        store.create(fields("User Story")).unwrap();
+        let api = store.create(fields("api endpoint")).unwrap();
+
+        // A fresh store over the same file sees both templates.
+        let reopened = CardTemplateStore::for_home(dir.path());
+        let names: Vec<String> = reopened
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["api endpoint", "User Story"]);
+        assert_eq!(reopened.get(api.template_id).unwrap(), api);
+    }
+
+    #[test]
+    fn duplicate_names_are_rejected() {
+        let dir = TempDir::new().unwrap();
+        let store = CardTemplateStore::for_home(dir.path());
+        store.create(fields("User Story")).unwrap();
+        let err = store.create(fields("user story")).unwrap_err();
+        assert!(matches!(err, CardTemplateError::DuplicateName(_)));
+    }
+
+    #[test]
+    fn update_and_delete() {
+        let dir = TempDir::new().unwrap();
+        let store = CardTemplateStore::for_home(dir.path());
+        let t = store.create(fields("Bug")).unwrap();
+
+        // Renaming to its own name is not a clash.
+        let mut changed = fields("Bug");
+        changed.card_type = "risk".to_string();
+        let updated = store.update(t.template_id, changed).unwrap();
+        assert_eq!(updated.card_type, "risk");
+        assert_eq!(updated.created_at, t.created_at);
+
+        store.delete(t.template_id).unwrap();
+        assert!(store.list().unwrap().is_empty());
+        assert!(matches!(
+            store.delete(t.template_id),
+            Err(CardTemplateError::NotFound(_))
+        ));
+    }
+}
@@ -1,13 +1,18 @@
 // ABOUTME: Append-only JSONL event log for durable event storage.
-// ABOUTME: Provides crash-safe append, sequential replay, and repair for truncated files.
+// ABOUTME: Provides crash-safe append, sequential replay, repair for truncated files, and compaction.
 
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 
-use barnstormer_core::Event;
+use barnstormer_core::{Event, SpecState};
+use chrono::Utc;
 use thiserror::Error;
 
+use crate::snapshot::{
+    SnapshotData, SnapshotError, load_latest_snapshot, prune_after_save, save_snapshot,
+};
+
 /// Errors that can occur during JSONL log operations.
 #[derive(Debug, Error)]
 pub enum JsonlError {
@@ -16,6 +21,12 @@ pub enum JsonlError {
 
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("snapshot error: {0}")]
+    Snapshot(#[from] SnapshotError),
+
+    #[error("no snapshot covers events through {0}")]
+    NotSnapshotted(u64),
 }
 
 /// An append-only JSONL event log backed by a file.
@@ -75,6 +86,28 @@ impl JsonlLog {
         Ok(events)
     }
 
+    /// Like `replay`, but skips lines that do not parse (the ones `repair`
+    /// would drop) instead of failing. Returns the events in order and the
+    /// number of lines skipped. Leaves the file untouched.
+    pub fn replay_valid(path: &Path) -> Result<(Vec<Event>, usize), JsonlError> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut events = Vec::new();
+        let mut skipped = 0;
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Event>(&line) {
+                Ok(event) => events.push(event),
+                Err(_) => skipped += 1,
+            }
+        }
+
+        Ok((events, skipped))
+    }
+
     /// Repair a potentially corrupted JSONL file by keeping only complete,
     /// parseable lines and truncating any partial trailing data.
     /// Uses atomic temp-file + fsync + rename to prevent data loss on crash.
@@ -96,30 +129,105 @@ impl JsonlLog {
         }
 
         let count = valid_lines.len();
+        rewrite(path, &valid_lines)?;
+        Ok(count)
+    }
 
-        // Write valid lines to a temp file, fsync, then atomically rename
-        let tmp_path = path.with_extension("jsonl.tmp");
-        let mut tmp_file = File::create(&tmp_path)?;
-        for line in &valid_lines {
-            writeln!(tmp_file, "{}", line)?;
+    /// Compact the log against `state`, which must reflect every event up to
+    /// its `last_event_id`. Saves `state` as a snapshot in the sibling
+    /// `snapshots/` directory, then rewrites the log to keep only events from
+    /// `last_event_id` on, so recovery replays the tail instead of the whole
+    /// history. The snapshot is written before the log is touched and the log
+    /// is replaced by atomic rename, so an interrupted compaction leaves
+    /// either the old log or the compacted one, never a partial file. Older
+    /// snapshots are pruned down to `keep`.
+    pub fn compact(&mut self, state: &SpecState, keep: usize) -> Result<(), JsonlError> {
+        if state.last_event_id == 0 {
+            return Ok(());
         }
-        tmp_file.sync_all()?;
-
-        // Atomic rename over the original
-        fs::rename(&tmp_path, path)?;
-
-        // Fsync the parent directory to ensure the rename metadata is durable.
-        // Without this, a crash after rename could leave the directory entry
-        // pointing at the old file. Best-effort: if the fsync fails, the
-        // rename already succeeded and the data is consistent.
-        if let Some(parent) = path.parent()
-            && let Ok(dir) = File::open(parent)
-        {
-            let _ = dir.sync_all();
+        let snapshots_dir = self
+            .path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("snapshots");
+        // Compaction has no swarm to ask, so carry the newest snapshot's
+        // agent memory forward rather than dropping it.
+        let agent_contexts = load_latest_snapshot(&snapshots_dir)?
+            .map(|snap| snap.agent_contexts)
+            .unwrap_or_default();
+        save_snapshot(
+            &snapshots_dir,
+            &SnapshotData {
+                state: state.clone(),
+                last_event_id: state.last_event_id,
+                agent_contexts,
+                saved_at: Utc::now(),
+            },
+        )?;
+        prune_after_save(&snapshots_dir, keep);
+
+        // Keep the event at `last_event_id` so the log still records where
+        // the snapshot left off; recovery skips it since the snapshot has it.
+        self.compact_through(state.last_event_id - 1)
+    }
+
+    /// Rewrite the log without the events at or before `up_to_event_id`.
+    /// Refuses with `NotSnapshotted`, leaving the log alone, unless the
+    /// newest snapshot in the sibling `snapshots/` directory covers them,
+    /// since recovery would have nothing to rebuild them from. The log is
+    /// replaced by atomic rename, as in `compact`.
+    pub fn compact_through(&mut self, up_to_event_id: u64) -> Result<(), JsonlError> {
+        let snapshots_dir = self
+            .path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join("snapshots");
+        let covered = load_latest_snapshot(&snapshots_dir)?.map(|snap| snap.last_event_id);
+        if up_to_event_id > 0 && covered.is_none_or(|id| id < up_to_event_id) {
+            return Err(JsonlError::NotSnapshotted(up_to_event_id));
         }
 
-        Ok(count)
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut kept_lines = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if let Ok(event) = serde_json::from_str::<Event>(&line)
+                && event.event_id > up_to_event_id
+            {
+                kept_lines.push(line);
+            }
+        }
+        rewrite(&self.path, &kept_lines)?;
+
+        // The open handle still points at the replaced file.
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+        Ok(())
+    }
+}
+
+/// Replace the file at `path` with `lines`: write a temp file, fsync it,
+/// then atomically rename it over the original.
+fn rewrite(path: &Path, lines: &[String]) -> Result<(), JsonlError> {
+    let tmp_path = path.with_extension("jsonl.tmp");
+    let mut tmp_file = File::create(&tmp_path)?;
+    for line in lines {
+        writeln!(tmp_file, "{}", line)?;
     }
+    tmp_file.sync_all()?;
+
+    // Atomic rename over the original
+    fs::rename(&tmp_path, path)?;
+
+    // Fsync the parent directory to ensure the rename metadata is durable.
+    // Without this, a crash after rename could leave the directory entry
+    // pointing at the old file. Best-effort: if the fsync fails, the
+    // rename already succeeded and the data is consistent.
+    if let Some(parent) = path.parent()
+        && let Ok(dir) = File::open(parent)
+    {
+        let _ = dir.sync_all();
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -221,6 +329,26 @@ mod tests {
         assert_eq!(events[1].event_id, 2);
     }
 
+    #[test]
+    fn replay_valid_skips_partial_lines_without_rewriting() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("torn.jsonl");
+
+        let mut log = JsonlLog::open(&path).unwrap();
+        log.append(&make_spec_created_event(1)).unwrap();
+        drop(log);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, r#"{{"event_id":2,"spec"#).unwrap();
+        drop(file);
+        let on_disk = fs::read_to_string(&path).unwrap();
+
+        let (events, skipped) = JsonlLog::replay_valid(&path).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(skipped, 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), on_disk);
+        assert!(JsonlLog::replay(&path).is_err());
+    }
+
     #[test]
     fn repair_no_op_on_clean_file() {
         let dir = TempDir::new().unwrap();
@@ -239,6 +367,89 @@ mod tests {
         assert_eq!(events.len(), 3);
     }
 
+    #[test]
+    fn compact_snapshots_state_and_truncates_log() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        let mut log = JsonlLog::open(&path).unwrap();
+        let mut state = SpecState::new();
+        for event_id in 1..=5 {
+            let event = make_spec_created_event(event_id);
+            log.append(&event).unwrap();
+            state.apply(&event);
+        }
+        // An event the state has not seen yet must survive compaction.
+        let mut behind = state.clone();
+        behind.last_event_id = 4;
+
+        log.compact(&behind, crate::DEFAULT_SNAPSHOT_KEEP).unwrap();
+
+        let ids: Vec<u64> = JsonlLog::replay(&path)
+            .unwrap()
+            .iter()
+            .map(|e| e.event_id)
+            .collect();
+        assert_eq!(ids, vec![4, 5]);
+        let snap = load_latest_snapshot(&dir.path().join("snapshots"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(snap.last_event_id, 4);
+        assert!(!dir.path().join("events.jsonl.tmp").exists());
+
+        // Appends after compaction land in the compacted file.
+        log.append(&make_spec_created_event(6)).unwrap();
+        let events = JsonlLog::replay(&path).unwrap();
+        assert_eq!(events.last().unwrap().event_id, 6);
+    }
+
+    #[test]
+    fn compact_through_requires_a_covering_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        let mut log = JsonlLog::open(&path).unwrap();
+        let mut state = SpecState::new();
+        for event_id in 1..=5 {
+            let event = make_spec_created_event(event_id);
+            log.append(&event).unwrap();
+            if event_id <= 3 {
+                state.apply(&event);
+            }
+        }
+
+        // Nothing covers the events yet, so the log is left whole.
+        assert!(matches!(
+            log.compact_through(3),
+            Err(JsonlError::NotSnapshotted(3))
+        ));
+        assert_eq!(JsonlLog::replay(&path).unwrap().len(), 5);
+
+        let snapshots_dir = dir.path().join("snapshots");
+        save_snapshot(
+            &snapshots_dir,
+            &SnapshotData {
+                state,
+                last_event_id: 3,
+                agent_contexts: Default::default(),
+                saved_at: Utc::now(),
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            log.compact_through(4),
+            Err(JsonlError::NotSnapshotted(4))
+        ));
+
+        log.compact_through(3).unwrap();
+        let ids: Vec<u64> = JsonlLog::replay(&path)
+            .unwrap()
+            .iter()
+            .map(|e| e.event_id)
+            .collect();
+        assert_eq!(ids, vec![4, 5]);
+    }
+
     #[test]
     fn append_is_crash_safe() {
         let dir = TempDir::new().unwrap();
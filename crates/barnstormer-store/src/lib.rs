@@ -1,14 +1,29 @@
 // ABOUTME: Persistence layer for barnstormer, handling event storage and state reconstruction.
-// ABOUTME: Provides JSONL event log, snapshot management, SQLite index, crash recovery, and storage management.
+// ABOUTME: Provides JSONL event log, snapshot management, SQLite index, crash recovery, storage management, usage stats, card templates, and UI preferences.
 
+pub mod card_templates;
 pub mod jsonl;
 pub mod manager;
 pub mod recovery;
 pub mod snapshot;
 pub mod sqlite;
+pub mod stats;
+pub mod ui_prefs;
 
+pub use card_templates::{CardTemplate, CardTemplateError, CardTemplateFields, CardTemplateStore};
 pub use jsonl::{JsonlError, JsonlLog};
-pub use manager::{ManagerError, StorageManager};
-pub use recovery::{RecoveryError, recover_spec};
-pub use snapshot::{SnapshotData, SnapshotError, load_latest_snapshot, save_snapshot};
-pub use sqlite::{SqliteError, SqliteIndex};
+pub use manager::{
+    COLD_STORAGE_DIR, ColdSpec, ManagerError, RecoveryRetry, StorageManager, TRASH_DIR,
+    retry_transient,
+};
+pub use recovery::{
+    RECOVERY_PROGRESS_INTERVAL, RecoveryError, SpecVerification, recover_spec,
+    recover_spec_with_progress, verify_spec,
+};
+pub use snapshot::{
+    DEFAULT_SNAPSHOT_KEEP, SnapshotData, SnapshotError, load_latest_snapshot, prune_after_save,
+    prune_snapshots, save_snapshot,
+};
+pub use sqlite::{SearchHit, SearchHitKind, SqliteError, SqliteIndex};
+pub use stats::{HomeStats, collect_home_stats};
+pub use ui_prefs::{UiPrefs, UiPrefsError, UiPrefsStore};
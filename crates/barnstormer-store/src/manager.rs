@@ -3,13 +3,15 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use barnstormer_core::export::{export_dot, export_markdown, export_yaml};
 use barnstormer_core::state::SpecState;
 use thiserror::Error;
 use ulid::Ulid;
 
-use crate::recovery::{RecoveryError, recover_spec};
+use crate::recovery::{RecoveryError, recover_spec_with_progress};
+use crate::sqlite::SqliteIndex;
 
 /// Errors that can occur during storage management operations.
 #[derive(Debug, Error)]
@@ -25,6 +27,96 @@ pub enum ManagerError {
 
     #[error("invalid spec directory name: {0}")]
     InvalidSpecDir(String),
+
+    #[error("spec not found: {0}")]
+    SpecNotFound(Ulid),
+
+    #[error("spec already exists: {0}")]
+    SpecExists(Ulid),
+}
+
+impl ManagerError {
+    /// Whether retrying might succeed: filesystem IO errors, directly or
+    /// while replaying a spec. Corrupt data and bad layouts are not retried.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            ManagerError::Io(_) | ManagerError::Recovery(RecoveryError::Io(_))
+        )
+    }
+}
+
+/// How startup recovery retries transient storage errors: up to `attempts`
+/// tries per operation, sleeping `backoff` after the first failure and
+/// doubling it after each further one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryRetry {
+    pub attempts: u32,
+    pub backoff: Duration,
+}
+
+impl RecoveryRetry {
+    /// A single attempt with no retries.
+    pub const NONE: Self = Self {
+        attempts: 1,
+        backoff: Duration::ZERO,
+    };
+}
+
+impl Default for RecoveryRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            backoff: Duration::from_millis(200),
+        }
+    }
+}
+
+/// Run `op`, retrying it per `retry` while it fails with a transient error.
+/// Returns the first success or the last error.
+pub fn retry_transient<T>(
+    retry: RecoveryRetry,
+    what: &str,
+    mut op: impl FnMut() -> Result<T, ManagerError>,
+) -> Result<T, ManagerError> {
+    let mut backoff = retry.backoff;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_transient() && attempt < retry.attempts => {
+                tracing::warn!(
+                    "{} failed (attempt {}/{}): {}; retrying in {:?}",
+                    what,
+                    attempt,
+                    retry.attempts,
+                    e,
+                    backoff
+                );
+                std::thread::sleep(backoff);
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Subdirectory of `specs/` holding specs moved to cold storage. Startup
+/// recovery does not look inside it, so those specs get no actor. This is
+/// unrelated to the spec's `archived` flag, which only hides a loaded spec.
+pub const COLD_STORAGE_DIR: &str = "cold";
+
+/// Directory under the home directory that deleted specs are moved to.
+pub const TRASH_DIR: &str = "trash";
+
+/// A spec in cold storage, as shown in the spec list. The title comes from
+/// the spec's SQLite index and falls back to the spec ID when it has none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColdSpec {
+    pub spec_id: Ulid,
+    pub title: String,
+    pub one_liner: String,
 }
 
 /// Manages the barnstormer home directory layout and provides high-level operations
@@ -64,6 +156,9 @@ impl StorageManager {
 
             let name = entry.file_name();
             let name_str = name.to_string_lossy();
+            if name_str == COLD_STORAGE_DIR {
+                continue;
+            }
             match name_str.parse::<Ulid>() {
                 Ok(spec_id) => results.push((spec_id, path)),
                 Err(_) => {
@@ -88,30 +183,157 @@ impl StorageManager {
         self.home.join("specs").join(spec_id.to_string())
     }
 
+    /// Delete a spec by moving its directory (event log, snapshots, exports,
+    /// context files and SQLite index) to `trash/` under the home directory,
+    /// from where it can be moved back by hand. A spec deleted more than once
+    /// gets a numbered suffix rather than overwriting the earlier copy.
+    /// Returns the spec's path in the trash. The caller must stop the spec's
+    /// actor, persister and swarm first so nothing writes it back.
+    pub fn trash_spec(&self, spec_id: &Ulid) -> Result<PathBuf, ManagerError> {
+        let trash_dir = self.home.join(TRASH_DIR);
+        fs::create_dir_all(&trash_dir)?;
+        let mut to = trash_dir.join(spec_id.to_string());
+        let mut copy = 1;
+        while fs::symlink_metadata(&to).is_ok() {
+            to = trash_dir.join(format!("{}.{}", spec_id, copy));
+            copy += 1;
+        }
+        move_spec_dir(spec_id, &self.get_spec_dir(spec_id), &to)?;
+        tracing::info!("moved deleted spec {} to {}", spec_id, to.display());
+        Ok(to)
+    }
+
+    /// Get the path to a cold-stored spec's directory (does not create it).
+    pub fn get_cold_spec_dir(&self, spec_id: &Ulid) -> PathBuf {
+        self.home
+            .join("specs")
+            .join(COLD_STORAGE_DIR)
+            .join(spec_id.to_string())
+    }
+
+    /// Move a spec's directory to cold storage under `specs/cold/`. The
+    /// caller must stop the spec's actor, persister and swarm first so
+    /// nothing writes to the old location.
+    pub fn move_to_cold_storage(&self, spec_id: &Ulid) -> Result<PathBuf, ManagerError> {
+        let from = self.get_spec_dir(spec_id);
+        let to = self.get_cold_spec_dir(spec_id);
+        fs::create_dir_all(self.home.join("specs").join(COLD_STORAGE_DIR))?;
+        move_spec_dir(spec_id, &from, &to)?;
+        tracing::info!("moved spec {} to cold storage at {}", spec_id, to.display());
+        Ok(to)
+    }
+
+    /// Move a cold-stored spec's directory back under `specs/`, where the
+    /// next recovery (or the caller) picks it up again.
+    pub fn restore_from_cold_storage(&self, spec_id: &Ulid) -> Result<PathBuf, ManagerError> {
+        let from = self.get_cold_spec_dir(spec_id);
+        let to = self.get_spec_dir(spec_id);
+        move_spec_dir(spec_id, &from, &to)?;
+        tracing::info!(
+            "restored spec {} from cold storage to {}",
+            spec_id,
+            to.display()
+        );
+        Ok(to)
+    }
+
+    /// List specs in cold storage, sorted by title. Reads each spec's SQLite
+    /// index rather than replaying its events.
+    pub fn list_cold_specs(&self) -> Result<Vec<ColdSpec>, ManagerError> {
+        let cold_dir = self.home.join("specs").join(COLD_STORAGE_DIR);
+        if !cold_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut specs = Vec::new();
+        for entry in fs::read_dir(&cold_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let Some(spec_id) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<Ulid>().ok())
+            else {
+                continue;
+            };
+            if !path.is_dir() {
+                continue;
+            }
+            specs.push(cold_spec_summary(spec_id, &path));
+        }
+        specs.sort_by(|a, b| {
+            a.title
+                .to_lowercase()
+                .cmp(&b.title.to_lowercase())
+                .then(a.spec_id.cmp(&b.spec_id))
+        });
+        Ok(specs)
+    }
+
     /// Recover all specs from their storage directories.
     /// Returns a list of (spec_id, recovered_state) pairs.
     /// Logs and skips specs that fail to recover.
     pub fn recover_all_specs(&self) -> Result<Vec<(Ulid, SpecState)>, ManagerError> {
-        let spec_dirs = self.list_spec_dirs()?;
+        self.recover_all_specs_with_progress(|_, _, _| {})
+    }
+
+    /// Like `recover_all_specs`, but reports replay progress per spec as
+    /// `progress(spec_id, processed, total)`. See `recover_spec_with_progress`.
+    pub fn recover_all_specs_with_progress(
+        &self,
+        progress: impl FnMut(Ulid, usize, usize),
+    ) -> Result<Vec<(Ulid, SpecState)>, ManagerError> {
+        self.recover_all_specs_with_retry(RecoveryRetry::NONE, progress)
+    }
+
+    /// Like `recover_all_specs_with_progress`, but retries the specs-directory
+    /// scan and each spec's replay on transient IO errors per `retry`. A spec
+    /// that still fails is skipped, and every skipped spec is logged once at
+    /// the end, so one bad spec never aborts the whole boot.
+    pub fn recover_all_specs_with_retry(
+        &self,
+        retry: RecoveryRetry,
+        mut progress: impl FnMut(Ulid, usize, usize),
+    ) -> Result<Vec<(Ulid, SpecState)>, ManagerError> {
+        let spec_dirs =
+            retry_transient(retry, "scanning specs directory", || self.list_spec_dirs())?;
         let mut recovered = Vec::new();
+        let mut skipped = Vec::new();
 
         for (spec_id, spec_dir) in &spec_dirs {
-            match recover_spec(spec_dir) {
+            let what = format!("recovering spec {}", spec_id);
+            let result = retry_transient(retry, &what, || {
+                Ok(recover_spec_with_progress(spec_dir, |processed, total| {
+                    progress(*spec_id, processed, total)
+                })?)
+            });
+            match result {
                 Ok((state, last_event_id)) => {
                     tracing::info!("recovered spec {} at event {}", spec_id, last_event_id);
                     recovered.push((*spec_id, state));
                 }
                 Err(e) => {
                     tracing::error!("failed to recover spec {}: {}", spec_id, e);
+                    skipped.push(spec_id.to_string());
                 }
             }
         }
 
+        if !skipped.is_empty() {
+            tracing::warn!(
+                "skipped {} specs that could not be recovered: {}",
+                skipped.len(),
+                skipped.join(", ")
+            );
+        }
+
         Ok(recovered)
     }
 
     /// Write export files (spec.md, spec.yaml, pipeline.dot) to the exports/ subdirectory.
+    /// Archived cards are left out.
     pub fn write_exports(spec_dir: &Path, state: &SpecState) -> Result<(), ManagerError> {
+        let state = &state.without_archived_cards();
         let exports_dir = spec_dir.join("exports");
         fs::create_dir_all(&exports_dir)?;
 
@@ -133,6 +355,57 @@ impl StorageManager {
     }
 }
 
+/// Rename a spec directory, refusing to overwrite an existing one. The
+/// source is checked with symlink_metadata so a symlinked spec dir counts as
+/// missing rather than having its target moved.
+fn move_spec_dir(spec_id: &Ulid, from: &Path, to: &Path) -> Result<(), ManagerError> {
+    match fs::symlink_metadata(from) {
+        Ok(meta) if meta.is_dir() => {}
+        Ok(_) => return Err(ManagerError::SpecNotFound(*spec_id)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(ManagerError::SpecNotFound(*spec_id));
+        }
+        Err(e) => return Err(e.into()),
+    }
+    if fs::symlink_metadata(to).is_ok() {
+        return Err(ManagerError::SpecExists(*spec_id));
+    }
+    fs::rename(from, to)?;
+    Ok(())
+}
+
+/// A cold-stored spec's title and one-liner from its index, if it has one.
+fn cold_spec_summary(spec_id: Ulid, spec_dir: &Path) -> ColdSpec {
+    let index_path = spec_dir.join("index.db");
+    let row = if index_path.is_file() {
+        match SqliteIndex::open(&index_path).and_then(|index| index.list_specs()) {
+            Ok(rows) => rows.into_iter().find(|r| r.spec_id == spec_id.to_string()),
+            Err(e) => {
+                tracing::warn!(
+                    "could not read index for cold-stored spec {}: {}",
+                    spec_id,
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+    match row {
+        Some(row) => ColdSpec {
+            spec_id,
+            title: row.title,
+            one_liner: row.one_liner,
+        },
+        None => ColdSpec {
+            spec_id,
+            title: spec_id.to_string(),
+            one_liner: String::new(),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,18 +429,32 @@ mod tests {
             notes: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            parent_spec_id: None,
+            merged_from: Vec::new(),
+            external_links: Vec::new(),
         };
         SpecState {
             core: Some(core),
             cards: BTreeMap::new(),
             transcript: Vec::new(),
-            pending_question: None,
+            pending_questions: Vec::new(),
             undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
             last_event_id: 0,
             lanes: vec!["Ideas".to_string(), "Plan".to_string(), "Spec".to_string()],
             phase: SpecPhase::Refining,
             canvas_content: None,
             context_attachments: Vec::new(),
+            pending_proposals: Vec::new(),
+            agent_model: None,
+            model_override: None,
+            archived: false,
+            done_checklist: Vec::new(),
+            token_usage: BTreeMap::new(),
+            agent_roster: None,
+            fetch_hosts: Vec::new(),
+            snapshot_on_shutdown: true,
+            agent_steps: 0,
         }
     }
 
@@ -199,6 +486,88 @@ mod tests {
         assert_eq!(mgr.get_spec_dir(&spec_id), spec_dir);
     }
 
+    #[test]
+    fn storage_manager_trashes_spec_dir() {
+        let dir = TempDir::new().unwrap();
+        let home = dir.path().join("barnstormer_home");
+        let mgr = StorageManager::new(home.clone()).unwrap();
+
+        let spec_id = Ulid::new();
+        let other_id = Ulid::new();
+        let spec_dir = mgr.create_spec_dir(&spec_id).unwrap();
+        mgr.create_spec_dir(&other_id).unwrap();
+        fs::write(spec_dir.join("events.jsonl"), "{}\n").unwrap();
+
+        let trashed = mgr.trash_spec(&spec_id).unwrap();
+        assert_eq!(trashed, home.join(TRASH_DIR).join(spec_id.to_string()));
+        assert!(trashed.join("events.jsonl").exists());
+        assert!(!spec_dir.exists());
+        let remaining: Vec<Ulid> = mgr
+            .list_spec_dirs()
+            .unwrap()
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(remaining, vec![other_id]);
+        assert!(matches!(
+            mgr.trash_spec(&spec_id),
+            Err(ManagerError::SpecNotFound(id)) if id == spec_id
+        ));
+
+        // Deleting the same spec again keeps the earlier copy.
+        mgr.create_spec_dir(&spec_id).unwrap();
+        let again = mgr.trash_spec(&spec_id).unwrap();
+        assert_eq!(again, home.join(TRASH_DIR).join(format!("{}.1", spec_id)));
+        assert!(trashed.join("events.jsonl").exists());
+    }
+
+    #[test]
+    fn storage_manager_moves_spec_dir_to_cold_storage_and_back() {
+        let dir = TempDir::new().unwrap();
+        let mgr = StorageManager::new(dir.path().join("barnstormer_home")).unwrap();
+
+        let state = make_state_with_core();
+        let spec_id = state.core.as_ref().unwrap().spec_id;
+        let untitled_id = Ulid::new();
+        let spec_dir = mgr.create_spec_dir(&spec_id).unwrap();
+        mgr.create_spec_dir(&untitled_id).unwrap();
+        fs::write(spec_dir.join("events.jsonl"), "{}\n").unwrap();
+        SqliteIndex::open(&spec_dir.join("index.db"))
+            .unwrap()
+            .sync_from_state(&state)
+            .unwrap();
+
+        let cold_dir = mgr.move_to_cold_storage(&spec_id).unwrap();
+        mgr.move_to_cold_storage(&untitled_id).unwrap();
+        assert!(!spec_dir.exists());
+        assert!(cold_dir.join("events.jsonl").exists());
+        assert!(mgr.list_spec_dirs().unwrap().is_empty());
+        assert!(mgr.recover_all_specs().unwrap().is_empty());
+
+        let cold = mgr.list_cold_specs().unwrap();
+        assert_eq!(cold.len(), 2);
+        assert_eq!(cold[0].spec_id, untitled_id);
+        assert_eq!(cold[0].title, untitled_id.to_string());
+        assert_eq!(cold[1].spec_id, spec_id);
+        assert_eq!(cold[1].title, "Export Spec");
+        assert_eq!(cold[1].one_liner, "For export testing");
+
+        assert_eq!(mgr.restore_from_cold_storage(&spec_id).unwrap(), spec_dir);
+        assert!(spec_dir.join("events.jsonl").exists());
+        assert_eq!(mgr.list_cold_specs().unwrap().len(), 1);
+        assert!(matches!(
+            mgr.restore_from_cold_storage(&spec_id),
+            Err(ManagerError::SpecNotFound(id)) if id == spec_id
+        ));
+
+        // A spec dir is never overwritten by a restore.
+        mgr.create_spec_dir(&untitled_id).unwrap();
+        assert!(matches!(
+            mgr.restore_from_cold_storage(&untitled_id),
+            Err(ManagerError::SpecExists(id)) if id == untitled_id
+        ));
+    }
+
     #[test]
     fn storage_manager_writes_exports() {
         let dir = TempDir::new().unwrap();
@@ -237,4 +606,76 @@ mod tests {
             "Card title should appear in synthesized prompt"
         );
     }
+
+    fn transient_io_error() -> ManagerError {
+        ManagerError::Io(std::io::Error::new(
+            std::io::ErrorKind::Interrupted,
+            "disk hiccup",
+        ))
+    }
+
+    #[test]
+    fn transient_error_on_first_attempt_is_retried() {
+        let retry = RecoveryRetry {
+            attempts: 3,
+            backoff: Duration::from_millis(1),
+        };
+        let mut calls = 0;
+        let result = retry_transient(retry, "test op", || {
+            calls += 1;
+            if calls == 1 {
+                Err(transient_io_error())
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 2);
+    }
+
+    #[test]
+    fn retries_stop_after_the_last_attempt_or_a_permanent_error() {
+        let retry = RecoveryRetry {
+            attempts: 3,
+            backoff: Duration::from_millis(1),
+        };
+        let mut calls = 0;
+        let result: Result<(), _> = retry_transient(retry, "test op", || {
+            calls += 1;
+            Err(transient_io_error())
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let result: Result<(), _> = retry_transient(retry, "test op", || {
+            calls += 1;
+            Err(ManagerError::InvalidSpecDir("nope".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1, "permanent errors are not retried");
+    }
+
+    #[test]
+    fn recovery_with_retry_skips_unreadable_specs() {
+        let dir = TempDir::new().unwrap();
+        let mgr = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let good = Ulid::new();
+        mgr.create_spec_dir(&good).unwrap();
+        let bad = Ulid::new();
+        let bad_dir = mgr.create_spec_dir(&bad).unwrap();
+        // A directory where the log should be fails every read attempt.
+        fs::create_dir(bad_dir.join("events.jsonl")).unwrap();
+
+        let recovered = mgr
+            .recover_all_specs_with_retry(
+                RecoveryRetry {
+                    attempts: 2,
+                    backoff: Duration::from_millis(1),
+                },
+                |_, _, _| {},
+            )
+            .unwrap();
+        let ids: Vec<Ulid> = recovered.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![good]);
+    }
 }
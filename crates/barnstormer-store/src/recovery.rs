@@ -3,6 +3,7 @@
 
 use std::path::Path;
 
+use barnstormer_core::Event;
 use barnstormer_core::state::SpecState;
 use thiserror::Error;
 use tracing;
@@ -27,6 +28,9 @@ pub enum RecoveryError {
     Sqlite(#[from] crate::sqlite::SqliteError),
 }
 
+/// How many replayed events pass between progress callbacks during recovery.
+pub const RECOVERY_PROGRESS_INTERVAL: usize = 1_000;
+
 /// Recover a spec's state from its storage directory.
 ///
 /// Recovery sequence:
@@ -36,8 +40,20 @@ pub enum RecoveryError {
 /// 4. Build SpecState from the events
 /// 5. Check SQLite integrity (compare last_event_id)
 /// 6. If mismatch: rebuild SQLite from all events
-/// 7. Return recovered state and last_event_id
+/// 7. Rebuild the full-text search entries from the recovered state
+/// 8. Return recovered state and last_event_id
 pub fn recover_spec(spec_dir: &Path) -> Result<(SpecState, u64), RecoveryError> {
+    recover_spec_with_progress(spec_dir, |_, _| {})
+}
+
+/// Like `recover_spec`, but calls `progress(processed, total)` while
+/// replaying the event log so large boots aren't opaque. `total` is the
+/// number of events after the snapshot; the callback fires every
+/// `RECOVERY_PROGRESS_INTERVAL` events and once more when replay finishes.
+pub fn recover_spec_with_progress(
+    spec_dir: &Path,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<(SpecState, u64), RecoveryError> {
     let events_path = spec_dir.join("events.jsonl");
     let snapshots_dir = spec_dir.join("snapshots");
     let index_path = spec_dir.join("index.db");
@@ -81,8 +97,13 @@ pub fn recover_spec(spec_dir: &Path) -> Result<(SpecState, u64), RecoveryError>
         all_events.len()
     );
 
-    for event in &tail_events {
+    let total = tail_events.len();
+    for (i, event) in tail_events.iter().enumerate() {
         state.apply(event);
+        let processed = i + 1;
+        if processed % RECOVERY_PROGRESS_INTERVAL == 0 || processed == total {
+            progress(processed, total);
+        }
     }
 
     let last_event_id = state.last_event_id;
@@ -101,24 +122,80 @@ pub fn recover_spec(spec_dir: &Path) -> Result<(SpecState, u64), RecoveryError>
                 sqlite_id,
                 last_event_id
             );
-            index.rebuild_from_events(&all_events)?;
+            rebuild_index(&index, &all_events, &state)?;
         }
         None => {
             tracing::info!("SQLite index empty, building from events");
-            index.rebuild_from_events(&all_events)?;
+            rebuild_index(&index, &all_events, &state)?;
         }
     }
 
+    // Step 7: The search table is derived from state, so a snapshot-backed
+    // recovery (or an index from before search existed) still finds everything.
+    index.rebuild_search(&state)?;
+
     Ok((state, last_event_id))
 }
 
+/// What `verify_spec` found in a spec directory.
+#[derive(Debug)]
+pub struct SpecVerification {
+    /// The state recovery would produce.
+    pub state: SpecState,
+    pub last_event_id: u64,
+    /// Unparseable log lines (e.g. a torn final write) that recovery's JSONL
+    /// repair would drop.
+    pub dropped_lines: usize,
+}
+
+/// Check that a spec directory recovers without changing anything on disk:
+/// the same snapshot-plus-tail replay as `recover_spec`, but the JSONL log
+/// is read rather than repaired and the SQLite index is not opened.
+pub fn verify_spec(spec_dir: &Path) -> Result<SpecVerification, RecoveryError> {
+    let (mut state, snapshot_event_id) = match load_latest_snapshot(&spec_dir.join("snapshots"))? {
+        Some(snap) => (snap.state, snap.last_event_id),
+        None => (SpecState::new(), 0),
+    };
+
+    let events_path = spec_dir.join("events.jsonl");
+    let (events, dropped_lines) = if events_path.exists() {
+        JsonlLog::replay_valid(&events_path)?
+    } else {
+        (Vec::new(), 0)
+    };
+    for event in events.iter().filter(|e| e.event_id > snapshot_event_id) {
+        state.apply(event);
+    }
+
+    Ok(SpecVerification {
+        last_event_id: state.last_event_id,
+        state,
+        dropped_lines,
+    })
+}
+
+/// Rebuild the SQLite index from the full event history, or from `state`
+/// when the log has been compacted and no longer starts at event 1.
+fn rebuild_index(
+    index: &SqliteIndex,
+    all_events: &[Event],
+    state: &SpecState,
+) -> Result<(), RecoveryError> {
+    match all_events.first() {
+        Some(first) if first.event_id > 1 => index.sync_from_state(state)?,
+        _ => index.rebuild_from_events(all_events)?,
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::jsonl::JsonlLog;
     use crate::snapshot::{SnapshotData, save_snapshot};
-    use barnstormer_core::card::Card;
+    use barnstormer_core::card::{Card, CardAttachment};
     use barnstormer_core::event::{Event, EventPayload};
+    use barnstormer_core::model::ExternalLink;
     use chrono::Utc;
     use std::collections::HashMap;
     use std::fs::{self, OpenOptions};
@@ -189,6 +266,76 @@ mod tests {
         assert_eq!(state.cards.len(), 1);
     }
 
+    #[test]
+    fn recover_with_progress_reports_increasing_counts() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        let spec_id = Ulid::new();
+
+        let mut events = vec![make_event(
+            1,
+            spec_id,
+            EventPayload::SpecCreated {
+                title: "Big Log".to_string(),
+                one_liner: "Many events".to_string(),
+                goal: "Report progress".to_string(),
+            },
+        )];
+        for i in 2..=2_500u64 {
+            events.push(make_event(
+                i,
+                spec_id,
+                EventPayload::CardCreated {
+                    card: Card::new("idea".to_string(), format!("Card {i}"), "human".to_string()),
+                },
+            ));
+        }
+        write_events(&spec_dir, &events);
+
+        let mut calls = Vec::new();
+        let (state, last_id) = recover_spec_with_progress(&spec_dir, |processed, total| {
+            calls.push((processed, total))
+        })
+        .unwrap();
+
+        assert_eq!(last_id, 2_500);
+        assert_eq!(state.cards.len(), 2_499);
+        assert_eq!(calls, vec![(1_000, 2_500), (2_000, 2_500), (2_500, 2_500)]);
+    }
+
+    #[test]
+    fn recovery_preserves_pinned_agent_model() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        let spec_id = Ulid::new();
+
+        let model = barnstormer_core::AgentModel {
+            provider: "anthropic".to_string(),
+            model: "claude-sonnet-4-5-20250929".to_string(),
+        };
+        let mut message = barnstormer_core::TranscriptMessage::new(
+            "planner-01HTEST".to_string(),
+            "Drafted the plan".to_string(),
+        );
+        message.agent_model = Some(model.clone());
+        let events = vec![
+            make_event(
+                1,
+                spec_id,
+                EventPayload::AgentModelPinned {
+                    provider: model.provider.clone(),
+                    model: model.model.clone(),
+                },
+            ),
+            make_event(2, spec_id, EventPayload::TranscriptAppended { message }),
+        ];
+        write_events(&spec_dir, &events);
+
+        let (state, _) = recover_spec(&spec_dir).unwrap();
+        assert_eq!(state.agent_model.as_ref(), Some(&model));
+        assert_eq!(state.transcript[0].agent_model.as_ref(), Some(&model));
+    }
+
     #[test]
     fn recover_from_snapshot_plus_tail() {
         let dir = TempDir::new().unwrap();
@@ -246,6 +393,178 @@ mod tests {
         assert_eq!(state.cards.len(), 19);
     }
 
+    #[test]
+    fn recovery_rebuilds_search_from_recovered_state() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        let spec_id = Ulid::new();
+
+        let card = Card::new(
+            "idea".to_string(),
+            "Searchable card".to_string(),
+            "human".to_string(),
+        );
+        let events = vec![
+            make_event(
+                1,
+                spec_id,
+                EventPayload::SpecCreated {
+                    title: "Search Recovery".to_string(),
+                    one_liner: "Test".to_string(),
+                    goal: "Find it again".to_string(),
+                },
+            ),
+            make_event(2, spec_id, EventPayload::CardCreated { card }),
+        ];
+        write_events(&spec_dir, &events);
+
+        // An index that claims to be current but has no search rows, like
+        // one written before the search table existed.
+        SqliteIndex::open(&spec_dir.join("index.db"))
+            .unwrap()
+            .set_last_event_id(2)
+            .unwrap();
+
+        recover_spec(&spec_dir).unwrap();
+        let index = SqliteIndex::open(&spec_dir.join("index.db")).unwrap();
+        assert_eq!(index.search(&spec_id, "searchable", 10).unwrap().len(), 1);
+
+        // Recovering again does not duplicate entries.
+        recover_spec(&spec_dir).unwrap();
+        assert_eq!(index.search(&spec_id, "searchable", 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn card_attachments_survive_snapshot_and_recovery() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        let spec_id = Ulid::new();
+
+        let card = Card::new(
+            "task".to_string(),
+            "Linked".to_string(),
+            "human".to_string(),
+        );
+        let card_id = card.card_id;
+        let first = CardAttachment::new("https://example.com/design", "Design doc").unwrap();
+        let second = CardAttachment::new("docs/adr/0007.md", "ADR 7").unwrap();
+        let events = vec![
+            make_event(
+                1,
+                spec_id,
+                EventPayload::SpecCreated {
+                    title: "Attachments".to_string(),
+                    one_liner: "Test".to_string(),
+                    goal: "Keep links".to_string(),
+                },
+            ),
+            make_event(2, spec_id, EventPayload::CardCreated { card }),
+            make_event(
+                3,
+                spec_id,
+                EventPayload::CardAttachmentAdded {
+                    card_id,
+                    index: 0,
+                    attachment: first.clone(),
+                },
+            ),
+            make_event(
+                4,
+                spec_id,
+                EventPayload::CardAttachmentAdded {
+                    card_id,
+                    index: 1,
+                    attachment: second.clone(),
+                },
+            ),
+        ];
+        write_events(&spec_dir, &events);
+
+        // Snapshot after the first attachment; the second comes from the tail.
+        let mut snap_state = SpecState::new();
+        for event in &events[..3] {
+            snap_state.apply(event);
+        }
+        save_snapshot(
+            &spec_dir.join("snapshots"),
+            &SnapshotData {
+                state: snap_state,
+                last_event_id: 3,
+                agent_contexts: HashMap::new(),
+                saved_at: Utc::now(),
+            },
+        )
+        .unwrap();
+
+        let (state, last_id) = recover_spec(&spec_dir).unwrap();
+        assert_eq!(last_id, 4);
+        assert_eq!(state.cards[&card_id].attachments, vec![first, second]);
+    }
+
+    #[test]
+    fn external_links_survive_snapshot_and_recovery() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        let spec_id = Ulid::new();
+
+        let links: Vec<ExternalLink> = ["APP-1", "APP-2", "APP-3"]
+            .iter()
+            .map(|key| {
+                ExternalLink::new(&format!("https://jira.example.com/browse/{}", key), key).unwrap()
+            })
+            .collect();
+        let mut events = vec![make_event(
+            1,
+            spec_id,
+            EventPayload::SpecCreated {
+                title: "Links".to_string(),
+                one_liner: "Test".to_string(),
+                goal: "Keep links".to_string(),
+            },
+        )];
+        for (index, link) in links.iter().enumerate() {
+            events.push(make_event(
+                index as u64 + 2,
+                spec_id,
+                EventPayload::ExternalLinkAdded {
+                    index,
+                    link: link.clone(),
+                },
+            ));
+        }
+        events.push(make_event(
+            5,
+            spec_id,
+            EventPayload::ExternalLinkRemoved {
+                link_id: links[1].link_id,
+            },
+        ));
+        write_events(&spec_dir, &events);
+
+        // Snapshot after the first two links; the rest comes from the tail.
+        let mut snap_state = SpecState::new();
+        for event in &events[..3] {
+            snap_state.apply(event);
+        }
+        save_snapshot(
+            &spec_dir.join("snapshots"),
+            &SnapshotData {
+                state: snap_state,
+                last_event_id: 3,
+                agent_contexts: HashMap::new(),
+                saved_at: Utc::now(),
+            },
+        )
+        .unwrap();
+
+        let (state, last_id) = recover_spec(&spec_dir).unwrap();
+        assert_eq!(last_id, 5);
+        assert_eq!(
+            state.core.unwrap().external_links,
+            vec![links[0].clone(), links[2].clone()]
+        );
+    }
+
     #[test]
     fn recover_repairs_partial_jsonl() {
         let dir = TempDir::new().unwrap();
@@ -292,6 +611,107 @@ mod tests {
         assert_eq!(state.cards.len(), 1);
     }
 
+    #[test]
+    fn recover_after_compaction_matches_full_replay() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        let spec_id = Ulid::new();
+
+        let mut events = vec![make_event(
+            1,
+            spec_id,
+            EventPayload::SpecCreated {
+                title: "Compacted".to_string(),
+                one_liner: "Test".to_string(),
+                goal: "Replay less".to_string(),
+            },
+        )];
+        for i in 0..5 {
+            let card = Card::new(
+                "idea".to_string(),
+                format!("Card {}", i),
+                "human".to_string(),
+            );
+            events.push(make_event(
+                i + 2,
+                spec_id,
+                EventPayload::CardCreated { card },
+            ));
+        }
+        write_events(&spec_dir, &events);
+        let (full_state, _) = recover_spec(&spec_dir).unwrap();
+
+        let events_path = spec_dir.join("events.jsonl");
+        JsonlLog::open(&events_path)
+            .unwrap()
+            .compact(&full_state, crate::DEFAULT_SNAPSHOT_KEEP)
+            .unwrap();
+        assert_eq!(JsonlLog::replay(&events_path).unwrap().len(), 1);
+
+        // With the index gone, it has to be rebuilt from the snapshot state
+        // because the compacted log no longer holds the early events.
+        fs::remove_file(spec_dir.join("index.db")).unwrap();
+        let (state, last_id) = recover_spec(&spec_dir).unwrap();
+
+        assert_eq!(last_id, 6);
+        assert_eq!(state.cards, full_state.cards);
+        assert_eq!(state.core, full_state.core);
+        let idx = SqliteIndex::open(&spec_dir.join("index.db")).unwrap();
+        assert_eq!(idx.list_cards(&spec_id).unwrap().len(), 5);
+        assert_eq!(idx.get_last_event_id().unwrap(), Some(6));
+    }
+
+    #[test]
+    fn verify_matches_recovery_and_leaves_the_spec_untouched() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        let spec_id = Ulid::new();
+        let card = Card::new("idea".to_string(), "Kept".to_string(), "human".to_string());
+        write_events(
+            &spec_dir,
+            &[
+                make_event(
+                    1,
+                    spec_id,
+                    EventPayload::SpecCreated {
+                        title: "Verify".to_string(),
+                        one_liner: "Test".to_string(),
+                        goal: "Dry run".to_string(),
+                    },
+                ),
+                make_event(2, spec_id, EventPayload::CardCreated { card }),
+            ],
+        );
+        let events_path = spec_dir.join("events.jsonl");
+        let mut file = OpenOptions::new().append(true).open(&events_path).unwrap();
+        write!(file, r#"{{"event_id":3,"#).unwrap();
+        drop(file);
+        let log_before = fs::read_to_string(&events_path).unwrap();
+
+        let verified = verify_spec(&spec_dir).unwrap();
+        assert_eq!(verified.last_event_id, 2);
+        assert_eq!(verified.state.cards.len(), 1);
+        assert_eq!(verified.dropped_lines, 1);
+        assert_eq!(fs::read_to_string(&events_path).unwrap(), log_before);
+        assert!(!spec_dir.join("index.db").exists());
+
+        let (recovered, last_id) = recover_spec(&spec_dir).unwrap();
+        assert_eq!(last_id, verified.last_event_id);
+        assert_eq!(recovered.cards, verified.state.cards);
+    }
+
+    #[test]
+    fn verify_reports_a_corrupt_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let spec_dir = make_spec_dir(&dir);
+        fs::write(spec_dir.join("snapshots").join("state_5.json"), "{not json").unwrap();
+
+        assert!(matches!(
+            verify_spec(&spec_dir),
+            Err(RecoveryError::Snapshot(_))
+        ));
+    }
+
     #[test]
     fn recover_rebuilds_stale_sqlite() {
         let dir = TempDir::new().unwrap();
@@ -1,10 +1,10 @@
-// ABOUTME: Atomic snapshot save and load for SpecState persistence.
-// ABOUTME: Writes snapshots with atomic rename for crash safety and loads the latest by event ID.
+// ABOUTME: Atomic snapshot save, load and pruning for SpecState persistence.
+// ABOUTME: Writes snapshots with atomic rename for crash safety, loads the latest by event ID, keeps the newest N.
 
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use barnstormer_core::SpecState;
 use chrono::{DateTime, Utc};
@@ -31,6 +31,70 @@ pub struct SnapshotData {
     pub saved_at: DateTime<Utc>,
 }
 
+/// Snapshots kept per spec when `BARNSTORMER_SNAPSHOT_KEEP` is unset.
+pub const DEFAULT_SNAPSHOT_KEEP: usize = 5;
+
+/// Only the field pruning needs, so old snapshots are not fully decoded.
+#[derive(Deserialize)]
+struct SnapshotStamp {
+    saved_at: DateTime<Utc>,
+}
+
+/// Delete all but the `keep` most recent snapshots (by `saved_at`, then
+/// event ID) in `dir`, returning how many were removed. The snapshot
+/// recovery would load, the one with the highest event ID, is always kept.
+/// Files that cannot be read as snapshots are left alone.
+pub fn prune_snapshots(dir: &Path, keep: usize) -> Result<usize, SnapshotError> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let mut snapshots: Vec<(DateTime<Utc>, u64, PathBuf)> = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        let Some(event_id) = name_str
+            .strip_prefix("state_")
+            .and_then(|rest| rest.strip_suffix(".json"))
+            .and_then(|id| id.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let stamp = fs::read_to_string(entry.path())
+            .ok()
+            .and_then(|json| serde_json::from_str::<SnapshotStamp>(&json).ok());
+        if let Some(stamp) = stamp {
+            snapshots.push((stamp.saved_at, event_id, entry.path()));
+        }
+    }
+
+    let latest_event_id = snapshots.iter().map(|(_, id, _)| *id).max();
+    snapshots.sort_by_key(|(saved_at, event_id, _)| std::cmp::Reverse((*saved_at, *event_id)));
+
+    let mut removed = 0;
+    for (_, event_id, path) in snapshots.into_iter().skip(keep) {
+        if Some(event_id) == latest_event_id {
+            continue;
+        }
+        fs::remove_file(&path)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Prune `dir` down to `keep` snapshots after a save. Failures are logged
+/// rather than returned: the snapshot just written is already safe on disk.
+pub fn prune_after_save(dir: &Path, keep: usize) {
+    match prune_snapshots(dir, keep) {
+        Ok(0) => {}
+        Ok(removed) => {
+            tracing::debug!("pruned {} old snapshots in {}", removed, dir.display())
+        }
+        Err(e) => tracing::warn!("failed to prune snapshots in {}: {}", dir.display(), e),
+    }
+}
+
 /// Save a snapshot to disk using atomic write (write to .tmp, fsync, rename).
 /// Creates the target directory if it does not exist.
 pub fn save_snapshot(dir: &Path, data: &SnapshotData) -> Result<(), SnapshotError> {
@@ -52,7 +116,9 @@ pub fn save_snapshot(dir: &Path, data: &SnapshotData) -> Result<(), SnapshotErro
 }
 
 /// Load the snapshot with the highest event ID from the given directory.
-/// Returns None if the directory is empty or does not exist.
+/// Returns None if the directory is empty or does not exist. Lanes that
+/// only cards name are added to the loaded state's lane list (see
+/// `SpecState::adopt_card_lanes`).
 pub fn load_latest_snapshot(dir: &Path) -> Result<Option<SnapshotData>, SnapshotError> {
     if !dir.exists() {
         return Ok(None);
@@ -85,7 +151,8 @@ pub fn load_latest_snapshot(dir: &Path) -> Result<Option<SnapshotData>, Snapshot
     match best {
         Some((_, path)) => {
             let contents = fs::read_to_string(&path)?;
-            let data: SnapshotData = serde_json::from_str(&contents)?;
+            let mut data: SnapshotData = serde_json::from_str(&contents)?;
+            data.state.adopt_card_lanes();
             Ok(Some(data))
         }
         None => Ok(None),
@@ -150,6 +217,26 @@ mod tests {
         assert_eq!(loaded.last_event_id, 20);
     }
 
+    #[test]
+    fn load_adds_lanes_of_legacy_cards() {
+        let dir = TempDir::new().unwrap();
+        let mut snap = make_snapshot(5);
+        let mut card =
+            barnstormer_core::Card::new("idea".to_string(), "Old".to_string(), "human".to_string());
+        card.lane = "Backlog".to_string();
+        snap.state.cards.insert(card.card_id, card);
+
+        save_snapshot(dir.path(), &snap).unwrap();
+
+        let loaded = load_latest_snapshot(dir.path())
+            .unwrap()
+            .expect("should find snapshot");
+        assert_eq!(
+            loaded.state.lanes.last().map(String::as_str),
+            Some("Backlog")
+        );
+    }
+
     #[test]
     fn load_returns_none_for_empty_dir() {
         let dir = TempDir::new().unwrap();
@@ -158,6 +245,57 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn prune_keeps_the_newest_snapshots() {
+        let dir = TempDir::new().unwrap();
+        let start = Utc::now();
+        for event_id in 1..=10 {
+            let mut snap = make_snapshot(event_id);
+            snap.saved_at = start + chrono::Duration::seconds(event_id as i64);
+            save_snapshot(dir.path(), &snap).unwrap();
+        }
+
+        assert_eq!(prune_snapshots(dir.path(), 5).unwrap(), 5);
+
+        let mut left: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(
+            left,
+            [
+                "state_10.json",
+                "state_6.json",
+                "state_7.json",
+                "state_8.json",
+                "state_9.json"
+            ]
+        );
+        let latest = load_latest_snapshot(dir.path()).unwrap().unwrap();
+        assert_eq!(latest.last_event_id, 10);
+        assert_eq!(prune_snapshots(dir.path(), 5).unwrap(), 0);
+    }
+
+    #[test]
+    fn prune_never_removes_the_highest_event_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let start = Utc::now();
+        // A clock step backwards left the newest event with the oldest stamp.
+        for (event_id, offset) in [(30, 0), (10, 1), (20, 2)] {
+            let mut snap = make_snapshot(event_id);
+            snap.saved_at = start + chrono::Duration::seconds(offset);
+            save_snapshot(dir.path(), &snap).unwrap();
+        }
+        fs::write(dir.path().join("state_5.json"), "{not json").unwrap();
+
+        assert_eq!(prune_snapshots(dir.path(), 1).unwrap(), 1);
+        assert!(dir.path().join("state_30.json").exists());
+        assert!(dir.path().join("state_20.json").exists());
+        assert!(!dir.path().join("state_10.json").exists());
+        assert!(dir.path().join("state_5.json").exists());
+    }
+
     #[test]
     fn save_creates_directory() {
         let dir = TempDir::new().unwrap();
@@ -1,11 +1,13 @@
 // ABOUTME: SQLite-backed index for fast spec and card queries without replaying events.
-// ABOUTME: Provides upsert, delete, list, and rebuild operations synchronized with the event log.
+// ABOUTME: Provides upsert, delete, list, rebuild, and FTS5 full-text search over cards and transcript messages.
 
 use std::path::Path;
 
 use barnstormer_core::card::Card;
 use barnstormer_core::event::{Event, EventPayload};
 use barnstormer_core::model::SpecCore;
+use barnstormer_core::state::SpecState;
+use barnstormer_core::transcript::TranscriptMessage;
 use rusqlite::{Connection, params};
 use thiserror::Error;
 use ulid::Ulid;
@@ -25,6 +27,40 @@ pub struct SpecSummary {
     pub one_liner: String,
     pub goal: String,
     pub updated_at: String,
+    pub archived: bool,
+}
+
+/// What a full-text search hit points at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchHitKind {
+    Card,
+    Transcript,
+}
+
+/// One full-text search match. `ref_id` is the card ID for card hits and
+/// the transcript message ID for transcript hits.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub spec_id: String,
+    pub kind: SearchHitKind,
+    pub ref_id: String,
+    /// Card title, or the sender of a transcript message.
+    pub title: String,
+    /// A short excerpt around the match.
+    pub snippet: String,
+    /// FTS5 bm25 rank; lower is a better match. Only comparable with
+    /// other hits from the same index.
+    pub rank: f64,
+}
+
+/// Turn free text into an FTS5 query: every word must match, as a prefix,
+/// and quotes keep FTS5 operators in user input from being interpreted.
+fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
 }
 
 /// A SQLite-backed index that mirrors spec and card data for fast reads.
@@ -48,7 +84,8 @@ impl SqliteIndex {
                 title TEXT NOT NULL,
                 one_liner TEXT NOT NULL,
                 goal TEXT NOT NULL,
-                updated_at TEXT NOT NULL
+                updated_at TEXT NOT NULL,
+                archived INTEGER NOT NULL DEFAULT 0
             );
 
             CREATE TABLE IF NOT EXISTS cards (
@@ -67,9 +104,27 @@ impl SqliteIndex {
             CREATE TABLE IF NOT EXISTS meta (
                 key TEXT PRIMARY KEY,
                 value TEXT NOT NULL
+            );
+
+            CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+                spec_id UNINDEXED,
+                kind UNINDEXED,
+                ref_id UNINDEXED,
+                title,
+                body
             );",
         )?;
 
+        // Indexes created before specs could be archived lack the column.
+        let has_archived = conn
+            .prepare("SELECT 1 FROM pragma_table_info('specs') WHERE name = 'archived'")?
+            .exists([])?;
+        if !has_archived {
+            conn.execute_batch(
+                "ALTER TABLE specs ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;",
+            )?;
+        }
+
         Ok(Self { conn })
     }
 
@@ -118,7 +173,7 @@ impl SqliteIndex {
                 card.updated_at.to_rfc3339(),
             ],
         )?;
-        Ok(())
+        self.refresh_card_search(card.card_id)
     }
 
     /// Delete a card row by card_id.
@@ -127,14 +182,144 @@ impl SqliteIndex {
             "DELETE FROM cards WHERE card_id = ?1",
             params![card_id.to_string()],
         )?;
+        self.refresh_card_search(*card_id)
+    }
+
+    /// Replace a card's search entry with its current title and body, or
+    /// drop it when the card row is gone.
+    fn refresh_card_search(&self, card_id: Ulid) -> Result<(), SqliteError> {
+        let card_id = card_id.to_string();
+        self.conn.execute(
+            "DELETE FROM search_index WHERE kind = 'card' AND ref_id = ?1",
+            params![card_id],
+        )?;
+        self.conn.execute(
+            "INSERT INTO search_index (spec_id, kind, ref_id, title, body)
+             SELECT spec_id, 'card', card_id, title, COALESCE(body, '')
+             FROM cards WHERE card_id = ?1",
+            params![card_id],
+        )?;
         Ok(())
     }
 
+    /// Add a transcript message to the search index.
+    fn index_message(
+        &self,
+        spec_id: &Ulid,
+        message: &TranscriptMessage,
+    ) -> Result<(), SqliteError> {
+        self.conn.execute(
+            "INSERT INTO search_index (spec_id, kind, ref_id, title, body)
+             VALUES (?1, 'transcript', ?2, ?3, ?4)",
+            params![
+                spec_id.to_string(),
+                message.message_id.to_string(),
+                message.sender,
+                message.content,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Rebuild a spec's search entries from its materialized state: every
+    /// card's title and body and every transcript message. Used after
+    /// recovery, where the state (not the event log alone) is authoritative.
+    pub fn rebuild_search(&self, state: &SpecState) -> Result<(), SqliteError> {
+        let Some(core) = &state.core else {
+            return Ok(());
+        };
+        let spec_id = core.spec_id;
+        self.conn.execute(
+            "DELETE FROM search_index WHERE spec_id = ?1",
+            params![spec_id.to_string()],
+        )?;
+        for card in state.cards.values() {
+            self.conn.execute(
+                "INSERT INTO search_index (spec_id, kind, ref_id, title, body)
+                 VALUES (?1, 'card', ?2, ?3, ?4)",
+                params![
+                    spec_id.to_string(),
+                    card.card_id.to_string(),
+                    card.title,
+                    card.body.as_deref().unwrap_or_default(),
+                ],
+            )?;
+        }
+        for message in &state.transcript {
+            self.index_message(&spec_id, message)?;
+        }
+        Ok(())
+    }
+
+    /// Bring the whole index in line with `state`: the spec row, its cards,
+    /// its search entries, and the last indexed event ID. For an index that
+    /// missed events, e.g. ones applied before anything was listening.
+    pub fn sync_from_state(&self, state: &SpecState) -> Result<(), SqliteError> {
+        let Some(core) = &state.core else {
+            return Ok(());
+        };
+        self.update_spec(core)?;
+        self.conn.execute(
+            "UPDATE specs SET archived = ?1 WHERE spec_id = ?2",
+            params![state.archived, core.spec_id.to_string()],
+        )?;
+        self.conn.execute(
+            "DELETE FROM cards WHERE spec_id = ?1",
+            params![core.spec_id.to_string()],
+        )?;
+        for card in state.cards.values() {
+            self.update_card(&core.spec_id, card)?;
+        }
+        self.rebuild_search(state)?;
+        self.set_last_event_id(state.last_event_id)
+    }
+
+    /// Full-text search a spec's cards and transcript. Every word of `text`
+    /// must match (as a prefix); hits come best match first, up to `limit`.
+    pub fn search(
+        &self,
+        spec_id: &Ulid,
+        text: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, SqliteError> {
+        let Some(query) = fts_query(text) else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT kind, ref_id, title, snippet(search_index, -1, '', '', '…', 12), spec_id, rank
+             FROM search_index
+             WHERE search_index MATCH ?1 AND spec_id = ?2
+             ORDER BY rank
+             LIMIT ?3",
+        )?;
+        let rows = stmt.query_map(params![query, spec_id.to_string(), limit as i64], |row| {
+            let kind: String = row.get(0)?;
+            Ok(SearchHit {
+                spec_id: row.get(4)?,
+                kind: if kind == "card" {
+                    SearchHitKind::Card
+                } else {
+                    SearchHitKind::Transcript
+                },
+                ref_id: row.get(1)?,
+                title: row.get(2)?,
+                snippet: row.get(3)?,
+                rank: row.get(5)?,
+            })
+        })?;
+
+        let mut hits = Vec::new();
+        for row in rows {
+            hits.push(row?);
+        }
+        Ok(hits)
+    }
+
     /// List all specs as summaries.
     pub fn list_specs(&self) -> Result<Vec<SpecSummary>, SqliteError> {
         let mut stmt = self
             .conn
-            .prepare("SELECT spec_id, title, one_liner, goal, updated_at FROM specs ORDER BY updated_at DESC")?;
+            .prepare("SELECT spec_id, title, one_liner, goal, updated_at, archived FROM specs ORDER BY updated_at DESC")?;
 
         let rows = stmt.query_map([], |row| {
             Ok(SpecSummary {
@@ -143,6 +328,7 @@ impl SqliteIndex {
                 one_liner: row.get(2)?,
                 goal: row.get(3)?,
                 updated_at: row.get(4)?,
+                archived: row.get(5)?,
             })
         })?;
 
@@ -211,6 +397,7 @@ impl SqliteIndex {
 
     /// Clear all data and rebuild from a list of events.
     pub fn rebuild_from_events(&self, events: &[Event]) -> Result<(), SqliteError> {
+        self.conn.execute("DELETE FROM search_index", [])?;
         self.conn.execute("DELETE FROM cards", [])?;
         self.conn.execute("DELETE FROM specs", [])?;
         self.conn.execute("DELETE FROM meta", [])?;
@@ -250,6 +437,43 @@ impl SqliteIndex {
                 )?;
             }
 
+            EventPayload::SpecForked { core, cards, .. } => {
+                self.conn.execute(
+                    "INSERT INTO specs (spec_id, title, one_liner, goal, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5)
+                     ON CONFLICT(spec_id) DO UPDATE SET
+                        title = excluded.title,
+                        one_liner = excluded.one_liner,
+                        goal = excluded.goal,
+                        updated_at = excluded.updated_at",
+                    params![
+                        spec_id.to_string(),
+                        core.title,
+                        core.one_liner,
+                        core.goal,
+                        event.timestamp.to_rfc3339(),
+                    ],
+                )?;
+                for card in cards {
+                    self.update_card(&spec_id, card)?;
+                }
+            }
+
+            EventPayload::SpecMerged {
+                cards, transcript, ..
+            } => {
+                for card in cards {
+                    self.update_card(&spec_id, card)?;
+                }
+                for message in transcript {
+                    self.index_message(&spec_id, message)?;
+                }
+            }
+
+            EventPayload::TranscriptAppended { message } => {
+                self.index_message(&spec_id, message)?;
+            }
+
             EventPayload::SpecCoreUpdated {
                 title,
                 one_liner,
@@ -319,6 +543,7 @@ impl SqliteIndex {
                     "UPDATE cards SET updated_at = ?1 WHERE card_id = ?2",
                     params![event.timestamp.to_rfc3339(), card_id.to_string()],
                 )?;
+                self.refresh_card_search(*card_id)?;
             }
 
             EventPayload::CardMoved {
@@ -332,18 +557,60 @@ impl SqliteIndex {
                 )?;
             }
 
+            EventPayload::CardsReordered { positions } => {
+                for p in positions {
+                    self.conn.execute(
+                        "UPDATE cards SET lane = ?1, sort_order = ?2, updated_at = ?3 WHERE card_id = ?4",
+                        params![p.lane, p.order, event.timestamp.to_rfc3339(), p.card_id.to_string()],
+                    )?;
+                }
+            }
+
             EventPayload::CardDeleted { card_id } => {
                 self.delete_card(card_id)?;
             }
 
-            EventPayload::UndoApplied { inverse_events, .. } => {
-                // Apply inverse events to the index
-                for inverse_payload in inverse_events {
+            EventPayload::LaneRenamed { to, card_ids, .. } => {
+                for card_id in card_ids {
+                    self.conn.execute(
+                        "UPDATE cards SET lane = ?1, updated_at = ?2 WHERE card_id = ?3",
+                        params![to, event.timestamp.to_rfc3339(), card_id.to_string()],
+                    )?;
+                }
+            }
+
+            EventPayload::LaneDeleted {
+                moved_to: Some(to),
+                card_ids,
+                ..
+            } => {
+                for card_id in card_ids {
+                    self.conn.execute(
+                        "UPDATE cards SET lane = ?1, updated_at = ?2 WHERE card_id = ?3",
+                        params![to, event.timestamp.to_rfc3339(), card_id.to_string()],
+                    )?;
+                }
+            }
+
+            EventPayload::SpecArchivedSet { archived } => {
+                self.conn.execute(
+                    "UPDATE specs SET archived = ?1 WHERE spec_id = ?2",
+                    params![archived, spec_id.to_string()],
+                )?;
+            }
+
+            EventPayload::UndoApplied {
+                inverse_events: payloads,
+                ..
+            }
+            | EventPayload::RedoApplied { payloads } => {
+                // Apply the inverse (undo) or original (redo) events to the index
+                for payload in payloads {
                     let synthetic = Event {
                         event_id: event.event_id,
                         spec_id: event.spec_id,
                         timestamp: event.timestamp,
-                        payload: inverse_payload.clone(),
+                        payload: payload.clone(),
                     };
                     self.apply_event(&synthetic)?;
                 }
@@ -433,6 +700,39 @@ mod tests {
         assert_eq!(specs[0].title, "Updated Spec");
     }
 
+    #[test]
+    fn sqlite_tracks_archived_flag_and_migrates_old_schema() {
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("index.db");
+        {
+            // An index written before specs could be archived.
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE specs (
+                    spec_id TEXT PRIMARY KEY,
+                    title TEXT NOT NULL,
+                    one_liner TEXT NOT NULL,
+                    goal TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );",
+            )
+            .unwrap();
+        }
+        let idx = SqliteIndex::open(&db_path).unwrap();
+
+        let spec = make_spec();
+        idx.update_spec(&spec).unwrap();
+        assert!(!idx.list_specs().unwrap()[0].archived);
+
+        idx.apply_event(&make_event(
+            2,
+            spec.spec_id,
+            EventPayload::SpecArchivedSet { archived: true },
+        ))
+        .unwrap();
+        assert!(idx.list_specs().unwrap()[0].archived);
+    }
+
     #[test]
     fn sqlite_card_crud() {
         let dir = TempDir::new().unwrap();
@@ -569,6 +869,7 @@ mod tests {
                 body: Some(Some("With a body".to_string())),
                 card_type: None,
                 refs: None,
+                tags: None,
             },
         ))
         .unwrap();
@@ -592,6 +893,126 @@ mod tests {
         assert_eq!(idx.get_last_event_id().unwrap(), Some(4));
     }
 
+    #[test]
+    fn sqlite_search_tracks_cards_and_transcript() {
+        let dir = TempDir::new().unwrap();
+        let idx = SqliteIndex::open(&dir.path().join("index.db")).unwrap();
+        let spec_id = Ulid::new();
+
+        idx.apply_event(&make_event(
+            1,
+            spec_id,
+            EventPayload::SpecCreated {
+                title: "Search".to_string(),
+                one_liner: "Find things".to_string(),
+                goal: "Test search".to_string(),
+            },
+        ))
+        .unwrap();
+        let card = Card::new(
+            "idea".to_string(),
+            "Offline sync".to_string(),
+            "human".to_string(),
+        );
+        let card_id = card.card_id;
+        idx.apply_event(&make_event(2, spec_id, EventPayload::CardCreated { card }))
+            .unwrap();
+        let message = TranscriptMessage::new(
+            "human".to_string(),
+            "We should support synchronization across devices".to_string(),
+        );
+        let message_id = message.message_id;
+        idx.apply_event(&make_event(
+            3,
+            spec_id,
+            EventPayload::TranscriptAppended { message },
+        ))
+        .unwrap();
+
+        // Prefix matching finds both the card title and the message.
+        let hits = idx.search(&spec_id, "sync", 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(
+            hits.iter()
+                .any(|h| h.kind == SearchHitKind::Card && h.ref_id == card_id.to_string())
+        );
+        let message_hit = hits
+            .iter()
+            .find(|h| h.kind == SearchHitKind::Transcript)
+            .unwrap();
+        assert_eq!(message_hit.ref_id, message_id.to_string());
+        assert!(message_hit.snippet.contains("synchronization"));
+        assert!(hits.iter().all(|h| h.spec_id == spec_id.to_string()));
+        assert!(hits.windows(2).all(|w| w[0].rank <= w[1].rank));
+
+        // Updating the body re-indexes the card.
+        idx.apply_event(&make_event(
+            4,
+            spec_id,
+            EventPayload::CardUpdated {
+                card_id,
+                title: None,
+                body: Some(Some("Conflict resolution with CRDTs".to_string())),
+                card_type: None,
+                refs: None,
+                tags: None,
+            },
+        ))
+        .unwrap();
+        let hits = idx.search(&spec_id, "crdts conflict", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].title, "Offline sync");
+
+        // Deleting the card removes it from search.
+        idx.apply_event(&make_event(
+            5,
+            spec_id,
+            EventPayload::CardDeleted { card_id },
+        ))
+        .unwrap();
+        assert!(idx.search(&spec_id, "crdts", 10).unwrap().is_empty());
+
+        // FTS5 syntax in user input is treated as plain text.
+        assert!(idx.search(&spec_id, "\"sync OR (", 10).is_ok());
+        assert!(idx.search(&spec_id, "   ", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn sqlite_sync_from_state_rebuilds_everything() {
+        let dir = TempDir::new().unwrap();
+        let idx = SqliteIndex::open(&dir.path().join("index.db")).unwrap();
+
+        let mut state = SpecState::new();
+        let core = SpecCore::new(
+            "Synced".to_string(),
+            "From state".to_string(),
+            "Catch up".to_string(),
+        );
+        let spec_id = core.spec_id;
+        state.core = Some(core);
+        let card = Card::new(
+            "task".to_string(),
+            "Write migration".to_string(),
+            "agent".to_string(),
+        );
+        state.cards.insert(card.card_id, card);
+        state.transcript.push(TranscriptMessage::new(
+            "agent".to_string(),
+            "The migration needs a backfill".to_string(),
+        ));
+        state.last_event_id = 9;
+
+        idx.sync_from_state(&state).unwrap();
+        assert_eq!(idx.list_specs().unwrap()[0].title, "Synced");
+        assert_eq!(idx.list_cards(&spec_id).unwrap().len(), 1);
+        assert_eq!(idx.search(&spec_id, "migration", 10).unwrap().len(), 2);
+        assert_eq!(idx.get_last_event_id().unwrap(), Some(9));
+
+        // Syncing again replaces rather than duplicates.
+        idx.sync_from_state(&state).unwrap();
+        assert_eq!(idx.search(&spec_id, "migration", 10).unwrap().len(), 2);
+    }
+
     #[test]
     fn sqlite_last_event_id_tracking() {
         let dir = TempDir::new().unwrap();
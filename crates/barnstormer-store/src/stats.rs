@@ -0,0 +1,286 @@
+// ABOUTME: Home-wide usage aggregation across every spec stored under a barnstormer home.
+// ABOUTME: Reads event logs and snapshots read-only, so it is safe to run while the server is down.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use barnstormer_core::state::SpecState;
+use serde::Serialize;
+
+use crate::jsonl::JsonlLog;
+use crate::manager::{ManagerError, StorageManager};
+use crate::snapshot::load_latest_snapshot;
+
+/// Aggregate usage figures for every spec under a barnstormer home.
+/// Contains counts only — no titles, card text, or transcript content.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HomeStats {
+    pub total_specs: usize,
+    pub total_cards: usize,
+    pub cards_by_type: BTreeMap<String, usize>,
+    /// Durable events each spec has recorded, including events that log
+    /// compaction has since dropped.
+    pub total_events: usize,
+    /// Agent steps started, also counted across compactions.
+    pub agent_steps: usize,
+    /// Input plus output tokens agents have recorded across every spec.
+    pub token_spend: u64,
+    /// Mean of `SpecCore::completeness` across specs that have a core.
+    pub average_completeness: f64,
+    /// Spec directories whose logs or snapshots could not be read.
+    pub skipped_specs: usize,
+}
+
+/// Walk every spec directory under `home` and aggregate usage counts.
+///
+/// Unlike `recover_spec`, this never repairs logs or rebuilds the SQLite
+/// index: state is rebuilt in memory from the latest snapshot plus the
+/// event tail. Specs that fail to load are counted in `skipped_specs`.
+pub fn collect_home_stats(home: &Path) -> Result<HomeStats, ManagerError> {
+    let manager = StorageManager::new(home.to_path_buf())?;
+    let mut stats = HomeStats::default();
+    let mut completeness_sum = 0.0;
+    let mut specs_with_core = 0usize;
+
+    for (spec_id, spec_dir) in manager.list_spec_dirs()? {
+        let state = match load_spec_read_only(&spec_dir) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                tracing::warn!("skipping spec {} in stats: {}", spec_id, e);
+                stats.skipped_specs += 1;
+                continue;
+            }
+        };
+
+        stats.total_specs += 1;
+        // Both come from the state rather than the log, which compaction
+        // truncates.
+        stats.total_events += state.last_event_id as usize;
+        stats.agent_steps += state.agent_steps as usize;
+
+        for card in state.cards.values() {
+            stats.total_cards += 1;
+            *stats
+                .cards_by_type
+                .entry(card.card_type.clone())
+                .or_default() += 1;
+        }
+
+        stats.token_spend += state
+            .token_usage
+            .values()
+            .map(|t| t.input_tokens + t.output_tokens)
+            .sum::<u64>();
+
+        if let Some(core) = &state.core {
+            completeness_sum += core.completeness();
+            specs_with_core += 1;
+        }
+    }
+
+    if specs_with_core > 0 {
+        stats.average_completeness = completeness_sum / specs_with_core as f64;
+    }
+
+    Ok(stats)
+}
+
+/// Rebuild a spec's state without touching anything on disk.
+fn load_spec_read_only(spec_dir: &Path) -> Result<SpecState, String> {
+    let snapshot = load_latest_snapshot(&spec_dir.join("snapshots")).map_err(|e| e.to_string())?;
+    let (mut state, snapshot_event_id) = match snapshot {
+        Some(snap) => (snap.state, snap.last_event_id),
+        None => (SpecState::new(), 0),
+    };
+
+    let events_path = spec_dir.join("events.jsonl");
+    let events = if events_path.exists() {
+        JsonlLog::replay(&events_path).map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+
+    for event in events.iter().filter(|e| e.event_id > snapshot_event_id) {
+        state.apply(event);
+    }
+
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barnstormer_core::card::Card;
+    use barnstormer_core::event::{Event, EventPayload};
+    use chrono::Utc;
+    use tempfile::TempDir;
+    use ulid::Ulid;
+
+    fn write_spec(manager: &StorageManager, payloads: Vec<EventPayload>) -> std::path::PathBuf {
+        let spec_id = Ulid::new();
+        let spec_dir = manager.create_spec_dir(&spec_id).unwrap();
+        let mut log = JsonlLog::open(&spec_dir.join("events.jsonl")).unwrap();
+        for (i, payload) in payloads.into_iter().enumerate() {
+            log.append(&Event {
+                event_id: i as u64 + 1,
+                spec_id,
+                timestamp: Utc::now(),
+                payload,
+            })
+            .unwrap();
+        }
+        spec_dir
+    }
+
+    fn created(title: &str) -> EventPayload {
+        EventPayload::SpecCreated {
+            title: title.to_string(),
+            one_liner: "One liner".to_string(),
+            goal: "Goal".to_string(),
+        }
+    }
+
+    fn card(card_type: &str) -> EventPayload {
+        EventPayload::CardCreated {
+            card: Card::new(
+                card_type.to_string(),
+                "Card".to_string(),
+                "human".to_string(),
+            ),
+        }
+    }
+
+    fn step(agent_id: &str) -> EventPayload {
+        EventPayload::AgentStepStarted {
+            agent_id: agent_id.to_string(),
+            description: "thinking".to_string(),
+        }
+    }
+
+    fn usage(role: &str, input_tokens: u64, output_tokens: u64) -> EventPayload {
+        EventPayload::UsageRecorded {
+            role: role.to_string(),
+            model: "claude-haiku-4-5".to_string(),
+            input_tokens,
+            output_tokens,
+        }
+    }
+
+    #[test]
+    fn empty_home_has_zero_counts() {
+        let dir = TempDir::new().unwrap();
+        let stats = collect_home_stats(dir.path()).unwrap();
+        assert_eq!(stats.total_specs, 0);
+        assert_eq!(stats.total_events, 0);
+        assert!(stats.cards_by_type.is_empty());
+        assert_eq!(stats.average_completeness, 0.0);
+        assert_eq!(stats.token_spend, 0);
+    }
+
+    #[test]
+    fn aggregates_counts_across_two_specs() {
+        let dir = TempDir::new().unwrap();
+        let manager = StorageManager::new(dir.path().to_path_buf()).unwrap();
+
+        // Spec A: 3/8 fields, two ideas and a task, two agent steps
+        // using 1,800 tokens.
+        write_spec(
+            &manager,
+            vec![
+                created("Alpha"),
+                card("idea"),
+                card("idea"),
+                card("task"),
+                step("manager"),
+                step("brainstormer"),
+                usage("manager", 1_000, 200),
+                usage("brainstormer", 500, 100),
+            ],
+        );
+
+        // Spec B: 5/8 fields, one idea and one deleted task, one agent step
+        // using 350 tokens.
+        let task = Card::new("task".to_string(), "Gone".to_string(), "human".to_string());
+        let task_id = task.card_id;
+        write_spec(
+            &manager,
+            vec![
+                created("Beta"),
+                EventPayload::SpecCoreUpdated {
+                    title: None,
+                    one_liner: None,
+                    goal: None,
+                    description: Some("Desc".to_string()),
+                    constraints: Some("None".to_string()),
+                    success_criteria: None,
+                    risks: None,
+                    notes: None,
+                },
+                card("idea"),
+                EventPayload::CardCreated { card: task },
+                EventPayload::CardDeleted { card_id: task_id },
+                step("planner"),
+                usage("planner", 300, 50),
+            ],
+        );
+
+        let stats = collect_home_stats(dir.path()).unwrap();
+        assert_eq!(stats.total_specs, 2);
+        assert_eq!(stats.total_events, 15);
+        assert_eq!(stats.agent_steps, 3);
+        assert_eq!(stats.token_spend, 2_150);
+        assert_eq!(stats.total_cards, 4);
+        assert_eq!(stats.cards_by_type.get("idea"), Some(&3));
+        assert_eq!(stats.cards_by_type.get("task"), Some(&1));
+        assert!((stats.average_completeness - 0.5).abs() < 1e-9);
+        assert_eq!(stats.skipped_specs, 0);
+    }
+
+    #[test]
+    fn event_and_step_counts_survive_compaction() {
+        let dir = TempDir::new().unwrap();
+        let manager = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let spec_dir = write_spec(
+            &manager,
+            vec![
+                created("Alpha"),
+                step("manager"),
+                step("planner"),
+                card("idea"),
+            ],
+        );
+        let before = collect_home_stats(dir.path()).unwrap();
+
+        let state = load_spec_read_only(&spec_dir).unwrap();
+        JsonlLog::open(&spec_dir.join("events.jsonl"))
+            .unwrap()
+            .compact(&state, crate::DEFAULT_SNAPSHOT_KEEP)
+            .unwrap();
+        assert_eq!(
+            JsonlLog::replay(&spec_dir.join("events.jsonl"))
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let after = collect_home_stats(dir.path()).unwrap();
+        assert_eq!(after.total_events, 4);
+        assert_eq!(after.agent_steps, 2);
+        assert_eq!(after.total_events, before.total_events);
+        assert_eq!(after.agent_steps, before.agent_steps);
+    }
+
+    #[test]
+    fn unreadable_log_is_skipped_not_fatal() {
+        let dir = TempDir::new().unwrap();
+        let manager = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        write_spec(&manager, vec![created("Ok")]);
+
+        let bad_dir = manager.create_spec_dir(&Ulid::new()).unwrap();
+        std::fs::write(bad_dir.join("events.jsonl"), "not json\n").unwrap();
+
+        let stats = collect_home_stats(dir.path()).unwrap();
+        assert_eq!(stats.total_specs, 1);
+        assert_eq!(stats.skipped_specs, 1);
+    }
+}
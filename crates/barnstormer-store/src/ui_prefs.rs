@@ -0,0 +1,196 @@
+// ABOUTME: Per-user, per-spec web UI preferences (e.g. collapsed document sections) in a JSON file.
+// ABOUTME: Users are keyed by a SHA-256 of their auth token so tokens never reach disk.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use ulid::Ulid;
+
+/// File name of the UI preferences store inside a barnstormer home.
+pub const UI_PREFS_FILE: &str = "ui_prefs.json";
+
+/// Key used for requests that carry no auth token.
+pub const ANONYMOUS_PREFS_KEY: &str = "anonymous";
+
+/// Errors that can occur while reading or writing UI preferences.
+#[derive(Debug, Error)]
+pub enum UiPrefsError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// UI preferences for one spec. The default is everything expanded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UiPrefs {
+    /// IDs of document sections the user has collapsed.
+    #[serde(default)]
+    pub collapsed_sections: Vec<String>,
+}
+
+/// Stored layout: user key -> spec ID -> preferences.
+type PrefsFile = BTreeMap<String, BTreeMap<Ulid, UiPrefs>>;
+
+/// UI preferences backed by a single JSON file. Each call reads the file
+/// afresh; callers that mutate concurrently must serialize access.
+#[derive(Debug, Clone)]
+pub struct UiPrefsStore {
+    path: PathBuf,
+}
+
+impl UiPrefsStore {
+    /// Create a store backed by the given file. A missing file means no
+    /// preferences have been saved yet.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Create a store at the standard location inside a barnstormer home.
+    pub fn for_home(home: &Path) -> Self {
+        Self::new(home.join(UI_PREFS_FILE))
+    }
+
+    /// Preferences saved for `spec_id` by the holder of `token`, or the
+    /// defaults when none were saved.
+    pub fn get(&self, token: Option<&str>, spec_id: Ulid) -> Result<UiPrefs, UiPrefsError> {
+        let prefs = self.load()?;
+        Ok(prefs
+            .get(&user_key(token))
+            .and_then(|specs| specs.get(&spec_id))
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Replace the preferences for `spec_id` saved by the holder of `token`.
+    pub fn set(
+        &self,
+        token: Option<&str>,
+        spec_id: Ulid,
+        ui_prefs: UiPrefs,
+    ) -> Result<(), UiPrefsError> {
+        let mut prefs = self.load()?;
+        let specs = prefs.entry(user_key(token)).or_default();
+        if ui_prefs == UiPrefs::default() {
+            specs.remove(&spec_id);
+        } else {
+            specs.insert(spec_id, ui_prefs);
+        }
+        prefs.retain(|_, specs| !specs.is_empty());
+        self.save(&prefs)
+    }
+
+    fn load(&self) -> Result<PrefsFile, UiPrefsError> {
+        if !self.path.exists() {
+            return Ok(PrefsFile::new());
+        }
+        let raw = fs::read_to_string(&self.path)?;
+        Ok(serde_json::from_str(&raw)?)
+    }
+
+    /// Atomic write: write to a sibling .tmp file, fsync, rename.
+    fn save(&self, prefs: &PrefsFile) -> Result<(), UiPrefsError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(prefs)?;
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Storage key for a user: a hex SHA-256 of their token, or
+/// [`ANONYMOUS_PREFS_KEY`] without one.
+fn user_key(token: Option<&str>) -> String {
+    match token {
+        Some(token) if !token.is_empty() => {
+            let digest = Sha256::digest(token.as_bytes());
+            let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+            format!("sha256:{}", hex)
+        }
+        _ => ANONYMOUS_PREFS_KEY.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn collapsed(ids: &[&str]) -> UiPrefs {
+        UiPrefs {
+            collapsed_sections: ids.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn missing_file_gives_defaults() {
+        let dir = TempDir::new().unwrap();
+        let store = UiPrefsStore::for_home(dir.path());
+        assert_eq!(store.get(None, Ulid::new()).unwrap(), UiPrefs::default());
+    }
+
+    #[test]
+    fn prefs_are_kept_per_token_and_spec() {
+        let dir = TempDir::new().unwrap();
+        let store = UiPrefsStore::for_home(dir.path());
+        let spec_a = Ulid::new();
+        let spec_b = Ulid::new();
+
+        store
+            .set(Some("alice"), spec_a, collapsed(&["risks"]))
+            .unwrap();
+        store.set(None, spec_a, collapsed(&["notes"])).unwrap();
+
+        assert_eq!(
+            store.get(Some("alice"), spec_a).unwrap(),
+            collapsed(&["risks"])
+        );
+        assert_eq!(store.get(None, spec_a).unwrap(), collapsed(&["notes"]));
+        assert_eq!(store.get(Some("bob"), spec_a).unwrap(), UiPrefs::default());
+        assert_eq!(
+            store.get(Some("alice"), spec_b).unwrap(),
+            UiPrefs::default()
+        );
+    }
+
+    #[test]
+    fn tokens_are_not_written_to_disk() {
+        let dir = TempDir::new().unwrap();
+        let store = UiPrefsStore::for_home(dir.path());
+        store
+            .set(
+                Some("super-secret-token"),
+                Ulid::new(),
+                collapsed(&["goal"]),
+            )
+            .unwrap();
+        let raw = fs::read_to_string(dir.path().join(UI_PREFS_FILE)).unwrap();
+        assert!(!raw.contains("super-secret-token"));
+        assert!(raw.contains("sha256:"));
+    }
+
+    #[test]
+    fn resetting_to_defaults_drops_the_entry() {
+        let dir = TempDir::new().unwrap();
+        let store = UiPrefsStore::for_home(dir.path());
+        let spec_id = Ulid::new();
+        store.set(None, spec_id, collapsed(&["goal"])).unwrap();
+        store.set(None, spec_id, UiPrefs::default()).unwrap();
+        let raw = fs::read_to_string(dir.path().join(UI_PREFS_FILE)).unwrap();
+        assert_eq!(raw.trim(), "{}");
+    }
+}
@@ -9,7 +9,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 use barnstormer_runtime::{RuntimeOptions, ServerHandle, launch};
-use barnstormer_server::ProviderStatus;
+use barnstormer_server::{BarnstormerConfig, ProviderStatus};
 use tauri::path::BaseDirectory;
 use tauri::{Manager, Runtime};
 
@@ -26,17 +26,23 @@ pub(crate) struct DesktopAppState {
     runtime: DesktopRuntimeState,
 }
 
-pub fn desktop_launch_options(app_home: PathBuf, static_dir: PathBuf) -> RuntimeOptions {
-    RuntimeOptions {
+/// Launch options for the embedded server: the settings `barnstormer.toml`
+/// in `app_home` and the environment resolve, on an ephemeral loopback port.
+pub fn desktop_launch_options(
+    app_home: PathBuf,
+    static_dir: PathBuf,
+) -> anyhow::Result<RuntimeOptions> {
+    let config = BarnstormerConfig::load(&app_home)?;
+    Ok(RuntimeOptions {
         home: Some(app_home),
         bind: Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0)),
+        // The Tauri webview cannot send a bearer header, so a configured
+        // token would silently 401 every API call from the embedded UI.
         auth_token: None,
         static_dir: Some(static_dir),
         open_browser: false,
-        // The Tauri webview cannot send a bearer header, so a stale shell
-        // env var would silently 401 every API call from the embedded UI.
-        disable_auth_fallback: true,
-    }
+        ..RuntimeOptions::from_config(&config)
+    })
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -126,10 +132,8 @@ pub(crate) fn start_server_locked(
         return Ok(existing.local_url().to_string());
     }
 
-    let launched = tauri::async_runtime::block_on(launch(desktop_launch_options(
-        app_home.to_path_buf(),
-        static_dir.to_path_buf(),
-    )))?;
+    let options = desktop_launch_options(app_home.to_path_buf(), static_dir.to_path_buf())?;
+    let launched = tauri::async_runtime::block_on(launch(options))?;
     let local_url = launched.local_url().to_string();
     *server = Some(launched);
     Ok(local_url)
@@ -216,9 +220,11 @@ mod tests {
         let launch = desktop_launch_options(
             PathBuf::from("/tmp/barnstormer-ui"),
             PathBuf::from("/tmp/barnstormer-static"),
-        );
+        )
+        .unwrap();
 
         assert!(!launch.open_browser);
+        assert!(launch.auth_token.is_none());
         assert_eq!(launch.bind.unwrap().ip().to_string(), "127.0.0.1");
         assert_eq!(
             launch.static_dir.unwrap(),
@@ -4,10 +4,16 @@
 use std::path::PathBuf;
 
 use barnstormer_agent::client::create_llm_client;
-use barnstormer_agent::import::{parse_with_llm, to_commands};
-use barnstormer_runtime::{RuntimeOptions, launch};
-use barnstormer_server::ProviderStatus;
-use barnstormer_store::{JsonlLog, StorageManager};
+use barnstormer_agent::import::{
+    ImportResult, parse_native_json, parse_with_llm, to_card_commands, to_commands,
+};
+use barnstormer_core::Command;
+use barnstormer_core::runner_outcome::{PhaseStatus, outcome_commands, parse_runner_outcome};
+use barnstormer_runtime::{RuntimeConfig, RuntimeOptions, launch};
+use barnstormer_server::{BarnstormerConfig, ProviderStatus};
+use barnstormer_store::{
+    JsonlLog, SpecVerification, StorageManager, collect_home_stats, recover_spec, verify_spec,
+};
 use clap::Parser;
 
 #[derive(Parser)]
@@ -18,6 +24,10 @@ enum Cli {
         /// Do not open the browser on startup
         #[arg(long, default_value = "false")]
         no_open: bool,
+
+        /// Print the effective configuration (secrets redacted) and exit
+        #[arg(long)]
+        print_config: bool,
     },
     /// Check if barnstormer is running
     Status,
@@ -31,9 +41,40 @@ enum Cli {
         #[arg(long)]
         text: Option<String>,
 
-        /// Format hint for the LLM (e.g. "dot", "yaml", "markdown")
+        /// Format hint for the LLM (e.g. "dot", "yaml", "markdown"). "json"
+        /// tries a native JSON import first, with no LLM call
         #[arg(long, short)]
         format: Option<String>,
+
+        /// Merge the extracted cards into this existing spec (by spec_id)
+        /// instead of creating a new one. Refused while the server is running.
+        #[arg(long, value_name = "SPEC_ID")]
+        into: Option<String>,
+
+        /// Treat the input as a DOT runner result (`phase: outcome` lines)
+        /// and record it in the --into spec. No LLM is used.
+        #[arg(long, requires = "into")]
+        runner_outcome: bool,
+    },
+    /// Print aggregate usage across every spec in the barnstormer home
+    Stats {
+        /// Emit machine-readable JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Snapshot every spec and truncate its event log so startup replays
+    /// less. Refused while the server is running.
+    Compact {
+        /// Compact only this spec instead of every spec in the home
+        #[arg(long, value_name = "SPEC_ID")]
+        spec: Option<String>,
+    },
+    /// Check that specs on disk recover cleanly, without changing them.
+    /// Exits non-zero if any spec fails
+    Verify {
+        /// Verify only this spec instead of every spec in the home
+        #[arg(value_name = "SPEC_ID")]
+        spec_id: Option<String>,
     },
 }
 
@@ -52,17 +93,30 @@ async fn main() {
     let cli = Cli::parse();
 
     match cli {
-        Cli::Start { no_open } => {
-            let server = launch(RuntimeOptions {
-                home: None,
-                bind: None,
-                auth_token: None,
-                static_dir: None,
+        Cli::Start {
+            no_open,
+            print_config,
+        } => {
+            let config = load_config();
+            let options = RuntimeOptions {
                 open_browser: !no_open,
-                disable_auth_fallback: false,
-            })
-            .await
-            .expect("failed to launch barnstormer runtime");
+                ..RuntimeOptions::from_config(&config)
+            };
+
+            if print_config {
+                let config =
+                    RuntimeConfig::from_parts(options).expect("failed to resolve configuration");
+                let providers = ProviderStatus::detect_with_defaults(
+                    &config.default_provider,
+                    config.default_model.as_deref(),
+                );
+                println!("{}", config.describe(&providers));
+                return;
+            }
+
+            let server = launch(options)
+                .await
+                .expect("failed to launch barnstormer runtime");
 
             // Open browser unless --no-open was specified
             if !no_open {
@@ -80,21 +134,51 @@ async fn main() {
                 }
             }
 
-            server.wait().await.expect("server error");
+            server
+                .wait_for_shutdown_signal()
+                .await
+                .expect("server error");
         }
         Cli::Status => {
-            let bind_addr =
-                std::env::var("BARNSTORMER_BIND").unwrap_or_else(|_| "127.0.0.1:7331".to_string());
+            let bind_addr = load_config().bind;
 
             println!("barnstormer status: checking {}...", bind_addr);
 
-            match std::net::TcpStream::connect(&bind_addr) {
+            match std::net::TcpStream::connect(bind_addr) {
                 Ok(_) => println!("barnstormer is running on {}", bind_addr),
                 Err(_) => println!("barnstormer is not running on {}", bind_addr),
             }
         }
-        Cli::Import { file, text, format } => {
-            if let Err(e) = run_import(file, text, format).await {
+        Cli::Import {
+            file,
+            text,
+            format,
+            into,
+            runner_outcome,
+        } => {
+            if let Err(e) = run_import(file, text, format, into, runner_outcome).await {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Cli::Stats { json } => {
+            if let Err(e) = run_stats(json) {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Cli::Compact { spec } => {
+            let config = load_config();
+            let keep = RuntimeConfig::from_parts(RuntimeOptions::from_config(&config))
+                .map(|runtime| runtime.snapshot_keep);
+            if let Err(e) = keep.and_then(|keep| run_compact(&config.home, config.bind, spec, keep))
+            {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Cli::Verify { spec_id } => {
+            if let Err(e) = run_verify(spec_id) {
                 eprintln!("error: {}", e);
                 std::process::exit(1);
             }
@@ -103,11 +187,28 @@ async fn main() {
 }
 
 /// Execute the import subcommand: read input, call LLM, persist spec.
+/// With `into`, the extracted cards are merged into that existing spec.
+/// With `runner_outcome`, the input is a DOT runner result recorded into
+/// the `into` spec without an LLM call.
 async fn run_import(
     file: Option<String>,
     text: Option<String>,
     format: Option<String>,
+    into: Option<String>,
+    runner_outcome: bool,
 ) -> Result<(), anyhow::Error> {
+    let config = load_config();
+    let storage = StorageManager::new(config.home.clone())?;
+
+    // Resolve the target spec before spending an LLM call on the input.
+    let target = match into.as_deref() {
+        Some(id) => {
+            ensure_server_stopped(config.bind)?;
+            Some(existing_spec_id(&storage, id)?)
+        }
+        None => None,
+    };
+
     // Read input content
     let content = match (file.as_deref(), text) {
         (_, Some(inline)) => inline,
@@ -129,6 +230,19 @@ async fn run_import(
         return Err(anyhow::anyhow!("input content is empty"));
     }
 
+    if runner_outcome {
+        let spec_id = target.ok_or_else(|| anyhow::anyhow!("--runner-outcome needs --into"))?;
+        let outcomes = parse_runner_outcome(&content).map_err(|e| anyhow::anyhow!(e))?;
+        let failed = outcomes
+            .iter()
+            .filter(|o| o.status == PhaseStatus::Failed)
+            .count();
+        append_to_spec(&storage, spec_id, outcome_commands(&outcomes)).await?;
+        println!("Recorded runner outcome in spec: {}", spec_id);
+        println!("  phases: {} ({} failed)", outcomes.len(), failed);
+        return Ok(());
+    }
+
     // Detect source format from file extension if not explicitly provided
     let source_hint = format.as_deref().or_else(|| {
         file.as_deref().and_then(|f| {
@@ -138,32 +252,60 @@ async fn run_import(
         })
     });
 
-    // Resolve LLM provider
-    let provider_status = ProviderStatus::detect();
-    let (client, model) = create_llm_client(
-        &provider_status.default_provider,
-        provider_status.default_model.as_deref(),
-    )?;
+    // JSON input may be a native export, which imports deterministically
+    // without an LLM call; anything else falls through to the LLM.
+    let native = if source_hint.is_some_and(|hint| hint.eq_ignore_ascii_case("json")) {
+        match parse_native_json(&content) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                println!(
+                    "Not a native spec JSON ({}); falling back to LLM import.",
+                    e
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let import_result = match native {
+        Some(result) => {
+            println!("Importing native JSON (no LLM call)...");
+            result
+        }
+        None => {
+            // Resolve LLM provider
+            let provider_status = ProviderStatus::detect_with_defaults(
+                &config.default_provider,
+                config.default_model.as_deref(),
+            );
+            let (client, model) = create_llm_client(
+                &provider_status.default_provider,
+                provider_status.default_model.as_deref(),
+            )?;
 
-    println!(
-        "Importing via {} ({})...",
-        provider_status.default_provider, model
-    );
+            println!(
+                "Importing via {} ({})...",
+                provider_status.default_provider, model
+            );
 
-    // Parse content via LLM
-    let import_result = parse_with_llm(&content, source_hint, &client, &model).await?;
+            // Parse content via LLM
+            parse_with_llm(&content, source_hint, &client, &model).await?
+        }
+    };
+
+    if let Some(spec_id) = target {
+        let added = import_into_spec(&storage, spec_id, &import_result).await?;
+        println!("Imported into spec: {}", spec_id);
+        println!("  cards added: {}", added);
+        return Ok(());
+    }
 
     let title = import_result.spec.title.clone();
     let card_count = import_result.cards.len();
     let commands = to_commands(&import_result);
 
-    // Set up storage
-    let barnstormer_home = std::env::var("BARNSTORMER_HOME")
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| dirs_or_default().join(".barnstormer"));
-
-    let storage = StorageManager::new(barnstormer_home.clone())?;
-
     let spec_id = ulid::Ulid::new();
     let spec_dir = storage.create_spec_dir(&spec_id)?;
 
@@ -187,9 +329,477 @@ async fn run_import(
     Ok(())
 }
 
+/// Parse a spec ID and confirm the spec exists in storage.
+fn existing_spec_id(storage: &StorageManager, id: &str) -> Result<ulid::Ulid, anyhow::Error> {
+    let spec_id = id
+        .parse::<ulid::Ulid>()
+        .map_err(|_| anyhow::anyhow!("invalid spec id: {}", id))?;
+    if !storage.get_spec_dir(&spec_id).join("events.jsonl").exists() {
+        return Err(anyhow::anyhow!("unknown spec: {}", spec_id));
+    }
+    Ok(spec_id)
+}
+
+/// Fail when a barnstormer server answers on `bind`. A running server may
+/// have the spec loaded: its actor would never see events appended to the
+/// log behind its back, and its persister would keep writing to a log that
+/// compaction has replaced.
+fn ensure_server_stopped(bind: std::net::SocketAddr) -> Result<(), anyhow::Error> {
+    let mut addr = bind;
+    if addr.ip().is_unspecified() {
+        addr.set_ip(std::net::Ipv4Addr::LOCALHOST.into());
+    }
+    if std::net::TcpStream::connect_timeout(&addr, std::time::Duration::from_millis(500)).is_ok() {
+        return Err(anyhow::anyhow!(
+            "barnstormer is running on {}; stop it before changing spec logs on disk",
+            addr
+        ));
+    }
+    Ok(())
+}
+
+/// Merge an import's cards into an existing spec. The spec's core fields
+/// are left as they are. Returns the number of cards added.
+async fn import_into_spec(
+    storage: &StorageManager,
+    spec_id: ulid::Ulid,
+    import_result: &ImportResult,
+) -> Result<usize, anyhow::Error> {
+    append_to_spec(storage, spec_id, to_card_commands(import_result)).await
+}
+
+/// Recover an existing spec's state, replay `commands` through a fresh
+/// actor, and append the resulting events to its log. Returns the number
+/// of commands applied.
+async fn append_to_spec(
+    storage: &StorageManager,
+    spec_id: ulid::Ulid,
+    commands: Vec<Command>,
+) -> Result<usize, anyhow::Error> {
+    let spec_dir = storage.get_spec_dir(&spec_id);
+    let log_path = spec_dir.join("events.jsonl");
+    if !log_path.exists() {
+        return Err(anyhow::anyhow!("unknown spec: {}", spec_id));
+    }
+
+    let (state, _) = recover_spec(&spec_dir)?;
+    let mut log = JsonlLog::open(&log_path)?;
+    let handle = barnstormer_core::spawn(spec_id, state);
+
+    let mut added = 0;
+    for cmd in commands {
+        let events = handle.send_command(cmd).await?;
+        for event in &events {
+            log.append(event)?;
+        }
+        added += 1;
+    }
+
+    Ok(added)
+}
+
+/// Execute the stats subcommand: aggregate counts from disk, no server needed.
+fn run_stats(json: bool) -> Result<(), anyhow::Error> {
+    let stats = collect_home_stats(&barnstormer_home())?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    println!("{:<24} {}", "specs", stats.total_specs);
+    println!("{:<24} {}", "cards", stats.total_cards);
+    for (card_type, count) in &stats.cards_by_type {
+        println!("{:<24} {}", format!("  {}", card_type), count);
+    }
+    println!("{:<24} {}", "events", stats.total_events);
+    println!("{:<24} {}", "agent steps", stats.agent_steps);
+    println!("{:<24} {}", "token spend", stats.token_spend);
+    println!(
+        "{:<24} {:.0}%",
+        "avg completeness",
+        stats.average_completeness * 100.0
+    );
+    if stats.skipped_specs > 0 {
+        println!("{:<24} {}", "skipped (unreadable)", stats.skipped_specs);
+    }
+
+    Ok(())
+}
+
+/// Execute the compact subcommand: compact one or every spec's event log
+/// against its recovered state, reporting the bytes reclaimed. A spec that
+/// fails is reported and the rest go on. Refused while a server answers on
+/// `bind`: its persisters would keep writing to the replaced logs. Each
+/// spec keeps `keep` snapshots.
+fn run_compact(
+    home: &std::path::Path,
+    bind: std::net::SocketAddr,
+    spec_id: Option<String>,
+    keep: usize,
+) -> Result<(), anyhow::Error> {
+    ensure_server_stopped(bind)?;
+    let storage = StorageManager::new(home.to_path_buf())?;
+    let spec_dirs = match spec_id {
+        Some(id) => {
+            let spec_id = existing_spec_id(&storage, &id)?;
+            vec![(spec_id, storage.get_spec_dir(&spec_id))]
+        }
+        None => storage.list_spec_dirs()?,
+    };
+
+    let mut failed = 0;
+    let mut reclaimed = 0;
+    for (spec_id, spec_dir) in spec_dirs {
+        match compact_spec(&spec_dir, keep) {
+            Ok(compacted) => {
+                reclaimed += compacted.bytes_reclaimed();
+                println!(
+                    "{}  {} -> {} events, {} bytes reclaimed",
+                    spec_id,
+                    compacted.events_before,
+                    compacted.events_after,
+                    compacted.bytes_reclaimed()
+                );
+            }
+            Err(e) => {
+                eprintln!("{}  failed: {}", spec_id, e);
+                failed += 1;
+            }
+        }
+    }
+    println!("{} bytes reclaimed", reclaimed);
+    if failed > 0 {
+        anyhow::bail!("{} specs could not be compacted", failed);
+    }
+    Ok(())
+}
+
+/// What compacting one spec's log changed.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Compacted {
+    events_before: usize,
+    events_after: usize,
+    bytes_before: u64,
+    bytes_after: u64,
+}
+
+impl Compacted {
+    fn bytes_reclaimed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Recover one spec and compact its log. The spec is snapshotted before
+/// the log is rewritten, so an interrupted run still recovers.
+fn compact_spec(spec_dir: &std::path::Path, keep: usize) -> Result<Compacted, anyhow::Error> {
+    let events_path = spec_dir.join("events.jsonl");
+    if !events_path.exists() {
+        return Ok(Compacted::default());
+    }
+    // Recovery repairs a torn tail first, so the counts are of valid events.
+    let (state, _) = recover_spec(spec_dir)?;
+    let events_before = JsonlLog::replay(&events_path)?.len();
+    let bytes_before = std::fs::metadata(&events_path)?.len();
+    JsonlLog::open(&events_path)?.compact(&state, keep)?;
+    Ok(Compacted {
+        events_before,
+        events_after: JsonlLog::replay(&events_path)?.len(),
+        bytes_before,
+        bytes_after: std::fs::metadata(&events_path)?.len(),
+    })
+}
+
+/// Execute the verify subcommand: replay one or every spec the way startup
+/// recovery would, print what each recovers to, and fail if any spec does
+/// not recover. Nothing on disk is created or changed.
+fn run_verify(spec_id: Option<String>) -> Result<(), anyhow::Error> {
+    let home = barnstormer_home();
+    // Checked up front so StorageManager::new never creates the directory.
+    if !home.join("specs").is_dir() {
+        anyhow::bail!("no specs directory under {}", home.display());
+    }
+    let storage = StorageManager::new(home)?;
+    let spec_dirs = match spec_id {
+        Some(id) => {
+            let spec_id = existing_spec_id(&storage, &id)?;
+            vec![(spec_id, storage.get_spec_dir(&spec_id))]
+        }
+        None => storage.list_spec_dirs()?,
+    };
+
+    let mut failed = 0;
+    for (spec_id, spec_dir) in &spec_dirs {
+        let result = verify_spec(spec_dir);
+        if result.is_err() {
+            failed += 1;
+        }
+        println!("{}", verify_line(*spec_id, &result));
+    }
+    println!("{} specs verified, {} failed", spec_dirs.len(), failed);
+    if failed > 0 {
+        anyhow::bail!("{} specs failed to recover", failed);
+    }
+    Ok(())
+}
+
+/// One line of `barnstormer verify` output for a spec.
+fn verify_line(
+    spec_id: ulid::Ulid,
+    result: &Result<SpecVerification, barnstormer_store::RecoveryError>,
+) -> String {
+    match result {
+        Ok(verified) => {
+            let mut line = format!(
+                "{}  ok      last_event_id={}  cards={}",
+                spec_id,
+                verified.last_event_id,
+                verified.state.cards.len()
+            );
+            if verified.dropped_lines > 0 {
+                line.push_str(&format!(
+                    "  ({} unreadable log lines will be dropped on recovery)",
+                    verified.dropped_lines
+                ));
+            }
+            line
+        }
+        Err(e) => format!("{}  FAILED  {}", spec_id, e),
+    }
+}
+
+/// Load `barnstormer.toml` from the barnstormer home merged with env vars.
+/// Exits on an invalid config.
+fn load_config() -> BarnstormerConfig {
+    match BarnstormerConfig::load(&barnstormer_home()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Resolve the barnstormer home from BARNSTORMER_HOME, defaulting to ~/.barnstormer.
+fn barnstormer_home() -> PathBuf {
+    std::env::var("BARNSTORMER_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs_or_default().join(".barnstormer"))
+}
+
 /// Get the user's home directory, falling back to /tmp if unavailable.
 fn dirs_or_default() -> PathBuf {
     std::env::var("HOME")
         .map(PathBuf::from)
         .unwrap_or_else(|_| PathBuf::from("/tmp"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barnstormer_agent::import::{ImportCard, ImportSpec};
+    use barnstormer_core::SpecState;
+    use barnstormer_store::DEFAULT_SNAPSHOT_KEEP;
+
+    /// Write a spec with the given card titles to storage, the way
+    /// `run_import` does for a fresh spec.
+    async fn seed_spec(storage: &StorageManager, card_titles: &[&str]) -> ulid::Ulid {
+        let spec_id = ulid::Ulid::new();
+        let spec_dir = storage.create_spec_dir(&spec_id).unwrap();
+        let mut log = JsonlLog::open(&spec_dir.join("events.jsonl")).unwrap();
+        let handle = barnstormer_core::spawn(spec_id, SpecState::new());
+        let mut commands = vec![Command::CreateSpec {
+            title: "Existing".to_string(),
+            one_liner: "Already here".to_string(),
+            goal: "Keep my cards".to_string(),
+        }];
+        commands.extend(card_titles.iter().map(|t| Command::CreateCard {
+            card_type: "idea".to_string(),
+            title: t.to_string(),
+            body: None,
+            lane: None,
+            created_by: "human".to_string(),
+            source_attachment_id: None,
+        }));
+        for cmd in commands {
+            for event in handle.send_command(cmd).await.unwrap() {
+                log.append(&event).unwrap();
+            }
+        }
+        spec_id
+    }
+
+    fn extracted(card_titles: &[&str]) -> ImportResult {
+        ImportResult {
+            spec: ImportSpec {
+                title: "Imported title".to_string(),
+                one_liner: "Should not replace the existing one".to_string(),
+                goal: "Ignored".to_string(),
+            },
+            update: None,
+            cards: card_titles
+                .iter()
+                .map(|t| ImportCard {
+                    card_type: "task".to_string(),
+                    title: t.to_string(),
+                    body: None,
+                    lane: Some("Plan".to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn import_into_is_refused_while_a_server_is_listening() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let err = ensure_server_stopped(addr).unwrap_err().to_string();
+        assert!(err.contains("stop it"), "{err}");
+
+        drop(listener);
+        assert!(ensure_server_stopped(addr).is_ok());
+    }
+
+    #[tokio::test]
+    async fn import_into_existing_spec_adds_cards_and_keeps_prior_ones() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let spec_id = seed_spec(&storage, &["Old one", "Old two"]).await;
+        let (before, _) = recover_spec(&storage.get_spec_dir(&spec_id)).unwrap();
+
+        let added = import_into_spec(&storage, spec_id, &extracted(&["New A", "New B", "New C"]))
+            .await
+            .unwrap();
+        assert_eq!(added, 3);
+
+        let (after, _) = recover_spec(&storage.get_spec_dir(&spec_id)).unwrap();
+        assert_eq!(after.cards.len(), before.cards.len() + 3);
+        for (card_id, card) in &before.cards {
+            assert_eq!(after.cards[card_id].title, card.title);
+        }
+        assert_eq!(after.core.as_ref().unwrap().title, "Existing");
+        assert!(after.last_event_id > before.last_event_id);
+    }
+
+    #[tokio::test]
+    async fn runner_outcome_with_failed_verify_adds_a_risk_card() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let spec_id = seed_spec(&storage, &[]).await;
+
+        let outcomes =
+            parse_runner_outcome("plan: passed\nverify: failed - flaky login test\n").unwrap();
+        append_to_spec(&storage, spec_id, outcome_commands(&outcomes))
+            .await
+            .unwrap();
+
+        let (state, _) = recover_spec(&storage.get_spec_dir(&spec_id)).unwrap();
+        let risk = state
+            .cards
+            .values()
+            .find(|c| c.card_type == "risk")
+            .expect("failed verify should file a risk card");
+        assert!(risk.title.contains("verify"));
+        assert_eq!(risk.body.as_deref(), Some("flaky login test"));
+        assert!(
+            state
+                .transcript
+                .iter()
+                .any(|m| m.content.contains("1 failed"))
+        );
+    }
+
+    #[tokio::test]
+    async fn compact_spec_keeps_state_and_shrinks_log() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let spec_id = seed_spec(&storage, &["One", "Two", "Three"]).await;
+        let spec_dir = storage.get_spec_dir(&spec_id);
+        let (before, _) = recover_spec(&spec_dir).unwrap();
+
+        let compacted = compact_spec(&spec_dir, DEFAULT_SNAPSHOT_KEEP).unwrap();
+        assert!(compacted.events_after < compacted.events_before);
+        assert_eq!(compacted.events_after, 1);
+        assert!(compacted.bytes_reclaimed() > 0);
+
+        let (after, last_event_id) = recover_spec(&spec_dir).unwrap();
+        assert_eq!(last_event_id, before.last_event_id);
+        assert_eq!(after.cards, before.cards);
+        assert_eq!(after.core, before.core);
+    }
+
+    #[tokio::test]
+    async fn compact_is_refused_while_a_server_is_listening() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let spec_id = seed_spec(&storage, &["One", "Two"]).await;
+        let log_path = storage.get_spec_dir(&spec_id).join("events.jsonl");
+        let before = std::fs::read(&log_path).unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let err = run_compact(dir.path(), addr, None, DEFAULT_SNAPSHOT_KEEP).unwrap_err();
+        assert!(err.to_string().contains("stop it"), "{err}");
+        assert_eq!(std::fs::read(&log_path).unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn compact_of_one_spec_is_refused_while_a_server_is_listening() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let spec_id = seed_spec(&storage, &["One", "Two"]).await;
+        let log_path = storage.get_spec_dir(&spec_id).join("events.jsonl");
+        let before = std::fs::read(&log_path).unwrap();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let err = run_compact(
+            dir.path(),
+            addr,
+            Some(spec_id.to_string()),
+            DEFAULT_SNAPSHOT_KEEP,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("stop it"), "{err}");
+        assert_eq!(std::fs::read(&log_path).unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn verify_reports_recovered_state_and_failures() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let spec_id = seed_spec(&storage, &["One", "Two"]).await;
+        let spec_dir = storage.get_spec_dir(&spec_id);
+
+        let (_, last_event_id) = recover_spec(&spec_dir).unwrap();
+
+        let line = verify_line(spec_id, &verify_spec(&spec_dir));
+        assert!(line.contains("ok"), "{}", line);
+        assert!(
+            line.contains(&format!("last_event_id={}", last_event_id)),
+            "{}",
+            line
+        );
+        assert!(line.contains("cards=2"), "{}", line);
+
+        std::fs::write(spec_dir.join("snapshots").join("state_9.json"), "{").unwrap();
+        let line = verify_line(spec_id, &verify_spec(&spec_dir));
+        assert!(line.contains("FAILED"), "{}", line);
+        assert!(line.contains("snapshot error"), "{}", line);
+    }
+
+    #[tokio::test]
+    async fn import_into_unknown_spec_is_refused() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let storage = StorageManager::new(dir.path().to_path_buf()).unwrap();
+        let missing = ulid::Ulid::new();
+
+        assert!(existing_spec_id(&storage, &missing.to_string()).is_err());
+        assert!(existing_spec_id(&storage, "not-a-ulid").is_err());
+        assert!(
+            import_into_spec(&storage, missing, &extracted(&["Lost"]))
+                .await
+                .is_err()
+        );
+    }
+}
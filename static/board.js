@@ -9,27 +9,6 @@ function initBoard() {
 
     var specId = boardEl.dataset.specId;
 
-    // Calculate a midpoint order between two neighbors, defaulting to
-    // reasonable bounds when at the edges of a lane.
-    function calculateOrder(evt) {
-        var items = evt.to.querySelectorAll('.card');
-        var newIndex = evt.newIndex;
-        var prevOrder = 0;
-        var nextOrder = 0;
-
-        if (newIndex > 0) {
-            prevOrder = parseFloat(items[newIndex - 1].dataset.order) || 0;
-        }
-
-        if (newIndex < items.length - 1) {
-            nextOrder = parseFloat(items[newIndex + 1].dataset.order) || (prevOrder + 2);
-        } else {
-            nextOrder = prevOrder + 2;
-        }
-
-        return (prevOrder + nextOrder) / 2;
-    }
-
     document.querySelectorAll('.lane-cards').forEach(function (lane) {
         // Destroy any existing Sortable instance to prevent duplicates
         // after HTMX swaps where old instances may linger on elements.
@@ -45,25 +24,20 @@ function initBoard() {
             chosenClass: 'sortable-chosen',
             onEnd: function (evt) {
                 var cardId = evt.item.dataset.cardId;
-                var newLane = evt.to.dataset.lane;
-                var newOrder = calculateOrder(evt);
-
-                // Update the data attributes on the moved card
-                evt.item.dataset.lane = newLane;
-                evt.item.dataset.order = newOrder;
-
-                fetch('/api/specs/' + specId + '/commands', {
-                    method: 'POST',
-                    headers: { 'Content-Type': 'application/json' },
-                    body: JSON.stringify({
-                        type: 'MoveCard',
-                        card_id: cardId,
-                        lane: newLane,
-                        order: newOrder,
-                        updated_by: 'human'
-                    })
-                }).catch(function (err) {
-                    console.error('Failed to move card:', err);
+                var prev = evt.item.previousElementSibling;
+
+                // The server picks the card's sort order from its new
+                // position and sends back the re-rendered board. A filtered
+                // board hides cards, so name the card it landed after rather
+                // than trusting the rendered index.
+                htmx.ajax('PUT', '/web/specs/' + specId + '/cards/' + cardId + '/move', {
+                    target: '#board',
+                    swap: 'outerHTML',
+                    values: {
+                        lane: evt.to.dataset.lane,
+                        index: evt.newIndex,
+                        after: prev ? prev.dataset.cardId : ''
+                    }
                 });
             }
         });
@@ -75,7 +49,7 @@ initBoard();
 
 // Re-initialize after HTMX swaps that contain board content
 document.addEventListener('htmx:afterSwap', function (event) {
-    if (event.detail.target.id === 'canvas') {
+    if (event.detail.target.id === 'canvas' || event.detail.target.id === 'board') {
         initBoard();
     }
 });